@@ -4,10 +4,11 @@
 }
 
 const BACKGROUND_COLOR = vec4<f32>(0., 0., 0., 0.75);
-const FOREGROUND_COLOR = vec4<f32>(0.6, 1., 0.6, 0.75);
 
 @group(2) @binding(0)
 var<uniform> value: f32;
+@group(2) @binding(1)
+var<uniform> color: vec4<f32>;
 
 struct Vertex {
     @builtin(instance_index) instance_index: u32,
@@ -42,9 +43,8 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
-    var color = FOREGROUND_COLOR;
     if in.x > value {
-        color = BACKGROUND_COLOR;
+        return BACKGROUND_COLOR;
     }
     return color;
 }
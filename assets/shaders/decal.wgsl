@@ -0,0 +1,77 @@
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::globals,
+}
+
+// Keep these in sync with /crates/terrain/src/decal.rs.
+const KIND_SCORCH = 0u;
+const KIND_CRATER = 1u;
+const KIND_FOUNDATION = 2u;
+
+const SCORCH_COLOR = vec4<f32>(0.05, 0.04, 0.03, 0.8);
+const CRATER_COLOR = vec4<f32>(0.08, 0.06, 0.05, 0.85);
+const CRATER_RIM_COLOR = vec4<f32>(0.3, 0.25, 0.2, 0.6);
+const FOUNDATION_COLOR = vec4<f32>(0.45, 0.45, 0.43, 0.6);
+const FOUNDATION_BORDER_COLOR = vec4<f32>(0.3, 0.3, 0.28, 0.8);
+// Fraction of the lifespan during which the decal fades out. Decals with zero
+// lifespan do not fade out.
+const FADE_FRACTION = 0.25;
+
+@group(2) @binding(0)
+var<uniform> kind: u32;
+@group(2) @binding(1)
+var<uniform> start_time: f32;
+@group(2) @binding(2)
+var<uniform> lifespan: f32;
+
+fn scorch(point: vec2<f32>) -> vec4<f32> {
+    let angle = atan2(point.y, point.x);
+    // Irregular (star like) edge of the burnt area.
+    let edge = 0.8 + 0.2 * sin(7. * angle) * sin(3. * angle + 1.);
+    var color = SCORCH_COLOR;
+    color.a *= 1. - smoothstep(0.2 * edge, edge, length(point));
+    return color;
+}
+
+fn crater(point: vec2<f32>) -> vec4<f32> {
+    let distance = length(point);
+    if distance > 1. {
+        return vec4<f32>(0.);
+    }
+
+    let rim = smoothstep(0.6, 0.8, distance) * (1. - smoothstep(0.8, 1., distance));
+    var color = mix(CRATER_COLOR, CRATER_RIM_COLOR, rim);
+    color.a *= 1. - smoothstep(0.85, 1., distance);
+    return color;
+}
+
+fn foundation(point: vec2<f32>) -> vec4<f32> {
+    let distance = max(abs(point.x), abs(point.y));
+    if distance > 0.92 {
+        return FOUNDATION_BORDER_COLOR;
+    }
+    return FOUNDATION_COLOR;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+) -> @location(0) vec4<f32> {
+    let point = 2. * in.uv - vec2<f32>(1.);
+
+    var color = vec4<f32>(0.);
+    if kind == KIND_SCORCH {
+        color = scorch(point);
+    } else if kind == KIND_CRATER {
+        color = crater(point);
+    } else if kind == KIND_FOUNDATION {
+        color = foundation(point);
+    }
+
+    if lifespan > 0. {
+        // Use max(0., ...) because the times are wrapping.
+        let age = max(0., globals.time - start_time) / lifespan;
+        color.a *= clamp((1. - age) / FADE_FRACTION, 0., 1.);
+    }
+    return color;
+}
@@ -0,0 +1,22 @@
+#import bevy_pbr::forward_io::VertexOutput
+
+// Keep this in sync with /crates/signs/src/rings.rs.
+const INNER_RADIUS = 0.85;
+// Width of the anti-aliased ring edges relative to the ring radius.
+const EDGE = 0.03;
+
+@group(2) @binding(0)
+var<uniform> color: vec4<f32>;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+) -> @location(0) vec4<f32> {
+    let distance = length(2. * in.uv - vec2<f32>(1.));
+    let inner = smoothstep(INNER_RADIUS, INNER_RADIUS + EDGE, distance);
+    let outer = 1. - smoothstep(1. - EDGE, 1., distance);
+
+    var out = color;
+    out.a *= 0.9 * inner * outer;
+    return out;
+}
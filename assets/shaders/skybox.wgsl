@@ -0,0 +1,28 @@
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::view,
+}
+
+@group(2) @binding(0)
+var day_texture: texture_cube<f32>;
+@group(2) @binding(1)
+var day_sampler: sampler;
+@group(2) @binding(2)
+var night_texture: texture_cube<f32>;
+@group(2) @binding(3)
+var night_sampler: sampler;
+@group(2) @binding(4)
+var<uniform> night_factor: f32;
+@group(2) @binding(5)
+var<uniform> brightness: f32;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+) -> @location(0) vec4<f32> {
+    // Cube maps are left-handed so we negate the z coordinate.
+    let direction = (in.world_position.xyz - view.world_position) * vec3<f32>(1., 1., -1.);
+    let day = textureSample(day_texture, day_sampler, direction);
+    let night = textureSample(night_texture, night_sampler, direction);
+    return mix(day, night, night_factor) * brightness * view.exposure;
+}
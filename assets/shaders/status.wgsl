@@ -0,0 +1,49 @@
+#import bevy_pbr::{
+    mesh_bindings::mesh,
+    mesh_functions::{get_model_matrix, mesh_position_local_to_clip},
+}
+
+@group(2) @binding(0)
+var<uniform> offset: vec2<f32>;
+@group(2) @binding(1)
+var image: texture_2d<f32>;
+@group(2) @binding(2)
+var image_sampler: sampler;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct FragmentInput {
+     @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    out.clip_position = mesh_position_local_to_clip(
+        get_model_matrix(vertex.instance_index),
+        vec4<f32>(0., 0., 0., 1.0),
+    );
+
+    // Keep in sync with bar.wgsl so that the icons keep their position
+    // relative to the entity bar.
+    let scale = max(1., out.clip_position.w / 40.);
+    out.clip_position += vec4<f32>(scale * (offset + vertex.position), 0., 0.);
+
+    out.uv = vertex.uv;
+    return out;
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    return textureSample(image, image_sampler, in.uv);
+}
@@ -10,9 +10,15 @@
 
 const SHAPE_COLOR = vec4<f32>(1., 1., 1., 0.75);
 const SHAPE_THICKNESS = 0.15;
+const BUILDABLE_COLOR = vec4<f32>(0.2, 0.8, 0.2, 0.7);
+const BLOCKED_COLOR = vec4<f32>(0.86, 0.08, 0.24, 0.6);
+const GRID_LINE_COLOR = vec4<f32>(1., 1., 1., 0.6);
+const GRID_LINE_THICKNESS = 0.05;
 // Keep these array lengths in sync with /crates/terrain/src/shader.rs.
 const MAX_KD_TREE_SIZE = 127u;
 const MAX_RECTANGLE_ARRAY_SIZE = 31u;
+const BUILD_GRID_SIZE = 32u;
+const BUILD_GRID_MASKS = 8u;
 
 struct KdTreeNode {
     @align(16) location: vec2<f32>,
@@ -34,12 +40,22 @@ struct Rectangles {
     count: u32,
 };
 
+struct BuildGrid {
+    origin: vec2<f32>,
+    cell_size: f32,
+    enabled: u32,
+    blocked: array<vec4<u32>, BUILD_GRID_MASKS>,
+};
+
 @group(2) @binding(100)
 var<uniform> uv_scale: f32;
 @group(2) @binding(101)
 var<uniform> circles: KdTree;
 @group(2) @binding(102)
 var<uniform> rectangles: Rectangles;
+@group(2) @binding(103)
+var<uniform> build_grid: BuildGrid;
+
 fn mix_colors(base: vec4<f32>, cover: vec4<f32>) -> vec4<f32> {
     let alpha = base.a * cover.a;
     let rgb = base.rgb * cover.a + cover.rgb * (1. - cover.a);
@@ -158,6 +174,37 @@ fn draw_rectangles(base: vec4<f32>, location: vec2<f32>) -> vec4<f32> {
     return base;
 }
 
+fn draw_build_grid(base: vec4<f32>, location: vec2<f32>) -> vec4<f32> {
+    if build_grid.enabled == 0u {
+        return base;
+    }
+
+    let grid_location = (location - build_grid.origin) / build_grid.cell_size;
+    let cell = floor(grid_location);
+    if any(cell < vec2<f32>(0.)) || any(cell >= vec2<f32>(f32(BUILD_GRID_SIZE))) {
+        return base;
+    }
+
+    let index = u32(cell.y) * BUILD_GRID_SIZE + u32(cell.x);
+    let mask = build_grid.blocked[index / 128u][(index / 32u) % 4u];
+    let blocked = ((mask >> (index % 32u)) & 1u) == 1u;
+
+    var output_color = base;
+    if blocked {
+        output_color = mix_colors(output_color, BLOCKED_COLOR);
+    } else {
+        output_color = mix_colors(output_color, BUILDABLE_COLOR);
+    }
+
+    let cell_location = (grid_location - cell) * build_grid.cell_size;
+    let line_distance = min(cell_location, vec2<f32>(build_grid.cell_size) - cell_location);
+    if any(line_distance <= vec2<f32>(GRID_LINE_THICKNESS)) {
+        output_color = mix_colors(output_color, GRID_LINE_COLOR);
+    }
+
+    return output_color;
+}
+
 @fragment
 fn fragment(
     in: VertexOutput,
@@ -170,6 +217,7 @@ fn fragment(
     out.color = apply_pbr_lighting(pbr_input);
 
     let location = uv_scale * in.uv;
+    out.color = draw_build_grid(out.color, location);
     out.color = draw_circles(out.color, location);
     out.color = draw_rectangles(out.color, location);
 
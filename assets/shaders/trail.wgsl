@@ -3,17 +3,17 @@
     mesh_view_bindings::globals,
 }
 
-const COLOR = vec4<f32>(1., 0.85, 0.1, 0.7);
-
 @group(2) @binding(0)
 var<uniform> start_time: f32;
+@group(2) @binding(1)
+var<uniform> color: vec4<f32>;
 
 @fragment
 fn fragment(
     in: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    var color = COLOR;
+    var out = color;
     // Use max(0., ...) because the times are wrapping.
-    color.a *= pow(0.001, max(0., globals.time - start_time)) * sqrt(1. - abs(in.uv.y));
-    return color;
+    out.a *= pow(0.001, max(0., globals.time - start_time)) * sqrt(1. - abs(in.uv.y));
+    return out;
 }
@@ -1,17 +1,28 @@
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 
 use crate::music::MusicPlugin;
+use crate::playlist::PlaylistPlugin;
 use crate::spatial::SpatialSoundPlugin;
+use crate::voice::VoicePlugin;
+pub use crate::voice::{PlayVoiceEvent, VoiceLine};
+pub use crate::volumes::UiChannel;
+use crate::volumes::VolumesPlugin;
 
 mod music;
+mod playlist;
 pub mod spatial;
+mod voice;
+mod volumes;
 
 pub struct AudioPluginGroup;
 
 impl PluginGroup for AudioPluginGroup {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
+            .add(VolumesPlugin)
+            .add(PlaylistPlugin)
             .add(MusicPlugin)
             .add(SpatialSoundPlugin)
+            .add(VoicePlugin)
     }
 }
@@ -1,47 +1,313 @@
+use std::time::Duration;
+
 use bevy::{asset::LoadState, prelude::*};
 use bevy_kira_audio::{
-    prelude::{Audio, AudioSource, Volume},
-    AudioControl,
+    prelude::AudioSource, AudioChannel, AudioControl, AudioInstance, AudioTween, PlaybackState,
 };
-use de_conf::Configuration;
 use de_core::state::AppState;
+use enum_map::EnumMap;
 use iyes_progress::prelude::*;
 
+use crate::{
+    playlist::{MusicContext, Playlist},
+    spatial::PlaySpatialAudioEvent,
+    volumes::{MusicChannel, Volumes},
+};
+
+/// Duration of the transition between two consecutive tracks.
+const CROSSFADE: Duration = Duration::from_secs(3);
+/// Combat intensity halves every this many seconds.
+const INTENSITY_HALF_LIFE: f32 = 10.;
+/// Battle music starts once combat intensity reaches this value.
+const BATTLE_INTENSITY: f32 = 8.;
+/// Battle music is replaced by calm music once combat intensity drops below
+/// this value.
+const CALM_INTENSITY: f32 = 2.;
+
 pub(crate) struct MusicPlugin;
 
 impl Plugin for MusicPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::AppLoading), setup)
+        app.init_resource::<Intensity>()
+            .add_systems(OnEnter(AppState::AppLoading), setup)
             .add_systems(
                 Update,
                 load.track_progress().run_if(in_state(AppState::AppLoading)),
             )
-            .add_systems(OnExit(AppState::AppLoading), start);
+            .add_systems(OnExit(AppState::AppLoading), start)
+            .add_systems(OnExit(AppState::InGame), reset_intensity)
+            .add_systems(
+                PostUpdate,
+                (
+                    update_intensity.run_if(in_state(AppState::InGame)),
+                    update_context,
+                    play,
+                )
+                    .chain()
+                    .run_if(resource_exists::<MusicPlayer>),
+            );
     }
 }
 
 #[derive(Resource)]
-struct Tracks(Handle<AudioSource>);
+struct PlaylistHandle(Handle<Playlist>);
+
+/// Recent amount of combat. It grows with each combat sound and decays
+/// exponentially.
+#[derive(Resource, Default)]
+struct Intensity(f32);
+
+impl Intensity {
+    fn update(&mut self, delta: f32, events: usize) {
+        self.0 = self.0 * 0.5f32.powf(delta / INTENSITY_HALF_LIFE) + events as f32;
+    }
+
+    /// Returns the in-game context given by the intensity. Hysteresis is
+    /// applied to avoid frequent switching.
+    fn context(&self, current: MusicContext) -> MusicContext {
+        if self.0 >= BATTLE_INTENSITY {
+            MusicContext::Battle
+        } else if self.0 < CALM_INTENSITY || current == MusicContext::Menu {
+            MusicContext::Calm
+        } else {
+            current
+        }
+    }
+}
+
+#[derive(Resource)]
+struct MusicPlayer {
+    context: MusicContext,
+    current: Option<Playing>,
+    shuffle: Shuffle,
+}
+
+struct Playing {
+    track: usize,
+    instance: Handle<AudioInstance>,
+    /// Whether the instance has been observed playing.
+    started: bool,
+}
+
+/// Shuffled queues of tracks of individual music contexts. All tracks of a
+/// context are played before any of them repeats.
+#[derive(Default)]
+struct Shuffle(EnumMap<MusicContext, Vec<usize>>);
+
+impl Shuffle {
+    /// Returns next track to be played.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - random number generator used for shuffling.
+    ///
+    /// * `context` - music context whose queue is used.
+    ///
+    /// * `tracks` - all (non-empty) tracks of the context.
+    ///
+    /// * `last` - last played track. It is not repeated right away unless it
+    ///   is the only track of the context.
+    fn next(
+        &mut self,
+        rng: &fastrand::Rng,
+        context: MusicContext,
+        tracks: &[usize],
+        last: Option<usize>,
+    ) -> usize {
+        let queue = &mut self.0[context];
+        if queue.is_empty() {
+            queue.extend_from_slice(tracks);
+            rng.shuffle(queue);
+
+            // The queue is popped from its end.
+            if queue.len() > 1 && queue.last().copied() == last {
+                queue.swap(0, tracks.len() - 1);
+            }
+        }
+        queue.pop().unwrap()
+    }
+}
 
 fn setup(mut commands: Commands, server: Res<AssetServer>) {
-    commands.insert_resource(Tracks(server.load("audio/music/menu_loop.mp3")));
+    commands.insert_resource(PlaylistHandle(
+        server.load("audio/music/music.playlist.json"),
+    ));
 }
 
-fn load(server: Res<AssetServer>, tracks: Res<Tracks>) -> Progress {
-    match server.get_load_state(&tracks.0) {
-        Some(LoadState::Loaded) => true.into(),
-        Some(LoadState::NotLoaded) | Some(LoadState::Loading) => false.into(),
+/// Waits for the playlist and for the menu tracks. Remaining tracks are loaded
+/// in the background.
+fn load(
+    server: Res<AssetServer>,
+    playlists: Res<Assets<Playlist>>,
+    playlist: Res<PlaylistHandle>,
+) -> Progress {
+    match server.get_load_state(&playlist.0) {
+        Some(LoadState::Loaded) => (),
+        Some(LoadState::NotLoaded) | Some(LoadState::Loading) => return false.into(),
         _ => panic!("Unexpected loading state."),
     }
+
+    let playlist = playlists.get(&playlist.0).unwrap();
+    playlist
+        .tracks_for(MusicContext::Menu)
+        .iter()
+        .map(
+            |&index| match server.get_load_state(playlist.track(index).source()) {
+                Some(LoadState::Loaded) => true.into(),
+                Some(LoadState::NotLoaded) | Some(LoadState::Loading) => false.into(),
+                _ => panic!("Unexpected loading state."),
+            },
+        )
+        .reduce(|a: Progress, b| a + b)
+        .unwrap()
+}
+
+fn start(mut commands: Commands) {
+    commands.insert_resource(MusicPlayer {
+        context: MusicContext::Menu,
+        current: None,
+        shuffle: Shuffle::default(),
+    });
+}
+
+fn reset_intensity(mut intensity: ResMut<Intensity>) {
+    intensity.0 = 0.;
+}
+
+fn update_intensity(
+    time: Res<Time>,
+    mut intensity: ResMut<Intensity>,
+    mut events: EventReader<PlaySpatialAudioEvent>,
+) {
+    let combat = events
+        .read()
+        .filter(|event| event.sound.is_combat())
+        .count();
+    intensity.update(time.delta_seconds(), combat);
+}
+
+fn update_context(
+    state: Res<State<AppState>>,
+    intensity: Res<Intensity>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    let context = match state.get() {
+        AppState::InGame => intensity.context(player.context),
+        AppState::AppLoading | AppState::InMenu => MusicContext::Menu,
+    };
+
+    // Do not trigger change detection unnecessarily.
+    if player.context != context {
+        player.context = context;
+    }
 }
 
-fn start(audio: Res<Audio>, tracks: Res<Tracks>, config: Res<Configuration>) {
-    if !config.audio().music_enabled() {
-        return;
+/// Starts next track once the current track is about to end or once it no
+/// longer suits the music context. The tracks are crossfaded. The music is
+/// played even when muted so that it can be heard once the volume is changed.
+#[allow(clippy::too_many_arguments)]
+fn play(
+    rng: Local<fastrand::Rng>,
+    mut player: ResMut<MusicPlayer>,
+    playlists: Res<Assets<Playlist>>,
+    playlist: Res<PlaylistHandle>,
+    sources: Res<Assets<AudioSource>>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    music: Res<AudioChannel<MusicChannel>>,
+    volumes: Res<Volumes>,
+) {
+    let playlist = playlists.get(&playlist.0).unwrap();
+    let context = player.context;
+
+    if let Some(current) = player.current.as_mut() {
+        let track = playlist.track(current.track);
+        let remaining = match music.state(&current.instance) {
+            PlaybackState::Playing { position } => {
+                current.started = true;
+                sources.get(track.source()).map_or(0., |source| {
+                    source.sound.duration().as_secs_f64() - position
+                })
+            }
+            // The instance has been stopped or has finished.
+            PlaybackState::Stopped if current.started => 0.,
+            // The play command has not been processed yet.
+            _ => return,
+        };
+
+        if track.suits(context) && remaining > CROSSFADE.as_secs_f64() {
+            return;
+        }
     }
 
-    audio
-        .play(tracks.0.clone())
-        .looped()
-        .with_volume(Volume::Amplitude(config.audio().music_volume().into()));
+    let last = player.current.take().map(|current| {
+        if let Some(instance) = instances.get_mut(&current.instance) {
+            instance.stop(AudioTween::linear(CROSSFADE));
+        }
+        current.track
+    });
+
+    let next = player
+        .shuffle
+        .next(&rng, context, playlist.tracks_for(context), last);
+    let track = playlist.track(next);
+    info!("Playing music track \"{}\".", track.title());
+    let instance = music
+        .play(track.source().clone())
+        .with_volume(volumes.music())
+        .fade_in(AudioTween::linear(CROSSFADE))
+        .handle();
+    player.current = Some(Playing {
+        track: next,
+        instance,
+        started: false,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intensity() {
+        let mut intensity = Intensity::default();
+        assert_eq!(intensity.context(MusicContext::Menu), MusicContext::Calm);
+
+        intensity.update(0.1, 10);
+        assert_eq!(intensity.context(MusicContext::Calm), MusicContext::Battle);
+
+        intensity.update(10., 0);
+        assert!((intensity.0 - 5.).abs() < 0.01);
+        assert_eq!(
+            intensity.context(MusicContext::Battle),
+            MusicContext::Battle
+        );
+        assert_eq!(intensity.context(MusicContext::Calm), MusicContext::Calm);
+
+        intensity.update(20., 0);
+        assert_eq!(intensity.context(MusicContext::Battle), MusicContext::Calm);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let rng = fastrand::Rng::with_seed(7);
+        let mut shuffle = Shuffle::default();
+
+        let tracks = [1, 3, 4];
+        let mut last = None;
+        for _ in 0..10 {
+            let mut played: Vec<usize> = (0..3)
+                .map(|_| {
+                    let next = shuffle.next(&rng, MusicContext::Calm, &tracks, last);
+                    assert_ne!(Some(next), last);
+                    last = Some(next);
+                    next
+                })
+                .collect();
+            played.sort();
+            assert_eq!(played, tracks);
+        }
+
+        assert_eq!(shuffle.next(&rng, MusicContext::Menu, &[2], Some(2)), 2);
+        assert_eq!(shuffle.next(&rng, MusicContext::Menu, &[2], Some(2)), 2);
+    }
 }
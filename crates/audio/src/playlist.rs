@@ -0,0 +1,200 @@
+use std::path::Path;
+
+use anyhow::{ensure, Context};
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    reflect::TypePath,
+    utils::BoxedFuture,
+};
+use bevy_kira_audio::prelude::AudioSource;
+use enum_iterator::{all, Sequence};
+use enum_map::{Enum, EnumMap};
+use serde::Deserialize;
+
+const PLAYLIST_EXTENSION: [&str; 1] = ["playlist.json"];
+
+pub(crate) struct PlaylistPlugin;
+
+impl Plugin for PlaylistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Playlist>()
+            .register_asset_loader(PlaylistLoader);
+    }
+}
+
+/// Situation in which music is played. Each track of a playlist is suitable
+/// for one or more contexts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum, Sequence, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MusicContext {
+    Menu,
+    Calm,
+    Battle,
+}
+
+/// All music tracks together with their metadata.
+#[derive(Asset, TypePath)]
+pub(crate) struct Playlist {
+    tracks: Vec<Track>,
+    /// Indices (into `tracks`) of tracks suitable for each context.
+    contexts: EnumMap<MusicContext, Vec<usize>>,
+}
+
+impl Playlist {
+    fn new(tracks: Vec<Track>) -> Self {
+        let mut contexts: EnumMap<MusicContext, Vec<usize>> = EnumMap::default();
+        for (index, track) in tracks.iter().enumerate() {
+            for &context in &track.contexts {
+                contexts[context].push(index);
+            }
+        }
+        Self { tracks, contexts }
+    }
+
+    pub(crate) fn track(&self, index: usize) -> &Track {
+        &self.tracks[index]
+    }
+
+    /// Returns indices of all tracks suitable for a context. There is always
+    /// at least one such track.
+    pub(crate) fn tracks_for(&self, context: MusicContext) -> &[usize] {
+        self.contexts[context].as_slice()
+    }
+}
+
+pub(crate) struct Track {
+    title: String,
+    contexts: Vec<MusicContext>,
+    source: Handle<AudioSource>,
+}
+
+impl Track {
+    pub(crate) fn title(&self) -> &str {
+        self.title.as_str()
+    }
+
+    pub(crate) fn source(&self) -> &Handle<AudioSource> {
+        &self.source
+    }
+
+    /// Returns true if the track is suitable for the context.
+    pub(crate) fn suits(&self, context: MusicContext) -> bool {
+        self.contexts.contains(&context)
+    }
+}
+
+#[derive(Deserialize)]
+struct PlaylistSerde {
+    tracks: Vec<TrackSerde>,
+}
+
+impl PlaylistSerde {
+    fn validate(&self) -> anyhow::Result<()> {
+        for context in all::<MusicContext>() {
+            ensure!(
+                self.tracks
+                    .iter()
+                    .any(|track| track.contexts.contains(&context)),
+                "There is no track for music context {context:?}."
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct TrackSerde {
+    title: String,
+    /// Path of the audio file relative to the playlist file.
+    path: String,
+    contexts: Vec<MusicContext>,
+}
+
+struct PlaylistLoader;
+
+impl AssetLoader for PlaylistLoader {
+    type Asset = Playlist;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, anyhow::Result<Self::Asset>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let playlist_serde: PlaylistSerde =
+                serde_json::from_slice(&bytes).context("Failed to parse playlist JSON")?;
+            playlist_serde.validate()?;
+
+            let directory = load_context
+                .path()
+                .parent()
+                .unwrap_or(Path::new(""))
+                .to_owned();
+            let tracks = playlist_serde
+                .tracks
+                .into_iter()
+                .map(|track| Track {
+                    source: load_context.load(directory.join(&track.path)),
+                    title: track.title,
+                    contexts: track.contexts,
+                })
+                .collect();
+
+            Ok(Playlist::new(tracks))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        PLAYLIST_EXTENSION.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let playlist: PlaylistSerde = serde_json::from_str(
+            r#"{"tracks": [
+                {"title": "A", "path": "a.mp3", "contexts": ["menu", "calm"]},
+                {"title": "B", "path": "b.mp3", "contexts": ["battle"]}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(playlist.validate().is_ok());
+
+        let playlist: PlaylistSerde = serde_json::from_str(
+            r#"{"tracks": [{"title": "A", "path": "a.mp3", "contexts": ["menu"]}]}"#,
+        )
+        .unwrap();
+        assert!(playlist.validate().is_err());
+    }
+
+    #[test]
+    fn test_playlist() {
+        let track = |title: &str, contexts: Vec<MusicContext>| Track {
+            title: title.to_owned(),
+            contexts,
+            source: Handle::default(),
+        };
+
+        let playlist = Playlist::new(vec![
+            track("A", vec![MusicContext::Menu, MusicContext::Calm]),
+            track("B", vec![MusicContext::Calm]),
+            track("C", vec![MusicContext::Battle]),
+        ]);
+        assert_eq!(playlist.tracks_for(MusicContext::Menu), &[0]);
+        assert_eq!(playlist.tracks_for(MusicContext::Calm), &[0, 1]);
+        assert_eq!(playlist.tracks_for(MusicContext::Battle), &[2]);
+        assert_eq!(playlist.track(1).title(), "B");
+        assert!(playlist.track(0).suits(MusicContext::Calm));
+        assert!(!playlist.track(0).suits(MusicContext::Battle));
+    }
+}
@@ -3,16 +3,24 @@ use std::f32::consts::PI;
 use bevy::{asset::LoadState, prelude::*};
 use bevy_kira_audio::{prelude::AudioSource, Audio, AudioControl, AudioInstance};
 use de_camera::CameraFocus;
-use de_conf::Configuration;
+use de_conf::{AttenuationCurve, Configuration};
 use de_core::{gamestate::GameState, state::AppState};
 use enum_map::{enum_map, Enum, EnumMap};
-use iyes_progress::{Progress, ProgressSystem};
+
+use crate::volumes::Volumes;
 
 // Angle occlusion parameters
 
 /// The angle is calculated from a point this many units behind the camera
 const SOUND_VIEW_ANGLE_OFFSET: f32 = 32.;
 
+/// Maximum number of simultaneously playing spatial sounds. Sounds requested
+/// over this limit are dropped so that large battles do not clip.
+const MAX_VOICES: usize = 32;
+/// Sounds whose initial volume (before the effects volume is applied) is
+/// below this threshold are not played at all.
+const MIN_VOLUME: f64 = 0.01;
+
 pub(crate) struct SpatialSoundPlugin;
 
 impl Plugin for SpatialSoundPlugin {
@@ -21,7 +29,7 @@ impl Plugin for SpatialSoundPlugin {
             .add_systems(OnEnter(AppState::AppLoading), setup)
             .add_systems(
                 Update,
-                load.track_progress().run_if(in_state(AppState::AppLoading)),
+                check_loading.run_if(resource_exists::<PendingSounds>),
             )
             .add_systems(
                 PostUpdate,
@@ -41,6 +49,35 @@ pub enum Sound {
     DestroyBuilding,
     DestroyUnit,
     LaserFire,
+    /// Laser beam hitting an object or the terrain.
+    Impact,
+    /// Artillery shell explosion.
+    Explosion,
+}
+
+impl Sound {
+    /// Returns true if the sound is caused by fighting.
+    pub(crate) fn is_combat(self) -> bool {
+        matches!(
+            self,
+            Self::DestroyBuilding
+                | Self::DestroyUnit
+                | Self::LaserFire
+                | Self::Impact
+                | Self::Explosion
+        )
+    }
+
+    /// Maximum number of simultaneously playing instances of the sound.
+    fn max_voices(self) -> usize {
+        match self {
+            Self::Construct | Self::Manufacture => 2,
+            Self::DestroyBuilding | Self::DestroyUnit => 4,
+            Self::LaserFire => 12,
+            Self::Impact => 8,
+            Self::Explosion => 6,
+        }
+    }
 }
 
 #[derive(Event)]
@@ -58,106 +95,238 @@ impl PlaySpatialAudioEvent {
 #[derive(Resource)]
 struct Sounds(EnumMap<Sound, Handle<AudioSource>>);
 
-#[derive(Component, Default)]
-struct SpatialSound;
+/// Sounds whose loading has not finished yet.
+#[derive(Resource)]
+struct PendingSounds(Vec<Sound>);
+
+#[derive(Component)]
+struct SpatialSound(Sound);
+
+/// Bookkeeping of playing spatial sounds, used to limit their number.
+#[derive(Default)]
+struct VoicePool {
+    total: usize,
+    counts: EnumMap<Sound, usize>,
+}
+
+impl VoicePool {
+    /// Tries to reserve a voice for the sound. Returns true if the sound may
+    /// be played, false if either the global or the per-sound limit has been
+    /// reached.
+    fn try_acquire(&mut self, sound: Sound) -> bool {
+        if self.total >= MAX_VOICES || self.counts[sound] >= sound.max_voices() {
+            return false;
+        }
 
+        self.total += 1;
+        self.counts[sound] += 1;
+        true
+    }
+}
+
+/// Sounds are not needed before the game starts, therefore they are loaded in
+/// the background. Sounds which are not loaded yet are not played.
 fn setup(mut commands: Commands, server: Res<AssetServer>) {
     use Sound::*;
+    commands.insert_resource(PendingSounds(
+        (0..Sound::LENGTH).map(Sound::from_usize).collect(),
+    ));
     commands.insert_resource(Sounds(enum_map! {
         Construct => server.load("audio/sounds/construct.ogg"),
         Manufacture => server.load("audio/sounds/manufacture.ogg"),
         DestroyBuilding => server.load("audio/sounds/destruction_building.ogg"),
         DestroyUnit => server.load("audio/sounds/destruction_unit.ogg"),
         LaserFire => server.load("audio/sounds/laser.ogg"),
+        Impact => server.load("audio/sounds/impact.ogg"),
+        Explosion => server.load("audio/sounds/explosion.ogg"),
     }));
 }
 
-fn load(server: Res<AssetServer>, sounds: Res<Sounds>) -> Progress {
-    Progress {
-        done: sounds
-            .0
-            .values()
-            .map(|handle| match server.get_load_state(handle) {
-                Some(LoadState::Loaded) => 1,
-                Some(LoadState::NotLoaded) | Some(LoadState::Loading) => 0,
-                _ => panic!("Unexpected loading state."),
-            })
-            .sum(),
-        total: sounds
-            .0
-            .len()
-            .try_into()
-            .expect("Trying to load an ungodly number of sounds"),
-    }
-}
-
-fn calculate_volume_and_pan(
-    camera: &GlobalTransform,
-    focus: &CameraFocus,
-    sound_position: Vec3,
-) -> (f64, f64) {
-    let cam_right = camera.right();
-    let sound_dir = (sound_position - camera.translation()).normalize();
-    let pan = cam_right.dot(sound_dir) * 0.5 + 0.5;
-
-    // Simulates sounds becoming quieter when further away
-    let attenuation_factor = {
-        let distance_from_camera_squared = camera.translation().distance_squared(sound_position);
-        // Anything closer than 70% of zoom distance is at full volume.
-        let min_distance_squared = (0.7 * focus.distance().inner()).powi(2);
-
-        min_distance_squared / distance_from_camera_squared
-    };
-
-    // Silences sounds whose sources are not in view
-    let occlusion_factor = {
-        // Volume is 1 from 0-25.5 degrees from the view direction, then linearly goes to 0 from 25.5-45 degrees.
-        // Angle is calculated from a point slightly behind the camera.
-        let camera_offset = camera.translation() + camera.back() * SOUND_VIEW_ANGLE_OFFSET;
-        let angle = (sound_position - camera_offset).angle_between(camera.forward());
-
-        // Slope parameter of linear function (-1 unit in 25.5 degrees or pi/8)
-        const SLOPE: f32 = -1.0 / (PI / 8.);
-        // Constant parameter of linear function
-        const CONSTANT: f32 = 2.0;
-        // Let's limit this to non-negative to avoid the weirdness that
-        // occurs if both factors end up being negative at some point
-        (SLOPE * angle + CONSTANT).max(0.0)
-    };
-
-    let volume = (attenuation_factor * occlusion_factor).clamp(0., 1.);
-
-    (volume as f64, pan as f64)
+/// Reports sounds which failed to load. A missing sound does not prevent the
+/// game from running, therefore the failure is only logged.
+fn check_loading(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    sounds: Res<Sounds>,
+    mut pending: ResMut<PendingSounds>,
+) {
+    pending.0.retain(|&sound| {
+        let source = &sounds.0[sound];
+        match server.get_load_state(source) {
+            Some(LoadState::Loaded) => false,
+            Some(LoadState::Failed) => {
+                let path = server
+                    .get_path(source)
+                    .map_or_else(|| "<unknown>".to_owned(), |path| path.to_string());
+                error!("Sound {path} failed to load.");
+                false
+            }
+            _ => true,
+        }
+    });
+
+    if pending.0.is_empty() {
+        commands.remove_resource::<PendingSounds>();
+    }
 }
 
+/// Parameters of spatial sound volume and panning calculation.
+struct Listener<'a> {
+    camera: &'a GlobalTransform,
+    curve: AttenuationCurve,
+    /// Sounds closer than this are played at full volume.
+    reference_distance: f32,
+    max_distance: f32,
+    panning_strength: f32,
+    /// Volume multiplier given by camera zoom.
+    zoom_gain: f32,
+}
+
+impl<'a> Listener<'a> {
+    fn new(camera: &'a GlobalTransform, focus: &CameraFocus, config: &Configuration) -> Self {
+        let audio = config.audio();
+        let distance = focus.distance().inner();
+
+        Self {
+            camera,
+            curve: audio.attenuation_curve(),
+            // Anything closer than 70% of zoom distance is at full volume.
+            reference_distance: 0.7 * distance,
+            max_distance: audio.max_audible_distance().inner(),
+            panning_strength: audio.panning_strength(),
+            zoom_gain: zoom_gain(
+                config.camera().min_distance().inner(),
+                distance,
+                audio.zoom_attenuation(),
+            ),
+        }
+    }
+
+    fn volume_and_pan(&self, sound_position: Vec3) -> (f64, f64) {
+        let camera = self.camera;
+        let cam_right = camera.right();
+        let sound_dir = (sound_position - camera.translation()).normalize();
+        let pan = 0.5 + 0.5 * self.panning_strength * cam_right.dot(sound_dir);
+
+        // Simulates sounds becoming quieter when further away
+        let attenuation_factor = attenuation(
+            self.curve,
+            self.reference_distance,
+            self.max_distance,
+            camera.translation().distance(sound_position),
+        );
+
+        // Silences sounds whose sources are not in view
+        let occlusion_factor = {
+            // Volume is 1 from 0-25.5 degrees from the view direction, then linearly goes to 0 from 25.5-45 degrees.
+            // Angle is calculated from a point slightly behind the camera.
+            let camera_offset = camera.translation() + camera.back() * SOUND_VIEW_ANGLE_OFFSET;
+            let angle = (sound_position - camera_offset).angle_between(camera.forward());
+
+            // Slope parameter of linear function (-1 unit in 25.5 degrees or pi/8)
+            const SLOPE: f32 = -1.0 / (PI / 8.);
+            // Constant parameter of linear function
+            const CONSTANT: f32 = 2.0;
+            // Let's limit this to non-negative to avoid the weirdness that
+            // occurs if both factors end up being negative at some point
+            (SLOPE * angle + CONSTANT).max(0.0)
+        };
+
+        let volume = (self.zoom_gain * attenuation_factor * occlusion_factor).clamp(0., 1.);
+
+        (volume as f64, pan as f64)
+    }
+}
+
+/// Returns volume multiplier (between 0 and 1) of a sound at a distance from
+/// the listener.
+///
+/// # Arguments
+///
+/// * `curve` - attenuation curve.
+///
+/// * `reference` - sounds closer than this are at full volume.
+///
+/// * `max` - sounds further than this are silent.
+///
+/// * `distance` - distance of the sound from the listener.
+fn attenuation(curve: AttenuationCurve, reference: f32, max: f32, distance: f32) -> f32 {
+    if distance >= max {
+        return 0.;
+    }
+    if distance <= reference {
+        return 1.;
+    }
+
+    match curve {
+        AttenuationCurve::InverseSquare => (reference / distance).powi(2),
+        AttenuationCurve::Inverse => reference / distance,
+        AttenuationCurve::Linear => 1. - (distance - reference) / (max - reference),
+    }
+}
+
+/// Returns volume multiplier given by camera zoom. Zoomed out camera covers
+/// larger area with more sound sources, thus all sounds are attenuated.
+fn zoom_gain(min_distance: f32, distance: f32, exponent: f32) -> f32 {
+    (min_distance / distance).min(1.).powf(exponent)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn play(
     mut commands: Commands,
     camera: Query<&GlobalTransform, With<Camera>>,
     focus: Res<CameraFocus>,
+    config: Res<Configuration>,
+    server: Res<AssetServer>,
     audio: Res<Audio>,
     sounds: Res<Sounds>,
-    config: Res<Configuration>,
+    volumes: Res<Volumes>,
+    playing: Query<&SpatialSound>,
     mut play_events: EventReader<PlaySpatialAudioEvent>,
 ) {
-    if !config.audio().sound_enabled() {
+    if volumes.effects() == 0. {
         play_events.clear();
     }
 
-    let camera = camera.single();
-    let sound_volume = config.audio().sound_volume() as f64;
+    let listener = Listener::new(camera.single(), &focus, &config);
+    let sound_volume = volumes.effects();
+
+    let mut pool = VoicePool::default();
+    for &SpatialSound(sound) in playing.iter() {
+        // Already playing sounds are counted even if over the limits.
+        pool.total += 1;
+        pool.counts[sound] += 1;
+    }
+
+    let mut requests: Vec<(Sound, Vec3, f64, f64)> = play_events
+        .read()
+        .filter_map(|&PlaySpatialAudioEvent { sound, position }| {
+            let (volume, pan) = listener.volume_and_pan(position);
+            (volume >= MIN_VOLUME).then_some((sound, position, volume, pan))
+        })
+        .collect();
+    // The loudest sounds are given voices first.
+    requests.sort_unstable_by(|a, b| b.2.total_cmp(&a.2));
+
+    for (sound, position, volume, pan) in requests {
+        let source = &sounds.0[sound];
+        if server.get_load_state(source) != Some(LoadState::Loaded) {
+            continue;
+        }
+        if !pool.try_acquire(sound) {
+            continue;
+        }
 
-    for PlaySpatialAudioEvent { sound, position } in play_events.read() {
-        let (volume, pan) = calculate_volume_and_pan(camera, &focus, *position);
         let handle = audio
-            .play(sounds.0[*sound].clone())
+            .play(source.clone())
             .with_volume(volume * sound_volume)
             .with_panning(pan)
             .handle();
 
         commands.spawn((
-            TransformBundle::from_transform(Transform::from_translation(*position)),
+            TransformBundle::from_transform(Transform::from_translation(position)),
             handle,
-            SpatialSound,
+            SpatialSound(sound),
         ));
     }
 }
@@ -170,10 +339,11 @@ fn update_spatial(
     camera: Query<&GlobalTransform, With<Camera>>,
     focus: Res<CameraFocus>,
     config: Res<Configuration>,
+    volumes: Res<Volumes>,
     mut audio_instances: ResMut<Assets<AudioInstance>>,
 ) {
-    let camera = camera.single();
-    let sound_volume = config.audio().sound_volume() as f64;
+    let listener = Listener::new(camera.single(), &focus, &config);
+    let sound_volume = volumes.effects();
 
     for (entity, audio, transform) in &spatial_audios {
         let Some(audio_instance) = audio_instances.get_mut(audio) else {
@@ -181,9 +351,65 @@ fn update_spatial(
             continue;
         };
 
-        let (volume, pan) = calculate_volume_and_pan(camera, &focus, transform.translation());
+        let (volume, pan) = listener.volume_and_pan(transform.translation());
 
         audio_instance.set_volume(volume * sound_volume, default());
         audio_instance.set_panning(pan, default());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_pool() {
+        let mut pool = VoicePool::default();
+        for _ in 0..Sound::Construct.max_voices() {
+            assert!(pool.try_acquire(Sound::Construct));
+        }
+        assert!(!pool.try_acquire(Sound::Construct));
+        assert!(pool.try_acquire(Sound::Manufacture));
+        assert_eq!(pool.total, 3);
+
+        for sound in [Sound::LaserFire, Sound::Impact, Sound::Explosion] {
+            while pool.try_acquire(sound) {}
+        }
+        assert_eq!(pool.total, 29);
+
+        assert!(pool.try_acquire(Sound::DestroyUnit));
+        assert!(pool.try_acquire(Sound::DestroyUnit));
+        assert!(pool.try_acquire(Sound::DestroyBuilding));
+        assert_eq!(pool.total, MAX_VOICES);
+        assert!(!pool.try_acquire(Sound::DestroyBuilding));
+    }
+
+    #[test]
+    fn test_attenuation() {
+        for curve in [
+            AttenuationCurve::InverseSquare,
+            AttenuationCurve::Inverse,
+            AttenuationCurve::Linear,
+        ] {
+            assert_eq!(attenuation(curve, 10., 100., 5.), 1.);
+            assert_eq!(attenuation(curve, 10., 100., 100.), 0.);
+            let near = attenuation(curve, 10., 100., 20.);
+            let far = attenuation(curve, 10., 100., 60.);
+            assert!(0. < far && far < near && near < 1.);
+        }
+
+        assert_eq!(
+            attenuation(AttenuationCurve::InverseSquare, 10., 100., 20.),
+            0.25
+        );
+        assert_eq!(attenuation(AttenuationCurve::Inverse, 10., 100., 20.), 0.5);
+        assert_eq!(attenuation(AttenuationCurve::Linear, 10., 100., 55.), 0.5);
+    }
+
+    #[test]
+    fn test_zoom_gain() {
+        assert_eq!(zoom_gain(20., 20., 0.5), 1.);
+        assert_eq!(zoom_gain(20., 80., 0.5), 0.5);
+        assert_eq!(zoom_gain(20., 80., 0.), 1.);
+    }
+}
@@ -0,0 +1,210 @@
+//! Voice lines: short responses of units to player commands and
+//! announcements of game events.
+//!
+//! Each voice line belongs to a category. Lines of a category are not
+//! repeated more often than allowed by the category cooldown so that, for
+//! example, quickly repeated commands do not result in a cacophony.
+//! Announcements are driven by (already throttled) game notifications, see
+//! [`de_core::notification`].
+//!
+//! Voice lines are optional: lines whose recordings have not (yet) been
+//! loaded are silently skipped.
+
+use std::time::Duration;
+
+use bevy::{asset::LoadState, prelude::*};
+use bevy_kira_audio::{prelude::AudioSource, AudioChannel, AudioControl};
+use de_core::{
+    gamestate::GameState,
+    notification::{GameNotification, NotificationKind, NotificationSet},
+    state::AppState,
+};
+use enum_map::{enum_map, Enum, EnumMap};
+
+use crate::volumes::VoiceChannel;
+
+pub(crate) struct VoicePlugin;
+
+impl Plugin for VoicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayVoiceEvent>()
+            .add_systems(OnEnter(AppState::AppLoading), setup)
+            .add_systems(OnEnter(AppState::InGame), init_cooldowns)
+            .add_systems(OnExit(AppState::InGame), cleanup_cooldowns)
+            .add_systems(
+                PostUpdate,
+                (
+                    announce
+                        .run_if(on_event::<GameNotification>())
+                        .after(NotificationSet::Throttle)
+                        .before(play),
+                    play.run_if(on_event::<PlayVoiceEvent>()),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq)]
+pub enum VoiceLine {
+    /// A unit of the player has been selected.
+    Selected,
+    /// Units of the player were commanded to move.
+    Move,
+    /// Units of the player were commanded to attack.
+    Attack,
+    /// A unit of the player has been destroyed.
+    UnitLost,
+    /// A factory of the player has finished manufacturing of a unit.
+    ConstructionComplete,
+    /// An object of the player is being damaged.
+    UnderAttack,
+    /// A player has been eliminated from the game.
+    PlayerEliminated,
+    /// An object of the player is low on energy.
+    LowPower,
+}
+
+impl VoiceLine {
+    fn category(self) -> VoiceCategory {
+        match self {
+            Self::Selected | Self::Move | Self::Attack => VoiceCategory::Response,
+            Self::UnitLost
+            | Self::ConstructionComplete
+            | Self::UnderAttack
+            | Self::PlayerEliminated
+            | Self::LowPower => VoiceCategory::Announcement,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq)]
+enum VoiceCategory {
+    Response,
+    Announcement,
+}
+
+impl VoiceCategory {
+    /// Minimum time between two consecutive lines of the category.
+    fn cooldown(self) -> Duration {
+        match self {
+            Self::Response => Duration::from_millis(500),
+            // Overlapping announcements would be hard to understand.
+            Self::Announcement => Duration::from_secs(1),
+        }
+    }
+}
+
+/// Send this event to play a voice line. The line is not played if another
+/// line of the same category was played recently.
+#[derive(Event)]
+pub struct PlayVoiceEvent(VoiceLine);
+
+impl PlayVoiceEvent {
+    pub fn new(line: VoiceLine) -> Self {
+        Self(line)
+    }
+}
+
+#[derive(Resource)]
+struct Voices(EnumMap<VoiceLine, Handle<AudioSource>>);
+
+/// Time (since game start) when a line of each category was last played.
+#[derive(Resource, Default)]
+struct Cooldowns(EnumMap<VoiceCategory, Option<Duration>>);
+
+impl Cooldowns {
+    /// Returns true and restarts the category cooldown if a line of the
+    /// category may be played now.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - category of the line to be played.
+    ///
+    /// * `time` - elapsed time since a fixed point in time in the past.
+    fn try_restart(&mut self, category: VoiceCategory, time: Duration) -> bool {
+        let last = &mut self.0[category];
+        if last.is_some_and(|last| time < last + category.cooldown()) {
+            return false;
+        }
+
+        *last = Some(time);
+        true
+    }
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    use VoiceLine::*;
+    commands.insert_resource(Voices(enum_map! {
+        Selected => server.load("audio/voice/selected.ogg"),
+        Move => server.load("audio/voice/move.ogg"),
+        Attack => server.load("audio/voice/attack.ogg"),
+        UnitLost => server.load("audio/voice/unit_lost.ogg"),
+        ConstructionComplete => server.load("audio/voice/construction_complete.ogg"),
+        UnderAttack => server.load("audio/voice/under_attack.ogg"),
+        PlayerEliminated => server.load("audio/voice/player_eliminated.ogg"),
+        LowPower => server.load("audio/voice/low_power.ogg"),
+    }));
+}
+
+fn init_cooldowns(mut commands: Commands) {
+    commands.init_resource::<Cooldowns>();
+}
+
+fn cleanup_cooldowns(mut commands: Commands) {
+    commands.remove_resource::<Cooldowns>();
+}
+
+fn announce(
+    mut notifications: EventReader<GameNotification>,
+    mut events: EventWriter<PlayVoiceEvent>,
+) {
+    for notification in notifications.read() {
+        let line = match notification.kind() {
+            NotificationKind::UnderAttack => VoiceLine::UnderAttack,
+            NotificationKind::UnitLost => VoiceLine::UnitLost,
+            NotificationKind::ProductionComplete => VoiceLine::ConstructionComplete,
+            NotificationKind::PlayerEliminated => VoiceLine::PlayerEliminated,
+            NotificationKind::LowPower | NotificationKind::PowerDepleted => VoiceLine::LowPower,
+            // There are no announcements of these notifications.
+            NotificationKind::FactoryIdle | NotificationKind::NoRallyPoint => continue,
+        };
+        events.send(PlayVoiceEvent::new(line));
+    }
+}
+
+fn play(
+    time: Res<Time>,
+    server: Res<AssetServer>,
+    voices: Res<Voices>,
+    mut cooldowns: ResMut<Cooldowns>,
+    channel: Res<AudioChannel<VoiceChannel>>,
+    mut events: EventReader<PlayVoiceEvent>,
+) {
+    for event in events.read() {
+        let handle = &voices.0[event.0];
+        if server.get_load_state(handle) != Some(LoadState::Loaded) {
+            continue;
+        }
+        if cooldowns.try_restart(event.0.category(), time.elapsed()) {
+            channel.play(handle.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldowns() {
+        let mut cooldowns = Cooldowns::default();
+
+        let time = |millis| Duration::from_millis(millis);
+        assert!(cooldowns.try_restart(VoiceCategory::Announcement, time(10000)));
+        assert!(!cooldowns.try_restart(VoiceCategory::Announcement, time(10500)));
+        assert!(cooldowns.try_restart(VoiceCategory::Response, time(10500)));
+        assert!(cooldowns.try_restart(VoiceCategory::Announcement, time(11000)));
+        assert!(!cooldowns.try_restart(VoiceCategory::Response, time(10900)));
+    }
+}
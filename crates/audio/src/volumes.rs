@@ -0,0 +1,136 @@
+use bevy::{prelude::*, window::WindowFocused};
+use bevy_kira_audio::{AudioApp, AudioChannel, AudioControl};
+use de_conf::{AudioConf, Configuration};
+
+pub(crate) struct VolumesPlugin;
+
+impl Plugin for VolumesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<UiChannel>()
+            .add_audio_channel::<VoiceChannel>()
+            .init_resource::<Volumes>()
+            .add_systems(
+                PreUpdate,
+                (
+                    update_volumes.run_if(resource_exists::<Configuration>),
+                    apply_volumes.run_if(resource_changed::<Volumes>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Audio channel of the game music.
+#[derive(Resource)]
+pub(crate) struct MusicChannel;
+
+/// Audio channel of voice lines.
+#[derive(Resource)]
+pub(crate) struct VoiceChannel;
+
+/// Audio channel for user interface sounds. Its volume is managed
+/// automatically according to the configuration.
+#[derive(Resource)]
+pub struct UiChannel;
+
+/// Effective (i.e. including master volume and window focus) volumes of
+/// individual audio channels.
+#[derive(Resource, Default, PartialEq)]
+pub(crate) struct Volumes {
+    music: f64,
+    effects: f64,
+    ui: f64,
+}
+
+impl Volumes {
+    fn new(conf: &AudioConf, focused: bool) -> Self {
+        let scale = if conf.mute_when_unfocused() && !focused {
+            0.
+        } else {
+            1.
+        };
+
+        Self {
+            music: scale * conf.music_volume() as f64,
+            effects: scale * conf.sound_volume() as f64,
+            ui: scale * conf.ui_volume() as f64,
+        }
+    }
+
+    pub(crate) fn music(&self) -> f64 {
+        self.music
+    }
+
+    pub(crate) fn effects(&self) -> f64 {
+        self.effects
+    }
+
+    pub(crate) fn ui(&self) -> f64 {
+        self.ui
+    }
+}
+
+struct Focus(bool);
+
+impl Default for Focus {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+fn update_volumes(
+    mut focus: Local<Focus>,
+    config: Res<Configuration>,
+    mut volumes: ResMut<Volumes>,
+    mut events: EventReader<WindowFocused>,
+) {
+    for event in events.read() {
+        focus.0 = event.focused;
+    }
+    volumes.set_if_neq(Volumes::new(config.audio(), focus.0));
+}
+
+fn apply_volumes(
+    volumes: Res<Volumes>,
+    music: Res<AudioChannel<MusicChannel>>,
+    ui: Res<AudioChannel<UiChannel>>,
+    voice: Res<AudioChannel<VoiceChannel>>,
+) {
+    music.set_volume(volumes.music());
+    ui.set_volume(volumes.ui());
+    voice.set_volume(volumes.effects());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volumes() {
+        let conf = AudioConf::default();
+        for focused in [true, false] {
+            let volumes = Volumes::new(&conf, focused);
+            assert_eq!(volumes.music(), 1.);
+            assert_eq!(volumes.effects(), 1.);
+            assert_eq!(volumes.ui(), 1.);
+        }
+    }
+
+    #[test]
+    fn test_mute_when_unfocused() {
+        let mut value = serde_json::to_value(AudioConf::default()).unwrap();
+        value["mute_when_unfocused"] = true.into();
+        let conf: AudioConf = serde_json::from_value(value).unwrap();
+
+        let volumes = Volumes::new(&conf, true);
+        assert_eq!(volumes.music(), 1.);
+        assert_eq!(volumes.effects(), 1.);
+        assert_eq!(volumes.ui(), 1.);
+
+        let volumes = Volumes::new(&conf, false);
+        assert_eq!(volumes.music(), 0.);
+        assert_eq!(volumes.effects(), 0.);
+        assert_eq!(volumes.ui(), 0.);
+    }
+}
@@ -0,0 +1,321 @@
+//! Armies are named, persistent groups of units. Aggregate state of each
+//! army (centroid, average health and composition) is recomputed every
+//! frame so that it can be used by formations, AI and the HUD.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState, objects::ObjectTypeComponent, player::PlayerComponent, state::AppState,
+};
+use de_objects::Health;
+use de_types::{
+    objects::{ActiveObjectType, ObjectType, UnitType},
+    player::Player,
+    projection::ToFlat,
+};
+use enum_map::EnumMap;
+
+pub(crate) struct ArmyPlugin;
+
+impl Plugin for ArmyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SetArmyEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PreUpdate,
+                handle_army_events
+                    .run_if(in_state(GameState::Playing))
+                    .in_set(ArmySet::SetArmyEvent),
+            )
+            .add_systems(
+                PostUpdate,
+                update
+                    .run_if(in_state(GameState::Playing))
+                    .in_set(ArmySet::Update),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum ArmySet {
+    SetArmyEvent,
+    Update,
+}
+
+/// Send this event to add a unit to an army, move it to another army or
+/// remove it from its army. The event is ignored if the unit does not belong
+/// to the owner of the army.
+#[derive(Event)]
+pub struct SetArmyEvent {
+    entity: Entity,
+    army: Option<ArmyId>,
+}
+
+impl SetArmyEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - the unit whose army is changed.
+    ///
+    /// * `army` - the new army of the unit or None if the unit shall leave
+    ///   its current army.
+    pub fn new(entity: Entity, army: Option<ArmyId>) -> Self {
+        Self { entity, army }
+    }
+
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    fn army(&self) -> Option<ArmyId> {
+        self.army
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArmyId(u32);
+
+/// Units with this component are members of an army.
+#[derive(Component, Deref)]
+pub struct ArmyMember(ArmyId);
+
+/// All armies of the current game. Armies persist even if all their members
+/// are destroyed, until they are disbanded.
+#[derive(Resource, Default)]
+pub struct Armies {
+    next_id: u32,
+    armies: BTreeMap<ArmyId, Army>,
+}
+
+impl Armies {
+    /// Creates a new empty army and returns its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - owner of the army.
+    ///
+    /// * `name` - human readable name of the army.
+    pub fn create(&mut self, player: Player, name: impl Into<String>) -> ArmyId {
+        let id = ArmyId(self.next_id);
+        self.next_id += 1;
+        self.armies.insert(id, Army::new(player, name.into()));
+        id
+    }
+
+    /// Removes an army. Its members leave the army during the next update.
+    pub fn disband(&mut self, id: ArmyId) -> Option<Army> {
+        self.armies.remove(&id)
+    }
+
+    pub fn get(&self, id: ArmyId) -> Option<&Army> {
+        self.armies.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: ArmyId) -> Option<&mut Army> {
+        self.armies.get_mut(&id)
+    }
+
+    /// Returns an iterator over all armies ordered by their creation.
+    pub fn iter(&self) -> impl Iterator<Item = (ArmyId, &Army)> {
+        self.armies.iter().map(|(&id, army)| (id, army))
+    }
+}
+
+pub struct Army {
+    name: String,
+    player: Player,
+    members: Vec<Entity>,
+    position_sum: Vec2,
+    health_sum: f32,
+    composition: EnumMap<UnitType, u32>,
+}
+
+impl Army {
+    fn new(player: Player, name: String) -> Self {
+        Self {
+            name,
+            player,
+            members: Vec::new(),
+            position_sum: Vec2::ZERO,
+            health_sum: 0.,
+            composition: EnumMap::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
+    /// Owner of the army.
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    /// Members of the army as of the last update.
+    pub fn members(&self) -> &[Entity] {
+        self.members.as_slice()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Average flat position of the army members or None if the army is
+    /// empty.
+    pub fn centroid(&self) -> Option<Vec2> {
+        (!self.is_empty()).then(|| self.position_sum / self.members.len() as f32)
+    }
+
+    /// Average health fraction of the army members or None if the army is
+    /// empty. See [`Health::fraction`].
+    pub fn health(&self) -> Option<f32> {
+        (!self.is_empty()).then(|| self.health_sum / self.members.len() as f32)
+    }
+
+    /// Number of army members of each unit type.
+    pub fn composition(&self) -> &EnumMap<UnitType, u32> {
+        &self.composition
+    }
+
+    fn clear(&mut self) {
+        self.members.clear();
+        self.position_sum = Vec2::ZERO;
+        self.health_sum = 0.;
+        self.composition = EnumMap::default();
+    }
+
+    fn add(&mut self, entity: Entity, position: Vec2, unit_type: UnitType, health: f32) {
+        self.members.push(entity);
+        self.position_sum += position;
+        self.health_sum += health;
+        self.composition[unit_type] += 1;
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<Armies>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Armies>();
+}
+
+fn handle_army_events(
+    mut commands: Commands,
+    armies: Res<Armies>,
+    players: Query<&PlayerComponent>,
+    mut events: EventReader<SetArmyEvent>,
+) {
+    for event in events.read() {
+        let Ok(&player) = players.get(event.entity()) else {
+            continue;
+        };
+        match event.army() {
+            Some(id) => {
+                let Some(army) = armies.get(id) else {
+                    continue;
+                };
+                if army.player() != *player {
+                    warn!(
+                        "Cannot add {:?} of {:?} to army {:?} of {:?}.",
+                        event.entity(),
+                        *player,
+                        army.name(),
+                        army.player()
+                    );
+                    continue;
+                }
+                commands.entity(event.entity()).insert(ArmyMember(id));
+            }
+            None => {
+                commands.entity(event.entity()).remove::<ArmyMember>();
+            }
+        }
+    }
+}
+
+fn update(
+    mut commands: Commands,
+    mut armies: ResMut<Armies>,
+    members: Query<(
+        Entity,
+        &Transform,
+        &ObjectTypeComponent,
+        &Health,
+        &ArmyMember,
+    )>,
+) {
+    for army in armies.armies.values_mut() {
+        army.clear();
+    }
+
+    for (entity, transform, &object_type, health, member) in members.iter() {
+        let ObjectType::Active(ActiveObjectType::Unit(unit_type)) = *object_type else {
+            continue;
+        };
+
+        match armies.get_mut(**member) {
+            Some(army) => army.add(
+                entity,
+                transform.translation.to_flat(),
+                unit_type,
+                health.fraction(),
+            ),
+            None => {
+                commands.entity(entity).remove::<ArmyMember>();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_armies() {
+        let mut armies = Armies::default();
+        let alpha = armies.create(Player::Player1, "Alpha");
+        let beta = armies.create(Player::Player2, "Beta");
+        assert_ne!(alpha, beta);
+        assert_eq!(armies.get(beta).unwrap().player(), Player::Player2);
+
+        let army = armies.get_mut(alpha).unwrap();
+        assert!(army.centroid().is_none());
+        assert!(army.health().is_none());
+
+        army.add(
+            Entity::from_raw(1),
+            Vec2::new(1., 2.),
+            UnitType::Attacker,
+            1.,
+        );
+        army.add(
+            Entity::from_raw(2),
+            Vec2::new(3., -2.),
+            UnitType::Attacker,
+            0.5,
+        );
+        army.add(Entity::from_raw(3), Vec2::new(2., 3.), UnitType::Scout, 0.);
+        assert_eq!(army.members().len(), 3);
+        assert_eq!(army.centroid(), Some(Vec2::new(2., 1.)));
+        assert_eq!(army.health(), Some(0.5));
+        assert_eq!(army.composition()[UnitType::Attacker], 2);
+        assert_eq!(army.composition()[UnitType::Scout], 1);
+        assert_eq!(army.composition()[UnitType::Harvester], 0);
+
+        army.clear();
+        assert!(army.is_empty());
+        assert_eq!(army.composition()[UnitType::Attacker], 0);
+
+        assert_eq!(armies.disband(alpha).unwrap().name(), "Alpha");
+        assert!(armies.get(alpha).is_none());
+        let names: Vec<&str> = armies.iter().map(|(_, army)| army.name()).collect();
+        assert_eq!(names, vec!["Beta"]);
+    }
+}
@@ -0,0 +1,291 @@
+//! A minimal behaviour tree implementation. Trees are composed of control
+//! nodes (sequences and selectors) and leaf nodes (conditions and actions).
+//! Actions typically send events of other subsystems, for example
+//! [`crate::ChaseTargetEvent`] or `de_combat::AttackEvent`.
+//!
+//! All trees are evaluated from the root on every frame. Control nodes
+//! remember their running child and resume from it, i.e. children preceding
+//! a running child are not re-evaluated until the running child finishes.
+
+use bevy::prelude::*;
+use de_core::gamestate::GameState;
+
+pub(crate) struct BehaviourTreePlugin;
+
+impl Plugin for BehaviourTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            tick.run_if(in_state(GameState::Playing))
+                .run_if(any_with_component::<BehaviourTree>)
+                .in_set(BehaviourTreeSet::Tick),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum BehaviourTreeSet {
+    Tick,
+}
+
+/// Result of a single evaluation of a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    /// The node has not finished yet and needs to be evaluated again during
+    /// the next frame.
+    Running,
+}
+
+type ConditionFn = dyn Fn(&World, Entity) -> bool + Send + Sync;
+type ActionFn = dyn FnMut(&mut World, Entity) -> Status + Send + Sync;
+
+/// A node of a behaviour tree.
+pub enum Node {
+    /// Evaluates children in order until one of them does not succeed. It
+    /// succeeds only if all children succeed.
+    Sequence {
+        children: Vec<Node>,
+        /// Index of the child to start the next evaluation from.
+        running: usize,
+    },
+    /// Evaluates children in order until one of them does not fail. It fails
+    /// only if all children fail.
+    Selector {
+        children: Vec<Node>,
+        /// Index of the child to start the next evaluation from.
+        running: usize,
+    },
+    Condition(Box<ConditionFn>),
+    Action(Box<ActionFn>),
+}
+
+impl Node {
+    pub fn sequence(children: Vec<Node>) -> Self {
+        Self::Sequence {
+            children,
+            running: 0,
+        }
+    }
+
+    pub fn selector(children: Vec<Node>) -> Self {
+        Self::Selector {
+            children,
+            running: 0,
+        }
+    }
+
+    /// Creates a leaf node which succeeds if the condition holds for the
+    /// entity and fails otherwise.
+    pub fn condition<F>(condition: F) -> Self
+    where
+        F: Fn(&World, Entity) -> bool + Send + Sync + 'static,
+    {
+        Self::Condition(Box::new(condition))
+    }
+
+    /// Creates a leaf node which succeeds if the entity has a component.
+    pub fn has<C: Component>() -> Self {
+        Self::condition(|world, entity| world.get::<C>(entity).is_some())
+    }
+
+    pub fn action<F>(action: F) -> Self
+    where
+        F: FnMut(&mut World, Entity) -> Status + Send + Sync + 'static,
+    {
+        Self::Action(Box::new(action))
+    }
+
+    /// Creates a leaf node which sends an event and succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - creates the event from the entity evaluating the tree.
+    pub fn send<E, F>(event: F) -> Self
+    where
+        E: Event,
+        F: Fn(Entity) -> E + Send + Sync + 'static,
+    {
+        Self::action(move |world, entity| {
+            world.send_event(event(entity));
+            Status::Success
+        })
+    }
+
+    fn tick(&mut self, world: &mut World, entity: Entity) -> Status {
+        match self {
+            Self::Sequence { children, running } => {
+                tick_children(children, running, world, entity, Status::Success)
+            }
+            Self::Selector { children, running } => {
+                tick_children(children, running, world, entity, Status::Failure)
+            }
+            Self::Condition(condition) => {
+                if condition(world, entity) {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            Self::Action(action) => action(world, entity),
+        }
+    }
+}
+
+/// Evaluates children starting with the running one until a child returns a
+/// status different from `proceed`. The index of a running child is stored
+/// so that the next evaluation resumes from it.
+fn tick_children(
+    children: &mut [Node],
+    running: &mut usize,
+    world: &mut World,
+    entity: Entity,
+    proceed: Status,
+) -> Status {
+    for (index, child) in children.iter_mut().enumerate().skip(*running) {
+        let status = child.tick(world, entity);
+        if status != proceed {
+            *running = if status == Status::Running { index } else { 0 };
+            return status;
+        }
+    }
+
+    *running = 0;
+    proceed
+}
+
+/// Entities with this component are driven by a behaviour tree.
+#[derive(Component)]
+pub struct BehaviourTree {
+    /// The root is None only while the tree is being evaluated.
+    root: Option<Node>,
+}
+
+impl BehaviourTree {
+    pub fn new(root: Node) -> Self {
+        Self { root: Some(root) }
+    }
+}
+
+fn tick(world: &mut World, trees: &mut QueryState<Entity, With<BehaviourTree>>) {
+    let entities: Vec<Entity> = trees.iter(world).collect();
+
+    for entity in entities {
+        // The tree is taken out of the world so that its nodes may access the
+        // world mutably.
+        let Some(mut root) = world
+            .get_mut::<BehaviourTree>(entity)
+            .and_then(|mut tree| tree.root.take())
+        else {
+            continue;
+        };
+
+        root.tick(world, entity);
+
+        if let Some(mut tree) = world.get_mut::<BehaviourTree>(entity) {
+            tree.root = Some(root);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct Armed;
+
+    #[derive(Component, Default)]
+    struct Counter(u32);
+
+    fn increment() -> Node {
+        Node::action(|world, entity| {
+            world.get_mut::<Counter>(entity).unwrap().0 += 1;
+            Status::Success
+        })
+    }
+
+    #[test]
+    fn test_tree() {
+        let mut world = World::new();
+        let armed = world.spawn((Armed, Counter::default())).id();
+        let unarmed = world.spawn(Counter::default()).id();
+
+        let mut root = Node::selector(vec![
+            Node::sequence(vec![Node::has::<Armed>(), increment(), increment()]),
+            Node::sequence(vec![
+                increment(),
+                Node::action(|_, _| Status::Running),
+                increment(),
+            ]),
+        ]);
+
+        assert_eq!(root.tick(&mut world, armed), Status::Success);
+        assert_eq!(world.get::<Counter>(armed).unwrap().0, 2);
+        assert_eq!(root.tick(&mut world, unarmed), Status::Running);
+        assert_eq!(world.get::<Counter>(unarmed).unwrap().0, 1);
+
+        let mut root = Node::selector(vec![Node::has::<Armed>(), increment()]);
+        assert_eq!(root.tick(&mut world, unarmed), Status::Success);
+        assert_eq!(world.get::<Counter>(unarmed).unwrap().0, 2);
+        let mut root = Node::sequence(vec![Node::has::<Armed>(), increment()]);
+        assert_eq!(root.tick(&mut world, unarmed), Status::Failure);
+        assert_eq!(world.get::<Counter>(unarmed).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_resume() {
+        let mut world = World::new();
+        let entity = world.spawn(Counter::default()).id();
+
+        let mut root = Node::sequence(vec![
+            increment(),
+            Node::action(|world, entity| {
+                if world.get::<Counter>(entity).unwrap().0 < 3 {
+                    world.get_mut::<Counter>(entity).unwrap().0 += 1;
+                    Status::Running
+                } else {
+                    Status::Success
+                }
+            }),
+            increment(),
+        ]);
+
+        assert_eq!(root.tick(&mut world, entity), Status::Running);
+        assert_eq!(world.get::<Counter>(entity).unwrap().0, 2);
+        // The first child is not re-evaluated while the second one runs.
+        assert_eq!(root.tick(&mut world, entity), Status::Running);
+        assert_eq!(world.get::<Counter>(entity).unwrap().0, 3);
+        assert_eq!(root.tick(&mut world, entity), Status::Success);
+        assert_eq!(world.get::<Counter>(entity).unwrap().0, 4);
+        // Finished sequence starts from the first child again.
+        assert_eq!(root.tick(&mut world, entity), Status::Success);
+        assert_eq!(world.get::<Counter>(entity).unwrap().0, 6);
+    }
+
+    #[test]
+    fn test_system() {
+        #[derive(Event)]
+        struct Ping(Entity);
+
+        let mut app = App::new();
+        app.add_event::<Ping>().add_systems(Update, tick);
+        let entity = app.world.spawn(BehaviourTree::new(Node::send(Ping))).id();
+
+        app.update();
+        let events = app.world.resource::<Events<Ping>>();
+        let pings: Vec<Entity> = events
+            .get_reader()
+            .read(events)
+            .map(|ping| ping.0)
+            .collect();
+        assert_eq!(pings, vec![entity]);
+        assert!(app
+            .world
+            .get::<BehaviourTree>(entity)
+            .unwrap()
+            .root
+            .is_some());
+    }
+}
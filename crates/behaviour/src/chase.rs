@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 use de_core::gamestate::GameState;
+use de_objects::ChaseDistances;
 use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
 use de_types::projection::ToFlat;
 
+use crate::{kiting::KeepDistance, stance::Leash};
+
 pub(crate) struct ChasePlugin;
 
 impl Plugin for ChasePlugin {
@@ -14,13 +17,19 @@ impl Plugin for ChasePlugin {
                     .run_if(in_state(GameState::Playing))
                     .in_set(ChaseSet::ChaseTargetEvent),
             )
-            .add_systems(Update, chase.run_if(in_state(GameState::Playing)));
+            .add_systems(
+                Update,
+                chase
+                    .run_if(in_state(GameState::Playing))
+                    .in_set(ChaseSet::Chase),
+            );
     }
 }
 
 #[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
 pub enum ChaseSet {
     ChaseTargetEvent,
+    Chase,
 }
 
 /// Send this event to start or stop chasing of an entity (movable or static).
@@ -53,7 +62,7 @@ impl ChaseTargetEvent {
 
 /// Units with this component will chase the target entity.
 #[derive(Component, Deref)]
-struct ChaseTargetComponent(ChaseTarget);
+pub(crate) struct ChaseTargetComponent(ChaseTarget);
 
 impl ChaseTargetComponent {
     fn new(target: ChaseTarget) -> Self {
@@ -64,8 +73,9 @@ impl ChaseTargetComponent {
 #[derive(Clone)]
 pub struct ChaseTarget {
     target: Entity,
-    min_distance: f32,
-    max_distance: f32,
+    distances: ChaseDistances,
+    leash: Option<Leash>,
+    keep_distance: Option<KeepDistance>,
 }
 
 impl ChaseTarget {
@@ -75,41 +85,47 @@ impl ChaseTarget {
     ///
     /// * `target` - entity to chase.
     ///
-    /// * `min_distance` - minimum distance between the chasing entity and the
-    ///   cased entity. Elevation is ignored during the distance calculation.
-    ///
-    /// * `max_distance` - maximum distance between the chasing entity and the
-    ///   cased entity. Elevation is ignored during the distance calculation.
-    ///
-    /// # Panics
-    ///
-    /// May panic if `min_distance` or `max_distance` is not non-negative
-    /// finite number or when `min_distance` is greater or equal to
-    /// `max_distance`.
-    pub fn new(target: Entity, min_distance: f32, max_distance: f32) -> Self {
-        debug_assert!(min_distance.is_finite());
-        debug_assert!(max_distance.is_finite());
-        debug_assert!(min_distance >= 0.);
-        debug_assert!(max_distance >= 0.);
-        debug_assert!(min_distance < max_distance);
-
+    /// * `distances` - distances kept between the chasing entity and the
+    ///   chased entity. These are usually derived from the weapon of the
+    ///   chasing entity.
+    pub fn new(target: Entity, distances: ChaseDistances) -> Self {
         Self {
             target,
-            min_distance,
-            max_distance,
+            distances,
+            leash: None,
+            keep_distance: None,
         }
     }
 
+    /// Limits the chase to a leash. The chase is stopped and the chasing
+    /// entity returns to the leash anchor once the target escapes the leash
+    /// circle.
+    pub fn with_leash(mut self, leash: Leash) -> Self {
+        self.leash = Some(leash);
+        self
+    }
+
+    /// Makes the chasing entity back away from the target once it gets
+    /// closer than the kept distance.
+    pub fn with_keep_distance(mut self, keep_distance: KeepDistance) -> Self {
+        self.keep_distance = Some(keep_distance);
+        self
+    }
+
     pub fn target(&self) -> Entity {
         self.target
     }
 
-    fn min_distance(&self) -> f32 {
-        self.min_distance
+    fn distances(&self) -> ChaseDistances {
+        self.distances
+    }
+
+    fn leash(&self) -> Option<&Leash> {
+        self.leash.as_ref()
     }
 
-    fn max_distance(&self) -> f32 {
-        self.max_distance
+    pub(crate) fn keep_distance(&self) -> Option<KeepDistance> {
+        self.keep_distance
     }
 }
 
@@ -143,11 +159,24 @@ fn chase(
             }
         };
 
+        if let Some(leash) = chase_target
+            .leash()
+            .filter(|leash| !leash.holds(target_position))
+        {
+            commands.entity(entity).remove::<ChaseTargetComponent>();
+            path_events.send(UpdateEntityPathEvent::new(
+                entity,
+                PathTarget::new(leash.anchor(), PathQueryProps::exact(), false),
+            ));
+            continue;
+        }
+
         let (path_target, distance) = path_target
             .map(|path_target| (path_target.location(), path_target.properties().distance()))
             .unwrap_or((transform.translation.to_flat(), 0.));
 
-        if (target_position - path_target).length() + distance <= chase_target.max_distance() {
+        let distances = chase_target.distances();
+        if (target_position - path_target).length() + distance <= distances.max_distance() {
             continue;
         }
 
@@ -155,7 +184,7 @@ fn chase(
             entity,
             PathTarget::new(
                 target_position,
-                PathQueryProps::new(chase_target.min_distance(), chase_target.max_distance()),
+                PathQueryProps::new(distances.min_distance(), distances.max_distance()),
                 true,
             ),
         ));
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use de_core::gamestate::GameState;
+use de_map::size::MapBounds;
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
+use de_types::projection::ToFlat;
+
+use crate::chase::{ChaseSet, ChaseTargetComponent};
+
+/// Distance (in meters) beyond the kept distance to which an entity retreats
+/// from a chased target.
+const RETREAT_DISTANCE: f32 = 10.;
+
+pub(crate) struct KitingPlugin;
+
+impl Plugin for KitingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            kite.run_if(in_state(GameState::Playing))
+                .after(ChaseSet::Chase),
+        );
+    }
+}
+
+/// Minimum distance kept by a chasing entity from the chased entity. Once the
+/// chased entity gets closer, the chasing entity backs away from it. This is
+/// useful for ranged units which can fire while moving.
+#[derive(Clone, Copy, Debug)]
+pub struct KeepDistance(f32);
+
+impl KeepDistance {
+    /// # Arguments
+    ///
+    /// * `distance` - minimum distance between the chasing entity and the
+    ///   chased entity. Elevation is ignored during the distance calculation.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `distance` is not a positive finite number.
+    pub fn new(distance: f32) -> Self {
+        debug_assert!(distance.is_finite());
+        debug_assert!(distance > 0.);
+        Self(distance)
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.0
+    }
+
+    /// Returns the position to which an entity retreats from a target, or
+    /// None if the target is far enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - current position of the retreating entity.
+    ///
+    /// * `target` - position of the chased target.
+    fn retreat(&self, position: Vec2, target: Vec2) -> Option<Vec2> {
+        if position.distance_squared(target) >= self.0.powi(2) {
+            return None;
+        }
+
+        let direction = (position - target).try_normalize().unwrap_or(Vec2::X);
+        Some(target + (self.0 + RETREAT_DISTANCE) * direction)
+    }
+}
+
+fn kite(
+    bounds: Res<MapBounds>,
+    mut path_events: EventWriter<UpdateEntityPathEvent>,
+    chasing: Query<(
+        Entity,
+        &Transform,
+        &ChaseTargetComponent,
+        Option<&PathTarget>,
+    )>,
+    targets: Query<&Transform>,
+) {
+    for (entity, transform, chase_target, path_target) in chasing.iter() {
+        let Some(keep_distance) = chase_target.keep_distance() else {
+            continue;
+        };
+        // Chase paths are permanent, the entity is already retreating
+        // otherwise.
+        if path_target.is_some_and(|path_target| !path_target.permanent()) {
+            continue;
+        }
+        let Ok(target) = targets.get(chase_target.target()) else {
+            continue;
+        };
+
+        let position = transform.translation.to_flat();
+        let Some(retreat) = keep_distance.retreat(position, target.translation.to_flat()) else {
+            continue;
+        };
+
+        path_events.send(UpdateEntityPathEvent::new(
+            entity,
+            PathTarget::new(
+                retreat.clamp(bounds.min(), bounds.max()),
+                PathQueryProps::new(0., RETREAT_DISTANCE),
+                false,
+            ),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retreat() {
+        let keep_distance = KeepDistance::new(20.);
+        assert!(keep_distance
+            .retreat(Vec2::new(20., 0.), Vec2::ZERO)
+            .is_none());
+        assert_eq!(
+            keep_distance.retreat(Vec2::new(0., -5.), Vec2::new(0., 5.)),
+            Some(Vec2::new(0., -25.))
+        );
+        assert_eq!(
+            keep_distance.retreat(Vec2::ONE, Vec2::ONE),
+            Some(Vec2::new(31., 1.))
+        );
+    }
+}
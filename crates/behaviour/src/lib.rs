@@ -1,15 +1,40 @@
 //! This crate implements various entity behavior systems.
 
+use army::ArmyPlugin;
+pub use army::{Armies, Army, ArmyId, ArmyMember, ArmySet, SetArmyEvent};
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+use btree::BehaviourTreePlugin;
+pub use btree::{BehaviourTree, BehaviourTreeSet, Node, Status};
 use chase::ChasePlugin;
 pub use chase::{ChaseSet, ChaseTarget, ChaseTargetEvent};
+pub use kiting::KeepDistance;
+use kiting::KitingPlugin;
+use patrol::PatrolPlugin;
+pub use patrol::{PatrolSet, PatrolWaypoints, SetPatrolEvent};
+use stance::StancePlugin;
+pub use stance::{Leash, SetStanceEvent, Stance, StanceSet};
+pub use wander::Wander;
+use wander::WanderPlugin;
 
+mod army;
+mod btree;
 mod chase;
+mod kiting;
+mod patrol;
+mod stance;
+mod wander;
 
 pub struct BehaviourPluginGroup;
 
 impl PluginGroup for BehaviourPluginGroup {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>().add(ChasePlugin)
+        PluginGroupBuilder::start::<Self>()
+            .add(ChasePlugin)
+            .add(KitingPlugin)
+            .add(PatrolPlugin)
+            .add(StancePlugin)
+            .add(BehaviourTreePlugin)
+            .add(ArmyPlugin)
+            .add(WanderPlugin)
     }
 }
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use de_core::gamestate::GameState;
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
+
+use crate::chase::ChaseTargetComponent;
+
+pub(crate) struct PatrolPlugin;
+
+impl Plugin for PatrolPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SetPatrolEvent>()
+            .add_systems(
+                PreUpdate,
+                handle_patrol_events
+                    .run_if(in_state(GameState::Playing))
+                    .in_set(PatrolSet::SetPatrolEvent),
+            )
+            .add_systems(Update, patrol.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum PatrolSet {
+    SetPatrolEvent,
+}
+
+/// Send this event to start or stop patrolling of a locally simulated movable
+/// entity.
+#[derive(Event)]
+pub struct SetPatrolEvent {
+    entity: Entity,
+    waypoints: Option<Vec<Vec2>>,
+}
+
+impl SetPatrolEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - the patrolling entity.
+    ///
+    /// * `waypoints` - positions to be visited in a loop or None if patrolling
+    ///   shall be stopped.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `waypoints` contains less than two positions.
+    pub fn new(entity: Entity, waypoints: Option<Vec<Vec2>>) -> Self {
+        debug_assert!(waypoints
+            .as_ref()
+            .map_or(true, |waypoints| waypoints.len() >= 2));
+        Self { entity, waypoints }
+    }
+
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    fn waypoints(&self) -> Option<&[Vec2]> {
+        self.waypoints.as_deref()
+    }
+}
+
+/// Entities with this component visit a list of positions in a loop
+/// indefinitely.
+///
+/// Patrolling is paused while the entity chases a target (e.g. an enemy
+/// engaged along the route) and resumed once the chase is over.
+#[derive(Component)]
+pub struct PatrolWaypoints {
+    waypoints: Vec<Vec2>,
+    current: usize,
+}
+
+impl PatrolWaypoints {
+    fn new(waypoints: Vec<Vec2>) -> Self {
+        Self {
+            waypoints,
+            current: 0,
+        }
+    }
+
+    /// Returns all positions of the patrol route.
+    pub fn waypoints(&self) -> &[Vec2] {
+        self.waypoints.as_slice()
+    }
+
+    /// Returns the position the entity is currently heading to.
+    pub fn current(&self) -> Vec2 {
+        self.waypoints[self.current]
+    }
+
+    /// Moves to the next position of the route, the first position follows
+    /// the last one.
+    fn advance(&mut self) {
+        self.current = (self.current + 1) % self.waypoints.len();
+    }
+
+    fn path_event(&self, entity: Entity) -> UpdateEntityPathEvent {
+        UpdateEntityPathEvent::new(
+            entity,
+            PathTarget::new(self.current(), PathQueryProps::exact(), false),
+        )
+    }
+}
+
+fn handle_patrol_events(
+    mut commands: Commands,
+    mut events: EventReader<SetPatrolEvent>,
+    mut path_events: EventWriter<UpdateEntityPathEvent>,
+) {
+    for event in events.read() {
+        let mut entity_commands = commands.entity(event.entity());
+        match event.waypoints() {
+            Some(waypoints) => {
+                let patrol = PatrolWaypoints::new(waypoints.to_vec());
+                path_events.send(patrol.path_event(event.entity()));
+                entity_commands.insert(patrol);
+            }
+            None => {
+                entity_commands.remove::<PatrolWaypoints>();
+            }
+        }
+    }
+}
+
+fn patrol(
+    mut removed_paths: RemovedComponents<PathTarget>,
+    mut removed_chases: RemovedComponents<ChaseTargetComponent>,
+    mut patrols: Query<&mut PatrolWaypoints, Without<ChaseTargetComponent>>,
+    mut path_events: EventWriter<UpdateEntityPathEvent>,
+) {
+    // Path target is removed once the current waypoint is reached (or found
+    // unreachable).
+    for entity in removed_paths.read() {
+        if let Ok(mut patrol) = patrols.get_mut(entity) {
+            patrol.advance();
+            path_events.send(patrol.path_event(entity));
+        }
+    }
+
+    // Chase is over, return to the route.
+    for entity in removed_chases.read() {
+        if let Ok(patrol) = patrols.get(entity) {
+            path_events.send(patrol.path_event(entity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waypoints() {
+        let mut patrol = PatrolWaypoints::new(vec![
+            Vec2::new(1., 2.),
+            Vec2::new(3., 4.),
+            Vec2::new(5., 6.),
+        ]);
+        assert_eq!(patrol.waypoints().len(), 3);
+        assert_eq!(patrol.current(), Vec2::new(1., 2.));
+        patrol.advance();
+        assert_eq!(patrol.current(), Vec2::new(3., 4.));
+        patrol.advance();
+        assert_eq!(patrol.current(), Vec2::new(5., 6.));
+        patrol.advance();
+        assert_eq!(patrol.current(), Vec2::new(1., 2.));
+    }
+}
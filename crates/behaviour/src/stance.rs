@@ -0,0 +1,183 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use de_core::gamestate::GameState;
+
+/// Distance (in meters) beyond its aggro range up to which a defensive entity
+/// pursues an automatically engaged enemy. The distance is measured from the
+/// position where the engagement started.
+const LEASH_DISTANCE: f32 = 30.;
+
+pub(crate) struct StancePlugin;
+
+impl Plugin for StancePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SetStanceEvent>().add_systems(
+            PreUpdate,
+            handle_stance_events
+                .run_if(in_state(GameState::Playing))
+                .in_set(StanceSet::SetStanceEvent),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum StanceSet {
+    SetStanceEvent,
+}
+
+/// Send this event to change stance of a locally simulated entity.
+#[derive(Event)]
+pub struct SetStanceEvent {
+    entity: Entity,
+    stance: Stance,
+}
+
+impl SetStanceEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - the entity whose stance is changed.
+    ///
+    /// * `stance` - the new stance.
+    pub fn new(entity: Entity, stance: Stance) -> Self {
+        Self { entity, stance }
+    }
+
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    fn stance(&self) -> Stance {
+        self.stance
+    }
+}
+
+/// Stance determines how an armed entity reacts to nearby enemies. Entities
+/// without this component behave as if they had the default stance.
+///
+/// Explicitly ordered attacks are not affected by the stance except that
+/// entities holding fire never fire.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Stance {
+    /// Enemies within aggro range are engaged and pursued indefinitely.
+    Aggressive,
+    /// Enemies within aggro range are engaged but pursued only up to a leash
+    /// distance from the position where the engagement started.
+    #[default]
+    Defensive,
+    /// Enemies are never engaged and no weapons are fired.
+    HoldFire,
+}
+
+impl Stance {
+    /// Returns the stance following this stance in a cycle of all stances.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Aggressive => Self::Defensive,
+            Self::Defensive => Self::HoldFire,
+            Self::HoldFire => Self::Aggressive,
+        }
+    }
+
+    /// Returns true if entities in this stance fire their weapons.
+    pub fn fires(self) -> bool {
+        self != Self::HoldFire
+    }
+
+    /// Returns true if entities in this stance automatically engage enemies
+    /// within aggro range.
+    pub fn engages(self) -> bool {
+        self != Self::HoldFire
+    }
+
+    /// Returns the leash of an automatic engagement or None if pursuit of the
+    /// enemy is unlimited.
+    ///
+    /// # Arguments
+    ///
+    /// * `anchor` - position of the engaging entity at the start of the
+    ///   engagement.
+    ///
+    /// * `aggro_range` - distance within which the entity engages enemies.
+    pub fn leash(self, anchor: Vec2, aggro_range: f32) -> Option<Leash> {
+        match self {
+            Self::Defensive => Some(Leash::new(anchor, aggro_range + LEASH_DISTANCE)),
+            Self::Aggressive | Self::HoldFire => None,
+        }
+    }
+}
+
+impl fmt::Display for Stance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Aggressive => write!(f, "Aggressive"),
+            Self::Defensive => write!(f, "Defensive"),
+            Self::HoldFire => write!(f, "Hold Fire"),
+        }
+    }
+}
+
+/// A circle out of which an entity does not pursue its target.
+#[derive(Clone, Copy, Debug)]
+pub struct Leash {
+    anchor: Vec2,
+    radius: f32,
+}
+
+impl Leash {
+    /// # Arguments
+    ///
+    /// * `anchor` - center of the leash circle. Entities return to it once
+    ///   their target escapes.
+    ///
+    /// * `radius` - radius of the leash circle.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `radius` is not a non-negative finite number.
+    pub fn new(anchor: Vec2, radius: f32) -> Self {
+        debug_assert!(radius.is_finite());
+        debug_assert!(radius >= 0.);
+        Self { anchor, radius }
+    }
+
+    pub fn anchor(&self) -> Vec2 {
+        self.anchor
+    }
+
+    /// Returns true if a position lies within the leash circle.
+    pub fn holds(&self, position: Vec2) -> bool {
+        self.anchor.distance_squared(position) <= self.radius.powi(2)
+    }
+}
+
+fn handle_stance_events(mut commands: Commands, mut events: EventReader<SetStanceEvent>) {
+    for event in events.read() {
+        if let Some(mut entity_commands) = commands.get_entity(event.entity()) {
+            entity_commands.insert(event.stance());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stance() {
+        let mut stance = Stance::default();
+        assert_eq!(stance, Stance::Defensive);
+        stance = stance.next();
+        assert_eq!(stance, Stance::HoldFire);
+        assert!(!stance.fires());
+        assert!(!stance.engages());
+        assert_eq!(stance.next(), Stance::Aggressive);
+        assert_eq!(stance.next().next(), Stance::Defensive);
+
+        assert!(Stance::Aggressive.leash(Vec2::ZERO, 10.).is_none());
+        let leash = Stance::Defensive.leash(Vec2::new(1., 2.), 10.).unwrap();
+        assert_eq!(leash.anchor(), Vec2::new(1., 2.));
+        assert!(leash.holds(Vec2::new(1., 12. + LEASH_DISTANCE)));
+        assert!(!leash.holds(Vec2::new(11. + LEASH_DISTANCE, 2.1)));
+    }
+}
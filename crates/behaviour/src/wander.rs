@@ -0,0 +1,121 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::prelude::*;
+use de_core::gamestate::GameState;
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
+use de_types::projection::ToFlat;
+
+use crate::chase::ChaseTargetComponent;
+
+/// Maximum distance (in meters) traveled by a wandering entity in a single
+/// move.
+const MAX_STEP: f32 = 15.;
+/// Minimum time a wandering entity idles between two moves.
+const MIN_IDLE: Duration = Duration::from_secs(3);
+/// Maximum time a wandering entity idles between two moves.
+const MAX_IDLE: Duration = Duration::from_secs(12);
+
+pub(crate) struct WanderPlugin;
+
+impl Plugin for WanderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, wander.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// Locally simulated movable entities with this component slowly and
+/// randomly roam inside a circular area. The entity makes short moves
+/// separated by random idle periods.
+///
+/// Wandering is paused while the entity moves to another target or chases a
+/// target.
+#[derive(Component)]
+pub struct Wander {
+    center: Vec2,
+    radius: f32,
+    idle: Duration,
+}
+
+impl Wander {
+    /// # Arguments
+    ///
+    /// * `center` - center of the area the entity wanders in.
+    ///
+    /// * `radius` - radius of the area the entity wanders in.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `radius` is not a positive finite number.
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        debug_assert!(radius.is_finite());
+        debug_assert!(radius > 0.);
+        Self {
+            center,
+            radius,
+            idle: MIN_IDLE,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns a random position inside the wander area at most
+    /// [`MAX_STEP`] away from `position`. Entities outside of the area
+    /// head back to it.
+    fn next_target(&self, rng: &fastrand::Rng, position: Vec2) -> Vec2 {
+        let offset = MAX_STEP * rng.f32().sqrt() * Vec2::from_angle(TAU * rng.f32());
+        let target = position + offset;
+        self.center + (target - self.center).clamp_length_max(self.radius)
+    }
+}
+
+/// Entities which neither move to a location nor chase anything.
+type IdleFilter = (Without<PathTarget>, Without<ChaseTargetComponent>);
+
+fn wander(
+    time: Res<Time>,
+    rng: Local<fastrand::Rng>,
+    mut wanderers: Query<(Entity, &Transform, &mut Wander), IdleFilter>,
+    mut path_events: EventWriter<UpdateEntityPathEvent>,
+) {
+    for (entity, transform, mut wander) in wanderers.iter_mut() {
+        wander.idle = wander.idle.saturating_sub(time.delta());
+        if !wander.idle.is_zero() {
+            continue;
+        }
+
+        wander.idle = MIN_IDLE + (MAX_IDLE - MIN_IDLE).mul_f32(rng.f32());
+        let target = wander.next_target(&rng, transform.translation.to_flat());
+        path_events.send(UpdateEntityPathEvent::new(
+            entity,
+            PathTarget::new(target, PathQueryProps::new(0., MAX_STEP), false),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_target() {
+        let rng = fastrand::Rng::with_seed(42);
+        let wander = Wander::new(Vec2::new(10., 20.), 30.);
+
+        for _ in 0..100 {
+            let position = Vec2::new(15., 5.);
+            let target = wander.next_target(&rng, position);
+            assert!(target.distance(wander.center()) <= 30. + 1e-4);
+            assert!(target.distance(position) <= MAX_STEP + 1e-4);
+        }
+
+        // Entities outside of the area head back to it.
+        let target = wander.next_target(&rng, Vec2::new(100., 20.));
+        assert!(target.distance(wander.center()) <= 30. + 1e-4);
+    }
+}
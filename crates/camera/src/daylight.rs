@@ -0,0 +1,144 @@
+//! This module implements day/night cycle of scene lighting. The cycle is
+//! active only for maps with [`DayNightCycle`] configured, lighting of other
+//! maps stays static.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use de_core::{gamestate::GameState, state::AppState};
+use de_map::meta::DayNightCycle;
+
+use crate::skybox::{SkyboxMaterial, DEFAULT_SKYBOX_BRIGHTNESS};
+
+/// Phase of the day (0 is sunrise, 0.25 noon, 0.5 sunset and 0.75 midnight)
+/// at the start of each game.
+const START_PHASE: f32 = 0.1;
+/// Tilt of the sun path (in radians) from the zenith, so that the sun never
+/// shines straight down.
+const SUN_PATH_TILT: f32 = 0.5;
+
+const DAY_ILLUMINANCE: f32 = 10_000.;
+const NIGHT_ILLUMINANCE: f32 = 600.;
+const DAY_AMBIENT_BRIGHTNESS: f32 = 180.;
+const NIGHT_AMBIENT_BRIGHTNESS: f32 = 40.;
+const DAY_SKYBOX_BRIGHTNESS: f32 = DEFAULT_SKYBOX_BRIGHTNESS;
+// The night cubemap itself is dark, thus the skybox brightness is reduced
+// only mildly so that the stars stay visible.
+const NIGHT_SKYBOX_BRIGHTNESS: f32 = 150.;
+
+const NOON_COLOR: Color = Color::WHITE;
+const HORIZON_COLOR: Color = Color::rgb(1., 0.62, 0.36);
+const NIGHT_COLOR: Color = Color::rgb(0.55, 0.65, 1.);
+
+pub(crate) struct DaylightPlugin;
+
+impl Plugin for DaylightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                update
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<DayNightCycle>),
+            );
+    }
+}
+
+/// Game time elapsed since the start of the day/night cycle.
+#[derive(Resource, Default)]
+struct DayTime(f32);
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<DayTime>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<DayTime>();
+}
+
+fn update(
+    time: Res<Time>,
+    cycle: Res<DayNightCycle>,
+    mut day_time: ResMut<DayTime>,
+    mut ambient: ResMut<AmbientLight>,
+    mut lights: Query<(&mut DirectionalLight, &mut Transform)>,
+    mut skybox_materials: ResMut<Assets<SkyboxMaterial>>,
+    skyboxes: Query<&Handle<SkyboxMaterial>>,
+) {
+    day_time.0 = (day_time.0 + time.delta_seconds()) % cycle.length();
+    let lighting = Lighting::at(START_PHASE + day_time.0 / cycle.length());
+
+    ambient.color = lighting.color;
+    ambient.brightness = lighting.ambient_brightness;
+
+    for (mut light, mut transform) in lights.iter_mut() {
+        light.color = lighting.color;
+        light.illuminance = lighting.illuminance;
+        transform.look_to(lighting.direction, Vec3::Y);
+    }
+
+    for handle in skyboxes.iter() {
+        let material = skybox_materials.get_mut(handle).unwrap();
+        material.night_factor = 1. - lighting.daylight;
+        material.brightness = lighting.skybox_brightness;
+    }
+}
+
+struct Lighting {
+    /// Direction of the light rays of the sun during the day or of the moon
+    /// during the night.
+    direction: Vec3,
+    color: Color,
+    illuminance: f32,
+    ambient_brightness: f32,
+    skybox_brightness: f32,
+    /// 1 during the day, 0 during the night.
+    daylight: f32,
+}
+
+impl Lighting {
+    /// Computes scene lighting at a phase of the day. The phase wraps around
+    /// 1.
+    fn at(phase: f32) -> Self {
+        let angle = TAU * phase.rem_euclid(1.);
+        let elevation = angle.sin();
+
+        let to_sun = Vec3::new(
+            angle.cos(),
+            elevation * SUN_PATH_TILT.cos(),
+            elevation * SUN_PATH_TILT.sin(),
+        );
+        // The moon is opposite to the sun.
+        let direction = if elevation >= 0. { -to_sun } else { to_sun };
+
+        let daylight = smoothstep(-0.1, 0.2, elevation);
+        let sun_color = lerp_color(HORIZON_COLOR, NOON_COLOR, smoothstep(0., 0.5, elevation));
+
+        Self {
+            direction,
+            color: lerp_color(NIGHT_COLOR, sun_color, daylight),
+            illuminance: lerp(NIGHT_ILLUMINANCE, DAY_ILLUMINANCE, daylight),
+            ambient_brightness: lerp(NIGHT_AMBIENT_BRIGHTNESS, DAY_AMBIENT_BRIGHTNESS, daylight),
+            skybox_brightness: lerp(NIGHT_SKYBOX_BRIGHTNESS, DAY_SKYBOX_BRIGHTNESS, daylight),
+            daylight,
+        }
+    }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0., 1.);
+    t * t * (3. - 2. * t)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    Color::rgb(
+        lerp(a.r(), b.r(), t),
+        lerp(a.g(), b.g(), t),
+        lerp(a.b(), b.b(), t),
+    )
+}
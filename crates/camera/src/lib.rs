@@ -4,11 +4,13 @@ pub use camera::{
     CameraFocus, CameraSet, MoveCameraHorizontallyEvent, MoveFocusEvent, RotateCameraEvent,
     TiltCameraEvent, ZoomCameraEvent,
 };
+use daylight::DaylightPlugin;
 use distance::DistancePlugin;
 pub use distance::{CameraDistance, DistanceSet};
 use skybox::SkyboxPlugin;
 
 mod camera;
+mod daylight;
 mod distance;
 mod skybox;
 
@@ -20,5 +22,6 @@ impl PluginGroup for CameraPluginGroup {
             .add(CameraPlugin)
             .add(DistancePlugin)
             .add(SkyboxPlugin)
+            .add(DaylightPlugin)
     }
 }
@@ -1,58 +1,121 @@
+//! This module implements the skybox. The sky is rendered as a large cube
+//! attached to the camera, which blends between a day and a night cubemap.
+//!
+//! The night cubemap is generated from the day cubemap during application
+//! loading.
+
 use bevy::{
     asset::LoadState,
-    core_pipeline::Skybox,
+    pbr::{MaterialPipeline, MaterialPipelineKey, NotShadowCaster, NotShadowReceiver},
     prelude::*,
-    render::render_resource::{TextureViewDescriptor, TextureViewDimension},
+    reflect::TypePath,
+    render::{
+        mesh::MeshVertexBufferLayout,
+        render_resource::{
+            AsBindGroup, RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError,
+            TextureFormat, TextureViewDescriptor, TextureViewDimension,
+        },
+        view::NoFrustumCulling,
+    },
 };
 use de_core::{gamestate::GameState, state::AppState};
 use iyes_progress::prelude::*;
 
+/// Edge length of the skybox cube. It is far larger than camera far plane so
+/// that everything rendered is in front of the sky.
+const SKYBOX_SIZE: f32 = 100_000.;
+/// Skybox brightness used when the lighting is static.
+pub(crate) const DEFAULT_SKYBOX_BRIGHTNESS: f32 = 300.;
+/// Night sky color is the day sky color multiplied by this (per channel).
+const NIGHT_TINT: [f32; 3] = [0.12, 0.15, 0.3];
+/// Fraction of night sky pixels which are stars.
+const STAR_DENSITY: f32 = 0.0005;
+const STAR_COLOR: [u8; 3] = [230, 235, 255];
+
 pub(crate) struct SkyboxPlugin;
 
 impl Plugin for SkyboxPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::AppLoading), load)
-            .add_systems(
-                Update,
-                (
-                    configure_cubemap
-                        .track_progress()
-                        .run_if(in_state(AppState::AppLoading)),
-                    setup_camera.run_if(in_state(GameState::Loading)),
-                ),
-            );
+        app.add_plugins(MaterialPlugin::<SkyboxMaterial> {
+            prepass_enabled: false,
+            ..default()
+        })
+        .add_systems(OnEnter(AppState::AppLoading), load)
+        .add_systems(
+            Update,
+            (
+                configure_cubemaps
+                    .track_progress()
+                    .run_if(in_state(AppState::AppLoading)),
+                setup_camera.run_if(in_state(GameState::Loading)),
+            ),
+        );
+    }
+}
+
+/// Material of the skybox cube.
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+pub(crate) struct SkyboxMaterial {
+    #[texture(0, dimension = "cube")]
+    #[sampler(1)]
+    day: Handle<Image>,
+    #[texture(2, dimension = "cube")]
+    #[sampler(3)]
+    night: Handle<Image>,
+    /// 0 displays the day cubemap only, 1 displays the night cubemap only.
+    #[uniform(4)]
+    pub(crate) night_factor: f32,
+    #[uniform(5)]
+    pub(crate) brightness: f32,
+}
+
+impl Material for SkyboxMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/skybox.wgsl".into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // The skybox is viewed from the inside.
+        descriptor.primitive.cull_mode = None;
+        Ok(())
     }
 }
 
 #[derive(Resource)]
 struct SkyboxSource {
-    handle: Handle<Image>,
-    configured: bool,
+    day: Handle<Image>,
+    /// This is set once the day cubemap is loaded and configured.
+    night: Option<Handle<Image>>,
 }
 
 fn load(mut commands: Commands, server: Res<AssetServer>) {
     commands.insert_resource(SkyboxSource {
-        handle: server.load("textures/skybox.png"),
-        configured: false,
+        day: server.load("textures/skybox.png"),
+        night: None,
     });
 }
 
-fn configure_cubemap(
+fn configure_cubemaps(
     server: Res<AssetServer>,
     mut source: ResMut<SkyboxSource>,
     mut images: ResMut<Assets<Image>>,
 ) -> Progress {
-    if source.configured {
+    if source.night.is_some() {
         return true.into();
     }
 
-    match server.get_load_state(&source.handle) {
+    match server.get_load_state(&source.day) {
         Some(LoadState::Loaded) => (),
         Some(LoadState::NotLoaded) | Some(LoadState::Loading) => return false.into(),
         _ => panic!("Unexpected loading state."),
     }
 
-    let image = images.get_mut(&source.handle).unwrap();
+    let image = images.get_mut(&source.day).unwrap();
     image.reinterpret_stacked_2d_as_array(
         image.texture_descriptor.size.height / image.texture_descriptor.size.width,
     );
@@ -61,21 +124,85 @@ fn configure_cubemap(
         ..default()
     });
 
-    source.configured = true;
+    let night = night_cubemap(image);
+    source.night = Some(images.add(night));
     true.into()
 }
 
+/// Generates a night sky cubemap from a day sky cubemap. The sky is darkened,
+/// tinted blue and sprinkled with stars.
+fn night_cubemap(day: &Image) -> Image {
+    assert_eq!(day.texture_descriptor.format, TextureFormat::Rgba8UnormSrgb);
+
+    let mut night = day.clone();
+    for (index, pixel) in night.data.chunks_exact_mut(4).enumerate() {
+        if is_star(index) {
+            pixel[..3].copy_from_slice(&STAR_COLOR);
+        } else {
+            for (channel, tint) in pixel.iter_mut().zip(NIGHT_TINT) {
+                *channel = (*channel as f32 * tint).round() as u8;
+            }
+        }
+    }
+    night
+}
+
+/// Returns true if a night sky pixel with a given index is a star. The stars
+/// are pseudo-randomly but deterministically scattered.
+fn is_star(index: usize) -> bool {
+    let mut hash = index as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7feb_352d);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x846c_a68b);
+    hash ^= hash >> 16;
+    (hash as f32 / u32::MAX as f32) < STAR_DENSITY
+}
+
 fn setup_camera(
     mut commands: Commands,
-    skybox: ResMut<SkyboxSource>,
-    camera_query: Query<(Entity, Has<Skybox>), With<Camera>>,
+    source: Res<SkyboxSource>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+    camera_query: Query<Entity, With<Camera3d>>,
+    skyboxes: Query<(), With<Handle<SkyboxMaterial>>>,
 ) {
-    let (entity, configured) = camera_query.single();
-    if configured {
+    if !skyboxes.is_empty() {
         return;
     }
-    commands.entity(entity).insert(Skybox {
-        image: skybox.handle.clone(),
-        brightness: 300.,
-    });
+
+    let material = SkyboxMaterial {
+        day: source.day.clone(),
+        night: source.night.clone().unwrap(),
+        night_factor: 0.,
+        brightness: DEFAULT_SKYBOX_BRIGHTNESS,
+    };
+
+    // The skybox is a child of the camera so that it moves with it and it
+    // is despawned together with the camera.
+    commands
+        .entity(camera_query.single())
+        .with_children(|parent| {
+            parent.spawn((
+                MaterialMeshBundle {
+                    mesh: meshes.add(Cuboid::from_size(Vec3::splat(SKYBOX_SIZE))),
+                    material: materials.add(material),
+                    ..default()
+                },
+                NoFrustumCulling,
+                NotShadowCaster,
+                NotShadowReceiver,
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_star() {
+        let stars = (0..1_000_000).filter(|&index| is_star(index)).count();
+        assert!((300..700).contains(&stars));
+    }
 }
@@ -0,0 +1,326 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_behaviour::{ChaseSet, ChaseTarget, ChaseTargetEvent, Stance};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{Anchored, Cloaked, Disabled, ObjectTypeComponent},
+    player::PlayerComponent,
+};
+use de_objects::{ArtilleryCannon, SolidObjects, Trajectory};
+use de_pathing::ScheduledPath;
+use de_types::projection::ToFlat;
+use de_uom::HitPoint;
+
+use crate::{
+    attack::{targetable, AttackEvent},
+    shell::{launch_velocity, LocalFireShellEvent},
+    veterancy::Veterancy,
+    AttackingSet,
+};
+
+pub(crate) struct ArtilleryPlugin;
+
+impl Plugin for ArtilleryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (
+                init,
+                attack
+                    .in_set(AttackingSet::Attack)
+                    .before(ChaseSet::ChaseTargetEvent),
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor)),
+        )
+        .add_systems(
+            Update,
+            (
+                deploy.in_set(AttackingSet::Charge),
+                fire.after(AttackingSet::Charge).before(AttackingSet::Fire),
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor)),
+        );
+    }
+}
+
+/// Disabled artillery is neither (un)deployed, reloaded nor fired.
+type DeployQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static ArtilleryCannon,
+        &'static mut Deployment,
+        Option<&'static Bombarding>,
+        Has<ScheduledPath>,
+    ),
+    Without<Disabled>,
+>;
+
+type FireQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static PlayerComponent,
+        &'static mut ArtilleryCannon,
+        &'static Deployment,
+        Option<&'static Bombarding>,
+        Option<&'static Stance>,
+        Option<&'static Veterancy>,
+    ),
+    Without<Disabled>,
+>;
+
+#[derive(Component)]
+struct Bombarding {
+    enemy: Entity,
+}
+
+/// Deployment state of an artillery. The artillery can fire only when
+/// deployed and move only when mobile.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+enum Deployment {
+    #[default]
+    Mobile,
+    /// Time since the deployment started is attached.
+    Deploying(Duration),
+    Deployed,
+    /// Time since the undeployment started is attached.
+    Undeploying(Duration),
+}
+
+impl Deployment {
+    /// Returns true if the carrier cannot move.
+    fn anchored(self) -> bool {
+        self != Self::Mobile
+    }
+
+    /// Updates the state.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - time elapsed since the last update.
+    ///
+    /// * `deploy_time` - time it takes to fully deploy or undeploy.
+    ///
+    /// * `target` - Some(true) if the carrier should deploy, Some(false) if
+    ///   it should undeploy and None if it should finish the current
+    ///   transition.
+    fn update(&mut self, delta: Duration, deploy_time: Duration, target: Option<bool>) {
+        *self = match (*self, target) {
+            (Self::Mobile, Some(true)) => Self::Deploying(Duration::ZERO),
+            (Self::Deployed, Some(false)) => Self::Undeploying(Duration::ZERO),
+            // Reversing a transition takes as long as the transition took so
+            // far.
+            (Self::Deploying(elapsed), Some(false)) => {
+                Self::Undeploying(deploy_time.saturating_sub(elapsed))
+            }
+            (Self::Undeploying(elapsed), Some(true)) => {
+                Self::Deploying(deploy_time.saturating_sub(elapsed))
+            }
+            (state, _) => state,
+        };
+
+        *self = match *self {
+            Self::Deploying(elapsed) if elapsed + delta >= deploy_time => Self::Deployed,
+            Self::Deploying(elapsed) => Self::Deploying(elapsed + delta),
+            Self::Undeploying(elapsed) if elapsed + delta >= deploy_time => Self::Mobile,
+            Self::Undeploying(elapsed) => Self::Undeploying(elapsed + delta),
+            state => state,
+        };
+    }
+}
+
+fn init(mut commands: Commands, new: Query<Entity, Added<ArtilleryCannon>>) {
+    for entity in new.iter() {
+        commands.entity(entity).insert(Deployment::default());
+    }
+}
+
+fn attack(
+    mut commands: Commands,
+    solids: SolidObjects,
+    mut attack_events: EventReader<AttackEvent>,
+    cannons: Query<&ArtilleryCannon>,
+    targets: Query<&ObjectTypeComponent>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+) {
+    for event in attack_events.read() {
+        if let Ok(cannon) = cannons.get(event.attacker()) {
+            let Ok(&target_type) = targets.get(event.enemy()) else {
+                continue;
+            };
+            if !targetable(&solids, cannon.targets(), *target_type) {
+                continue;
+            }
+
+            commands.entity(event.attacker()).insert(Bombarding {
+                enemy: event.enemy(),
+            });
+
+            let target = ChaseTarget::new(event.enemy(), cannon.chase_distances());
+            chase_events.send(ChaseTargetEvent::new(event.attacker(), Some(target)));
+        }
+    }
+}
+
+fn deploy(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut artillery: DeployQuery,
+    targets: Query<&Transform>,
+) {
+    for (entity, transform, cannon, mut deployment, bombarding, moving) in artillery.iter_mut() {
+        let in_range = bombarding
+            .and_then(|bombarding| targets.get(bombarding.enemy).ok())
+            .is_some_and(|target| {
+                let distance = (target.translation - transform.translation)
+                    .to_flat()
+                    .length();
+                distance >= cannon.min_range() && distance <= cannon.range()
+            });
+
+        // Deployed artillery stays deployed until it needs to move.
+        let target = if moving {
+            Some(false)
+        } else if in_range {
+            Some(true)
+        } else {
+            None
+        };
+
+        let anchored = deployment.anchored();
+        let mut new = *deployment;
+        new.update(time.delta(), cannon.deploy_time(), target);
+        if new == *deployment {
+            continue;
+        }
+        *deployment = new;
+
+        if anchored != new.anchored() {
+            let mut entity_commands = commands.entity(entity);
+            if new.anchored() {
+                entity_commands.insert(Anchored);
+            } else {
+                entity_commands.remove::<Anchored>();
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fire(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    solids: SolidObjects,
+    mut artillery: FireQuery,
+    targets: Query<(&Transform, &ObjectTypeComponent, Option<&Cloaked>)>,
+    mut events: EventWriter<LocalFireShellEvent>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+) {
+    for (attacker, transform, &player, mut cannon, &deployment, bombarding, stance, veterancy) in
+        artillery.iter_mut()
+    {
+        let rank = veterancy.map(Veterancy::rank).unwrap_or_default();
+        cannon
+            .reload_mut()
+            .tick(time.delta().mul_f32(rank.rate_of_fire()));
+
+        let Some(bombarding) = bombarding else {
+            continue;
+        };
+        let Ok((enemy_transform, &target_type, cloaked)) = targets.get(bombarding.enemy) else {
+            commands.entity(attacker).remove::<Bombarding>();
+            continue;
+        };
+        // Undetected cloaked targets are lost.
+        if cloaked.is_some_and(|cloaked| !cloaked.visible_to(*player)) {
+            commands.entity(attacker).remove::<Bombarding>();
+            chase_events.send(ChaseTargetEvent::new(attacker, None));
+            continue;
+        }
+        if deployment != Deployment::Deployed
+            || !cannon.reload().loaded()
+            || stance.is_some_and(|stance| !stance.fires())
+        {
+            continue;
+        }
+
+        let distance = (enemy_transform.translation - transform.translation)
+            .to_flat()
+            .length();
+        if distance < cannon.min_range() || distance > cannon.range() {
+            continue;
+        }
+
+        let muzzle = transform.transform_point(cannon.muzzle());
+        let enemy_aabb = solids.get(*target_type).collider().aabb();
+        let enemy_centroid = enemy_transform.translation + Vec3::from(enemy_aabb.center());
+        let velocity = match cannon.trajectory() {
+            Trajectory::Ballistic => launch_velocity(muzzle, enemy_centroid, cannon.muzzle_speed()),
+            Trajectory::Linear => (enemy_centroid - muzzle)
+                .try_normalize()
+                .map(|direction| cannon.muzzle_speed() * direction),
+        };
+        let Some(velocity) = velocity else {
+            continue;
+        };
+
+        events.send(LocalFireShellEvent::new(
+            attacker,
+            *player,
+            muzzle,
+            velocity,
+            cannon.trajectory(),
+            HitPoint::new(
+                cannon.damage() * config.starting(*player).handicap().damage() * rank.damage(),
+            ),
+        ));
+        cannon.reload_mut().fire();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deployment() {
+        let deploy_time = Duration::from_secs(3);
+        let second = Duration::from_secs(1);
+
+        let mut deployment = Deployment::default();
+        deployment.update(second, deploy_time, None);
+        assert_eq!(deployment, Deployment::Mobile);
+
+        deployment.update(second, deploy_time, Some(true));
+        assert_eq!(deployment, Deployment::Deploying(second));
+        deployment.update(second, deploy_time, None);
+        assert_eq!(deployment, Deployment::Deploying(2 * second));
+
+        // Reversal takes as long as the deployment took so far.
+        deployment.update(second, deploy_time, Some(false));
+        assert_eq!(deployment, Deployment::Undeploying(2 * second));
+        deployment.update(second, deploy_time, Some(true));
+        assert_eq!(deployment, Deployment::Deploying(2 * second));
+        deployment.update(second, deploy_time, None);
+        assert_eq!(deployment, Deployment::Deployed);
+        assert!(deployment.anchored());
+
+        deployment.update(second, deploy_time, Some(true));
+        assert_eq!(deployment, Deployment::Deployed);
+        deployment.update(2 * second, deploy_time, Some(false));
+        assert_eq!(deployment, Deployment::Undeploying(2 * second));
+        deployment.update(second, deploy_time, Some(false));
+        assert_eq!(deployment, Deployment::Mobile);
+        assert!(!deployment.anchored());
+    }
+}
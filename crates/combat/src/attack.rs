@@ -1,21 +1,23 @@
 use std::{cmp::Ordering, collections::BinaryHeap};
 
 use bevy::prelude::*;
-use de_behaviour::{ChaseSet, ChaseTarget, ChaseTargetEvent};
-use de_core::{gamestate::GameState, objects::ObjectTypeComponent};
-use de_index::SpatialQuery;
-use de_objects::{LaserCannon, SolidObjects};
+use de_behaviour::{
+    ChaseSet, ChaseTarget, ChaseTargetEvent, KeepDistance, Leash, PatrolWaypoints, Stance,
+};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{self, Active, Cloaked, Disabled, Garrisoned, ObjectTypeComponent, StaticSolid},
+    player::PlayerComponent,
+};
+use de_index::{CachedSpatialQuery, SpatialQuery};
+use de_objects::{LaserCannon, SolidObjects, TargetDomain};
+use de_pathing::PathTarget;
+use de_types::{objects::ObjectType, projection::ToFlat};
 use parry3d::query::Ray;
 
 use crate::laser::LaserFireEvent;
-use crate::{sightline::LineOfSight, AttackingSet};
-
-/// Multiple of cannon range. The attacking entities will try to stay as close
-/// or further from attacked targets.
-const MIN_CHASE_DISTNACE: f32 = 0.4;
-/// Multiple of cannon range. The attacking entities will try to stay as close
-/// or closer from attacked targets.
-const MAX_CHASE_DISTNACE: f32 = 0.9;
+use crate::{sightline::LineOfSight, turret::Aim, veterancy::Veterancy, AttackingSet};
 
 pub(crate) struct AttackPlugin;
 
@@ -30,17 +32,21 @@ impl Plugin for AttackPlugin {
                         .before(ChaseSet::ChaseTargetEvent),
                     update_positions.after(AttackingSet::Attack),
                 )
-                    .run_if(in_state(GameState::Playing)),
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
             )
             .add_systems(
                 Update,
                 (
+                    engage,
                     charge.in_set(AttackingSet::Charge),
                     aim_and_fire
                         .after(AttackingSet::Charge)
+                        .after(AttackingSet::Aim)
                         .before(AttackingSet::Fire),
                 )
-                    .run_if(in_state(GameState::Playing)),
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
             );
     }
 }
@@ -49,6 +55,7 @@ impl Plugin for AttackPlugin {
 pub struct AttackEvent {
     attacker: Entity,
     enemy: Entity,
+    leash: Option<Leash>,
 }
 
 impl AttackEvent {
@@ -59,38 +66,65 @@ impl AttackEvent {
     ///
     /// * `enemy` - an attacked entity. It may be non-locally simulated entity.
     pub fn new(attacker: Entity, enemy: Entity) -> Self {
-        Self { attacker, enemy }
+        Self {
+            attacker,
+            enemy,
+            leash: None,
+        }
+    }
+
+    /// Limits pursuit of the enemy to a leash. The attack is abandoned once
+    /// the enemy escapes the leash circle.
+    fn with_leash(mut self, leash: Option<Leash>) -> Self {
+        self.leash = leash;
+        self
     }
 
-    fn attacker(&self) -> Entity {
+    pub(crate) fn attacker(&self) -> Entity {
         self.attacker
     }
 
-    fn enemy(&self) -> Entity {
+    pub(crate) fn enemy(&self) -> Entity {
         self.enemy
     }
+
+    fn leash(&self) -> Option<Leash> {
+        self.leash
+    }
 }
 
 #[derive(Component)]
-struct Attacking {
+pub(crate) struct Attacking {
     enemy: Entity,
+    leash: Option<Leash>,
     muzzle: Vec3,
     target: Option<Vec3>,
 }
 
 impl Attacking {
-    fn new(enemy: Entity) -> Self {
+    fn new(enemy: Entity, leash: Option<Leash>) -> Self {
         Self {
             enemy,
+            leash,
             muzzle: Vec3::ZERO,
             target: None,
         }
     }
 
+    /// Returns true if the enemy at a given position is out of the leash.
+    fn escaped(&self, position: Vec2) -> bool {
+        self.leash.is_some_and(|leash| !leash.holds(position))
+    }
+
     fn distance(&self) -> Option<f32> {
         self.target.map(|target| target.distance(self.muzzle))
     }
 
+    /// Returns world-space direction from the muzzle to the aimed point.
+    pub(crate) fn direction(&self) -> Option<Vec3> {
+        self.ray().map(|ray| ray.dir.into())
+    }
+
     fn ray(&self) -> Option<Ray> {
         self.target.map(|target| {
             let direction = (target - self.muzzle).normalize();
@@ -99,23 +133,40 @@ impl Attacking {
     }
 }
 
+/// Returns true if a weapon restricted to a target domain can fire at an
+/// object of a given type.
+pub(crate) fn targetable(solids: &SolidObjects, domain: TargetDomain, target: ObjectType) -> bool {
+    domain.allows(solids.get(target).flight().is_some())
+}
+
 fn attack(
     mut commands: Commands,
+    solids: SolidObjects,
     mut attack_events: EventReader<AttackEvent>,
     cannons: Query<&LaserCannon>,
+    targets: Query<&ObjectTypeComponent>,
     mut chase_events: EventWriter<ChaseTargetEvent>,
 ) {
     for event in attack_events.read() {
         if let Ok(cannon) = cannons.get(event.attacker()) {
+            let Ok(&target_type) = targets.get(event.enemy()) else {
+                continue;
+            };
+            if !targetable(&solids, cannon.targets(), *target_type) {
+                continue;
+            }
+
             commands
                 .entity(event.attacker())
-                .insert(Attacking::new(event.enemy()));
+                .insert(Attacking::new(event.enemy(), event.leash()));
 
-            let target = ChaseTarget::new(
-                event.enemy(),
-                MIN_CHASE_DISTNACE * cannon.range(),
-                MAX_CHASE_DISTNACE * cannon.range(),
-            );
+            let mut target = ChaseTarget::new(event.enemy(), cannon.chase_distances());
+            if let Some(leash) = event.leash() {
+                target = target.with_leash(leash);
+            }
+            if let Some(distance) = cannon.keep_distance() {
+                target = target.with_keep_distance(KeepDistance::new(distance));
+            }
             chase_events.send(ChaseTargetEvent::new(event.attacker(), Some(target)));
         }
     }
@@ -124,13 +175,31 @@ fn attack(
 fn update_positions(
     mut commands: Commands,
     solids: SolidObjects,
-    mut cannons: Query<(Entity, &Transform, &LaserCannon, &mut Attacking)>,
-    targets: Query<(&Transform, &ObjectTypeComponent)>,
+    mut cannons: Query<(
+        Entity,
+        &Transform,
+        &PlayerComponent,
+        &LaserCannon,
+        &mut Attacking,
+    )>,
+    targets: Query<(&Transform, &ObjectTypeComponent, Option<&Cloaked>)>,
     sightline: SpatialQuery<Entity>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
 ) {
-    for (attacker, transform, cannon, mut attacking) in cannons.iter_mut() {
+    for (attacker, transform, &player, cannon, mut attacking) in cannons.iter_mut() {
         match targets.get(attacking.enemy) {
-            Ok((enemy_transform, &target_type)) => {
+            // Undetected cloaked targets are lost.
+            Ok((_, _, Some(cloaked))) if !cloaked.visible_to(*player) => {
+                commands.entity(attacker).remove::<Attacking>();
+                chase_events.send(ChaseTargetEvent::new(attacker, None));
+            }
+            // The chase is stopped by the chase subsystem itself.
+            Ok((enemy_transform, _, _))
+                if attacking.escaped(enemy_transform.translation.to_flat()) =>
+            {
+                commands.entity(attacker).remove::<Attacking>();
+            }
+            Ok((enemy_transform, &target_type, _)) => {
                 attacking.muzzle = transform.translation + cannon.muzzle();
 
                 let enemy_aabb = solids.get(*target_type).collider().aabb();
@@ -151,17 +220,152 @@ fn update_positions(
     }
 }
 
-fn charge(time: Res<Time>, mut cannons: Query<(&mut LaserCannon, Option<&Attacking>)>) {
-    for (mut cannon, attacking) in cannons.iter_mut() {
-        let charge = attacking
-            .and_then(|attacking| attacking.distance())
-            .map_or(false, |distance| distance <= cannon.range());
-        cannon.charge_mut().tick(time.delta(), charge);
+type EngageQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static PlayerComponent,
+        &'static LaserCannon,
+        Option<&'static Stance>,
+        Has<PathTarget>,
+        Has<PatrolWaypoints>,
+        Has<StaticSolid>,
+    ),
+    (
+        With<objects::Local>,
+        Without<Attacking>,
+        Without<Disabled>,
+        Without<Garrisoned>,
+    ),
+>;
+
+type EnemyData = (
+    Entity,
+    &'static Transform,
+    &'static PlayerComponent,
+    &'static ObjectTypeComponent,
+    Option<&'static Cloaked>,
+);
+type EnemyFilter = (With<Active>, Without<Garrisoned>);
+type EnemyItem<'a> = (
+    Entity,
+    &'a Transform,
+    &'a PlayerComponent,
+    &'a ObjectTypeComponent,
+    Option<&'a Cloaked>,
+);
+
+/// Idle and patrolling entities attack the closest visible enemy within their
+/// aggro range unless their stance forbids it. Units moving to a location do
+/// not get distracted. Enemies which the cannon cannot fire at are ignored.
+///
+/// Static objects (towers) repeat an identical scan every frame, therefore
+/// their scans are cached.
+fn engage(
+    config: Res<GameConfig>,
+    solids: SolidObjects,
+    attackers: EngageQuery,
+    enemies: SpatialQuery<EnemyData, EnemyFilter>,
+    mut cached_enemies: CachedSpatialQuery<EnemyData, EnemyFilter>,
+    mut attack_events: EventWriter<AttackEvent>,
+) {
+    for (attacker, transform, &player, cannon, stance, moving, patrolling, stationary) in
+        attackers.iter()
+    {
+        let stance = stance.copied().unwrap_or_default();
+        if !stance.engages() || (moving && !patrolling) {
+            continue;
+        }
+
+        let position = transform.translation.to_flat();
+        let radius = cannon.aggro_range();
+        let enemy = if stationary {
+            let candidates = cached_enemies.query_circle(position, radius, Some(attacker));
+            closest_enemy(candidates, &config, &solids, player, cannon, position)
+        } else {
+            let candidates = enemies.query_circle(position, radius, Some(attacker));
+            closest_enemy(candidates, &config, &solids, player, cannon, position)
+        };
+
+        if let Some(enemy) = enemy {
+            attack_events.send(
+                AttackEvent::new(attacker, enemy)
+                    .with_leash(stance.leash(position, cannon.aggro_range())),
+            );
+        }
     }
 }
 
+/// Returns the closest of the candidates which is a visible enemy targetable
+/// by the cannon.
+fn closest_enemy<'a>(
+    candidates: impl Iterator<Item = EnemyItem<'a>>,
+    config: &GameConfig,
+    solids: &SolidObjects,
+    player: PlayerComponent,
+    cannon: &LaserCannon,
+    position: Vec2,
+) -> Option<Entity> {
+    candidates
+        .filter(|&(_, _, owner, &enemy_type, cloaked)| {
+            !config.teams().allied(*player, **owner)
+                && cloaked.map_or(true, |cloaked| cloaked.visible_to(*player))
+                && targetable(solids, cannon.targets(), *enemy_type)
+        })
+        .map(|(enemy, enemy_transform, ..)| {
+            (
+                enemy,
+                position.distance(enemy_transform.translation.to_flat()),
+            )
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(enemy, _)| enemy)
+}
+
+type ChargeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut LaserCannon,
+        Option<&'static Attacking>,
+        Option<&'static Stance>,
+        Has<Disabled>,
+        Option<&'static Veterancy>,
+    ),
+>;
+
+fn charge(time: Res<Time>, mut cannons: ChargeQuery) {
+    for (mut cannon, attacking, stance, disabled, veterancy) in cannons.iter_mut() {
+        // Disabled cannons and cannons holding fire are not charged and thus
+        // gradually discharge.
+        let charge = !disabled
+            && stance.map_or(true, |stance| stance.fires())
+            && attacking
+                .and_then(|attacking| attacking.distance())
+                .map_or(false, |distance| distance <= cannon.range());
+        // Veterans charge (and discharge) faster.
+        let rate = veterancy.map_or(1., |veterancy| veterancy.rank().rate_of_fire());
+        cannon.charge_mut().tick(time.delta().mul_f32(rate), charge);
+    }
+}
+
+type FireQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut LaserCannon,
+        &'static Attacking,
+        Option<&'static Aim>,
+        Option<&'static Stance>,
+    ),
+    Without<Disabled>,
+>;
+
 fn aim_and_fire(
-    mut attackers: Query<(Entity, &mut LaserCannon, &Attacking)>,
+    mut attackers: FireQuery,
     sightline: LineOfSight,
     mut events: EventWriter<LaserFireEvent>,
 ) {
@@ -170,12 +374,17 @@ fn aim_and_fire(
     // done in real-time (unaffected by update frequency).
     let mut fire_queue = BinaryHeap::new();
 
-    for (attacker, mut cannon, attacking) in attackers {
+    for (attacker, mut cannon, attacking, aim, stance) in attackers {
+        // Articulated cannons need to be aimed at the target first.
+        let aimed = aim.is_none_or(|aim| aim.on_target());
+        let fires = stance.map_or(true, |stance| stance.fires());
         let ray = attacking.ray().filter(|ray| {
-            sightline
-                .sight(ray, cannon.range(), attacker)
-                .entity()
-                .map_or(false, |e| e == attacking.enemy)
+            aimed
+                && fires
+                && sightline
+                    .sight(ray, cannon.range(), attacker)
+                    .entity()
+                    .map_or(false, |e| e == attacking.enemy)
         });
 
         if let Some(ray) = ray {
@@ -214,7 +423,7 @@ impl<'a> FireScheduleItem<'a> {
             self.attacker,
             self.ray,
             self.cannon.range(),
-            self.cannon.damage(),
+            self.cannon.effect(),
         ));
         self.cannon.charge_mut().fire()
     }
@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{self, Cloaked, Disabled, Garrisoned},
+    player::PlayerComponent,
+};
+use de_energy::Battery;
+use de_messages::ToPlayers;
+use de_multiplayer::{NetEntities, NetRecvCloakEvent, ToPlayersEvent};
+use de_objects::{CloakingDevice, Detector, Radar};
+use de_signs::{StatusIcon, StatusIconsAppExt, UpdateStatusIconEvent};
+use de_types::player::{Player, PlayerRange};
+
+use crate::support::Powered;
+
+const CLOAKED_ICON: StatusIcon = StatusIcon::new("cloaked");
+
+pub(crate) struct CloakPlugin;
+
+impl Plugin for CloakPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_status_icon(CLOAKED_ICON)
+            .add_event::<ToggleCloakEvent>()
+            .add_event::<LocalCloakEvent>()
+            .add_event::<CloakEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle
+                        .run_if(on_event::<ToggleCloakEvent>())
+                        .before(CloakSet::Local),
+                    drain.before(CloakSet::Local),
+                    local_cloak
+                        .run_if(on_event::<LocalCloakEvent>())
+                        .in_set(CloakSet::Local)
+                        .before(CloakSet::Cloak),
+                    remote_cloak
+                        .run_if(on_event::<NetRecvCloakEvent>())
+                        .before(CloakSet::Cloak),
+                    cloak
+                        .run_if(on_event::<CloakEvent>())
+                        .in_set(CloakSet::Cloak),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            )
+            .add_systems(
+                PostUpdate,
+                ((detect, update_visibility).chain(), reveal_uncloaked)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+enum CloakSet {
+    Local,
+    Cloak,
+}
+
+/// Send this event to cloak an uncloaked (or uncloak a cloaked) locally
+/// simulated entity. The event is ignored for entities without a cloaking
+/// device.
+#[derive(Event)]
+pub struct ToggleCloakEvent(Entity);
+
+impl ToggleCloakEvent {
+    pub fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
+
+#[derive(Event)]
+struct LocalCloakEvent {
+    entity: Entity,
+    cloaked: bool,
+}
+
+impl LocalCloakEvent {
+    fn new(entity: Entity, cloaked: bool) -> Self {
+        Self { entity, cloaked }
+    }
+}
+
+#[derive(Event)]
+struct CloakEvent {
+    entity: Entity,
+    cloaked: bool,
+}
+
+impl CloakEvent {
+    fn new(entity: Entity, cloaked: bool) -> Self {
+        Self { entity, cloaked }
+    }
+}
+
+type ToggleFilter = (
+    With<CloakingDevice>,
+    With<objects::Local>,
+    Without<Disabled>,
+);
+
+fn toggle(
+    devices: Query<(Has<Cloaked>, &Battery), ToggleFilter>,
+    mut in_events: EventReader<ToggleCloakEvent>,
+    mut out_events: EventWriter<LocalCloakEvent>,
+) {
+    for event in in_events.read() {
+        let Ok((cloaked, battery)) = devices.get(event.0) else {
+            continue;
+        };
+
+        // An empty battery cannot power the cloaking device.
+        if cloaked || battery.energy() > 0. {
+            out_events.send(LocalCloakEvent::new(event.0, !cloaked));
+        }
+    }
+}
+
+type DrainQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static CloakingDevice,
+        &'static mut Battery,
+        Has<Disabled>,
+    ),
+    (With<Cloaked>, With<objects::Local>),
+>;
+
+/// Drains energy of cloaked entities and uncloaks those which cannot power
+/// their cloaking device anymore.
+fn drain(time: Res<Time>, mut devices: DrainQuery, mut events: EventWriter<LocalCloakEvent>) {
+    for (entity, device, mut battery, disabled) in devices.iter_mut() {
+        let energy = device.drain() * time.delta_seconds_f64();
+        if disabled || !battery.try_consume(energy) {
+            events.send(LocalCloakEvent::new(entity, false));
+        }
+    }
+}
+
+fn local_cloak(
+    config: Res<GameConfig>,
+    net_entities: NetEntities,
+    mut in_events: EventReader<LocalCloakEvent>,
+    mut out_events: EventWriter<CloakEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    for event in in_events.read() {
+        out_events.send(CloakEvent::new(event.entity, event.cloaked));
+
+        if config.multiplayer() {
+            net_events.send(ToPlayersEvent::new(ToPlayers::Cloak {
+                entity: net_entities.local_net_id(event.entity),
+                cloaked: event.cloaked,
+            }));
+        }
+    }
+}
+
+fn remote_cloak(
+    mut in_events: EventReader<NetRecvCloakEvent>,
+    mut out_events: EventWriter<CloakEvent>,
+) {
+    for event in in_events.read() {
+        out_events.send(CloakEvent::new(event.entity(), event.cloaked()));
+    }
+}
+
+fn cloak(
+    mut commands: Commands,
+    objects: Query<Has<Cloaked>, With<PlayerComponent>>,
+    mut events: EventReader<CloakEvent>,
+    mut icon_events: EventWriter<UpdateStatusIconEvent>,
+) {
+    for event in events.read() {
+        // The entity might have been despawned in the meantime.
+        let Ok(cloaked) = objects.get(event.entity) else {
+            continue;
+        };
+        if cloaked == event.cloaked {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(event.entity);
+        if event.cloaked {
+            entity_commands.insert(Cloaked::default());
+        } else {
+            entity_commands.remove::<Cloaked>();
+        }
+        icon_events.send(UpdateStatusIconEvent::new(
+            event.entity,
+            CLOAKED_ICON,
+            event.cloaked,
+        ));
+    }
+}
+
+/// Updates which players see each cloaked entity. An entity is visible to
+/// players who share vision with its owner or with an owner of a nearby
+/// detector or powered radar.
+fn detect(
+    config: Res<GameConfig>,
+    detectors: Query<(&Transform, &PlayerComponent, &Detector), Without<Disabled>>,
+    radars: Query<(&Transform, &PlayerComponent, &Radar), With<Powered>>,
+    mut cloaked: Query<(&Transform, &PlayerComponent, &mut Cloaked)>,
+) {
+    for (transform, &owner, mut cloaked) in cloaked.iter_mut() {
+        let detected_by: Vec<Player> = detectors
+            .iter()
+            .map(|(transform, &player, detector)| (transform, player, detector.range()))
+            .chain(
+                radars
+                    .iter()
+                    .map(|(transform, &player, radar)| (transform, player, radar.range())),
+            )
+            .filter(|(detector_transform, _, range)| {
+                detector_transform
+                    .translation
+                    .distance_squared(transform.translation)
+                    <= range.powi(2)
+            })
+            .map(|(_, player, _)| *player)
+            .collect();
+
+        for observer in PlayerRange::up_to(Player::Player4) {
+            let visible = config.shares_vision(observer, *owner)
+                || detected_by
+                    .iter()
+                    .any(|&detector| config.shares_vision(observer, detector));
+
+            // Do not unnecessarily trigger change detection.
+            if cloaked.visible_to(observer) != visible {
+                cloaked.set_visible_to(observer, visible);
+            }
+        }
+    }
+}
+
+/// Garrisoned entities stay hidden regardless of their cloak.
+fn update_visibility(
+    config: Res<GameConfig>,
+    mut cloaked: Query<(Ref<Cloaked>, &mut Visibility), Without<Garrisoned>>,
+) {
+    let playable = config.locals().playable();
+    for (cloaked, mut visibility) in cloaked.iter_mut() {
+        if !cloaked.is_changed() {
+            continue;
+        }
+
+        *visibility = if cloaked.visible_to(playable) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn reveal_uncloaked(
+    mut uncloaked: RemovedComponents<Cloaked>,
+    mut visibilities: Query<&mut Visibility, (Without<Cloaked>, Without<Garrisoned>)>,
+) {
+    for entity in uncloaked.read() {
+        if let Ok(mut visibility) = visibilities.get_mut(entity) {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use ahash::AHashSet;
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{self, Disabled, ObjectTypeComponent},
+    player::PlayerComponent,
+};
+use de_energy::Battery;
+use de_objects::{PointDefense, SolidObjects};
+use parry3d::query::Ray;
+
+use crate::{
+    shell::{LocalInterceptShellEvent, Shell, ShellSet},
+    trail::LocalLaserTrailEvent,
+    AttackingSet,
+};
+
+pub(crate) struct PointDefensePlugin;
+
+impl Plugin for PointDefensePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            intercept
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor))
+                .after(AttackingSet::Charge)
+                .before(ShellSet::Intercept),
+        );
+    }
+}
+
+type DefenderQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static PlayerComponent,
+        &'static ObjectTypeComponent,
+        &'static mut PointDefense,
+        &'static mut Battery,
+    ),
+    (With<objects::Local>, Without<Disabled>),
+>;
+
+/// Shoots down enemy shells flying close to locally simulated point defense
+/// modules.
+///
+/// Interceptions are decided solely by the game instance simulating the
+/// defender, other instances are notified via the shell subsystem.
+fn intercept(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    solids: SolidObjects,
+    mut defenders: DefenderQuery,
+    shells: Query<(Entity, &Transform, &Shell)>,
+    mut intercept_events: EventWriter<LocalInterceptShellEvent>,
+    mut trail_events: EventWriter<LocalLaserTrailEvent>,
+) {
+    // A shell can be shot down only once.
+    let mut intercepted: AHashSet<Entity> = AHashSet::new();
+
+    for (transform, &owner, &object_type, mut defense, mut battery) in defenders.iter_mut() {
+        defense.tick(time.delta());
+        if !defense.ready() {
+            continue;
+        }
+
+        let aabb = solids.get(*object_type).collider().aabb();
+        let muzzle = transform.translation + Vec3::from(aabb.center());
+        let range_squared = defense.range().powi(2);
+
+        let Some((shell, position)) = shells
+            .iter()
+            .filter(|(entity, _, shell)| {
+                !intercepted.contains(entity) && !config.teams().allied(*owner, shell.player())
+            })
+            .map(|(entity, shell_transform, _)| (entity, shell_transform.translation))
+            .filter(|&(_, position)| muzzle.distance_squared(position) <= range_squared)
+            .min_by(|(_, a), (_, b)| {
+                muzzle
+                    .distance_squared(*a)
+                    .total_cmp(&muzzle.distance_squared(*b))
+            })
+        else {
+            continue;
+        };
+
+        if !battery.try_consume(defense.energy()) {
+            continue;
+        }
+
+        defense.intercept();
+        intercepted.insert(shell);
+        intercept_events.send(LocalInterceptShellEvent::new(shell));
+        trail_events.send(LocalLaserTrailEvent::new(Ray::new(
+            muzzle.into(),
+            (position - muzzle).into(),
+        )));
+    }
+}
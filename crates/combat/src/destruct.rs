@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState,
+    gconfig::is_editor,
+    objects::{self, Active, ObjectTypeComponent},
+};
+use de_objects::{Health, SolidObjects};
+use de_types::projection::ToFlat;
+use de_uom::{HitPoint, Metre, Quantity};
+
+use crate::{
+    health::{HealthSet, LocalUpdateHealthEvent},
+    splash::{AoEDamageEvent, Falloff, SplashSet},
+};
+
+/// Maximum distance between footprints of a self-destructed object and an
+/// object damaged by the blast.
+const BLAST_RANGE: Metre = Quantity::new_unchecked(8.);
+/// Damage dealt by the blast to objects right next to the self-destructed
+/// object. The damage linearly decreases with distance.
+const BLAST_DAMAGE: HitPoint = Quantity::new_unchecked(6.);
+
+pub(crate) struct SelfDestructPlugin;
+
+impl Plugin for SelfDestructPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SelfDestructEvent>().add_systems(
+            Update,
+            self_destruct
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor))
+                .run_if(on_event::<SelfDestructEvent>())
+                .before(SplashSet::Damage)
+                .before(HealthSet::Update),
+        );
+    }
+}
+
+/// Send this event to destroy a locally simulated active object. The object
+/// explodes, damaging nearby objects, and is despawned as if it was destroyed
+/// in combat.
+#[derive(Event)]
+pub struct SelfDestructEvent(Entity);
+
+impl SelfDestructEvent {
+    pub fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
+
+type LocalActive = (With<Active>, With<objects::Local>);
+
+fn self_destruct(
+    solids: SolidObjects,
+    objects: Query<(&Transform, &ObjectTypeComponent, &Health), LocalActive>,
+    mut events: EventReader<SelfDestructEvent>,
+    mut health_events: EventWriter<LocalUpdateHealthEvent>,
+    mut aoe_events: EventWriter<AoEDamageEvent>,
+) {
+    for event in events.read() {
+        let Ok((transform, &object_type, health)) = objects.get(event.0) else {
+            continue;
+        };
+        if health.destroyed() {
+            continue;
+        }
+
+        health_events.send(LocalUpdateHealthEvent::new(event.0, -health.health()));
+
+        let radius = solids.get(*object_type).ichnography().radius();
+        aoe_events.send(
+            AoEDamageEvent::new(
+                transform.translation.to_flat(),
+                BLAST_RANGE,
+                BLAST_DAMAGE,
+                Falloff::Linear,
+            )
+            .with_source(event.0)
+            .with_core_radius(Metre::new(radius)),
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use ahash::AHashMap;
+use bevy::{
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+};
+use de_core::{
+    gconfig::GameConfig,
+    objects::{Active, Disabled, ObjectTypeComponent},
+    state::AppState,
+};
+use de_messages::ToPlayers;
+use de_multiplayer::{NetEntities, NetRecvDisableEvent, ToPlayersEvent};
+use de_objects::SolidObjects;
+use de_signs::{StatusIcon, StatusIconsAppExt, UpdateStatusIconEvent};
+
+const DISABLED_ICON: StatusIcon = StatusIcon::new("disabled");
+/// Size of the disabled effect relative to the size of the object.
+const EFFECT_SCALE: f32 = 1.3;
+
+pub(crate) struct DisablePlugin;
+
+impl Plugin for DisablePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_status_icon(DISABLED_ICON)
+            .add_event::<LocalDisableEvent>()
+            .add_event::<DisableEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    (
+                        local_disable
+                            .run_if(on_event::<LocalDisableEvent>())
+                            .before(disable),
+                        remote_disable
+                            .run_if(on_event::<NetRecvDisableEvent>())
+                            .before(disable),
+                        disable.run_if(on_event::<DisableEvent>()),
+                    )
+                        .in_set(DisableSet::Update),
+                    expire.after(DisableSet::Update),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub(crate) enum DisableSet {
+    Update,
+}
+
+/// Send this event to disable an entity as a result of actions of locally
+/// simulated entity.
+#[derive(Event)]
+pub(crate) struct LocalDisableEvent {
+    entity: Entity,
+    duration: Duration,
+}
+
+impl LocalDisableEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - the entity to disable. The event is ignored for non-active
+    ///   entities.
+    ///
+    /// * `duration` - how long the entity stays disabled.
+    pub(crate) fn new(entity: Entity, duration: Duration) -> Self {
+        Self { entity, duration }
+    }
+}
+
+/// Send this event to disable any active entity.
+#[derive(Event)]
+struct DisableEvent {
+    entity: Entity,
+    duration: Duration,
+}
+
+impl DisableEvent {
+    fn new(entity: Entity, duration: Duration) -> Self {
+        Self { entity, duration }
+    }
+}
+
+/// Disabled state of an object.
+#[derive(Component)]
+struct DisabledUntil {
+    /// Time (since app startup) when the object is enabled again.
+    time: Duration,
+    /// Entity of the visual effect attached to the object.
+    effect: Entity,
+}
+
+#[derive(Resource)]
+struct EffectAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(EffectAssets {
+        mesh: meshes.add(Sphere::new(1.).mesh().ico(3).unwrap()),
+        material: materials.add(StandardMaterial {
+            base_color: Color::rgba(0.4, 0.7, 1., 0.25),
+            emissive: Color::rgb(0.6, 1.2, 3.),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..Default::default()
+        }),
+    });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<EffectAssets>();
+}
+
+fn local_disable(
+    config: Res<GameConfig>,
+    net_entities: NetEntities,
+    active: Query<(), With<Active>>,
+    mut in_events: EventReader<LocalDisableEvent>,
+    mut out_events: EventWriter<DisableEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    for event in in_events.read() {
+        // Inactive objects, e.g. trees, have no systems to disable.
+        if !active.contains(event.entity) {
+            continue;
+        }
+
+        out_events.send(DisableEvent::new(event.entity, event.duration));
+
+        if config.multiplayer() {
+            net_events.send(ToPlayersEvent::new(ToPlayers::Disable {
+                entity: net_entities.net_id(event.entity),
+                duration: event.duration,
+            }));
+        }
+    }
+}
+
+fn remote_disable(
+    mut in_events: EventReader<NetRecvDisableEvent>,
+    mut out_events: EventWriter<DisableEvent>,
+) {
+    for event in in_events.read() {
+        out_events.send(DisableEvent::new(event.entity(), event.duration()));
+    }
+}
+
+fn disable(
+    mut commands: Commands,
+    time: Res<Time>,
+    assets: Res<EffectAssets>,
+    solids: SolidObjects,
+    mut objects: Query<(&ObjectTypeComponent, Option<&mut DisabledUntil>), With<Active>>,
+    mut events: EventReader<DisableEvent>,
+    mut icon_events: EventWriter<UpdateStatusIconEvent>,
+) {
+    // An entity might be hit multiple times during a single frame.
+    let mut ends: AHashMap<Entity, Duration> = AHashMap::new();
+    for event in events.read() {
+        let end = time.elapsed() + event.duration;
+        ends.entry(event.entity)
+            .and_modify(|time| *time = (*time).max(end))
+            .or_insert(end);
+    }
+
+    for (entity, end) in ends {
+        let Ok((&object_type, until)) = objects.get_mut(entity) else {
+            continue;
+        };
+
+        // Repeated hits prolong the disabled time but do not stack.
+        if let Some(mut until) = until {
+            until.time = until.time.max(end);
+            continue;
+        }
+
+        let aabb = solids.get(*object_type).collider().aabb();
+        let effect = commands
+            .spawn((
+                PbrBundle {
+                    mesh: assets.mesh.clone(),
+                    material: assets.material.clone(),
+                    transform: Transform::from_translation(Vec3::from(aabb.center()))
+                        .with_scale(EFFECT_SCALE * Vec3::from(aabb.half_extents())),
+                    ..default()
+                },
+                NotShadowCaster,
+                NotShadowReceiver,
+            ))
+            .id();
+
+        commands
+            .entity(entity)
+            .insert((Disabled, DisabledUntil { time: end, effect }))
+            .add_child(effect);
+        icon_events.send(UpdateStatusIconEvent::new(entity, DISABLED_ICON, true));
+    }
+}
+
+fn expire(
+    mut commands: Commands,
+    time: Res<Time>,
+    objects: Query<(Entity, &DisabledUntil)>,
+    mut icon_events: EventWriter<UpdateStatusIconEvent>,
+) {
+    for (entity, until) in objects.iter() {
+        if time.elapsed() < until.time {
+            continue;
+        }
+
+        commands.entity(until.effect).despawn_recursive();
+        commands
+            .entity(entity)
+            .remove::<(Disabled, DisabledUntil)>();
+        icon_events.send(UpdateStatusIconEvent::new(entity, DISABLED_ICON, false));
+    }
+}
@@ -0,0 +1,495 @@
+use std::f32::consts::TAU;
+
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_behaviour::{ChaseTarget, ChaseTargetEvent};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{self, Cloaked, Garrisoned, MovableSolid, ObjectTypeComponent},
+    player::PlayerComponent,
+    schedule::PostMovement,
+};
+use de_index::SpatialQuery;
+use de_messages::ToPlayers;
+use de_multiplayer::{NetEntities, NetRecvGarrisonEvent, ToPlayersEvent};
+use de_objects::{ChaseDistances, Garrison, SolidObjects};
+use de_signs::UpdateProgressBarEvent;
+use de_types::projection::ToFlat;
+use de_uom::{Metre, Quantity};
+use parry3d::{
+    bounding_volume::{Aabb, BoundingVolume},
+    math::Isometry,
+};
+
+/// Units board a carrier once they are at most this far from its footprint.
+const BOARDING_DISTANCE: Metre = Quantity::new_unchecked(2.);
+/// Distance between the footprints of a carrier and units leaving it.
+const EXIT_DISTANCE: Metre = Quantity::new_unchecked(1.);
+/// Number of evenly spread directions tried when searching for an
+/// unobstructed exit position around a carrier.
+const EXIT_DIRECTIONS: usize = 12;
+/// Number of rings (each [`EXIT_DISTANCE`] further from the carrier) tried
+/// when searching for an unobstructed exit position.
+const EXIT_RINGS: usize = 3;
+
+pub(crate) struct GarrisonPlugin;
+
+impl Plugin for GarrisonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GarrisonEvent>()
+            .add_event::<UngarrisonEvent>()
+            .add_event::<LocalGarrisonEvent>()
+            .add_event::<GarrisonChangeEvent>()
+            .add_systems(
+                Update,
+                (
+                    approach
+                        .run_if(on_event::<GarrisonEvent>())
+                        .before(GarrisonSet::Local),
+                    board.before(GarrisonSet::Local),
+                    unload
+                        .run_if(on_event::<UngarrisonEvent>())
+                        .before(GarrisonSet::Local),
+                    local_change
+                        .run_if(on_event::<LocalGarrisonEvent>())
+                        .in_set(GarrisonSet::Local)
+                        .before(GarrisonSet::Change),
+                    remote_change
+                        .run_if(on_event::<NetRecvGarrisonEvent>())
+                        .before(GarrisonSet::Change),
+                    eject_orphans.before(GarrisonSet::Change),
+                    prune_occupants.before(GarrisonSet::Change),
+                    change
+                        .run_if(on_event::<GarrisonChangeEvent>())
+                        .in_set(GarrisonSet::Change),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            )
+            .add_systems(
+                PostMovement,
+                follow
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+enum GarrisonSet {
+    Local,
+    Change,
+}
+
+/// Send this event to garrison a locally simulated unit in a carrier, for
+/// example in a transport or a bunker. The unit moves to the carrier and
+/// boards it once close enough.
+///
+/// The event is ignored if the unit does not fit into the carrier or if the
+/// owner of the unit does not control the carrier.
+#[derive(Event)]
+pub struct GarrisonEvent {
+    unit: Entity,
+    carrier: Option<Entity>,
+}
+
+impl GarrisonEvent {
+    /// # Arguments
+    ///
+    /// * `unit` - the unit to be garrisoned.
+    ///
+    /// * `carrier` - the carrier to board. None means that the unit stops
+    ///   boarding any carrier.
+    pub fn new(unit: Entity, carrier: Option<Entity>) -> Self {
+        Self { unit, carrier }
+    }
+}
+
+/// Send this event to unload all locally simulated units garrisoned in a
+/// carrier.
+#[derive(Event)]
+pub struct UngarrisonEvent(Entity);
+
+impl UngarrisonEvent {
+    pub fn new(carrier: Entity) -> Self {
+        Self(carrier)
+    }
+}
+
+#[derive(Event)]
+struct LocalGarrisonEvent {
+    unit: Entity,
+    carrier: Option<Entity>,
+}
+
+impl LocalGarrisonEvent {
+    fn new(unit: Entity, carrier: Option<Entity>) -> Self {
+        Self { unit, carrier }
+    }
+}
+
+#[derive(Event)]
+struct GarrisonChangeEvent {
+    unit: Entity,
+    carrier: Option<Entity>,
+}
+
+impl GarrisonChangeEvent {
+    fn new(unit: Entity, carrier: Option<Entity>) -> Self {
+        Self { unit, carrier }
+    }
+}
+
+/// A unit moving towards a carrier to board it.
+#[derive(Component)]
+struct Boarding(Entity);
+
+type UnitFilter = (
+    With<objects::Local>,
+    With<MovableSolid>,
+    Without<Garrison>,
+    Without<Garrisoned>,
+);
+
+fn approach(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    solids: SolidObjects,
+    units: Query<(&PlayerComponent, &ObjectTypeComponent), UnitFilter>,
+    carriers: Query<(&PlayerComponent, &ObjectTypeComponent, &Garrison)>,
+    mut events: EventReader<GarrisonEvent>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+) {
+    for event in events.read() {
+        let Ok((&unit_owner, &unit_type)) = units.get(event.unit) else {
+            continue;
+        };
+        let Some(carrier) = event.carrier else {
+            commands.entity(event.unit).remove::<Boarding>();
+            continue;
+        };
+        let Ok((&carrier_owner, &carrier_type, garrison)) = carriers.get(carrier) else {
+            continue;
+        };
+        if !config.controls(*unit_owner, *carrier_owner) {
+            continue;
+        }
+
+        let unit_solid = solids.get(*unit_type);
+        // Only small ground units fit into garrisons.
+        if unit_solid.flight().is_some()
+            || unit_solid.ichnography().radius() > garrison.max_radius()
+        {
+            continue;
+        }
+
+        let radius = solids.get(*carrier_type).ichnography().radius();
+        commands.entity(event.unit).insert(Boarding(carrier));
+        chase_events.send(ChaseTargetEvent::new(
+            event.unit,
+            Some(ChaseTarget::new(
+                carrier,
+                ChaseDistances::new(0., radius + BOARDING_DISTANCE.inner()),
+            )),
+        ));
+    }
+}
+
+fn board(
+    mut commands: Commands,
+    solids: SolidObjects,
+    units: Query<(Entity, &Transform, &ObjectTypeComponent, &Boarding), UnitFilter>,
+    carriers: Query<(&Transform, &ObjectTypeComponent, &Garrison)>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+    mut events: EventWriter<LocalGarrisonEvent>,
+) {
+    // Units boarding during this system run are not yet in the garrisons.
+    let mut boarding: AHashMap<Entity, usize> = AHashMap::new();
+
+    for (unit, transform, &unit_type, &Boarding(carrier)) in units.iter() {
+        let Ok((carrier_transform, &carrier_type, garrison)) = carriers.get(carrier) else {
+            commands.entity(unit).remove::<Boarding>();
+            continue;
+        };
+
+        let distance = transform
+            .translation
+            .to_flat()
+            .distance(carrier_transform.translation.to_flat());
+        let max_distance = solids.get(*carrier_type).ichnography().radius()
+            + solids.get(*unit_type).ichnography().radius()
+            + BOARDING_DISTANCE.inner();
+        if distance > max_distance {
+            continue;
+        }
+
+        commands.entity(unit).remove::<Boarding>();
+        chase_events.send(ChaseTargetEvent::new(unit, None));
+
+        let pending = boarding.entry(carrier).or_default();
+        if garrison.occupants().len() + *pending < usize::from(garrison.capacity()) {
+            *pending += 1;
+            events.send(LocalGarrisonEvent::new(unit, Some(carrier)));
+        }
+    }
+}
+
+fn unload(
+    carriers: Query<&Garrison>,
+    locals: Query<(), With<objects::Local>>,
+    mut in_events: EventReader<UngarrisonEvent>,
+    mut out_events: EventWriter<LocalGarrisonEvent>,
+) {
+    for event in in_events.read() {
+        let Ok(garrison) = carriers.get(event.0) else {
+            continue;
+        };
+
+        for &unit in garrison.occupants() {
+            if locals.contains(unit) {
+                out_events.send(LocalGarrisonEvent::new(unit, None));
+            }
+        }
+    }
+}
+
+fn local_change(
+    config: Res<GameConfig>,
+    net_entities: NetEntities,
+    mut in_events: EventReader<LocalGarrisonEvent>,
+    mut out_events: EventWriter<GarrisonChangeEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    for event in in_events.read() {
+        out_events.send(GarrisonChangeEvent::new(event.unit, event.carrier));
+
+        if config.multiplayer() {
+            net_events.send(ToPlayersEvent::new(ToPlayers::Garrison {
+                entity: net_entities.local_net_id(event.unit),
+                carrier: event.carrier.map(|carrier| net_entities.net_id(carrier)),
+            }));
+        }
+    }
+}
+
+fn remote_change(
+    mut in_events: EventReader<NetRecvGarrisonEvent>,
+    mut out_events: EventWriter<GarrisonChangeEvent>,
+) {
+    for event in in_events.read() {
+        out_events.send(GarrisonChangeEvent::new(event.entity(), event.carrier()));
+    }
+}
+
+/// Units garrisoned in a destroyed carrier are released by all game
+/// instances.
+fn eject_orphans(
+    units: Query<(Entity, &Garrisoned)>,
+    carriers: Query<(), With<Garrison>>,
+    mut events: EventWriter<GarrisonChangeEvent>,
+) {
+    for (unit, garrisoned) in units.iter() {
+        if !carriers.contains(garrisoned.carrier()) {
+            events.send(GarrisonChangeEvent::new(unit, None));
+        }
+    }
+}
+
+/// Despawned units (for example units self-destructed while garrisoned) are
+/// removed from garrisons of their carriers.
+fn prune_occupants(
+    mut removed: RemovedComponents<Garrisoned>,
+    entities: Query<()>,
+    mut carriers: Query<(Entity, &mut Garrison)>,
+    mut bar_events: EventWriter<UpdateProgressBarEvent>,
+) {
+    if removed.read().count() == 0 {
+        return;
+    }
+
+    for (carrier, mut garrison) in carriers.iter_mut() {
+        if garrison
+            .occupants()
+            .iter()
+            .all(|&unit| entities.contains(unit))
+        {
+            continue;
+        }
+
+        garrison.retain(|unit| entities.contains(unit));
+        bar_events.send(UpdateProgressBarEvent::new(
+            carrier,
+            (!garrison.occupants().is_empty()).then(|| garrison.occupancy()),
+        ));
+    }
+}
+
+type ChangeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ObjectTypeComponent,
+        &'static mut Transform,
+        Option<&'static Garrisoned>,
+        Option<&'static mut Cloaked>,
+    ),
+    Without<Garrison>,
+>;
+
+fn change(
+    mut commands: Commands,
+    solids: SolidObjects,
+    space: SpatialQuery<Entity>,
+    mut units: ChangeQuery,
+    mut carriers: Query<(&Transform, &ObjectTypeComponent, &mut Garrison)>,
+    mut events: EventReader<GarrisonChangeEvent>,
+    mut bar_events: EventWriter<UpdateProgressBarEvent>,
+) {
+    // Units leaving during this system run are not yet in the spatial index.
+    let mut exits: Vec<Aabb> = Vec::new();
+
+    for event in events.read() {
+        let Ok((&unit_type, mut transform, garrisoned, cloaked)) = units.get_mut(event.unit) else {
+            continue;
+        };
+
+        match (event.carrier, garrisoned) {
+            (Some(carrier), None) => {
+                let Ok((_, _, mut garrison)) = carriers.get_mut(carrier) else {
+                    continue;
+                };
+                if !garrison.load(event.unit) {
+                    warn!("Cannot garrison {:?}, {carrier:?} is full.", event.unit);
+                    continue;
+                }
+
+                commands
+                    .entity(event.unit)
+                    .insert((Garrisoned::new(carrier), Visibility::Hidden));
+                bar_events.send(UpdateProgressBarEvent::new(
+                    carrier,
+                    Some(garrison.occupancy()),
+                ));
+            }
+            (None, Some(garrisoned)) => {
+                let carrier = garrisoned.carrier();
+                if let Ok((carrier_transform, &carrier_type, mut garrison)) =
+                    carriers.get_mut(carrier)
+                {
+                    garrison.unload(event.unit);
+                    bar_events.send(UpdateProgressBarEvent::new(
+                        carrier,
+                        (!garrison.occupants().is_empty()).then(|| garrison.occupancy()),
+                    ));
+
+                    // Leaving units are spread around the carrier.
+                    let unit_solid = solids.get(*unit_type);
+                    let exit = ExitSearch {
+                        carrier,
+                        center: carrier_transform.translation,
+                        distance: solids.get(*carrier_type).ichnography().radius()
+                            + unit_solid.ichnography().radius()
+                            + EXIT_DISTANCE.inner(),
+                        start_angle: TAU * garrison.occupancy(),
+                        unit_aabb: unit_solid.collider().aabb(),
+                    };
+                    let (translation, aabb) = exit.find(&space, &exits);
+                    exits.push(aabb);
+                    transform.translation = translation;
+                } else {
+                    // Units are lifted to the top of the carrier while
+                    // garrisoned. Ground units are placed back to the (flat)
+                    // terrain.
+                    transform.translation.y = 0.;
+                }
+
+                let mut entity_commands = commands.entity(event.unit);
+                entity_commands.remove::<Garrisoned>();
+                match cloaked {
+                    // Visibility of cloaked units is managed by the cloaking
+                    // systems.
+                    Some(mut cloaked) => cloaked.set_changed(),
+                    None => {
+                        entity_commands.insert(Visibility::Inherited);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Search for a position around a carrier where a leaving unit is not
+/// obstructed by other objects.
+struct ExitSearch {
+    carrier: Entity,
+    center: Vec3,
+    /// Distance of the nearest candidate positions from the carrier.
+    distance: f32,
+    start_angle: f32,
+    /// Local-space AABB of the leaving unit.
+    unit_aabb: Aabb,
+}
+
+impl ExitSearch {
+    /// Returns the first unobstructed candidate position together with the
+    /// world-space AABB of the unit placed there. The nearest candidate is
+    /// returned if all candidates are obstructed.
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - spatial index of the obstructing objects.
+    ///
+    /// * `taken` - AABBs of units which already left a carrier but are not yet
+    ///   in the spatial index.
+    fn find(&self, space: &SpatialQuery<Entity>, taken: &[Aabb]) -> (Vec3, Aabb) {
+        let first = self.candidate(0, 0);
+
+        for ring in 0..EXIT_RINGS {
+            for direction in 0..EXIT_DIRECTIONS {
+                let (translation, aabb) = self.candidate(ring, direction);
+                let obstructed = taken.iter().any(|other| other.intersects(&aabb))
+                    || space.query_aabb(&aabb, Some(self.carrier)).next().is_some();
+                if !obstructed {
+                    return (translation, aabb);
+                }
+            }
+        }
+
+        first
+    }
+
+    fn candidate(&self, ring: usize, direction: usize) -> (Vec3, Aabb) {
+        let angle = self.start_angle + TAU * direction as f32 / EXIT_DIRECTIONS as f32;
+        let distance = self.distance + ring as f32 * EXIT_DISTANCE.inner();
+        let translation = self.center + distance * Vec3::new(angle.cos(), 0., angle.sin());
+
+        let isometry = Isometry::translation(translation.x, translation.y, translation.z);
+        let mut aabb = self.unit_aabb.transform_by(&isometry);
+        aabb.mins.y = f32::NEG_INFINITY;
+        aabb.maxs.y = f32::INFINITY;
+        (translation, aabb)
+    }
+}
+
+/// Garrisoned units are kept on the top of their carriers so that they can
+/// fire from there.
+fn follow(
+    solids: SolidObjects,
+    mut units: Query<(&Garrisoned, &mut Transform), Without<Garrison>>,
+    carriers: Query<(&Transform, &ObjectTypeComponent), With<Garrison>>,
+) {
+    for (garrisoned, mut transform) in units.iter_mut() {
+        let Ok((carrier_transform, &carrier_type)) = carriers.get(garrisoned.carrier()) else {
+            continue;
+        };
+
+        let height = solids.get(*carrier_type).collider().aabb().maxs.y;
+        let translation = carrier_transform.translation + height * Vec3::Y;
+        // Do not unnecessarily trigger change detection.
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+    }
+}
@@ -1,10 +1,30 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
-use de_core::{gconfig::GameConfig, objects::Local, state::AppState};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    notification::{GameNotification, NotifyEvent},
+    objects::{self, Active, Disabled, ObjectTypeComponent},
+    player::PlayerComponent,
+    state::AppState,
+};
+use de_energy::Battery;
 use de_messages::ToPlayers;
 use de_multiplayer::{NetEntities, NetRecvHealthEvent, ToPlayersEvent};
-use de_objects::Health;
-use de_signs::UpdateBarValueEvent;
-use de_spawner::{DespawnActiveLocalEvent, DespawnerSet};
+use de_objects::{
+    damage_multiplier, ArmorClass, DamageType, Health, Regeneration, ShieldGenerator, SolidObjects,
+};
+use de_signs::{BarId, UpdateBarValueEvent};
+use de_spawner::{DespawnActiveLocalEvent, DespawnInactiveEvent, DespawnerSet};
+use de_types::projection::ToFlat;
+use de_uom::HitPoint;
+
+use crate::veterancy::KillEvent;
+
+/// Passive health changes are applied in steps of this duration so that they
+/// do not flood other players with health updates.
+const REGENERATION_PERIOD: Duration = Duration::from_secs(1);
 
 pub(crate) struct HealthPlugin;
 
@@ -28,8 +48,27 @@ impl Plugin for HealthPlugin {
                     find_dead
                         .after(HealthSet::Update)
                         .before(DespawnerSet::Despawn),
+                    find_destroyed_inactive
+                        .after(HealthSet::Update)
+                        .before(DespawnerSet::Despawn),
+                    init_shields,
                 )
                     .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                regenerate_shields
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor))
+                    .before(HealthSet::Update),
+            )
+            .add_systems(
+                Update,
+                regenerate
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor))
+                    .run_if(|config: Res<GameConfig>| config.regeneration())
+                    .before(HealthSet::Update),
             );
     }
 }
@@ -45,6 +84,8 @@ pub(crate) enum HealthSet {
 pub(crate) struct LocalUpdateHealthEvent {
     entity: Entity,
     delta: f32,
+    damage_type: Option<DamageType>,
+    source: Option<Entity>,
 }
 
 impl LocalUpdateHealthEvent {
@@ -53,7 +94,107 @@ impl LocalUpdateHealthEvent {
     /// Panics if health delta is not finite.
     pub(crate) fn new(entity: Entity, delta: f32) -> Self {
         assert!(delta.is_finite());
-        Self { entity, delta }
+        Self {
+            entity,
+            delta,
+            damage_type: None,
+            source: None,
+        }
+    }
+
+    /// Creates an event dealing damage of a type to an entity. The damage is
+    /// scaled by [`damage_multiplier`] according to [`ArmorClass`] of the
+    /// entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if damage is not finite.
+    pub(crate) fn damage(entity: Entity, damage: HitPoint, damage_type: DamageType) -> Self {
+        assert!(damage.inner().is_finite());
+        Self {
+            entity,
+            delta: -damage.inner(),
+            damage_type: Some(damage_type),
+            source: None,
+        }
+    }
+
+    /// Sets the entity responsible for the health change. Destruction of
+    /// the affected entity is credited to it, see [`crate::Veterancy`].
+    pub(crate) fn with_source(mut self, source: Entity) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+/// Personal energy shield of an object. The shield absorbs damage before
+/// [`Health`] and regenerates once the object is not damaged for a while,
+/// see [`ShieldGenerator`].
+///
+/// Shields are simulated only by the game instance simulating the object.
+/// Damage is absorbed before the health change is synchronized with other
+/// players, thus other game instances see only the remaining health change.
+#[derive(Component)]
+pub struct Shield {
+    generator: ShieldGenerator,
+    value: f32,
+    /// Remaining time until the shield starts to regenerate.
+    delay: Duration,
+}
+
+impl Shield {
+    fn new(generator: ShieldGenerator) -> Self {
+        Self {
+            value: generator.capacity(),
+            generator,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Amount of damage the shield absorbs before object health is affected.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns fraction of the shield capacity which is charged.
+    pub fn fraction(&self) -> f32 {
+        self.value / self.generator.capacity()
+    }
+
+    /// Absorbs (a part of) damage and postpones shield regeneration.
+    ///
+    /// # Arguments
+    ///
+    /// * `damage` - non-negative damage dealt to the object.
+    ///
+    /// # Returns
+    ///
+    /// Remaining damage to be dealt to object health.
+    fn absorb(&mut self, damage: f32) -> f32 {
+        debug_assert!(damage >= 0.);
+        self.delay = self.generator.delay();
+        let absorbed = damage.min(self.value);
+        self.value -= absorbed;
+        damage - absorbed
+    }
+
+    /// Regenerates the shield. Returns true if the shield value changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_delta` - time delta since last call to this method.
+    fn regenerate(&mut self, time_delta: Duration) -> bool {
+        let elapsed = time_delta.saturating_sub(self.delay);
+        self.delay = self.delay.saturating_sub(time_delta);
+
+        let capacity = self.generator.capacity();
+        if elapsed.is_zero() || self.value >= capacity {
+            return false;
+        }
+
+        self.value =
+            (self.value + self.generator.regeneration() * elapsed.as_secs_f32()).min(capacity);
+        true
     }
 }
 
@@ -62,62 +203,232 @@ impl LocalUpdateHealthEvent {
 struct UpdateHealthEvent {
     entity: Entity,
     delta: f32,
+    source: Option<Entity>,
 }
 
 impl UpdateHealthEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - entity whose health is changed.
+    ///
+    /// * `delta` - amount of change.
+    ///
+    /// * `source` - locally simulated entity responsible for the change, if
+    ///   known.
+    ///
     /// # Panics
     ///
     /// Panics if health delta is not finite.
-    fn new(entity: Entity, delta: f32) -> Self {
+    fn new(entity: Entity, delta: f32, source: Option<Entity>) -> Self {
         assert!(delta.is_finite());
-        Self { entity, delta }
+        Self {
+            entity,
+            delta,
+            source,
+        }
     }
 }
 
+type LocalShields<'w, 's> = Query<'w, 's, &'static mut Shield, With<objects::Local>>;
+
+#[allow(clippy::too_many_arguments)]
 fn update_local_health(
     config: Res<GameConfig>,
     net_entities: NetEntities,
+    armors: Query<&ArmorClass>,
+    mut shields: LocalShields,
     mut in_events: EventReader<LocalUpdateHealthEvent>,
     mut out_events: EventWriter<UpdateHealthEvent>,
     mut net_events: EventWriter<ToPlayersEvent>,
+    mut bar_events: EventWriter<UpdateBarValueEvent>,
 ) {
     for event in in_events.read() {
-        out_events.send(UpdateHealthEvent::new(event.entity, event.delta));
+        let delta = match event.damage_type {
+            Some(damage_type) => {
+                let armor = armors.get(event.entity).copied().unwrap_or_default();
+                damage_multiplier(damage_type, armor) * event.delta
+            }
+            None => event.delta,
+        };
+        let delta = absorb(&mut shields, &mut bar_events, event.entity, delta);
+        out_events.send(UpdateHealthEvent::new(event.entity, delta, event.source));
 
         if config.multiplayer() {
-            net_events.send(ToPlayersEvent::new(ToPlayers::ChangeHealth {
-                entity: net_entities.net_id(event.entity),
-                delta: event.delta.try_into().unwrap(),
-            }));
+            sync_health(&net_entities, &mut net_events, event.entity, delta);
         }
     }
 }
 
+/// Health changes of locally simulated shielded objects made by other players
+/// do not account for the shield. The shield absorbs the damage here and the
+/// absorbed amount is returned to the object at other game instances.
 fn update_remote_health(
+    net_entities: NetEntities,
+    mut shields: LocalShields,
     mut in_events: EventReader<NetRecvHealthEvent>,
     mut out_events: EventWriter<UpdateHealthEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+    mut bar_events: EventWriter<UpdateBarValueEvent>,
 ) {
     for event in in_events.read() {
-        out_events.send(UpdateHealthEvent::new(event.entity(), event.delta()));
+        let delta = absorb(&mut shields, &mut bar_events, event.entity(), event.delta());
+        out_events.send(UpdateHealthEvent::new(event.entity(), delta, None));
+
+        let absorbed = delta - event.delta();
+        if absorbed > 0. {
+            sync_health(&net_entities, &mut net_events, event.entity(), absorbed);
+        }
     }
 }
 
+/// Lets the shield of a locally simulated entity absorb (a part of) damage.
+/// Returns the remaining health change.
+fn absorb(
+    shields: &mut LocalShields,
+    bar_events: &mut EventWriter<UpdateBarValueEvent>,
+    entity: Entity,
+    delta: f32,
+) -> f32 {
+    if delta >= 0. {
+        return delta;
+    }
+    let Ok(mut shield) = shields.get_mut(entity) else {
+        return delta;
+    };
+
+    let delta = -shield.absorb(-delta);
+    bar_events.send(UpdateBarValueEvent::new(
+        entity,
+        BarId::Shield,
+        shield.fraction(),
+    ));
+    delta
+}
+
+fn sync_health(
+    net_entities: &NetEntities,
+    net_events: &mut EventWriter<ToPlayersEvent>,
+    entity: Entity,
+    delta: f32,
+) {
+    let delta = delta.try_into().unwrap();
+    let message = match net_entities.inactive_net_id(entity) {
+        Some(object) => ToPlayers::ChangeInactiveHealth { object, delta },
+        None => ToPlayers::ChangeHealth {
+            entity: net_entities.net_id(entity),
+            delta,
+        },
+    };
+    net_events.send(ToPlayersEvent::new(message));
+}
+
 fn update_health(
-    mut healths: Query<&mut Health>,
+    config: Res<GameConfig>,
+    mut healths: Query<(&mut Health, &Transform, Option<&PlayerComponent>)>,
     mut health_events: EventReader<UpdateHealthEvent>,
     mut bar_events: EventWriter<UpdateBarValueEvent>,
+    mut notify_events: EventWriter<NotifyEvent>,
+    mut kill_events: EventWriter<KillEvent>,
 ) {
     for event in health_events.read() {
-        let Ok(mut health) = healths.get_mut(event.entity) else {
+        let Ok((mut health, transform, player)) = healths.get_mut(event.entity) else {
             continue;
         };
+
+        let alive = !health.destroyed();
         health.update(event.delta);
-        bar_events.send(UpdateBarValueEvent::new(event.entity, health.fraction()));
+        if alive && health.destroyed() {
+            if let (Some(killer), Some(&victim)) = (event.source, player) {
+                kill_events.send(KillEvent::new(killer, *victim));
+            }
+        }
+
+        bar_events.send(UpdateBarValueEvent::new(
+            event.entity,
+            BarId::Health,
+            health.fraction(),
+        ));
+
+        if event.delta < 0. && player.is_some_and(|&player| config.locals().is_playable(*player)) {
+            notify_events.send(NotifyEvent::new(GameNotification::under_attack(
+                transform.translation.to_flat(),
+            )));
+        }
+    }
+}
+
+type RegenerationQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Health,
+        &'static Regeneration,
+        &'static Battery,
+        Has<Disabled>,
+    ),
+    With<objects::Local>,
+>;
+
+/// Repairs locally simulated objects while they are powered and degrades
+/// them while they are not, see [`Regeneration`]. Regeneration never raises
+/// health above the maximum.
+fn regenerate(
+    time: Res<Time>,
+    mut elapsed: Local<Duration>,
+    objects: RegenerationQuery,
+    mut events: EventWriter<LocalUpdateHealthEvent>,
+) {
+    *elapsed += time.delta();
+    if *elapsed < REGENERATION_PERIOD {
+        return;
+    }
+    *elapsed -= REGENERATION_PERIOD;
+
+    for (entity, health, regeneration, battery, disabled) in objects.iter() {
+        let powered = !disabled && battery.energy() > 0.;
+        let delta = regeneration.rate(powered) * REGENERATION_PERIOD.as_secs_f32();
+        let delta = delta.min((health.max() - health.health()).max(0.));
+        if delta != 0. && !health.destroyed() {
+            events.send(LocalUpdateHealthEvent::new(entity, delta));
+        }
+    }
+}
+
+type NewLocalActive = (Added<Active>, With<objects::Local>);
+
+fn init_shields(
+    mut commands: Commands,
+    solids: SolidObjects,
+    entities: Query<(Entity, &ObjectTypeComponent), NewLocalActive>,
+) {
+    for (entity, &object_type) in entities.iter() {
+        if let Some(generator) = solids.get(*object_type).shield() {
+            commands
+                .entity(entity)
+                .insert(Shield::new(generator.clone()));
+        }
+    }
+}
+
+fn regenerate_shields(
+    time: Res<Time>,
+    mut shields: Query<(Entity, &mut Shield)>,
+    mut bar_events: EventWriter<UpdateBarValueEvent>,
+) {
+    for (entity, mut shield) in shields.iter_mut() {
+        if shield.regenerate(time.delta()) {
+            bar_events.send(UpdateBarValueEvent::new(
+                entity,
+                BarId::Shield,
+                shield.fraction(),
+            ));
+        }
     }
 }
 
 type LocallyChangedHealth<'w, 's> =
-    Query<'w, 's, (Entity, &'static Health), (With<Local>, Changed<Health>)>;
+    Query<'w, 's, (Entity, &'static Health), (With<objects::Local>, Changed<Health>)>;
 
 fn find_dead(
     entities: LocallyChangedHealth,
@@ -129,3 +440,42 @@ fn find_dead(
         }
     }
 }
+
+type ChangedInactiveHealth<'w, 's> =
+    Query<'w, 's, (Entity, &'static Health), (Without<Active>, Changed<Health>)>;
+
+/// Inactive objects are destroyed by all game instances independently since
+/// their health is synchronized.
+fn find_destroyed_inactive(
+    entities: ChangedInactiveHealth,
+    mut event_writer: EventWriter<DespawnInactiveEvent>,
+) {
+    for (entity, health) in entities.iter() {
+        if health.destroyed() {
+            event_writer.send(DespawnInactiveEvent::new(entity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shield() {
+        let mut shield = Shield::new(ShieldGenerator::new(20., 2., Duration::from_secs(5)));
+        assert_eq!(shield.fraction(), 1.);
+        assert!(!shield.regenerate(Duration::from_secs(1)));
+
+        assert_eq!(shield.absorb(15.), 0.);
+        assert_eq!(shield.value(), 5.);
+        assert_eq!(shield.absorb(10.), 5.);
+        assert_eq!(shield.value(), 0.);
+
+        assert!(!shield.regenerate(Duration::from_secs(4)));
+        assert!(shield.regenerate(Duration::from_secs(2)));
+        assert_eq!(shield.value(), 2.);
+        assert!(shield.regenerate(Duration::from_secs(100)));
+        assert_eq!(shield.fraction(), 1.);
+    }
+}
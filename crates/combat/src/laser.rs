@@ -1,11 +1,20 @@
 use bevy::prelude::*;
-use de_core::gamestate::GameState;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    player::PlayerComponent,
+};
+use de_objects::{DamageType, WeaponEffect};
+use de_uom::HitPoint;
 use parry3d::query::Ray;
 
 use crate::{
+    disable::{DisableSet, LocalDisableEvent},
     health::{HealthSet, LocalUpdateHealthEvent},
     sightline::LineOfSight,
+    support::Shielded,
     trail::LocalLaserTrailEvent,
+    veterancy::Veterancy,
     AttackingSet,
 };
 
@@ -16,8 +25,10 @@ impl Plugin for LaserPlugin {
         app.add_event::<LaserFireEvent>().add_systems(
             Update,
             fire.run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor))
                 .in_set(AttackingSet::Fire)
-                .before(HealthSet::Update),
+                .before(HealthSet::Update)
+                .before(DisableSet::Update),
         );
     }
 }
@@ -31,7 +42,7 @@ pub(crate) struct LaserFireEvent {
     attacker: Entity,
     ray: Ray,
     max_toi: f32,
-    damage: f32,
+    effect: WeaponEffect,
 }
 
 impl LaserFireEvent {
@@ -46,15 +57,13 @@ impl LaserFireEvent {
     /// * `max_toi` - this limits maximum distance to hit unit. The furthest
     ///   point is given by formula `ray.origin + max_toi * ray.dir`.
     ///
-    /// * `damage` - if an entity is hit, its health will be lowered by this
-    ///   amount.
-    #[allow(dead_code)]
-    pub(crate) fn new(attacker: Entity, ray: Ray, max_toi: f32, damage: f32) -> Self {
+    /// * `effect` - effect of the laser on a hit entity.
+    pub(crate) fn new(attacker: Entity, ray: Ray, max_toi: f32, effect: WeaponEffect) -> Self {
         Self {
             attacker,
             ray,
             max_toi,
-            damage,
+            effect,
         }
     }
 
@@ -70,15 +79,20 @@ impl LaserFireEvent {
         self.max_toi
     }
 
-    fn damage(&self) -> f32 {
-        self.damage
+    fn effect(&self) -> WeaponEffect {
+        self.effect
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn fire(
     mut fires: EventReader<LaserFireEvent>,
+    config: Res<GameConfig>,
     sightline: LineOfSight,
+    attackers: Query<(&PlayerComponent, Option<&Veterancy>)>,
+    shielded: Query<&Shielded>,
     mut health: EventWriter<LocalUpdateHealthEvent>,
+    mut disable: EventWriter<LocalDisableEvent>,
     mut trail: EventWriter<LocalLaserTrailEvent>,
 ) {
     for fire in fires.read() {
@@ -89,8 +103,29 @@ fn fire(
             observation.toi() * fire.ray().dir,
         )));
 
-        if let Some(entity) = observation.entity() {
-            health.send(LocalUpdateHealthEvent::new(entity, -fire.damage()));
+        let Some(entity) = observation.entity() else {
+            continue;
+        };
+        match fire.effect() {
+            WeaponEffect::Damage(damage) => {
+                let damage = attackers
+                    .get(fire.attacker())
+                    .map_or(damage, |(&owner, rank)| {
+                        let rank = rank.map_or(1., |veterancy| veterancy.rank().damage());
+                        damage * config.starting(*owner).handicap().damage() * rank
+                    });
+                let damage = HitPoint::new(damage);
+                let damage = shielded
+                    .get(entity)
+                    .map_or(damage, |shielded| shielded.absorb(damage));
+                health.send(
+                    LocalUpdateHealthEvent::damage(entity, damage, DamageType::Laser)
+                        .with_source(fire.attacker()),
+                );
+            }
+            WeaponEffect::Disable(duration) => {
+                disable.send(LocalDisableEvent::new(entity, duration));
+            }
         }
     }
 }
@@ -1,18 +1,51 @@
+use artillery::ArtilleryPlugin;
 pub use attack::AttackEvent;
 use attack::AttackPlugin;
 use bevy::{
     app::PluginGroupBuilder,
     prelude::{PluginGroup, SystemSet},
 };
+use cloak::CloakPlugin;
+pub use cloak::ToggleCloakEvent;
+use defense::PointDefensePlugin;
+pub use destruct::SelfDestructEvent;
+use destruct::SelfDestructPlugin;
+use disable::DisablePlugin;
+use garrison::GarrisonPlugin;
+pub use garrison::{GarrisonEvent, UngarrisonEvent};
 use health::HealthPlugin;
+pub use health::Shield;
 use laser::LaserPlugin;
+use orders::OrdersPlugin;
+pub use repair::RepairEvent;
+use repair::RepairPlugin;
+use shell::ShellPlugin;
+use splash::SplashPlugin;
+pub use splash::{AoEDamageEvent, Falloff};
+use support::SupportPlugin;
 use trail::TrailPlugin;
+use turret::TurretPlugin;
+use veterancy::VeterancyPlugin;
+pub use veterancy::{Rank, Veterancy};
 
+mod artillery;
 mod attack;
+mod cloak;
+mod defense;
+mod destruct;
+mod disable;
+mod garrison;
 mod health;
 mod laser;
+mod orders;
+mod repair;
+mod shell;
 mod sightline;
+mod splash;
+mod support;
 mod trail;
+mod turret;
+mod veterancy;
 
 pub struct CombatPluginGroup;
 
@@ -21,8 +54,21 @@ impl PluginGroup for CombatPluginGroup {
         PluginGroupBuilder::start::<Self>()
             .add(LaserPlugin)
             .add(AttackPlugin)
+            .add(TurretPlugin)
+            .add(ArtilleryPlugin)
+            .add(ShellPlugin)
             .add(TrailPlugin)
             .add(HealthPlugin)
+            .add(DisablePlugin)
+            .add(PointDefensePlugin)
+            .add(CloakPlugin)
+            .add(GarrisonPlugin)
+            .add(SelfDestructPlugin)
+            .add(SplashPlugin)
+            .add(SupportPlugin)
+            .add(RepairPlugin)
+            .add(VeterancyPlugin)
+            .add(OrdersPlugin)
     }
 }
 
@@ -30,5 +76,6 @@ impl PluginGroup for CombatPluginGroup {
 enum AttackingSet {
     Attack,
     Charge,
+    Aim,
     Fire,
 }
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use de_construction::{OrdersSet, StandingOrder, StandingOrderEvent};
+use de_core::{gamestate::GameState, gconfig::is_editor};
+
+use crate::{attack::AttackEvent, garrison::GarrisonEvent};
+
+pub(crate) struct OrdersPlugin;
+
+impl Plugin for OrdersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            execute
+                .run_if(on_event::<StandingOrderEvent>())
+                .after(OrdersSet::Apply)
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor)),
+        );
+    }
+}
+
+/// Executes standing orders to attack or to board a carrier given to
+/// manufactured units.
+fn execute(
+    mut events: EventReader<StandingOrderEvent>,
+    mut attack_events: EventWriter<AttackEvent>,
+    mut garrison_events: EventWriter<GarrisonEvent>,
+) {
+    for event in events.read() {
+        match event.order() {
+            StandingOrder::Attack(enemy) => {
+                attack_events.send(AttackEvent::new(event.unit(), enemy));
+            }
+            StandingOrder::Garrison(carrier) => {
+                garrison_events.send(GarrisonEvent::new(event.unit(), Some(carrier)));
+            }
+            StandingOrder::AttackMove(_) | StandingOrder::Harvest(_) => (),
+        }
+    }
+}
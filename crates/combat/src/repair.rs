@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_behaviour::{ChaseSet, ChaseTarget, ChaseTargetEvent};
+use de_core::{
+    gamestate::GameState,
+    gconfig::is_editor,
+    objects::{self, Disabled, Garrisoned, ObjectTypeComponent},
+    player::PlayerComponent,
+};
+use de_energy::Battery;
+use de_objects::{Health, RepairBeam, SolidObjects};
+use de_types::projection::ToFlat;
+use parry3d::query::Ray;
+
+use crate::{
+    health::{HealthSet, LocalUpdateHealthEvent},
+    trail::LocalLaserTrailEvent,
+};
+
+/// Health is restored in discrete steps of this length. Each step is
+/// visualized with a single beam trail.
+const REPAIR_PERIOD: Duration = Duration::from_millis(500);
+
+pub(crate) struct RepairPlugin;
+
+impl Plugin for RepairPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RepairEvent>()
+            .add_systems(
+                PreUpdate,
+                order
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor))
+                    .run_if(on_event::<RepairEvent>())
+                    .before(ChaseSet::ChaseTargetEvent),
+            )
+            .add_systems(
+                Update,
+                repair
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor))
+                    .before(HealthSet::Update),
+            );
+    }
+}
+
+/// Send this event to make a locally simulated unit with a [`RepairBeam`]
+/// repair another object. The unit follows the object and restores its
+/// health, draining its own battery, until the object is fully repaired or
+/// destroyed.
+///
+/// Only objects of the owner of the repairing unit can be repaired.
+#[derive(Event)]
+pub struct RepairEvent {
+    repairer: Entity,
+    target: Option<Entity>,
+}
+
+impl RepairEvent {
+    /// # Arguments
+    ///
+    /// * `repairer` - the repairing unit.
+    ///
+    /// * `target` - the object to be repaired. None means that the unit stops
+    ///   repairing.
+    pub fn new(repairer: Entity, target: Option<Entity>) -> Self {
+        Self { repairer, target }
+    }
+}
+
+#[derive(Component)]
+struct Repairing {
+    target: Entity,
+    elapsed: Duration,
+}
+
+impl Repairing {
+    fn new(target: Entity) -> Self {
+        Self {
+            target,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Advances the repair timer. Returns true if a repair step is due.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_delta` - time delta since last call to this method.
+    fn tick(&mut self, time_delta: Duration) -> bool {
+        self.elapsed += time_delta;
+        if self.elapsed < REPAIR_PERIOD {
+            return false;
+        }
+        self.elapsed -= REPAIR_PERIOD;
+        true
+    }
+}
+
+fn order(
+    mut commands: Commands,
+    mut events: EventReader<RepairEvent>,
+    repairers: Query<&RepairBeam, With<objects::Local>>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+) {
+    for event in events.read() {
+        let Ok(beam) = repairers.get(event.repairer) else {
+            continue;
+        };
+
+        match event.target.filter(|&target| target != event.repairer) {
+            Some(target) => {
+                commands
+                    .entity(event.repairer)
+                    .insert(Repairing::new(target));
+                chase_events.send(ChaseTargetEvent::new(
+                    event.repairer,
+                    Some(ChaseTarget::new(target, beam.chase_distances())),
+                ));
+            }
+            None => {
+                commands.entity(event.repairer).remove::<Repairing>();
+            }
+        }
+    }
+}
+
+type Repairers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static PlayerComponent,
+        &'static RepairBeam,
+        &'static mut Repairing,
+        &'static mut Battery,
+    ),
+    (With<objects::Local>, Without<Disabled>, Without<Garrisoned>),
+>;
+
+type Targets<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static ObjectTypeComponent,
+        &'static PlayerComponent,
+        &'static Health,
+    ),
+>;
+
+#[allow(clippy::too_many_arguments)]
+fn repair(
+    mut commands: Commands,
+    time: Res<Time>,
+    solids: SolidObjects,
+    mut repairers: Repairers,
+    targets: Targets,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+    mut health_events: EventWriter<LocalUpdateHealthEvent>,
+    mut trail_events: EventWriter<LocalLaserTrailEvent>,
+) {
+    for (repairer, transform, &player, beam, mut repairing, mut battery) in repairers.iter_mut() {
+        // Health increases are accepted only from the simulating game
+        // instance, therefore other players' objects cannot be repaired.
+        let target = targets
+            .get(repairing.target)
+            .ok()
+            .filter(|&(_, _, &owner, health)| {
+                *owner == *player && !health.destroyed() && health.health() < health.max()
+            });
+        let Some((target_transform, &target_type, _, health)) = target else {
+            commands.entity(repairer).remove::<Repairing>();
+            chase_events.send(ChaseTargetEvent::new(repairer, None));
+            continue;
+        };
+
+        let target_solid = solids.get(*target_type);
+        let distance = transform
+            .translation
+            .to_flat()
+            .distance(target_transform.translation.to_flat())
+            - target_solid.ichnography().radius();
+        if distance > beam.range() || !repairing.tick(time.delta()) {
+            continue;
+        }
+        if !battery.try_consume(beam.drain() * REPAIR_PERIOD.as_secs_f64()) {
+            continue;
+        }
+
+        let delta = (beam.rate() * REPAIR_PERIOD.as_secs_f32()).min(health.max() - health.health());
+        health_events.send(LocalUpdateHealthEvent::new(repairing.target, delta));
+
+        let muzzle = transform.transform_point(beam.muzzle());
+        let centroid =
+            target_transform.translation + Vec3::from(target_solid.collider().aabb().center());
+        trail_events.send(LocalLaserTrailEvent::repair(Ray::new(
+            muzzle.into(),
+            (centroid - muzzle).into(),
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick() {
+        let mut repairing = Repairing::new(Entity::from_raw(1));
+        assert!(!repairing.tick(Duration::from_millis(300)));
+        assert!(repairing.tick(Duration::from_millis(300)));
+        assert_eq!(repairing.elapsed, Duration::from_millis(100));
+        assert!(!repairing.tick(Duration::from_millis(300)));
+        assert!(repairing.tick(Duration::from_millis(100)));
+    }
+}
@@ -0,0 +1,475 @@
+use std::time::Duration;
+
+use bevy::{
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+};
+use de_audio::spatial::{PlaySpatialAudioEvent, Sound};
+use de_core::{
+    cleanup::DespawnOnGameExit, gamestate::GameState, gconfig::GameConfig, state::AppState,
+};
+use de_index::SpatialQuery;
+use de_messages::{NetProjectile, ToPlayers};
+use de_multiplayer::{MessagesSet, NetRecvProjectileEvent, ToPlayersEvent};
+use de_objects::{DamageType, Trajectory};
+use de_terrain::{DecalKind, DecalSet, SpawnDecalEvent, TerrainCollider};
+use de_types::{player::Player, projection::ToFlat};
+use de_uom::{HitPoint, Metre, MetrePerSecondSquared, Quantity};
+
+use crate::{
+    health::HealthSet,
+    splash::{AoEDamageEvent, Falloff},
+    AttackingSet,
+};
+
+/// Gravitational acceleration.
+const GRAVITY: MetrePerSecondSquared = Quantity::new_unchecked(9.8);
+/// Shells (and rockets) still in flight after this time are removed.
+const MAX_FLIGHT_TIME: Duration = Duration::from_secs(30);
+const SHELL_RADIUS: Metre = Quantity::new_unchecked(0.25);
+/// Radius of the blast caused by a shell impact (and of the scorch mark left
+/// on the terrain). The damage linearly decreases with distance.
+const IMPACT_RADIUS: Metre = Quantity::new_unchecked(2.);
+
+pub(crate) struct ShellPlugin;
+
+impl Plugin for ShellPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LocalFireShellEvent>()
+            .add_event::<FireShellEvent>()
+            .add_event::<LocalInterceptShellEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    (
+                        local_intercept.run_if(on_event::<LocalInterceptShellEvent>()),
+                        remote_intercept.run_if(on_event::<NetRecvProjectileEvent>()),
+                    )
+                        .in_set(ShellSet::Intercept)
+                        .before(AttackingSet::Fire),
+                    fly.in_set(AttackingSet::Fire)
+                        .before(HealthSet::Update)
+                        .before(DecalSet::Spawn),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    local_fire
+                        .before(MessagesSet::SendMessages)
+                        .before(ShellSet::Fire),
+                    remote_fire.before(ShellSet::Fire),
+                    spawn.in_set(ShellSet::Fire),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub(crate) enum ShellSet {
+    Fire,
+    Intercept,
+}
+
+/// Send this event to fire a shell (or a rocket) from a locally simulated
+/// entity.
+#[derive(Event)]
+pub(crate) struct LocalFireShellEvent {
+    attacker: Entity,
+    player: Player,
+    origin: Vec3,
+    velocity: Vec3,
+    trajectory: Trajectory,
+    damage: HitPoint,
+}
+
+impl LocalFireShellEvent {
+    /// # Arguments
+    ///
+    /// * `attacker` - the firing entity. The shell never hits this entity.
+    ///
+    /// * `player` - owner of the firing entity.
+    ///
+    /// * `origin` - world-space position where the shell is spawned.
+    ///
+    /// * `velocity` - initial velocity of the shell.
+    ///
+    /// * `trajectory` - ballistic shells are affected by gravity, linear
+    ///   shells (rockets) keep their initial velocity.
+    ///
+    /// * `damage` - damage dealt by the impact blast to objects right at the
+    ///   impact point.
+    pub(crate) fn new(
+        attacker: Entity,
+        player: Player,
+        origin: Vec3,
+        velocity: Vec3,
+        trajectory: Trajectory,
+        damage: HitPoint,
+    ) -> Self {
+        Self {
+            attacker,
+            player,
+            origin,
+            velocity,
+            trajectory,
+            damage,
+        }
+    }
+}
+
+/// Spawns a shell. Remotely fired shells are visual only since damage is
+/// dealt by the game instance simulating the attacker.
+#[derive(Event)]
+struct FireShellEvent {
+    player: Player,
+    origin: Vec3,
+    velocity: Vec3,
+    trajectory: Trajectory,
+    impact: Option<Impact>,
+}
+
+#[derive(Clone, Copy)]
+struct Impact {
+    attacker: Entity,
+    damage: HitPoint,
+}
+
+/// Send this event to shoot down a flying shell.
+#[derive(Event)]
+pub(crate) struct LocalInterceptShellEvent(Entity);
+
+impl LocalInterceptShellEvent {
+    /// # Arguments
+    ///
+    /// * `shell` - the intercepted shell entity.
+    pub(crate) fn new(shell: Entity) -> Self {
+        Self(shell)
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct Shell {
+    player: Player,
+    origin: Vec3,
+    launch_velocity: Vec3,
+    velocity: Vec3,
+    trajectory: Trajectory,
+    flight_time: Duration,
+    impact: Option<Impact>,
+}
+
+impl Shell {
+    /// Owner of the object which fired the shell.
+    pub(crate) fn player(&self) -> Player {
+        self.player
+    }
+
+    /// Returns true if the shell was launched with the given parameters.
+    fn launched(&self, origin: Vec3, velocity: Vec3) -> bool {
+        self.origin == origin && self.launch_velocity == velocity
+    }
+}
+
+#[derive(Resource)]
+struct ShellAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ShellAssets {
+        mesh: meshes.add(Sphere::new(SHELL_RADIUS.inner()).mesh().ico(2).unwrap()),
+        material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.2, 0.2, 0.2),
+            emissive: Color::rgb(4., 1.5, 0.3),
+            ..Default::default()
+        }),
+    });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<ShellAssets>();
+}
+
+fn local_fire(
+    config: Res<GameConfig>,
+    mut in_events: EventReader<LocalFireShellEvent>,
+    mut out_events: EventWriter<FireShellEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    for event in in_events.read() {
+        out_events.send(FireShellEvent {
+            player: event.player,
+            origin: event.origin,
+            velocity: event.velocity,
+            trajectory: event.trajectory,
+            impact: Some(Impact {
+                attacker: event.attacker,
+                damage: event.damage,
+            }),
+        });
+
+        if config.multiplayer() {
+            let (player, origin, velocity) =
+                (event.player, event.origin.into(), event.velocity.into());
+            let projectile = match event.trajectory {
+                Trajectory::Ballistic => NetProjectile::Shell {
+                    player,
+                    origin,
+                    velocity,
+                },
+                Trajectory::Linear => NetProjectile::Rocket {
+                    player,
+                    origin,
+                    velocity,
+                },
+            };
+            net_events.send(ToPlayersEvent::new(ToPlayers::Projectile(projectile)));
+        }
+    }
+}
+
+fn remote_fire(
+    mut in_events: EventReader<NetRecvProjectileEvent>,
+    mut out_events: EventWriter<FireShellEvent>,
+) {
+    for event in in_events.read() {
+        let (player, origin, velocity, trajectory) = match **event {
+            NetProjectile::Shell {
+                player,
+                origin,
+                velocity,
+            } => (player, origin, velocity, Trajectory::Ballistic),
+            NetProjectile::Rocket {
+                player,
+                origin,
+                velocity,
+            } => (player, origin, velocity, Trajectory::Linear),
+            _ => continue,
+        };
+
+        out_events.send(FireShellEvent {
+            player,
+            origin: origin.into(),
+            velocity: velocity.into(),
+            trajectory,
+            impact: None,
+        });
+    }
+}
+
+fn spawn(
+    mut commands: Commands,
+    assets: Res<ShellAssets>,
+    mut events: EventReader<FireShellEvent>,
+) {
+    for event in events.read() {
+        commands.spawn((
+            PbrBundle {
+                mesh: assets.mesh.clone(),
+                material: assets.material.clone(),
+                transform: Transform::from_translation(event.origin),
+                ..Default::default()
+            },
+            Shell {
+                player: event.player,
+                origin: event.origin,
+                launch_velocity: event.velocity,
+                velocity: event.velocity,
+                trajectory: event.trajectory,
+                flight_time: Duration::ZERO,
+                impact: event.impact,
+            },
+            DespawnOnGameExit,
+            NotShadowCaster,
+            NotShadowReceiver,
+        ));
+    }
+}
+
+fn local_intercept(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    shells: Query<(&Transform, &Shell)>,
+    mut events: EventReader<LocalInterceptShellEvent>,
+    mut sounds: EventWriter<PlaySpatialAudioEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    for event in events.read() {
+        let Ok((transform, shell)) = shells.get(event.0) else {
+            continue;
+        };
+
+        commands.entity(event.0).despawn();
+        sounds.send(PlaySpatialAudioEvent::new(
+            Sound::Explosion,
+            transform.translation,
+        ));
+
+        if config.multiplayer() {
+            net_events.send(ToPlayersEvent::new(ToPlayers::Projectile(
+                NetProjectile::Intercepted {
+                    origin: shell.origin.into(),
+                    velocity: shell.launch_velocity.into(),
+                },
+            )));
+        }
+    }
+}
+
+fn remote_intercept(
+    mut commands: Commands,
+    shells: Query<(Entity, &Transform, &Shell)>,
+    mut events: EventReader<NetRecvProjectileEvent>,
+    mut sounds: EventWriter<PlaySpatialAudioEvent>,
+) {
+    for event in events.read() {
+        let NetProjectile::Intercepted { origin, velocity } = **event else {
+            continue;
+        };
+
+        // The shell might have already hit something.
+        let (origin, velocity) = (origin.into(), velocity.into());
+        if let Some((entity, transform, _)) = shells
+            .iter()
+            .find(|(_, _, shell)| shell.launched(origin, velocity))
+        {
+            commands.entity(entity).despawn();
+            sounds.send(PlaySpatialAudioEvent::new(
+                Sound::Explosion,
+                transform.translation,
+            ));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fly(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut shells: Query<(Entity, &mut Transform, &mut Shell)>,
+    terrain: TerrainCollider,
+    entities: SpatialQuery<Entity>,
+    mut aoe: EventWriter<AoEDamageEvent>,
+    mut decals: EventWriter<SpawnDecalEvent>,
+    mut sounds: EventWriter<PlaySpatialAudioEvent>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, mut transform, mut shell) in shells.iter_mut() {
+        shell.flight_time += time.delta();
+        if shell.flight_time > MAX_FLIGHT_TIME {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let start = transform.translation;
+        if shell.trajectory == Trajectory::Ballistic {
+            shell.velocity.y -= GRAVITY.inner() * delta;
+        }
+        let stop = start + delta * shell.velocity;
+
+        let ignore = shell.impact.map(|impact| impact.attacker);
+        let Some(obstacle) = entities.line_of_sight(&terrain, start, stop, ignore) else {
+            transform.translation = stop;
+            continue;
+        };
+
+        commands.entity(entity).despawn();
+
+        let position = start.lerp(stop, obstacle.toi());
+        sounds.send(PlaySpatialAudioEvent::new(Sound::Explosion, position));
+
+        if let Some(impact) = shell.impact {
+            aoe.send(
+                AoEDamageEvent::new(
+                    position.to_flat(),
+                    IMPACT_RADIUS,
+                    impact.damage,
+                    Falloff::Linear,
+                )
+                .with_damage_type(DamageType::Shell)
+                .with_source(impact.attacker),
+            );
+        }
+
+        if obstacle.entity().is_none() {
+            decals.send(SpawnDecalEvent::circle(
+                DecalKind::Scorch,
+                position.to_flat(),
+                IMPACT_RADIUS.inner(),
+            ));
+        }
+    }
+}
+
+/// Returns initial velocity of a ballistic projectile so that it flies
+/// through the target following the lower (flatter) of the two possible
+/// trajectories. None is returned if the target is out of reach or right
+/// above or below the origin.
+///
+/// # Arguments
+///
+/// * `origin` - position of the projectile at launch.
+///
+/// * `target` - position to be hit.
+///
+/// * `speed` - initial speed of the projectile.
+pub(crate) fn launch_velocity(origin: Vec3, target: Vec3, speed: f32) -> Option<Vec3> {
+    let delta = target - origin;
+    let flat = Vec2::new(delta.x, delta.z);
+    let distance = flat.length();
+    if distance < 1e-3 {
+        return None;
+    }
+
+    let gravity = GRAVITY.inner();
+    let speed_sq = speed * speed;
+    let discriminant =
+        speed_sq * speed_sq - gravity * (gravity * distance * distance + 2. * delta.y * speed_sq);
+    if discriminant < 0. {
+        return None;
+    }
+
+    let elevation = ((speed_sq - discriminant.sqrt()) / (gravity * distance)).atan();
+    let direction = flat / distance;
+    let (sin, cos) = elevation.sin_cos();
+    Some(speed * Vec3::new(cos * direction.x, sin, cos * direction.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_velocity() {
+        let origin = Vec3::new(1., 2., -3.);
+
+        for target in [
+            Vec3::new(60., 2., -3.),
+            Vec3::new(-40., 10., 70.),
+            Vec3::new(20., -5., 90.),
+        ] {
+            let velocity = launch_velocity(origin, target, 40.).unwrap();
+            assert!((velocity.length() - 40.).abs() < 1e-3);
+
+            let flat_distance = (target - origin).to_flat().length();
+            let flat_speed = Vec2::new(velocity.x, velocity.z).length();
+            let time = flat_distance / flat_speed;
+            let hit = origin + time * velocity - 0.5 * GRAVITY.inner() * time * time * Vec3::Y;
+            assert!(hit.distance(target) < 1e-2, "{hit} != {target}");
+        }
+
+        assert!(launch_velocity(origin, Vec3::new(500., 2., -3.), 40.).is_none());
+        assert!(launch_velocity(origin, origin + Vec3::Y, 40.).is_none());
+    }
+}
@@ -1,4 +1,7 @@
-use bevy::{ecs::system::SystemParam, prelude::Entity};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Entity, Vec3},
+};
 use de_index::SpatialQuery;
 use de_terrain::TerrainCollider;
 use parry3d::query::Ray;
@@ -22,16 +25,15 @@ impl<'w, 's> LineOfSight<'w, 's> {
     /// * `observer` - the entity making the observation. This is needed so the
     ///   entity doesn't observe itself.
     pub(crate) fn sight(&self, ray: &Ray, max_toi: f32, observer: Entity) -> Observation {
-        // It is more efficient to calculate the terrain hit. Do it first so
-        // max_toi can be lowered in case of a hit.
-        let hit = match self.terrain.cast_ray(ray, max_toi) {
-            Some(intersection) => Observation::new(intersection.toi, None),
+        let a: Vec3 = ray.origin.into();
+        let b: Vec3 = ray.point_at(max_toi).into();
+        match self
+            .entities
+            .line_of_sight(&self.terrain, a, b, Some(observer))
+        {
+            Some(obstacle) => Observation::new(max_toi * obstacle.toi(), obstacle.entity()),
             None => Observation::new(max_toi, None),
-        };
-        self.entities
-            .cast_ray(ray, hit.toi(), Some(observer))
-            .map(|i| Observation::new(i.toi(), Some(i.entity())))
-            .unwrap_or(hit)
+        }
     }
 }
 
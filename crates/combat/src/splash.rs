@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use de_core::{gamestate::GameState, gconfig::is_editor, objects::ObjectTypeComponent};
+use de_index::SpatialQuery;
+use de_objects::{DamageType, Health, SolidObjects};
+use de_types::projection::ToFlat;
+use de_uom::{HitPoint, Metre};
+
+use crate::{
+    health::{HealthSet, LocalUpdateHealthEvent},
+    support::Shielded,
+    AttackingSet,
+};
+
+pub(crate) struct SplashPlugin;
+
+impl Plugin for SplashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AoEDamageEvent>().add_systems(
+            Update,
+            splash
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor))
+                .run_if(on_event::<AoEDamageEvent>())
+                .in_set(SplashSet::Damage)
+                .after(AttackingSet::Fire)
+                .before(HealthSet::Update),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub(crate) enum SplashSet {
+    Damage,
+}
+
+/// Send this event to deal damage to all objects in an area, e.g. as a result
+/// of an explosion caused by a locally simulated entity.
+#[derive(Event)]
+pub struct AoEDamageEvent {
+    center: Vec2,
+    radius: Metre,
+    damage: HitPoint,
+    falloff: Falloff,
+    damage_type: DamageType,
+    source: Option<Entity>,
+    core_radius: Metre,
+}
+
+impl AoEDamageEvent {
+    /// # Arguments
+    ///
+    /// * `center` - center of the damaged area on the map.
+    ///
+    /// * `radius` - objects whose footprints are within this distance from
+    ///   the center (or from the core, see [`Self::with_core_radius`]) are
+    ///   damaged.
+    ///
+    /// * `damage` - damage dealt to objects right at the center.
+    ///
+    /// * `falloff` - decrease of damage with distance from the center.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `radius` or `damage` is not a positive finite number.
+    pub fn new(center: Vec2, radius: Metre, damage: HitPoint, falloff: Falloff) -> Self {
+        debug_assert!(radius.inner().is_finite());
+        debug_assert!(radius > Metre::ZERO);
+        debug_assert!(damage.inner().is_finite());
+        debug_assert!(damage > HitPoint::ZERO);
+        Self {
+            center,
+            radius,
+            damage,
+            falloff,
+            damage_type: DamageType::Blast,
+            source: None,
+            core_radius: Metre::ZERO,
+        }
+    }
+
+    /// Sets type of the dealt damage. It defaults to [`DamageType::Blast`].
+    pub fn with_damage_type(mut self, damage_type: DamageType) -> Self {
+        self.damage_type = damage_type;
+        self
+    }
+
+    /// Sets the entity responsible for the damage. The entity itself is not
+    /// damaged and destruction of damaged objects is credited to it.
+    pub fn with_source(mut self, source: Entity) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Makes the damage originate from a circle with a given radius around
+    /// the center (e.g. footprint of an exploding object) rather than from
+    /// the center point. All distances are measured from the circle.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `radius` is not a non-negative finite number.
+    pub fn with_core_radius(mut self, radius: Metre) -> Self {
+        debug_assert!(radius.inner().is_finite());
+        debug_assert!(radius >= Metre::ZERO);
+        self.core_radius = radius;
+        self
+    }
+
+    /// Returns damage dealt to an object whose footprint is at a given
+    /// distance from the center.
+    fn damage(&self, distance: Metre) -> HitPoint {
+        let distance = distance.max(Metre::ZERO);
+        if distance > self.radius {
+            return HitPoint::ZERO;
+        }
+
+        match self.falloff {
+            Falloff::None => self.damage,
+            Falloff::Linear => self.damage * (1. - distance.inner() / self.radius.inner()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Falloff {
+    /// All objects in the area receive the full damage.
+    None,
+    /// Damage linearly decreases with distance and reaches zero at the edge
+    /// of the area.
+    Linear,
+}
+
+fn splash(
+    solids: SolidObjects,
+    targets: SpatialQuery<(Entity, &Transform, &ObjectTypeComponent), With<Health>>,
+    shielded: Query<&Shielded>,
+    mut events: EventReader<AoEDamageEvent>,
+    mut health_events: EventWriter<LocalUpdateHealthEvent>,
+) {
+    for event in events.read() {
+        for (target, transform, &target_type) in targets.query_circle(
+            event.center,
+            (event.core_radius + event.radius).inner(),
+            event.source,
+        ) {
+            let distance = Metre::new(
+                event.center.distance(transform.translation.to_flat())
+                    - solids.get(*target_type).ichnography().radius(),
+            ) - event.core_radius;
+            let damage = event.damage(distance);
+            if damage > HitPoint::ZERO {
+                let damage = shielded
+                    .get(target)
+                    .map_or(damage, |shielded| shielded.absorb(damage));
+                let mut health_event =
+                    LocalUpdateHealthEvent::damage(target, damage, event.damage_type);
+                if let Some(source) = event.source {
+                    health_event = health_event.with_source(source);
+                }
+                health_events.send(health_event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage() {
+        let event = AoEDamageEvent::new(
+            Vec2::ZERO,
+            Metre::new(10.),
+            HitPoint::new(20.),
+            Falloff::Linear,
+        );
+        assert_eq!(event.damage(Metre::new(-1.)), HitPoint::new(20.));
+        assert_eq!(event.damage(Metre::ZERO), HitPoint::new(20.));
+        assert_eq!(event.damage(Metre::new(5.)), HitPoint::new(10.));
+        assert_eq!(event.damage(Metre::new(10.)), HitPoint::ZERO);
+        assert_eq!(event.damage(Metre::new(11.)), HitPoint::ZERO);
+
+        let event = AoEDamageEvent::new(
+            Vec2::ZERO,
+            Metre::new(10.),
+            HitPoint::new(20.),
+            Falloff::None,
+        );
+        assert_eq!(event.damage(Metre::ZERO), HitPoint::new(20.));
+        assert_eq!(event.damage(Metre::new(10.)), HitPoint::new(20.));
+        assert_eq!(event.damage(Metre::new(11.)), HitPoint::ZERO);
+    }
+}
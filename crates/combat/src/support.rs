@@ -0,0 +1,218 @@
+use std::f32::consts::FRAC_PI_2;
+
+use ahash::AHashMap;
+use bevy::{
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{Disabled, MovableSolid},
+    player::PlayerComponent,
+    state::AppState,
+};
+use de_energy::Battery;
+use de_index::SpatialQuery;
+use de_objects::{Radar, ShieldDome};
+use de_types::projection::ToFlat;
+use de_uom::HitPoint;
+
+/// Elevation of the radar area visualization above the terrain.
+const RADAR_ELEVATION: f32 = 0.05;
+
+pub(crate) struct SupportPlugin;
+
+impl Plugin for SupportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (power, shield.after(power))
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+/// A support building whose area effects (shield dome, radar) are powered
+/// and thus active.
+#[derive(Component)]
+pub(crate) struct Powered {
+    /// Entity of the visualization of the area effects.
+    effect: Entity,
+}
+
+/// A unit protected by a shield dome.
+#[derive(Component)]
+pub(crate) struct Shielded {
+    reduction: f32,
+}
+
+impl Shielded {
+    /// Returns damage reduced by the shield.
+    pub(crate) fn absorb(&self, damage: HitPoint) -> HitPoint {
+        damage * (1. - self.reduction)
+    }
+}
+
+#[derive(Resource)]
+struct EffectAssets {
+    dome_mesh: Handle<Mesh>,
+    dome_material: Handle<StandardMaterial>,
+    radar_mesh: Handle<Mesh>,
+    radar_material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(EffectAssets {
+        dome_mesh: meshes.add(Sphere::new(1.).mesh().ico(4).unwrap()),
+        dome_material: materials.add(StandardMaterial {
+            base_color: Color::rgba(0.3, 0.6, 1., 0.12),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            cull_mode: None,
+            ..Default::default()
+        }),
+        radar_mesh: meshes.add(Circle::new(1.).mesh().resolution(64)),
+        radar_material: materials.add(StandardMaterial {
+            base_color: Color::rgba(0.3, 1., 0.4, 0.08),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..Default::default()
+        }),
+    });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<EffectAssets>();
+}
+
+type SupportQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Option<&'static ShieldDome>,
+        Option<&'static Radar>,
+        &'static mut Battery,
+        Option<&'static Powered>,
+        Has<Disabled>,
+    ),
+    Or<(With<ShieldDome>, With<Radar>)>,
+>;
+
+/// Drains energy of support buildings and (de)activates their area effects
+/// based on energy availability.
+///
+/// Batteries are simulated by all game instances, therefore all instances
+/// decide independently.
+fn power(
+    mut commands: Commands,
+    time: Res<Time>,
+    assets: Res<EffectAssets>,
+    mut buildings: SupportQuery,
+) {
+    for (entity, dome, radar, mut battery, powered, disabled) in buildings.iter_mut() {
+        let drain = dome.map_or(0., |dome| dome.drain()) + radar.map_or(0., |radar| radar.drain());
+        let active = !disabled && battery.try_consume(drain * time.delta_seconds_f64());
+
+        match (active, powered) {
+            (true, None) => {
+                let effect = commands
+                    .spawn(SpatialBundle::default())
+                    .with_children(|parent| {
+                        if let Some(dome) = dome {
+                            parent.spawn((
+                                PbrBundle {
+                                    mesh: assets.dome_mesh.clone(),
+                                    material: assets.dome_material.clone(),
+                                    transform: Transform::from_scale(Vec3::splat(dome.radius())),
+                                    ..default()
+                                },
+                                NotShadowCaster,
+                                NotShadowReceiver,
+                            ));
+                        }
+                        if let Some(radar) = radar {
+                            parent.spawn((
+                                PbrBundle {
+                                    mesh: assets.radar_mesh.clone(),
+                                    material: assets.radar_material.clone(),
+                                    transform: Transform {
+                                        translation: RADAR_ELEVATION * Vec3::Y,
+                                        rotation: Quat::from_rotation_x(-FRAC_PI_2),
+                                        scale: Vec3::splat(radar.range()),
+                                    },
+                                    ..default()
+                                },
+                                NotShadowCaster,
+                                NotShadowReceiver,
+                            ));
+                        }
+                    })
+                    .id();
+
+                commands
+                    .entity(entity)
+                    .insert(Powered { effect })
+                    .add_child(effect);
+            }
+            (false, Some(powered)) => {
+                commands.entity(powered.effect).despawn_recursive();
+                commands.entity(entity).remove::<Powered>();
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Updates which units are protected by a powered shield dome of an allied
+/// player.
+fn shield(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    domes: Query<(&Transform, &PlayerComponent, &ShieldDome), With<Powered>>,
+    candidates: SpatialQuery<(Entity, &PlayerComponent), With<MovableSolid>>,
+    mut shielded: Query<(Entity, &mut Shielded)>,
+) {
+    let mut reductions: AHashMap<Entity, f32> = AHashMap::new();
+
+    for (transform, &owner, dome) in domes.iter() {
+        let center = transform.translation.to_flat();
+        for (unit, &unit_owner) in candidates.query_circle(center, dome.radius(), None) {
+            if !config.teams().allied(*owner, *unit_owner) {
+                continue;
+            }
+
+            // Overlapping domes do not stack.
+            reductions
+                .entry(unit)
+                .and_modify(|reduction| *reduction = reduction.max(dome.reduction()))
+                .or_insert(dome.reduction());
+        }
+    }
+
+    for (unit, mut current) in shielded.iter_mut() {
+        match reductions.remove(&unit) {
+            Some(reduction) => {
+                // Do not unnecessarily trigger change detection.
+                if current.reduction != reduction {
+                    current.reduction = reduction;
+                }
+            }
+            None => {
+                commands.entity(unit).remove::<Shielded>();
+            }
+        }
+    }
+
+    for (unit, reduction) in reductions {
+        commands.entity(unit).insert(Shielded { reduction });
+    }
+}
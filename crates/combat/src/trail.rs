@@ -18,10 +18,23 @@ use de_core::{
 };
 use de_messages::{NetProjectile, ToPlayers};
 use de_multiplayer::{MessagesSet, NetRecvProjectileEvent, ToPlayersEvent};
+use de_terrain::{DecalKind, DecalSet, SpawnDecalEvent, TerrainCollider};
+use de_types::projection::ToFlat;
 use parry3d::query::Ray;
 
 const TRAIL_LIFESPAN: Duration = Duration::from_millis(500);
 const TRAIL_THICKNESS: f32 = 0.1;
+/// Number of trail entities allocated at the start of the game. Trails are
+/// reused instead of being spawned and despawned with each shot. When all of
+/// them are in use, the oldest one is cut short and reused.
+const TRAIL_POOL_SIZE: usize = 256;
+/// Radius of the scorch mark left on the terrain by a laser hit.
+const SCORCH_RADIUS: f32 = 0.6;
+/// Maximum relative distance between a trail end and the terrain surface
+/// for the trail to be considered a terrain hit.
+const SCORCH_TOLERANCE: f32 = 0.01;
+const LASER_COLOR: Color = Color::rgba_linear(1., 0.85, 0.1, 0.7);
+const REPAIR_COLOR: Color = Color::rgba_linear(0.2, 1., 0.5, 0.7);
 
 pub(crate) struct TrailPlugin;
 
@@ -41,6 +54,7 @@ impl Plugin for TrailPlugin {
                     remote_laser_trail.before(TrailSet::Trail),
                     laser_trail.in_set(TrailSet::Trail),
                     laser_sound.in_set(TrailSet::Trail),
+                    laser_scorch.in_set(TrailSet::Trail).before(DecalSet::Spawn),
                     update,
                 )
                     .run_if(in_state(GameState::Playing)),
@@ -54,31 +68,90 @@ enum TrailSet {
 }
 
 #[derive(Event)]
-pub(crate) struct LocalLaserTrailEvent(Ray);
+pub(crate) struct LocalLaserTrailEvent {
+    ray: Ray,
+    kind: TrailKind,
+}
 
 impl LocalLaserTrailEvent {
-    /// Send this event to spawn a new trail. The trail will automatically fade
-    /// out and disappear.
+    /// Send this event to spawn a new laser trail. The trail will
+    /// automatically fade out and disappear.
     ///
     /// # Arguments
     ///
     /// * `ray` - the trail originates at the ray origin. The trail ends at the
     ///   `ray.origin + ray.dir`.
     pub(crate) fn new(ray: Ray) -> Self {
-        Self(ray)
+        Self {
+            ray,
+            kind: TrailKind::Laser,
+        }
+    }
+
+    /// Send this event to spawn a new repair beam trail. Contrary to laser
+    /// trails, repair beams are silent and leave no scorch marks.
+    ///
+    /// See [`Self::new`].
+    pub(crate) fn repair(ray: Ray) -> Self {
+        Self {
+            ray,
+            kind: TrailKind::Repair,
+        }
     }
 }
 
 #[derive(Event)]
-struct LaserTrailEvent(Ray);
+struct LaserTrailEvent {
+    ray: Ray,
+    kind: TrailKind,
+}
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TrailKind {
+    Laser,
+    Repair,
+}
+
+impl TrailKind {
+    fn color(self) -> Color {
+        match self {
+            Self::Laser => LASER_COLOR,
+            Self::Repair => REPAIR_COLOR,
+        }
+    }
+}
+
+/// Pre-allocated trail entities.
 #[derive(Resource)]
-struct MeshHandle(Handle<Mesh>);
+struct TrailPool {
+    trails: Vec<Entity>,
+    next: usize,
+}
+
+impl TrailPool {
+    fn new(trails: Vec<Entity>) -> Self {
+        debug_assert!(!trails.is_empty());
+        Self { trails, next: 0 }
+    }
+
+    /// Returns the least recently used trail. All trails have the same
+    /// lifespan, therefore it is either finished or it is the oldest one.
+    fn take(&mut self) -> Entity {
+        let entity = self.trails[self.next];
+        self.next = (self.next + 1) % self.trails.len();
+        entity
+    }
+}
 
 #[derive(Component, Default)]
 struct Trail(Duration);
 
 impl Trail {
+    /// Returns a trail which is already finished (and hidden).
+    fn finished_trail() -> Self {
+        Self(TRAIL_LIFESPAN)
+    }
+
     fn tick(&mut self, duration: Duration) {
         self.0 += duration;
     }
@@ -92,14 +165,18 @@ impl Trail {
 struct TrailMaterial {
     #[uniform(0)]
     start_time: f32,
+    #[uniform(1)]
+    color: Color,
 }
 
 impl TrailMaterial {
     /// # Arguments
     ///
     /// `start_time` - wrapped time since the application startup.
-    fn new(start_time: f32) -> Self {
-        Self { start_time }
+    ///
+    /// `color` - color of the trail at its start.
+    fn new(start_time: f32, color: Color) -> Self {
+        Self { start_time, color }
     }
 }
 
@@ -130,15 +207,19 @@ fn local_laser_trail(
     mut net_events: EventWriter<ToPlayersEvent>,
 ) {
     for event in in_events.read() {
-        out_events.send(LaserTrailEvent(event.0));
+        out_events.send(LaserTrailEvent {
+            ray: event.ray,
+            kind: event.kind,
+        });
 
         if config.multiplayer() {
-            net_events.send(ToPlayersEvent::new(ToPlayers::Projectile(
-                NetProjectile::Laser {
-                    origin: event.0.origin.into(),
-                    direction: event.0.dir.into(),
-                },
-            )));
+            let origin = event.ray.origin.into();
+            let direction = event.ray.dir.into();
+            let projectile = match event.kind {
+                TrailKind::Laser => NetProjectile::Laser { origin, direction },
+                TrailKind::Repair => NetProjectile::RepairBeam { origin, direction },
+            };
+            net_events.send(ToPlayersEvent::new(ToPlayers::Projectile(projectile)));
         }
     }
 }
@@ -150,38 +231,59 @@ fn remote_laser_trail(
     for event in in_events.read() {
         match **event {
             NetProjectile::Laser { origin, direction } => {
-                out_events.send(LaserTrailEvent(Ray::new(origin.into(), direction.into())));
+                out_events.send(LaserTrailEvent {
+                    ray: Ray::new(origin.into(), direction.into()),
+                    kind: TrailKind::Laser,
+                });
+            }
+            NetProjectile::RepairBeam { origin, direction } => {
+                out_events.send(LaserTrailEvent {
+                    ray: Ray::new(origin.into(), direction.into()),
+                    kind: TrailKind::Repair,
+                });
             }
+            NetProjectile::Shell { .. }
+            | NetProjectile::Rocket { .. }
+            | NetProjectile::Intercepted { .. } => (),
         }
     }
 }
 
+type TrailQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Trail,
+        &'static mut Transform,
+        &'static mut Visibility,
+        &'static Handle<TrailMaterial>,
+    ),
+>;
+
 fn laser_trail(
-    mut commands: Commands,
     mut materials: ResMut<Assets<TrailMaterial>>,
     time: Res<Time>,
-    mesh: Res<MeshHandle>,
+    mut pool: ResMut<TrailPool>,
+    mut trails: TrailQuery,
     mut events: EventReader<LaserTrailEvent>,
 ) {
     for event in events.read() {
-        let material = materials.add(TrailMaterial::new(time.elapsed_seconds_wrapped()));
-
-        commands.spawn((
-            MaterialMeshBundle::<TrailMaterial> {
-                mesh: mesh.0.clone(),
-                material,
-                transform: Transform {
-                    translation: event.0.origin.into(),
-                    rotation: Quat::from_rotation_arc(Vec3::X, event.0.dir.normalize().into()),
-                    scale: Vec3::new(event.0.dir.norm(), 1., 1.),
-                },
-                ..Default::default()
-            },
-            Trail::default(),
-            DespawnOnGameExit,
-            NotShadowCaster,
-            NotShadowReceiver,
-        ));
+        let Ok((mut trail, mut transform, mut visibility, material)) = trails.get_mut(pool.take())
+        else {
+            continue;
+        };
+
+        *trail = Trail::default();
+        *transform = Transform {
+            translation: event.ray.origin.into(),
+            rotation: Quat::from_rotation_arc(Vec3::X, event.ray.dir.normalize().into()),
+            scale: Vec3::new(event.ray.dir.norm(), 1., 1.),
+        };
+        *visibility = Visibility::Inherited;
+        if let Some(material) = materials.get_mut(material) {
+            material.start_time = time.elapsed_seconds_wrapped();
+            material.color = event.kind.color();
+        }
     }
 }
 
@@ -190,29 +292,93 @@ fn laser_sound(
     mut sound_events: EventWriter<PlaySpatialAudioEvent>,
 ) {
     for event in events.read() {
+        if event.kind != TrailKind::Laser {
+            continue;
+        }
+
         sound_events.send(PlaySpatialAudioEvent::new(
             Sound::LaserFire,
-            event.0.origin.into(),
+            event.ray.origin.into(),
+        ));
+        // The beam is cut short where it hits an object or the terrain.
+        sound_events.send(PlaySpatialAudioEvent::new(
+            Sound::Impact,
+            event.ray.point_at(1.).into(),
         ));
     }
 }
 
-fn update(mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut Trail)>) {
-    for (entity, mut trail) in query.iter_mut() {
+fn laser_scorch(
+    terrain: TerrainCollider,
+    mut events: EventReader<LaserTrailEvent>,
+    mut decal_events: EventWriter<SpawnDecalEvent>,
+) {
+    for event in events.read() {
+        if event.kind != TrailKind::Laser {
+            continue;
+        }
+
+        // The trail ends at `ray.origin + ray.dir`, i.e. at TOI 1.
+        let Some(intersection) = terrain.cast_ray(&event.ray, 1. + SCORCH_TOLERANCE) else {
+            continue;
+        };
+        if intersection.toi < 1. - SCORCH_TOLERANCE {
+            continue;
+        }
+
+        decal_events.send(SpawnDecalEvent::circle(
+            DecalKind::Scorch,
+            event.ray.point_at(intersection.toi).to_flat(),
+            SCORCH_RADIUS,
+        ));
+    }
+}
+
+fn update(time: Res<Time>, mut query: Query<(&mut Trail, &mut Visibility)>) {
+    for (mut trail, mut visibility) in query.iter_mut() {
+        if trail.finished() {
+            continue;
+        }
+
         trail.tick(time.delta());
         if trail.finished() {
-            commands.entity(entity).despawn();
+            *visibility = Visibility::Hidden;
         }
     }
 }
 
-fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<TrailMaterial>>,
+) {
     let mesh = meshes.add(generate_trail_mesh());
-    commands.insert_resource(MeshHandle(mesh));
+
+    let trails = (0..TRAIL_POOL_SIZE)
+        .map(|_| {
+            commands
+                .spawn((
+                    MaterialMeshBundle::<TrailMaterial> {
+                        mesh: mesh.clone(),
+                        // Each trail fades out independently, therefore it
+                        // needs its own material.
+                        material: materials.add(TrailMaterial::new(0., LASER_COLOR)),
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    },
+                    Trail::finished_trail(),
+                    DespawnOnGameExit,
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                ))
+                .id()
+        })
+        .collect();
+    commands.insert_resource(TrailPool::new(trails));
 }
 
 fn cleanup(mut commands: Commands) {
-    commands.remove_resource::<MeshHandle>();
+    commands.remove_resource::<TrailPool>();
 }
 
 /// This generates a trail mesh starting at (0, 0, 0) and pointing towards +X
@@ -267,3 +433,18 @@ fn generate_trail_mesh() -> Mesh {
     mesh.insert_indices(indices);
     mesh
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool() {
+        let entities: Vec<Entity> = (0..3).map(Entity::from_raw).collect();
+        let mut pool = TrailPool::new(entities.clone());
+        assert_eq!(pool.take(), entities[0]);
+        assert_eq!(pool.take(), entities[1]);
+        assert_eq!(pool.take(), entities[2]);
+        assert_eq!(pool.take(), entities[0]);
+    }
+}
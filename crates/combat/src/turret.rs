@@ -0,0 +1,223 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use bevy::prelude::*;
+use de_core::gamestate::GameState;
+use de_objects::{LaserCannon, SubNode, SubNodes};
+use de_uom::{Quantity, RadianPerSecond, Second};
+
+use crate::{attack::Attacking, AttackingSet};
+
+/// Maximum turret rotation speed.
+const MAX_YAW_SPEED: RadianPerSecond = Quantity::new_unchecked(PI);
+/// Maximum barrel elevation speed.
+const MAX_PITCH_SPEED: RadianPerSecond = Quantity::new_unchecked(FRAC_PI_2);
+/// Minimum and maximum barrel elevation in radians.
+const PITCH_RANGE: (f32, f32) = (-0.3, 1.2);
+/// A cannon is aimed once both yaw and pitch differ by at most this angle
+/// (in radians) from the desired direction.
+const AIM_TOLERANCE: f32 = 0.05;
+
+pub(crate) struct TurretPlugin;
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                init,
+                aim.in_set(AttackingSet::Aim).after(AttackingSet::Charge),
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Articulated cannon of an object. Cannons with this component fire only once
+/// aimed at the target.
+#[derive(Component)]
+pub(crate) struct Aim {
+    turret: Option<Articulated>,
+    barrel: Option<Articulated>,
+    yaw: f32,
+    pitch: f32,
+    on_target: bool,
+}
+
+impl Aim {
+    /// Returns true if the cannon points towards the desired direction.
+    pub(crate) fn on_target(&self) -> bool {
+        self.on_target
+    }
+
+    /// Rotates the cannon towards a desired direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - time elapsed since the last update.
+    ///
+    /// * `direction` - desired cannon direction in the object local
+    ///   coordinates or None if the cannon should return to its rest position.
+    fn update(&mut self, delta: Second, direction: Option<Vec3>) {
+        let (yaw, pitch) = match direction {
+            Some(direction) => {
+                let yaw = if self.turret.is_some() {
+                    (-direction.z).atan2(direction.x)
+                } else {
+                    0.
+                };
+                let pitch = if self.barrel.is_some() {
+                    let flat = Vec2::new(direction.x, direction.z).length();
+                    direction.y.atan2(flat).clamp(PITCH_RANGE.0, PITCH_RANGE.1)
+                } else {
+                    0.
+                };
+                (yaw, pitch)
+            }
+            None => (0., 0.),
+        };
+
+        let yaw_diff = normalize_angle(yaw - self.yaw);
+        let max_yaw_delta = MAX_YAW_SPEED * delta;
+        self.yaw = normalize_angle(self.yaw + yaw_diff.clamp(-max_yaw_delta, max_yaw_delta));
+
+        let max_pitch_delta = MAX_PITCH_SPEED * delta;
+        self.pitch += (pitch - self.pitch).clamp(-max_pitch_delta, max_pitch_delta);
+
+        self.on_target = direction.is_some()
+            && normalize_angle(yaw - self.yaw).abs() <= AIM_TOLERANCE
+            && (pitch - self.pitch).abs() <= AIM_TOLERANCE;
+    }
+}
+
+/// A model node rotated relative to its rest (original) rotation.
+struct Articulated {
+    entity: Entity,
+    rest: Quat,
+}
+
+type NewArticulated<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static SubNodes, Option<&'static mut Aim>),
+    (Changed<SubNodes>, With<LaserCannon>),
+>;
+
+fn init(mut commands: Commands, mut objects: NewArticulated, nodes: Query<&Transform>) {
+    for (entity, sub_nodes, aim) in objects.iter_mut() {
+        let articulated = |node: SubNode| {
+            sub_nodes.get(node).and_then(|entity| {
+                nodes.get(entity).ok().map(|transform| Articulated {
+                    entity,
+                    rest: transform.rotation,
+                })
+            })
+        };
+
+        let turret = articulated(SubNode::Turret);
+        let barrel = articulated(SubNode::Barrel);
+
+        // Sub-nodes change when the model scene is re-spawned (e.g. after a
+        // change of its detail level), the aim is kept.
+        match aim {
+            Some(mut aim) => {
+                aim.turret = turret;
+                aim.barrel = barrel;
+            }
+            None => {
+                commands.entity(entity).insert(Aim {
+                    turret,
+                    barrel,
+                    yaw: 0.,
+                    pitch: 0.,
+                    on_target: false,
+                });
+            }
+        }
+    }
+}
+
+fn aim(
+    time: Res<Time>,
+    mut objects: Query<(&Transform, &mut Aim, Option<&Attacking>)>,
+    mut nodes: Query<&mut Transform, Without<Aim>>,
+) {
+    for (transform, mut aim, attacking) in objects.iter_mut() {
+        let direction = attacking
+            .and_then(|attacking| attacking.direction())
+            .map(|direction| transform.rotation.inverse() * direction);
+        aim.update(Second::try_from(time.delta_seconds()).unwrap(), direction);
+
+        if let Some(turret) = aim.turret.as_ref() {
+            if let Ok(mut node) = nodes.get_mut(turret.entity) {
+                node.rotation = turret.rest * Quat::from_rotation_y(aim.yaw);
+            }
+        }
+        if let Some(barrel) = aim.barrel.as_ref() {
+            if let Ok(mut node) = nodes.get_mut(barrel.entity) {
+                node.rotation = barrel.rest * Quat::from_rotation_z(aim.pitch);
+            }
+        }
+    }
+}
+
+fn normalize_angle(mut angle: f32) -> f32 {
+    angle %= TAU;
+    if angle > PI {
+        angle -= TAU;
+    } else if angle <= -PI {
+        angle += TAU
+    }
+    angle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aim() {
+        let mut aim = Aim {
+            turret: Some(Articulated {
+                entity: Entity::PLACEHOLDER,
+                rest: Quat::IDENTITY,
+            }),
+            barrel: Some(Articulated {
+                entity: Entity::PLACEHOLDER,
+                rest: Quat::IDENTITY,
+            }),
+            yaw: 0.,
+            pitch: 0.,
+            on_target: false,
+        };
+
+        // Target behind the object and slightly above.
+        let direction = Vec3::new(-1., 0.2, -0.01).normalize();
+        aim.update(Second::new(0.5), Some(direction));
+        assert!(!aim.on_target());
+        assert!((aim.yaw - FRAC_PI_2).abs() < 1e-6);
+
+        aim.update(Second::new(0.6), Some(direction));
+        assert!(aim.on_target());
+        assert!((aim.yaw - PI).abs() < 0.02);
+        assert!((aim.pitch - 0.2f32.atan()).abs() < 0.02);
+
+        aim.update(Second::new(0.1), None);
+        assert!(!aim.on_target());
+    }
+
+    #[test]
+    fn test_aim_without_turret() {
+        let mut aim = Aim {
+            turret: None,
+            barrel: None,
+            yaw: 0.,
+            pitch: 0.,
+            on_target: false,
+        };
+
+        aim.update(Second::new(0.01), Some(Vec3::new(0., 1., 1.).normalize()));
+        assert!(aim.on_target());
+        assert_eq!(aim.yaw, 0.);
+        assert_eq!(aim.pitch, 0.);
+    }
+}
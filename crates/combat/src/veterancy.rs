@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::{self, Active, ObjectTypeComponent},
+    player::PlayerComponent,
+};
+use de_objects::{ArtilleryCannon, Health, InitialHealths, LaserCannon};
+use de_signs::{StatusIcon, StatusIconsAppExt, UpdateStatusIconEvent};
+use de_types::{objects::ObjectType, player::Player};
+
+use crate::health::{HealthSet, LocalUpdateHealthEvent};
+
+const VETERAN_ICON: StatusIcon = StatusIcon::new("rank_veteran");
+const ELITE_ICON: StatusIcon = StatusIcon::new("rank_elite");
+const HEROIC_ICON: StatusIcon = StatusIcon::new("rank_heroic");
+
+pub(crate) struct VeterancyPlugin;
+
+impl Plugin for VeterancyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_status_icon(VETERAN_ICON)
+            .register_status_icon(ELITE_ICON)
+            .register_status_icon(HEROIC_ICON)
+            .add_event::<KillEvent>()
+            .add_systems(
+                Update,
+                (
+                    init,
+                    promote
+                        .run_if(on_event::<KillEvent>())
+                        .after(HealthSet::Update),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+/// This event is sent when a locally simulated entity destroys an object of
+/// a player.
+#[derive(Event)]
+pub(crate) struct KillEvent {
+    killer: Entity,
+    victim: Player,
+}
+
+impl KillEvent {
+    /// # Arguments
+    ///
+    /// * `killer` - the entity which dealt the final blow.
+    ///
+    /// * `victim` - owner of the destroyed object.
+    pub(crate) fn new(killer: Entity, victim: Player) -> Self {
+        Self { killer, victim }
+    }
+}
+
+/// Rank of a unit gained by destroying enemy objects. Higher ranks improve
+/// combat capabilities of the unit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    #[default]
+    Recruit,
+    Veteran,
+    Elite,
+    Heroic,
+}
+
+impl Rank {
+    fn from_kills(kills: u32) -> Self {
+        match kills {
+            0..=2 => Self::Recruit,
+            3..=7 => Self::Veteran,
+            8..=14 => Self::Elite,
+            _ => Self::Heroic,
+        }
+    }
+
+    /// Multiplier of damage dealt by the unit.
+    pub fn damage(self) -> f32 {
+        match self {
+            Self::Recruit => 1.,
+            Self::Veteran => 1.1,
+            Self::Elite => 1.2,
+            Self::Heroic => 1.35,
+        }
+    }
+
+    /// Multiplier of the rate of fire (i.e. of the speed of charging and
+    /// reloading) of the unit.
+    pub fn rate_of_fire(self) -> f32 {
+        match self {
+            Self::Recruit => 1.,
+            Self::Veteran => 1.1,
+            Self::Elite => 1.2,
+            Self::Heroic => 1.3,
+        }
+    }
+
+    /// Multiplier of the maximum health of the unit.
+    pub fn max_health(self) -> f32 {
+        match self {
+            Self::Recruit => 1.,
+            Self::Veteran => 1.1,
+            Self::Elite => 1.25,
+            Self::Heroic => 1.4,
+        }
+    }
+
+    fn icon(self) -> Option<StatusIcon> {
+        match self {
+            Self::Recruit => None,
+            Self::Veteran => Some(VETERAN_ICON),
+            Self::Elite => Some(ELITE_ICON),
+            Self::Heroic => Some(HEROIC_ICON),
+        }
+    }
+}
+
+/// Experience of a locally simulated armed unit.
+///
+/// Veterancy is tracked only by the game instance simulating the unit, thus
+/// ranks (and rank icons) of units simulated by other players are not known.
+#[derive(Component, Default)]
+pub struct Veterancy {
+    kills: u32,
+}
+
+impl Veterancy {
+    /// Number of enemy objects destroyed by the unit.
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    pub fn rank(&self) -> Rank {
+        Rank::from_kills(self.kills)
+    }
+
+    /// Records a destroyed enemy object. Returns true if the rank changed.
+    fn record_kill(&mut self) -> bool {
+        let rank = self.rank();
+        self.kills += 1;
+        self.rank() != rank
+    }
+}
+
+type NewUnits<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    (
+        Added<Active>,
+        With<objects::Local>,
+        Or<(With<LaserCannon>, With<ArtilleryCannon>)>,
+    ),
+>;
+
+fn init(mut commands: Commands, units: NewUnits) {
+    for entity in units.iter() {
+        commands.entity(entity).insert(Veterancy::default());
+    }
+}
+
+fn promote(
+    config: Res<GameConfig>,
+    healths: Res<InitialHealths>,
+    mut events: EventReader<KillEvent>,
+    mut units: Query<(
+        &PlayerComponent,
+        &ObjectTypeComponent,
+        &mut Veterancy,
+        &mut Health,
+    )>,
+    mut health_events: EventWriter<LocalUpdateHealthEvent>,
+    mut icon_events: EventWriter<UpdateStatusIconEvent>,
+) {
+    for event in events.read() {
+        let Ok((&player, &object_type, mut veterancy, mut health)) = units.get_mut(event.killer)
+        else {
+            continue;
+        };
+        // Friendly fire is not rewarded.
+        if config.teams().allied(*player, event.victim) {
+            continue;
+        }
+
+        let previous = veterancy.rank();
+        if !veterancy.record_kill() {
+            continue;
+        }
+        let rank = veterancy.rank();
+        info!("{:?} has been promoted to {rank:?}.", event.killer);
+
+        if let Some(icon) = previous.icon() {
+            icon_events.send(UpdateStatusIconEvent::new(event.killer, icon, false));
+        }
+        if let Some(icon) = rank.icon() {
+            icon_events.send(UpdateStatusIconEvent::new(event.killer, icon, true));
+        }
+
+        let ObjectType::Active(active_type) = *object_type else {
+            continue;
+        };
+        // The gained maximum health is granted to the unit right away.
+        let max = rank.max_health() * healths.health(active_type).max();
+        let gain = max - health.max();
+        health.set_max(max);
+        if gain > 0. && !health.destroyed() {
+            health_events.send(LocalUpdateHealthEvent::new(event.killer, gain));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_veterancy() {
+        let mut veterancy = Veterancy::default();
+        assert_eq!(veterancy.rank(), Rank::Recruit);
+        assert!(!veterancy.record_kill());
+        assert!(!veterancy.record_kill());
+        assert!(veterancy.record_kill());
+        assert_eq!(veterancy.kills(), 3);
+        assert_eq!(veterancy.rank(), Rank::Veteran);
+
+        assert_eq!(Rank::from_kills(8), Rank::Elite);
+        assert_eq!(Rank::from_kills(100), Rank::Heroic);
+        assert!(Rank::Heroic.damage() > Rank::Elite.damage());
+    }
+}
@@ -37,7 +37,6 @@ pub fn derive_config(input: TokenStream) -> TokenStream {
     let mut config_fields: Vec<ConfigField> = Vec::new();
 
     let struct_publicity = &input.vis;
-    let mut is_finite_check = false;
 
     if let Data::Struct(data_struct) = data {
         if let Fields::Named(fields) = &data_struct.fields {
@@ -53,6 +52,7 @@ pub fn derive_config(input: TokenStream) -> TokenStream {
                 let field_type = &field_info.field_type;
 
                 let inner_check_fns = &mut field_info.check_fns;
+                let mut is_finite_check = false;
                 for attr in &field.attrs {
                     // get the check attribute with closures like this: #[check(|v| v > 0)]
                     if attr.path.is_ident("check") {
@@ -66,18 +66,18 @@ pub fn derive_config(input: TokenStream) -> TokenStream {
                             return error;
                         };
                     } else if attr.path.is_ident("is_finite") {
-                        is_finite_check = !is_finite_check;
+                        is_finite_check = true;
                     }
+                }
 
-                    if is_finite_check {
-                        let check_fn = Some(quote! {
-                            (|#field_name: &#field_type| {
-                                Ok(ensure!(#field_name.is_finite(), "`{}` is not finite. It must be a finite Number with type: {}", stringify!(#field_name), stringify!(#field_type)))
-                            })
-                        });
+                if is_finite_check {
+                    let check_fn = Some(quote! {
+                        (|#field_name: &#field_type| {
+                            Ok(ensure!(#field_name.is_finite(), "`{}` is not finite. It must be a finite Number with type: {}", stringify!(#field_name), stringify!(#field_type)))
+                        })
+                    });
 
-                        inner_check_fns.push(check_fn)
-                    }
+                    inner_check_fns.push(check_fn)
                 }
 
                 config_fields.push(field_info);
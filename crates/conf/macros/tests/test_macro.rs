@@ -33,6 +33,7 @@ pub struct Camera {
     #[ensure(*touchpad_zoom_sensitivity > 1., "`touchpad_zoom_sensitivity` must be greater than 1.0.")]
     pub touchpad_zoom_sensitivity: f32,
 
+    #[is_finite]
     #[ensure(*rotation_sensitivity > 0., "`rotation_sensitivity` must be greater than 0.0.")]
     pub rotation_sensitivity: f32,
 }
@@ -2,13 +2,20 @@
 //! This module implements final (i.e. parsed and validated) game configuration
 //! objects and their building from persistent configuration.
 
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
 
 use anyhow::{ensure, Context, Error, Result};
 use async_std::path::Path;
+use bevy::render::color::Color;
 use conf_macros::Config;
+use de_core::notification::NotificationKind;
+use de_types::player::Player;
 use de_uom::{LogicalPixel, Metre};
 use serde::{Deserialize, Serialize};
+use tracing::Level;
 use url::Url;
 
 use crate::bundle_config;
@@ -22,6 +29,9 @@ pub struct MultiplayerConf {
     #[ensure(lobby.scheme() == "http", "Only `http` scheme is allowed for `lobby`.")]
     lobby: Url,
     connector: SocketAddr,
+
+    #[ensure(*start_countdown <= 60, "`start_countdown` must be smaller or equal to 60.")]
+    start_countdown: u8,
 }
 
 #[derive(Deserialize, Serialize, Config, Debug, Clone)]
@@ -65,9 +75,152 @@ pub struct AudioConf {
     #[ensure(*music_volume >= 0., "`music_volume` must be greater than or equal to 0.0.")]
     #[ensure(*music_volume <= 1., "`music_volume` must be smaller or equal to 1.0.")]
     music_volume: f32,
+
+    #[is_finite]
+    #[ensure(*ui_volume >= 0., "`ui_volume` must be greater than or equal to 0.0.")]
+    #[ensure(*ui_volume <= 1., "`ui_volume` must be smaller or equal to 1.0.")]
+    ui_volume: f32,
+
+    mute_when_unfocused: bool,
+
+    attenuation_curve: AttenuationCurve,
+
+    #[is_finite]
+    #[ensure(*max_audible_distance > 0., "`max_audible_distance` must be positive.")]
+    max_audible_distance: f32,
+
+    #[is_finite]
+    #[ensure(*panning_strength >= 0., "`panning_strength` must be greater than or equal to 0.0.")]
+    #[ensure(*panning_strength <= 1., "`panning_strength` must be smaller or equal to 1.0.")]
+    panning_strength: f32,
+
+    #[is_finite]
+    #[ensure(*zoom_attenuation >= 0., "`zoom_attenuation` must be greater than or equal to 0.0.")]
+    #[ensure(*zoom_attenuation <= 1., "`zoom_attenuation` must be smaller or equal to 1.0.")]
+    zoom_attenuation: f32,
+}
+
+#[derive(Deserialize, Serialize, Config, Debug, Clone)]
+pub struct GraphicsConf {
+    player_palette: PlayerPalette,
+
+    #[is_finite]
+    #[ensure(*ui_scale >= 0.5, "`ui_scale` must be greater than or equal to 0.5.")]
+    #[ensure(*ui_scale <= 2., "`ui_scale` must be smaller or equal to 2.0.")]
+    ui_scale: f32,
+
+    #[is_finite]
+    #[ensure(*hud_margin >= 0., "`hud_margin` must be greater than or equal to 0.0.")]
+    #[ensure(*hud_margin <= 10., "`hud_margin` must be smaller or equal to 10.0.")]
+    hud_margin: f32,
+}
+
+#[derive(Deserialize, Serialize, Config, Debug, Clone)]
+pub struct ChatConf {
+    profanity_filter: bool,
+}
+
+#[derive(Deserialize, Serialize, Config, Debug, Clone)]
+pub struct NotificationsConf {
+    under_attack: bool,
+    unit_lost: bool,
+    production_complete: bool,
+    player_eliminated: bool,
+    low_power: bool,
+    power_depleted: bool,
+    factory_idle: bool,
+    no_rally_point: bool,
+}
+
+#[derive(Deserialize, Serialize, Config, Debug, Clone)]
+pub struct LoggingConf {
+    level: LogLevel,
+
+    directory: Option<PathBuf>,
+
+    #[ensure(*max_file_size > 0, "`max_file_size` must be positive.")]
+    max_file_size: u32,
+
+    #[ensure(*max_files > 0, "`max_files` must be positive.")]
+    max_files: u32,
 }
 // --------------------
 
+/// Function of spatial sound volume on the distance from the listener.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttenuationCurve {
+    /// Volume is inversely proportional to the squared distance.
+    #[default]
+    InverseSquare,
+    /// Volume is inversely proportional to the distance.
+    Inverse,
+    /// Volume decreases linearly down to zero at the maximum audible
+    /// distance.
+    Linear,
+}
+
+/// Set of colors used to distinguish objects of individual players.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerPalette {
+    #[default]
+    Standard,
+    /// Palette distinguishable under the most common forms of color vision
+    /// deficiency.
+    Colorblind,
+}
+
+/// Maximum verbosity of logged events.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+const STANDARD_COLORS: [Color; Player::MAX_PLAYERS] = [
+    Color::rgb(0.1, 0.1, 0.9),
+    Color::rgb(0.1, 0.9, 0.1),
+    Color::rgb(0.9, 0.1, 0.1),
+    Color::rgb(0.9, 0.9, 0.1),
+];
+/// Subset of the Okabe–Ito palette, which remains distinguishable under the
+/// most common forms of color vision deficiency.
+const COLORBLIND_COLORS: [Color; Player::MAX_PLAYERS] = [
+    Color::rgb(0., 0.447, 0.698),
+    Color::rgb(0.902, 0.624, 0.),
+    Color::rgb(0., 0.620, 0.451),
+    Color::rgb(0.8, 0.475, 0.655),
+];
+
+impl PlayerPalette {
+    /// Returns the color of a player in the palette.
+    pub fn color(self, player: Player) -> Color {
+        let colors = match self {
+            Self::Standard => &STANDARD_COLORS,
+            Self::Colorblind => &COLORBLIND_COLORS,
+        };
+        colors[(player.to_num() - 1) as usize]
+    }
+}
+
 // ---- default implementations ----
 
 impl Default for MultiplayerConf {
@@ -75,6 +228,7 @@ impl Default for MultiplayerConf {
         Self {
             lobby: Url::parse("http://lobby.de-game.org:8080").unwrap(),
             connector: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(34, 159, 189, 173)), 8082),
+            start_countdown: 5,
         }
     }
 }
@@ -99,6 +253,56 @@ impl Default for AudioConf {
             master_volume: 1.,
             sound_volume: 1.,
             music_volume: 1.,
+            ui_volume: 1.,
+            mute_when_unfocused: false,
+            attenuation_curve: AttenuationCurve::default(),
+            max_audible_distance: 200.,
+            panning_strength: 1.,
+            zoom_attenuation: 0.5,
+        }
+    }
+}
+
+impl Default for GraphicsConf {
+    fn default() -> Self {
+        Self {
+            player_palette: PlayerPalette::default(),
+            ui_scale: 1.,
+            hud_margin: 0.,
+        }
+    }
+}
+
+impl Default for ChatConf {
+    fn default() -> Self {
+        Self {
+            profanity_filter: true,
+        }
+    }
+}
+
+impl Default for NotificationsConf {
+    fn default() -> Self {
+        Self {
+            under_attack: true,
+            unit_lost: true,
+            production_complete: true,
+            player_eliminated: true,
+            low_power: true,
+            power_depleted: true,
+            factory_idle: true,
+            no_rally_point: true,
+        }
+    }
+}
+
+impl Default for LoggingConf {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::default(),
+            directory: None,
+            max_file_size: 16,
+            max_files: 10,
         }
     }
 }
@@ -189,6 +393,12 @@ impl MultiplayerConf {
     pub fn connector(&self) -> SocketAddr {
         self.connector
     }
+
+    /// Number of seconds counted down before a created multiplayer game
+    /// starts once all players are ready.
+    pub fn start_countdown(&self) -> u8 {
+        self.start_countdown
+    }
 }
 
 impl AudioConf {
@@ -218,11 +428,148 @@ impl AudioConf {
     pub fn music_volume(&self) -> f32 {
         self.master_volume * self.music_volume
     }
+
+    /// Whether UI sounds are enabled (UI and master volume are above zero).
+    pub fn ui_enabled(&self) -> bool {
+        self.audio_enabled() && self.ui_volume > 0.
+    }
+
+    pub fn ui_volume(&self) -> f32 {
+        self.master_volume * self.ui_volume
+    }
+
+    /// Whether all audio should be muted while the game window is not
+    /// focused.
+    pub fn mute_when_unfocused(&self) -> bool {
+        self.mute_when_unfocused
+    }
+
+    /// Dependency of spatial sound volume on its distance from the camera.
+    pub fn attenuation_curve(&self) -> AttenuationCurve {
+        self.attenuation_curve
+    }
+
+    /// Spatial sounds further than this from the camera are not heard.
+    pub fn max_audible_distance(&self) -> Metre {
+        Metre::new(self.max_audible_distance)
+    }
+
+    /// Stereo panning of spatial sounds in range from 0 (mono) to 1 (full
+    /// panning).
+    pub fn panning_strength(&self) -> f32 {
+        self.panning_strength
+    }
+
+    /// Exponent of spatial sound volume reduction with camera zoom. Spatial
+    /// sounds are scaled by `(min_distance / distance)^zoom_attenuation`,
+    /// where `distance` is current camera distance and `min_distance` its
+    /// minimum.
+    pub fn zoom_attenuation(&self) -> f32 {
+        self.zoom_attenuation
+    }
+}
+
+impl GraphicsConf {
+    /// Colors used to tint objects of individual players.
+    pub fn player_palette(&self) -> PlayerPalette {
+        self.player_palette
+    }
+
+    /// Scale of the UI relative to its size on a 1080 logical pixels tall
+    /// window. The UI is further scaled proportionally to the window height.
+    pub fn ui_scale(&self) -> f32 {
+        self.ui_scale
+    }
+
+    /// Safe area margin, i.e. distance of HUD panels from screen edges, in
+    /// percent of the window size.
+    pub fn hud_margin(&self) -> f32 {
+        self.hud_margin
+    }
+}
+
+impl ChatConf {
+    /// Whether profane words in received chat messages should be censored.
+    pub fn profanity_filter(&self) -> bool {
+        self.profanity_filter
+    }
+}
+
+impl NotificationsConf {
+    /// Whether notifications of a kind are displayed and announced.
+    pub fn enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::UnderAttack => self.under_attack,
+            NotificationKind::UnitLost => self.unit_lost,
+            NotificationKind::ProductionComplete => self.production_complete,
+            NotificationKind::PlayerEliminated => self.player_eliminated,
+            NotificationKind::LowPower => self.low_power,
+            NotificationKind::PowerDepleted => self.power_depleted,
+            NotificationKind::FactoryIdle => self.factory_idle,
+            NotificationKind::NoRallyPoint => self.no_rally_point,
+        }
+    }
+}
+
+impl LoggingConf {
+    /// Default maximum verbosity of logged events. It is overridden by
+    /// `RUST_LOG` environment variable.
+    pub fn level(&self) -> Level {
+        self.level.into()
+    }
+
+    /// Directory to store log files into. Platform specific default is used
+    /// if None.
+    pub fn directory(&self) -> Option<&PathBuf> {
+        self.directory.as_ref()
+    }
+
+    /// Maximum size of a single log file in bytes. A new log file is started
+    /// once the current one would exceed this size.
+    pub fn max_file_size(&self) -> u64 {
+        u64::from(self.max_file_size) * 1024 * 1024
+    }
+
+    /// Maximum number of log files (including compressed rotated files)
+    /// retained in the log directory.
+    pub fn max_files(&self) -> usize {
+        self.max_files as usize
+    }
 }
 
 // Bundle configuration neatly into a single struct
 bundle_config!(
     camera: CameraConf: Camera, // Conf file -> Camera -> CameraConf
     multiplayer: MultiplayerConf: MultiplayerConf,  // Conf file -> MultiplayerConf
-    audio: AudioConf: AudioConf
+    audio: AudioConf: AudioConf,
+    graphics: GraphicsConf: GraphicsConf,
+    chat: ChatConf: ChatConf,
+    notifications: NotificationsConf: NotificationsConf,
+    logging: LoggingConf: LoggingConf
 );
+
+#[cfg(test)]
+mod tests {
+    use de_types::player::PlayerRange;
+
+    use super::*;
+
+    #[test]
+    fn test_player_color() {
+        for palette in [PlayerPalette::Standard, PlayerPalette::Colorblind] {
+            let colors: Vec<Color> = PlayerRange::up_to(Player::Player4)
+                .map(|player| palette.color(player))
+                .collect();
+            for (i, a) in colors.iter().enumerate() {
+                for b in &colors[i + 1..] {
+                    assert_ne!(a, b);
+                }
+            }
+        }
+
+        assert_eq!(
+            PlayerPalette::Colorblind.color(Player::Player2),
+            COLORBLIND_COLORS[1]
+        );
+    }
+}
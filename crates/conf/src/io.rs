@@ -27,9 +27,10 @@ mod tests {
     use std::net::{IpAddr, Ipv6Addr};
 
     use async_std::{path::PathBuf, task};
+    use de_core::notification::NotificationKind;
     use de_uom::Metre;
 
-    use crate::conf::Configuration;
+    use crate::conf::{AttenuationCurve, Configuration, PlayerPalette};
 
     #[test]
     fn test_load_conf() {
@@ -51,7 +52,25 @@ mod tests {
             IpAddr::V6(Ipv6Addr::LOCALHOST)
         );
         assert_eq!(conf.multiplayer().connector().port(), 8083);
+        assert_eq!(conf.multiplayer().start_countdown(), 3);
         assert_eq!(conf.camera().min_distance(), Metre::new(12.5));
         assert_eq!(conf.camera().max_distance(), Metre::new(250.));
+        assert_eq!(conf.graphics().player_palette(), PlayerPalette::Colorblind);
+        assert_eq!(conf.graphics().ui_scale(), 1.25);
+        assert_eq!(conf.graphics().hud_margin(), 0.);
+        assert_eq!(conf.audio().music_volume(), 0.5);
+        assert_eq!(conf.audio().ui_volume(), 0.25);
+        assert!(conf.audio().mute_when_unfocused());
+        assert_eq!(conf.audio().attenuation_curve(), AttenuationCurve::Linear);
+        assert_eq!(conf.audio().max_audible_distance(), Metre::new(150.));
+        assert_eq!(conf.audio().panning_strength(), 0.5);
+        assert_eq!(conf.audio().zoom_attenuation(), 0.5);
+        assert!(!conf.chat().profanity_filter());
+        assert!(!conf.notifications().enabled(NotificationKind::FactoryIdle));
+        assert!(conf.notifications().enabled(NotificationKind::LowPower));
+        assert_eq!(conf.logging().level(), tracing::Level::DEBUG);
+        assert!(conf.logging().directory().is_none());
+        assert_eq!(conf.logging().max_file_size(), 16 * 1024 * 1024);
+        assert_eq!(conf.logging().max_files(), 3);
     }
 }
@@ -8,10 +8,12 @@
 mod conf;
 mod io;
 mod macros;
+mod palette;
 mod plugin;
 
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 pub use conf::*;
+pub use palette::Palette;
 use plugin::ConfPlugin;
 
 pub struct ConfigPluginGroup;
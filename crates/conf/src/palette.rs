@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use de_types::player::Player;
+
+use crate::PlayerPalette;
+
+const STANDARD_HEALTH_COLOR: Color = Color::rgba(0.6, 1., 0.6, 0.75);
+const STANDARD_PROGRESS_COLOR: Color = Color::rgba(0.4, 0.75, 1., 0.75);
+/// Yellow and sky blue of the Okabe–Ito palette.
+const COLORBLIND_HEALTH_COLOR: Color = Color::rgba(0.941, 0.894, 0.259, 0.75);
+const COLORBLIND_PROGRESS_COLOR: Color = Color::rgba(0.337, 0.706, 0.914, 0.75);
+/// Shield bars are distinguished from other bars by brightness, thus the
+/// color is shared by all palettes.
+const SHIELD_COLOR: Color = Color::rgba(0.9, 0.9, 1., 0.75);
+
+/// Colors shared by all in-game visuals distinguishing players or showing
+/// object state, e.g. object tints, selection markers, minimap blips or
+/// health bars.
+///
+/// The resource is inserted once the configuration is loaded and it is based
+/// on the configured [`PlayerPalette`].
+#[derive(Resource, Clone, Debug)]
+pub struct Palette {
+    players: PlayerPalette,
+    health: Color,
+    progress: Color,
+}
+
+impl Palette {
+    pub fn new(players: PlayerPalette) -> Self {
+        let (health, progress) = match players {
+            PlayerPalette::Standard => (STANDARD_HEALTH_COLOR, STANDARD_PROGRESS_COLOR),
+            PlayerPalette::Colorblind => (COLORBLIND_HEALTH_COLOR, COLORBLIND_PROGRESS_COLOR),
+        };
+
+        Self {
+            players,
+            health,
+            progress,
+        }
+    }
+
+    /// Returns the color of a player.
+    pub fn player(&self, player: Player) -> Color {
+        self.players.color(player)
+    }
+
+    /// Color of object health bars.
+    pub fn health(&self) -> Color {
+        self.health
+    }
+
+    /// Color of object progress bars, e.g. manufacturing progress.
+    pub fn progress(&self) -> Color {
+        self.progress
+    }
+
+    /// Color of object shield bars.
+    pub fn shield(&self) -> Color {
+        SHIELD_COLOR
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(PlayerPalette::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette() {
+        for players in [PlayerPalette::Standard, PlayerPalette::Colorblind] {
+            let palette = Palette::new(players);
+            assert_eq!(
+                palette.player(Player::Player3),
+                players.color(Player::Player3)
+            );
+            assert_ne!(palette.health(), palette.progress());
+        }
+
+        assert_ne!(
+            Palette::new(PlayerPalette::Standard).health(),
+            Palette::new(PlayerPalette::Colorblind).health()
+        );
+    }
+}
@@ -3,20 +3,23 @@ use bevy::{
     tasks::{futures_lite::future, IoTaskPool, Task},
 };
 use de_core::fs::conf_dir;
-use de_core::state::AppState;
-use de_gui::ToastEvent;
+use de_core::{notification::EnabledNotifications, state::AppState};
+use de_gui::{GuiScale, ToastEvent};
 use iyes_progress::prelude::*;
 use tracing::error;
 
 use crate::macros::ConfigLoadError;
-use crate::Configuration;
+use crate::{Configuration, Palette};
 
 pub(super) struct ConfPlugin;
 
 impl Plugin for ConfPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::AppLoading), start_loading)
-            .add_systems(OnExit(AppState::AppLoading), cleanup)
+            .add_systems(
+                OnExit(AppState::AppLoading),
+                (cleanup, setup_gui_scale, setup_palette, setup_notifications),
+            )
             .add_systems(
                 Update,
                 poll_conf
@@ -33,6 +36,20 @@ fn cleanup(mut commands: Commands) {
     commands.remove_resource::<LoadingTask>();
 }
 
+fn setup_gui_scale(mut commands: Commands, conf: Res<Configuration>) {
+    commands.insert_resource(GuiScale::new(conf.graphics().ui_scale()));
+}
+
+fn setup_palette(mut commands: Commands, conf: Res<Configuration>) {
+    commands.insert_resource(Palette::new(conf.graphics().player_palette()));
+}
+
+fn setup_notifications(mut commands: Commands, conf: Res<Configuration>) {
+    commands.insert_resource(EnabledNotifications::new(|kind| {
+        conf.notifications().enabled(kind)
+    }));
+}
+
 fn start_loading(mut commands: Commands) {
     let task = IoTaskPool::get().spawn(async {
         let path = conf_dir().map_err(ConfigLoadError::from)?.join("conf.yaml");
@@ -0,0 +1,29 @@
+use std::{
+    env::{self, VarError},
+    str::FromStr,
+};
+
+use anyhow::Context;
+
+/// Load and parse the value of an environment variable.
+///
+/// # Arguments
+///
+/// * `name` - name of the environment variable to load.
+///
+/// * `default` - value to be returned if the environment variable is not
+///   set.
+pub(crate) fn var<T>(name: &str, default: T) -> anyhow::Result<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    match env::var(name) {
+        Ok(value) => T::from_str(value.as_str())
+            .with_context(|| format!("Failed to parse environment variable \"{name}\"")),
+        Err(VarError::NotPresent) => Ok(default),
+        Err(error) => {
+            Err(error).with_context(|| format!("Failed to load environment variable \"{name}\""))
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
 use async_std::{
     channel::{Receiver, Sender},
+    future::timeout,
     task,
 };
-use de_messages::{FromGame, JoinError, Readiness, ToGame};
+use de_messages::{FromGame, JoinError, MapHashNet, Readiness, ToGame};
 use de_net::{OutPackage, Peers, Reliability};
 use tracing::{error, info, warn};
 
@@ -12,15 +16,20 @@ use super::{
     message::{InMessage, MessageMeta},
     state::{GameState, JoinError as JoinErrorInner},
 };
-use crate::clients::Clients;
+use crate::{clients::Clients, lan::LanGames};
+
+/// Time between two consecutive [`FromGame::Countdown`] messages.
+const COUNTDOWN_TICK: Duration = Duration::from_secs(1);
 
 pub(super) struct GameProcessor {
     port: u16,
     owner: SocketAddr,
+    countdown: Option<Countdown>,
     messages: Receiver<InMessage<ToGame>>,
     outputs: Sender<OutPackage>,
     state: GameState,
     clients: Clients,
+    lan_games: LanGames,
 }
 
 impl GameProcessor {
@@ -31,14 +40,17 @@ impl GameProcessor {
         outputs: Sender<OutPackage>,
         state: GameState,
         clients: Clients,
+        lan_games: LanGames,
     ) -> Self {
         Self {
             port,
             owner,
+            countdown: None,
             messages,
             outputs,
             state,
             clients,
+            lan_games,
         }
     }
 
@@ -51,7 +63,9 @@ impl GameProcessor {
         // Wait a little to ensure that game creation message (send from main
         // server) is delivered first.
         task::sleep(Duration::from_millis(100)).await;
-        self.join(self.owner).await.unwrap();
+        let map_hash = self.state.map_hash().await;
+        self.join(self.owner, map_hash).await.unwrap();
+        self.announce().await;
 
         loop {
             if self.outputs.is_closed() {
@@ -62,7 +76,24 @@ impl GameProcessor {
                 break;
             }
 
-            let Ok(message) = self.messages.recv().await else {
+            let message = match self.countdown.as_ref() {
+                Some(countdown) => {
+                    let wait = countdown
+                        .next_tick
+                        .saturating_duration_since(Instant::now());
+                    match timeout(wait, self.messages.recv()).await {
+                        Ok(message) => message,
+                        Err(_) => {
+                            self.tick_countdown().await;
+                            self.announce().await;
+                            continue;
+                        }
+                    }
+                }
+                None => self.messages.recv().await,
+            };
+
+            let Ok(message) = message else {
                 error!(
                     "Game message channel on port {} is unexpectedly closed.",
                     self.port
@@ -78,8 +109,8 @@ impl GameProcessor {
                 ToGame::Ping(id) => {
                     self.process_ping(message.meta(), *id).await;
                 }
-                ToGame::Join => {
-                    self.process_join(message.meta()).await;
+                ToGame::Join(map_hash) => {
+                    self.process_join(message.meta(), *map_hash).await;
                 }
                 ToGame::Leave => {
                     self.process_leave(message.meta()).await;
@@ -93,18 +124,30 @@ impl GameProcessor {
                 info!("Everybody disconnected, quitting...");
                 break;
             }
+
+            self.update_countdown().await;
+            self.announce().await;
         }
 
+        self.lan_games.remove(self.port).await;
+
         info!(
             "Game server message handler on port {} finished.",
             self.port
         );
     }
 
+    /// Updates state of the game in the LAN games registry.
+    async fn announce(&mut self) {
+        let num_players = self.state.len().await;
+        let open = self.state.readiness().await == Readiness::NotReady;
+        self.lan_games.update(self.port, num_players, open).await;
+    }
+
     /// Returns true if the massage should be ignored and further handles such
     /// messages.
     async fn handle_ignore(&self, message: &InMessage<ToGame>) -> bool {
-        if matches!(message.message(), ToGame::Join | ToGame::Leave) {
+        if matches!(message.message(), ToGame::Join(_) | ToGame::Leave) {
             // Join must be excluded from the condition because of the
             // chicken and egg problem.
             //
@@ -153,7 +196,7 @@ impl GameProcessor {
     }
 
     /// Process connect message.
-    async fn process_join(&mut self, meta: MessageMeta) {
+    async fn process_join(&mut self, meta: MessageMeta, map_hash: MapHashNet) {
         if let Err(err) = self.clients.reserve(meta.source).await {
             warn!("Join request error: {err}");
             self.send(
@@ -165,7 +208,7 @@ impl GameProcessor {
             return;
         }
 
-        match self.join(meta.source).await {
+        match self.join(meta.source, map_hash).await {
             Ok(_) => {
                 self.clients.set(meta.source, self.port).await;
             }
@@ -213,13 +256,27 @@ impl GameProcessor {
                         )
                         .await;
                     }
+                    JoinErrorInner::MapMismatch => {
+                        warn!(
+                            "Player {:?} could not join game on port {} because of a different \
+                             map.",
+                            meta.source, self.port
+                        );
+
+                        self.send(
+                            &FromGame::JoinError(JoinError::MapMismatch),
+                            Reliability::Unordered,
+                            meta.source,
+                        )
+                        .await;
+                    }
                 }
             }
         }
     }
 
-    async fn join(&mut self, addr: SocketAddr) -> Result<(), JoinErrorInner> {
-        let id = self.state.add(addr).await?;
+    async fn join(&mut self, addr: SocketAddr, map_hash: MapHashNet) -> Result<(), JoinErrorInner> {
+        let id = self.state.add(addr, map_hash).await?;
         info!(
             "Player {id} on {addr:?} just joined game on port {}.",
             self.port
@@ -232,6 +289,18 @@ impl GameProcessor {
             Some(addr),
         )
         .await;
+
+        for (peer, readiness) in self.state.players_readiness(Some(addr)).await {
+            if readiness != Readiness::NotReady {
+                self.send(
+                    &FromGame::PeerReadiness(peer, readiness),
+                    Reliability::SemiOrdered,
+                    addr,
+                )
+                .await;
+            }
+        }
+
         Ok(())
     }
 
@@ -266,8 +335,20 @@ impl GameProcessor {
     }
 
     async fn process_readiness(&mut self, meta: MessageMeta, readiness: Readiness) {
+        let started = self.state.readiness().await != Readiness::NotReady;
         match self.state.update_readiness(meta.source, readiness).await {
             Ok(progressed) => {
+                if !started {
+                    if let Some(id) = self.state.id(meta.source).await {
+                        self.send_all(
+                            &FromGame::PeerReadiness(id, readiness),
+                            Reliability::SemiOrdered,
+                            Some(meta.source),
+                        )
+                        .await;
+                    }
+                }
+
                 if progressed {
                     self.send_all(
                         &FromGame::GameReadiness(readiness),
@@ -284,6 +365,53 @@ impl GameProcessor {
         }
     }
 
+    /// Starts or cancels the countdown to the game start based on current
+    /// readiness of the players.
+    async fn update_countdown(&mut self) {
+        let all_ready = self.state.all_ready().await;
+
+        if all_ready && self.countdown.is_none() {
+            info!("Starting countdown of game on port {}.", self.port);
+            self.countdown = Some(Countdown::new(self.state.countdown().await));
+            self.tick_countdown().await;
+        } else if !all_ready && self.countdown.is_some() {
+            info!("Cancelling countdown of game on port {}.", self.port);
+            self.countdown = None;
+            self.send_all(
+                &FromGame::CountdownCancelled,
+                Reliability::SemiOrdered,
+                None,
+            )
+            .await;
+        }
+    }
+
+    /// Announces the remaining time of the countdown or starts the game once
+    /// the countdown is over.
+    async fn tick_countdown(&mut self) {
+        let Some(countdown) = self.countdown.as_mut() else {
+            return;
+        };
+
+        if countdown.remaining == 0 {
+            self.countdown = None;
+            if self.state.start().await {
+                info!("Game on port {} started.", self.port);
+                self.send_all(
+                    &FromGame::GameReadiness(Readiness::Ready),
+                    Reliability::SemiOrdered,
+                    None,
+                )
+                .await;
+            }
+        } else {
+            let message = FromGame::Countdown(countdown.remaining);
+            countdown.tick();
+            self.send_all(&message, Reliability::SemiOrdered, None)
+                .await;
+        }
+    }
+
     /// Send a reliable message to all players of the game.
     ///
     /// # Arguments
@@ -312,3 +440,24 @@ impl GameProcessor {
         let _ = self.outputs.send(message).await;
     }
 }
+
+/// A running countdown to the game start.
+struct Countdown {
+    /// Number of seconds to be announced at the next tick.
+    remaining: u8,
+    next_tick: Instant,
+}
+
+impl Countdown {
+    fn new(secs: u8) -> Self {
+        Self {
+            remaining: secs,
+            next_tick: Instant::now(),
+        }
+    }
+
+    fn tick(&mut self) {
+        self.remaining -= 1;
+        self.next_tick += COUNTDOWN_TICK;
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use de_messages::{EntityNet, ToPlayers};
+use de_types::{player::Player, projection::ToFlat};
+use glam::{IVec2, Vec2, Vec3};
+
+/// Transform updates of entities within this distance (in meters) from the
+/// viewport of a player are of interest to the player.
+const VIEWPORT_RADIUS: f32 = 120.;
+/// Size (in meters) of cells of the coarse spatial index of entities.
+/// Transform updates of entities in the same or in a neighbouring cell as an
+/// entity of a player are of interest to the player.
+const CELL_SIZE: f32 = 60.;
+/// Minimum time between two transform updates of an entity delivered to a
+/// player to whom the entity is not of interest.
+const COARSE_PERIOD: Duration = Duration::from_secs(4);
+
+/// Interest management of player messages. Each player receives all transform
+/// updates of entities near its camera or near entities simulated by the
+/// player. Updates of other entities are delivered via a coarse low-rate
+/// channel.
+///
+/// Only transform updates are subject to the interest management. All other
+/// messages are always delivered.
+#[derive(Default)]
+pub(super) struct Interest {
+    viewports: AHashMap<Player, Vec2>,
+    index: CellIndex,
+    /// Time of the last delivered coarse update of an entity to each player.
+    coarse: AHashMap<EntityNet, AHashMap<Player, Instant>>,
+}
+
+impl Interest {
+    /// Updates the interest state based on a message sent by a player.
+    pub(super) fn update(&mut self, source: Player, message: &ToPlayers) {
+        match message {
+            ToPlayers::Viewport(point) => {
+                self.viewports.insert(source, Vec2::from(*point));
+            }
+            ToPlayers::Spawn {
+                entity, transform, ..
+            }
+            | ToPlayers::Transform { entity, transform } => {
+                let position = Vec3::from(transform.translation()).to_flat();
+                if position.is_finite() {
+                    self.index.place(*entity, position);
+                }
+            }
+            ToPlayers::Despawn { entity } => {
+                self.index.remove(*entity);
+                self.coarse.remove(entity);
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns true if the message should be delivered to a player now.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - the player receiving the message.
+    ///
+    /// * `message` - the delivered message.
+    ///
+    /// * `time` - current time.
+    pub(super) fn admit(&mut self, target: Player, message: &ToPlayers, time: Instant) -> bool {
+        let ToPlayers::Transform { entity, transform } = message else {
+            return true;
+        };
+
+        let position = Vec3::from(transform.translation()).to_flat();
+        if self.interesting(target, position) {
+            return true;
+        }
+
+        let deliveries = self.coarse.entry(*entity).or_default();
+        if deliveries
+            .get(&target)
+            .is_some_and(|&last| time < last + COARSE_PERIOD)
+        {
+            return false;
+        }
+
+        deliveries.insert(target, time);
+        true
+    }
+
+    /// Returns true if a point is near the viewport of a player or near an
+    /// entity simulated by the player.
+    fn interesting(&self, target: Player, point: Vec2) -> bool {
+        // Nothing is known about the player's camera (yet), thus full rate
+        // updates are delivered to avoid any visible degradation.
+        let Some(viewport) = self.viewports.get(&target) else {
+            return true;
+        };
+
+        viewport.distance(point) <= VIEWPORT_RADIUS || self.index.nearby(target, point)
+    }
+}
+
+/// Coarse spatial index of entities. It keeps the number of entities
+/// simulated by each player in square cells.
+#[derive(Default)]
+struct CellIndex {
+    entities: AHashMap<EntityNet, IVec2>,
+    counts: AHashMap<(Player, IVec2), u32>,
+}
+
+impl CellIndex {
+    /// Inserts or moves an entity to a position.
+    fn place(&mut self, entity: EntityNet, position: Vec2) {
+        let cell = Self::cell(position);
+        match self.entities.insert(entity, cell) {
+            Some(old) if old == cell => return,
+            Some(old) => self.decrement(entity.player(), old),
+            None => (),
+        }
+
+        *self.counts.entry((entity.player(), cell)).or_default() += 1;
+    }
+
+    fn remove(&mut self, entity: EntityNet) {
+        if let Some(cell) = self.entities.remove(&entity) {
+            self.decrement(entity.player(), cell);
+        }
+    }
+
+    /// Returns true if there is an entity simulated by a player in the cell of
+    /// the position or in any of the eight neighbouring cells.
+    fn nearby(&self, player: Player, position: Vec2) -> bool {
+        let center = Self::cell(position);
+        (-1..=1).any(|x| {
+            (-1..=1).any(|y| {
+                self.counts
+                    .contains_key(&(player, center + IVec2::new(x, y)))
+            })
+        })
+    }
+
+    fn decrement(&mut self, player: Player, cell: IVec2) {
+        let key = (player, cell);
+        if let Some(count) = self.counts.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&key);
+            }
+        }
+    }
+
+    fn cell(position: Vec2) -> IVec2 {
+        (position / CELL_SIZE).floor().as_ivec2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use de_messages::NetEntityIndex;
+
+    use super::*;
+
+    fn entity(player: Player, index: u32) -> EntityNet {
+        EntityNet::new(player, NetEntityIndex::from(index))
+    }
+
+    #[test]
+    fn test_cell_index() {
+        let mut index = CellIndex::default();
+        let a = entity(Player::Player1, 1);
+        let b = entity(Player::Player1, 2);
+
+        index.place(a, Vec2::new(10., 10.));
+        index.place(b, Vec2::new(20., 10.));
+        assert!(index.nearby(Player::Player1, Vec2::new(-50., 100.)));
+        assert!(!index.nearby(Player::Player1, Vec2::new(-70., 100.)));
+        assert!(!index.nearby(Player::Player2, Vec2::new(10., 10.)));
+
+        index.place(a, Vec2::new(500., 500.));
+        assert!(index.nearby(Player::Player1, Vec2::new(10., 10.)));
+        index.remove(b);
+        assert!(!index.nearby(Player::Player1, Vec2::new(10., 10.)));
+        assert!(index.nearby(Player::Player1, Vec2::new(450., 530.)));
+
+        index.remove(a);
+        assert!(index.counts.is_empty());
+    }
+
+    #[test]
+    fn test_interesting() {
+        let mut interest = Interest::default();
+        let point = Vec2::new(1000., 1000.);
+        assert!(interest.interesting(Player::Player2, point));
+
+        interest.viewports.insert(Player::Player2, Vec2::ZERO);
+        assert!(!interest.interesting(Player::Player2, point));
+        assert!(interest.interesting(Player::Player2, Vec2::new(100., 0.)));
+
+        interest
+            .index
+            .place(entity(Player::Player2, 3), Vec2::new(990., 1010.));
+        assert!(interest.interesting(Player::Player2, point));
+        assert!(interest.interesting(Player::Player3, point));
+    }
+}
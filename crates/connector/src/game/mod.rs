@@ -1,15 +1,22 @@
 use std::net::SocketAddr;
 
 use async_std::{channel::bounded, task};
+use de_messages::MapHashNet;
 use de_net::{self, Socket};
 use de_types::player::Player;
+use tracing::error;
 
 use self::{greceiver::GameProcessor, state::GameState};
-use crate::clients::Clients;
+use crate::{
+    clients::Clients,
+    lan::LanGames,
+    recording::{Recorder, RecordingConf},
+};
 
 mod buffer;
 mod ereceiver;
 mod greceiver;
+mod interest;
 mod message;
 mod mreceiver;
 mod preceiver;
@@ -21,6 +28,9 @@ mod state;
 ///
 /// * `clients` - global clients tracker.
 ///
+/// * `lan_games` - registry of games announced in the local network. The
+///   game state is kept up to date in the registry if the game is announced.
+///
 /// * `socket` - socket to use for the game server.
 ///
 /// * `owner` - address of the creator of the game. This client will be
@@ -29,13 +39,38 @@ mod state;
 ///
 /// * `max_players` - maximum number of clients which may connect to the game
 ///   at the same time
+///
+/// * `map_hash` - hash of the map of the game. Clients with a different map
+///   are not allowed to join the game.
+///
+/// * `countdown` - number of seconds counted down before the game starts
+///   once all clients are ready.
+///
+/// * `recording` - configuration of game recording. The game is not recorded
+///   if None.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn startup(
     clients: Clients,
+    lan_games: LanGames,
     socket: Socket,
     owner: SocketAddr,
     max_players: Player,
+    map_hash: MapHashNet,
+    countdown: u8,
+    recording: Option<&RecordingConf>,
 ) {
     let port = socket.port();
+    let recorder = match recording {
+        Some(conf) => match Recorder::start(conf, port).await {
+            Ok(recorder) => Some(recorder),
+            Err(err) => {
+                error!("Failed to start recording of the game on port {port}: {err:?}");
+                None
+            }
+        },
+        None => None,
+    };
+
     let (outputs, inputs, errors) = de_net::startup(
         |t| {
             task::spawn(t);
@@ -49,7 +84,7 @@ pub(crate) async fn startup(
     let (players_sender, players_receiver) = bounded(16);
     task::spawn(mreceiver::run(port, inputs, server_sender, players_sender));
 
-    let state = GameState::new(max_players);
+    let state = GameState::new(max_players, map_hash, countdown);
     let server = GameProcessor::new(
         port,
         owner,
@@ -57,8 +92,15 @@ pub(crate) async fn startup(
         outputs.clone(),
         state.clone(),
         clients,
+        lan_games,
     );
     task::spawn(server.run());
 
-    task::spawn(preceiver::run(port, players_receiver, outputs, state));
+    task::spawn(preceiver::run(
+        port,
+        players_receiver,
+        outputs,
+        state,
+        recorder,
+    ));
 }
@@ -5,16 +5,20 @@ use de_messages::{BorrowedFromPlayers, FromGame, ToPlayers};
 use de_net::{OutPackage, PackageSender, Peers};
 use tracing::{error, info, warn};
 
-use super::{message::InMessage, state::GameState};
+use super::{interest::Interest, message::InMessage, state::GameState};
+use crate::recording::Recorder;
 
 pub(super) async fn run(
     port: u16,
     messages: Receiver<InMessage<ToPlayers>>,
     outputs: PackageSender,
     mut state: GameState,
+    mut recorder: Option<Recorder>,
 ) {
     info!("Starting game player package handler on port {port}...");
 
+    let mut interest = Interest::default();
+
     'main: loop {
         if messages.is_closed() {
             break;
@@ -54,9 +58,17 @@ pub(super) async fn run(
                 continue;
             };
 
+            interest.update(player_id, message.message());
+
             let out_message = BorrowedFromPlayers::new(player_id, message.message());
-            for buffer in state.lock().await.buffers_mut(Some(meta.source)) {
-                if let Err(err) = buffer.push(meta.reliability, &out_message, time) {
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(time, &out_message);
+            }
+            for slot in state.lock().await.slots_mut(Some(meta.source)) {
+                if !interest.admit(slot.id(), message.message(), time) {
+                    continue;
+                }
+                if let Err(err) = slot.buffer_mut().push(meta.reliability, &out_message, time) {
                     warn!("Could not encode player message, skipping: {err:?}");
                 }
             }
@@ -2,7 +2,7 @@ use std::{collections::hash_map::Entry, net::SocketAddr};
 
 use ahash::AHashMap;
 use async_std::sync::{Arc, RwLock, RwLockWriteGuard};
-use de_messages::Readiness;
+use de_messages::{MapHashNet, Readiness};
 use de_types::player::{Player, PlayerRange};
 use thiserror::Error;
 
@@ -14,9 +14,22 @@ pub(super) struct GameState {
 }
 
 impl GameState {
-    pub(super) fn new(max_players: Player) -> Self {
+    /// # Arguments
+    ///
+    /// * `max_players` - maximum number of players of the game.
+    ///
+    /// * `map_hash` - hash of the map of the game. Only players with the same
+    ///   map hash may join the game.
+    ///
+    /// * `countdown` - number of seconds counted down before the game starts
+    ///   once all players are ready.
+    pub(super) fn new(max_players: Player, map_hash: MapHashNet, countdown: u8) -> Self {
         Self {
-            inner: Arc::new(RwLock::new(GameStateInner::new(max_players))),
+            inner: Arc::new(RwLock::new(GameStateInner::new(
+                max_players,
+                map_hash,
+                countdown,
+            ))),
         }
     }
 
@@ -31,6 +44,32 @@ impl GameState {
         self.inner.read().await.is_empty()
     }
 
+    /// Returns number of players currently connected to the game.
+    pub(super) async fn len(&self) -> u8 {
+        self.inner.read().await.len()
+    }
+
+    /// Returns current readiness of the game as a whole.
+    pub(super) async fn readiness(&self) -> Readiness {
+        self.inner.read().await.readiness
+    }
+
+    /// Returns true if the game has not yet started and all connected players
+    /// are ready.
+    pub(super) async fn all_ready(&self) -> bool {
+        self.inner.read().await.all_ready()
+    }
+
+    /// Returns hash of the map of the game.
+    pub(super) async fn map_hash(&self) -> MapHashNet {
+        self.inner.read().await.map_hash
+    }
+
+    /// Returns number of seconds counted down before the game starts.
+    pub(super) async fn countdown(&self) -> u8 {
+        self.inner.read().await.countdown
+    }
+
     /// Returns true if a player with `addr` is connected to the game.
     pub(super) async fn contains(&self, addr: SocketAddr) -> bool {
         self.inner.read().await.contains(addr)
@@ -43,8 +82,18 @@ impl GameState {
     }
 
     /// Adds a player to the game and returns ID of the added player.
-    pub(super) async fn add(&mut self, addr: SocketAddr) -> Result<Player, JoinError> {
-        self.inner.write().await.add(addr)
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - address of the player.
+    ///
+    /// * `map_hash` - hash of the map reported by the player.
+    pub(super) async fn add(
+        &mut self,
+        addr: SocketAddr,
+        map_hash: MapHashNet,
+    ) -> Result<Player, JoinError> {
+        self.inner.write().await.add(addr, map_hash)
     }
 
     /// Removes a single player from the game. It returns state object of the
@@ -54,7 +103,8 @@ impl GameState {
     }
 
     /// Updates readiness of a single player. Whole game readiness is updated
-    /// once all players reach another readiness stage.
+    /// once all players reach another readiness stage. The game never
+    /// progresses to [`Readiness::Ready`] this way, see [`Self::start`].
     ///
     /// Returns true if game readiness progressed as a result (to the readiness
     /// of the player).
@@ -66,6 +116,26 @@ impl GameState {
         self.inner.write().await.update_readiness(addr, readiness)
     }
 
+    /// Progresses the game to [`Readiness::Ready`] if all players are ready.
+    ///
+    /// Returns true if game readiness progressed as a result.
+    pub(super) async fn start(&mut self) -> bool {
+        self.inner.write().await.start()
+    }
+
+    /// Returns ID and readiness of all or all but one players connected to
+    /// the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `exclude` - if not None, this player is not included.
+    pub(super) async fn players_readiness(
+        &self,
+        exclude: Option<SocketAddr>,
+    ) -> Vec<(Player, Readiness)> {
+        self.inner.read().await.players_readiness(exclude)
+    }
+
     /// Constructs and returns package targets which includes all or all but
     /// one players connected to the game.
     ///
@@ -94,18 +164,34 @@ impl<'a> GameStateGuard<'a> {
     ) -> impl Iterator<Item = &mut PlayerBuffer> {
         self.guard.buffers_mut(exclude)
     }
+
+    /// Returns an iterator over slots of all or all but one player.
+    ///
+    /// # Arguments
+    ///
+    /// * `exclude` - exclude this player from the iterator.
+    pub(super) fn slots_mut(
+        &mut self,
+        exclude: Option<SocketAddr>,
+    ) -> impl Iterator<Item = &mut PlayerSlot> {
+        self.guard.slots_mut(exclude)
+    }
 }
 
 struct GameStateInner {
     available_ids: AvailableIds,
+    map_hash: MapHashNet,
+    countdown: u8,
     readiness: Readiness,
     players: AHashMap<SocketAddr, PlayerSlot>,
 }
 
 impl GameStateInner {
-    fn new(max_players: Player) -> Self {
+    fn new(max_players: Player, map_hash: MapHashNet, countdown: u8) -> Self {
         Self {
             available_ids: AvailableIds::new(max_players),
+            map_hash,
+            countdown,
             readiness: Readiness::default(),
             players: AHashMap::new(),
         }
@@ -115,6 +201,11 @@ impl GameStateInner {
         self.players.is_empty()
     }
 
+    fn len(&self) -> u8 {
+        // There are at most `Player::MAX_PLAYERS` players thus this never overflows.
+        self.players.len() as u8
+    }
+
     fn contains(&self, addr: SocketAddr) -> bool {
         self.players.contains_key(&addr)
     }
@@ -123,10 +214,22 @@ impl GameStateInner {
         self.players.get(&addr).map(|p| p.id)
     }
 
-    fn add(&mut self, addr: SocketAddr) -> Result<Player, JoinError> {
+    fn all_ready(&self) -> bool {
+        self.readiness == Readiness::NotReady
+            && !self.players.is_empty()
+            && self
+                .players
+                .values()
+                .all(|p| p.readiness == Readiness::Ready)
+    }
+
+    fn add(&mut self, addr: SocketAddr, map_hash: MapHashNet) -> Result<Player, JoinError> {
         if self.readiness != Readiness::NotReady {
             return Err(JoinError::GameNotOpened);
         }
+        if self.map_hash != map_hash {
+            return Err(JoinError::MapMismatch);
+        }
 
         match self.players.entry(addr) {
             Entry::Occupied(_) => Err(JoinError::AlreadyJoined),
@@ -160,6 +263,13 @@ impl GameStateInner {
         };
 
         if player.readiness > readiness {
+            if self.readiness == Readiness::NotReady {
+                // The game has not started yet, thus the player is at most
+                // ready and might change their mind.
+                player.readiness = readiness;
+                return Ok(false);
+            }
+
             return Err(ReadinessUpdateError::Downgrade {
                 from: player.readiness,
                 to: readiness,
@@ -187,6 +297,11 @@ impl GameStateInner {
 
         player.readiness = readiness;
 
+        if self.readiness == Readiness::NotReady {
+            // The game is started explicitly, see `Self::start()`.
+            return Ok(false);
+        }
+
         let previous = self.readiness;
         self.readiness = self.players.values().map(|p| p.readiness).min().unwrap();
         let progressed = previous != self.readiness;
@@ -194,6 +309,25 @@ impl GameStateInner {
         Ok(progressed)
     }
 
+    fn start(&mut self) -> bool {
+        if !self.all_ready() {
+            return false;
+        }
+
+        self.readiness = Readiness::Ready;
+        true
+    }
+
+    fn players_readiness(&self, exclude: Option<SocketAddr>) -> Vec<(Player, Readiness)> {
+        let mut readiness = Vec::with_capacity(self.players.len());
+        for (&addr, player) in self.players.iter() {
+            if Some(addr) != exclude {
+                readiness.push((player.id, player.readiness));
+            }
+        }
+        readiness
+    }
+
     fn targets(&self, exclude: Option<SocketAddr>) -> Vec<SocketAddr> {
         let mut addrs = Vec::with_capacity(self.players.len());
         for &addr in self.players.keys() {
@@ -208,11 +342,15 @@ impl GameStateInner {
         &mut self,
         exclude: Option<SocketAddr>,
     ) -> impl Iterator<Item = &mut PlayerBuffer> {
+        self.slots_mut(exclude).map(|player| &mut player.buffer)
+    }
+
+    fn slots_mut(&mut self, exclude: Option<SocketAddr>) -> impl Iterator<Item = &mut PlayerSlot> {
         self.players.iter_mut().filter_map(move |(&addr, player)| {
             if Some(addr) == exclude {
                 None
             } else {
-                Some(&mut player.buffer)
+                Some(player)
             }
         })
     }
@@ -258,6 +396,8 @@ pub(super) enum JoinError {
     GameFull,
     #[error("The game is no longer opened.")]
     GameNotOpened,
+    #[error("The player has a different map than the game.")]
+    MapMismatch,
 }
 
 #[derive(Debug, Error, PartialEq)]
@@ -304,16 +444,30 @@ mod tests {
 
     use super::*;
 
+    fn map_hash() -> MapHashNet {
+        MapHashNet::from([7; 32])
+    }
+
     #[test]
     fn test_state() {
         task::block_on(task::spawn(async {
-            let mut state = GameState::new(Player::Player4);
+            let mut state = GameState::new(Player::Player4, map_hash(), 0);
             let mut ids: HashSet<Player> = HashSet::new();
 
-            assert!(ids.insert(state.add("127.0.0.1:1001".parse().unwrap()).await.unwrap()));
+            assert!(ids.insert(
+                state
+                    .add("127.0.0.1:1001".parse().unwrap(), map_hash())
+                    .await
+                    .unwrap()
+            ));
             assert!(state.contains("127.0.0.1:1001".parse().unwrap()).await);
 
-            assert!(ids.insert(state.add("127.0.0.1:1002".parse().unwrap()).await.unwrap()));
+            assert!(ids.insert(
+                state
+                    .add("127.0.0.1:1002".parse().unwrap(), map_hash())
+                    .await
+                    .unwrap()
+            ));
             assert!(state.contains("127.0.0.1:1001".parse().unwrap()).await);
             assert!(state.contains("127.0.0.1:1002".parse().unwrap()).await);
 
@@ -327,26 +481,42 @@ mod tests {
             assert!(!state.contains("127.0.0.1:1001".parse().unwrap()).await);
             assert!(state.contains("127.0.0.1:1002".parse().unwrap()).await);
 
-            assert!(ids.insert(state.add("127.0.0.1:1001".parse().unwrap()).await.unwrap()));
+            assert!(ids.insert(
+                state
+                    .add("127.0.0.1:1001".parse().unwrap(), map_hash())
+                    .await
+                    .unwrap()
+            ));
             assert!(state.contains("127.0.0.1:1001".parse().unwrap()).await);
             assert!(state.contains("127.0.0.1:1002".parse().unwrap()).await);
 
             assert!(matches!(
-                state.add("127.0.0.1:1001".parse().unwrap()).await,
+                state
+                    .add("127.0.0.1:1001".parse().unwrap(), map_hash())
+                    .await,
                 Err(JoinError::AlreadyJoined),
             ));
+            assert!(matches!(
+                state
+                    .add("127.0.0.1:1010".parse().unwrap(), MapHashNet::from([8; 32]))
+                    .await,
+                Err(JoinError::MapMismatch),
+            ));
+            assert!(!state.contains("127.0.0.1:1010".parse().unwrap()).await);
 
             for i in 3..=4 {
                 assert!(ids.insert(
                     state
-                        .add(format!("127.0.0.1:100{i}").parse().unwrap())
+                        .add(format!("127.0.0.1:100{i}").parse().unwrap(), map_hash())
                         .await
                         .unwrap()
                 ));
             }
 
             assert!(matches!(
-                state.add("127.0.0.1:1020".parse().unwrap()).await,
+                state
+                    .add("127.0.0.1:1020".parse().unwrap(), map_hash())
+                    .await,
                 Err(JoinError::GameFull),
             ));
             assert!(!state.contains("127.0.0.1:1020".parse().unwrap()).await);
@@ -359,10 +529,10 @@ mod tests {
         let client_b: SocketAddr = "127.0.0.1:8082".parse().unwrap();
         let client_c: SocketAddr = "127.0.0.1:8083".parse().unwrap();
 
-        let mut state = GameStateInner::new(Player::Player3);
+        let mut state = GameStateInner::new(Player::Player3, map_hash(), 0);
 
-        state.add(client_a).unwrap();
-        state.add(client_b).unwrap();
+        state.add(client_a, map_hash()).unwrap();
+        state.add(client_b, map_hash()).unwrap();
 
         assert_eq!(state.readiness, Readiness::NotReady);
 
@@ -373,10 +543,42 @@ mod tests {
 
         assert!(!state.update_readiness(client_b, Readiness::Ready).unwrap());
         assert_eq!(state.readiness, Readiness::NotReady);
-        assert!(state.update_readiness(client_a, Readiness::Ready).unwrap());
+        assert_eq!(
+            state.players_readiness(Some(client_a)),
+            vec![(Player::Player2, Readiness::Ready)]
+        );
+        assert!(!state.all_ready());
+        assert!(!state.start());
+        assert!(!state.update_readiness(client_a, Readiness::Ready).unwrap());
+        assert_eq!(state.readiness, Readiness::NotReady);
+        assert!(state.all_ready());
+
+        assert!(!state
+            .update_readiness(client_b, Readiness::NotReady)
+            .unwrap());
+        assert!(!state.all_ready());
+        assert!(!state.start());
+        assert!(!state.update_readiness(client_b, Readiness::Ready).unwrap());
+        assert!(state.all_ready());
+
+        assert!(state.start());
         assert_eq!(state.readiness, Readiness::Ready);
+        assert!(!state.all_ready());
+
+        assert_eq!(
+            state
+                .update_readiness(client_b, Readiness::NotReady)
+                .unwrap_err(),
+            ReadinessUpdateError::Downgrade {
+                from: Readiness::Ready,
+                to: Readiness::NotReady
+            }
+        );
 
-        assert_eq!(state.add(client_c), Err(JoinError::GameNotOpened));
+        assert_eq!(
+            state.add(client_c, map_hash()),
+            Err(JoinError::GameNotOpened)
+        );
 
         assert_eq!(
             state
@@ -419,11 +621,13 @@ mod tests {
 
     #[test]
     fn test_targets() {
-        let mut state = GameStateInner::new(Player::Player4);
+        let mut state = GameStateInner::new(Player::Player4, map_hash(), 0);
 
         assert!(state.targets(None).is_empty());
 
-        state.add("127.0.0.1:2001".parse().unwrap()).unwrap();
+        state
+            .add("127.0.0.1:2001".parse().unwrap(), map_hash())
+            .unwrap();
         assert_eq!(
             HashSet::<SocketAddr>::from_iter(state.targets(None).into_iter()),
             HashSet::from_iter(["127.0.0.1:2001".parse().unwrap()])
@@ -432,8 +636,12 @@ mod tests {
             .targets(Some("127.0.0.1:2001".parse().unwrap()))
             .is_empty());
 
-        state.add("127.0.0.1:2002".parse().unwrap()).unwrap();
-        state.add("127.0.0.1:2003".parse().unwrap()).unwrap();
+        state
+            .add("127.0.0.1:2002".parse().unwrap(), map_hash())
+            .unwrap();
+        state
+            .add("127.0.0.1:2003".parse().unwrap(), map_hash())
+            .unwrap();
         assert_eq!(
             HashSet::<SocketAddr>::from_iter(state.targets(None).into_iter()),
             HashSet::from_iter([
@@ -0,0 +1,270 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    time::Duration,
+};
+
+use ahash::AHashMap;
+use async_std::{
+    sync::{Arc, RwLock},
+    task,
+};
+use de_messages::{GameDescription, LanGame};
+use de_net::{DiscoveryResponder, MAX_DISCOVERY_PAYLOAD};
+use de_types::player::Player;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Selects which discovery queries are answered. See [`DiscoveryMode`].
+pub(crate) const MODE_VAR: &str = "DE_DISCOVERY";
+/// Time to wait before another attempt to receive a query after a failure.
+const ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Determines which clients may discover games opened at the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum DiscoveryMode {
+    /// Discovery queries are not answered at all.
+    Off,
+    /// Only queries from private, link-local and loopback addresses are
+    /// answered so that the games are not announced to the Internet.
+    #[default]
+    Lan,
+    /// Queries from all addresses are answered. This is useful for
+    /// self-hosted servers whose players connect directly by the server
+    /// address.
+    Public,
+}
+
+impl DiscoveryMode {
+    fn answers(self, source: SocketAddr) -> bool {
+        match self {
+            Self::Off => false,
+            Self::Lan => is_local(source),
+            Self::Public => true,
+        }
+    }
+}
+
+impl FromStr for DiscoveryMode {
+    type Err = DiscoveryModeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "off" => Ok(Self::Off),
+            "lan" => Ok(Self::Lan),
+            "public" => Ok(Self::Public),
+            _ => Err(DiscoveryModeError(value.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("unknown discovery mode \"{0}\", expected one of: off, lan, public")]
+pub(crate) struct DiscoveryModeError(String);
+
+/// Registry of games announced to clients in the local network.
+#[derive(Clone)]
+pub(crate) struct LanGames {
+    inner: Arc<RwLock<AHashMap<u16, LanEntry>>>,
+}
+
+impl LanGames {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(AHashMap::new())),
+        }
+    }
+
+    /// Starts announcing of a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - port of the game server.
+    ///
+    /// * `max_players` - maximum number of players of the game.
+    ///
+    /// * `description` - announced description of the game.
+    pub(crate) async fn register(
+        &mut self,
+        port: u16,
+        max_players: Player,
+        description: GameDescription,
+    ) {
+        self.inner.write().await.insert(
+            port,
+            LanEntry {
+                max_players,
+                description,
+                num_players: 0,
+                open: true,
+            },
+        );
+    }
+
+    /// Updates state of an announced game. Nothing happens if the game is not
+    /// announced.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - port of the game server.
+    ///
+    /// * `num_players` - number of players currently connected to the game.
+    ///
+    /// * `open` - whether new players may join the game.
+    pub(crate) async fn update(&mut self, port: u16, num_players: u8, open: bool) {
+        if let Some(entry) = self.inner.write().await.get_mut(&port) {
+            entry.num_players = num_players;
+            entry.open = open;
+        }
+    }
+
+    /// Stops announcing of a game.
+    pub(crate) async fn remove(&mut self, port: u16) {
+        self.inner.write().await.remove(&port);
+    }
+
+    /// Returns all games which may be joined by new players.
+    async fn open_games(&self) -> Vec<LanGame> {
+        self.inner
+            .read()
+            .await
+            .iter()
+            .filter(|(_, entry)| entry.open && entry.num_players < entry.max_players.to_num())
+            .map(|(&port, entry)| {
+                LanGame::new(
+                    port,
+                    entry.num_players,
+                    entry.max_players,
+                    entry.description.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+struct LanEntry {
+    max_players: Player,
+    description: GameDescription,
+    num_players: u8,
+    open: bool,
+}
+
+/// Responds to discovery queries with one datagram per open game.
+///
+/// # Arguments
+///
+/// * `responder` - socket of the discovery protocol.
+///
+/// * `games` - registry of the announced games.
+///
+/// * `mode` - determines which queries are answered.
+pub(crate) async fn run(responder: DiscoveryResponder, games: LanGames, mode: DiscoveryMode) {
+    info!("Responding to discovery queries in {mode:?} mode...");
+
+    loop {
+        let source = match responder.recv_query().await {
+            Ok(source) => source,
+            Err(err) => {
+                warn!("Failed to receive a LAN discovery query: {err:?}");
+                task::sleep(ERROR_BACKOFF).await;
+                continue;
+            }
+        };
+
+        if !mode.answers(source) {
+            continue;
+        }
+
+        for game in games.open_games().await {
+            let payload = match bincode::encode_to_vec(&game, bincode::config::standard()) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    warn!("Failed to encode LAN game announcement: {err:?}");
+                    continue;
+                }
+            };
+            if payload.len() > MAX_DISCOVERY_PAYLOAD {
+                warn!("LAN game announcement is too long: {} bytes", payload.len());
+                continue;
+            }
+
+            if let Err(err) = responder.respond(source, &payload).await {
+                warn!("Failed to send LAN discovery response to {source:?}: {err:?}");
+            }
+        }
+    }
+}
+
+fn is_local(addr: SocketAddr) -> bool {
+    match addr.ip() {
+        IpAddr::V4(ip) => ip.is_private() || ip.is_link_local() || ip.is_loopback(),
+        IpAddr::V6(ip) => ip.is_loopback(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn test_open_games() {
+        task::block_on(async {
+            let description =
+                GameDescription::new("Game".to_owned(), "a".repeat(64), "Map".to_owned()).unwrap();
+
+            let mut games = LanGames::new();
+            games
+                .register(1000, Player::Player2, description.clone())
+                .await;
+            games.register(1001, Player::Player4, description).await;
+            games.update(1001, 1, true).await;
+            games.update(1002, 1, true).await;
+
+            let mut open = games.open_games().await;
+            open.sort_by_key(|game| game.port());
+            assert_eq!(open.len(), 2);
+            assert_eq!(open[0].port(), 1000);
+            assert_eq!(open[0].num_players(), 0);
+            assert_eq!(open[1].port(), 1001);
+            assert_eq!(open[1].num_players(), 1);
+            assert_eq!(open[1].max_players(), Player::Player4);
+
+            games.update(1000, 2, true).await;
+            games.update(1001, 2, false).await;
+            assert!(games.open_games().await.is_empty());
+
+            games.update(1000, 1, true).await;
+            games.remove(1000).await;
+            assert!(games.open_games().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_is_local() {
+        let local = |a, b, c, d| is_local(SocketAddr::new(Ipv4Addr::new(a, b, c, d).into(), 80));
+        assert!(local(192, 168, 1, 20));
+        assert!(local(10, 0, 0, 1));
+        assert!(local(127, 0, 0, 1));
+        assert!(!local(8, 8, 8, 8));
+    }
+
+    #[test]
+    fn test_discovery_mode() {
+        let public = SocketAddr::new(Ipv4Addr::new(8, 8, 8, 8).into(), 80);
+        let private = SocketAddr::new(Ipv4Addr::new(192, 168, 0, 3).into(), 80);
+
+        assert_eq!(DiscoveryMode::default(), DiscoveryMode::Lan);
+        assert_eq!(
+            "public".parse::<DiscoveryMode>().unwrap(),
+            DiscoveryMode::Public
+        );
+        assert!("everybody".parse::<DiscoveryMode>().is_err());
+
+        assert!(!DiscoveryMode::Off.answers(private));
+        assert!(DiscoveryMode::Lan.answers(private));
+        assert!(!DiscoveryMode::Lan.answers(public));
+        assert!(DiscoveryMode::Public.answers(public));
+    }
+}
@@ -1,12 +1,20 @@
 use anyhow::Context;
 use async_std::task;
-use de_net::Socket;
-use tracing::info;
+use de_net::{DiscoveryResponder, Socket};
+use tracing::{info, warn};
 
-use crate::server::MainServer;
+pub use crate::recording::RecordingReader;
+use crate::{
+    lan::{DiscoveryMode, LanGames},
+    recording::RecordingConf,
+    server::MainServer,
+};
 
 mod clients;
+mod env;
 mod game;
+mod lan;
+mod recording;
 mod server;
 
 const PORT: u16 = 8082;
@@ -14,16 +22,36 @@ const PORT: u16 = 8082;
 pub fn start() -> Result<(), String> {
     info!("Starting...");
     task::block_on(task::spawn(async {
-        start_inner().await.map_err(|error| format!("{:?}", error))
+        serve(PORT).await.map_err(|error| format!("{:?}", error))
     }))
 }
 
-async fn start_inner() -> anyhow::Result<()> {
-    let socket = Socket::bind(Some(PORT))
+/// Runs DE Connector with its main server listening on a given port. This is
+/// useful for running DE Connector in-process, for example in tests.
+///
+/// The returned future resolves only if the server fails.
+pub async fn serve(port: u16) -> anyhow::Result<()> {
+    let socket = Socket::bind(Some(port))
         .await
-        .with_context(|| format!("Failed to open network on port {PORT}"))?;
-    info!("Listening on port {PORT}");
+        .with_context(|| format!("Failed to open network on port {port}"))?;
+    info!("Listening on port {port}");
 
-    let server = MainServer::start(socket);
+    let recording = RecordingConf::from_env()?;
+    if let Some(conf) = recording.as_ref() {
+        info!("Recording games to {}", conf.dir().display());
+    }
+
+    let lan_games = LanGames::new();
+    let discovery_mode = env::var(lan::MODE_VAR, DiscoveryMode::default())?;
+    if discovery_mode != DiscoveryMode::Off {
+        match DiscoveryResponder::bind().await {
+            Ok(responder) => {
+                task::spawn(lan::run(responder, lan_games.clone(), discovery_mode));
+            }
+            Err(err) => warn!("Game discovery disabled, failed to open its socket: {err:?}"),
+        }
+    }
+
+    let server = MainServer::start(socket, recording, lan_games);
     server.run().await
 }
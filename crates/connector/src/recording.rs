@@ -0,0 +1,327 @@
+//! Optional recording of all relayed player messages. Recordings are meant
+//! for investigation of reported cheating and for reproduction of crashes.
+//!
+//! Each game is recorded to a separate gzip compressed file. The file is a
+//! sequence of bincode encoded records, each record consists of time in
+//! milliseconds since the game start and the relayed message. Use
+//! [`RecordingReader`] to read the recordings.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::ensure;
+use async_std::{
+    channel::{self, Receiver, Sender},
+    fs,
+    io::{prelude::WriteExt, BufWriter},
+    stream::StreamExt,
+    task,
+};
+use bincode::{
+    config::Configuration,
+    decode_from_std_read, encode_to_vec,
+    error::{DecodeError, EncodeError},
+};
+use de_messages::{BorrowedFromPlayers, FromPlayers};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use tracing::{error, warn};
+
+use crate::env::var;
+
+/// Games are recorded into this directory. Recording is disabled if the
+/// variable is not set.
+const DIR_VAR: &str = "DE_RECORDINGS_DIR";
+/// Maximum number of retained recordings.
+const MAX_FILES_VAR: &str = "DE_RECORDINGS_MAX_FILES";
+/// Recordings older than this number of hours are removed.
+const MAX_AGE_VAR: &str = "DE_RECORDINGS_MAX_AGE_HOURS";
+const DEFAULT_MAX_FILES: usize = 1000;
+const DEFAULT_MAX_AGE_HOURS: u64 = 7 * 24;
+/// Suffix of recording files.
+const SUFFIX: &str = ".rec.gz";
+const BINCODE_CONF: Configuration = bincode::config::standard();
+/// Compressed data are written to the recording file once they exceed this
+/// number of bytes.
+const WRITE_THRESHOLD: usize = 64 * 1024;
+
+/// Configuration of game recording.
+#[derive(Clone)]
+pub(crate) struct RecordingConf {
+    dir: PathBuf,
+    max_files: usize,
+    max_age: Duration,
+}
+
+impl RecordingConf {
+    /// Loads the configuration from environment variables. None is returned
+    /// if recording is disabled.
+    pub(crate) fn from_env() -> anyhow::Result<Option<Self>> {
+        let Some(dir) = env::var_os(DIR_VAR) else {
+            return Ok(None);
+        };
+
+        let max_files = var(MAX_FILES_VAR, DEFAULT_MAX_FILES)?;
+        ensure!(max_files > 0, "\"{MAX_FILES_VAR}\" must be positive.");
+        let max_age_hours = var(MAX_AGE_VAR, DEFAULT_MAX_AGE_HOURS)?;
+
+        Ok(Some(Self {
+            dir: PathBuf::from(dir),
+            max_files,
+            max_age: Duration::from_secs(max_age_hours * 3600),
+        }))
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        self.dir.as_path()
+    }
+}
+
+/// Recorder of messages relayed within a single game. The messages are
+/// compressed and written to the recording file from a dedicated task. The
+/// file is finished once the recorder is dropped.
+pub(crate) struct Recorder {
+    start: Instant,
+    sender: Sender<Vec<u8>>,
+}
+
+impl Recorder {
+    /// Creates a new recording file and removes old recordings above the
+    /// retention limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `conf` - recording configuration.
+    ///
+    /// * `port` - port of the recorded game. It is included in the file name.
+    pub(crate) async fn start(conf: &RecordingConf, port: u16) -> io::Result<Self> {
+        fs::create_dir_all(conf.dir.as_path()).await?;
+        prune(conf).await?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let file = fs::File::create(conf.dir.join(format!("{timestamp}-{port}{SUFFIX}"))).await?;
+
+        let (sender, receiver) = channel::unbounded();
+        task::spawn(async move {
+            if let Err(err) = write_records(BufWriter::new(file), receiver).await {
+                error!("Game recording on port {port} failed: {err:?}");
+            }
+        });
+
+        Ok(Self {
+            start: Instant::now(),
+            sender,
+        })
+    }
+
+    /// Records a relayed message.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - time of the message arrival.
+    ///
+    /// * `message` - the relayed message.
+    pub(crate) fn record(&mut self, time: Instant, message: &BorrowedFromPlayers) {
+        let time = time.saturating_duration_since(self.start);
+        match encode_record(time, message) {
+            Ok(record) => {
+                // The writer task ends only after an error, which is
+                // already logged.
+                let _ = self.sender.try_send(record);
+            }
+            Err(err) => warn!("Could not encode recorded message: {err:?}"),
+        }
+    }
+}
+
+/// Reader of a game recording.
+pub struct RecordingReader<R: Read> {
+    decoder: BufReader<GzDecoder<R>>,
+}
+
+impl RecordingReader<File> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        File::open(path).map(Self::new)
+    }
+}
+
+impl<R: Read> RecordingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            decoder: BufReader::new(GzDecoder::new(inner)),
+        }
+    }
+}
+
+impl<R: Read> Iterator for RecordingReader<R> {
+    /// Time since the game start and the recorded message.
+    type Item = Result<(Duration, FromPlayers), DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let millis: u64 = match decode_from_std_read(&mut self.decoder, BINCODE_CONF) {
+            Ok(millis) => millis,
+            Err(DecodeError::UnexpectedEnd { .. }) => return None,
+            Err(DecodeError::Io { inner, .. }) if inner.kind() == io::ErrorKind::UnexpectedEof => {
+                return None
+            }
+            Err(err) => return Some(Err(err)),
+        };
+
+        Some(
+            decode_from_std_read(&mut self.decoder, BINCODE_CONF)
+                .map(|message| (Duration::from_millis(millis), message)),
+        )
+    }
+}
+
+fn encode_record(time: Duration, message: &BorrowedFromPlayers) -> Result<Vec<u8>, EncodeError> {
+    let millis = u64::try_from(time.as_millis()).unwrap_or(u64::MAX);
+    encode_to_vec((millis, message), BINCODE_CONF)
+}
+
+/// Compresses records and writes them to the target until the records
+/// channel is closed.
+async fn write_records<W>(mut target: W, records: Receiver<Vec<u8>>) -> io::Result<()>
+where
+    W: async_std::io::Write + Unpin,
+{
+    // Compression is done in memory so that no blocking IO is done.
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    while let Ok(record) = records.recv().await {
+        encoder.write_all(&record)?;
+
+        let compressed = encoder.get_mut();
+        if compressed.len() >= WRITE_THRESHOLD {
+            target.write_all(compressed.as_slice()).await?;
+            compressed.clear();
+        }
+    }
+
+    target.write_all(&encoder.finish()?).await?;
+    target.flush().await
+}
+
+/// Removes recordings older than the maximum age and the oldest recordings so
+/// that a new recording fits into the maximum number of files.
+async fn prune(conf: &RecordingConf) -> io::Result<()> {
+    let now = SystemTime::now();
+
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(conf.dir.as_path()).await?;
+    while let Some(entry) = entries.next().await {
+        let entry = entry?;
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !name.ends_with(SUFFIX) {
+            continue;
+        }
+
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let modified = metadata.modified()?;
+        if now
+            .duration_since(modified)
+            .is_ok_and(|age| age > conf.max_age)
+        {
+            fs::remove_file(path).await?;
+        } else {
+            files.push((modified, path));
+        }
+    }
+
+    if files.len() < conf.max_files {
+        return Ok(());
+    }
+
+    files.sort();
+    let excess = files.len() + 1 - conf.max_files;
+    for (_, path) in files.into_iter().take(excess) {
+        fs::remove_file(path).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs as std_fs;
+
+    use de_messages::ToPlayers;
+    use de_types::player::Player;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test_records() {
+        let (sender, receiver) = channel::unbounded();
+        let surrender = ToPlayers::Surrender;
+        sender
+            .try_send(
+                encode_record(
+                    Duration::from_millis(1500),
+                    &BorrowedFromPlayers::new(Player::Player2, &surrender),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        sender
+            .try_send(
+                encode_record(
+                    Duration::from_secs(7),
+                    &BorrowedFromPlayers::new(Player::Player4, &surrender),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        drop(sender);
+
+        let mut data = Vec::new();
+        task::block_on(write_records(&mut data, receiver)).unwrap();
+
+        let records: Vec<(Duration, FromPlayers)> = RecordingReader::new(data.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, Duration::from_millis(1500));
+        assert_eq!(records[0].1.source(), Player::Player2);
+        assert!(matches!(records[0].1.message(), ToPlayers::Surrender));
+        assert_eq!(records[1].0, Duration::from_secs(7));
+        assert_eq!(records[1].1.source(), Player::Player4);
+    }
+
+    #[test]
+    fn test_prune() {
+        let dir = tempdir().unwrap();
+        std_fs::write(dir.path().join("unrelated.txt"), "keep").unwrap();
+        for name in ["a", "b", "c"] {
+            std_fs::write(dir.path().join(format!("{name}{SUFFIX}")), "").unwrap();
+        }
+
+        let conf = RecordingConf {
+            dir: dir.path().to_owned(),
+            max_files: 2,
+            max_age: Duration::from_secs(3600),
+        };
+        task::block_on(prune(&conf)).unwrap();
+
+        let mut names: Vec<String> = std_fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"unrelated.txt".to_owned()));
+    }
+}
@@ -2,14 +2,14 @@ use std::net::SocketAddr;
 
 use anyhow::Context;
 use async_std::task;
-use de_messages::{FromServer, GameOpenError, ToServer};
+use de_messages::{FromServer, GameDescription, GameOpenError, MapHashNet, ToServer};
 use de_net::{
     self, MessageDecoder, OutPackage, PackageReceiver, PackageSender, Peers, Reliability, Socket,
 };
 use de_types::player::Player;
 use tracing::{error, info, warn};
 
-use crate::{clients::Clients, game};
+use crate::{clients::Clients, game, lan::LanGames, recording::RecordingConf};
 
 /// Main game server responsible for initial communication with clients and
 /// establishment of game sub-servers.
@@ -17,11 +17,26 @@ pub(crate) struct MainServer {
     outputs: PackageSender,
     inputs: PackageReceiver,
     clients: Clients,
+    recording: Option<RecordingConf>,
+    lan_games: LanGames,
 }
 
 impl MainServer {
     /// Setup the server & startup its network stack.
-    pub(crate) fn start(socket: Socket) -> Self {
+    ///
+    /// # Arguments
+    ///
+    /// * `socket` - socket of the main server.
+    ///
+    /// * `recording` - configuration of game recording. Games are not
+    ///   recorded if None.
+    ///
+    /// * `lan_games` - registry of games announced in the local network.
+    pub(crate) fn start(
+        socket: Socket,
+        recording: Option<RecordingConf>,
+        lan_games: LanGames,
+    ) -> Self {
         let (outputs, inputs, _) = de_net::startup(
             |t| {
                 task::spawn(t);
@@ -32,6 +47,8 @@ impl MainServer {
             outputs,
             inputs,
             clients: Clients::new(),
+            recording,
+            lan_games,
         }
     }
 
@@ -67,14 +84,29 @@ impl MainServer {
 
             match message {
                 ToServer::Ping(id) => self.reply(&FromServer::Pong(id), source).await?,
-                ToServer::OpenGame { max_players } => self.open_game(source, max_players).await?,
+                ToServer::OpenGame {
+                    max_players,
+                    map_hash,
+                    countdown,
+                    description,
+                } => {
+                    self.open_game(source, max_players, map_hash, countdown, description)
+                        .await?
+                }
             }
         }
 
         Ok(())
     }
 
-    async fn open_game(&mut self, source: SocketAddr, max_players: Player) -> anyhow::Result<()> {
+    async fn open_game(
+        &mut self,
+        source: SocketAddr,
+        max_players: Player,
+        map_hash: MapHashNet,
+        countdown: u8,
+        description: Option<GameDescription>,
+    ) -> anyhow::Result<()> {
         if let Err(err) = self.clients.reserve(source).await {
             warn!("OpenGame request error: {err}");
             self.reply(
@@ -92,7 +124,29 @@ impl MainServer {
 
                 info!("Starting new game on port {port}.");
                 self.reply(&FromServer::GameOpened { port }, source).await?;
-                game::startup(self.clients.clone(), socket, source, max_players).await;
+
+                if let Some(description) = description {
+                    match description.validate() {
+                        Ok(()) => {
+                            self.lan_games
+                                .register(port, max_players, description)
+                                .await;
+                        }
+                        Err(err) => warn!("Game on port {port} is not announced in LAN: {err}"),
+                    }
+                }
+
+                game::startup(
+                    self.clients.clone(),
+                    self.lan_games.clone(),
+                    socket,
+                    source,
+                    max_players,
+                    map_hash,
+                    countdown,
+                    self.recording.as_ref(),
+                )
+                .await;
                 Ok(())
             }
             Err(error) => {
@@ -4,7 +4,7 @@ use std::{
 };
 
 use async_std::{future::timeout, task};
-use de_messages::{FromGame, FromServer, JoinError, Readiness, ToGame, ToServer};
+use de_messages::{FromGame, FromServer, JoinError, MapHashNet, Readiness, ToGame, ToServer};
 use de_net::{
     self, ConnErrorReceiver, OutPackage, PackageReceiver, PackageSender, Peers, Reliability, Socket,
 };
@@ -40,10 +40,14 @@ fn test() {
         let mut comms_b = Comms::init().await;
         let mut comms_c = Comms::init().await;
         let mut comms_d = Comms::init().await;
+        let map_hash = MapHashNet::from([1; 32]);
 
         comms_a
             .send(ToServer::OpenGame {
                 max_players: 3.try_into().unwrap(),
+                map_hash,
+                countdown: 1,
+                description: None,
             })
             .await;
         let mut response = comms_a.recv::<FromServer>().await;
@@ -61,12 +65,19 @@ fn test() {
 
         check_response!(comms_a, FromGame::Joined(Player::Player1));
 
-        comms_b.send(ToGame::Join).await;
+        comms_b.send(ToGame::Join(map_hash)).await;
         check_response!(comms_b, FromGame::Joined(Player::Player2));
         check_response!(comms_a, FromGame::PeerJoined(Player::Player2));
 
+        comms_c.send(ToGame::Join(MapHashNet::from([2; 32]))).await;
+        check_response!(comms_c, FromGame::JoinError(JoinError::MapMismatch));
+
         comms_a.send(ToGame::Readiness(Readiness::Ready)).await;
-        // The other player is not yet ready -> no message should be received.
+        check_response!(
+            comms_b,
+            FromGame::PeerReadiness(Player::Player1, Readiness::Ready)
+        );
+        // The other player is not yet ready -> no countdown should start.
         assert!(
             timeout(Duration::from_millis(500), comms_a.recv::<FromGame>())
                 .await
@@ -77,12 +88,35 @@ fn test() {
                 .await
                 .is_err()
         );
+
         comms_b.send(ToGame::Readiness(Readiness::Ready)).await;
+        check_response!(
+            comms_a,
+            FromGame::PeerReadiness(Player::Player2, Readiness::Ready)
+        );
+        check_response!(comms_a, FromGame::Countdown(1));
+        check_response!(comms_b, FromGame::Countdown(1));
+
+        comms_b.send(ToGame::Readiness(Readiness::NotReady)).await;
+        check_response!(
+            comms_a,
+            FromGame::PeerReadiness(Player::Player2, Readiness::NotReady)
+        );
+        check_response!(comms_a, FromGame::CountdownCancelled);
+        check_response!(comms_b, FromGame::CountdownCancelled);
+
+        comms_b.send(ToGame::Readiness(Readiness::Ready)).await;
+        check_response!(
+            comms_a,
+            FromGame::PeerReadiness(Player::Player2, Readiness::Ready)
+        );
+        check_response!(comms_a, FromGame::Countdown(1));
+        check_response!(comms_b, FromGame::Countdown(1));
 
         check_response!(comms_a, FromGame::GameReadiness(Readiness::Ready));
         check_response!(comms_b, FromGame::GameReadiness(Readiness::Ready));
 
-        comms_c.send(ToGame::Join).await;
+        comms_c.send(ToGame::Join(map_hash)).await;
         check_response!(comms_c, FromGame::JoinError(JoinError::GameNotOpened));
 
         comms_a.send(ToGame::Readiness(Readiness::Prepared)).await;
@@ -103,7 +137,7 @@ fn test() {
         check_response!(comms_a, FromGame::GameReadiness(Readiness::Prepared));
         check_response!(comms_b, FromGame::GameReadiness(Readiness::Prepared));
 
-        comms_d.send(ToGame::Join).await;
+        comms_d.send(ToGame::Join(map_hash)).await;
         check_response!(comms_d, FromGame::JoinError(JoinError::GameNotOpened));
 
         comms_a
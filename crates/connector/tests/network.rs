@@ -13,6 +13,8 @@ use crate::common::{spawn_and_wait, term_and_wait};
 mod common;
 
 const SERVER_ADDR: SocketAddr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8082));
+/// Bincode encoded map hash, i.e. 32 bytes without any length prefix.
+const MAP_HASH: [u8; 32] = [3; 32];
 
 #[derive(Debug)]
 struct ReceivedBuffer(Vec<Incomming>);
@@ -336,11 +338,14 @@ async fn create_game() -> (Socket, u16) {
 
     // [32 + 16] -> unordered + Peers::Server
     // [0, 0, 7] -> datagram ID = 7
-    // [1 2] -> ToGame::OpenGame { max_players: Player3 }
-    client
-        .send(SERVER_ADDR, &[32 + 16, 0, 0, 7, 1, 2])
-        .await
-        .unwrap();
+    // [1 2] -> ToGame::OpenGame { max_players: Player3, ..
+    // [MAP_HASH] -> .. map_hash: MAP_HASH, ..
+    // [0] -> .. countdown: 0, ..
+    // [0] -> .. description: None }
+    let mut data = vec![32 + 16, 0, 0, 7, 1, 2];
+    data.extend_from_slice(&MAP_HASH);
+    data.extend_from_slice(&[0, 0]);
+    client.send(SERVER_ADDR, &data).await.unwrap();
 
     let mut received = ReceivedBuffer::new();
     received.load(&mut client, &mut buffer).await;
@@ -409,8 +414,10 @@ async fn join_game(game_port: u16) -> Socket {
 
     // [32 + 16] -> unordered + Peers::Server
     // [0, 0, 3] -> datagram ID = 3
-    // [1] -> ToGame::Join
-    client.send(server, &[32 + 16, 0, 0, 3, 1]).await.unwrap();
+    // [1 MAP_HASH] -> ToGame::Join(MAP_HASH)
+    let mut data = vec![32 + 16, 0, 0, 3, 1];
+    data.extend_from_slice(&MAP_HASH);
+    client.send(server, &data).await.unwrap();
 
     let mut received = ReceivedBuffer::new();
     received.load(&mut client, &mut buffer).await;
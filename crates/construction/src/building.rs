@@ -0,0 +1,224 @@
+use std::{collections::VecDeque, time::Duration};
+
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    state::AppState,
+};
+use de_objects::SolidObjects;
+use de_spawner::SpawnLocalActiveEvent;
+use de_types::{
+    objects::{ActiveObjectType, BuildingType, ObjectType},
+    player::Player,
+};
+
+pub(crate) struct BuildingPlugin;
+
+impl Plugin for BuildingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EnqueueBuildingEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (enqueue, construct.after(enqueue))
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+/// Send this event to enqueue a building to be constructed. Buildings of a
+/// player are constructed one after another in the order of enqueuing.
+///
+/// Construction cost is not handled, the building must already be paid for.
+#[derive(Event)]
+pub struct EnqueueBuildingEvent {
+    player: Player,
+    building: QueuedBuilding,
+}
+
+impl EnqueueBuildingEvent {
+    /// # Arguments
+    ///
+    /// * `player` - owner of the building.
+    ///
+    /// * `building_type` - type of the building to be constructed.
+    ///
+    /// * `transform` - placement of the building.
+    pub fn new(player: Player, building_type: BuildingType, transform: Transform) -> Self {
+        Self {
+            player,
+            building: QueuedBuilding {
+                building_type,
+                transform,
+            },
+        }
+    }
+}
+
+/// A building waiting for construction or under construction.
+#[derive(Clone, Copy)]
+pub struct QueuedBuilding {
+    building_type: BuildingType,
+    transform: Transform,
+}
+
+impl QueuedBuilding {
+    pub fn building_type(&self) -> BuildingType {
+        self.building_type
+    }
+
+    pub fn transform(&self) -> &Transform {
+        &self.transform
+    }
+}
+
+/// Construction queues of all locally simulated players.
+#[derive(Resource, Default)]
+pub struct BuildingQueues(AHashMap<Player, BuildingQueue>);
+
+impl BuildingQueues {
+    /// Returns all buildings of a player waiting for construction or under
+    /// construction. The building under construction is returned first.
+    pub fn queue(&self, player: Player) -> impl Iterator<Item = &QueuedBuilding> + '_ {
+        self.0
+            .get(&player)
+            .into_iter()
+            .flat_map(|queue| queue.queue.iter())
+    }
+}
+
+#[derive(Default)]
+struct BuildingQueue {
+    queue: VecDeque<QueuedBuilding>,
+    /// Construction time spent on the first building in the queue.
+    progress: Duration,
+}
+
+impl BuildingQueue {
+    fn push(&mut self, building: QueuedBuilding) {
+        self.queue.push_back(building);
+    }
+
+    /// Advances the construction and returns all buildings finished in the
+    /// meantime.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - time elapsed since the last call.
+    ///
+    /// * `time` - returns construction time of a building type.
+    fn construct<F>(&mut self, delta: Duration, time: F) -> Vec<QueuedBuilding>
+    where
+        F: Fn(BuildingType) -> Duration,
+    {
+        let mut finished = Vec::new();
+        self.progress += delta;
+
+        while let Some(building) = self.queue.front() {
+            let required = time(building.building_type);
+            if self.progress < required {
+                break;
+            }
+            self.progress -= required;
+            finished.push(self.queue.pop_front().unwrap());
+        }
+
+        if self.queue.is_empty() {
+            self.progress = Duration::ZERO;
+        }
+        finished
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<BuildingQueues>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<BuildingQueues>();
+}
+
+fn enqueue(
+    config: Res<GameConfig>,
+    mut queues: ResMut<BuildingQueues>,
+    mut events: EventReader<EnqueueBuildingEvent>,
+) {
+    for event in events.read() {
+        debug_assert!(config.locals().is_local(event.player));
+        queues
+            .0
+            .entry(event.player)
+            .or_default()
+            .push(event.building);
+    }
+}
+
+fn construct(
+    time: Res<Time>,
+    solids: SolidObjects,
+    mut queues: ResMut<BuildingQueues>,
+    mut spawn_events: EventWriter<SpawnLocalActiveEvent>,
+) {
+    let construction_time = |building_type| {
+        solids
+            .get(ObjectType::Active(ActiveObjectType::Building(
+                building_type,
+            )))
+            .construction()
+            .map_or(Duration::ZERO, |construction| construction.time())
+    };
+
+    for (&player, queue) in queues.0.iter_mut() {
+        for building in queue.construct(time.delta(), construction_time) {
+            spawn_events.send(SpawnLocalActiveEvent::stationary(
+                ActiveObjectType::Building(building.building_type),
+                building.transform,
+                player,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue() {
+        let time = |building_type| match building_type {
+            BuildingType::Base => Duration::from_secs(10),
+            _ => Duration::from_secs(2),
+        };
+        let building = |building_type| QueuedBuilding {
+            building_type,
+            transform: Transform::IDENTITY,
+        };
+
+        let mut queue = BuildingQueue::default();
+        assert!(queue.construct(Duration::from_secs(5), time).is_empty());
+
+        queue.push(building(BuildingType::Base));
+        queue.push(building(BuildingType::Radar));
+        queue.push(building(BuildingType::Bunker));
+        assert!(queue.construct(Duration::from_secs(9), time).is_empty());
+
+        let finished = queue.construct(Duration::from_secs(1), time);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].building_type(), BuildingType::Base);
+
+        // Time exceeding construction of a building is spent on the next one.
+        let finished = queue.construct(Duration::from_secs(3), time);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].building_type(), BuildingType::Radar);
+
+        let finished = queue.construct(Duration::from_secs(2), time);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].building_type(), BuildingType::Bunker);
+        assert!(queue.queue.is_empty());
+        assert_eq!(queue.progress, Duration::ZERO);
+    }
+}
@@ -1,13 +1,24 @@
 use bevy::{app::PluginGroupBuilder, prelude::*};
+use building::BuildingPlugin;
+pub use building::{BuildingQueues, EnqueueBuildingEvent, QueuedBuilding};
 use manufacturing::ManufacturingPlugin;
 pub use manufacturing::{AssemblyLine, ChangeDeliveryLocationEvent, EnqueueAssemblyEvent};
+use orders::OrdersPlugin;
+pub use orders::{
+    OrdersSet, SetStandingOrderEvent, StandingOrder, StandingOrderEvent, StandingOrders,
+};
 
+mod building;
 mod manufacturing;
+mod orders;
 
 pub struct ConstructionPluginGroup;
 
 impl PluginGroup for ConstructionPluginGroup {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>().add(ManufacturingPlugin)
+        PluginGroupBuilder::start::<Self>()
+            .add(BuildingPlugin)
+            .add(ManufacturingPlugin)
+            .add(OrdersPlugin)
     }
 }
@@ -4,6 +4,9 @@ use ahash::AHashMap;
 use bevy::prelude::*;
 use de_core::{
     gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    gstats::GameStats,
+    notification::{GameNotification, NotifyEvent},
     objects::{Local, ObjectTypeComponent},
     player::PlayerComponent,
     state::AppState,
@@ -13,6 +16,7 @@ use de_objects::SolidObjects;
 use de_pathing::{PathQueryProps, PathTarget};
 use de_signs::{
     LineLocation, UpdateLineEndEvent, UpdateLineLocationEvent, UpdatePoleLocationEvent,
+    UpdateProgressBarEvent,
 };
 use de_spawner::{ObjectCounter, SpawnLocalActiveEvent};
 use de_types::{
@@ -23,8 +27,12 @@ use de_types::{
 use parry2d::bounding_volume::Aabb;
 use parry3d::math::Isometry;
 
-const MANUFACTURING_TIME: Duration = Duration::from_secs(2);
+use crate::orders::StandingOrders;
+
 const DEFAULT_TARGET_DISTANCE: f32 = 20.;
+/// Factories of the local player are reported once they have not been
+/// manufacturing anything for this long.
+const IDLE_NOTIFICATION_DELAY: Duration = Duration::from_secs(60);
 
 pub(crate) struct ManufacturingPlugin;
 
@@ -42,10 +50,18 @@ impl Plugin for ManufacturingPlugin {
                     deliver
                         .after(ManufacturingSet::ChangeLocations)
                         .after(ManufacturingSet::Produce),
+                    update_progress_bars.after(ManufacturingSet::Produce),
+                    detect_idle.after(ManufacturingSet::Produce),
                 )
-                    .run_if(in_state(GameState::Playing)),
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            )
+            .add_systems(
+                Update,
+                enqueue
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
             )
-            .add_systems(Update, enqueue.run_if(in_state(GameState::Playing)))
             .add_systems(PostUpdate, configure.run_if(in_state(AppState::InGame)));
     }
 }
@@ -126,6 +142,52 @@ impl DeliverEvent {
 #[derive(Component)]
 struct DeliveryLocation(Vec2);
 
+/// Marks factories whose delivery location was explicitly set.
+#[derive(Component)]
+struct RallyPoint;
+
+/// Manufacturing progress (in percent) last displayed on the progress bar of
+/// a factory, or None if the bar is hidden.
+#[derive(Component, Default)]
+struct DisplayedProgress(Option<u8>);
+
+/// Manufacturing activity of a factory used to report idle factories.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+enum Activity {
+    Busy,
+    /// The factory has been idle since a time (since game start).
+    Idle(Duration),
+    /// The factory is idle and it has already been reported.
+    Reported,
+}
+
+impl Activity {
+    /// Updates the activity and returns true if the factory should be
+    /// reported as idle.
+    ///
+    /// # Arguments
+    ///
+    /// * `busy` - whether the factory is manufacturing something.
+    ///
+    /// * `time` - elapsed time since a fixed point in time in the past.
+    fn update(&mut self, busy: bool, time: Duration) -> bool {
+        if busy {
+            *self = Self::Busy;
+            return false;
+        }
+
+        match *self {
+            Self::Busy => *self = Self::Idle(time),
+            Self::Idle(since) if time >= since + IDLE_NOTIFICATION_DELAY => {
+                *self = Self::Reported;
+                return true;
+            }
+            Self::Idle(_) | Self::Reported => (),
+        }
+        false
+    }
+}
+
 impl DeliveryLocation {
     fn initial(local_aabb: Aabb, transform: &Transform) -> Self {
         let target = Vec2::new(
@@ -149,12 +211,27 @@ impl AssemblyLine {
         &mut self.blocks
     }
 
+    /// Returns all units in the assembly line in the order of their delivery.
+    pub fn queue(&self) -> impl Iterator<Item = UnitType> + '_ {
+        self.queue.iter().map(|item| item.unit())
+    }
+
     /// Returns the first item in the assembly line (i.e. the first one to be
     /// delivered).
     fn current(&self) -> Option<UnitType> {
         self.queue.front().map(|item| item.unit())
     }
 
+    /// Returns manufacturing progress (between 0 and 1) of the first item in
+    /// the assembly line or None if the line is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - elapsed time since a fixed point in time in the past.
+    fn progress(&self, time: Duration) -> Option<f32> {
+        self.queue.front().map(|item| item.fraction(time))
+    }
+
     /// Put another unit into the manufacturing queue.
     fn enqueue(&mut self, unit: UnitType, time: Duration) {
         let mut item = ProductionItem::new(unit);
@@ -235,6 +312,17 @@ impl ProductionItem {
         self.unit
     }
 
+    /// Returns time it takes to manufacture the unit.
+    fn manufacturing_time(&self) -> Duration {
+        match self.unit {
+            UnitType::Attacker => Duration::from_secs(2),
+            UnitType::Artillery => Duration::from_secs(5),
+            UnitType::Scout => Duration::from_secs(1),
+            UnitType::Harvester => Duration::from_secs(3),
+            UnitType::Transport => Duration::from_secs(3),
+        }
+    }
+
     /// Returns true if the unit is actively manufactured.
     fn is_active(&self) -> bool {
         self.restarted.is_some()
@@ -253,8 +341,8 @@ impl ProductionItem {
     fn stop(&mut self, time: Duration) {
         if let Some(last) = self.restarted {
             self.accumulated += time - last;
-            if self.accumulated > MANUFACTURING_TIME {
-                self.accumulated = MANUFACTURING_TIME;
+            if self.accumulated > self.manufacturing_time() {
+                self.accumulated = self.manufacturing_time();
             }
         }
         self.restarted = None;
@@ -263,8 +351,8 @@ impl ProductionItem {
     /// If the item is already finished, stop the manufacturing and clip its
     /// due time to just now.
     fn block(&mut self, time: Duration) {
-        if self.progress(time) >= MANUFACTURING_TIME {
-            self.accumulated = MANUFACTURING_TIME;
+        if self.progress(time) >= self.manufacturing_time() {
+            self.accumulated = self.manufacturing_time();
             self.restarted = Some(time);
         }
     }
@@ -273,13 +361,20 @@ impl ProductionItem {
     /// how long it has been finished.
     fn finished(&self, time: Duration) -> Option<Duration> {
         let progress = self.progress(time);
-        if progress >= MANUFACTURING_TIME {
-            Some(progress - MANUFACTURING_TIME)
+        if progress >= self.manufacturing_time() {
+            Some(progress - self.manufacturing_time())
         } else {
             None
         }
     }
 
+    /// Returns fraction (between 0 and 1) of the unit manufacturing already
+    /// done.
+    fn fraction(&self, time: Duration) -> f32 {
+        let fraction = self.progress(time).as_secs_f32() / self.manufacturing_time().as_secs_f32();
+        fraction.min(1.)
+    }
+
     /// Returns for how long cumulatively the unit has been manufactured.
     fn progress(&self, time: Duration) -> Duration {
         self.accumulated
@@ -308,14 +403,19 @@ fn configure(
                 entity,
                 LineLocation::new(start, end),
             ));
-            commands
-                .entity(entity)
-                .insert((AssemblyLine::default(), delivery_location));
+            commands.entity(entity).insert((
+                AssemblyLine::default(),
+                DisplayedProgress::default(),
+                StandingOrders::default(),
+                delivery_location,
+                Activity::Busy,
+            ));
         }
     }
 }
 
 fn change_locations(
+    mut commands: Commands,
     mut events: EventReader<ChangeDeliveryLocationEvent>,
     mut locations: Query<&mut DeliveryLocation>,
     mut pole_events: EventWriter<UpdatePoleLocationEvent>,
@@ -325,6 +425,7 @@ fn change_locations(
         if let Ok(mut location) = locations.get_mut(event.factory()) {
             let owner = event.factory();
             location.0 = event.position();
+            commands.entity(owner).insert(RallyPoint);
             pole_events.send(UpdatePoleLocationEvent::new(owner, event.position()));
             let end = event.position().to_msl();
             line_events.send(UpdateLineEndEvent::new(owner, end));
@@ -404,15 +505,55 @@ fn produce(
     }
 }
 
+/// Updates factory progress bars once the progress changes by at least a
+/// percent.
+fn update_progress_bars(
+    time: Res<Time>,
+    mut factories: Query<(Entity, &AssemblyLine, &mut DisplayedProgress)>,
+    mut events: EventWriter<UpdateProgressBarEvent>,
+) {
+    for (factory, assembly, mut displayed) in factories.iter_mut() {
+        let progress = assembly.progress(time.elapsed());
+        let percent = progress.map(|progress| (100. * progress).floor() as u8);
+        if displayed.0 != percent {
+            displayed.0 = percent;
+            events.send(UpdateProgressBarEvent::new(factory, progress));
+        }
+    }
+}
+
+fn detect_idle(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut factories: Query<(&Transform, &PlayerComponent, &AssemblyLine, &mut Activity)>,
+    mut notify_events: EventWriter<NotifyEvent>,
+) {
+    for (transform, &player, assembly, mut activity) in factories.iter_mut() {
+        if !config.locals().is_playable(*player) {
+            continue;
+        }
+
+        if activity.update(assembly.current().is_some(), time.elapsed()) {
+            notify_events.send(NotifyEvent::new(GameNotification::factory_idle(
+                transform.translation.to_flat(),
+            )));
+        }
+    }
+}
+
 fn deliver(
+    config: Res<GameConfig>,
     solids: SolidObjects,
     mut deliver_events: EventReader<DeliverEvent>,
     mut spawn_active_events: EventWriter<SpawnLocalActiveEvent>,
+    mut notify_events: EventWriter<NotifyEvent>,
+    mut stats: ResMut<GameStats>,
     factories: Query<(
         &Transform,
         &ObjectTypeComponent,
         &PlayerComponent,
         &DeliveryLocation,
+        Has<RallyPoint>,
     )>,
 ) {
     for delivery in deliver_events.read() {
@@ -422,10 +563,22 @@ fn deliver(
             delivery.factory()
         );
 
-        let (transform, &factory_object_type, &player, delivery_location) =
+        let (transform, &factory_object_type, &player, delivery_location, rally_point) =
             factories.get(delivery.factory()).unwrap();
         let object_type = ActiveObjectType::Unit(delivery.unit());
 
+        if config.locals().is_playable(*player) {
+            stats.record_unit(delivery.unit());
+
+            let position = transform.translation.to_flat();
+            notify_events.send(NotifyEvent::new(GameNotification::production_complete(
+                position,
+            )));
+            if !rally_point {
+                notify_events.send(NotifyEvent::new(GameNotification::no_rally_point(position)));
+            }
+        }
+
         let factory = solids.get(*factory_object_type).factory().unwrap();
         debug_assert!(factory.products().contains(&delivery.unit()));
         let spawn_point = transform.transform_point(factory.position().to_msl());
@@ -435,12 +588,15 @@ fn deliver(
             PathQueryProps::new(0., f32::INFINITY),
             false,
         );
-        spawn_active_events.send(SpawnLocalActiveEvent::new(
-            object_type,
-            Transform::from_translation(spawn_point),
-            *player,
-            Some(path_target),
-        ));
+        spawn_active_events.send(
+            SpawnLocalActiveEvent::new(
+                object_type,
+                Transform::from_translation(spawn_point),
+                *player,
+                Some(path_target),
+            )
+            .with_source(delivery.factory()),
+        );
     }
 }
 
@@ -485,4 +641,47 @@ mod tests {
         );
         assert!(line.produce(Duration::from_secs(90)).is_none());
     }
+
+    #[test]
+    fn test_manufacturing_time() {
+        let mut line = AssemblyLine::default();
+        line.enqueue(UnitType::Artillery, Duration::from_secs(10));
+        line.enqueue(UnitType::Attacker, Duration::from_secs(10));
+        assert_eq!(
+            line.queue().collect::<Vec<_>>(),
+            vec![UnitType::Artillery, UnitType::Attacker]
+        );
+
+        assert!(line.produce(Duration::from_secs(14)).is_none());
+        assert_eq!(line.progress(Duration::from_secs(14)), Some(0.8));
+        assert_eq!(
+            line.produce(Duration::from_secs(15)).unwrap(),
+            UnitType::Artillery
+        );
+        assert_eq!(line.queue().collect::<Vec<_>>(), vec![UnitType::Attacker]);
+        assert!(line.produce(Duration::from_secs(16)).is_none());
+        assert_eq!(line.progress(Duration::from_secs(16)), Some(0.5));
+        assert_eq!(
+            line.produce(Duration::from_secs(17)).unwrap(),
+            UnitType::Attacker
+        );
+        assert!(line.progress(Duration::from_secs(17)).is_none());
+    }
+
+    #[test]
+    fn test_activity() {
+        let time = |secs| Duration::from_secs(secs);
+
+        let mut activity = Activity::Busy;
+        assert!(!activity.update(true, time(10)));
+        assert!(!activity.update(false, time(20)));
+        assert_eq!(activity, Activity::Idle(time(20)));
+        assert!(!activity.update(false, time(79)));
+        assert!(activity.update(false, time(80)));
+        assert!(!activity.update(false, time(200)));
+        assert_eq!(activity, Activity::Reported);
+
+        assert!(!activity.update(true, time(210)));
+        assert_eq!(activity, Activity::Busy);
+    }
 }
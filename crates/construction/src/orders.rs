@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use de_core::{gamestate::GameState, gconfig::is_editor, objects::ObjectTypeComponent};
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
+use de_spawner::{LocalActiveSpawnedEvent, SpawnerSet};
+use de_types::objects::{ActiveObjectType, UnitType};
+use enum_map::EnumMap;
+
+pub(crate) struct OrdersPlugin;
+
+impl Plugin for OrdersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SetStandingOrderEvent>()
+            .add_event::<StandingOrderEvent>()
+            .add_systems(
+                Update,
+                (
+                    set_orders.in_set(OrdersSet::SetOrders),
+                    apply_orders
+                        .in_set(OrdersSet::Apply)
+                        .after(OrdersSet::SetOrders)
+                        .after(SpawnerSet::Spawner),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum OrdersSet {
+    SetOrders,
+    /// [`StandingOrderEvent`] is sent in this set.
+    Apply,
+}
+
+/// An order automatically given to each unit (of a given type) manufactured
+/// by a factory.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StandingOrder {
+    /// Attack an entity.
+    Attack(Entity),
+    /// Move to a location. Enemies met on the way are engaged according to
+    /// the stance of the unit.
+    AttackMove(Vec2),
+    /// Board a carrier.
+    Garrison(Entity),
+    /// Gather resources from a deposit. The order is ignored by units which
+    /// cannot harvest.
+    Harvest(Entity),
+}
+
+impl StandingOrder {
+    /// Returns the targeted entity or None if the order targets a location.
+    fn target(&self) -> Option<Entity> {
+        match self {
+            Self::Attack(target) | Self::Garrison(target) | Self::Harvest(target) => Some(*target),
+            Self::AttackMove(_) => None,
+        }
+    }
+}
+
+/// Standing orders of a factory, one per manufactured unit type.
+///
+/// An order targeting an entity is canceled once the entity no longer
+/// exists.
+#[derive(Component, Default)]
+pub struct StandingOrders(EnumMap<UnitType, Option<StandingOrder>>);
+
+impl StandingOrders {
+    /// Returns the order given to each manufactured unit of a type.
+    pub fn get(&self, unit: UnitType) -> Option<StandingOrder> {
+        self.0[unit]
+    }
+
+    fn set(&mut self, unit: UnitType, order: Option<StandingOrder>) {
+        self.0[unit] = order;
+    }
+}
+
+/// Send this event to change a standing order of a factory.
+#[derive(Event)]
+pub struct SetStandingOrderEvent {
+    factory: Entity,
+    unit: UnitType,
+    order: Option<StandingOrder>,
+}
+
+impl SetStandingOrderEvent {
+    /// # Arguments
+    ///
+    /// * `factory` - a locally simulated building with a factory.
+    ///
+    /// * `unit` - the order is given to each manufactured unit of this type.
+    ///
+    /// * `order` - the new standing order. None means that the units are only
+    ///   sent to the delivery location of the factory.
+    pub fn new(factory: Entity, unit: UnitType, order: Option<StandingOrder>) -> Self {
+        Self {
+            factory,
+            unit,
+            order,
+        }
+    }
+
+    fn factory(&self) -> Entity {
+        self.factory
+    }
+
+    fn unit(&self) -> UnitType {
+        self.unit
+    }
+
+    fn order(&self) -> Option<StandingOrder> {
+        self.order
+    }
+}
+
+/// This event is sent when a standing order of a factory is given to a newly
+/// manufactured unit.
+///
+/// Location orders are executed directly, all other orders are executed by
+/// the plugins implementing the respective behaviour (attacking, boarding,
+/// harvesting) in reaction to this event.
+#[derive(Event)]
+pub struct StandingOrderEvent {
+    unit: Entity,
+    order: StandingOrder,
+}
+
+impl StandingOrderEvent {
+    fn new(unit: Entity, order: StandingOrder) -> Self {
+        Self { unit, order }
+    }
+
+    /// The manufactured, locally simulated, unit.
+    pub fn unit(&self) -> Entity {
+        self.unit
+    }
+
+    pub fn order(&self) -> StandingOrder {
+        self.order
+    }
+}
+
+fn set_orders(
+    mut events: EventReader<SetStandingOrderEvent>,
+    mut factories: Query<&mut StandingOrders>,
+) {
+    for event in events.read() {
+        if let Ok(mut orders) = factories.get_mut(event.factory()) {
+            orders.set(event.unit(), event.order());
+        }
+    }
+}
+
+fn apply_orders(
+    mut spawned_events: EventReader<LocalActiveSpawnedEvent>,
+    mut factories: Query<&mut StandingOrders>,
+    targets: Query<(), With<ObjectTypeComponent>>,
+    mut path_events: EventWriter<UpdateEntityPathEvent>,
+    mut order_events: EventWriter<StandingOrderEvent>,
+) {
+    for event in spawned_events.read() {
+        let ActiveObjectType::Unit(unit_type) = event.object_type() else {
+            continue;
+        };
+        let Ok(mut orders) = factories.get_mut(event.source()) else {
+            continue;
+        };
+        let Some(order) = orders.get(unit_type) else {
+            continue;
+        };
+
+        if order
+            .target()
+            .map_or(false, |target| !targets.contains(target))
+        {
+            info!(
+                "Target of standing order for {unit_type} in {:?} no longer exists.",
+                event.source()
+            );
+            orders.set(unit_type, None);
+            continue;
+        }
+
+        let unit = event.entity();
+        if let StandingOrder::AttackMove(location) = order {
+            path_events.send(UpdateEntityPathEvent::new(
+                unit,
+                PathTarget::new(location, PathQueryProps::exact(), false),
+            ));
+        }
+        order_events.send(StandingOrderEvent::new(unit, order));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standing_orders() {
+        let target = Entity::from_raw(7);
+        let mut orders = StandingOrders::default();
+        assert!(orders.get(UnitType::Attacker).is_none());
+
+        orders.set(UnitType::Attacker, Some(StandingOrder::Attack(target)));
+        orders.set(UnitType::Harvester, Some(StandingOrder::Harvest(target)));
+        assert_eq!(
+            orders.get(UnitType::Attacker),
+            Some(StandingOrder::Attack(target))
+        );
+        assert!(orders.get(UnitType::Scout).is_none());
+        assert_eq!(
+            orders.get(UnitType::Harvester).unwrap().target(),
+            Some(target)
+        );
+
+        let location = Vec2::new(10., -4.);
+        orders.set(UnitType::Scout, Some(StandingOrder::AttackMove(location)));
+        assert!(orders.get(UnitType::Scout).unwrap().target().is_none());
+
+        orders.set(UnitType::Attacker, None);
+        assert!(orders.get(UnitType::Attacker).is_none());
+    }
+}
@@ -1,9 +1,16 @@
 use bevy::prelude::*;
-use de_behaviour::ChaseTargetEvent;
-use de_combat::AttackEvent;
-use de_construction::{AssemblyLine, ChangeDeliveryLocationEvent};
+use de_audio::{PlayVoiceEvent, VoiceLine};
+use de_behaviour::{ChaseTargetEvent, SetPatrolEvent};
+use de_combat::{AttackEvent, GarrisonEvent, RepairEvent};
+use de_construction::{
+    AssemblyLine, ChangeDeliveryLocationEvent, SetStandingOrderEvent, StandingOrder, StandingOrders,
+};
 use de_core::{gamestate::GameState, objects::MovableSolid, schedule::InputSchedule};
+use de_economy::HarvestEvent;
+use de_objects::{Garrison, Harvester, RepairBeam};
 use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
+use de_types::{objects::UnitType, projection::ToFlat};
+use enum_iterator::all;
 
 use crate::selection::Selected;
 
@@ -12,14 +19,24 @@ pub(super) struct ExecutorPlugin;
 impl Plugin for ExecutorPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SendSelectedEvent>()
+            .add_event::<PatrolSelectedEvent>()
             .add_event::<DeliveryLocationSelectedEvent>()
+            .add_event::<AttackMoveSelectedEvent>()
             .add_event::<GroupAttackEvent>()
+            .add_event::<GroupHarvestEvent>()
+            .add_event::<GroupGarrisonEvent>()
+            .add_event::<GroupRepairEvent>()
             .add_systems(
                 InputSchedule,
                 (
                     send_selected_system.in_set(CommandsSet::SendSelected),
+                    patrol_system.in_set(CommandsSet::Patrol),
                     delivery_location_system.in_set(CommandsSet::DeliveryLocation),
                     attack_system.in_set(CommandsSet::Attack),
+                    harvest_system.in_set(CommandsSet::Harvest),
+                    garrison_system.in_set(CommandsSet::Garrison),
+                    repair_system.in_set(CommandsSet::Repair),
+                    standing_orders_system.in_set(CommandsSet::StandingOrders),
                 )
                     .run_if(in_state(GameState::Playing)),
             );
@@ -29,8 +46,13 @@ impl Plugin for ExecutorPlugin {
 #[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
 pub(crate) enum CommandsSet {
     SendSelected,
+    Patrol,
     DeliveryLocation,
     Attack,
+    Harvest,
+    Garrison,
+    Repair,
+    StandingOrders,
 }
 
 /// Send this event to send all selected movable units to a point on the map.
@@ -47,6 +69,21 @@ impl SendSelectedEvent {
     }
 }
 
+/// Send this event to make all selected movable units patrol between their
+/// current position and a point on the map.
+#[derive(Event)]
+pub(crate) struct PatrolSelectedEvent(Vec2);
+
+impl PatrolSelectedEvent {
+    pub(crate) fn new(target: Vec2) -> Self {
+        Self(target)
+    }
+
+    fn target(&self) -> Vec2 {
+        self.0
+    }
+}
+
 /// Send this event to set manufacturing delivery location for all selected
 /// building with a factory.
 #[derive(Event)]
@@ -62,6 +99,21 @@ impl DeliveryLocationSelectedEvent {
     }
 }
 
+/// Send this event to make all selected buildings with a factory send their
+/// manufactured units to a point on the map, engaging enemies on the way.
+#[derive(Event)]
+pub(crate) struct AttackMoveSelectedEvent(Vec2);
+
+impl AttackMoveSelectedEvent {
+    pub(crate) fn new(target: Vec2) -> Self {
+        Self(target)
+    }
+
+    fn target(&self) -> Vec2 {
+        self.0
+    }
+}
+
 /// Send this event to attack an enemy with all selected movable units. The
 /// target must be an enemy entity.
 #[derive(Event)]
@@ -77,17 +129,76 @@ impl GroupAttackEvent {
     }
 }
 
+/// Send this event to make all selected harvesters gather resources from a
+/// deposit. The target must be a resource deposit entity.
+#[derive(Event)]
+pub(crate) struct GroupHarvestEvent(Entity);
+
+impl GroupHarvestEvent {
+    pub(crate) fn new(deposit: Entity) -> Self {
+        Self(deposit)
+    }
+
+    fn deposit(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Send this event to garrison all selected movable units in a carrier. The
+/// target must be an entity with a garrison.
+#[derive(Event)]
+pub(crate) struct GroupGarrisonEvent(Entity);
+
+impl GroupGarrisonEvent {
+    pub(crate) fn new(carrier: Entity) -> Self {
+        Self(carrier)
+    }
+
+    fn carrier(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Send this event to make all selected units with a repair beam repair an
+/// object. The target must be a damaged object of the local player.
+#[derive(Event)]
+pub(crate) struct GroupRepairEvent(Entity);
+
+impl GroupRepairEvent {
+    pub(crate) fn new(target: Entity) -> Self {
+        Self(target)
+    }
+
+    fn target(&self) -> Entity {
+        self.0
+    }
+}
+
 type SelectedMovable = (With<Selected>, With<MovableSolid>);
 
+#[allow(clippy::too_many_arguments)]
 fn send_selected_system(
     mut send_events: EventReader<SendSelectedEvent>,
     selected: Query<Entity, SelectedMovable>,
     mut path_events: EventWriter<UpdateEntityPathEvent>,
     mut chase_events: EventWriter<ChaseTargetEvent>,
+    mut harvest_events: EventWriter<HarvestEvent>,
+    mut garrison_events: EventWriter<GarrisonEvent>,
+    mut patrol_events: EventWriter<SetPatrolEvent>,
+    mut repair_events: EventWriter<RepairEvent>,
+    mut voice_events: EventWriter<PlayVoiceEvent>,
 ) {
     if let Some(send) = send_events.read().last() {
+        if !selected.is_empty() {
+            voice_events.send(PlayVoiceEvent::new(VoiceLine::Move));
+        }
+
         for entity in selected.iter() {
             chase_events.send(ChaseTargetEvent::new(entity, None));
+            harvest_events.send(HarvestEvent::new(entity, None));
+            garrison_events.send(GarrisonEvent::new(entity, None));
+            patrol_events.send(SetPatrolEvent::new(entity, None));
+            repair_events.send(RepairEvent::new(entity, None));
             path_events.send(UpdateEntityPathEvent::new(
                 entity,
                 PathTarget::new(send.target(), PathQueryProps::exact(), false),
@@ -96,6 +207,33 @@ fn send_selected_system(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn patrol_system(
+    mut in_events: EventReader<PatrolSelectedEvent>,
+    selected: Query<(Entity, &Transform), SelectedMovable>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+    mut harvest_events: EventWriter<HarvestEvent>,
+    mut garrison_events: EventWriter<GarrisonEvent>,
+    mut patrol_events: EventWriter<SetPatrolEvent>,
+    mut repair_events: EventWriter<RepairEvent>,
+    mut voice_events: EventWriter<PlayVoiceEvent>,
+) {
+    if let Some(event) = in_events.read().last() {
+        if !selected.is_empty() {
+            voice_events.send(PlayVoiceEvent::new(VoiceLine::Move));
+        }
+
+        for (entity, transform) in selected.iter() {
+            chase_events.send(ChaseTargetEvent::new(entity, None));
+            harvest_events.send(HarvestEvent::new(entity, None));
+            garrison_events.send(GarrisonEvent::new(entity, None));
+            repair_events.send(RepairEvent::new(entity, None));
+            let waypoints = vec![transform.translation.to_flat(), event.target()];
+            patrol_events.send(SetPatrolEvent::new(entity, Some(waypoints)));
+        }
+    }
+}
+
 type SelectedFactory = (With<Selected>, With<AssemblyLine>);
 
 fn delivery_location_system(
@@ -113,11 +251,163 @@ fn delivery_location_system(
 fn attack_system(
     mut group_events: EventReader<GroupAttackEvent>,
     selected: Query<Entity, SelectedMovable>,
+    carriers: Query<&Garrison, With<Selected>>,
     mut individual_events: EventWriter<AttackEvent>,
+    mut garrison_events: EventWriter<GarrisonEvent>,
+    mut repair_events: EventWriter<RepairEvent>,
+    mut voice_events: EventWriter<PlayVoiceEvent>,
 ) {
     if let Some(group_event) = group_events.read().last() {
+        if !selected.is_empty() || !carriers.is_empty() {
+            voice_events.send(PlayVoiceEvent::new(VoiceLine::Attack));
+        }
+
         for attacker in selected.iter() {
+            garrison_events.send(GarrisonEvent::new(attacker, None));
+            repair_events.send(RepairEvent::new(attacker, None));
             individual_events.send(AttackEvent::new(attacker, group_event.target()));
         }
+
+        // Garrisoned units attack from within their carriers.
+        for garrison in carriers.iter() {
+            for &attacker in garrison.occupants() {
+                individual_events.send(AttackEvent::new(attacker, group_event.target()));
+            }
+        }
+    }
+}
+
+type SelectedHarvesters = (With<Selected>, With<Harvester>);
+
+fn harvest_system(
+    mut group_events: EventReader<GroupHarvestEvent>,
+    selected: Query<Entity, SelectedHarvesters>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+    mut harvest_events: EventWriter<HarvestEvent>,
+    mut patrol_events: EventWriter<SetPatrolEvent>,
+) {
+    if let Some(group_event) = group_events.read().last() {
+        for harvester in selected.iter() {
+            chase_events.send(ChaseTargetEvent::new(harvester, None));
+            patrol_events.send(SetPatrolEvent::new(harvester, None));
+            harvest_events.send(HarvestEvent::new(harvester, Some(group_event.deposit())));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn garrison_system(
+    mut group_events: EventReader<GroupGarrisonEvent>,
+    selected: Query<Entity, SelectedMovable>,
+    mut chase_events: EventWriter<ChaseTargetEvent>,
+    mut harvest_events: EventWriter<HarvestEvent>,
+    mut garrison_events: EventWriter<GarrisonEvent>,
+    mut patrol_events: EventWriter<SetPatrolEvent>,
+    mut repair_events: EventWriter<RepairEvent>,
+    mut voice_events: EventWriter<PlayVoiceEvent>,
+) {
+    if let Some(group_event) = group_events.read().last() {
+        let carrier = group_event.carrier();
+        let mut units = selected
+            .iter()
+            .filter(|&entity| entity != carrier)
+            .peekable();
+        if units.peek().is_some() {
+            voice_events.send(PlayVoiceEvent::new(VoiceLine::Move));
+        }
+
+        for unit in units {
+            chase_events.send(ChaseTargetEvent::new(unit, None));
+            harvest_events.send(HarvestEvent::new(unit, None));
+            patrol_events.send(SetPatrolEvent::new(unit, None));
+            repair_events.send(RepairEvent::new(unit, None));
+            garrison_events.send(GarrisonEvent::new(unit, Some(carrier)));
+        }
+    }
+}
+
+type SelectedRepairers = (With<Selected>, With<RepairBeam>);
+
+fn repair_system(
+    mut group_events: EventReader<GroupRepairEvent>,
+    selected: Query<Entity, SelectedRepairers>,
+    mut harvest_events: EventWriter<HarvestEvent>,
+    mut garrison_events: EventWriter<GarrisonEvent>,
+    mut patrol_events: EventWriter<SetPatrolEvent>,
+    mut repair_events: EventWriter<RepairEvent>,
+    mut voice_events: EventWriter<PlayVoiceEvent>,
+) {
+    if let Some(group_event) = group_events.read().last() {
+        let target = group_event.target();
+        let mut units = selected
+            .iter()
+            .filter(|&entity| entity != target)
+            .peekable();
+        if units.peek().is_some() {
+            voice_events.send(PlayVoiceEvent::new(VoiceLine::Move));
+        }
+
+        // The chase is started by the repair subsystem itself.
+        for unit in units {
+            harvest_events.send(HarvestEvent::new(unit, None));
+            garrison_events.send(GarrisonEvent::new(unit, None));
+            patrol_events.send(SetPatrolEvent::new(unit, None));
+            repair_events.send(RepairEvent::new(unit, Some(target)));
+        }
+    }
+}
+
+/// Sets standing orders of all selected factories according to the last group
+/// command. Changing delivery location cancels only attack-move orders, i.e.
+/// the other orders targeting a location.
+fn standing_orders_system(
+    mut location_events: EventReader<DeliveryLocationSelectedEvent>,
+    mut attack_move_events: EventReader<AttackMoveSelectedEvent>,
+    mut attack_events: EventReader<GroupAttackEvent>,
+    mut harvest_events: EventReader<GroupHarvestEvent>,
+    mut garrison_events: EventReader<GroupGarrisonEvent>,
+    selected: Query<(Entity, &StandingOrders), With<Selected>>,
+    mut order_events: EventWriter<SetStandingOrderEvent>,
+) {
+    let mut orders: Vec<(UnitType, Option<StandingOrder>)> = Vec::new();
+
+    if location_events.read().last().is_some() {
+        for (factory, factory_orders) in selected.iter() {
+            for unit in all::<UnitType>() {
+                if let Some(StandingOrder::AttackMove(_)) = factory_orders.get(unit) {
+                    order_events.send(SetStandingOrderEvent::new(factory, unit, None));
+                }
+            }
+        }
+    }
+    if let Some(event) = attack_move_events.read().last() {
+        let order = Some(StandingOrder::AttackMove(event.target()));
+        orders.extend(
+            all::<UnitType>()
+                .filter(|&unit| unit != UnitType::Harvester)
+                .map(|unit| (unit, order)),
+        );
+    }
+    if let Some(event) = attack_events.read().last() {
+        let order = Some(StandingOrder::Attack(event.target()));
+        orders.extend(
+            all::<UnitType>()
+                .filter(|&unit| unit != UnitType::Harvester)
+                .map(|unit| (unit, order)),
+        );
+    }
+    if let Some(event) = harvest_events.read().last() {
+        let order = Some(StandingOrder::Harvest(event.deposit()));
+        orders.push((UnitType::Harvester, order));
+    }
+    if let Some(event) = garrison_events.read().last() {
+        let order = Some(StandingOrder::Garrison(event.carrier()));
+        orders.extend(all::<UnitType>().map(|unit| (unit, order)));
+    }
+
+    for (factory, _) in selected.iter() {
+        for &(unit, order) in orders.iter() {
+            order_events.send(SetStandingOrderEvent::new(factory, unit, order));
+        }
     }
 }
@@ -1,6 +1,8 @@
 //! This module implements user input / user command handling, for example
 //! keyboard shortcuts, mouse actions events, and so on.
 
+use std::time::Duration;
+
 use bevy::{
     input::{
         keyboard::KeyboardInput,
@@ -10,19 +12,24 @@ use bevy::{
     prelude::*,
     window::PrimaryWindow,
 };
+use de_behaviour::{SetStanceEvent, Stance, StanceSet};
 use de_camera::{
     CameraSet, MoveCameraHorizontallyEvent, RotateCameraEvent, TiltCameraEvent, ZoomCameraEvent,
 };
+use de_combat::{SelfDestructEvent, ToggleCloakEvent, UngarrisonEvent};
 use de_conf::Configuration;
 use de_core::{
     gamestate::GameState,
     gconfig::GameConfig,
-    objects::{ObjectTypeComponent, Playable},
+    gstats::GameStats,
+    objects::{self, Garrisoned, ObjectTypeComponent, Playable},
     player::PlayerComponent,
     schedule::InputSchedule,
     screengeom::ScreenRect,
 };
-use de_spawner::{DraftAllowed, ObjectCounter};
+use de_gui::{KeyboardCapture, ToastEvent};
+use de_objects::{Deposit, Garrison, Health, RepairBeam};
+use de_spawner::{DraftAllowed, ObjectCounter, TransferOwnershipEvent};
 use de_types::{
     objects::{BuildingType, PLAYER_MAX_BUILDINGS},
     projection::ToFlat,
@@ -30,11 +37,12 @@ use de_types::{
 use enum_map::enum_map;
 
 use super::{
-    executor::DeliveryLocationSelectedEvent, keyboard::KeyCondition, CommandsSet, GroupAttackEvent,
-    SendSelectedEvent,
+    executor::DeliveryLocationSelectedEvent, keyboard::KeyCondition, AttackMoveSelectedEvent,
+    CommandsSet, GroupAttackEvent, GroupGarrisonEvent, GroupHarvestEvent, GroupRepairEvent,
+    PatrolSelectedEvent, SendSelectedEvent,
 };
 use crate::{
-    draft::{DiscardDraftsEvent, DraftSet, NewDraftEvent, SpawnDraftsEvent},
+    draft::{BuildingCounter, DiscardDraftsEvent, DraftSet, NewDraftEvent, SpawnDraftsEvent},
     hud::{GameMenuSet, ToggleGameMenuEvent, UpdateSelectionBoxEvent},
     mouse::{
         DragUpdateType, MouseClickedEvent, MouseDoubleClickedEvent, MouseDraggedEvent, MouseSet,
@@ -48,6 +56,9 @@ use crate::{
 /// Horizontal camera movement is initiated if mouse cursor is within this
 /// distance to window edge.
 const MOVE_MARGIN: f32 = 2.;
+/// Self-destruction has to be confirmed by a repeated key press within this
+/// time limit.
+const SELF_DESTRUCT_CONFIRMATION: Duration = Duration::from_secs(3);
 
 pub(super) struct HandlersPlugin;
 
@@ -56,6 +67,9 @@ impl HandlersPlugin {
         let key_map = enum_map! {
             BuildingType::Base => KeyCode::KeyB,
             BuildingType::PowerHub => KeyCode::KeyP,
+            BuildingType::Bunker => KeyCode::KeyK,
+            BuildingType::ShieldDome => KeyCode::KeyD,
+            BuildingType::Radar => KeyCode::KeyR,
         };
 
         for (building_type, &key) in key_map.iter() {
@@ -81,8 +95,13 @@ impl Plugin for HandlersPlugin {
                     .after(PointerSet::Update)
                     .after(MouseSet::Buttons)
                     .before(CommandsSet::SendSelected)
+                    .before(CommandsSet::Patrol)
                     .before(CommandsSet::DeliveryLocation)
-                    .before(CommandsSet::Attack),
+                    .before(CommandsSet::Attack)
+                    .before(CommandsSet::Harvest)
+                    .before(CommandsSet::Garrison)
+                    .before(CommandsSet::Repair)
+                    .before(CommandsSet::StandingOrders),
                 left_click_handler
                     .run_if(on_click(MouseButton::Left))
                     .in_set(HandlersSet::LeftClick)
@@ -121,6 +140,14 @@ impl Plugin for HandlersPlugin {
                 update_drags
                     .before(AreaSelectSet::SelectInArea)
                     .after(MouseSet::Buttons),
+                toggle_cloak.run_if(KeyCondition::single(KeyCode::KeyC).build()),
+                ungarrison.run_if(KeyCondition::single(KeyCode::KeyU).build()),
+                cycle_stance
+                    .run_if(KeyCondition::single(KeyCode::KeyS).build())
+                    .before(StanceSet::SetStanceEvent),
+                self_destruct.run_if(KeyCondition::single(KeyCode::Delete).build()),
+                gift_to_ally.run_if(KeyCondition::single(KeyCode::KeyG).build()),
+                count_actions.after(MouseSet::Buttons),
             )
                 .run_if(in_state(GameState::Playing)),
         );
@@ -150,19 +177,60 @@ fn on_double_click(button: MouseButton) -> impl Fn(EventReader<MouseDoubleClicke
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn right_click_handler(
     config: Res<GameConfig>,
+    keys: Res<ButtonInput<KeyCode>>,
     mut send_events: EventWriter<SendSelectedEvent>,
+    mut patrol_events: EventWriter<PatrolSelectedEvent>,
     mut location_events: EventWriter<DeliveryLocationSelectedEvent>,
+    mut attack_move_events: EventWriter<AttackMoveSelectedEvent>,
     mut attack_events: EventWriter<GroupAttackEvent>,
-    targets: Query<&PlayerComponent>,
+    mut harvest_events: EventWriter<GroupHarvestEvent>,
+    mut garrison_events: EventWriter<GroupGarrisonEvent>,
+    mut repair_events: EventWriter<GroupRepairEvent>,
+    targets: Query<(&PlayerComponent, Option<&Health>)>,
+    neutrals: Query<(), (With<Health>, Without<PlayerComponent>)>,
+    deposits: Query<(), With<Deposit>>,
+    carriers: Query<&PlayerComponent, With<Garrison>>,
+    repairers: Query<(), (With<Selected>, With<RepairBeam>)>,
     pointer: Res<Pointer>,
 ) {
+    // Own damaged objects are repaired only if the selection can repair them
+    // so that they can be clicked through otherwise.
+    if let Some(damaged) = pointer.entity().filter(|&entity| {
+        !repairers.is_empty()
+            && targets.get(entity).map_or(false, |(&owner, health)| {
+                config.locals().is_playable(*owner)
+                    && health.is_some_and(|health| health.health() < health.max())
+            })
+    }) {
+        repair_events.send(GroupRepairEvent::new(damaged));
+        return;
+    }
+
+    if let Some(deposit) = pointer.entity().filter(|&entity| deposits.contains(entity)) {
+        harvest_events.send(GroupHarvestEvent::new(deposit));
+        return;
+    }
+
+    if let Some(carrier) = pointer.entity().filter(|&entity| {
+        carriers.get(entity).map_or(false, |&owner| {
+            config.controls(config.locals().playable(), *owner)
+        })
+    }) {
+        garrison_events.send(GroupGarrisonEvent::new(carrier));
+        return;
+    }
+
+    // Destructible neutral objects are attacked only on explicit request so
+    // that they can be clicked through when moving units.
+    let force_attack = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
     match pointer.entity().filter(|&entity| {
         targets
             .get(entity)
-            .map(|&player| !config.locals().is_playable(*player))
-            .unwrap_or(false)
+            .map(|(&player, _)| !config.locals().is_playable(*player))
+            .unwrap_or(force_attack && neutrals.contains(entity))
     }) {
         Some(enemy) => {
             attack_events.send(GroupAttackEvent::new(enemy));
@@ -171,12 +239,38 @@ fn right_click_handler(
             let Some(target) = pointer.terrain_point().map(|p| p.to_flat()) else {
                 return;
             };
-            send_events.send(SendSelectedEvent::new(target));
-            location_events.send(DeliveryLocationSelectedEvent::new(target));
+            if keys.pressed(KeyCode::AltLeft) || keys.pressed(KeyCode::AltRight) {
+                patrol_events.send(PatrolSelectedEvent::new(target));
+            } else if force_attack {
+                // Factories give manufactured units an attack-move order
+                // instead of changing their delivery location.
+                send_events.send(SendSelectedEvent::new(target));
+                attack_move_events.send(AttackMoveSelectedEvent::new(target));
+            } else {
+                send_events.send(SendSelectedEvent::new(target));
+                location_events.send(DeliveryLocationSelectedEvent::new(target));
+            }
         }
     }
 }
 
+/// Counts mouse clicks and key presses of the player, see [`GameStats`].
+fn count_actions(
+    mut stats: ResMut<GameStats>,
+    mut click_events: EventReader<MouseClickedEvent>,
+    keys: Res<ButtonInput<KeyCode>>,
+    capture: KeyboardCapture,
+) {
+    let clicks = click_events.read().count();
+    // Typing (e.g. into the chat) is not a game action.
+    let presses = if capture.captured() {
+        0
+    } else {
+        keys.get_just_pressed().count()
+    };
+    stats.record_actions((clicks + presses) as u32);
+}
+
 fn double_click_handler(
     keys: Res<ButtonInput<KeyCode>>,
     pointer: Res<Pointer>,
@@ -326,7 +420,8 @@ fn left_click_handler(
         };
         select_events.send(event);
     } else {
-        draft_events.send(SpawnDraftsEvent);
+        let keep = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
+        draft_events.send(SpawnDraftsEvent::new(keep));
     }
 }
 
@@ -344,16 +439,12 @@ fn handle_escape(
 
 fn place_draft(
     building_type: BuildingType,
-) -> impl Fn(Res<GameConfig>, Res<ObjectCounter>, Res<Pointer>, EventWriter<NewDraftEvent>) {
+) -> impl Fn(Res<GameConfig>, BuildingCounter, Res<Pointer>, EventWriter<NewDraftEvent>) {
     move |conf: Res<GameConfig>,
-          counter: Res<ObjectCounter>,
+          counter: BuildingCounter,
           pointer: Res<Pointer>,
           mut events: EventWriter<NewDraftEvent>| {
-        if counter
-            .player(conf.locals().playable())
-            .map_or(0, |c| c.building_count())
-            >= PLAYER_MAX_BUILDINGS
-        {
+        if counter.count(conf.locals().playable()) >= PLAYER_MAX_BUILDINGS {
             warn!("Maximum number of buildings reached.");
             return;
         }
@@ -362,18 +453,137 @@ fn place_draft(
             Some(point) => point,
             None => return,
         };
-        events.send(NewDraftEvent::new(point, building_type));
+        events.send(NewDraftEvent::new(
+            point,
+            building_type,
+            conf.locals().playable(),
+        ));
     }
 }
 
-fn select_all(
-    playable: Query<Entity, (With<Playable>, Without<Selected>)>,
-    mut events: EventWriter<SelectEvent>,
-) {
+type UnselectedPlayable = (With<Playable>, Without<Selected>, Without<Garrisoned>);
+
+fn select_all(playable: Query<Entity, UnselectedPlayable>, mut events: EventWriter<SelectEvent>) {
     let entities = playable.iter().collect();
     events.send(SelectEvent::many(entities, SelectionMode::AddToggle));
 }
 
+fn toggle_cloak(
+    selected: Query<Entity, With<Selected>>,
+    mut events: EventWriter<ToggleCloakEvent>,
+) {
+    for entity in selected.iter() {
+        events.send(ToggleCloakEvent::new(entity));
+    }
+}
+
+fn ungarrison(
+    selected: Query<Entity, (With<Selected>, With<Garrison>)>,
+    mut events: EventWriter<UngarrisonEvent>,
+) {
+    for entity in selected.iter() {
+        events.send(UngarrisonEvent::new(entity));
+    }
+}
+
+/// Switches selected objects to the next stance. Objects with different
+/// stances are all switched to the default stance.
+fn cycle_stance(
+    selected: Query<(Entity, Option<&Stance>), With<Selected>>,
+    mut toasts: EventWriter<ToastEvent>,
+    mut events: EventWriter<SetStanceEvent>,
+) {
+    let mut stances = selected
+        .iter()
+        .map(|(_, stance)| stance.copied().unwrap_or_default());
+    let Some(first) = stances.next() else {
+        return;
+    };
+    let stance = if stances.all(|stance| stance == first) {
+        first.next()
+    } else {
+        Stance::default()
+    };
+
+    for (entity, _) in selected.iter() {
+        events.send(SetStanceEvent::new(entity, stance));
+    }
+    toasts.send(ToastEvent::new(format!("Stance: {stance}")));
+}
+
+/// Self-destructs selected objects on a second key press. The first key press
+/// only asks for confirmation.
+fn self_destruct(
+    time: Res<Time>,
+    mut pending: Local<Option<(Duration, Vec<Entity>)>>,
+    selected: Query<Entity, With<Selected>>,
+    mut toasts: EventWriter<ToastEvent>,
+    mut events: EventWriter<SelfDestructEvent>,
+) {
+    let mut entities: Vec<Entity> = selected.iter().collect();
+    if entities.is_empty() {
+        return;
+    }
+    entities.sort();
+
+    let now = time.elapsed();
+    // The confirmation is valid only for the very same selection.
+    let confirmed = pending.take().map_or(false, |(deadline, pending)| {
+        now <= deadline && pending == entities
+    });
+
+    if confirmed {
+        for &entity in entities.iter() {
+            events.send(SelfDestructEvent::new(entity));
+        }
+    } else {
+        toasts.send(ToastEvent::new(
+            "Press Delete again to self-destruct the selection.",
+        ));
+        *pending = Some((now + SELF_DESTRUCT_CONFIRMATION, entities));
+    }
+}
+
+type SelectedLocal = (With<Selected>, With<objects::Local>);
+
+/// Gives selected objects of the playable player to an ally. The ally with
+/// the fewest objects receives the whole selection.
+fn gift_to_ally(
+    config: Res<GameConfig>,
+    counter: Res<ObjectCounter>,
+    selected: Query<(Entity, &PlayerComponent), SelectedLocal>,
+    mut toasts: EventWriter<ToastEvent>,
+    mut events: EventWriter<TransferOwnershipEvent>,
+) {
+    let playable = config.locals().playable();
+    let entities: Vec<Entity> = selected
+        .iter()
+        .filter_map(|(entity, &player)| (*player == playable).then_some(entity))
+        .collect();
+    if entities.is_empty() {
+        return;
+    }
+
+    let ally = counter
+        .counters()
+        .filter(|&(&player, objects)| {
+            player != playable && config.teams().allied(playable, player) && objects.total() > 0
+        })
+        .min_by_key(|&(&player, objects)| (objects.total(), player))
+        .map(|(&player, _)| player);
+    let Some(ally) = ally else {
+        toasts.send(ToastEvent::new(
+            "There is no ally to give the selection to.",
+        ));
+        return;
+    };
+
+    for entity in entities {
+        events.send(TransferOwnershipEvent::new(entity, ally));
+    }
+    toasts.send(ToastEvent::new(format!("Selection given to {ally}.")));
+}
+
 fn select_all_visible(mut events: EventWriter<SelectInRectEvent>) {
     events.send(SelectInRectEvent::new(
         ScreenRect::full(),
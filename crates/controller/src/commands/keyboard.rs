@@ -2,6 +2,7 @@ use bevy::{
     input::{keyboard::KeyboardInput, ButtonState},
     prelude::*,
 };
+use de_gui::KeyboardCapture;
 
 /// Builder of keyboard events & state based system execution condition.
 #[derive(Copy, Clone)]
@@ -33,10 +34,21 @@ impl KeyCondition {
         self
     }
 
+    /// Builds the condition. It never passes while keyboard input is captured
+    /// by the UI, e.g. while the user is typing into a text box.
     pub(super) fn build(
         self,
-    ) -> impl Fn(Res<ButtonInput<KeyCode>>, EventReader<KeyboardInput>) -> bool {
-        move |keys: Res<ButtonInput<KeyCode>>, mut events: EventReader<KeyboardInput>| {
+    ) -> impl Fn(Res<ButtonInput<KeyCode>>, EventReader<KeyboardInput>, KeyboardCapture) -> bool
+    {
+        move |keys: Res<ButtonInput<KeyCode>>,
+              mut events: EventReader<KeyboardInput>,
+              capture: KeyboardCapture| {
+            if capture.captured() {
+                // Consume the events so that they do not fire later.
+                events.clear();
+                return false;
+            }
+
             let proper_key = events
                 .read()
                 .filter(|k| k.state == ButtonState::Pressed && k.key_code == self.key)
@@ -3,7 +3,9 @@
 
 use bevy::prelude::*;
 pub(crate) use executor::{
-    CommandsSet, DeliveryLocationSelectedEvent, GroupAttackEvent, SendSelectedEvent,
+    AttackMoveSelectedEvent, CommandsSet, DeliveryLocationSelectedEvent, GroupAttackEvent,
+    GroupGarrisonEvent, GroupHarvestEvent, GroupRepairEvent, PatrolSelectedEvent,
+    SendSelectedEvent,
 };
 
 use self::{executor::ExecutorPlugin, handlers::HandlersPlugin};
@@ -1,12 +1,30 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_construction::{BuildingQueues, EnqueueBuildingEvent};
 use de_core::{
     cleanup::DespawnOnGameExit, gamestate::GameState, gconfig::GameConfig,
     objects::ObjectTypeComponent, schedule::InputSchedule, state::AppState,
 };
-use de_spawner::{DraftAllowed, DraftBundle, SpawnLocalActiveEvent};
-use de_types::objects::{BuildingType, ObjectType};
+use de_economy::Stocks;
+use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle, ToastEvent};
+use de_index::{ColliderWithCache, QueryCollider};
+use de_objects::SolidObjects;
+use de_spawner::{DraftAllowed, DraftBundle, ObjectCounter, SpawnLocalActiveEvent};
+use de_types::{
+    objects::{ActiveObjectType, BuildingType, ObjectType, PLAYER_MAX_BUILDINGS},
+    player::Player,
+    projection::ToFlat,
+};
+use parry2d::bounding_volume::{Aabb, BoundingVolume};
+use parry3d::math::Isometry;
+
+use crate::{
+    hud::HUD_COLOR,
+    mouse::{MousePosition, Pointer, PointerSet},
+};
 
-use crate::mouse::{Pointer, PointerSet};
+/// Offset (in logical pixels) of the draft cost preview from the mouse
+/// cursor.
+const PREVIEW_OFFSET: f32 = 16.;
 
 pub(crate) struct DraftPlugin;
 
@@ -32,6 +50,12 @@ impl Plugin for DraftPlugin {
                         .run_if(in_state(GameState::Playing))
                         .after(PointerSet::Update),
                 ),
+            )
+            .add_systems(OnEnter(GameState::Playing), setup_preview)
+            .add_systems(OnExit(GameState::Playing), cleanup_preview)
+            .add_systems(
+                PostUpdate,
+                update_preview.run_if(in_state(GameState::Playing)),
             );
     }
 }
@@ -44,22 +68,49 @@ pub(crate) enum DraftSet {
 }
 
 #[derive(Event)]
-pub(crate) struct SpawnDraftsEvent;
+pub(crate) struct SpawnDraftsEvent {
+    keep: bool,
+}
+
+impl SpawnDraftsEvent {
+    /// # Arguments
+    ///
+    /// * `keep` - if true, the drafts are kept after the buildings are placed
+    ///   so that more buildings of the same type might be placed one after
+    ///   another. The drafts are despawned regardless once their owner
+    ///   reaches the maximum number of buildings.
+    pub(crate) fn new(keep: bool) -> Self {
+        Self { keep }
+    }
+
+    fn keep(&self) -> bool {
+        self.keep
+    }
+}
 
 #[derive(Event)]
 pub(crate) struct NewDraftEvent {
     point: Vec3,
     building_type: BuildingType,
+    player: Player,
 }
 
 #[derive(Event)]
 pub(crate) struct DiscardDraftsEvent;
 
 impl NewDraftEvent {
-    pub(crate) fn new(point: Vec3, building_type: BuildingType) -> Self {
+    /// # Arguments
+    ///
+    /// * `point` - initial position of the draft.
+    ///
+    /// * `building_type` - type of the drafted building.
+    ///
+    /// * `player` - owner of the building once the draft is placed.
+    pub(crate) fn new(point: Vec3, building_type: BuildingType, player: Player) -> Self {
         Self {
             point,
             building_type,
+            player,
         }
     }
 
@@ -70,26 +121,149 @@ impl NewDraftEvent {
     fn building_type(&self) -> BuildingType {
         self.building_type
     }
+
+    fn player(&self) -> Player {
+        self.player
+    }
+}
+
+/// Player to whom the drafted building is going to belong.
+#[derive(Component)]
+struct DraftOwner(Player);
+
+/// Reasons why a building cannot be placed.
+enum PlacementError {
+    NotEnoughResources,
+    /// The building would overlap with a building waiting for construction.
+    Queued,
+}
+
+impl PlacementError {
+    fn message(&self) -> &'static str {
+        match self {
+            Self::NotEnoughResources => "Not enough ore.",
+            Self::Queued => "Another building is going to be constructed there.",
+        }
+    }
+}
+
+/// Places buildings from drafts.
+///
+/// In the map editor, buildings are placed immediately and for free. In a
+/// game, the building cost is paid and the building is enqueued for
+/// construction.
+/// Counts both existing and enqueued buildings.
+#[derive(SystemParam)]
+pub(crate) struct BuildingCounter<'w> {
+    counter: Res<'w, ObjectCounter>,
+    queues: Res<'w, BuildingQueues>,
+}
+
+impl<'w> BuildingCounter<'w> {
+    /// Returns number of existing and enqueued buildings of a player.
+    pub(crate) fn count(&self, player: Player) -> u32 {
+        let existing = self
+            .counter
+            .player(player)
+            .map_or(0, |c| c.building_count());
+        existing + self.queues.queue(player).count() as u32
+    }
+}
+
+#[derive(SystemParam)]
+struct Placement<'w> {
+    config: Res<'w, GameConfig>,
+    counter: BuildingCounter<'w>,
+    solids: SolidObjects<'w>,
+    stocks: ResMut<'w, Stocks>,
+    queues: Res<'w, BuildingQueues>,
+    spawn_active_events: EventWriter<'w, SpawnLocalActiveEvent>,
+    enqueue_events: EventWriter<'w, EnqueueBuildingEvent>,
+}
+
+impl<'w> Placement<'w> {
+    fn place(
+        &mut self,
+        player: Player,
+        building_type: BuildingType,
+        transform: Transform,
+    ) -> Result<(), PlacementError> {
+        let object_type = ActiveObjectType::Building(building_type);
+        if self.config.editor() {
+            self.spawn_active_events
+                .send(SpawnLocalActiveEvent::stationary(
+                    object_type,
+                    transform,
+                    player,
+                ));
+            return Ok(());
+        }
+
+        let aabb = self.flat_aabb(building_type, &transform);
+        if self.queues.queue(player).any(|queued| {
+            aabb.intersects(&self.flat_aabb(queued.building_type(), queued.transform()))
+        }) {
+            return Err(PlacementError::Queued);
+        }
+
+        let cost = self
+            .solids
+            .get(ObjectType::Active(object_type))
+            .construction()
+            .map_or(0., |construction| construction.cost());
+        if !self.stocks.try_spend(player, cost) {
+            return Err(PlacementError::NotEnoughResources);
+        }
+
+        self.enqueue_events
+            .send(EnqueueBuildingEvent::new(player, building_type, transform));
+        Ok(())
+    }
+
+    /// Returns map AABB of a building placed with a transform.
+    fn flat_aabb(&self, building_type: BuildingType, transform: &Transform) -> Aabb {
+        let object_type = ObjectType::Active(ActiveObjectType::Building(building_type));
+        QueryCollider::new(
+            self.solids.get(object_type).collider(),
+            Isometry::new(
+                transform.translation.into(),
+                transform.rotation.to_scaled_axis().into(),
+            ),
+        )
+        .world_aabb()
+        .to_flat()
+    }
 }
 
 fn spawn(
     mut commands: Commands,
-    game_config: Res<GameConfig>,
-    drafts: Query<(Entity, &Transform, &ObjectTypeComponent, &DraftAllowed)>,
-    mut spawn_active_events: EventWriter<SpawnLocalActiveEvent>,
+    mut events: EventReader<SpawnDraftsEvent>,
+    mut placement: Placement,
+    drafts: Query<(
+        Entity,
+        &Transform,
+        &ObjectTypeComponent,
+        &DraftAllowed,
+        &DraftOwner,
+    )>,
+    mut toasts: EventWriter<ToastEvent>,
 ) {
-    for (entity, &transform, &object_type, draft) in drafts.iter() {
+    let keep = events.read().last().map_or(false, |event| event.keep());
+
+    for (entity, &transform, &object_type, draft, owner) in drafts.iter() {
         if draft.allowed() {
-            commands.entity(entity).despawn_recursive();
-            let ObjectType::Active(object_type) = *object_type else {
-                panic!("Cannot place draft of an inactive object.");
+            let ObjectType::Active(ActiveObjectType::Building(building_type)) = *object_type else {
+                panic!("Cannot place draft of a non-building object.");
             };
 
-            spawn_active_events.send(SpawnLocalActiveEvent::stationary(
-                object_type,
-                transform,
-                game_config.locals().playable(),
-            ));
+            if let Err(error) = placement.place(owner.0, building_type, transform) {
+                toasts.send(ToastEvent::new(error.message()));
+                continue;
+            }
+
+            if !keep || placement.counter.count(owner.0) + 1 >= PLAYER_MAX_BUILDINGS {
+                commands.entity(entity).despawn_recursive();
+            }
         }
     }
 }
@@ -116,6 +290,7 @@ fn new_drafts(
                 ..Default::default()
             },
         ),
+        DraftOwner(event.player()),
         DespawnOnGameExit,
     ));
 }
@@ -140,3 +315,88 @@ fn move_drafts(pointer: Res<Pointer>, mut drafts: Query<&mut Transform, With<Dra
         }
     }
 }
+
+/// UI node displaying cost and power consumption of the drafted building next
+/// to the mouse cursor.
+#[derive(Resource)]
+struct DraftPreview {
+    node: Entity,
+    text: Entity,
+}
+
+fn setup_preview(mut commands: GuiCommands) {
+    let node = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    margin: UiRect::new(
+                        Val::Px(PREVIEW_OFFSET),
+                        Val::Px(0.),
+                        Val::Px(PREVIEW_OFFSET),
+                        Val::Px(0.),
+                    ),
+                    ..default()
+                },
+                background_color: HUD_COLOR.into(),
+                ..default()
+            },
+            DespawnOnGameExit,
+        ))
+        .id();
+    let text = commands
+        .spawn_body_text(
+            OuterStyle {
+                margin: UiRect::all(Val::Px(4.)),
+                ..default()
+            },
+            "",
+        )
+        .id();
+    commands.entity(node).add_child(text);
+
+    commands.insert_resource(DraftPreview { node, text });
+}
+
+fn cleanup_preview(mut commands: Commands) {
+    commands.remove_resource::<DraftPreview>();
+}
+
+fn update_preview(
+    preview: Res<DraftPreview>,
+    mouse: Res<MousePosition>,
+    stocks: Res<Stocks>,
+    solids: SolidObjects,
+    drafts: Query<(&ObjectTypeComponent, &DraftOwner)>,
+    mut styles: Query<&mut Style>,
+    mut text_ops: BodyTextOps,
+) {
+    let mut style = styles.get_mut(preview.node).unwrap();
+    let (Some(position), Some((&object_type, owner))) = (mouse.ndc(), drafts.iter().next()) else {
+        if style.display != Display::None {
+            style.display = Display::None;
+        }
+        return;
+    };
+
+    style.display = Display::Flex;
+    style.left = Val::Percent(50. * (position.x + 1.));
+    style.top = Val::Percent(50. * (1. - position.y));
+
+    let solid = solids.get(*object_type);
+    let cost = solid
+        .construction()
+        .map_or(0., |construction| construction.cost());
+    let mut text = format!("Ore: {}", cost.ceil());
+    if stocks.amount(owner.0) < cost {
+        text.push_str(" (not enough)");
+    }
+    // Drain is in joules per second.
+    let drain = solid.power_drain() / 1000.;
+    text.push_str(&format!(" | Power: -{drain:.0} kW"));
+
+    text_ops
+        .set_text(preview.text, text)
+        .expect("Failed to set text of draft preview");
+}
@@ -0,0 +1,83 @@
+//! This module implements an in-game map editor. The editor is active during
+//! a game started with [`de_core::gconfig::GameConfig::new_editor`]: the map
+//! is loaded and spawned as usual but the user places and removes objects of
+//! any player instead of playing the game.
+
+use bevy::{app::PluginGroupBuilder, prelude::*};
+use de_core::{gamestate::GameState, gconfig::is_editor, state::AppState};
+use de_types::{objects::ObjectType, player::Player};
+
+use self::{palette::PalettePlugin, placement::PlacementPlugin, storage::StoragePlugin};
+
+mod palette;
+mod placement;
+mod storage;
+
+pub struct EditorPluginGroup;
+
+impl PluginGroup for EditorPluginGroup {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(EditorPlugin)
+            .add(PalettePlugin)
+            .add(PlacementPlugin)
+            .add(StoragePlugin)
+    }
+}
+
+struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup.run_if(is_editor))
+            .add_systems(OnExit(AppState::InGame), cleanup);
+    }
+}
+
+/// Type and owner of objects placed on the map by the user.
+#[derive(Resource)]
+struct Brush {
+    object_type: Option<ObjectType>,
+    player: Player,
+}
+
+impl Brush {
+    /// Type of objects placed on the map or None if nothing is being placed.
+    fn object_type(&self) -> Option<ObjectType> {
+        self.object_type
+    }
+
+    /// Owner of placed active objects. It has no effect on inactive objects.
+    fn player(&self) -> Player {
+        self.player
+    }
+
+    /// Starts placing objects of the given type. Stops placing them if they
+    /// are already being placed.
+    fn toggle(&mut self, object_type: ObjectType) {
+        if self.object_type == Some(object_type) {
+            self.object_type = None;
+        } else {
+            self.object_type = Some(object_type);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.object_type = None;
+    }
+
+    fn set_player(&mut self, player: Player) {
+        self.player = player;
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(Brush {
+        object_type: None,
+        player: Player::Player1,
+    });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Brush>();
+}
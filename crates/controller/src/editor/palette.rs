@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use de_core::{
+    cleanup::DespawnOnGameExit, gamestate::GameState, gconfig::is_editor, schedule::InputSchedule,
+};
+use de_gui::{ButtonCommands, ButtonOps, GuiCommands, OuterStyle};
+use de_map::meta::MapMetadata;
+use de_types::{
+    objects::{ActiveObjectType, BuildingType, ObjectType},
+    player::Player,
+};
+
+use super::{
+    storage::{SaveMapEvent, ValidateMapEvent},
+    Brush,
+};
+use crate::hud::{InteractionBlocker, HUD_COLOR};
+
+pub(super) struct PalettePlugin;
+
+impl Plugin for PalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup.run_if(is_editor))
+            .add_systems(OnExit(GameState::Playing), cleanup)
+            .add_systems(
+                InputSchedule,
+                button_system
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(is_editor),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct PaletteNodes {
+    player: Entity,
+}
+
+#[derive(Component, Clone, Copy)]
+enum ButtonAction {
+    Brush(ObjectType),
+    NextPlayer,
+    Validate,
+    Save,
+}
+
+fn setup(mut commands: GuiCommands) {
+    let panel = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(12.),
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(0.),
+                    top: Val::Percent(5.),
+                    bottom: Val::Percent(35.),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::horizontal(Val::Percent(0.5)),
+                    ..default()
+                },
+                background_color: HUD_COLOR.into(),
+                ..default()
+            },
+            DespawnOnGameExit,
+            InteractionBlocker,
+        ))
+        .id();
+
+    for object_type in enum_iterator::all::<ObjectType>() {
+        spawn_button(
+            &mut commands,
+            panel,
+            ButtonAction::Brush(object_type),
+            brush_caption(object_type),
+        );
+    }
+    let player = spawn_button(
+        &mut commands,
+        panel,
+        ButtonAction::NextPlayer,
+        player_caption(Player::Player1),
+    );
+    spawn_button(&mut commands, panel, ButtonAction::Validate, "Validate");
+    spawn_button(&mut commands, panel, ButtonAction::Save, "Save");
+
+    commands.insert_resource(PaletteNodes { player });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<PaletteNodes>();
+}
+
+fn spawn_button(
+    commands: &mut GuiCommands,
+    parent: Entity,
+    action: ButtonAction,
+    caption: impl Into<String>,
+) -> Entity {
+    let button = commands
+        .spawn_button(
+            OuterStyle {
+                width: Val::Percent(100.),
+                height: Val::Percent(8.),
+                margin: UiRect::vertical(Val::Percent(0.5)),
+            },
+            caption,
+        )
+        .insert(action)
+        .id();
+    commands.entity(parent).add_child(button);
+    button
+}
+
+fn brush_caption(object_type: ObjectType) -> String {
+    match object_type {
+        ObjectType::Active(ActiveObjectType::Building(BuildingType::Base)) => {
+            "Base (Start)".to_owned()
+        }
+        ObjectType::Active(ActiveObjectType::Building(building)) => building.to_string(),
+        ObjectType::Active(ActiveObjectType::Unit(unit)) => unit.to_string(),
+        ObjectType::Inactive(inactive) => inactive.to_string(),
+    }
+}
+
+fn player_caption(player: Player) -> String {
+    format!("Player {}", player.to_num())
+}
+
+/// Returns the player following `player`, wrapping around after the last
+/// player of the map.
+fn next_player(player: Player, max_player: Player) -> Player {
+    if player >= max_player {
+        Player::Player1
+    } else {
+        Player::try_from(player.to_num() + 1).unwrap()
+    }
+}
+
+fn button_system(
+    nodes: Res<PaletteNodes>,
+    metadata: Res<MapMetadata>,
+    mut brush: ResMut<Brush>,
+    mut button_ops: ButtonOps,
+    interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
+    mut validate_events: EventWriter<ValidateMapEvent>,
+    mut save_events: EventWriter<SaveMapEvent>,
+) {
+    for (&interaction, &action) in interactions.iter() {
+        if let Interaction::Pressed = interaction {
+            match action {
+                ButtonAction::Brush(object_type) => brush.toggle(object_type),
+                ButtonAction::NextPlayer => {
+                    let player = next_player(brush.player(), metadata.max_player());
+                    brush.set_player(player);
+                    button_ops
+                        .set_text(nodes.player, player_caption(player))
+                        .unwrap();
+                }
+                ButtonAction::Validate => {
+                    validate_events.send(ValidateMapEvent);
+                }
+                ButtonAction::Save => {
+                    save_events.send(SaveMapEvent);
+                }
+            }
+        }
+    }
+}
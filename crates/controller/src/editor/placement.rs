@@ -0,0 +1,169 @@
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use de_core::{
+    gamestate::GameState,
+    gconfig::is_editor,
+    objects::{Active, ObjectTypeComponent},
+    player::PlayerComponent,
+    schedule::InputSchedule,
+};
+use de_gui::KeyboardCapture;
+use de_spawner::{
+    DespawnActiveLocalEvent, DespawnInactiveEvent, DraftAllowed, SpawnInactiveEvent,
+    SpawnLocalActiveEvent,
+};
+use de_types::objects::{ActiveObjectType, BuildingType, ObjectType};
+
+use super::Brush;
+use crate::{
+    draft::{DiscardDraftsEvent, DraftSet, NewDraftEvent},
+    mouse::{MouseClickedEvent, MouseSet, Pointer, PointerSet},
+};
+
+const BASE: ObjectType = ObjectType::Active(ActiveObjectType::Building(BuildingType::Base));
+
+pub(super) struct PlacementPlugin;
+
+impl Plugin for PlacementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            InputSchedule,
+            (
+                discard_draft
+                    .run_if(resource_changed::<Brush>)
+                    .before(DraftSet::New),
+                new_draft
+                    .after(discard_draft)
+                    .after(PointerSet::Update)
+                    .before(DraftSet::New),
+                clear_brush
+                    .run_if(on_event::<DiscardDraftsEvent>())
+                    .after(DraftSet::Discard),
+                place
+                    .after(PointerSet::Update)
+                    .after(MouseSet::Buttons)
+                    .before(DraftSet::Spawn),
+                remove
+                    .run_if(input_just_pressed(KeyCode::Delete))
+                    .run_if(|capture: KeyboardCapture| !capture.captured())
+                    .after(PointerSet::Update),
+            )
+                .run_if(in_state(GameState::Playing))
+                .run_if(is_editor),
+        );
+    }
+}
+
+/// Drafts are used to place buildings. Any existing draft is removed once the
+/// brush changes.
+fn discard_draft(mut commands: Commands, drafts: Query<Entity, With<DraftAllowed>>) {
+    for entity in drafts.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Creates a new draft whenever a building brush is active and the previous
+/// draft was placed or discarded.
+fn new_draft(
+    brush: Res<Brush>,
+    pointer: Res<Pointer>,
+    drafts: Query<(), With<DraftAllowed>>,
+    mut events: EventWriter<NewDraftEvent>,
+) {
+    let Some(ObjectType::Active(ActiveObjectType::Building(building_type))) = brush.object_type()
+    else {
+        return;
+    };
+    if !drafts.is_empty() {
+        return;
+    }
+    let Some(point) = pointer.terrain_point() else {
+        return;
+    };
+
+    events.send(NewDraftEvent::new(point, building_type, brush.player()));
+}
+
+fn clear_brush(mut brush: ResMut<Brush>) {
+    brush.clear();
+}
+
+/// Places units and inactive objects on the map. Buildings are placed via
+/// drafts.
+///
+/// Base of each player marks its starting location, therefore any previous
+/// base of the player is removed once a new one is placed.
+#[allow(clippy::too_many_arguments)]
+fn place(
+    brush: Res<Brush>,
+    pointer: Res<Pointer>,
+    mut clicks: EventReader<MouseClickedEvent>,
+    drafts: Query<&DraftAllowed>,
+    objects: Query<(Entity, &ObjectTypeComponent, &PlayerComponent)>,
+    mut spawn_active_events: EventWriter<SpawnLocalActiveEvent>,
+    mut spawn_inactive_events: EventWriter<SpawnInactiveEvent>,
+    mut despawn_events: EventWriter<DespawnActiveLocalEvent>,
+) {
+    // It is desirable to exhaust the iterator, thus .filter().count() is used
+    // instead of .any()
+    if clicks
+        .read()
+        .filter(|event| event.button() == MouseButton::Left)
+        .count()
+        == 0
+    {
+        return;
+    }
+
+    let Some(object_type) = brush.object_type() else {
+        return;
+    };
+    let Some(point) = pointer.terrain_point() else {
+        return;
+    };
+    let transform = Transform::from_translation(point);
+
+    match object_type {
+        ObjectType::Active(ActiveObjectType::Building(_)) => {
+            if object_type != BASE || !drafts.iter().any(|draft| draft.allowed()) {
+                return;
+            }
+
+            for (entity, &other_type, &player) in objects.iter() {
+                if *other_type == BASE && *player == brush.player() {
+                    despawn_events.send(DespawnActiveLocalEvent::new(entity));
+                }
+            }
+        }
+        ObjectType::Active(active_type) => {
+            spawn_active_events.send(SpawnLocalActiveEvent::stationary(
+                active_type,
+                transform,
+                brush.player(),
+            ));
+        }
+        ObjectType::Inactive(inactive_type) => {
+            spawn_inactive_events.send(SpawnInactiveEvent::new(inactive_type, transform));
+        }
+    }
+}
+
+/// Removes the object under the mouse cursor.
+fn remove(
+    pointer: Res<Pointer>,
+    objects: Query<Has<Active>, With<ObjectTypeComponent>>,
+    mut despawn_active_events: EventWriter<DespawnActiveLocalEvent>,
+    mut despawn_inactive_events: EventWriter<DespawnInactiveEvent>,
+) {
+    let Some(entity) = pointer.entity() else {
+        return;
+    };
+    let Ok(active) = objects.get(entity) else {
+        return;
+    };
+
+    if active {
+        despawn_active_events.send(DespawnActiveLocalEvent::new(entity));
+    } else {
+        despawn_inactive_events.send(DespawnInactiveEvent::new(entity));
+    }
+}
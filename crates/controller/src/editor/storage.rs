@@ -0,0 +1,316 @@
+use std::f32::consts::TAU;
+
+use async_std::{fs, path::PathBuf};
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
+use de_core::{
+    fs::{maps_dir, DirError},
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    log_full_error,
+    objects::{MovableSolid, ObjectTypeComponent, StaticSolid},
+    player::PlayerComponent,
+    state::AppState,
+};
+use de_gui::ToastEvent;
+use de_loader::diagnose_map;
+use de_map::{
+    content::{ActiveObject, InactiveObject, InnerObject, Object},
+    diagnostics::{MapReport, Severity},
+    io::{store_map, MapStoringError},
+    map::Map,
+    meta::MapMetadata,
+    script::MapScript,
+};
+use de_objects::SolidObjects;
+use de_types::{objects::ObjectType, player::Player, projection::ToFlat};
+use glam::EulerRot;
+use thiserror::Error;
+
+pub(super) struct StoragePlugin;
+
+impl Plugin for StoragePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ValidateMapEvent>()
+            .add_event::<SaveMapEvent>()
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    validate.run_if(on_event::<ValidateMapEvent>()),
+                    save.run_if(on_event::<SaveMapEvent>()),
+                    check_saved.run_if(resource_exists::<SaveTask>),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(is_editor),
+            );
+    }
+}
+
+/// Send this event to validate the edited map and to report the results to
+/// the user.
+#[derive(Event)]
+pub(super) struct ValidateMapEvent;
+
+/// Send this event to store the edited map to the user maps directory.
+#[derive(Event)]
+pub(super) struct SaveMapEvent;
+
+#[derive(Resource)]
+struct SaveTask(Task<Result<PathBuf, SaveError>>);
+
+/// Path of the file the edited map was most recently saved to.
+#[derive(Resource)]
+struct SavedPath(PathBuf);
+
+type MapObjects<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ObjectTypeComponent,
+        &'static Transform,
+        Option<&'static PlayerComponent>,
+    ),
+    Or<(With<StaticSolid>, With<MovableSolid>)>,
+>;
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<SaveTask>();
+    commands.remove_resource::<SavedPath>();
+}
+
+fn validate(
+    metadata: Res<MapMetadata>,
+    script: Res<MapScript>,
+    objects: MapObjects,
+    solids: SolidObjects,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let map = build_map(&metadata, &script, map_objects(&objects));
+    let report = diagnose_map(&map, |object_type| solids.get(object_type).ichnography());
+    log_report(&report);
+
+    if report.is_empty() {
+        toasts.send(ToastEvent::new("The map is valid."));
+    } else {
+        toasts.send(ToastEvent::new(format!(
+            "The map has {} problem(s), see the log for details.",
+            report.issues().len()
+        )));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn save(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    metadata: Res<MapMetadata>,
+    script: Res<MapScript>,
+    task: Option<Res<SaveTask>>,
+    saved: Option<Res<SavedPath>>,
+    objects: MapObjects,
+    solids: SolidObjects,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    if task.is_some() {
+        toasts.send(ToastEvent::new("The map is already being saved."));
+        return;
+    }
+
+    let map = build_map(&metadata, &script, map_objects(&objects));
+    let report = diagnose_map(&map, |object_type| solids.get(object_type).ichnography());
+    log_report(&report);
+    if !report.is_valid() {
+        toasts.send(ToastEvent::new(
+            "The map is invalid and cannot be saved, see the log for details.",
+        ));
+        return;
+    }
+
+    let previous: PathBuf = match saved {
+        Some(saved) => saved.0.clone(),
+        None => config.map_path().into(),
+    };
+    let task = IoTaskPool::get().spawn(async move {
+        let dir = maps_dir().map_err(|source| SaveError::Dir { source })?;
+        fs::create_dir_all(&dir)
+            .await
+            .map_err(|source| SaveError::Io { source })?;
+
+        // Map file names are derived from map content hashes, thus the edited
+        // map gets a new name.
+        let path = map.compute_hash().construct_path(dir.clone());
+        store_map(&map, &path)
+            .await
+            .map_err(|source| SaveError::Storing { source })?;
+
+        // The previous version of a user map is replaced. Bundled maps are
+        // kept intact.
+        if previous != path && previous.parent() == Some(dir.as_path()) {
+            fs::remove_file(&previous)
+                .await
+                .map_err(|source| SaveError::Io { source })?;
+        }
+        Ok(path)
+    });
+    commands.insert_resource(SaveTask(task));
+}
+
+fn check_saved(
+    mut commands: Commands,
+    mut task: ResMut<SaveTask>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+        return;
+    };
+    commands.remove_resource::<SaveTask>();
+
+    match result {
+        Ok(path) => {
+            info!("Map saved to {}.", path.display());
+            commands.insert_resource(SavedPath(path.clone()));
+            toasts.send(ToastEvent::new(format!(
+                "The map was saved to {}.",
+                path.display()
+            )));
+        }
+        Err(err) => {
+            toasts.send(ToastEvent::new(format!("Map saving failed: {err}")));
+            log_full_error!(err);
+        }
+    }
+}
+
+fn log_report(report: &MapReport) {
+    for issue in report.issues() {
+        match issue.severity() {
+            Severity::Warning => warn!("Edited map {issue}"),
+            Severity::Error => error!("Edited map {issue}"),
+        }
+    }
+}
+
+fn map_objects<'a>(
+    objects: &'a MapObjects,
+) -> impl Iterator<Item = (ObjectType, &'a Transform, Option<Player>)> + 'a {
+    objects
+        .iter()
+        .map(|(&object_type, transform, player)| (*object_type, transform, player.map(|p| **p)))
+}
+
+/// Creates a map from objects currently present in the game.
+///
+/// Objects are moved inside the map bounds if they are (slightly) outside of
+/// it.
+///
+/// # Arguments
+///
+/// * `metadata` - metadata of the edited map.
+///
+/// * `script` - script of the edited map, it is kept intact.
+///
+/// * `objects` - type, transform and (for active objects) owner of each
+///   object on the map.
+fn build_map<'a, I>(metadata: &MapMetadata, script: &MapScript, objects: I) -> Map
+where
+    I: Iterator<Item = (ObjectType, &'a Transform, Option<Player>)>,
+{
+    let bounds = metadata.bounds();
+    let mut map = Map::empty(metadata.clone());
+
+    for (object_type, transform, player) in objects {
+        let position = transform
+            .translation
+            .to_flat()
+            .clamp(bounds.min(), bounds.max());
+        let heading = transform.rotation.to_euler(EulerRot::YXZ).0.rem_euclid(TAU);
+        // Rounding may produce exactly τ.
+        let heading = if heading < TAU { heading } else { 0. };
+
+        let inner = match object_type {
+            ObjectType::Active(active_type) => InnerObject::Active(ActiveObject::new(
+                active_type,
+                player.expect("Active object without a player."),
+            )),
+            ObjectType::Inactive(inactive_type) => {
+                InnerObject::Inactive(InactiveObject::new(inactive_type))
+            }
+        };
+        map.insert_object(Object::new(map.new_placement(position, heading), inner));
+    }
+
+    for trigger in script.triggers() {
+        map.insert_trigger(trigger.clone());
+    }
+
+    map
+}
+
+#[derive(Error, Debug)]
+enum SaveError {
+    #[error("user maps directory error")]
+    Dir { source: DirError },
+    #[error(transparent)]
+    Io { source: std::io::Error },
+    #[error("map storing error")]
+    Storing { source: MapStoringError },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use de_map::size::MapBounds;
+    use de_types::{
+        objects::{ActiveObjectType, BuildingType, InactiveObjectType},
+        projection::ToAltitude,
+    };
+    use glam::{Quat, Vec2, Vec3};
+
+    use super::*;
+
+    #[test]
+    fn test_build_map() {
+        let metadata = MapMetadata::new(
+            "Test Map".into(),
+            MapBounds::new(Vec2::new(100., 100.)),
+            Player::Player2,
+        );
+        let base = ObjectType::Active(ActiveObjectType::Building(BuildingType::Base));
+        let tree = ObjectType::Inactive(InactiveObjectType::Tree);
+
+        let inside = Transform {
+            translation: Vec2::new(10., -20.).to_msl(),
+            rotation: Quat::from_rotation_y(-0.5 * PI),
+            ..default()
+        };
+        let outside = Transform::from_translation(Vec2::new(50.5, 7.).to_msl());
+        let objects = [
+            (base, &inside, Some(Player::Player2)),
+            (tree, &outside, None),
+        ];
+
+        let map = build_map(&metadata, &MapScript::default(), objects.into_iter());
+        assert!(map.diagnose().is_valid());
+
+        let objects = map.content().objects();
+        assert_eq!(objects.len(), 2);
+
+        let placement = objects[0].placement();
+        assert_eq!(placement.position(), Vec2::new(10., -20.));
+        let transform = placement.to_transform();
+        assert!(
+            (transform.rotation * Vec3::X).distance(inside.rotation * Vec3::X) < 1e-4,
+            "{transform:?}"
+        );
+        match objects[0].inner() {
+            InnerObject::Active(object) => assert_eq!(object.player(), Player::Player2),
+            InnerObject::Inactive(_) => unreachable!(),
+        }
+
+        assert_eq!(objects[1].placement().position(), Vec2::new(50., 7.));
+    }
+}
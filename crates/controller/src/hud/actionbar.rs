@@ -1,16 +1,20 @@
 use bevy::prelude::*;
-use de_construction::EnqueueAssemblyEvent;
+use de_conf::Configuration;
+use de_construction::{AssemblyLine, EnqueueAssemblyEvent};
 use de_core::{
     cleanup::DespawnOnGameExit, gamestate::GameState, objects::ObjectTypeComponent,
     schedule::InputSchedule,
 };
 use de_gui::{ButtonCommands, GuiCommands, OuterStyle};
-use de_objects::SolidObjects;
-use de_types::objects::UnitType;
+use de_objects::{AssetCollection, ObjectIcons, SolidObjects};
+use de_types::objects::{ActiveObjectType, ObjectType, UnitType};
 
 use super::{interaction::InteractionBlocker, HUD_COLOR};
 use crate::selection::Selected;
 
+/// Maximum number of displayed manufacturing queue items.
+const MAX_QUEUE_ICONS: usize = 10;
+
 pub(crate) struct ActionBarPlugin;
 
 impl Plugin for ActionBarPlugin {
@@ -24,6 +28,7 @@ impl Plugin for ActionBarPlugin {
                     update
                         .run_if(resource_exists_and_changed::<ActiveEntity>)
                         .after(ActionBarSet::DetectUpdate),
+                    update_queue.after(ActionBarSet::DetectUpdate),
                 )
                     .run_if(in_state(GameState::Playing)),
             )
@@ -40,22 +45,36 @@ enum ActionBarSet {
 }
 
 #[derive(Resource)]
-struct ActionBarNode(Entity);
+struct ActionBarNodes {
+    /// Parent of "manufacture this" buttons.
+    products: Entity,
+    /// Parent of icons of units in the manufacturing queue.
+    queue: Entity,
+}
 
 #[derive(Resource, Default)]
 struct ActiveEntity(Option<Entity>);
 
+/// Manufacturing queue currently displayed in the action bar.
+#[derive(Resource, Default, PartialEq)]
+struct DisplayedQueue {
+    factory: Option<Entity>,
+    units: Vec<UnitType>,
+}
+
 /// An entity attached to every "manufacture this" button in the action bar.
 #[derive(Component)]
 struct ButtonAction(UnitType);
 
 fn cleanup(mut commands: Commands) {
-    commands.remove_resource::<ActionBarNode>();
+    commands.remove_resource::<ActionBarNodes>();
     commands.remove_resource::<ActiveEntity>();
+    commands.remove_resource::<DisplayedQueue>();
 }
 
-fn setup(mut commands: Commands) {
-    let entity = commands
+fn setup(mut commands: Commands, conf: Res<Configuration>) {
+    let margin = conf.graphics().hud_margin();
+    let bar = commands
         .spawn((
             NodeBundle {
                 style: Style {
@@ -64,8 +83,9 @@ fn setup(mut commands: Commands) {
                     position_type: PositionType::Absolute,
                     left: Val::Percent(20.),
                     right: Val::Percent(80.),
-                    top: Val::Percent(85.),
+                    top: Val::Percent(85. - margin),
                     bottom: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
                     ..default()
                 },
                 background_color: HUD_COLOR.into(),
@@ -76,8 +96,27 @@ fn setup(mut commands: Commands) {
         ))
         .id();
 
-    commands.insert_resource(ActionBarNode(entity));
+    let mut spawn_row = |height: f32| {
+        let row = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(height),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        commands.entity(bar).add_child(row);
+        row
+    };
+    let products = spawn_row(65.);
+    let queue = spawn_row(35.);
+
+    commands.insert_resource(ActionBarNodes { products, queue });
     commands.init_resource::<ActiveEntity>();
+    commands.init_resource::<DisplayedQueue>();
 }
 
 fn detect_update(mut active: ResMut<ActiveEntity>, selected: Query<Entity, With<Selected>>) {
@@ -90,25 +129,26 @@ fn detect_update(mut active: ResMut<ActiveEntity>, selected: Query<Entity, With<
 fn update(
     mut commands: GuiCommands,
     solids: SolidObjects,
-    bar_node: Res<ActionBarNode>,
+    icons: Res<ObjectIcons>,
+    nodes: Res<ActionBarNodes>,
     active: Res<ActiveEntity>,
     objects: Query<&ObjectTypeComponent>,
 ) {
-    commands.entity(bar_node.0).despawn_descendants();
+    commands.entity(nodes.products).despawn_descendants();
 
     let Some(active) = active.0 else { return };
     let object_type = *objects.get(active).unwrap();
 
     if let Some(factory) = solids.get(*object_type).factory() {
         for &unit in factory.products() {
-            spawn_button(&mut commands, bar_node.0, unit);
+            spawn_button(&mut commands, icons.as_ref(), nodes.products, unit);
         }
     }
 }
 
-fn spawn_button(commands: &mut GuiCommands, parent: Entity, unit: UnitType) {
+fn spawn_button(commands: &mut GuiCommands, icons: &ObjectIcons, parent: Entity, unit: UnitType) {
     let button = commands
-        .spawn_button(
+        .spawn_image_button(
             OuterStyle {
                 width: Val::Percent(10.),
                 height: Val::Percent(80.),
@@ -119,13 +159,58 @@ fn spawn_button(commands: &mut GuiCommands, parent: Entity, unit: UnitType) {
                     Val::Percent(2.),
                 ),
             },
-            unit.to_string().chars().next().unwrap(),
+            unit_icon(icons, unit),
         )
         .insert(ButtonAction(unit))
         .id();
     commands.entity(parent).add_child(button);
 }
 
+fn update_queue(
+    mut commands: Commands,
+    icons: Res<ObjectIcons>,
+    nodes: Res<ActionBarNodes>,
+    active: Res<ActiveEntity>,
+    mut displayed: ResMut<DisplayedQueue>,
+    lines: Query<&AssemblyLine>,
+) {
+    let line = active.0.and_then(|entity| lines.get(entity).ok());
+    let queue = DisplayedQueue {
+        factory: line.and(active.0),
+        units: line
+            .map(|line| line.queue().take(MAX_QUEUE_ICONS).collect())
+            .unwrap_or_default(),
+    };
+    if *displayed == queue {
+        return;
+    }
+
+    commands.entity(nodes.queue).despawn_descendants();
+    for &unit in &queue.units {
+        let icon = commands
+            .spawn(ImageBundle {
+                style: Style {
+                    height: Val::Percent(80.),
+                    aspect_ratio: Some(1.),
+                    margin: UiRect::horizontal(Val::Percent(0.5)),
+                    ..default()
+                },
+                image: UiImage::new(unit_icon(icons.as_ref(), unit)),
+                ..default()
+            })
+            .id();
+        commands.entity(nodes.queue).add_child(icon);
+    }
+
+    *displayed = queue;
+}
+
+fn unit_icon(icons: &ObjectIcons, unit: UnitType) -> Handle<Image> {
+    icons
+        .get(ObjectType::Active(ActiveObjectType::Unit(unit)))
+        .clone()
+}
+
 fn button_system(
     active: Res<ActiveEntity>,
     interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
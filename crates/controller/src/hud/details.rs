@@ -1,7 +1,9 @@
 use bevy::prelude::*;
-use de_core::{cleanup::DespawnOnGameExit, gamestate::GameState};
+use de_conf::Configuration;
+use de_core::{cleanup::DespawnOnGameExit, gamestate::GameState, objects::ObjectTypeComponent};
 use de_energy::Battery;
 use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle};
+use de_objects::{AssetCollection, Harvester, ObjectIcons};
 
 use super::{interaction::InteractionBlocker, HUD_COLOR};
 use crate::selection::Selected;
@@ -13,7 +15,10 @@ pub(crate) struct DetailsPlugin;
 impl Plugin for DetailsPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Playing), setup)
-            .add_systems(PostUpdate, update.run_if(in_state(GameState::Playing)))
+            .add_systems(
+                PostUpdate,
+                (update, update_portrait).run_if(in_state(GameState::Playing)),
+            )
             .add_systems(OnExit(GameState::Playing), clean_up);
     }
 }
@@ -21,7 +26,12 @@ impl Plugin for DetailsPlugin {
 #[derive(Resource)]
 struct DetailsText(Entity);
 
-fn setup(mut commands: GuiCommands) {
+/// Icon of the selected object displayed when a single object is selected.
+#[derive(Resource)]
+struct Portrait(Entity);
+
+fn setup(mut commands: GuiCommands, conf: Res<Configuration>) {
+    let margin = conf.graphics().hud_margin();
     let node = commands
         .spawn((
             NodeBundle {
@@ -29,10 +39,11 @@ fn setup(mut commands: GuiCommands) {
                     width: Val::Percent(20.),
                     height: Val::Percent(30.),
                     position_type: PositionType::Absolute,
-                    left: Val::Percent(0.),
+                    left: Val::Percent(margin),
                     right: Val::Percent(20.),
-                    top: Val::Percent(70.),
+                    top: Val::Percent(70. - margin),
                     bottom: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
                     ..default()
                 },
                 background_color: HUD_COLOR.into(),
@@ -42,6 +53,19 @@ fn setup(mut commands: GuiCommands) {
             InteractionBlocker,
         ))
         .id();
+    let portrait = commands
+        .spawn(ImageBundle {
+            style: Style {
+                display: Display::None,
+                height: Val::Percent(40.),
+                aspect_ratio: Some(1.),
+                margin: UiRect::all(Val::Percent(5.)),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(node).add_child(portrait);
     let details_text = commands
         .spawn_body_text(
             OuterStyle {
@@ -54,6 +78,7 @@ fn setup(mut commands: GuiCommands) {
     commands.entity(node).add_child(details_text);
 
     commands.insert_resource(DetailsText(details_text));
+    commands.insert_resource(Portrait(portrait));
 }
 
 fn format_units(value: f64, units: &str) -> String {
@@ -78,15 +103,44 @@ fn most_significant(value: f64) -> f64 {
     }
 }
 
+fn update_portrait(
+    icons: Res<ObjectIcons>,
+    portrait: Res<Portrait>,
+    selected: Query<&ObjectTypeComponent, With<Selected>>,
+    mut images: Query<(&mut Style, &mut UiImage)>,
+) {
+    let (mut style, mut image) = images.get_mut(portrait.0).unwrap();
+
+    match selected.get_single() {
+        Ok(&object_type) => {
+            let icon = icons.get(*object_type);
+            if image.texture != *icon {
+                image.texture = icon.clone();
+            }
+            if style.display != Display::Flex {
+                style.display = Display::Flex;
+            }
+        }
+        Err(_) => {
+            if style.display != Display::None {
+                style.display = Display::None;
+            }
+        }
+    }
+}
+
 fn update(
     ui: Res<DetailsText>,
     selected: Query<Entity, With<Selected>>,
     battery: Query<&Battery>,
+    harvesters: Query<&Harvester>,
     mut text_ops: BodyTextOps,
 ) {
     let mut battery_total = 0.;
     let mut battery_max = 0.;
     let mut selected_count = 0;
+    let mut cargo_total = 0.;
+    let mut cargo_max = 0.;
 
     for entity in selected.iter() {
         if let Ok(battery) = battery.get(entity) {
@@ -95,6 +149,10 @@ fn update(
             battery_total += battery.energy();
             battery_max += battery.capacity();
         }
+        if let Ok(harvester) = harvesters.get(entity) {
+            cargo_total += harvester.load();
+            cargo_max += harvester.capacity();
+        }
     }
 
     if battery_max == 0. {
@@ -104,13 +162,16 @@ fn update(
         return;
     }
 
-    let text = format!(
+    let mut text = format!(
         "Battery: {} / {} ({:.1}%)\nSelected {}",
         format_units(battery_total, "J"),
         format_units(battery_max, "J"),
         battery_total * 100. / battery_max,
         selected_count,
     );
+    if cargo_max > 0. {
+        text.push_str(&format!("\nCargo: {} / {}", cargo_total.floor(), cargo_max));
+    }
 
     text_ops
         .set_text(ui.0, text)
@@ -119,7 +180,8 @@ fn update(
 
 fn clean_up(mut commands: Commands) {
     // remove DetailsText
-    commands.remove_resource::<DetailsText>()
+    commands.remove_resource::<DetailsText>();
+    commands.remove_resource::<Portrait>();
 }
 
 #[cfg(test)]
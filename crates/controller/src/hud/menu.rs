@@ -1,8 +1,11 @@
 use core::fmt;
 
 use bevy::prelude::*;
-use de_core::{gamestate::GameState, schedule::InputSchedule, state::AppState};
+use de_core::{
+    gamestate::GameState, gconfig::GameConfig, schedule::InputSchedule, state::AppState,
+};
 use de_gui::{ButtonCommands, GuiCommands, OuterStyle};
+use de_spawner::SurrenderEvent;
 
 use super::interaction::InteractionBlocker;
 
@@ -34,18 +37,20 @@ struct PopUpMenu;
 
 #[derive(Component, Clone, Copy)]
 enum ButtonAction {
+    Surrender,
     Quit,
 }
 
 impl fmt::Display for ButtonAction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Surrender => write!(f, "Surrender"),
             Self::Quit => write!(f, "Quit Game"),
         }
     }
 }
 
-fn setup(mut commands: GuiCommands) {
+fn setup(mut commands: GuiCommands, config: Res<GameConfig>) {
     let root_node = commands
         .spawn(NodeBundle {
             style: Style {
@@ -83,6 +88,9 @@ fn setup(mut commands: GuiCommands) {
         .id();
     commands.entity(root_node).add_child(menu_node);
 
+    if !config.editor() {
+        button(&mut commands, menu_node, ButtonAction::Surrender);
+    }
     button(&mut commands, menu_node, ButtonAction::Quit);
 }
 
@@ -129,11 +137,15 @@ fn toggle_system(
 
 fn button_system(
     mut next_state: ResMut<NextState<AppState>>,
+    mut surrender_events: EventWriter<SurrenderEvent>,
     interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
 ) {
     for (&interaction, &action) in interactions.iter() {
         if let Interaction::Pressed = interaction {
             match action {
+                ButtonAction::Surrender => {
+                    surrender_events.send(SurrenderEvent);
+                }
                 ButtonAction::Quit => next_state.set(AppState::InMenu),
             }
         }
@@ -1,4 +1,5 @@
 use bevy::{ecs::system::SystemParam, prelude::*};
+use de_pathing::TerrainOverview;
 
 use super::nodes::MinimapNode;
 
@@ -33,6 +34,21 @@ impl<'a> Drawing<'a> {
         Self { size, data }
     }
 
+    pub(super) fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Returns RGBA data of the whole image.
+    pub(super) fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Overwrite whole of the image with RGBA data of an image of the same
+    /// size.
+    pub(super) fn copy(&mut self, data: &[u8]) {
+        self.data.copy_from_slice(data);
+    }
+
     /// Fill whole of the image with a color.
     pub(super) fn fill(&mut self, color: Color) {
         let bytes = color.as_rgba_u32().to_le_bytes();
@@ -41,6 +57,18 @@ impl<'a> Drawing<'a> {
         }
     }
 
+    /// Fill whole of the image with a terrain overview. The overview is
+    /// scaled with nearest neighbor sampling.
+    pub(super) fn overview(&mut self, overview: &TerrainOverview) {
+        let scale = Vec2::ONE / self.size.as_vec2();
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                let point = (UVec2::new(x, y).as_vec2() + 0.5) * scale;
+                self.set_pixel_bytes(x, y, overview.sample(point));
+            }
+        }
+    }
+
     /// Fill a rectangle with a color.
     pub(super) fn line(&mut self, start: Vec2, end: Vec2, color: Color) {
         panic_bounds("start", start);
@@ -168,6 +196,8 @@ fn panic_bounds(name: &str, point: Vec2) {
 
 #[cfg(test)]
 mod tests {
+    use de_map::size::MapBounds;
+
     use super::*;
 
     #[test]
@@ -190,6 +220,23 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_overview() {
+        let size = UVec2::new(2, 1);
+        let mut data = [0u8; 4 * 2];
+        let mut drawing = Drawing::new(size, data.as_mut_slice());
+        let overview = TerrainOverview::new(MapBounds::new(Vec2::new(20., 10.)), &[]);
+        drawing.overview(&overview);
+
+        assert_eq!(
+            data,
+            [
+                155, 117, 81, 255, // (0, 0)
+                155, 117, 81, 255, // (1, 0)
+            ]
+        )
+    }
+
     #[test]
     fn test_rect() {
         let size = UVec2::splat(5);
@@ -1,10 +1,19 @@
+use std::time::Duration;
+
 use bevy::{ecs::system::SystemParam, prelude::*};
+use de_conf::Palette;
 use de_core::{
-    gamestate::GameState, objects::ObjectTypeComponent, player::PlayerComponent,
+    gamestate::GameState,
+    gconfig::GameConfig,
+    notification::{GameNotification, NotificationKind},
+    objects::{Cloaked, Garrisoned, ObjectTypeComponent},
+    player::PlayerComponent,
     schedule::PostMovement,
+    state::AppState,
 };
 use de_map::size::MapBounds;
 use de_objects::SolidObjects;
+use de_pathing::TerrainOverview;
 use de_terrain::TerrainCollider;
 use de_types::{
     objects::{ActiveObjectType, ObjectType},
@@ -18,35 +27,25 @@ use parry2d::{
 };
 
 use super::draw::DrawingParam;
-use crate::ray::ScreenRay;
+use crate::{hud::notifications::notification_color, ray::ScreenRay};
 
 const TERRAIN_COLOR: Color = Color::rgb(0.61, 0.46, 0.32);
-const PLAYER_COLORS: [Color; Player::MAX_PLAYERS] = [
-    Color::rgb(0.1, 0.1, 0.9),
-    Color::rgb(0.1, 0.9, 0.1),
-    Color::rgb(0.9, 0.1, 0.1),
-    Color::rgb(0.9, 0.9, 0.1),
-];
 const MIN_ENTITY_SIZE: Vec2 = Vec2::splat(0.02);
 const CAMERA_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+/// For how long is a notification highlighted on the minimap.
+const PING_DURATION: Duration = Duration::from_secs(4);
+/// Period of the ping pulsing.
+const PING_PERIOD: f32 = 0.8;
+const PING_MIN_SIZE: f32 = 0.02;
+const PING_MAX_SIZE: f32 = 0.12;
 
-#[derive(Resource, Debug)]
-struct PlayerColors([Color; Player::MAX_PLAYERS]);
-
-impl PlayerColors {
-    fn get_color(&self, player: Player, object_type: ActiveObjectType) -> Color {
-        let player_idx: usize = (player.to_num() - 1) as usize;
-        let player_color = self.0[player_idx].as_hsla();
-        match object_type {
-            ActiveObjectType::Building(_) => player_color,
-            ActiveObjectType::Unit(_) => player_color.with_s(0.7 * player_color.s()),
-        }
-    }
-}
-
-impl Default for PlayerColors {
-    fn default() -> Self {
-        Self(PLAYER_COLORS)
+/// Returns minimap color of an object. Units are drawn with less saturated
+/// player color than buildings.
+fn blip_color(palette: &Palette, player: Player, object_type: ActiveObjectType) -> Color {
+    let player_color = palette.player(player).as_hsla();
+    match object_type {
+        ActiveObjectType::Building(_) => player_color,
+        ActiveObjectType::Unit(_) => player_color.with_s(0.7 * player_color.s()),
     }
 }
 
@@ -54,17 +53,23 @@ pub(super) struct FillPlugin;
 
 impl Plugin for FillPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            PostMovement,
-            (
-                clear_system.in_set(FillSet::Clear),
-                draw_entities_system
-                    .in_set(FillSet::DrawEntities)
-                    .after(FillSet::Clear),
-                draw_camera_system.after(FillSet::DrawEntities),
-            )
-                .run_if(in_state(GameState::Playing)),
-        );
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostMovement,
+                (
+                    clear_system.in_set(FillSet::Clear),
+                    draw_entities_system
+                        .in_set(FillSet::DrawEntities)
+                        .after(FillSet::Clear),
+                    draw_camera_system
+                        .in_set(FillSet::DrawCamera)
+                        .after(FillSet::DrawEntities),
+                    update_pings_system.before(draw_pings_system),
+                    draw_pings_system.after(FillSet::DrawCamera),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
     }
 }
 
@@ -72,6 +77,46 @@ impl Plugin for FillPlugin {
 enum FillSet {
     Clear,
     DrawEntities,
+    DrawCamera,
+}
+
+/// Minimap highlights of recent game notifications.
+#[derive(Resource, Default)]
+struct Pings(Vec<Ping>);
+
+struct Ping {
+    /// Flat position of the notification source.
+    position: Vec2,
+    kind: NotificationKind,
+    /// Time (since game start) when the ping was created.
+    start: Duration,
+}
+
+impl Ping {
+    /// Relative (to the map size) size of the ping square at a given time.
+    fn size(&self, time: Duration) -> f32 {
+        let phase = ((time - self.start).as_secs_f32() / PING_PERIOD).fract();
+        PING_MIN_SIZE + phase * (PING_MAX_SIZE - PING_MIN_SIZE)
+    }
+}
+
+/// Terrain overview scaled to the minimap resolution. Scaling of the overview
+/// is expensive, thus it is done only after the overview or the resolution
+/// changes.
+#[derive(Resource, Default)]
+struct Background {
+    size: UVec2,
+    data: Vec<u8>,
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<Pings>();
+    commands.init_resource::<Background>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Pings>();
+    commands.remove_resource::<Background>();
 }
 
 #[derive(SystemParam)]
@@ -93,24 +138,54 @@ impl<'w> UiCoords<'w> {
     }
 }
 
-fn clear_system(mut drawing: DrawingParam) {
+fn clear_system(
+    mut drawing: DrawingParam,
+    overview: Option<Res<TerrainOverview>>,
+    mut background: ResMut<Background>,
+) {
     let mut drawing = drawing.drawing();
-    drawing.fill(TERRAIN_COLOR);
+    // The overview is computed asynchronously shortly after the game starts.
+    let Some(overview) = overview else {
+        drawing.fill(TERRAIN_COLOR);
+        return;
+    };
+
+    if overview.is_changed() || background.size != drawing.size() {
+        drawing.overview(overview.as_ref());
+        background.size = drawing.size();
+        background.data = drawing.data().to_vec();
+    } else {
+        drawing.copy(&background.data);
+    }
 }
 
 fn draw_entities_system(
     mut drawing: DrawingParam,
     ui_coords: UiCoords,
     solids: SolidObjects,
-    colors: Local<PlayerColors>,
-    entities: Query<(&Transform, &PlayerComponent, &ObjectTypeComponent)>,
+    palette: Res<Palette>,
+    config: Res<GameConfig>,
+    entities: Query<
+        (
+            &Transform,
+            &PlayerComponent,
+            &ObjectTypeComponent,
+            Option<&Cloaked>,
+        ),
+        Without<Garrisoned>,
+    >,
 ) {
     let mut drawing = drawing.drawing();
+    let playable = config.locals().playable();
+
+    for (transform, &player, &object_type, cloaked) in entities.iter() {
+        if cloaked.is_some_and(|cloaked| !cloaked.visible_to(playable)) {
+            continue;
+        }
 
-    for (transform, &player, &object_type) in entities.iter() {
         let minimap_position = ui_coords.flat_to_rel(transform.translation.to_flat());
         if let ObjectType::Active(active_object) = *object_type {
-            let color = colors.get_color(*player, active_object);
+            let color = blip_color(&palette, *player, active_object);
             let radius = solids.get(*object_type).ichnography().radius();
             let rect_size = MIN_ENTITY_SIZE.max(ui_coords.size_to_rel(Vec2::splat(radius)));
             drawing.rect(minimap_position, rect_size, color);
@@ -118,6 +193,50 @@ fn draw_entities_system(
     }
 }
 
+fn update_pings_system(
+    time: Res<Time>,
+    mut pings: ResMut<Pings>,
+    mut notifications: EventReader<GameNotification>,
+) {
+    let time = time.elapsed();
+    pings.0.retain(|ping| time < ping.start + PING_DURATION);
+
+    for notification in notifications.read() {
+        if let Some(position) = notification.position() {
+            pings.0.push(Ping {
+                position,
+                kind: notification.kind(),
+                start: time,
+            });
+        }
+    }
+}
+
+fn draw_pings_system(
+    mut drawing: DrawingParam,
+    ui_coords: UiCoords,
+    time: Res<Time>,
+    pings: Res<Pings>,
+) {
+    if pings.0.is_empty() {
+        return;
+    }
+
+    let mut drawing = drawing.drawing();
+    for ping in pings.0.iter() {
+        let center = ui_coords.flat_to_rel(ping.position);
+        let half_size = Vec2::splat(0.5 * ping.size(time.elapsed()));
+        let min = (center - half_size).clamp(Vec2::ZERO, Vec2::ONE);
+        let max = (center + half_size).clamp(Vec2::ZERO, Vec2::ONE);
+
+        let color = notification_color(ping.kind);
+        drawing.line(min, Vec2::new(max.x, min.y), color);
+        drawing.line(Vec2::new(max.x, min.y), max, color);
+        drawing.line(max, Vec2::new(min.x, max.y), color);
+        drawing.line(Vec2::new(min.x, max.y), min, color);
+    }
+}
+
 #[derive(SystemParam)]
 struct CameraPoint<'w, 's> {
     ray: ScreenRay<'w, 's>,
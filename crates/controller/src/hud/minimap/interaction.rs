@@ -43,7 +43,8 @@ impl Plugin for InteractionPlugin {
                         .before(CommandsSet::SendSelected),
                     delivery_location_system
                         .after(InteractionSet::PressHandler)
-                        .before(CommandsSet::DeliveryLocation),
+                        .before(CommandsSet::DeliveryLocation)
+                        .before(CommandsSet::StandingOrders),
                 )
                     .run_if(in_state(GameState::Playing)),
             );
@@ -5,6 +5,7 @@ use bevy::{
         render_resource::{Extent3d, TextureDimension, TextureFormat},
     },
 };
+use de_conf::Configuration;
 use de_core::{cleanup::DespawnOnGameExit, gamestate::GameState, schedule::PreMovement};
 use de_map::size::MapBounds;
 
@@ -28,7 +29,13 @@ impl Plugin for NodesPlugin {
 #[derive(Component)]
 pub(super) struct MinimapNode;
 
-fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>, map_bounds: Res<MapBounds>) {
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    map_bounds: Res<MapBounds>,
+    conf: Res<Configuration>,
+) {
+    let margin = conf.graphics().hud_margin();
     let handle = images.add(new_image(UVec2::splat(128)));
     let map_size = map_bounds.size();
     let aspect = map_size.x / map_size.y;
@@ -40,8 +47,8 @@ fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>, map_bounds:
                     position_type: PositionType::Absolute,
                     padding: UiRect::all(MINIMAP_PADDING),
                     width: MINIMAP_WIDTH,
-                    right: Val::Px(0.),
-                    bottom: Val::Px(0.),
+                    right: Val::Percent(margin),
+                    bottom: Val::Percent(margin),
                     ..default()
                 },
                 background_color: HUD_COLOR.into(),
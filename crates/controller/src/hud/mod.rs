@@ -5,18 +5,23 @@ mod details;
 mod interaction;
 mod menu;
 mod minimap;
+mod notifications;
+mod objectives;
+mod observer;
 mod selection;
+mod stock;
 
-pub(crate) use interaction::HudNodes;
+pub(crate) use interaction::{HudNodes, InteractionBlocker};
 pub(crate) use menu::{GameMenuSet, ToggleGameMenuEvent};
 pub(crate) use selection::UpdateSelectionBoxEvent;
 
 use self::{
     actionbar::ActionBarPlugin, details::DetailsPlugin, menu::MenuPlugin, minimap::MinimapPlugin,
-    selection::SelectionPlugin,
+    notifications::NotificationsPlugin, objectives::ObjectivesPlugin, observer::ObserverPlugin,
+    selection::SelectionPlugin, stock::StockPlugin,
 };
 
-const HUD_COLOR: Color = Color::BLACK;
+pub(crate) const HUD_COLOR: Color = Color::BLACK;
 
 pub(crate) struct HudPlugin;
 
@@ -28,6 +33,10 @@ impl Plugin for HudPlugin {
             ActionBarPlugin,
             MenuPlugin,
             MinimapPlugin,
+            StockPlugin,
+            ObjectivesPlugin,
+            ObserverPlugin,
+            NotificationsPlugin,
         ));
     }
 }
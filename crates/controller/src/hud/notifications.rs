@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_core::{
+    cleanup::DespawnOnGameExit,
+    gamestate::GameState,
+    notification::{GameNotification, NotificationKind, NotificationSet},
+};
+use de_gui::ToastEvent;
+use de_types::projection::ToAltitude;
+
+/// For how long is an off-screen indicator displayed.
+const INDICATOR_DURATION: Duration = Duration::from_secs(5);
+/// Size of an off-screen indicator in logical pixels.
+const INDICATOR_SIZE: f32 = 16.;
+/// Off-screen indicators are kept this far (in NDC units) from the screen
+/// edges.
+const INDICATOR_MARGIN: f32 = 0.03;
+
+pub(crate) struct NotificationsPlugin;
+
+impl Plugin for NotificationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                toast.run_if(on_event::<GameNotification>()),
+                spawn_indicators.run_if(on_event::<GameNotification>()),
+                update_indicators.after(spawn_indicators),
+            )
+                .after(NotificationSet::Throttle)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// A UI element pointing to the place of a notification which is outside of
+/// the screen.
+#[derive(Component)]
+struct Indicator {
+    /// Flat position of the notification source.
+    position: Vec2,
+    /// Time (since game start) when the indicator is removed.
+    expiration: Duration,
+}
+
+fn toast(mut notifications: EventReader<GameNotification>, mut toasts: EventWriter<ToastEvent>) {
+    for notification in notifications.read() {
+        toasts.send(ToastEvent::new(notification.message()));
+    }
+}
+
+fn spawn_indicators(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut notifications: EventReader<GameNotification>,
+) {
+    for notification in notifications.read() {
+        let Some(position) = notification.position() else {
+            continue;
+        };
+
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    // Displayed once the position is known to be off-screen.
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(INDICATOR_SIZE),
+                    height: Val::Px(INDICATOR_SIZE),
+                    margin: UiRect::new(
+                        Val::Px(-0.5 * INDICATOR_SIZE),
+                        Val::Px(0.),
+                        Val::Px(-0.5 * INDICATOR_SIZE),
+                        Val::Px(0.),
+                    ),
+                    ..default()
+                },
+                background_color: notification_color(notification.kind()).into(),
+                ..default()
+            },
+            Indicator {
+                position,
+                expiration: time.elapsed() + INDICATOR_DURATION,
+            },
+            DespawnOnGameExit,
+        ));
+    }
+}
+
+fn update_indicators(
+    mut commands: Commands,
+    time: Res<Time>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut indicators: Query<(Entity, &Indicator, &mut Style)>,
+) {
+    let (camera, camera_transform) = camera.single();
+
+    for (entity, indicator, mut style) in indicators.iter_mut() {
+        if indicator.expiration <= time.elapsed() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let point = indicator.position.to_msl();
+        let Some(ndc) = camera.world_to_ndc(camera_transform, point) else {
+            style.display = Display::None;
+            continue;
+        };
+
+        // Camera looks in the direction of its local -Z axis.
+        let behind = camera_transform
+            .affine()
+            .inverse()
+            .transform_point3(point)
+            .z
+            > 0.;
+        match edge_position(ndc.truncate(), behind) {
+            Some(position) => {
+                style.display = Display::Flex;
+                style.left = Val::Percent(50. * (position.x + 1.));
+                style.top = Val::Percent(50. * (1. - position.y));
+            }
+            None => {
+                style.display = Display::None;
+            }
+        }
+    }
+}
+
+/// Returns NDC position of an off-screen indicator or None if the indicated
+/// point is on the screen.
+///
+/// # Arguments
+///
+/// * `ndc` - NDC coordinates of the indicated point.
+///
+/// * `behind` - whether the point is behind the camera. Projection of such
+///   points is mirrored.
+fn edge_position(ndc: Vec2, behind: bool) -> Option<Vec2> {
+    let ndc = if behind { -ndc } else { ndc };
+    let max = ndc.abs().max_element();
+    if !behind && max <= 1. {
+        return None;
+    }
+    if max == 0. {
+        return Some(Vec2::new(0., -1. + INDICATOR_MARGIN));
+    }
+
+    // Points behind the camera are always pushed to the screen edge.
+    Some(ndc * (1. - INDICATOR_MARGIN) / max)
+}
+
+/// Color of UI elements highlighting notifications of a kind.
+pub(super) fn notification_color(kind: NotificationKind) -> Color {
+    match kind {
+        NotificationKind::UnderAttack | NotificationKind::UnitLost => Color::rgb(1., 0.2, 0.1),
+        NotificationKind::LowPower | NotificationKind::PowerDepleted => Color::rgb(1., 0.8, 0.1),
+        NotificationKind::ProductionComplete
+        | NotificationKind::PlayerEliminated
+        | NotificationKind::FactoryIdle
+        | NotificationKind::NoRallyPoint => Color::WHITE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_position() {
+        assert!(edge_position(Vec2::new(0.5, -0.9), false).is_none());
+
+        let position = edge_position(Vec2::new(4., 1.), false).unwrap();
+        assert_eq!(position, Vec2::new(1., 0.25) * (1. - INDICATOR_MARGIN));
+
+        let position = edge_position(Vec2::new(0.5, 0.1), true).unwrap();
+        assert_eq!(position, Vec2::new(-1., -0.2) * (1. - INDICATOR_MARGIN));
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use de_conf::Configuration;
+use de_core::{cleanup::DespawnOnGameExit, gamestate::GameState};
+use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle};
+use de_loader::Objectives;
+use de_map::script::ObjectiveStatus;
+
+use super::{interaction::InteractionBlocker, HUD_COLOR};
+
+pub(crate) struct ObjectivesPlugin;
+
+impl Plugin for ObjectivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup)
+            .add_systems(
+                PostUpdate,
+                update
+                    .run_if(resource_exists_and_changed::<Objectives>)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup);
+    }
+}
+
+#[derive(Resource)]
+struct ObjectivesPanel {
+    node: Entity,
+    text: Entity,
+}
+
+fn setup(mut commands: GuiCommands, conf: Res<Configuration>) {
+    let margin = conf.graphics().hud_margin();
+    let node = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    width: Val::Percent(20.),
+                    position_type: PositionType::Absolute,
+                    right: Val::Percent(margin),
+                    top: Val::Percent(5. + margin),
+                    ..default()
+                },
+                background_color: HUD_COLOR.into(),
+                ..default()
+            },
+            DespawnOnGameExit,
+            InteractionBlocker,
+        ))
+        .id();
+    let text = commands
+        .spawn_body_text(
+            OuterStyle {
+                margin: UiRect::all(Val::Percent(5.)),
+                ..default()
+            },
+            "",
+        )
+        .id();
+    commands.entity(node).add_child(text);
+
+    commands.insert_resource(ObjectivesPanel { node, text });
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<ObjectivesPanel>();
+}
+
+fn update(
+    panel: Res<ObjectivesPanel>,
+    objectives: Res<Objectives>,
+    mut styles: Query<&mut Style>,
+    mut text_ops: BodyTextOps,
+) {
+    // The panel is hidden in scenarios without objectives.
+    styles.get_mut(panel.node).unwrap().display = if objectives.is_empty() {
+        Display::None
+    } else {
+        Display::Flex
+    };
+
+    let lines: Vec<String> = objectives
+        .iter()
+        .map(|objective| {
+            let status = match objective.status() {
+                ObjectiveStatus::Active => "[ ]",
+                ObjectiveStatus::Completed => "[x]",
+                ObjectiveStatus::Failed => "[-]",
+            };
+            format!("{status} {}", objective.description())
+        })
+        .collect();
+    text_ops
+        .set_text(panel.text, lines.join("\n"))
+        .expect("Failed to set text of objectives");
+}
@@ -0,0 +1,325 @@
+use std::{collections::VecDeque, time::Duration};
+
+use ahash::AHashMap;
+use bevy::{ecs::system::SystemParam, input::common_conditions::input_just_pressed, prelude::*};
+use de_construction::AssemblyLine;
+use de_core::{
+    cleanup::DespawnOnGameExit,
+    gamestate::GameState,
+    gconfig::GameConfig,
+    objects::{Active, ObjectTypeComponent},
+    player::PlayerComponent,
+};
+use de_economy::Stocks;
+use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle};
+use de_objects::Health;
+use de_spawner::ObjectCounter;
+use de_types::{
+    objects::{ActiveObjectType, ObjectType, UnitType},
+    player::{Player, PlayerRange},
+};
+use enum_map::EnumMap;
+
+use super::interaction::InteractionBlocker;
+
+/// Key toggling the observer overlay.
+const TOGGLE_KEY: KeyCode = KeyCode::F10;
+/// Interval between consecutive updates of the overlay.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+/// Income (resources gathered, regardless of spending) is averaged over this
+/// time window.
+const INCOME_WINDOW: Duration = Duration::from_secs(60);
+
+pub(crate) struct ObserverPlugin;
+
+impl Plugin for ObserverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup)
+            .add_systems(OnExit(GameState::Playing), cleanup)
+            .add_systems(
+                Update,
+                toggle
+                    .run_if(input_just_pressed(TOGGLE_KEY))
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                PostUpdate,
+                (sample_income, update.run_if(panel_visible))
+                    .chain()
+                    .run_if(resource_exists::<Stocks>)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct ObserverPanel {
+    node: Entity,
+    visible: bool,
+    /// Column node and its text for each player.
+    columns: [(Entity, Entity); Player::MAX_PLAYERS],
+}
+
+#[derive(Resource)]
+struct ObserverTimer(Timer);
+
+/// Amounts of resources gathered by each player sampled over the last
+/// [`INCOME_WINDOW`].
+#[derive(Resource, Default)]
+struct IncomeTracker(AHashMap<Player, VecDeque<(Duration, f32)>>);
+
+impl IncomeTracker {
+    /// Records total amount of resources gathered by a player until a given
+    /// time.
+    fn record(&mut self, player: Player, time: Duration, amount: f32) {
+        let samples = self.0.entry(player).or_default();
+        samples.push_back((time, amount));
+        while samples.front().map_or(false, |&(oldest, _)| {
+            time.saturating_sub(oldest) > INCOME_WINDOW
+        }) {
+            samples.pop_front();
+        }
+    }
+
+    /// Returns average income of a player per minute over the tracked window
+    /// or None if not enough samples were recorded.
+    fn per_minute(&self, player: Player) -> Option<f32> {
+        let samples = self.0.get(&player)?;
+        let &(start_time, start_amount) = samples.front()?;
+        let &(end_time, end_amount) = samples.back()?;
+
+        let elapsed = (end_time - start_time).as_secs_f32();
+        if elapsed <= 0. {
+            return None;
+        }
+        Some(60. * (end_amount - start_amount) / elapsed)
+    }
+}
+
+fn setup(mut commands: GuiCommands) {
+    let node = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    flex_direction: FlexDirection::Row,
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(20.),
+                    right: Val::Percent(20.),
+                    top: Val::Percent(5.),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.7).into(),
+                ..default()
+            },
+            DespawnOnGameExit,
+            InteractionBlocker,
+        ))
+        .id();
+
+    let columns = [0; Player::MAX_PLAYERS].map(|_| {
+        let column = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100. / Player::MAX_PLAYERS as f32),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        commands.entity(node).add_child(column);
+
+        let text = commands
+            .spawn_body_text(
+                OuterStyle {
+                    margin: UiRect::all(Val::Percent(5.)),
+                    ..default()
+                },
+                "",
+            )
+            .id();
+        commands.entity(column).add_child(text);
+
+        (column, text)
+    });
+
+    commands.insert_resource(ObserverPanel {
+        node,
+        visible: false,
+        columns,
+    });
+    commands.insert_resource(ObserverTimer(Timer::new(
+        UPDATE_INTERVAL,
+        TimerMode::Repeating,
+    )));
+    commands.init_resource::<IncomeTracker>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<ObserverPanel>();
+    commands.remove_resource::<ObserverTimer>();
+    commands.remove_resource::<IncomeTracker>();
+}
+
+fn panel_visible(panel: Res<ObserverPanel>) -> bool {
+    panel.visible
+}
+
+fn toggle(
+    mut panel: ResMut<ObserverPanel>,
+    mut timer: ResMut<ObserverTimer>,
+    mut styles: Query<&mut Style>,
+) {
+    panel.visible = !panel.visible;
+    styles.get_mut(panel.node).unwrap().display = if panel.visible {
+        // Fill the overlay on the next tick of the timer.
+        timer.0.set_elapsed(UPDATE_INTERVAL);
+        Display::Flex
+    } else {
+        Display::None
+    };
+}
+
+fn sample_income(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    stocks: Res<Stocks>,
+    mut timer: ResMut<ObserverTimer>,
+    mut tracker: ResMut<IncomeTracker>,
+) {
+    timer.0.tick(time.delta());
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    // Only stocks of players simulated by this game instance are known.
+    for &player in config.locals().locals() {
+        tracker.record(player, time.elapsed(), stocks.gathered(player));
+    }
+}
+
+/// Sources of the statistics displayed in the overlay.
+#[derive(SystemParam)]
+struct Statistics<'w, 's> {
+    counter: Res<'w, ObjectCounter>,
+    tracker: Res<'w, IncomeTracker>,
+    units: Query<
+        'w,
+        's,
+        (
+            &'static PlayerComponent,
+            &'static ObjectTypeComponent,
+            &'static Health,
+        ),
+        With<Active>,
+    >,
+    lines: Query<'w, 's, (&'static PlayerComponent, &'static AssemblyLine)>,
+}
+
+fn update(
+    config: Res<GameConfig>,
+    panel: Res<ObserverPanel>,
+    timer: Res<ObserverTimer>,
+    stats: Statistics,
+    mut styles: Query<&mut Style>,
+    mut text_ops: BodyTextOps,
+) {
+    if !timer.0.just_finished() {
+        return;
+    }
+
+    let mut army: AHashMap<Player, (u32, f32)> = AHashMap::new();
+    for (&player, &object_type, health) in stats.units.iter() {
+        if let ObjectType::Active(ActiveObjectType::Unit(_)) = *object_type {
+            let entry = army.entry(*player).or_default();
+            entry.0 += 1;
+            entry.1 += health.fraction();
+        }
+    }
+
+    let mut production: AHashMap<Player, EnumMap<UnitType, u32>> = AHashMap::new();
+    for (&player, line) in stats.lines.iter() {
+        let queued = production.entry(*player).or_default();
+        for unit in line.queue() {
+            queued[unit] += 1;
+        }
+    }
+
+    let playable = config.locals().playable();
+    for (player, &(column, text)) in PlayerRange::up_to(Player::Player4).zip(panel.columns.iter()) {
+        // Only allies (with shared vision) are observed during a regular
+        // game so that the overlay does not reveal enemy armies and
+        // economy. There are no spectators yet, god mode is the closest.
+        let observed = cfg!(feature = "godmode") || config.shares_vision(playable, player);
+        let Some(objects) = stats.counter.player(player).filter(|_| observed) else {
+            styles.get_mut(column).unwrap().display = Display::None;
+            continue;
+        };
+        styles.get_mut(column).unwrap().display = Display::Flex;
+
+        let (unit_count, strength) = army.get(&player).copied().unwrap_or_default();
+        let army_line = if unit_count > 0 {
+            format!(
+                "Army: {unit_count} units ({:.0}% health)",
+                100. * strength / unit_count as f32
+            )
+        } else {
+            "Army: none".to_owned()
+        };
+
+        let production_line = match production.get(&player) {
+            Some(queued) if queued.values().any(|&count| count > 0) => {
+                let items: Vec<String> = queued
+                    .iter()
+                    .filter(|&(_, &count)| count > 0)
+                    .map(|(unit, count)| format!("{count}x {unit}"))
+                    .collect();
+                format!("Production: {}", items.join(", "))
+            }
+            // Assembly lines of remote players are not known.
+            _ if !config.locals().is_local(player) => "Production: unknown".to_owned(),
+            _ => "Production: idle".to_owned(),
+        };
+
+        let income_line = match stats.tracker.per_minute(player) {
+            Some(income) => format!("Income: {income:.0} ore/min"),
+            None => "Income: unknown".to_owned(),
+        };
+
+        text_ops
+            .set_text(
+                text,
+                format!(
+                    "Player {}\n{army_line}\nBuildings: {}\n{production_line}\n{income_line}",
+                    player.to_num(),
+                    objects.building_count(),
+                ),
+            )
+            .expect("Failed to set text of observer overlay");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_income_tracker() {
+        let mut tracker = IncomeTracker::default();
+        assert!(tracker.per_minute(Player::Player1).is_none());
+
+        tracker.record(Player::Player1, Duration::from_secs(1), 10.);
+        assert!(tracker.per_minute(Player::Player1).is_none());
+
+        tracker.record(Player::Player1, Duration::from_secs(31), 40.);
+        assert_eq!(tracker.per_minute(Player::Player1).unwrap(), 60.);
+
+        // The first sample falls out of the window.
+        tracker.record(Player::Player1, Duration::from_secs(91), 100.);
+        assert_eq!(tracker.per_minute(Player::Player1).unwrap(), 60.);
+        tracker.record(Player::Player1, Duration::from_secs(151), 190.);
+        assert_eq!(tracker.per_minute(Player::Player1).unwrap(), 90.);
+
+        assert!(tracker.per_minute(Player::Player2).is_none());
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use de_conf::Configuration;
+use de_core::{cleanup::DespawnOnGameExit, gamestate::GameState, gconfig::GameConfig};
+use de_economy::Stocks;
+use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle};
+
+use super::{interaction::InteractionBlocker, HUD_COLOR};
+
+pub(crate) struct StockPlugin;
+
+impl Plugin for StockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup)
+            .add_systems(
+                PostUpdate,
+                update
+                    .run_if(resource_exists_and_changed::<Stocks>)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup);
+    }
+}
+
+#[derive(Resource)]
+struct StockText(Entity);
+
+fn setup(mut commands: GuiCommands, conf: Res<Configuration>) {
+    let margin = conf.graphics().hud_margin();
+    let node = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(12.),
+                    height: Val::Percent(4.),
+                    position_type: PositionType::Absolute,
+                    right: Val::Percent(margin),
+                    top: Val::Percent(margin),
+                    ..default()
+                },
+                background_color: HUD_COLOR.into(),
+                ..default()
+            },
+            DespawnOnGameExit,
+            InteractionBlocker,
+        ))
+        .id();
+    let text = commands
+        .spawn_body_text(
+            OuterStyle {
+                margin: UiRect::all(Val::Percent(5.)),
+                ..default()
+            },
+            "",
+        )
+        .id();
+    commands.entity(node).add_child(text);
+
+    commands.insert_resource(StockText(text));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<StockText>();
+}
+
+fn update(
+    ui: Res<StockText>,
+    config: Res<GameConfig>,
+    stocks: Res<Stocks>,
+    mut text_ops: BodyTextOps,
+) {
+    let amount = stocks.amount(config.locals().playable());
+    text_ops
+        .set_text(ui.0, format!("Ore: {}", amount.floor()))
+        .expect("Failed to set text of resource stock");
+}
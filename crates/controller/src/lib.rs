@@ -3,12 +3,14 @@
 use bevy::{app::PluginGroupBuilder, prelude::*};
 use commands::CommandsPlugin;
 use draft::DraftPlugin;
+pub use editor::EditorPluginGroup;
 use hud::HudPlugin;
 use mouse::MousePlugin;
 use selection::SelectionPlugin;
 
 mod commands;
 mod draft;
+mod editor;
 mod frustum;
 mod hud;
 mod mouse;
@@ -1,5 +1,8 @@
 use bevy::prelude::*;
-use de_core::{gamestate::GameState, schedule::InputSchedule, state::AppState};
+use de_core::{
+    gamestate::GameState, gconfig::GameConfig, objects::Cloaked, schedule::InputSchedule,
+    state::AppState,
+};
 use de_index::SpatialQuery;
 use de_signs::UpdateBarVisibilityEvent;
 use de_terrain::TerrainCollider;
@@ -76,13 +79,21 @@ fn pointer_update_system(
     screen_ray: ScreenRay,
     entities: SpatialQuery<()>,
     terrain: TerrainCollider,
+    config: Res<GameConfig>,
+    cloaked: Query<&Cloaked>,
 ) {
     let ray = mouse.ndc().map(|cursor| screen_ray.ray(cursor));
 
     let entity = ray
         .as_ref()
         .and_then(|ray| entities.cast_ray(ray, f32::INFINITY, None))
-        .map(|intersection| intersection.entity());
+        .map(|intersection| intersection.entity())
+        // Undetected cloaked entities cannot be pointed at.
+        .filter(|&entity| {
+            cloaked.get(entity).map_or(true, |cloaked| {
+                cloaked.visible_to(config.locals().playable())
+            })
+        });
 
     // Do not unnecessarily trigger change detection.
     if resource.entity() != entity {
@@ -6,6 +6,7 @@ use de_core::{
     schedule::InputSchedule,
     screengeom::ScreenRect,
 };
+use de_index::SpatialQuery;
 use de_objects::SolidObjects;
 use de_types::objects::ObjectType;
 
@@ -69,14 +70,14 @@ impl SelectInRectEvent {
 fn select_in_area(
     screen_frustum: ScreenFrustum,
     solids: SolidObjects,
-    candidates: Query<(Entity, &ObjectTypeComponent, &Transform), With<Playable>>,
+    candidates: SpatialQuery<(Entity, &ObjectTypeComponent, &Transform), With<Playable>>,
     mut in_events: EventReader<SelectInRectEvent>,
     mut out_events: EventWriter<SelectEvent>,
 ) {
     for in_event in in_events.read() {
         let event_frustum = screen_frustum.rect(in_event.rect());
         let entities: Vec<Entity> = candidates
-            .iter()
+            .query_frustum(&event_frustum, None)
             .filter(|(_, &object_type, _)| {
                 in_event
                     .filter_object_type()
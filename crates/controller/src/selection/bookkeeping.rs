@@ -1,7 +1,15 @@
 use ahash::AHashSet;
 use bevy::{ecs::system::SystemParam, prelude::*};
-use de_core::{gamestate::GameState, schedule::InputSchedule};
-use de_signs::{UpdateBarVisibilityEvent, UpdateLineVisibilityEvent, UpdatePoleVisibilityEvent};
+use de_audio::{PlayVoiceEvent, VoiceLine};
+use de_core::{
+    gamestate::GameState,
+    objects::{Garrisoned, MovableSolid, Playable},
+    schedule::InputSchedule,
+};
+use de_signs::{
+    UpdateBarVisibilityEvent, UpdateLineVisibilityEvent, UpdatePoleVisibilityEvent,
+    UpdateRingVisibilityEvent,
+};
 use de_terrain::MarkerVisibility;
 
 use crate::SELECTION_BAR_ID;
@@ -16,6 +24,7 @@ impl Plugin for BookkeepingPlugin {
             .add_systems(
                 InputSchedule,
                 (
+                    deselect_unplayable.before(SelectionSet::Update),
                     update_selection.in_set(SelectionSet::Update),
                     (selected_system, deselected_system).after(SelectionSet::Update),
                 )
@@ -147,6 +156,25 @@ impl<'w, 's> Selector<'w, 's> {
     }
 }
 
+type SelectedPlayable = (With<Selected>, With<Playable>, Without<Garrisoned>);
+type SelectedUnplayable = (With<Selected>, Or<(Without<Playable>, With<Garrisoned>)>);
+
+/// Deselects all selected entities which are no longer playable, for example
+/// due to a transfer of their ownership to another player, or which got
+/// garrisoned.
+fn deselect_unplayable(
+    playable: Query<Entity, SelectedPlayable>,
+    unplayable: Query<(), SelectedUnplayable>,
+    mut events: EventWriter<SelectEvent>,
+) {
+    if !unplayable.is_empty() {
+        events.send(SelectEvent::many(
+            playable.iter().collect(),
+            SelectionMode::Replace,
+        ));
+    }
+}
+
 fn update_selection(mut events: EventReader<SelectEvent>, selector_builder: SelectorBuilder) {
     let mut selector = selector_builder.build();
     for event in events.read() {
@@ -155,14 +183,21 @@ fn update_selection(mut events: EventReader<SelectEvent>, selector_builder: Sele
     selector.execute();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn selected_system(
     mut events: EventReader<SelectedEvent>,
+    units: Query<(), With<MovableSolid>>,
     mut markers: Query<&mut MarkerVisibility>,
     mut bars: EventWriter<UpdateBarVisibilityEvent>,
     mut poles: EventWriter<UpdatePoleVisibilityEvent>,
     mut lines: EventWriter<UpdateLineVisibilityEvent>,
+    mut rings: EventWriter<UpdateRingVisibilityEvent>,
+    mut voice: EventWriter<PlayVoiceEvent>,
 ) {
+    let mut units_selected = false;
     for event in events.read() {
+        units_selected |= units.contains(event.0);
+
         if let Ok(mut visibility) = markers.get_mut(event.0) {
             visibility.0.update_visible(SELECTION_BAR_ID, true);
         }
@@ -175,6 +210,11 @@ fn selected_system(
 
         poles.send(UpdatePoleVisibilityEvent::new(event.0, true));
         lines.send(UpdateLineVisibilityEvent::new(event.0, true));
+        rings.send(UpdateRingVisibilityEvent::new(event.0, true));
+    }
+
+    if units_selected {
+        voice.send(PlayVoiceEvent::new(VoiceLine::Selected));
     }
 }
 
@@ -184,6 +224,7 @@ fn deselected_system(
     mut bars: EventWriter<UpdateBarVisibilityEvent>,
     mut poles: EventWriter<UpdatePoleVisibilityEvent>,
     mut lines: EventWriter<UpdateLineVisibilityEvent>,
+    mut rings: EventWriter<UpdateRingVisibilityEvent>,
 ) {
     for event in events.read() {
         if let Ok(mut visibility) = markers.get_mut(event.0) {
@@ -198,5 +239,6 @@ fn deselected_system(
 
         poles.send(UpdatePoleVisibilityEvent::new(event.0, false));
         lines.send(UpdateLineVisibilityEvent::new(event.0, false));
+        rings.send(UpdateRingVisibilityEvent::new(event.0, false));
     }
 }
@@ -0,0 +1,233 @@
+//! Lifecycle audit of game entities: entities spawned and despawned during
+//! the game are counted per category and all entities spawned during the game
+//! which survive the game cleanup are reported as leaks.
+
+use std::collections::BTreeMap;
+
+use bevy::{
+    ecs::component::Components,
+    prelude::*,
+    utils::{get_short_name, HashMap, HashSet},
+};
+
+use super::{DespawnOnGameExit, SurvivesGameExit};
+use crate::{objects::ObjectTypeComponent, state::AppState};
+
+/// Maximum number of component names used to describe a category of an
+/// entity without an object type or a name.
+const MAX_CATEGORY_COMPONENTS: usize = 6;
+
+pub(super) struct AuditPlugin;
+
+impl Plugin for AuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), start_audit)
+            .add_systems(OnExit(AppState::InGame), finish_audit)
+            .add_systems(
+                Update,
+                track
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_exists::<EntityAudit>),
+            )
+            .add_systems(Last, check_leaks.run_if(resource_exists::<LeakCheck>));
+    }
+}
+
+/// Lifecycle statistics of entities marked with [`DespawnOnGameExit`] during
+/// a single game.
+#[derive(Resource)]
+struct EntityAudit {
+    /// Entities which existed before the game was entered.
+    baseline: HashSet<Entity>,
+    /// Category of each currently existing tracked entity.
+    tracked: HashMap<Entity, String>,
+    categories: BTreeMap<String, CategoryStats>,
+}
+
+impl EntityAudit {
+    fn new(baseline: HashSet<Entity>) -> Self {
+        Self {
+            baseline,
+            tracked: HashMap::new(),
+            categories: BTreeMap::new(),
+        }
+    }
+
+    fn spawned(&mut self, entity: Entity, category: String) {
+        self.categories.entry(category.clone()).or_default().spawned += 1;
+        self.tracked.insert(entity, category);
+    }
+
+    fn despawned(&mut self, entity: Entity) {
+        if let Some(category) = self.tracked.remove(&entity) {
+            self.categories.get_mut(&category).unwrap().despawned += 1;
+        }
+    }
+}
+
+#[derive(Default)]
+struct CategoryStats {
+    spawned: u32,
+    despawned: u32,
+}
+
+/// Entities spawned during the last game. Those still existing once the game
+/// cleanup is finished are reported as leaks.
+#[derive(Resource)]
+struct LeakCheck(Vec<Entity>);
+
+fn start_audit(mut commands: Commands, entities: Query<Entity>) {
+    commands.insert_resource(EntityAudit::new(entities.iter().collect()));
+}
+
+fn track(
+    mut audit: ResMut<EntityAudit>,
+    components: &Components,
+    added: Query<EntityRef, Added<DespawnOnGameExit>>,
+    mut removed: RemovedComponents<DespawnOnGameExit>,
+) {
+    for entity in added.iter() {
+        audit.spawned(entity.id(), category(entity, components));
+    }
+    for entity in removed.read() {
+        audit.despawned(entity);
+    }
+}
+
+fn finish_audit(mut commands: Commands, audit: Option<Res<EntityAudit>>, entities: Query<Entity>) {
+    let Some(audit) = audit else {
+        return;
+    };
+
+    for (category, stats) in audit.categories.iter() {
+        debug!(
+            "Game entities of category \"{category}\": {} spawned, {} despawned during the game.",
+            stats.spawned, stats.despawned
+        );
+    }
+
+    let spawned = entities
+        .iter()
+        .filter(|entity| !audit.baseline.contains(entity))
+        .collect();
+    commands.insert_resource(LeakCheck(spawned));
+    commands.remove_resource::<EntityAudit>();
+}
+
+fn check_leaks(
+    mut commands: Commands,
+    check: Res<LeakCheck>,
+    components: &Components,
+    entities: Query<EntityRef>,
+) {
+    commands.remove_resource::<LeakCheck>();
+
+    let mut leaks: BTreeMap<String, u32> = BTreeMap::new();
+    for &entity in check.0.iter() {
+        let Ok(entity) = entities.get(entity) else {
+            continue;
+        };
+        if survives(entity, &entities) {
+            continue;
+        }
+        *leaks.entry(category(entity, components)).or_default() += 1;
+    }
+
+    for (category, count) in leaks {
+        warn!("{count} entities of category \"{category}\" survived the game exit.");
+    }
+}
+
+/// Returns true if the entity or any of its ancestors is marked with
+/// [`SurvivesGameExit`].
+fn survives<'w>(mut entity: EntityRef<'w>, entities: &'w Query<EntityRef>) -> bool {
+    loop {
+        if entity.contains::<SurvivesGameExit>() {
+            return true;
+        }
+        let Some(parent) = entity.get::<Parent>() else {
+            return false;
+        };
+        let Ok(parent) = entities.get(parent.get()) else {
+            return false;
+        };
+        entity = parent;
+    }
+}
+
+/// Returns a human readable category of an entity. The category is based on
+/// object type or name of the entity, or on names of its components as a
+/// fallback.
+fn category(entity: EntityRef, components: &Components) -> String {
+    if let Some(object_type) = entity.get::<ObjectTypeComponent>() {
+        return object_type.to_string();
+    }
+    if let Some(name) = entity.get::<Name>() {
+        return name.as_str().to_owned();
+    }
+
+    let mut names: Vec<String> = entity
+        .archetype()
+        .components()
+        .filter_map(|id| components.get_info(id))
+        .map(|info| get_short_name(info.name()))
+        .collect();
+    names.sort_unstable();
+    if names.len() > MAX_CATEGORY_COMPONENTS {
+        names.truncate(MAX_CATEGORY_COMPONENTS);
+        names.push("...".to_owned());
+    }
+    names.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use de_types::objects::{ActiveObjectType, ObjectType, UnitType};
+
+    use super::*;
+
+    #[test]
+    fn test_category() {
+        let mut world = World::new();
+        let unit = world
+            .spawn((
+                ObjectTypeComponent::from(ObjectType::Active(ActiveObjectType::Unit(
+                    UnitType::Attacker,
+                ))),
+                Name::new("Ignored"),
+            ))
+            .id();
+        let named = world.spawn((Name::new("Camera"), DespawnOnGameExit)).id();
+        let other = world.spawn((DespawnOnGameExit, SurvivesGameExit)).id();
+
+        let components = world.components();
+        assert_eq!(
+            category(world.entity(unit), components),
+            ObjectType::Active(ActiveObjectType::Unit(UnitType::Attacker)).to_string()
+        );
+        assert_eq!(category(world.entity(named), components), "Camera");
+        assert_eq!(
+            category(world.entity(other), components),
+            "DespawnOnGameExit, SurvivesGameExit"
+        );
+    }
+
+    #[test]
+    fn test_audit() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+
+        let mut audit = EntityAudit::new(HashSet::new());
+        audit.spawned(a, "A".to_owned());
+        audit.spawned(b, "A".to_owned());
+        audit.despawned(a);
+        audit.despawned(a);
+        audit.despawned(Entity::PLACEHOLDER);
+
+        let stats = audit.categories.get("A").unwrap();
+        assert_eq!(stats.spawned, 2);
+        assert_eq!(stats.despawned, 1);
+        assert!(audit.tracked.contains_key(&b));
+    }
+}
@@ -1,12 +1,20 @@
 use bevy::prelude::*;
 
+#[cfg(debug_assertions)]
+use self::audit::AuditPlugin;
 use crate::state::AppState;
 
+#[cfg(debug_assertions)]
+mod audit;
+
 pub(crate) struct CleanupPlugin;
 
 impl Plugin for CleanupPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnExit(AppState::InGame), cleanup);
+
+        #[cfg(debug_assertions)]
+        app.add_plugins(AuditPlugin);
     }
 }
 
@@ -15,6 +23,12 @@ impl Plugin for CleanupPlugin {
 #[derive(Component)]
 pub struct DespawnOnGameExit;
 
+/// Mark entities (and thus their descendants) which are spawned during the
+/// game but intentionally outlive it with this component. Such entities are
+/// not reported as leaks.
+#[derive(Component)]
+pub struct SurvivesGameExit;
+
 fn cleanup(mut commands: Commands, query: Query<Entity, With<DespawnOnGameExit>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
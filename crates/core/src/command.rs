@@ -0,0 +1,244 @@
+//! Text commands (e.g. `/surrender`) shared by all text frontends, namely the
+//! chat and the developer console.
+//!
+//! Subsystems register commands they handle with [`register_command`] during
+//! app building and react to [`CommandEvent`]. Frontends send
+//! [`RunCommandEvent`] with the text typed by the user.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+/// Prefix distinguishing commands from ordinary chat messages.
+pub const COMMAND_PREFIX: char = '/';
+
+pub(crate) struct CommandPlugin;
+
+impl Plugin for CommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CommandRegistry>()
+            .add_event::<RunCommandEvent>()
+            .add_event::<CommandEvent>()
+            .add_systems(
+                PreUpdate,
+                run.run_if(on_event::<RunCommandEvent>())
+                    .in_set(CommandSet::Run),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum CommandSet {
+    Run,
+}
+
+/// Send this event to execute a command typed by the user. Invalid commands
+/// are logged as warnings.
+#[derive(Event)]
+pub struct RunCommandEvent(String);
+
+impl RunCommandEvent {
+    /// # Arguments
+    ///
+    /// * `line` - the command, e.g. `/mute Alice`. The command prefix is
+    ///   optional.
+    pub fn new(line: impl Into<String>) -> Self {
+        Self(line.into())
+    }
+}
+
+/// This event is sent when a registered command is executed.
+#[derive(Event, Debug, PartialEq)]
+pub struct CommandEvent {
+    name: String,
+    args: Vec<String>,
+}
+
+impl CommandEvent {
+    /// Name of the command without the command prefix.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Arguments of the command. Their number matches the registered
+    /// command.
+    pub fn args(&self) -> &[String] {
+        self.args.as_slice()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CommandInfo {
+    name: &'static str,
+    args: &'static [&'static str],
+    description: &'static str,
+}
+
+impl CommandInfo {
+    /// # Arguments
+    ///
+    /// * `name` - name of the command without the command prefix.
+    ///
+    /// * `args` - names of all (mandatory) arguments of the command.
+    ///
+    /// * `description` - short human readable description of the command.
+    pub const fn new(
+        name: &'static str,
+        args: &'static [&'static str],
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            args,
+            description,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn description(&self) -> &str {
+        self.description
+    }
+
+    /// Returns usage of the command, e.g. `/mute <player>`.
+    pub fn usage(&self) -> String {
+        let mut usage = format!("{COMMAND_PREFIX}{}", self.name);
+        for arg in self.args {
+            usage.push_str(&format!(" <{arg}>"));
+        }
+        usage
+    }
+}
+
+/// All commands known to the application.
+#[derive(Resource, Default)]
+pub struct CommandRegistry(BTreeMap<&'static str, CommandInfo>);
+
+impl CommandRegistry {
+    /// Registers a new command.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a command of the same name is already registered.
+    pub fn register(&mut self, info: CommandInfo) {
+        let name = info.name;
+        assert!(
+            self.0.insert(name, info).is_none(),
+            "Command `{name}` is already registered."
+        );
+    }
+
+    /// Returns an iterator over all registered commands ordered by their
+    /// name.
+    pub fn iter(&self) -> impl Iterator<Item = &CommandInfo> {
+        self.0.values()
+    }
+
+    /// Parses a line of text into a registered command. The command prefix
+    /// is optional. Arguments are separated by whitespace.
+    pub fn parse(&self, line: &str) -> Result<CommandEvent, CommandError> {
+        let line = line.trim();
+        let line = line.strip_prefix(COMMAND_PREFIX).unwrap_or(line);
+
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return Err(CommandError::Empty);
+        };
+        let Some(info) = self.0.get(name) else {
+            return Err(CommandError::Unknown(name.to_owned()));
+        };
+
+        let args: Vec<String> = words.map(|word| word.to_owned()).collect();
+        if args.len() != info.args.len() {
+            return Err(CommandError::Arguments(info.usage()));
+        }
+
+        Ok(CommandEvent {
+            name: name.to_owned(),
+            args,
+        })
+    }
+}
+
+/// Registers a command to the app. This function may be called before the
+/// plugin of this module is added.
+///
+/// # Panics
+///
+/// Panics if a command of the same name is already registered.
+pub fn register_command(app: &mut App, info: CommandInfo) {
+    app.init_resource::<CommandRegistry>()
+        .add_event::<CommandEvent>();
+    app.world.resource_mut::<CommandRegistry>().register(info);
+}
+
+/// Returns true if a text (e.g. a chat message) is a command rather than
+/// ordinary text.
+pub fn is_command(text: &str) -> bool {
+    text.trim_start().starts_with(COMMAND_PREFIX)
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CommandError {
+    #[error("empty command")]
+    Empty,
+    #[error("unknown command `{0}`")]
+    Unknown(String),
+    #[error("invalid number of arguments, usage: {0}")]
+    Arguments(String),
+}
+
+fn run(
+    registry: Res<CommandRegistry>,
+    mut in_events: EventReader<RunCommandEvent>,
+    mut out_events: EventWriter<CommandEvent>,
+) {
+    for event in in_events.read() {
+        match registry.parse(event.0.as_str()) {
+            Ok(command) => {
+                info!("Running command {:?}", event.0);
+                out_events.send(command);
+            }
+            Err(err) => warn!("Invalid command {:?}: {err}", event.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mut registry = CommandRegistry::default();
+        registry.register(CommandInfo::new("surrender", &[], "Surrender the game."));
+        registry.register(CommandInfo::new("mute", &["player"], "Mute a player."));
+
+        let names: Vec<&str> = registry.iter().map(|info| info.name()).collect();
+        assert_eq!(names, vec!["mute", "surrender"]);
+
+        let command = registry.parse("/surrender").unwrap();
+        assert_eq!(command.name(), "surrender");
+        assert!(command.args().is_empty());
+
+        let command = registry.parse("  mute   Alice ").unwrap();
+        assert_eq!(command.name(), "mute");
+        assert_eq!(command.args(), &["Alice".to_owned()]);
+
+        assert_eq!(registry.parse(" / "), Err(CommandError::Empty));
+        assert_eq!(
+            registry.parse("/fly"),
+            Err(CommandError::Unknown("fly".to_owned()))
+        );
+        assert_eq!(
+            registry.parse("/mute"),
+            Err(CommandError::Arguments("/mute <player>".to_owned()))
+        );
+
+        assert!(is_command(" /ping"));
+        assert!(!is_command("gg"));
+    }
+}
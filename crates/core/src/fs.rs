@@ -8,6 +8,28 @@ pub fn conf_dir() -> Result<AsyncPathBuf, DirError> {
     dir(dirs::config_dir)
 }
 
+/// Returns directory with user maps. The maps are listed together with the
+/// maps bundled with the game.
+pub fn maps_dir() -> Result<AsyncPathBuf, DirError> {
+    dir(dirs::data_dir).map(|d| d.join("maps"))
+}
+
+/// Returns directory with user asset bundles (e.g. mods). All bundles in the
+/// directory are mounted during application start.
+pub fn bundles_dir() -> Result<AsyncPathBuf, DirError> {
+    dir(dirs::data_dir).map(|d| d.join("bundles"))
+}
+
+/// Returns directory with persistent progress of campaigns played by the user.
+pub fn campaigns_dir() -> Result<AsyncPathBuf, DirError> {
+    dir(dirs::data_dir).map(|d| d.join("campaigns"))
+}
+
+/// Returns directory with the local player profile, e.g. lifetime statistics.
+pub fn profile_dir() -> Result<AsyncPathBuf, DirError> {
+    dir(dirs::data_dir).map(|d| d.join("profile"))
+}
+
 /// Returns DE logging directory.
 pub fn logs_dir() -> Result<AsyncPathBuf, DirError> {
     dir(dirs::cache_dir).map(|d| d.join("logs"))
@@ -1,7 +1,10 @@
 use std::path::{Path, PathBuf};
 
 use bevy::prelude::*;
-use de_types::player::{Player, PlayerRange};
+use de_types::{
+    objects::UnitType,
+    player::{Player, PlayerRange},
+};
 use tinyvec::{array_vec, ArrayVec};
 
 /// This resource is automatically removed when
@@ -10,7 +13,13 @@ use tinyvec::{array_vec, ArrayVec};
 pub struct GameConfig {
     map_path: PathBuf,
     multiplayer: bool,
+    editor: bool,
     locals: LocalPlayers,
+    teams: Teams,
+    regeneration: bool,
+    /// Starting conditions of each player, the first item belongs to
+    /// [`Player::Player1`].
+    starting: [StartingConditions; Player::MAX_PLAYERS],
 }
 
 impl GameConfig {
@@ -18,7 +27,45 @@ impl GameConfig {
         Self {
             map_path: map_path.into(),
             multiplayer,
+            editor: false,
             locals,
+            teams: Teams::default(),
+            regeneration: true,
+            starting: Default::default(),
+        }
+    }
+
+    /// Sets alliances between players. By default, there are no alliances.
+    pub fn with_teams(mut self, teams: Teams) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    /// Enables or disables passive health regeneration and degradation of
+    /// objects. It is enabled by default.
+    pub fn with_regeneration(mut self, regeneration: bool) -> Self {
+        self.regeneration = regeneration;
+        self
+    }
+
+    /// Sets starting conditions of a player. By default, all players start
+    /// with no extra resources or units and without any handicap.
+    pub fn with_starting(mut self, player: Player, starting: StartingConditions) -> Self {
+        self.starting[(player.to_num() - 1) as usize] = starting;
+        self
+    }
+
+    /// Creates configuration of a map editing session. All players are
+    /// simulated locally and no game rules (e.g. game end detection) apply.
+    pub fn new_editor<P: Into<PathBuf>>(map_path: P) -> Self {
+        Self {
+            map_path: map_path.into(),
+            multiplayer: false,
+            editor: true,
+            locals: LocalPlayers::from_max_player(Player::Player1, Player::Player4),
+            teams: Teams::default(),
+            regeneration: false,
+            starting: Default::default(),
         }
     }
 
@@ -30,9 +77,183 @@ impl GameConfig {
         self.multiplayer
     }
 
+    /// Returns true if the map is being edited rather than played.
+    pub fn editor(&self) -> bool {
+        self.editor
+    }
+
     pub fn locals(&self) -> &LocalPlayers {
         &self.locals
     }
+
+    pub fn teams(&self) -> &Teams {
+        &self.teams
+    }
+
+    /// Returns true if health of objects passively regenerates or degrades.
+    pub fn regeneration(&self) -> bool {
+        self.regeneration
+    }
+
+    pub fn starting(&self, player: Player) -> &StartingConditions {
+        &self.starting[(player.to_num() - 1) as usize]
+    }
+
+    /// Returns true if objects of `owner` reveal their surroundings (in the
+    /// fog of war) to `observer`.
+    pub fn shares_vision(&self, observer: Player, owner: Player) -> bool {
+        observer == owner || (self.teams.shared_vision() && self.teams.allied(observer, owner))
+    }
+
+    /// Returns true if `controller` is allowed to command objects of `owner`.
+    pub fn controls(&self, controller: Player, owner: Player) -> bool {
+        controller == owner || (self.teams.shared_control() && self.teams.allied(controller, owner))
+    }
+}
+
+/// Alliances between players and options of allied play.
+pub struct Teams {
+    /// Team of each player, the first item belongs to [`Player::Player1`].
+    teams: [u8; Player::MAX_PLAYERS],
+    shared_vision: bool,
+    shared_control: bool,
+}
+
+impl Teams {
+    /// # Arguments
+    ///
+    /// * `teams` - team of each player, the first item belongs to
+    ///   [`Player::Player1`]. Players with equal team number are allied.
+    ///
+    /// * `shared_vision` - whether allied players see what their allies see.
+    ///
+    /// * `shared_control` - whether allied players can command objects of
+    ///   their allies.
+    pub fn new(
+        teams: [u8; Player::MAX_PLAYERS],
+        shared_vision: bool,
+        shared_control: bool,
+    ) -> Self {
+        Self {
+            teams,
+            shared_vision,
+            shared_control,
+        }
+    }
+
+    /// Returns true if both players are in the same team. Each player is
+    /// allied with itself.
+    pub fn allied(&self, a: Player, b: Player) -> bool {
+        self.team(a) == self.team(b)
+    }
+
+    pub fn shared_vision(&self) -> bool {
+        self.shared_vision
+    }
+
+    pub fn shared_control(&self) -> bool {
+        self.shared_control
+    }
+
+    fn team(&self, player: Player) -> u8 {
+        self.teams[(player.to_num() - 1) as usize]
+    }
+}
+
+impl Default for Teams {
+    /// Each player is in its own team, id est there are no alliances.
+    fn default() -> Self {
+        let mut teams = [0; Player::MAX_PLAYERS];
+        for (index, team) in teams.iter_mut().enumerate() {
+            *team = index as u8;
+        }
+        Self::new(teams, false, false)
+    }
+}
+
+/// Conditions of a player at the start of a game. They make asymmetric
+/// setups possible, e.g. giving a weaker player a head start.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StartingConditions {
+    resources: f32,
+    units: Vec<UnitType>,
+    handicap: Handicap,
+}
+
+impl StartingConditions {
+    /// # Arguments
+    ///
+    /// * `resources` - amount of resources in the player's stock at the
+    ///   start of the game.
+    ///
+    /// * `units` - units spawned around the player's base at the start of
+    ///   the game, on top of objects placed on the map.
+    ///
+    /// * `handicap` - multipliers applied to the player during the game.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resources` is not a non-negative finite number.
+    pub fn new(resources: f32, units: Vec<UnitType>, handicap: Handicap) -> Self {
+        assert!(resources.is_finite() && resources >= 0.);
+        Self {
+            resources,
+            units,
+            handicap,
+        }
+    }
+
+    pub fn resources(&self) -> f32 {
+        self.resources
+    }
+
+    pub fn units(&self) -> &[UnitType] {
+        self.units.as_slice()
+    }
+
+    pub fn handicap(&self) -> Handicap {
+        self.handicap
+    }
+}
+
+/// Multipliers applied to a player during a game. Multipliers smaller than
+/// 1 weaken the player, multipliers larger than 1 strengthen the player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Handicap {
+    damage: f32,
+    income: f32,
+}
+
+impl Handicap {
+    /// # Arguments
+    ///
+    /// * `damage` - multiplier of damage dealt by objects of the player.
+    ///
+    /// * `income` - multiplier of resources gathered by the player.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the multipliers is not a positive finite number.
+    pub fn new(damage: f32, income: f32) -> Self {
+        assert!(damage.is_finite() && damage > 0.);
+        assert!(income.is_finite() && income > 0.);
+        Self { damage, income }
+    }
+
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+
+    pub fn income(&self) -> f32 {
+        self.income
+    }
+}
+
+impl Default for Handicap {
+    /// No handicap, id est all multipliers are equal to 1.
+    fn default() -> Self {
+        Self::new(1., 1.)
+    }
 }
 
 /// Info about players directly controlled or simulated on this computer.
@@ -100,6 +321,14 @@ pub fn is_multiplayer(config: Option<Res<GameConfig>>) -> bool {
     }
 }
 
+/// System condition which returns true during a map editing session.
+pub fn is_editor(config: Option<Res<GameConfig>>) -> bool {
+    match config {
+        Some(config) => config.editor(),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,5 +341,64 @@ mod tests {
             LocalPlayers::from_max_player(Player::Player1, Player::Player4),
         );
         assert_eq!(config.map_path().to_string_lossy(), "/some/path");
+        assert!(!config.editor());
+
+        let config = GameConfig::new_editor("/some/path");
+        assert!(config.editor());
+        assert!(!config.multiplayer());
+        assert!(config.locals().is_local(Player::Player4));
+    }
+
+    #[test]
+    fn test_teams() {
+        let config = GameConfig::new(
+            "/some/path",
+            true,
+            LocalPlayers::from_single(Player::Player1),
+        );
+        assert!(config.controls(Player::Player1, Player::Player1));
+        assert!(!config.controls(Player::Player1, Player::Player2));
+        assert!(!config.shares_vision(Player::Player1, Player::Player2));
+
+        let config = config.with_teams(Teams::new([0, 0, 1, 1], true, false));
+        assert!(config.teams().allied(Player::Player1, Player::Player2));
+        assert!(!config.teams().allied(Player::Player1, Player::Player3));
+        assert!(config.shares_vision(Player::Player1, Player::Player2));
+        assert!(!config.shares_vision(Player::Player1, Player::Player3));
+        assert!(!config.controls(Player::Player1, Player::Player2));
+
+        let config = config.with_teams(Teams::new([0, 1, 0, 1], false, true));
+        assert!(!config.shares_vision(Player::Player1, Player::Player3));
+        assert!(config.controls(Player::Player1, Player::Player3));
+        assert!(!config.controls(Player::Player1, Player::Player2));
+    }
+
+    #[test]
+    fn test_starting() {
+        let config = GameConfig::new(
+            "/some/path",
+            false,
+            LocalPlayers::from_single(Player::Player1),
+        );
+        assert_eq!(
+            config.starting(Player::Player2),
+            &StartingConditions::default()
+        );
+        assert_eq!(config.starting(Player::Player2).handicap().damage(), 1.);
+
+        let starting = StartingConditions::new(
+            200.,
+            vec![UnitType::Attacker, UnitType::Harvester],
+            Handicap::new(1.2, 1.5),
+        );
+        let config = config.with_starting(Player::Player2, starting.clone());
+        assert_eq!(config.starting(Player::Player2), &starting);
+        assert_eq!(config.starting(Player::Player2).resources(), 200.);
+        assert_eq!(config.starting(Player::Player2).units().len(), 2);
+        assert_eq!(config.starting(Player::Player2).handicap().income(), 1.5);
+        assert_eq!(
+            config.starting(Player::Player1),
+            &StartingConditions::default()
+        );
     }
 }
@@ -0,0 +1,89 @@
+//! Statistics of the local player gathered during a single game.
+//!
+//! A fresh [`GameStats`] resource is inserted whenever a game starts. It is
+//! intentionally kept after the game ends so that it can be processed
+//! together with [`crate::gresult::GameResult`], e.g. by the menu.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_types::objects::UnitType;
+use enum_map::EnumMap;
+
+use crate::{gamestate::GameState, state::AppState};
+
+pub(crate) struct GameStatsPlugin;
+
+impl Plugin for GameStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(Update, tick.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Resource, Clone, Default)]
+pub struct GameStats {
+    actions: u32,
+    units: EnumMap<UnitType, u32>,
+    duration: Duration,
+}
+
+impl GameStats {
+    /// Number of actions (e.g. mouse clicks and key presses) done by the
+    /// local player.
+    pub fn actions(&self) -> u32 {
+        self.actions
+    }
+
+    /// Returns the number of units of a type manufactured by the local
+    /// player.
+    pub fn manufactured(&self, unit: UnitType) -> u32 {
+        self.units[unit]
+    }
+
+    /// Game time spent in the playing state.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn record_actions(&mut self, count: u32) {
+        self.actions += count;
+    }
+
+    pub fn record_unit(&mut self, unit: UnitType) {
+        self.units[unit] += 1;
+    }
+
+    /// Prolongs the game duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_delta` - time delta since last call to this method.
+    pub fn tick(&mut self, time_delta: Duration) {
+        self.duration += time_delta;
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(GameStats::default());
+}
+
+fn tick(time: Res<Time>, mut stats: ResMut<GameStats>) {
+    stats.tick(time.delta());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut stats = GameStats::default();
+        stats.record_actions(2);
+        stats.record_actions(1);
+        stats.record_unit(UnitType::Scout);
+        assert_eq!(stats.actions(), 3);
+        assert_eq!(stats.manufactured(UnitType::Scout), 1);
+        assert_eq!(stats.manufactured(UnitType::Attacker), 0);
+    }
+}
@@ -1,13 +1,17 @@
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 use cleanup::CleanupPlugin;
+use command::CommandPlugin;
 use gamestate::GameStateSetupPlugin;
+use gstats::GameStatsPlugin;
 use iyes_progress::prelude::*;
+use notification::NotificationPlugin;
 use schedule::GameSchedulesPlugin;
 use state::AppState;
 use visibility::VisibilityPlugin;
 
 pub mod assets;
 pub mod cleanup;
+pub mod command;
 mod errors;
 pub mod events;
 pub mod flags;
@@ -16,6 +20,8 @@ pub mod fs;
 pub mod gamestate;
 pub mod gconfig;
 pub mod gresult;
+pub mod gstats;
+pub mod notification;
 pub mod objects;
 pub mod player;
 pub mod schedule;
@@ -35,5 +41,8 @@ impl PluginGroup for CorePluginGroup {
             .add(GameStateSetupPlugin)
             .add(VisibilityPlugin)
             .add(CleanupPlugin)
+            .add(NotificationPlugin)
+            .add(CommandPlugin)
+            .add(GameStatsPlugin)
     }
 }
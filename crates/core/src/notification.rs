@@ -0,0 +1,298 @@
+//! Notifications of the local player about important game events, e.g. an
+//! attacked object or an eliminated player.
+//!
+//! Game systems send [`NotifyEvent`]. Notifications are throttled per kind so
+//! that, for example, a burst of lost units is reported only once. Accepted
+//! notifications are re-sent as [`GameNotification`] from systems in
+//! [`NotificationSet::Throttle`]. Notifications of kinds disabled in
+//! [`EnabledNotifications`] are dropped. All alerts (announcer, toasts, minimap,
+//! on-screen indicators) are driven by these events.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use de_types::player::Player;
+use enum_map::{Enum, EnumMap};
+
+use crate::{gamestate::GameState, state::AppState};
+
+pub(crate) struct NotificationPlugin;
+
+impl Plugin for NotificationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NotifyEvent>()
+            .add_event::<GameNotification>()
+            .init_resource::<EnabledNotifications>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostUpdate,
+                throttle
+                    .run_if(on_event::<NotifyEvent>())
+                    .run_if(in_state(GameState::Playing))
+                    .in_set(NotificationSet::Throttle),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum NotificationSet {
+    Throttle,
+}
+
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// An object of the player is being damaged.
+    UnderAttack,
+    /// A unit of the player has been destroyed.
+    UnitLost,
+    /// A factory of the player has finished manufacturing of a unit.
+    ProductionComplete,
+    /// A player has been eliminated from the game.
+    PlayerEliminated,
+    /// An object of the player is low on energy.
+    LowPower,
+    /// An object of the player has run out of energy.
+    PowerDepleted,
+    /// A factory of the player has not been manufacturing anything for a
+    /// while.
+    FactoryIdle,
+    /// A factory of the player without a rally point has finished
+    /// manufacturing of a unit.
+    NoRallyPoint,
+}
+
+impl NotificationKind {
+    /// Minimum time between two consecutive notifications of the kind.
+    fn cooldown(self) -> Duration {
+        match self {
+            Self::UnderAttack => Duration::from_secs(20),
+            Self::UnitLost => Duration::from_secs(5),
+            Self::ProductionComplete => Duration::from_secs(3),
+            Self::PlayerEliminated => Duration::ZERO,
+            Self::LowPower => Duration::from_secs(30),
+            Self::PowerDepleted => Duration::from_secs(30),
+            Self::FactoryIdle => Duration::from_secs(60),
+            Self::NoRallyPoint => Duration::from_secs(30),
+        }
+    }
+}
+
+/// A notification of the local player.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GameNotification {
+    kind: NotificationKind,
+    position: Option<Vec2>,
+    player: Option<Player>,
+}
+
+impl GameNotification {
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the attacked object.
+    pub fn under_attack(position: Vec2) -> Self {
+        Self::new(NotificationKind::UnderAttack, Some(position), None)
+    }
+
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the destroyed unit.
+    pub fn unit_lost(position: Vec2) -> Self {
+        Self::new(NotificationKind::UnitLost, Some(position), None)
+    }
+
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the factory.
+    pub fn production_complete(position: Vec2) -> Self {
+        Self::new(NotificationKind::ProductionComplete, Some(position), None)
+    }
+
+    pub fn player_eliminated(player: Player) -> Self {
+        Self::new(NotificationKind::PlayerEliminated, None, Some(player))
+    }
+
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the object low on energy.
+    pub fn low_power(position: Vec2) -> Self {
+        Self::new(NotificationKind::LowPower, Some(position), None)
+    }
+
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the object out of energy.
+    pub fn power_depleted(position: Vec2) -> Self {
+        Self::new(NotificationKind::PowerDepleted, Some(position), None)
+    }
+
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the idle factory.
+    pub fn factory_idle(position: Vec2) -> Self {
+        Self::new(NotificationKind::FactoryIdle, Some(position), None)
+    }
+
+    /// # Arguments
+    ///
+    /// * `position` - flat position of the factory.
+    pub fn no_rally_point(position: Vec2) -> Self {
+        Self::new(NotificationKind::NoRallyPoint, Some(position), None)
+    }
+
+    fn new(kind: NotificationKind, position: Option<Vec2>, player: Option<Player>) -> Self {
+        Self {
+            kind,
+            position,
+            player,
+        }
+    }
+
+    pub fn kind(&self) -> NotificationKind {
+        self.kind
+    }
+
+    /// Flat position of the notification source or None if the notification
+    /// is not bound to a place on the map.
+    pub fn position(&self) -> Option<Vec2> {
+        self.position
+    }
+
+    /// Human readable text of the notification.
+    pub fn message(&self) -> String {
+        match self.kind {
+            NotificationKind::UnderAttack => "We are under attack!".to_owned(),
+            NotificationKind::UnitLost => "Unit lost.".to_owned(),
+            NotificationKind::ProductionComplete => "Production complete.".to_owned(),
+            NotificationKind::PlayerEliminated => match self.player {
+                Some(player) => format!("The {player} has been eliminated."),
+                None => "A player has been eliminated.".to_owned(),
+            },
+            NotificationKind::LowPower => "Low power.".to_owned(),
+            NotificationKind::PowerDepleted => "Out of power.".to_owned(),
+            NotificationKind::FactoryIdle => "Factory idle.".to_owned(),
+            NotificationKind::NoRallyPoint => "Unit ready, no rally point set.".to_owned(),
+        }
+    }
+}
+
+/// Send this event to notify the local player about a game event. The
+/// notification is dropped if another notification of the same kind was
+/// accepted recently.
+#[derive(Event)]
+pub struct NotifyEvent(GameNotification);
+
+impl NotifyEvent {
+    pub fn new(notification: GameNotification) -> Self {
+        Self(notification)
+    }
+}
+
+/// Kinds of notifications the local player wants to receive. All kinds are
+/// enabled by default.
+#[derive(Resource)]
+pub struct EnabledNotifications(EnumMap<NotificationKind, bool>);
+
+impl EnabledNotifications {
+    /// # Arguments
+    ///
+    /// * `enabled` - returns whether a notification kind is enabled.
+    pub fn new(enabled: impl FnMut(NotificationKind) -> bool) -> Self {
+        Self(EnumMap::from_fn(enabled))
+    }
+
+    pub fn is_enabled(&self, kind: NotificationKind) -> bool {
+        self.0[kind]
+    }
+}
+
+impl Default for EnabledNotifications {
+    fn default() -> Self {
+        Self::new(|_| true)
+    }
+}
+
+/// Time (since game start) of the last accepted notification of each kind.
+#[derive(Resource, Default)]
+struct Cooldowns(EnumMap<NotificationKind, Option<Duration>>);
+
+impl Cooldowns {
+    /// Returns true and restarts the kind cooldown if a notification of the
+    /// kind may be accepted now.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of the notification.
+    ///
+    /// * `time` - elapsed time since a fixed point in time in the past.
+    fn try_restart(&mut self, kind: NotificationKind, time: Duration) -> bool {
+        let last = &mut self.0[kind];
+        if last.is_some_and(|last| time < last + kind.cooldown()) {
+            return false;
+        }
+
+        *last = Some(time);
+        true
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<Cooldowns>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Cooldowns>();
+}
+
+fn throttle(
+    time: Res<Time>,
+    enabled: Res<EnabledNotifications>,
+    mut cooldowns: ResMut<Cooldowns>,
+    mut in_events: EventReader<NotifyEvent>,
+    mut out_events: EventWriter<GameNotification>,
+) {
+    for event in in_events.read() {
+        let kind = event.0.kind();
+        if enabled.is_enabled(kind) && cooldowns.try_restart(kind, time.elapsed()) {
+            out_events.send(event.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldowns() {
+        let mut cooldowns = Cooldowns::default();
+
+        let time = Duration::from_secs(10);
+        assert!(cooldowns.try_restart(NotificationKind::UnitLost, time));
+        assert!(!cooldowns.try_restart(NotificationKind::UnitLost, time));
+        assert!(cooldowns.try_restart(NotificationKind::LowPower, time));
+        assert!(cooldowns.try_restart(NotificationKind::PlayerEliminated, time));
+        assert!(cooldowns.try_restart(NotificationKind::PlayerEliminated, time));
+
+        let time = Duration::from_secs(15);
+        assert!(cooldowns.try_restart(NotificationKind::UnitLost, time));
+        assert!(!cooldowns.try_restart(NotificationKind::LowPower, time));
+    }
+
+    #[test]
+    fn test_enabled() {
+        assert!(EnabledNotifications::default().is_enabled(NotificationKind::FactoryIdle));
+
+        let enabled = EnabledNotifications::new(|kind| kind != NotificationKind::FactoryIdle);
+        assert!(!enabled.is_enabled(NotificationKind::FactoryIdle));
+        assert!(enabled.is_enabled(NotificationKind::NoRallyPoint));
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            GameNotification::player_eliminated(Player::Player2).message(),
+            "The player 2 has been eliminated."
+        );
+    }
+}
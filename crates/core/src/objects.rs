@@ -1,7 +1,9 @@
 use std::fmt;
 
 use bevy::prelude::*;
-use de_types::objects::ObjectType;
+use de_types::{objects::ObjectType, player::Player};
+
+use crate::flags::Flags;
 
 /// Active object which can be played by any player (including AI).
 #[derive(Component)]
@@ -24,6 +26,56 @@ pub struct StaticSolid;
 #[derive(Component)]
 pub struct MovableSolid;
 
+/// A movable object which temporarily cannot move on its own, for example a
+/// deployed artillery. Its path is kept and followed once the component is
+/// removed.
+#[derive(Component)]
+pub struct Anchored;
+
+/// An active object which is temporarily disabled, for example after being
+/// hit by an EMP. Disabled objects can neither fire, move nor use energy.
+#[derive(Component)]
+pub struct Disabled;
+
+/// An active object which is cloaked. Cloaked objects are visible only to
+/// players who share vision with the owner or who detect the object.
+#[derive(Component, Default)]
+pub struct Cloaked {
+    /// Bit of each player to which the object is visible is set.
+    visible: Flags,
+}
+
+impl Cloaked {
+    /// Returns true if the object is visible to (and thus can be targeted
+    /// by) the player.
+    pub fn visible_to(&self, player: Player) -> bool {
+        self.visible.get(player.to_num().into())
+    }
+
+    pub fn set_visible_to(&mut self, player: Player, value: bool) {
+        self.visible.set(player.to_num().into(), value);
+    }
+}
+
+/// A unit garrisoned in another object, for example in a transport or a
+/// bunker. Garrisoned units move together with the carrier, are hidden and
+/// cannot be hit.
+#[derive(Component, Clone, Copy)]
+pub struct Garrisoned {
+    carrier: Entity,
+}
+
+impl Garrisoned {
+    pub fn new(carrier: Entity) -> Self {
+        Self { carrier }
+    }
+
+    /// The object in which the unit is garrisoned.
+    pub fn carrier(&self) -> Entity {
+        self.carrier
+    }
+}
+
 #[derive(Component, Deref, Clone, Copy)]
 pub struct ObjectTypeComponent(ObjectType);
 
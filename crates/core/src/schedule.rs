@@ -1,10 +1,26 @@
 //! This module extends default Bevy schedules.
+//!
+//! It also implements a shared per-frame time budget of heavy computations,
+//! see [`ComputeBudget`].
+
+use std::time::Duration;
 
 use bevy::{
     app::MainScheduleOrder,
     ecs::schedule::{ScheduleBuildSettings, ScheduleLabel},
     prelude::*,
 };
+use enum_map::{Enum, EnumMap};
+
+/// Total time per frame shared by all [`ComputeKind`]s.
+const FRAME_BUDGET: Duration = Duration::from_millis(4);
+/// Maximum number of frames of allowance a computation kind might overdraw.
+/// This bounds the time the kind is blocked after a single long computation.
+const MAX_DEBT_FRAMES: f32 = 30.;
+/// Cost of a single computation assumed before any computation is measured.
+const INITIAL_ESTIMATE: Duration = Duration::from_millis(1);
+/// Weight of the last measurement in the running cost estimate.
+const ESTIMATE_WEIGHT: f32 = 0.2;
 
 pub struct GameSchedulesPlugin;
 
@@ -31,6 +47,9 @@ impl Plugin for GameSchedulesPlugin {
         Self::insert_schedule(app, InputSchedule, PreMovement);
         Self::insert_schedule(app, PreMovement, Movement);
         Self::insert_schedule(app, Movement, PostMovement);
+
+        app.init_resource::<ComputeBudget>()
+            .add_systems(First, refill_budget);
     }
 }
 
@@ -57,3 +76,192 @@ pub struct Movement;
 /// This schedule includes for example update to spatial index of movable objects.
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PostMovement;
+
+/// Kinds of heavy computations time-sliced by [`ComputeBudget`].
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq)]
+pub enum ComputeKind {
+    /// Searches of paths of individual entities.
+    PathSearch,
+    /// Rebuilds of the path finder after static objects change.
+    FinderUpdate,
+    /// Rendering of the terrain overview after a map is loaded.
+    TerrainOverview,
+}
+
+impl ComputeKind {
+    /// Share of the frame budget allotted to the kind. Shares of all kinds
+    /// sum to 1.
+    fn share(self) -> f32 {
+        match self {
+            Self::PathSearch => 0.75,
+            Self::FinderUpdate => 0.15,
+            Self::TerrainOverview => 0.1,
+        }
+    }
+
+    /// Time allotted to the kind each frame in seconds.
+    fn allowance(self) -> f32 {
+        FRAME_BUDGET.as_secs_f32() * self.share()
+    }
+}
+
+/// Shared per-frame time budget of heavy computations which might be
+/// deferred to later frames, for example path searches.
+///
+/// Each [`ComputeKind`] receives a share of the budget every frame. A
+/// computation is started only if the kind has a positive balance, see
+/// [`Self::try_start`], and its measured duration is charged once it
+/// finishes, see [`Self::finish`]. Expensive computations thus make the kind
+/// skip several following frames instead of spiking a single frame.
+/// Computations which do not finish, for example because they are
+/// superseded, return their reservation with [`Self::cancel`] and their work
+/// is requeued by the caller. Computations executed on the async compute task pool are accounted as
+/// well so that the pool is not saturated by a single kind.
+#[derive(Resource)]
+pub struct ComputeBudget(EnumMap<ComputeKind, KindBudget>);
+
+impl ComputeBudget {
+    /// Starts a computation if the budget of its kind is not exhausted.
+    ///
+    /// The returned ticket has to be passed to [`Self::finish`] once the
+    /// computation finishes.
+    pub fn try_start(&mut self, kind: ComputeKind) -> Option<ComputeTicket> {
+        if self.0[kind].balance > 0. {
+            Some(self.start(kind))
+        } else {
+            None
+        }
+    }
+
+    /// Starts a computation regardless of the budget of its kind. This is
+    /// meant for computations which cannot be deferred, for example those
+    /// directly requested by the player. The computation still consumes the
+    /// budget.
+    ///
+    /// The returned ticket has to be passed to [`Self::finish`] once the
+    /// computation finishes.
+    pub fn start(&mut self, kind: ComputeKind) -> ComputeTicket {
+        let budget = &mut self.0[kind];
+        let estimate = budget.estimate.as_secs_f32();
+        budget.balance -= estimate;
+        ComputeTicket { kind, estimate }
+    }
+
+    /// Charges actual duration of a finished computation.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticket` - ticket returned when the computation was started.
+    ///
+    /// * `elapsed` - measured duration of the computation.
+    pub fn finish(&mut self, ticket: ComputeTicket, elapsed: Duration) {
+        let budget = &mut self.0[ticket.kind];
+        let min_balance = -MAX_DEBT_FRAMES * ticket.kind.allowance();
+        budget.balance =
+            (budget.balance + ticket.estimate - elapsed.as_secs_f32()).max(min_balance);
+        budget.estimate =
+            budget.estimate.mul_f32(1. - ESTIMATE_WEIGHT) + elapsed.mul_f32(ESTIMATE_WEIGHT);
+    }
+
+    /// Returns the time reserved by a computation which is abandoned before
+    /// it finishes. The caller is responsible for requeuing the work if it is
+    /// still needed.
+    pub fn cancel(&mut self, ticket: ComputeTicket) {
+        self.0[ticket.kind].balance += ticket.estimate;
+    }
+
+    fn refill(&mut self) {
+        for (kind, budget) in self.0.iter_mut() {
+            let allowance = kind.allowance();
+            budget.balance = (budget.balance + allowance).min(allowance);
+        }
+    }
+}
+
+impl Default for ComputeBudget {
+    fn default() -> Self {
+        Self(EnumMap::from_fn(|_| KindBudget {
+            balance: 0.,
+            estimate: INITIAL_ESTIMATE,
+        }))
+    }
+}
+
+struct KindBudget {
+    /// Remaining time of the kind in seconds. It is negative when the kind
+    /// has overdrawn its budget.
+    balance: f32,
+    /// Running estimate of the duration of a single computation.
+    estimate: Duration,
+}
+
+/// A started computation, see [`ComputeBudget::try_start`]. Each ticket has to
+/// be either finished or cancelled, see [`ComputeBudget::finish`] and
+/// [`ComputeBudget::cancel`].
+#[must_use]
+#[derive(Debug)]
+pub struct ComputeTicket {
+    kind: ComputeKind,
+    /// Time in seconds charged when the computation was started.
+    estimate: f32,
+}
+
+fn refill_budget(mut budget: ResMut<ComputeBudget>) {
+    budget.refill();
+}
+
+#[cfg(test)]
+mod tests {
+    use enum_map::enum_map;
+
+    use super::*;
+
+    #[test]
+    fn test_shares() {
+        let shares: EnumMap<ComputeKind, f32> = enum_map! {
+            kind => kind.share(),
+        };
+        assert!((shares.values().sum::<f32>() - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut budget = ComputeBudget::default();
+        assert!(budget.try_start(ComputeKind::PathSearch).is_none());
+
+        // 3 ms allowance of path searches, each estimated to 1 ms.
+        budget.refill();
+        let first = budget.try_start(ComputeKind::PathSearch).unwrap();
+        let second = budget.try_start(ComputeKind::PathSearch).unwrap();
+        budget.finish(first, Duration::from_millis(1));
+        budget.finish(second, Duration::from_millis(6));
+        assert!(budget.try_start(ComputeKind::PathSearch).is_none());
+        assert!(budget.try_start(ComputeKind::FinderUpdate).is_some());
+
+        // The overdrawn 4 ms are paid off in following frames.
+        budget.refill();
+        assert!(budget.try_start(ComputeKind::PathSearch).is_none());
+        budget.refill();
+        let third = budget.try_start(ComputeKind::PathSearch).unwrap();
+        assert!(budget.try_start(ComputeKind::PathSearch).is_none());
+
+        // Cancelled computations do not consume the budget.
+        budget.cancel(third);
+        assert!(budget.try_start(ComputeKind::PathSearch).is_some());
+    }
+
+    #[test]
+    fn test_max_debt() {
+        let mut budget = ComputeBudget::default();
+        let ticket = budget.start(ComputeKind::FinderUpdate);
+        budget.finish(ticket, Duration::from_secs(10));
+
+        for _ in 0..29 {
+            budget.refill();
+        }
+        assert!(budget.try_start(ComputeKind::FinderUpdate).is_none());
+        budget.refill();
+        budget.refill();
+        assert!(budget.try_start(ComputeKind::FinderUpdate).is_some());
+    }
+}
@@ -0,0 +1,370 @@
+use std::time::Duration;
+
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_construction::{OrdersSet, StandingOrder, StandingOrderEvent};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    objects::ObjectTypeComponent,
+    player::PlayerComponent,
+};
+use de_index::SpatialQuery;
+use de_messages::ToPlayers;
+use de_multiplayer::{NetEntities, NetRecvExtractEvent, ToPlayersEvent};
+use de_objects::{Deposit, Harvester, SolidObjects};
+use de_pathing::{PathQueryProps, PathTarget, UpdateEntityPathEvent};
+use de_types::{player::Player, projection::ToFlat};
+
+use crate::Stocks;
+
+/// Maximum gap in meters between a harvester and a deposit (or a depot) for
+/// the harvester to be able to gather (or unload) resources.
+const REACH: f32 = 2.;
+/// Resources extracted from deposits are announced to other players at most
+/// once per this period so that they are not flooded with updates.
+const SYNC_PERIOD: Duration = Duration::from_secs(1);
+
+pub(crate) struct HarvestPlugin;
+
+impl Plugin for HarvestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HarvestEvent>()
+            .add_systems(OnEnter(GameState::Playing), setup)
+            .add_systems(OnExit(GameState::Playing), cleanup)
+            .add_systems(
+                PreUpdate,
+                handle_harvest_events
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor))
+                    .in_set(HarvestSet::HarvestEvent),
+            )
+            .add_systems(
+                Update,
+                (
+                    extract_remote
+                        .run_if(on_event::<NetRecvExtractEvent>())
+                        .before(harvest),
+                    harvest,
+                    sync_extractions
+                        .run_if(|config: Res<GameConfig>| config.multiplayer())
+                        .after(harvest),
+                    execute_orders
+                        .run_if(on_event::<StandingOrderEvent>())
+                        .after(OrdersSet::Apply),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum HarvestSet {
+    HarvestEvent,
+}
+
+/// Send this event to start or stop resource gathering by a harvester.
+#[derive(Event)]
+pub struct HarvestEvent {
+    harvester: Entity,
+    deposit: Option<Entity>,
+}
+
+impl HarvestEvent {
+    /// # Arguments
+    ///
+    /// * `harvester` - a locally simulated harvesting entity. The event is
+    ///   ignored for entities which cannot harvest.
+    ///
+    /// * `deposit` - resource deposit to gather from or None if harvesting
+    ///   shall be stopped.
+    pub fn new(harvester: Entity, deposit: Option<Entity>) -> Self {
+        Self { harvester, deposit }
+    }
+}
+
+type HarvestersQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static ObjectTypeComponent,
+        &'static PlayerComponent,
+        &'static mut Harvester,
+        &'static mut Harvesting,
+        Has<PathTarget>,
+    ),
+>;
+
+type DepotsQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static ObjectTypeComponent,
+        &'static PlayerComponent,
+    ),
+    Without<Harvester>,
+>;
+
+type DepotCandidates<'w, 's> = SpatialQuery<
+    'w,
+    's,
+    (&'static ObjectTypeComponent, &'static PlayerComponent),
+    Without<Harvester>,
+>;
+
+/// Amounts of resources extracted from each deposit by locally simulated
+/// harvesters not yet announced to other players.
+#[derive(Resource, Default)]
+struct PendingExtractions(AHashMap<Entity, f32>);
+
+/// Harvesters with this component repeatedly gather resources from a deposit
+/// and bring them to the nearest depot of their owner.
+#[derive(Component)]
+struct Harvesting {
+    deposit: Entity,
+    state: HarvestState,
+    /// True if a path to the target of the current state has already been
+    /// requested.
+    path_requested: bool,
+}
+
+impl Harvesting {
+    fn new(deposit: Entity) -> Self {
+        Self {
+            deposit,
+            state: HarvestState::Approaching,
+            path_requested: false,
+        }
+    }
+
+    fn set_state(&mut self, state: HarvestState) {
+        if self.state != state {
+            self.state = state;
+            self.path_requested = false;
+        }
+    }
+
+    /// Makes sure that the harvester is moving towards a target.
+    ///
+    /// Returns false if the target cannot be reached, i.e. the already
+    /// requested path either was not found or finished too far from the
+    /// target.
+    ///
+    /// The path is requested only once per state so that any previously
+    /// followed path is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `harvester` - the harvesting entity.
+    ///
+    /// * `moving` - whether the harvester currently follows a path.
+    ///
+    /// * `target` - position of the target.
+    ///
+    /// * `reach` - the harvester needs to get at least this close to the
+    ///   target.
+    fn approach(
+        &mut self,
+        path_events: &mut EventWriter<UpdateEntityPathEvent>,
+        harvester: Entity,
+        moving: bool,
+        target: Vec2,
+        reach: f32,
+    ) -> bool {
+        if self.path_requested {
+            return moving;
+        }
+
+        path_events.send(UpdateEntityPathEvent::new(
+            harvester,
+            PathTarget::new(
+                target,
+                PathQueryProps::new(reach - 0.5 * REACH, reach),
+                false,
+            ),
+        ));
+        self.path_requested = true;
+        true
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum HarvestState {
+    /// The harvester is moving towards the deposit.
+    Approaching,
+    /// The harvester is gathering resources from the deposit.
+    Gathering,
+    /// The harvester is moving towards a depot (the attached entity) to
+    /// unload carried resources.
+    Returning(Entity),
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<PendingExtractions>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<PendingExtractions>();
+}
+
+/// Executes standing orders to harvest given to manufactured units.
+fn execute_orders(
+    mut order_events: EventReader<StandingOrderEvent>,
+    mut harvest_events: EventWriter<HarvestEvent>,
+) {
+    for event in order_events.read() {
+        if let StandingOrder::Harvest(deposit) = event.order() {
+            harvest_events.send(HarvestEvent::new(event.unit(), Some(deposit)));
+        }
+    }
+}
+
+fn handle_harvest_events(
+    mut commands: Commands,
+    mut events: EventReader<HarvestEvent>,
+    harvesters: Query<(), With<Harvester>>,
+) {
+    for event in events.read() {
+        if !harvesters.contains(event.harvester) {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(event.harvester);
+        match event.deposit {
+            Some(deposit) => entity_commands.insert(Harvesting::new(deposit)),
+            None => entity_commands.remove::<Harvesting>(),
+        };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn harvest(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    solids: SolidObjects,
+    mut stocks: ResMut<Stocks>,
+    mut harvesters: HarvestersQuery,
+    mut deposits: Query<(&Transform, &ObjectTypeComponent, &mut Deposit)>,
+    depots: DepotsQuery,
+    candidates: DepotCandidates,
+    mut pending: ResMut<PendingExtractions>,
+    mut path_events: EventWriter<UpdateEntityPathEvent>,
+) {
+    let nearest_depot = |player: Player, position: Vec2| -> Option<Entity> {
+        candidates
+            .nearest(position, None, |&(&object_type, &owner)| {
+                *owner == player && solids.get(*object_type).depot()
+            })
+            .map(|depot| depot.entity())
+    };
+
+    for (entity, transform, &object_type, &player, mut harvester, mut harvesting, moving) in
+        harvesters.iter_mut()
+    {
+        let position = transform.translation.to_flat();
+        let radius = solids.get(*object_type).ichnography().radius();
+
+        if let HarvestState::Returning(depot) = harvesting.state {
+            let Ok((depot_transform, &depot_type, _)) = depots.get(depot) else {
+                match nearest_depot(*player, position) {
+                    Some(depot) => harvesting.set_state(HarvestState::Returning(depot)),
+                    None => {
+                        commands.entity(entity).remove::<Harvesting>();
+                    }
+                }
+                continue;
+            };
+
+            let target = depot_transform.translation.to_flat();
+            let reach = radius + solids.get(*depot_type).ichnography().radius() + REACH;
+            if position.distance(target) <= reach {
+                let income = config.starting(*player).handicap().income();
+                stocks.add(*player, income * harvester.unload());
+                harvesting.set_state(HarvestState::Approaching);
+            } else if !harvesting.approach(&mut path_events, entity, moving, target, reach) {
+                commands.entity(entity).remove::<Harvesting>();
+            }
+            continue;
+        }
+
+        let deposit = deposits
+            .get_mut(harvesting.deposit)
+            .ok()
+            .filter(|(_, _, deposit)| !deposit.exhausted());
+
+        let Some((deposit_transform, &deposit_type, mut deposit)) =
+            deposit.filter(|_| !harvester.full())
+        else {
+            let depot = if harvester.load() > 0. {
+                nearest_depot(*player, position)
+            } else {
+                None
+            };
+            match depot {
+                Some(depot) => harvesting.set_state(HarvestState::Returning(depot)),
+                None => {
+                    commands.entity(entity).remove::<Harvesting>();
+                }
+            }
+            continue;
+        };
+
+        let target = deposit_transform.translation.to_flat();
+        let reach = radius + solids.get(*deposit_type).ichnography().radius() + REACH;
+        if position.distance(target) <= reach {
+            harvesting.set_state(HarvestState::Gathering);
+            let amount = (harvester.rate() * time.delta_seconds()).min(harvester.free_capacity());
+            let extracted = deposit.extract(amount);
+            harvester.fill(extracted);
+            if config.multiplayer() {
+                *pending.0.entry(harvesting.deposit).or_default() += extracted;
+            }
+        } else {
+            harvesting.set_state(HarvestState::Approaching);
+            if !harvesting.approach(&mut path_events, entity, moving, target, reach) {
+                commands.entity(entity).remove::<Harvesting>();
+            }
+        }
+    }
+}
+
+/// Announces resources extracted by locally simulated harvesters to other
+/// players.
+fn sync_extractions(
+    mut elapsed: Local<Duration>,
+    time: Res<Time>,
+    net_entities: NetEntities,
+    mut pending: ResMut<PendingExtractions>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    *elapsed += time.delta();
+    if *elapsed < SYNC_PERIOD {
+        return;
+    }
+    *elapsed -= SYNC_PERIOD;
+
+    for (deposit, amount) in pending.0.drain() {
+        // The deposit might have been despawned in the meantime.
+        let Some(object) = net_entities.inactive_net_id(deposit) else {
+            continue;
+        };
+        net_events.send(ToPlayersEvent::new(ToPlayers::ExtractDeposit {
+            object,
+            amount: amount.try_into().unwrap(),
+        }));
+    }
+}
+
+/// Applies resource extraction by harvesters simulated by other players.
+fn extract_remote(mut events: EventReader<NetRecvExtractEvent>, mut deposits: Query<&mut Deposit>) {
+    for event in events.read() {
+        if let Ok(mut deposit) = deposits.get_mut(event.deposit()) {
+            deposit.extract(event.amount());
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! This crate implements gathering of natural resources and per-player
+//! resource stocks.
+
+use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+use harvest::HarvestPlugin;
+pub use harvest::{HarvestEvent, HarvestSet};
+use stock::StockPlugin;
+pub use stock::Stocks;
+
+mod harvest;
+mod stock;
+
+pub struct EconomyPluginGroup;
+
+impl PluginGroup for EconomyPluginGroup {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(StockPlugin)
+            .add(HarvestPlugin)
+    }
+}
@@ -0,0 +1,112 @@
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{gconfig::GameConfig, state::AppState};
+use de_types::player::Player;
+
+pub(crate) struct StockPlugin;
+
+impl Plugin for StockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup);
+    }
+}
+
+/// Amount of resources gathered by each player.
+///
+/// Only players simulated by the local game instance are tracked.
+#[derive(Resource, Default)]
+pub struct Stocks {
+    players: AHashMap<Player, f32>,
+    /// Total amount of resources gathered since the start of the game.
+    /// Starting resources are not included.
+    gathered: AHashMap<Player, f32>,
+}
+
+impl Stocks {
+    /// Returns amount of resources available to a player.
+    pub fn amount(&self, player: Player) -> f32 {
+        self.players.get(&player).copied().unwrap_or(0.)
+    }
+
+    /// Returns total amount of resources gathered by a player since the
+    /// start of the game.
+    pub fn gathered(&self, player: Player) -> f32 {
+        self.gathered.get(&player).copied().unwrap_or(0.)
+    }
+
+    /// Increases stock of a player by gathered resources.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `amount` is not a non-negative finite number.
+    pub(crate) fn add(&mut self, player: Player, amount: f32) {
+        debug_assert!(amount.is_finite());
+        debug_assert!(amount >= 0.);
+        *self.players.entry(player).or_default() += amount;
+        *self.gathered.entry(player).or_default() += amount;
+    }
+
+    /// Decreases stock of a player. Returns false and keeps the stock intact
+    /// if the player does not have enough resources.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `amount` is not a non-negative finite number.
+    pub fn try_spend(&mut self, player: Player, amount: f32) -> bool {
+        debug_assert!(amount.is_finite());
+        debug_assert!(amount >= 0.);
+        if amount == 0. {
+            return true;
+        }
+
+        match self.players.get_mut(&player) {
+            Some(stock) if *stock >= amount => {
+                *stock -= amount;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn setup(mut commands: Commands, config: Res<GameConfig>) {
+    let mut stocks = Stocks::default();
+    for &player in config.locals().locals() {
+        let resources = config.starting(player).resources();
+        if resources > 0. {
+            stocks.players.insert(player, resources);
+        }
+    }
+    commands.insert_resource(stocks);
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Stocks>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stocks() {
+        let mut stocks = Stocks::default();
+        assert_eq!(stocks.amount(Player::Player1), 0.);
+
+        stocks.add(Player::Player1, 20.);
+        stocks.add(Player::Player2, 5.);
+        stocks.add(Player::Player1, 2.5);
+        assert_eq!(stocks.amount(Player::Player1), 22.5);
+        assert_eq!(stocks.amount(Player::Player2), 5.);
+        assert_eq!(stocks.amount(Player::Player3), 0.);
+        assert_eq!(stocks.gathered(Player::Player1), 22.5);
+
+        assert!(!stocks.try_spend(Player::Player2, 6.));
+        assert!(stocks.try_spend(Player::Player2, 5.));
+        assert_eq!(stocks.amount(Player::Player2), 0.);
+        assert!(stocks.try_spend(Player::Player3, 0.));
+        assert!(!stocks.try_spend(Player::Player3, 1.));
+        assert_eq!(stocks.gathered(Player::Player2), 5.);
+    }
+}
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
+use de_core::{gconfig::is_editor, objects::Disabled};
 
 pub(crate) struct BatteryPlugin;
 
 impl Plugin for BatteryPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, discharge_battery);
+        app.add_systems(Update, discharge_battery.run_if(not(is_editor)));
     }
 }
 
@@ -50,6 +51,24 @@ impl Battery {
         self.energy
     }
 
+    /// The current energy level relative to the capacity of the battery.
+    pub fn fraction(&self) -> f64 {
+        self.energy / self.capacity
+    }
+
+    /// Consumes energy from the battery. Returns false and keeps the battery
+    /// intact if there is not enough energy stored.
+    pub fn try_consume(&mut self, energy: f64) -> bool {
+        debug_assert!(energy.is_finite());
+        debug_assert!(energy >= 0.);
+
+        if self.energy < energy {
+            return false;
+        }
+        self.change(-energy);
+        true
+    }
+
     /// Directly changes the energy level of the battery by the given amount of energy.
     fn change(&mut self, delta: f64) {
         debug_assert!(delta.is_finite());
@@ -58,14 +77,18 @@ impl Battery {
     }
 }
 
-/// Discharges the batteries of all units.
+/// Discharges the batteries of all units. Batteries of disabled units are
+/// not used and thus kept intact.
 ///
 /// # Arguments
 ///
 /// * `time` - The time since the last update.
 ///
 /// * `battery` - The battery.
-pub(crate) fn discharge_battery(time: Res<Time>, mut battery: Query<&mut Battery>) {
+pub(crate) fn discharge_battery(
+    time: Res<Time>,
+    mut battery: Query<&mut Battery, Without<Disabled>>,
+) {
     let delta = time.delta_seconds();
     let discharge_delta = DISCHARGE_RATE * delta as f64;
     for mut battery in battery.iter_mut() {
@@ -116,4 +139,19 @@ mod tests {
 
         assert!(battery.energy() == DEFAULT_CAPACITY - DISCHARGE_RATE);
     }
+
+    #[test]
+    fn test_consume() {
+        let mut battery = Battery::new(200., 50.);
+        assert!(battery.try_consume(30.));
+        assert_eq!(battery.energy(), 20.);
+        assert!(!battery.try_consume(30.));
+        assert_eq!(battery.energy(), 20.);
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(Battery::default().fraction(), 1.);
+        assert_eq!(Battery::new(200., 50.).fraction(), 0.25);
+    }
 }
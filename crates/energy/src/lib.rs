@@ -1,14 +1,18 @@
 mod battery;
+mod status;
 
 pub use battery::Battery;
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+pub use status::LowEnergyEvent;
 
-use crate::battery::BatteryPlugin;
+use crate::{battery::BatteryPlugin, status::StatusPlugin};
 
 pub struct EnergyPluginGroup;
 
 impl PluginGroup for EnergyPluginGroup {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>().add(BatteryPlugin)
+        PluginGroupBuilder::start::<Self>()
+            .add(BatteryPlugin)
+            .add(StatusPlugin)
     }
 }
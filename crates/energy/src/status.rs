@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    notification::{GameNotification, NotifyEvent},
+    player::PlayerComponent,
+};
+use de_types::projection::ToFlat;
+
+use crate::battery::{discharge_battery, Battery};
+
+/// Batteries charged below this fraction of their capacity are low on energy.
+const LOW_ENERGY_THRESHOLD: f64 = 0.2;
+
+pub(crate) struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LowEnergyEvent>().add_systems(
+            Update,
+            (update_low_energy, update_depleted)
+                .after(discharge_battery)
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(is_editor)),
+        );
+    }
+}
+
+/// This event is sent whenever an entity's battery drops below or rises
+/// above the low energy threshold.
+#[derive(Event)]
+pub struct LowEnergyEvent {
+    entity: Entity,
+    low: bool,
+}
+
+impl LowEnergyEvent {
+    fn new(entity: Entity, low: bool) -> Self {
+        Self { entity, low }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns true if the battery is low on energy.
+    pub fn low(&self) -> bool {
+        self.low
+    }
+}
+
+/// Marks entities whose battery is low on energy.
+#[derive(Component)]
+struct LowEnergy;
+
+/// Marks entities whose battery is empty.
+#[derive(Component)]
+struct Depleted;
+
+/// Changed batteries together with presence of a status marker `M`.
+type ChangedBatteries<'w, 's, M> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Battery,
+        &'static Transform,
+        Option<&'static PlayerComponent>,
+        Has<M>,
+    ),
+    Changed<Battery>,
+>;
+
+fn update_low_energy(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    batteries: ChangedBatteries<LowEnergy>,
+    mut events: EventWriter<LowEnergyEvent>,
+    mut notify_events: EventWriter<NotifyEvent>,
+) {
+    for (entity, battery, transform, player, was_low) in batteries.iter() {
+        let low = battery.fraction() < LOW_ENERGY_THRESHOLD;
+        if low == was_low {
+            continue;
+        }
+
+        if low {
+            commands.entity(entity).insert(LowEnergy);
+
+            if player.is_some_and(|&player| config.locals().is_playable(*player)) {
+                notify_events.send(NotifyEvent::new(GameNotification::low_power(
+                    transform.translation.to_flat(),
+                )));
+            }
+        } else {
+            commands.entity(entity).remove::<LowEnergy>();
+        }
+        events.send(LowEnergyEvent::new(entity, low));
+    }
+}
+
+fn update_depleted(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    batteries: ChangedBatteries<Depleted>,
+    mut notify_events: EventWriter<NotifyEvent>,
+) {
+    for (entity, battery, transform, player, was_depleted) in batteries.iter() {
+        let depleted = battery.energy() <= 0.;
+        if depleted == was_depleted {
+            continue;
+        }
+
+        if depleted {
+            commands.entity(entity).insert(Depleted);
+
+            if player.is_some_and(|&player| config.locals().is_playable(*player)) {
+                notify_events.send(NotifyEvent::new(GameNotification::power_depleted(
+                    transform.translation.to_flat(),
+                )));
+            }
+        } else {
+            commands.entity(entity).remove::<Depleted>();
+        }
+    }
+}
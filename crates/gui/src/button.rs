@@ -18,6 +18,10 @@ impl Plugin for ButtonPlugin {
 
 pub trait ButtonCommands<'w, 's> {
     fn spawn_button(&mut self, size: OuterStyle, caption: impl Into<String>) -> EntityCommands<'_>;
+
+    /// Spawns a button with an image (instead of a text caption) in its
+    /// center.
+    fn spawn_image_button(&mut self, size: OuterStyle, image: Handle<Image>) -> EntityCommands<'_>;
 }
 
 impl<'w, 's> ButtonCommands<'w, 's> for GuiCommands<'w, 's> {
@@ -46,6 +50,39 @@ impl<'w, 's> ButtonCommands<'w, 's> for GuiCommands<'w, 's> {
 
         commands
     }
+
+    fn spawn_image_button(
+        &mut self,
+        style: OuterStyle,
+        image: Handle<Image>,
+    ) -> EntityCommands<'_> {
+        let mut commands = self.spawn(ButtonBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                width: style.width,
+                height: style.height,
+                margin: style.margin,
+                ..default()
+            },
+            ..default()
+        });
+
+        commands.with_children(|builder| {
+            builder.spawn(ImageBundle {
+                style: Style {
+                    max_width: Val::Percent(90.),
+                    max_height: Val::Percent(90.),
+                    aspect_ratio: Some(1.),
+                    ..default()
+                },
+                image: UiImage::new(image),
+                ..default()
+            });
+        });
+
+        commands
+    }
 }
 
 #[derive(SystemParam)]
@@ -10,6 +10,8 @@ use bevy::{
     ui::UiSystem,
 };
 
+use crate::textbox::TextBox;
+
 pub(crate) struct FocusPlugin;
 
 impl Plugin for FocusPlugin {
@@ -80,8 +82,32 @@ where
     }
 }
 
+/// Marker of UI nodes which capture all keyboard input while they exist,
+/// e.g. overlays with text input. See [`KeyboardCapture`].
+#[derive(Component)]
+pub struct CapturesKeyboard;
+
+/// This system parameter tells whether keyboard input is meant for the UI
+/// rather than for game hotkeys, i.e. whether a text box is focused or a
+/// [`CapturesKeyboard`] node exists.
+#[derive(SystemParam)]
+pub struct KeyboardCapture<'w, 's> {
+    focus: Res<'w, UiFocus>,
+    text_boxes: Query<'w, 's, (), With<TextBox>>,
+    captures: Query<'w, 's, (), With<CapturesKeyboard>>,
+}
+
+impl<'w, 's> KeyboardCapture<'w, 's> {
+    pub fn captured(&self) -> bool {
+        self.focus
+            .current
+            .is_some_and(|entity| self.text_boxes.contains(entity))
+            || !self.captures.is_empty()
+    }
+}
+
 #[derive(Resource, Default)]
-pub(super) struct UiFocus {
+pub struct UiFocus {
     previous: Option<Entity>,
     current: Option<Entity>,
 }
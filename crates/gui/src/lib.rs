@@ -8,13 +8,15 @@ use button::ButtonPlugin;
 pub use button::{ButtonCommands, ButtonOps};
 pub use commands::GuiCommands;
 use focus::FocusPlugin;
-pub use focus::SetFocusEvent;
+pub use focus::{CapturesKeyboard, KeyboardCapture, SetFocusEvent};
 pub use label::LabelCommands;
+pub use scale::GuiScale;
+use scale::ScalePlugin;
 pub use style::OuterStyle;
 use text::TextPlugin;
 pub use text::TextProps;
 use textbox::TextBoxPlugin;
-pub use textbox::{TextBoxCommands, TextBoxQuery};
+pub use textbox::{TextBoxCommands, TextBoxOps, TextBoxQuery};
 pub use toast::ToastEvent;
 use toast::ToastPlugin;
 
@@ -23,6 +25,7 @@ mod button;
 mod commands;
 mod focus;
 mod label;
+mod scale;
 mod style;
 mod text;
 mod textbox;
@@ -38,5 +41,6 @@ impl PluginGroup for GuiPluginGroup {
             .add(ButtonPlugin)
             .add(TextBoxPlugin)
             .add(ToastPlugin)
+            .add(ScalePlugin)
     }
 }
@@ -0,0 +1,83 @@
+use bevy::{prelude::*, ui::UiSystem, window::PrimaryWindow};
+
+/// Window height (in logical pixels) at which the UI is drawn at the user
+/// chosen scale. The UI is proportionally scaled up on larger and down on
+/// smaller windows.
+const REFERENCE_HEIGHT: f32 = 1080.;
+
+pub(crate) struct ScalePlugin;
+
+impl Plugin for ScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GuiScale>()
+            .add_systems(PostUpdate, update_scale.before(UiSystem::Layout));
+    }
+}
+
+/// Scale of the UI chosen by the user. The final UI scale is this value
+/// multiplied by the ratio of the window height to the reference height of
+/// 1080 logical pixels.
+///
+/// Logical pixels already account for the scale factor (DPI) of the monitor,
+/// thus the UI keeps its physical size across monitors with different pixel
+/// densities.
+#[derive(Resource)]
+pub struct GuiScale(f32);
+
+impl GuiScale {
+    /// # Panics
+    ///
+    /// May panic if `scale` is not a positive finite number.
+    pub fn new(scale: f32) -> Self {
+        debug_assert!(scale.is_finite());
+        debug_assert!(scale > 0.);
+        Self(scale)
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.0
+    }
+
+    /// Returns final UI scale for a window of a given height (in logical
+    /// pixels).
+    fn ui_scale(&self, window_height: f32) -> f32 {
+        self.0 * window_height / REFERENCE_HEIGHT
+    }
+}
+
+impl Default for GuiScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+fn update_scale(
+    gui_scale: Res<GuiScale>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    // Minimized windows may have zero size.
+    if window.height() <= 0. {
+        return;
+    }
+
+    let scale = gui_scale.ui_scale(window.height());
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ui_scale() {
+        assert_eq!(GuiScale::default().ui_scale(1080.), 1.);
+        assert_eq!(GuiScale::default().ui_scale(2160.), 2.);
+        assert_eq!(GuiScale::new(1.5).ui_scale(720.), 1.);
+    }
+}
@@ -74,6 +74,29 @@ impl<'w, 's> TextBoxQuery<'w, 's> {
     }
 }
 
+#[derive(SystemParam)]
+pub struct TextBoxOps<'w, 's> {
+    text_boxes: Query<'w, 's, (&'static mut TextBox, &'static Children)>,
+    texts: Query<'w, 's, &'static mut Text>,
+}
+
+impl<'w, 's> TextBoxOps<'w, 's> {
+    /// Clears a text box and returns its previous text. None is returned if
+    /// the entity is not a text box.
+    pub fn take_text(&mut self, entity: Entity) -> Option<String> {
+        let (mut text_box, children) = self.text_boxes.get_mut(entity).ok()?;
+        let text = std::mem::take(&mut text_box.text);
+
+        for &child in children.iter() {
+            if let Ok(mut ui_text) = self.texts.get_mut(child) {
+                ui_text.sections[0].value = text_box.ui_text();
+            }
+        }
+
+        Some(text)
+    }
+}
+
 #[derive(Component)]
 pub struct TextBox {
     text: String,
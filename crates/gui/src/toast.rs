@@ -1,7 +1,7 @@
 use std::{collections::VecDeque, time::Duration};
 
 use bevy::prelude::*;
-use de_core::state::AppState;
+use de_core::{cleanup::SurvivesGameExit, state::AppState};
 
 use crate::text::TextProps;
 
@@ -143,6 +143,7 @@ fn spawn(commands: &mut Commands, text_props: &TextProps, text: String) -> Entit
         z_index: ZIndex::Local(10000),
         ..default()
     });
+    commands.insert(SurvivesGameExit);
 
     commands.with_children(|builder| {
         builder.spawn(TextBundle::from_section(text, text_style));
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use criterion::{
+    criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion, PlotConfiguration,
+    Throughput,
+};
+use de_index::{EntityIndex, LocalCollider};
+use de_objects::ObjectCollider;
+use de_test_utils::load_points;
+use parry3d::{
+    math::{Isometry, Vector},
+    shape::{Cuboid, TriMesh},
+};
+
+const MAP_SIZE: f32 = 2000.;
+
+fn setup_index(points: &[Vec2]) -> EntityIndex {
+    let mut index = EntityIndex::new();
+    for (i, point) in points.iter().enumerate() {
+        let collider = LocalCollider::new(
+            ObjectCollider::from(TriMesh::from(Cuboid::new(Vector::new(3., 3., 4.)))),
+            Isometry::new(Vector::new(point.x, 0., point.y), Vector::identity()),
+        );
+        index.insert(Entity::from_raw(i as u32), collider);
+    }
+    index
+}
+
+fn update_benchmark(c: &mut Criterion) {
+    // Entities move by `step` meters in each iteration. Small steps emulate
+    // stationary jitter, large steps fast moving armies.
+    for step in [0.001, 0.1, 1., 10.] {
+        let mut group = c.benchmark_group(format!("Index Update - Step {step}m"));
+
+        let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+        group.plot_config(plot_config);
+
+        for num_entities in [100, 1000, 10_000, 100_000] {
+            let points = load_points(&num_entities.try_into().unwrap(), MAP_SIZE);
+            let mut index = setup_index(&points);
+            let mut offset = 0.;
+
+            group.throughput(Throughput::Elements(num_entities as u64));
+            group.bench_function(BenchmarkId::from_parameter(num_entities), |b| {
+                b.iter(|| {
+                    offset = if offset > 0. { 0. } else { step };
+                    index.update_batch(points.iter().enumerate().map(|(i, point)| {
+                        (
+                            Entity::from_raw(i as u32),
+                            Isometry::new(
+                                Vector::new(point.x + offset, 0., point.y),
+                                Vector::identity(),
+                            ),
+                        )
+                    }));
+                });
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, update_benchmark);
+criterion_main!(benches);
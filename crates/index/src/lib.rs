@@ -3,13 +3,17 @@
 //! entities.
 
 mod precise;
+mod vision;
 
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 use precise::PreciseIndexPlugin;
 pub use precise::{
-    ColliderWithCache, EntityIndex, LocalCollider, PreciseIndexSet, QueryCollider,
-    RayEntityIntersection, SpatialQuery,
+    BatchQuery, CachedSpatialQuery, ColliderWithCache, EntityDistance, EntityIndex, LocalCollider,
+    PreciseIndexSet, QueryCacheStats, QueryCollider, QueryShape, RayEntityIntersection,
+    SightObstacle, SpatialQuery,
 };
+use vision::VisionPlugin;
+pub use vision::{PlayerVision, VisionSet, VisionSets};
 
 /// Size (in world-space) of a single square tile where entities are kept.
 const TILE_SIZE: f32 = 10.;
@@ -18,6 +22,8 @@ pub struct IndexPluginGroup;
 
 impl PluginGroup for IndexPluginGroup {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>().add(PreciseIndexPlugin)
+        PluginGroupBuilder::start::<Self>()
+            .add(PreciseIndexPlugin)
+            .add(VisionPlugin)
     }
 }
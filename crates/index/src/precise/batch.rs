@@ -0,0 +1,197 @@
+//! This module implements batched spatial queries. Many queries are
+//! submitted at once and processed in parallel on Bevy's compute task pool.
+
+use std::hash::Hash;
+
+use ahash::AHashMap;
+use bevy::{
+    ecs::query::{QueryData, QueryFilter},
+    prelude::*,
+    tasks::ComputeTaskPool,
+};
+use parry3d::bounding_volume::Aabb;
+
+use super::index::SpatialQuery;
+
+/// Minimum number of queries processed by a single task. Spawning a task for
+/// each query would be slower than processing them sequentially.
+const MIN_CHUNK_SIZE: usize = 16;
+
+/// Geometry of a single batched query.
+#[derive(Clone, Copy, Debug)]
+pub enum QueryShape {
+    /// All entities whose world-space AABB intersects the AABB.
+    Aabb(Aabb),
+    /// All entities whose world-space AABB, projected to the map, is within
+    /// the `radius` from the `center`.
+    Circle { center: Vec2, radius: f32 },
+    /// Up to `k` entities closest to the `point` on the map, sorted by
+    /// increasing distance.
+    Nearest { point: Vec2, k: usize },
+}
+
+/// A single query of a batch submitted to [`SpatialQuery::query_batch`].
+pub struct BatchQuery<K> {
+    id: K,
+    shape: QueryShape,
+    ignore: Option<Entity>,
+}
+
+impl<K> BatchQuery<K> {
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the query. Results are keyed by this ID, thus
+    ///   it should be unique within a batch.
+    ///
+    /// * `shape` - geometry of the query.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
+    pub fn new(id: K, shape: QueryShape, ignore: Option<Entity>) -> Self {
+        Self { id, shape, ignore }
+    }
+}
+
+impl<'w, 's, Q, F> SpatialQuery<'w, 's, Q, F>
+where
+    Q: QueryData + Sync + Send + 'static,
+    F: QueryFilter + Sync + Send + 'static,
+{
+    /// Processes a batch of queries in parallel and returns matching entities
+    /// keyed by query ID.
+    ///
+    /// Results of queries with a duplicate ID are merged.
+    pub fn query_batch<K>(&self, queries: &[BatchQuery<K>]) -> AHashMap<K, Vec<Entity>>
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+    {
+        let pool = ComputeTaskPool::get();
+        let chunk_size = queries
+            .len()
+            .div_ceil(pool.thread_num().max(1))
+            .max(MIN_CHUNK_SIZE);
+
+        let results = pool.scope(|scope| {
+            for chunk in queries.chunks(chunk_size) {
+                scope.spawn(async move {
+                    chunk
+                        .iter()
+                        .map(|query| (query.id.clone(), self.query_single(query)))
+                        .collect::<Vec<_>>()
+                });
+            }
+        });
+
+        let mut merged: AHashMap<K, Vec<Entity>> = AHashMap::with_capacity(queries.len());
+        for (id, entities) in results.into_iter().flatten() {
+            merged.entry(id).or_default().extend(entities);
+        }
+        merged
+    }
+
+    fn query_single<K>(&self, query: &BatchQuery<K>) -> Vec<Entity> {
+        let ignore = query.ignore;
+        match query.shape {
+            QueryShape::Aabb(aabb) => self
+                .index()
+                .query_aabb(&aabb)
+                .filter(|&entity| ignore != Some(entity) && self.contains(entity))
+                .collect(),
+            QueryShape::Circle { center, radius } => self
+                .index()
+                .query_circle(center, radius)
+                .filter(|&entity| ignore != Some(entity) && self.contains(entity))
+                .collect(),
+            QueryShape::Nearest { point, k } => self
+                .k_nearest(point, k, ignore)
+                .iter()
+                .map(|nearest| nearest.entity())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::SystemState, tasks::TaskPool};
+    use de_objects::ObjectCollider;
+    use parry3d::{
+        math::{Isometry, Point, Vector},
+        shape::{Cuboid, TriMesh, TriMeshFlags},
+    };
+
+    use super::*;
+    use crate::precise::{collider::LocalCollider, index::EntityIndex};
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn test_query_batch() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut world = World::new();
+        let mut index = EntityIndex::new();
+        let mut entities = Vec::new();
+        for i in 0..100 {
+            let entity = if i % 2 == 0 {
+                world.spawn(Marker).id()
+            } else {
+                world.spawn_empty().id()
+            };
+            entities.push(entity);
+
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            let x = 10. * i as f32;
+            index.insert(
+                entity,
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(x, 0., 0.), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+        world.insert_resource(index);
+
+        let queries: Vec<BatchQuery<usize>> = (0..100)
+            .map(|i| {
+                let x = 10. * i as f32;
+                let shape = match i % 3 {
+                    0 => QueryShape::Aabb(Aabb::new(
+                        Point::new(x - 12., -1., -1.),
+                        Point::new(x + 12., 1., 1.),
+                    )),
+                    1 => QueryShape::Circle {
+                        center: Vec2::new(x, 0.),
+                        radius: 10.,
+                    },
+                    _ => QueryShape::Nearest {
+                        point: Vec2::new(x, 0.),
+                        k: 2,
+                    },
+                };
+                BatchQuery::new(i, shape, Some(entities[i]))
+            })
+            .collect();
+
+        let mut state: SystemState<SpatialQuery<Entity, With<Marker>>> =
+            SystemState::new(&mut world);
+        let results = state.get(&world).query_batch(&queries);
+        assert_eq!(results.len(), 100);
+
+        for (i, mut found) in results {
+            let mut expected: Vec<usize> = (0..100).filter(|&j| j != i && j % 2 == 0).collect();
+            expected.sort_by_key(|&j| j.abs_diff(i));
+            if i % 3 == 2 {
+                expected.truncate(2);
+            } else {
+                expected.retain(|&j| j.abs_diff(i) == 1);
+            }
+
+            let mut expected: Vec<Entity> = expected.iter().map(|&j| entities[j]).collect();
+            expected.sort();
+            found.sort();
+            assert_eq!(found, expected, "query {i}");
+        }
+    }
+}
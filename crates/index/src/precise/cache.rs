@@ -0,0 +1,348 @@
+//! This module implements caching of spatial query results across frames.
+//!
+//! The index keeps a version of each tile which is bumped whenever an entity
+//! intersecting the tile is inserted, removed or moved. A cached result is
+//! valid as long as none of the tiles covered by the query has changed since
+//! the result was computed.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ahash::AHashMap;
+use bevy::{
+    ecs::{
+        query::{QueryData, QueryFilter, ROQueryItem},
+        system::SystemParam,
+    },
+    prelude::*,
+};
+use glam::IVec2;
+use parry3d::{bounding_volume::Aabb, math::Point};
+
+use super::{
+    index::{EntityIndex, SpatialQuery},
+    range::TileRange,
+};
+
+/// Maximum number of results kept by a single [`QueryCache`]. The cache is
+/// cleared once it gets full, so that caches of queries with ever changing
+/// parameters do not grow indefinitely.
+const MAX_ENTRIES: usize = 256;
+
+/// Each [`TileVersions`] gets a unique ID so that results cached from an
+/// index of a previous game are never reused.
+static NEXT_VERSIONS_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Per-tile modification tracking of the entity index.
+pub(super) struct TileVersions {
+    id: u64,
+    generation: u64,
+    tiles: AHashMap<IVec2, u64>,
+}
+
+impl TileVersions {
+    pub(super) fn new() -> Self {
+        Self {
+            id: NEXT_VERSIONS_ID.fetch_add(1, Ordering::Relaxed),
+            generation: 0,
+            tiles: AHashMap::new(),
+        }
+    }
+
+    /// Marks all tiles intersecting a world-space AABB as changed.
+    pub(super) fn touch(&mut self, aabb: &Aabb) {
+        self.generation += 1;
+        for tile in TileRange::from_aabb(aabb) {
+            self.tiles.insert(tile, self.generation);
+        }
+    }
+
+    /// Returns true if any tile intersecting a world-space AABB has changed
+    /// after a given generation.
+    fn changed_since(&self, aabb: &Aabb, generation: u64) -> bool {
+        TileRange::from_aabb(aabb).any(|tile| {
+            self.tiles
+                .get(&tile)
+                .is_some_and(|&version| version > generation)
+        })
+    }
+}
+
+/// Hit rate metrics of all [`CachedSpatialQuery`] instances. The counters
+/// are reset at the beginning of each game.
+#[derive(Resource, Default)]
+pub struct QueryCacheStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl QueryCacheStats {
+    /// Number of queries answered from a cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of queries which had to be evaluated against the index.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Ratio of cache hits to all cached queries. It is 0 if no query has
+    /// been made yet.
+    pub fn hit_rate(&self) -> f32 {
+        let hits = self.hits();
+        let total = hits + self.misses();
+        if total == 0 {
+            0.
+        } else {
+            hits as f32 / total as f32
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Aabb([u32; 6]),
+    Circle([u32; 3]),
+}
+
+impl CacheKey {
+    fn aabb(aabb: &Aabb) -> Self {
+        Self::Aabb([
+            aabb.mins.x.to_bits(),
+            aabb.mins.y.to_bits(),
+            aabb.mins.z.to_bits(),
+            aabb.maxs.x.to_bits(),
+            aabb.maxs.y.to_bits(),
+            aabb.maxs.z.to_bits(),
+        ])
+    }
+
+    fn circle(center: Vec2, radius: f32) -> Self {
+        Self::Circle([center.x.to_bits(), center.y.to_bits(), radius.to_bits()])
+    }
+}
+
+struct CacheEntry {
+    generation: u64,
+    bounds: Aabb,
+    entities: Vec<Entity>,
+}
+
+/// Results of recently made queries of a single system.
+#[derive(Default)]
+pub(super) struct QueryCache {
+    versions_id: Option<u64>,
+    entries: AHashMap<CacheKey, CacheEntry>,
+}
+
+impl QueryCache {
+    /// Returns cached entities if still valid or computes and caches them.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the queried index.
+    ///
+    /// * `stats` - hit rate metrics to be updated.
+    ///
+    /// * `key` - query identification.
+    ///
+    /// * `bounds` - world-space AABB covering all possibly matching entities.
+    ///
+    /// * `compute` - function evaluating the query against the index.
+    fn get_or_compute<C>(
+        &mut self,
+        index: &EntityIndex,
+        stats: &QueryCacheStats,
+        key: CacheKey,
+        bounds: Aabb,
+        compute: C,
+    ) -> &[Entity]
+    where
+        C: FnOnce(&EntityIndex) -> Vec<Entity>,
+    {
+        let versions = index.versions();
+        if self.versions_id != Some(versions.id) {
+            self.entries.clear();
+            self.versions_id = Some(versions.id);
+        }
+
+        let hit = self
+            .entries
+            .get(&key)
+            .is_some_and(|entry| !versions.changed_since(&entry.bounds, entry.generation));
+        stats.record(hit);
+
+        if !hit {
+            if self.entries.len() >= MAX_ENTRIES && !self.entries.contains_key(&key) {
+                self.entries.clear();
+            }
+
+            self.entries.insert(
+                key,
+                CacheEntry {
+                    generation: versions.generation,
+                    bounds,
+                    entities: compute(index),
+                },
+            );
+        }
+
+        &self.entries[&key].entities
+    }
+}
+
+/// System parameter implementing spatial queries whose results are cached
+/// across frames. This is beneficial for queries repeated with identical
+/// parameters in an area with little movement, for example scans around
+/// stationary objects.
+///
+/// Each system has its own cache. Only the spatial part of the queries is
+/// cached: the query data and filter are always evaluated anew.
+#[derive(SystemParam)]
+pub struct CachedSpatialQuery<'w, 's, Q, F = ()>
+where
+    Q: QueryData + Sync + Send + 'static,
+    F: QueryFilter + Sync + Send + 'static,
+{
+    spatial: SpatialQuery<'w, 's, Q, F>,
+    cache: Local<'s, QueryCache>,
+    stats: Res<'w, QueryCacheStats>,
+}
+
+impl<'w, 's, Q, F> CachedSpatialQuery<'w, 's, Q, F>
+where
+    Q: QueryData + Sync + Send + 'static,
+    F: QueryFilter + Sync + Send + 'static,
+{
+    /// Cached version of [`SpatialQuery::query_aabb`].
+    pub fn query_aabb(
+        &mut self,
+        aabb: &Aabb,
+        ignore: Option<Entity>,
+    ) -> impl Iterator<Item = ROQueryItem<'_, Q>> + '_ {
+        let aabb = *aabb;
+        let entities = self.cache.get_or_compute(
+            self.spatial.index(),
+            &self.stats,
+            CacheKey::aabb(&aabb),
+            aabb,
+            |index| index.query_aabb(&aabb).collect(),
+        );
+        Self::resolve(&self.spatial, entities, ignore)
+    }
+
+    /// Cached version of [`SpatialQuery::query_circle`].
+    pub fn query_circle(
+        &mut self,
+        center: Vec2,
+        radius: f32,
+        ignore: Option<Entity>,
+    ) -> impl Iterator<Item = ROQueryItem<'_, Q>> + '_ {
+        let bounds = Aabb::new(
+            Point::new(center.x - radius, f32::MIN, -center.y - radius),
+            Point::new(center.x + radius, f32::MAX, -center.y + radius),
+        );
+        let entities = self.cache.get_or_compute(
+            self.spatial.index(),
+            &self.stats,
+            CacheKey::circle(center, radius),
+            bounds,
+            |index| index.query_circle(center, radius).collect(),
+        );
+        Self::resolve(&self.spatial, entities, ignore)
+    }
+
+    fn resolve<'a>(
+        spatial: &'a SpatialQuery<'w, 's, Q, F>,
+        entities: &'a [Entity],
+        ignore: Option<Entity>,
+    ) -> impl Iterator<Item = ROQueryItem<'a, Q>> + 'a {
+        entities
+            .iter()
+            .filter(move |&&entity| ignore != Some(entity))
+            .filter_map(|&entity| spatial.get(entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+    use de_objects::ObjectCollider;
+    use parry3d::{
+        math::{Isometry, Vector},
+        shape::{Cuboid, TriMesh, TriMeshFlags},
+    };
+
+    use super::*;
+    use crate::precise::collider::LocalCollider;
+
+    #[test]
+    fn test_cached_query() {
+        let mut world = World::new();
+        world.init_resource::<QueryCacheStats>();
+
+        let near = world.spawn_empty().id();
+        let far = world.spawn_empty().id();
+
+        let mut index = EntityIndex::new();
+        for (entity, x) in [(near, 5.), (far, 100.)] {
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            index.insert(
+                entity,
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(x, 0., 0.), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+        world.insert_resource(index);
+
+        let mut state: SystemState<CachedSpatialQuery<Entity>> = SystemState::new(&mut world);
+        let mut query_circle = |world: &mut World| -> Vec<Entity> {
+            let mut query = state.get_mut(world);
+            query.query_circle(Vec2::ZERO, 10., None).collect()
+        };
+
+        assert_eq!(query_circle(&mut world), vec![near]);
+        assert_eq!(query_circle(&mut world), vec![near]);
+
+        // Moving an entity outside of the queried tiles keeps the cache valid.
+        world.resource_mut::<EntityIndex>().update_batch([(
+            far,
+            Isometry::new(Vector::new(120., 0., 0.), Vector::new(0., 0., 0.)),
+        )]);
+        assert_eq!(query_circle(&mut world), vec![near]);
+
+        world.resource_mut::<EntityIndex>().update_batch([(
+            near,
+            Isometry::new(Vector::new(50., 0., 0.), Vector::new(0., 0., 0.)),
+        )]);
+        assert_eq!(query_circle(&mut world), vec![]);
+        assert_eq!(query_circle(&mut world), vec![]);
+
+        let stats = world.resource::<QueryCacheStats>();
+        assert_eq!(stats.hits(), 3);
+        assert_eq!(stats.misses(), 2);
+        assert_eq!(stats.hit_rate(), 0.6);
+
+        // A new index invalidates all cached results.
+        let mut index = EntityIndex::new();
+        let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+        trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+        index.insert(
+            far,
+            LocalCollider::new(
+                ObjectCollider::from(trimesh),
+                Isometry::new(Vector::new(1., 0., 0.), Vector::new(0., 0., 0.)),
+            ),
+        );
+        world.insert_resource(index);
+        assert_eq!(query_circle(&mut world), vec![far]);
+    }
+}
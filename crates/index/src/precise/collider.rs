@@ -1,4 +1,6 @@
 use de_objects::ObjectCollider;
+use de_types::projection::ToFlat;
+use glam::Vec2;
 use parry3d::{
     bounding_volume::{Aabb, BoundingVolume},
     math::Isometry,
@@ -70,6 +72,15 @@ impl LocalCollider {
     pub(super) fn query_aabb(&self, aabb: &Aabb) -> bool {
         self.world_aabb.intersects(aabb)
     }
+
+    /// Returns distance on the map (i.e. in 2D) between a point and
+    /// world-space AABB of the collider. The distance is 0 if the point is
+    /// covered by the AABB.
+    pub(super) fn flat_distance(&self, point: Vec2) -> f32 {
+        let aabb = self.world_aabb.to_flat();
+        let closest = point.clamp(aabb.mins.into(), aabb.maxs.into());
+        point.distance(closest)
+    }
 }
 
 impl ColliderWithCache for LocalCollider {
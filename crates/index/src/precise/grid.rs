@@ -62,39 +62,27 @@ impl TileGrid {
         }
     }
 
-    /// Update bounding box of an entity.
-    ///
-    /// # Arguments
-    ///
-    /// * `entity` - entity to be updated.
-    ///
-    /// * `old_aabb` - bounding box used during insertion or last update of the
-    ///   entity.
-    ///
-    /// * `new_aabb` - new world-space bounding box.
+    /// Applies changes collected from (possibly many) entity updates. Each
+    /// affected tile is visited only once.
     ///
     /// # Panics
     ///
-    /// Might panic if the entity is not present in the grid or if `old_aabb`
-    /// differs from the last used update / insert AABB.
-    pub(super) fn update(&mut self, entity: Entity, old_aabb: &Aabb, new_aabb: &Aabb) {
-        let old_tiles = TileRange::from_aabb(old_aabb);
-        let new_tiles = TileRange::from_aabb(new_aabb);
-
-        // Most of the time entities move withing the some tile range.
-        if old_tiles == new_tiles {
-            return;
-        }
-
-        let intersection = old_tiles.intersection(&new_tiles);
-        for tile in old_tiles {
-            if intersection.excludes(tile) {
-                self.remove_from_tile(entity, tile);
+    /// Might panic if the changes are inconsistent with the grid, see
+    /// [`TileChanges::update`].
+    pub(super) fn apply(&mut self, changes: TileChanges) {
+        for (tile_coords, change) in changes.tiles {
+            let tile = self.tiles.entry(tile_coords).or_default();
+            for entity in change.removed {
+                let removed = tile.remove(&entity);
+                debug_assert!(removed);
             }
-        }
-        for tile in new_tiles {
-            if intersection.excludes(tile) {
-                self.insert_to_tile(entity, tile);
+            for entity in change.inserted {
+                let inserted = tile.insert(entity);
+                debug_assert!(inserted);
+            }
+
+            if tile.is_empty() {
+                self.tiles.remove(&tile_coords);
             }
         }
     }
@@ -132,6 +120,63 @@ impl TileGrid {
     }
 }
 
+/// Per-tile changes of entity sets collected from entity updates.
+pub(super) struct TileChanges {
+    tiles: AHashMap<IVec2, TileChange>,
+}
+
+#[derive(Default)]
+struct TileChange {
+    removed: Vec<Entity>,
+    inserted: Vec<Entity>,
+}
+
+impl TileChanges {
+    pub(super) fn new() -> Self {
+        Self {
+            tiles: AHashMap::new(),
+        }
+    }
+
+    /// Records an update of bounding box of an entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - entity to be updated.
+    ///
+    /// * `old_aabb` - bounding box used during insertion or last update of the
+    ///   entity.
+    ///
+    /// * `new_aabb` - new world-space bounding box.
+    ///
+    /// # Panics
+    ///
+    /// Applying the changes to the grid might panic if the entity is not
+    /// present in the grid or if `old_aabb` differs from the last used update
+    /// / insert AABB.
+    pub(super) fn update(&mut self, entity: Entity, old_aabb: &Aabb, new_aabb: &Aabb) {
+        let old_tiles = TileRange::from_aabb(old_aabb);
+        let new_tiles = TileRange::from_aabb(new_aabb);
+
+        // Most of the time entities move withing the some tile range.
+        if old_tiles == new_tiles {
+            return;
+        }
+
+        let intersection = old_tiles.intersection(&new_tiles);
+        for tile in old_tiles {
+            if intersection.excludes(tile) {
+                self.tiles.entry(tile).or_default().removed.push(entity);
+            }
+        }
+        for tile in new_tiles {
+            if intersection.excludes(tile) {
+                self.tiles.entry(tile).or_default().inserted.push(entity);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ahash::AHashSet;
@@ -206,7 +251,9 @@ mod tests {
             &AHashSet::from_iter(vec![entity_c])
         );
 
-        grid.update(entity_c, &aabb_c_old, &aabb_c_new);
+        let mut changes = TileChanges::new();
+        changes.update(entity_c, &aabb_c_old, &aabb_c_new);
+        grid.apply(changes);
         assert!(grid.get_tile_entities(IVec2::new(7, 8)).is_none());
         assert_eq!(
             grid.get_tile_entities(IVec2::new(8, 9)).unwrap(),
@@ -227,6 +274,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_apply_changes() {
+        let mut grid = TileGrid::new();
+
+        let entity_a = Entity::from_raw(1);
+        let aabb_a_old = Aabb::new(
+            Point::new(TILE_SIZE * 0.1, 0., -TILE_SIZE * 0.9),
+            Point::new(TILE_SIZE * 0.2, 1., -TILE_SIZE * 0.8),
+        );
+        let aabb_a_new = Aabb::new(
+            Point::new(TILE_SIZE * 1.1, 0., -TILE_SIZE * 0.9),
+            Point::new(TILE_SIZE * 1.2, 1., -TILE_SIZE * 0.8),
+        );
+        let entity_b = Entity::from_raw(2);
+        let aabb_b_old = aabb_a_new;
+        let aabb_b_new = Aabb::new(
+            Point::new(TILE_SIZE * 2.1, 0., -TILE_SIZE * 0.9),
+            Point::new(TILE_SIZE * 2.2, 1., -TILE_SIZE * 0.8),
+        );
+
+        grid.insert(entity_a, &aabb_a_old);
+        grid.insert(entity_b, &aabb_b_old);
+
+        let mut changes = TileChanges::new();
+        changes.update(entity_a, &aabb_a_old, &aabb_a_new);
+        changes.update(entity_b, &aabb_b_old, &aabb_b_new);
+        grid.apply(changes);
+
+        assert!(grid.get_tile_entities(IVec2::new(0, 0)).is_none());
+        assert_eq!(
+            grid.get_tile_entities(IVec2::new(1, 0)).unwrap(),
+            &AHashSet::from_iter(vec![entity_a])
+        );
+        assert_eq!(
+            grid.get_tile_entities(IVec2::new(2, 0)).unwrap(),
+            &AHashSet::from_iter(vec![entity_b])
+        );
+    }
+
     #[test]
     fn test_tile_range_from_aabb() {
         let aabb = Aabb::new(
@@ -3,14 +3,16 @@
 
 use std::cmp::Ordering;
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use bevy::{
     ecs::{
         query::{QueryData, QueryFilter, ROQueryItem},
         system::SystemParam,
     },
     prelude::*,
+    render::primitives::Frustum,
 };
+use de_core::frustum;
 use parry3d::{
     bounding_volume::{Aabb, BoundingVolume},
     math::{Isometry, Point},
@@ -19,14 +21,29 @@ use parry3d::{
 };
 
 use super::{
-    aabb::AabbCandidates, collider::ColliderWithCache, collider::LocalCollider, grid::TileGrid,
+    aabb::AabbCandidates,
+    cache::TileVersions,
+    collider::ColliderWithCache,
+    collider::LocalCollider,
+    grid::{TileChanges, TileGrid},
+    range::{tile_coords, TileRange},
+    ring::TileRing,
     segment::SegmentCandidates,
+    visible::frustum_aabb,
 };
+use crate::TILE_SIZE;
+
+/// Position updates moving an entity by less than this distance (in meters)
+/// and rotating it by less than [`MIN_UPDATE_ANGLE`] are ignored.
+const MIN_UPDATE_DISTANCE: f32 = 0.005;
+/// See [`MIN_UPDATE_DISTANCE`]. Given in radians.
+const MIN_UPDATE_ANGLE: f32 = 0.001;
 
 /// 2D rectangular grid based spatial index of entities.
 #[derive(Resource)]
 pub struct EntityIndex {
     grid: TileGrid,
+    versions: TileVersions,
     world_bounds: Aabb,
     colliders: AHashMap<Entity, LocalCollider>,
 }
@@ -37,6 +54,7 @@ impl EntityIndex {
     pub fn new() -> Self {
         Self {
             grid: TileGrid::new(),
+            versions: TileVersions::new(),
             world_bounds: Aabb::new(Point::origin(), Point::origin()),
             colliders: AHashMap::new(),
         }
@@ -45,6 +63,7 @@ impl EntityIndex {
     // Needs to be public because it is used in a benchmark.
     pub fn insert(&mut self, entity: Entity, collider: LocalCollider) {
         self.grid.insert(entity, collider.world_aabb());
+        self.versions.touch(collider.world_aabb());
         self.world_bounds.merge(collider.world_aabb());
         self.colliders.insert(entity, collider);
     }
@@ -55,20 +74,47 @@ impl EntityIndex {
             .remove(&entity)
             .expect("Tried to remove non-existent entity.");
         self.grid.remove(entity, collider.world_aabb());
+        self.versions.touch(collider.world_aabb());
     }
 
-    pub(super) fn update(&mut self, entity: Entity, position: Isometry<f32>) {
-        let collider = self
-            .colliders
-            .get_mut(&entity)
-            .expect("Tried to update non-existent entity.");
+    /// Updates positions of many entities at once.
+    ///
+    /// Tile changes are collected from all the updates and applied in a
+    /// single pass. Updates which move an entity only negligibly from its
+    /// last indexed position are ignored, so that small jitter of stationary
+    /// entities does not churn the index.
+    // Needs to be public because it is used in a benchmark.
+    pub fn update_batch<I>(&mut self, updates: I)
+    where
+        I: IntoIterator<Item = (Entity, Isometry<f32>)>,
+    {
+        let mut changes = TileChanges::new();
+
+        for (entity, position) in updates {
+            let collider = self
+                .colliders
+                .get_mut(&entity)
+                .expect("Tried to update non-existent entity.");
+
+            let old_position = collider.position();
+            if (position.translation.vector - old_position.translation.vector).norm()
+                < MIN_UPDATE_DISTANCE
+                && position.rotation.angle_to(&old_position.rotation) < MIN_UPDATE_ANGLE
+            {
+                continue;
+            }
 
-        let old_aabb = *collider.world_aabb();
-        collider.update_position(position);
-        let new_aabb = collider.world_aabb();
+            let old_aabb = *collider.world_aabb();
+            collider.update_position(position);
+            let new_aabb = collider.world_aabb();
 
-        self.world_bounds.merge(new_aabb);
-        self.grid.update(entity, &old_aabb, new_aabb);
+            self.world_bounds.merge(new_aabb);
+            self.versions.touch(&old_aabb);
+            self.versions.touch(new_aabb);
+            changes.update(entity, &old_aabb, new_aabb);
+        }
+
+        self.grid.apply(changes);
     }
 
     /// Returns an iterator of potentially intersecting entities.
@@ -89,10 +135,117 @@ impl EntityIndex {
     }
 
     /// Returns an iterator of potentially intersecting entities.
-    fn query_aabb<'a>(&'a self, aabb: &Aabb) -> AabbCandidates<'a> {
+    fn aabb_candidates<'a>(&'a self, aabb: &Aabb) -> AabbCandidates<'a> {
         AabbCandidates::new(&self.grid, aabb)
     }
 
+    /// Returns an iterator over all entities whose world-space AABB
+    /// intersects a given AABB. Each entity is returned exactly once.
+    pub fn query_aabb<'a>(&'a self, aabb: &'a Aabb) -> impl Iterator<Item = Entity> + 'a {
+        self.aabb_candidates(aabb)
+            .flatten()
+            .filter(|&candidate| self.get_collider(candidate).query_aabb(aabb))
+    }
+
+    /// Returns an iterator over all entities whose world-space AABB, projected
+    /// to the map, is within a distance from a point. Each entity is returned
+    /// exactly once.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - center of the circle on the map.
+    ///
+    /// * `radius` - radius of the circle. Must be a non-negative finite
+    ///   number.
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> impl Iterator<Item = Entity> + '_ {
+        debug_assert!(radius.is_finite());
+        debug_assert!(radius >= 0.);
+
+        let aabb = Aabb::new(
+            Point::new(center.x - radius, f32::MIN, -center.y - radius),
+            Point::new(center.x + radius, f32::MAX, -center.y + radius),
+        );
+        self.aabb_candidates(&aabb)
+            .flatten()
+            .filter(move |&candidate| self.get_collider(candidate).flat_distance(center) <= radius)
+    }
+
+    /// Returns an iterator over all entities whose world-space AABB
+    /// intersects a frustum. Each entity is returned exactly once.
+    ///
+    /// # Arguments
+    ///
+    /// * `frustum` - the frustum. Its half-spaces are expected in the order
+    ///   used by Bevy: left, right, bottom, top, near, far.
+    pub fn query_frustum<'a>(&'a self, frustum: &'a Frustum) -> impl Iterator<Item = Entity> + 'a {
+        // Unbounded frustums are limited by the world bounds only.
+        let aabb = match frustum_aabb(frustum) {
+            Some(aabb) => aabb.intersection(&self.world_bounds),
+            None => Some(self.world_bounds),
+        };
+
+        aabb.into_iter()
+            .flat_map(|aabb| self.aabb_candidates(&aabb).flatten())
+            .filter(move |&candidate| {
+                frustum::intersects_parry(
+                    frustum,
+                    Transform::IDENTITY,
+                    self.get_collider(candidate).world_aabb(),
+                )
+            })
+    }
+
+    /// Returns up to `k` entities closest to a point, sorted by increasing
+    /// distance. Only entities for which `accept` returns true are included.
+    ///
+    /// The distance is measured on the map (i.e. in 2D) between the point
+    /// and the entity's world-space AABB.
+    fn k_nearest<P>(&self, point: Vec2, k: usize, mut accept: P) -> Vec<(Entity, f32)>
+    where
+        P: FnMut(Entity) -> bool,
+    {
+        let mut nearest: Vec<(Entity, f32)> = Vec::new();
+        if k == 0 || self.colliders.is_empty() {
+            return nearest;
+        }
+
+        let center = tile_coords(point);
+        let max_radius = TileRange::from_aabb(&self.world_bounds).max_distance(center);
+        let mut visited = AHashSet::new();
+
+        for radius in 0..=max_radius {
+            for tile in TileRing::new(center, radius) {
+                let Some(entities) = self.grid.get_tile_entities(tile) else {
+                    continue;
+                };
+
+                for &entity in entities {
+                    if !visited.insert(entity) || !accept(entity) {
+                        continue;
+                    }
+
+                    let distance = self.get_collider(entity).flat_distance(point);
+                    nearest.push((entity, distance));
+                }
+            }
+
+            nearest.sort_unstable_by(|a, b| a.1.total_cmp(&b.1));
+            nearest.truncate(k);
+
+            // All not yet visited entities lie fully outside of the already
+            // visited tiles.
+            if nearest.len() == k && nearest[k - 1].1 <= radius as f32 * TILE_SIZE {
+                break;
+            }
+        }
+
+        nearest
+    }
+
+    pub(super) fn versions(&self) -> &TileVersions {
+        &self.versions
+    }
+
     fn get_collider(&self, entity: Entity) -> &LocalCollider {
         self.colliders
             .get(&entity)
@@ -177,7 +330,7 @@ where
     /// Returns true if queried solid object on the map, as indexed by
     /// [`super::PreciseIndexPlugin`], intersects with the given collider.
     pub fn collides(&self, collider: &impl ColliderWithCache) -> bool {
-        let candidate_sets = self.index.query_aabb(collider.world_aabb());
+        let candidate_sets = self.index.aabb_candidates(collider.world_aabb());
         candidate_sets.flatten().any(|candidate| {
             self.entities.get(candidate).map_or(false, |_| {
                 self.index.get_collider(candidate).intersects(collider)
@@ -185,6 +338,141 @@ where
         })
     }
 
+    /// Returns up to `k` queried entities closest to a point, sorted by
+    /// increasing distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - position on the map.
+    ///
+    /// * `k` - maximum number of returned entities.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
+    ///
+    /// The distance is measured on the map (i.e. in 2D) between the point and
+    /// the world-space AABB of the entity, thus it is 0 for entities covering
+    /// the point.
+    pub fn k_nearest(
+        &self,
+        point: Vec2,
+        k: usize,
+        ignore: Option<Entity>,
+    ) -> Vec<EntityDistance<ROQueryItem<'_, Q>>> {
+        self.index
+            .k_nearest(point, k, |candidate| {
+                ignore != Some(candidate) && self.entities.contains(candidate)
+            })
+            .into_iter()
+            .map(|(entity, distance)| {
+                EntityDistance::new(entity, distance, self.entities.get(entity).unwrap())
+            })
+            .collect()
+    }
+
+    /// Returns the queried entity closest to a point among entities accepted
+    /// by a predicate.
+    ///
+    /// Unlike filtering results of [`Self::k_nearest`], the search continues
+    /// until an accepted entity is found, thus it is suitable for rare
+    /// entities (e.g. buildings of a particular player).
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - position on the map.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
+    ///
+    /// * `accept` - only entities for which this returns true are considered.
+    pub fn nearest<'a, P>(
+        &'a self,
+        point: Vec2,
+        ignore: Option<Entity>,
+        mut accept: P,
+    ) -> Option<EntityDistance<ROQueryItem<'a, Q>>>
+    where
+        P: FnMut(&ROQueryItem<'a, Q>) -> bool,
+    {
+        self.index
+            .k_nearest(point, 1, |candidate| {
+                ignore != Some(candidate)
+                    && self
+                        .entities
+                        .get(candidate)
+                        .map_or(false, |item| accept(&item))
+            })
+            .pop()
+            .map(|(entity, distance)| {
+                EntityDistance::new(entity, distance, self.entities.get(entity).unwrap())
+            })
+    }
+
+    /// Returns an iterator over queried entities whose world-space AABB, as
+    /// indexed by [`super::PreciseIndexPlugin`], is within a given distance
+    /// from a point on the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - center of the circle on the map.
+    ///
+    /// * `radius` - radius of the circle. Must be a non-negative finite
+    ///   number.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
+    pub fn query_circle(
+        &self,
+        center: Vec2,
+        radius: f32,
+        ignore: Option<Entity>,
+    ) -> impl Iterator<Item = ROQueryItem<'_, Q>> + '_ {
+        self.index
+            .query_circle(center, radius)
+            .filter(move |&candidate| ignore != Some(candidate))
+            .filter_map(|candidate| self.entities.get(candidate).ok())
+    }
+
+    /// Returns an iterator over queried entities whose world-space AABB, as
+    /// indexed by [`super::PreciseIndexPlugin`], intersects a frustum (for
+    /// example the camera view frustum).
+    ///
+    /// # Arguments
+    ///
+    /// * `frustum` - the frustum. Its half-spaces are expected in the order
+    ///   used by Bevy: left, right, bottom, top, near, far.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
+    pub fn query_frustum<'a>(
+        &'a self,
+        frustum: &'a Frustum,
+        ignore: Option<Entity>,
+    ) -> impl Iterator<Item = ROQueryItem<'a, Q>> + 'a {
+        self.index
+            .query_frustum(frustum)
+            .filter(move |&candidate| ignore != Some(candidate))
+            .filter_map(|candidate| self.entities.get(candidate).ok())
+    }
+
+    pub(super) fn index(&self) -> &EntityIndex {
+        &self.index
+    }
+
+    /// Returns query item of an entity or None if it does not match the query.
+    pub(super) fn get(&self, entity: Entity) -> Option<ROQueryItem<'_, Q>> {
+        self.entities.get(entity).ok()
+    }
+
+    /// Returns true if the entity matches the query.
+    pub(super) fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(entity)
+    }
+
+    /// Returns an iterator over queried entities whose world-space AABB, as
+    /// indexed by [`super::PreciseIndexPlugin`], intersects a given AABB.
+    ///
+    /// # Arguments
+    ///
+    /// * `aabb` - world-space query box.
+    ///
+    /// * `ignore` - if not None, this entity is not included in the results.
     pub fn query_aabb<'a, 'b>(
         &'a self,
         aabb: &'b Aabb,
@@ -248,6 +536,35 @@ impl<T> PartialEq for RayEntityIntersection<T> {
 
 impl<T> Eq for RayEntityIntersection<T> {}
 
+pub struct EntityDistance<T> {
+    entity: Entity,
+    distance: f32,
+    item: T,
+}
+
+impl<T> EntityDistance<T> {
+    fn new(entity: Entity, distance: f32, item: T) -> Self {
+        Self {
+            entity,
+            distance,
+            item,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Distance between the query point and the entity.
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+}
+
 pub struct AabbQueryResults<'w, 's, 'a, 'b, Q, F = ()>
 where
     Q: QueryData + Sync + Send + 'static,
@@ -273,7 +590,7 @@ where
         aabb: &'b Aabb,
         ignore: Option<Entity>,
     ) -> Self {
-        let candidates = index.query_aabb(aabb);
+        let candidates = index.aabb_candidates(aabb);
 
         Self {
             entities,
@@ -378,7 +695,7 @@ mod tests {
             index.cast_ray(&ray_a, 120.).unwrap().flatten().collect();
         assert_eq!(entities_a, AHashSet::from_iter(vec![entity_a, entity_b]));
 
-        index.update(entity_b, position_b_2);
+        index.update_batch(vec![(entity_b, position_b_2)]);
         assert_eq!(
             index.get_collider(entity_b).world_aabb(),
             &Aabb::new(Point::new(5., 999., -202.), Point::new(9., 1001., -198.))
@@ -395,6 +712,138 @@ mod tests {
         assert!(index.cast_ray(&ray_b, 120.).is_none());
     }
 
+    #[test]
+    fn test_update_batch() {
+        let mut index = EntityIndex::new();
+        let entities: Vec<Entity> = (0..3).map(Entity::from_raw).collect();
+        for &entity in &entities {
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            index.insert(
+                entity,
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(5., 0., -5.), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+
+        index.update_batch(vec![
+            (
+                entities[0],
+                Isometry::new(Vector::new(45., 0., -5.), Vector::new(0., 0., 0.)),
+            ),
+            (
+                entities[1],
+                Isometry::new(Vector::new(5.001, 0., -5.), Vector::new(0., 0., 0.)),
+            ),
+            (
+                entities[2],
+                Isometry::new(Vector::new(5., 0., -25.), Vector::new(0., 0., 0.)),
+            ),
+        ]);
+
+        assert_eq!(
+            index.get_collider(entities[0]).world_aabb(),
+            &Aabb::new(Point::new(44., -1., -6.), Point::new(46., 1., -4.))
+        );
+        // Negligible move is ignored.
+        assert_eq!(
+            index.get_collider(entities[1]).world_aabb(),
+            &Aabb::new(Point::new(4., -1., -6.), Point::new(6., 1., -4.))
+        );
+
+        let near: AHashSet<Entity> = index.query_circle(Vec2::new(5., 5.), 2.).collect();
+        assert_eq!(near, AHashSet::from_iter(vec![entities[1]]));
+        let far: AHashSet<Entity> = index.query_circle(Vec2::new(45., 5.), 2.).collect();
+        assert_eq!(far, AHashSet::from_iter(vec![entities[0]]));
+        let far: AHashSet<Entity> = index.query_circle(Vec2::new(5., 25.), 2.).collect();
+        assert_eq!(far, AHashSet::from_iter(vec![entities[2]]));
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        let mut index = EntityIndex::new();
+        assert!(index.k_nearest(Vec2::ZERO, 3, |_| true).is_empty());
+
+        for (i, x) in [1., -25., 60., 4.5, 400.].iter().enumerate() {
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            index.insert(
+                Entity::from_raw(i as u32),
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(*x, 0., -*x), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+
+        let point = Vec2::new(3., 3.);
+        assert!(index.k_nearest(point, 0, |_| true).is_empty());
+
+        let nearest: Vec<Entity> = index
+            .k_nearest(point, 3, |_| true)
+            .iter()
+            .map(|&(entity, _)| entity)
+            .collect();
+        assert_eq!(
+            nearest,
+            vec![
+                Entity::from_raw(3),
+                Entity::from_raw(0),
+                Entity::from_raw(1)
+            ]
+        );
+
+        let nearest = index.k_nearest(point, 2, |entity| entity != Entity::from_raw(3));
+        assert_eq!(nearest.len(), 2);
+        assert_eq!(nearest[0].0, Entity::from_raw(0));
+        assert_eq!(nearest[0].1, 2f32.sqrt());
+        assert_eq!(nearest[1].0, Entity::from_raw(1));
+
+        let all = index.k_nearest(point, 10, |_| true);
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[4].0, Entity::from_raw(4));
+        assert!(all.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_query_circle_aabb() {
+        let mut index = EntityIndex::new();
+        for (i, x) in [1., -25., 60., 4.5].iter().enumerate() {
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            index.insert(
+                Entity::from_raw(i as u32),
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(*x, 0., -*x), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+
+        let circle: AHashSet<Entity> = index.query_circle(Vec2::new(3., 3.), 1.5).collect();
+        assert_eq!(
+            circle,
+            AHashSet::from_iter(vec![Entity::from_raw(0), Entity::from_raw(3)])
+        );
+        let circle: AHashSet<Entity> = index.query_circle(Vec2::new(3., 3.), 1.).collect();
+        assert_eq!(circle, AHashSet::from_iter(vec![Entity::from_raw(3)]));
+        let circle: Vec<Entity> = index.query_circle(Vec2::new(-25., -25.), 0.).collect();
+        assert_eq!(circle, vec![Entity::from_raw(1)]);
+
+        let aabb = Aabb::new(Point::new(-30., -1., -70.), Point::new(70., 1., 20.));
+        let entities: AHashSet<Entity> = index.query_aabb(&aabb).collect();
+        assert_eq!(
+            entities,
+            AHashSet::from_iter(vec![
+                Entity::from_raw(0),
+                Entity::from_raw(2),
+                Entity::from_raw(3)
+            ])
+        );
+    }
+
     #[test]
     fn test_entity_collider() {
         let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 2., 3.)).into();
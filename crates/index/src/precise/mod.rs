@@ -7,7 +7,7 @@
 use bevy::prelude::*;
 use de_core::{
     gamestate::GameState,
-    objects::{MovableSolid, ObjectTypeComponent, StaticSolid},
+    objects::{Garrisoned, MovableSolid, ObjectTypeComponent, StaticSolid},
     schedule::PostMovement,
     state::AppState,
 };
@@ -15,16 +15,24 @@ use de_objects::SolidObjects;
 use parry3d::math::Isometry;
 
 pub use self::{
+    batch::{BatchQuery, QueryShape},
+    cache::{CachedSpatialQuery, QueryCacheStats},
     collider::{ColliderWithCache, LocalCollider, QueryCollider},
-    index::{EntityIndex, RayEntityIntersection, SpatialQuery},
+    index::{EntityDistance, EntityIndex, RayEntityIntersection, SpatialQuery},
+    sight::SightObstacle,
 };
 
 mod aabb;
+mod batch;
+mod cache;
 mod collider;
 mod grid;
 mod index;
 mod range;
+mod ring;
 mod segment;
+mod sight;
+mod visible;
 
 type SolidEntityQuery<'w, 's> = Query<
     'w,
@@ -32,6 +40,7 @@ type SolidEntityQuery<'w, 's> = Query<
     (Entity, &'static ObjectTypeComponent, &'static Transform),
     (
         Without<Indexed>,
+        Without<Garrisoned>,
         Or<(With<StaticSolid>, With<MovableSolid>)>,
     ),
 >;
@@ -43,7 +52,9 @@ type MovedQuery<'w, 's> =
 /// entities.
 ///
 /// Only entities with marker component [`de_core::objects::StaticSolid`] or
-/// [`de_core::objects::MovableSolid`] are indexed.
+/// [`de_core::objects::MovableSolid`] are indexed. Entities with component
+/// [`de_core::objects::Garrisoned`] are removed from the index until the
+/// component is removed.
 ///
 /// The systems are executed only in state
 /// [`de_core::gamestate::GameState::Playing`]. The systems automatically
@@ -58,7 +69,7 @@ impl Plugin for PreciseIndexPlugin {
             .add_systems(OnExit(AppState::InGame), cleanup)
             .add_systems(
                 PostUpdate,
-                (insert, remove)
+                (insert, remove, unindex_garrisoned)
                     .run_if(in_state(GameState::Playing))
                     .in_set(PreciseIndexSet::Index),
             )
@@ -81,10 +92,18 @@ struct Indexed;
 
 fn setup(mut commands: Commands) {
     commands.insert_resource(EntityIndex::new());
+    commands.init_resource::<QueryCacheStats>();
 }
 
-fn cleanup(mut commands: Commands) {
+fn cleanup(mut commands: Commands, stats: Res<QueryCacheStats>) {
+    debug!(
+        "Spatial query cache: {} hits, {} misses, hit rate {:.3}",
+        stats.hits(),
+        stats.misses(),
+        stats.hit_rate()
+    );
     commands.remove_resource::<EntityIndex>();
+    commands.remove_resource::<QueryCacheStats>();
 }
 
 /// This system iterates over all not yet indexed entities, computes their
@@ -116,12 +135,21 @@ fn remove(mut index: ResMut<EntityIndex>, mut removed: RemovedComponents<Indexed
     }
 }
 
+fn unindex_garrisoned(
+    mut commands: Commands,
+    garrisoned: Query<Entity, (With<Indexed>, With<Garrisoned>)>,
+) {
+    for entity in garrisoned.iter() {
+        commands.entity(entity).remove::<Indexed>();
+    }
+}
+
 fn update(mut index: ResMut<EntityIndex>, moved: MovedQuery) {
-    for (entity, transform) in moved.iter() {
+    index.update_batch(moved.iter().map(|(entity, transform)| {
         let position = Isometry::new(
             transform.translation.into(),
             transform.rotation.to_scaled_axis().into(),
         );
-        index.update(entity, position);
-    }
+        (entity, position)
+    }));
 }
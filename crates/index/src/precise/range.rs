@@ -23,10 +23,8 @@ impl TileRange {
     /// touching and intersecting tiles are included in the range.
     pub(super) fn from_aabb(aabb: &Aabb) -> Self {
         let aabb = aabb.to_flat();
-        let min_flat: Vec2 = aabb.mins.into();
-        let max_flat: Vec2 = aabb.maxs.into();
-        let start = (min_flat / TILE_SIZE).floor().as_ivec2();
-        let stop = (max_flat / TILE_SIZE).floor().as_ivec2();
+        let start = tile_coords(aabb.mins.into());
+        let stop = tile_coords(aabb.maxs.into());
         Self::new(start, stop)
     }
 
@@ -50,12 +48,28 @@ impl TileRange {
         self.a.cmpgt(point).any() || self.b.cmplt(point).any()
     }
 
+    /// Returns maximum Chebyshev distance (in tiles) between a tile and any
+    /// tile of the range. The range must not be empty.
+    pub(super) fn max_distance(&self, tile: IVec2) -> i32 {
+        debug_assert!(self.a.cmple(self.b).all());
+        (self.a - tile)
+            .abs()
+            .max((self.b - tile).abs())
+            .max_element()
+    }
+
     /// Returns intersecting tile range. The result might be empty.
     pub(super) fn intersection(&self, other: &TileRange) -> TileRange {
         Self::new(self.a.max(other.a), self.b.min(other.b))
     }
 }
 
+/// Returns coordinates of the tile containing a point given in flat (map)
+/// coordinates.
+pub(super) fn tile_coords(point: Vec2) -> IVec2 {
+    (point / TILE_SIZE).floor().as_ivec2()
+}
+
 impl PartialEq for TileRange {
     fn eq(&self, other: &Self) -> bool {
         self.a == other.a && self.b == other.b
@@ -0,0 +1,101 @@
+use glam::IVec2;
+
+/// Iterable square ring of tiles, i.e. all tiles in a given Chebyshev
+/// distance from a center tile.
+pub(super) struct TileRing {
+    center: IVec2,
+    radius: i32,
+    index: i32,
+}
+
+impl TileRing {
+    /// # Arguments
+    ///
+    /// * `center` - central tile of the ring.
+    ///
+    /// * `radius` - Chebyshev distance of all ring tiles from the center. The
+    ///   ring consists of the center tile only if this is 0.
+    pub(super) fn new(center: IVec2, radius: i32) -> Self {
+        debug_assert!(radius >= 0);
+        Self {
+            center,
+            radius,
+            index: 0,
+        }
+    }
+
+    fn len(&self) -> i32 {
+        if self.radius == 0 {
+            1
+        } else {
+            8 * self.radius
+        }
+    }
+}
+
+impl Iterator for TileRing {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if self.index >= self.len() {
+            return None;
+        }
+
+        let side = 2 * self.radius + 1;
+        let index = self.index;
+        self.index += 1;
+
+        // Full bottom and top rows are followed by left and right columns
+        // without the corners.
+        let offset = if index < 2 * side {
+            IVec2::new(
+                index % side - self.radius,
+                if index < side {
+                    -self.radius
+                } else {
+                    self.radius
+                },
+            )
+        } else {
+            let index = index - 2 * side;
+            let column_len = side - 2;
+            IVec2::new(
+                if index < column_len {
+                    -self.radius
+                } else {
+                    self.radius
+                },
+                index % column_len - self.radius + 1,
+            )
+        };
+
+        Some(self.center + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::AHashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_ring() {
+        let center = IVec2::new(3, -2);
+
+        let tiles: Vec<IVec2> = TileRing::new(center, 0).collect();
+        assert_eq!(tiles, vec![center]);
+
+        for radius in 1..5 {
+            let tiles: Vec<IVec2> = TileRing::new(center, radius).collect();
+            assert_eq!(tiles.len() as i32, 8 * radius);
+
+            let unique: AHashSet<IVec2> = tiles.iter().cloned().collect();
+            assert_eq!(unique.len(), tiles.len());
+
+            for tile in tiles {
+                assert_eq!((tile - center).abs().max_element(), radius);
+            }
+        }
+    }
+}
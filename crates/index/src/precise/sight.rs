@@ -0,0 +1,146 @@
+//! This module implements line of sight queries combining terrain and entity
+//! colliders.
+
+use bevy::{
+    ecs::query::{QueryData, QueryFilter},
+    prelude::*,
+};
+use de_terrain::TerrainCollider;
+use parry3d::query::Ray;
+
+use super::index::SpatialQuery;
+
+/// The first obstacle blocking a line of sight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SightObstacle {
+    toi: f32,
+    entity: Option<Entity>,
+}
+
+impl SightObstacle {
+    fn new(toi: f32, entity: Option<Entity>) -> Self {
+        Self { toi, entity }
+    }
+
+    /// Position of the obstacle along the line given as a fraction of the
+    /// line length. The obstacle lies at `a + toi * (b - a)`.
+    pub fn toi(&self) -> f32 {
+        self.toi
+    }
+
+    /// The entity blocking the line of sight or None if the line is blocked
+    /// by the terrain.
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+impl<'w, 's, Q, F> SpatialQuery<'w, 's, Q, F>
+where
+    Q: QueryData + Sync + Send + 'static,
+    F: QueryFilter + Sync + Send + 'static,
+{
+    /// Returns the first obstacle on the line segment between two points or
+    /// None if the line of sight is clear.
+    ///
+    /// Only queried entities block the line of sight, thus the query filter
+    /// selects possible blockers. The terrain always blocks the line of sight.
+    ///
+    /// # Arguments
+    ///
+    /// * `terrain` - terrain collider used for the terrain part of the test.
+    ///
+    /// * `a` - world-space position of the observer.
+    ///
+    /// * `b` - world-space position of the observed point.
+    ///
+    /// * `ignore` - if not None, this entity never blocks the line of sight.
+    ///   This is typically the observer.
+    pub fn line_of_sight(
+        &self,
+        terrain: &TerrainCollider,
+        a: Vec3,
+        b: Vec3,
+        ignore: Option<Entity>,
+    ) -> Option<SightObstacle> {
+        let ray = Ray::new(a.into(), (b - a).into());
+
+        // It is more efficient to calculate the terrain hit. Do it first so
+        // max_toi can be lowered in case of a hit.
+        let terrain_hit = terrain
+            .cast_ray(&ray, 1.)
+            .map(|intersection| SightObstacle::new(intersection.toi, None));
+
+        let max_toi = terrain_hit.map_or(1., |hit| hit.toi());
+        self.cast_ray(&ray, max_toi, ignore)
+            .map(|intersection| SightObstacle::new(intersection.toi(), Some(intersection.entity())))
+            .or(terrain_hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+    use de_map::size::MapBounds;
+    use de_objects::ObjectCollider;
+    use de_terrain::TerrainBundle;
+    use parry3d::{
+        math::{Isometry, Vector},
+        shape::{Cuboid, TriMesh, TriMeshFlags},
+    };
+
+    use super::*;
+    use crate::precise::{collider::LocalCollider, index::EntityIndex};
+
+    #[test]
+    fn test_line_of_sight() {
+        let mut world = World::new();
+        world.spawn(TerrainBundle::flat(MapBounds::new(Vec2::splat(100.))));
+
+        let observer = world.spawn_empty().id();
+        let blocker = world.spawn_empty().id();
+
+        let mut index = EntityIndex::new();
+        for (entity, x) in [(observer, 0.), (blocker, 10.)] {
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            index.insert(
+                entity,
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(x, 1., 0.), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+        world.insert_resource(index);
+
+        let mut state: SystemState<(SpatialQuery<Entity>, TerrainCollider)> =
+            SystemState::new(&mut world);
+        let (entities, terrain) = state.get(&world);
+
+        let a = Vec3::new(0., 1., 0.);
+        assert_eq!(
+            entities.line_of_sight(&terrain, a, Vec3::new(5., 1., 0.), Some(observer)),
+            None
+        );
+        let obstacle = entities
+            .line_of_sight(&terrain, a, Vec3::new(20., 1., 0.), Some(observer))
+            .unwrap();
+        assert_eq!(obstacle.entity(), Some(blocker));
+        assert!((obstacle.toi() - 0.45).abs() < 1e-5);
+
+        let obstacle = entities
+            .line_of_sight(&terrain, a, Vec3::new(0., -3., 20.), Some(observer))
+            .unwrap();
+        assert_eq!(obstacle.entity(), None);
+        assert!((obstacle.toi() - 0.25).abs() < 1e-5);
+
+        assert_eq!(
+            entities
+                .line_of_sight(&terrain, a, Vec3::new(5., 1., 0.), None)
+                .unwrap()
+                .entity(),
+            Some(observer)
+        );
+    }
+}
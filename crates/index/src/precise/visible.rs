@@ -0,0 +1,126 @@
+//! This module implements frustum geometry needed by queries of entities
+//! intersecting a view frustum.
+
+use bevy::{prelude::*, render::primitives::Frustum};
+use parry3d::bounding_volume::{Aabb, BoundingVolume};
+
+/// Returns AABB of the frustum corners or None if the frustum is unbounded
+/// or degenerate.
+pub(super) fn frustum_aabb(frustum: &Frustum) -> Option<Aabb> {
+    let mut aabb: Option<Aabb> = None;
+
+    for x in 0..2 {
+        for y in 2..4 {
+            for z in 4..6 {
+                let corner = intersect_planes([
+                    frustum.half_spaces[x].normal_d(),
+                    frustum.half_spaces[y].normal_d(),
+                    frustum.half_spaces[z].normal_d(),
+                ])?;
+                let corner = Aabb::new(corner.into(), corner.into());
+
+                aabb = Some(match aabb {
+                    Some(aabb) => aabb.merged(&corner),
+                    None => corner,
+                });
+            }
+        }
+    }
+
+    aabb
+}
+
+/// Returns the intersection point of three planes given as `normal.dot(point)
+/// + d = 0`, or None if the point does not exist or is not finite.
+fn intersect_planes(planes: [Vec4; 3]) -> Option<Vec3> {
+    let normals = Mat3::from_cols(
+        planes[0].truncate(),
+        planes[1].truncate(),
+        planes[2].truncate(),
+    )
+    .transpose();
+
+    if normals.determinant().abs() < 1e-6 {
+        return None;
+    }
+
+    let point = normals.inverse() * -Vec3::new(planes[0].w, planes[1].w, planes[2].w);
+    point.is_finite().then_some(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::AHashSet;
+    use bevy::render::camera::{CameraProjection, PerspectiveProjection};
+    use de_objects::ObjectCollider;
+    use parry3d::{
+        math::{Isometry, Point, Vector},
+        shape::{Cuboid, TriMesh, TriMeshFlags},
+    };
+
+    use super::*;
+    use crate::precise::{collider::LocalCollider, index::EntityIndex};
+
+    #[test]
+    fn test_intersect_planes() {
+        let point = intersect_planes([
+            Vec4::new(1., 0., 0., -2.),
+            Vec4::new(0., 1., 0., 3.),
+            Vec4::new(0., 0., -1., 4.),
+        ])
+        .unwrap();
+        assert_eq!(point, Vec3::new(2., -3., 4.));
+
+        assert!(intersect_planes([
+            Vec4::new(1., 0., 0., -2.),
+            Vec4::new(1., 0., 0., 3.),
+            Vec4::new(0., 0., 1., 4.),
+        ])
+        .is_none());
+    }
+
+    #[test]
+    fn test_query_frustum() {
+        let mut index = EntityIndex::new();
+        for (i, x) in [-200., -20., 0., 20., 200.].iter().enumerate() {
+            let mut trimesh: TriMesh = Cuboid::new(Vector::new(1., 1., 1.)).into();
+            trimesh.set_flags(TriMeshFlags::ORIENTED).unwrap();
+            index.insert(
+                Entity::from_raw(i as u32),
+                LocalCollider::new(
+                    ObjectCollider::from(trimesh),
+                    Isometry::new(Vector::new(*x, 0., 0.), Vector::new(0., 0., 0.)),
+                ),
+            );
+        }
+
+        // Camera 50m above origin, looking down.
+        let transform = Transform::from_xyz(0., 50., 0.).looking_at(Vec3::ZERO, -Vec3::Z);
+        let projection = PerspectiveProjection {
+            aspect_ratio: 1.,
+            far: 100.,
+            ..Default::default()
+        };
+        let view_projection =
+            projection.get_projection_matrix() * transform.compute_matrix().inverse();
+        let frustum = Frustum::from_view_projection_custom_far(
+            &view_projection,
+            &transform.translation,
+            &transform.back(),
+            projection.far,
+        );
+
+        let aabb = frustum_aabb(&frustum).unwrap();
+        assert!(aabb.contains_local_point(&Point::new(0., 0., 0.)));
+
+        let visible: AHashSet<Entity> = index.query_frustum(&frustum).collect();
+        assert_eq!(
+            visible,
+            AHashSet::from_iter(vec![
+                Entity::from_raw(1),
+                Entity::from_raw(2),
+                Entity::from_raw(3)
+            ])
+        );
+    }
+}
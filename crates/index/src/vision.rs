@@ -0,0 +1,223 @@
+//! This module keeps track of map areas seen by each player. It is a building
+//! block of fog of war and of AI decisions.
+//!
+//! Vision is tracked on the tile grid of the spatial index. An object with a
+//! [`SightRange`] reveals all tiles whose centers are within its sight range
+//! from the center of the tile the object is on. Each player has a count of
+//! observers for every tile. Contributions of objects are updated only when
+//! they cross a tile boundary, change owner or get despawned.
+
+use ahash::AHashMap;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::{
+    gamestate::GameState, gconfig::GameConfig, player::PlayerComponent, schedule::PostMovement,
+    state::AppState,
+};
+use de_objects::SightRange;
+use de_types::{
+    player::{Player, PlayerRange},
+    projection::ToFlat,
+};
+
+use crate::TILE_SIZE;
+
+pub(crate) struct VisionPlugin;
+
+impl Plugin for VisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostMovement,
+                (remove, update.after(remove))
+                    .run_if(in_state(GameState::Playing))
+                    .in_set(VisionSet::Update),
+            );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
+pub enum VisionSet {
+    Update,
+}
+
+/// Tiles seen by each player. Vision shared among allies is not taken into
+/// account, see [`PlayerVision`].
+#[derive(Resource, Default)]
+pub struct VisionSets {
+    /// Number of observers of each (seen) tile, the first item belongs to
+    /// [`Player::Player1`].
+    players: [AHashMap<IVec2, u32>; Player::MAX_PLAYERS],
+    /// Current contributions of all observers.
+    observers: AHashMap<Entity, Observer>,
+}
+
+impl VisionSets {
+    /// Returns true if a point is seen by at least one object of a player.
+    pub fn sees(&self, player: Player, point: Vec2) -> bool {
+        self.player(player).contains_key(&tile(point))
+    }
+
+    fn player(&self, player: Player) -> &AHashMap<IVec2, u32> {
+        &self.players[(player.to_num() - 1) as usize]
+    }
+
+    fn player_mut(&mut self, player: Player) -> &mut AHashMap<IVec2, u32> {
+        &mut self.players[(player.to_num() - 1) as usize]
+    }
+
+    /// Inserts or updates contribution of an observer.
+    fn update(&mut self, entity: Entity, observer: Observer) {
+        match self.observers.insert(entity, observer) {
+            Some(old) if old == observer => return,
+            Some(old) => self.remove_coverage(old),
+            None => (),
+        }
+
+        let tiles = self.player_mut(observer.player);
+        for tile in observer.coverage() {
+            *tiles.entry(tile).or_default() += 1;
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(old) = self.observers.remove(&entity) {
+            self.remove_coverage(old);
+        }
+    }
+
+    fn remove_coverage(&mut self, observer: Observer) {
+        let tiles = self.player_mut(observer.player);
+        for tile in observer.coverage() {
+            let count = tiles.get_mut(&tile).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                tiles.remove(&tile);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Observer {
+    player: Player,
+    tile: IVec2,
+    range: f32,
+}
+
+impl Observer {
+    /// Returns all tiles seen by the observer.
+    fn coverage(self) -> impl Iterator<Item = IVec2> {
+        let center = tile_center(self.tile);
+        let range_squared = self.range.powi(2);
+        let tiles = (self.range / TILE_SIZE).ceil() as i32;
+
+        (-tiles..=tiles)
+            .flat_map(move |x| (-tiles..=tiles).map(move |y| self.tile + IVec2::new(x, y)))
+            .filter(move |&tile| tile_center(tile).distance_squared(center) <= range_squared)
+    }
+}
+
+/// System parameter for queries of player vision which take vision shared
+/// among allied players into account.
+#[derive(SystemParam)]
+pub struct PlayerVision<'w> {
+    config: Res<'w, GameConfig>,
+    sets: Res<'w, VisionSets>,
+}
+
+impl<'w> PlayerVision<'w> {
+    /// Returns true if a point is visible to a player.
+    pub fn visible(&self, observer: Player, point: Vec2) -> bool {
+        PlayerRange::up_to(Player::Player4)
+            .filter(|&owner| self.config.shares_vision(observer, owner))
+            .any(|owner| self.sets.sees(owner, point))
+    }
+}
+
+fn tile(point: Vec2) -> IVec2 {
+    (point / TILE_SIZE).floor().as_ivec2()
+}
+
+fn tile_center(tile: IVec2) -> Vec2 {
+    (tile.as_vec2() + 0.5) * TILE_SIZE
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<VisionSets>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<VisionSets>();
+}
+
+type ObserverQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static PlayerComponent,
+        &'static SightRange,
+    ),
+    Or<(
+        Changed<Transform>,
+        Changed<PlayerComponent>,
+        Changed<SightRange>,
+    )>,
+>;
+
+fn update(mut sets: ResMut<VisionSets>, observers: ObserverQuery) {
+    for (entity, transform, &player, sight) in observers.iter() {
+        sets.update(
+            entity,
+            Observer {
+                player: *player,
+                tile: tile(transform.translation.to_flat()),
+                range: sight.range(),
+            },
+        );
+    }
+}
+
+fn remove(mut sets: ResMut<VisionSets>, mut removed: RemovedComponents<SightRange>) {
+    for entity in removed.read() {
+        sets.remove(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vision_sets() {
+        let mut sets = VisionSets::default();
+        let a = Entity::from_raw(1);
+        let b = Entity::from_raw(2);
+
+        let observer = |tile: IVec2| Observer {
+            player: Player::Player1,
+            tile,
+            range: 2. * TILE_SIZE,
+        };
+
+        sets.update(a, observer(IVec2::ZERO));
+        assert!(sets.sees(Player::Player1, Vec2::new(1., 1.)));
+        assert!(sets.sees(Player::Player1, Vec2::new(-15., 5.)));
+        assert!(sets.sees(Player::Player1, Vec2::new(25., 5.)));
+        assert!(!sets.sees(Player::Player1, Vec2::new(25., 25.)));
+        assert!(!sets.sees(Player::Player1, Vec2::new(35., 5.)));
+        assert!(!sets.sees(Player::Player2, Vec2::new(1., 1.)));
+
+        sets.update(b, observer(IVec2::new(1, 0)));
+        sets.update(a, observer(IVec2::new(10, 0)));
+        assert!(sets.sees(Player::Player1, Vec2::new(1., 1.)));
+        assert!(sets.sees(Player::Player1, Vec2::new(105., 5.)));
+
+        sets.remove(b);
+        assert!(!sets.sees(Player::Player1, Vec2::new(1., 1.)));
+        sets.remove(a);
+        assert!(sets.player(Player::Player1).is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    net::SocketAddr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{
+    gconfig::{GameConfig, LocalPlayers},
+    schedule::GameSchedulesPlugin,
+    state::AppState,
+};
+use de_gui::ToastEvent;
+use de_messages::Readiness;
+use de_multiplayer::{
+    GameJoinedEvent, GameOpenedEvent, GameReadinessEvent, MultiplayerPluginGroup,
+    NetRecvDespawnActiveEvent, NetRecvSpawnActiveEvent, NetRecvTransformEvent, NetState,
+    PeerJoinedEvent, PeerLeftEvent,
+};
+use de_types::{objects::ActiveObjectType, player::Player};
+use iyes_progress::prelude::*;
+
+/// Maximum time the clients are updated while waiting for a condition.
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Wall-clock time between two consecutive updates of the clients.
+const FRAME: Duration = Duration::from_millis(10);
+
+/// Updates all clients until a condition holds.
+///
+/// # Panics
+///
+/// Panics if the condition does not hold within [`TIMEOUT`].
+pub fn update_until<F>(clients: &mut [TestClient], mut condition: F)
+where
+    F: FnMut(&[TestClient]) -> bool,
+{
+    let deadline = Instant::now() + TIMEOUT;
+    while !condition(clients) {
+        assert!(
+            Instant::now() < deadline,
+            "Condition not met within {TIMEOUT:?}."
+        );
+
+        for client in clients.iter_mut() {
+            client.update();
+        }
+        thread::sleep(FRAME);
+    }
+}
+
+/// A game client without any rendering, input, audio or game-play plugins.
+/// It consists of the multiplayer plugins and the bare minimum they depend
+/// on. Received multiplayer events are recorded to [`Received`].
+pub struct TestClient {
+    app: App,
+}
+
+impl TestClient {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            GameSchedulesPlugin,
+            ProgressPlugin::new(AppState::AppLoading).continue_to(AppState::InMenu),
+            MultiplayerPluginGroup,
+        ))
+        .init_state::<AppState>()
+        .add_event::<ToastEvent>()
+        .init_resource::<Received>()
+        .add_systems(Update, (record_net, record_objects));
+
+        Self { app }
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    pub fn app_state(&self) -> AppState {
+        *self.app.world.resource::<State<AppState>>().get()
+    }
+
+    pub fn net_state(&self) -> NetState {
+        *self.app.world.resource::<State<NetState>>().get()
+    }
+
+    pub fn received(&self) -> &Received {
+        self.app.world.resource::<Received>()
+    }
+
+    /// Sends an event to the client app. The event is processed during the
+    /// next update.
+    pub fn send<E: Event>(&mut self, event: E) {
+        self.app.world.send_event(event);
+    }
+
+    /// Starts a multiplayer game with the local player being the only
+    /// locally simulated player.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no game was joined.
+    pub fn start_game(&mut self) {
+        let player = self.received().player().expect("No game was joined.");
+        self.app.insert_resource(GameConfig::new(
+            "test.dem.tar",
+            true,
+            LocalPlayers::from_single(player),
+        ));
+        self.app
+            .world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+    }
+}
+
+impl Default for TestClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Multiplayer state as observed by a [`TestClient`].
+#[derive(Resource, Default)]
+pub struct Received {
+    opened: Option<SocketAddr>,
+    player: Option<Player>,
+    peers: Vec<Player>,
+    readiness: Readiness,
+    objects: AHashMap<Entity, RemoteObject>,
+}
+
+impl Received {
+    /// Address of the game opened by the client.
+    pub fn opened(&self) -> Option<SocketAddr> {
+        self.opened
+    }
+
+    /// Player of the client in the joined game.
+    pub fn player(&self) -> Option<Player> {
+        self.player
+    }
+
+    /// Other players currently connected to the joined game.
+    pub fn peers(&self) -> &[Player] {
+        self.peers.as_slice()
+    }
+
+    /// Last received readiness of the joined game.
+    pub fn readiness(&self) -> Readiness {
+        self.readiness
+    }
+
+    /// All currently existing objects simulated by other players.
+    pub fn objects(&self) -> Vec<RemoteObject> {
+        self.objects.values().copied().collect()
+    }
+}
+
+/// An object simulated by another player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RemoteObject {
+    player: Player,
+    object_type: ActiveObjectType,
+    translation: Vec3,
+}
+
+impl RemoteObject {
+    pub fn new(player: Player, object_type: ActiveObjectType, translation: Vec3) -> Self {
+        Self {
+            player,
+            object_type,
+            translation,
+        }
+    }
+
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    pub fn object_type(&self) -> ActiveObjectType {
+        self.object_type
+    }
+
+    pub fn translation(&self) -> Vec3 {
+        self.translation
+    }
+}
+
+fn record_net(
+    mut received: ResMut<Received>,
+    mut opened: EventReader<GameOpenedEvent>,
+    mut joined: EventReader<GameJoinedEvent>,
+    mut peer_joined: EventReader<PeerJoinedEvent>,
+    mut peer_left: EventReader<PeerLeftEvent>,
+    mut readiness: EventReader<GameReadinessEvent>,
+) {
+    for event in opened.read() {
+        received.opened = Some(event.0);
+    }
+    for event in joined.read() {
+        received.player = Some(event.player());
+    }
+    for event in peer_joined.read() {
+        received.peers.push(event.id());
+    }
+    for event in peer_left.read() {
+        received.peers.retain(|&player| player != event.id());
+    }
+    for event in readiness.read() {
+        received.readiness = **event;
+    }
+}
+
+fn record_objects(
+    mut received: ResMut<Received>,
+    mut spawned: EventReader<NetRecvSpawnActiveEvent>,
+    mut transformed: EventReader<NetRecvTransformEvent>,
+    mut despawned: EventReader<NetRecvDespawnActiveEvent>,
+) {
+    for event in spawned.read() {
+        received.objects.insert(
+            event.entity(),
+            RemoteObject::new(
+                event.player(),
+                event.object_type(),
+                event.transform().translation,
+            ),
+        );
+    }
+    for event in transformed.read() {
+        if let Some(object) = received.objects.get_mut(&event.entity()) {
+            object.translation = event.transform().translation;
+        }
+    }
+    for event in despawned.read() {
+        received.objects.remove(&event.entity());
+    }
+}
@@ -0,0 +1,31 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    thread,
+    time::Duration,
+};
+
+use async_std::task;
+use de_connector_lib::serve;
+
+/// Time given to DE Connector to open its sockets.
+const STARTUP_DELAY: Duration = Duration::from_millis(200);
+
+/// Starts a DE Connector in a background task. The connector runs until the
+/// process exits.
+///
+/// # Arguments
+///
+/// * `port` - port of the main server. Each test must use a different port
+///   so that tests might run in parallel.
+///
+/// Returns address of the main server.
+pub fn start_connector(port: u16) -> SocketAddr {
+    task::spawn(async move {
+        if let Err(err) = serve(port).await {
+            panic!("DE Connector failed: {err:?}");
+        }
+    });
+    thread::sleep(STARTUP_DELAY);
+
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+}
@@ -0,0 +1,15 @@
+//! This crate implements a harness for integration tests of the whole
+//! multiplayer stack: `de_net`, `de_messages`, `de_multiplayer` and DE
+//! Connector.
+//!
+//! A DE Connector is started in-process via [`start_connector`] and game
+//! clients are represented by headless Bevy apps, see [`TestClient`]. The
+//! clients are driven with [`update_until`].
+
+pub use crate::{
+    client::{update_until, Received, RemoteObject, TestClient},
+    connector::start_connector,
+};
+
+mod client;
+mod connector;
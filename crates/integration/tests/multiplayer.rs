@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use de_core::state::AppState;
+use de_integration::{start_connector, update_until, RemoteObject, TestClient};
+use de_messages::{EntityNet, MapHashNet, Readiness, ToPlayers};
+use de_multiplayer::{
+    NetGameConf, NetState, SetReadinessEvent, ShutdownMultiplayerEvent, StartMultiplayerEvent,
+    ToPlayersEvent,
+};
+use de_types::{
+    objects::{ActiveObjectType, BuildingType, UnitType},
+    player::Player,
+};
+use ntest::timeout;
+
+const CONNECTOR_PORT: u16 = 8182;
+const MAP_HASH: [u8; 32] = [5; 32];
+
+#[test]
+#[timeout(60_000)]
+fn test_round() {
+    let connector = start_connector(CONNECTOR_PORT);
+
+    let mut clients = [TestClient::new(), TestClient::new()];
+    update_until(&mut clients, |clients| {
+        clients
+            .iter()
+            .all(|client| client.app_state() == AppState::InMenu)
+    });
+
+    clients[0].send(StartMultiplayerEvent::new(NetGameConf::create_game(
+        connector,
+        Player::Player2,
+        MapHashNet::from(MAP_HASH),
+    )));
+    update_until(&mut clients, |clients| {
+        clients[0].received().opened().is_some() && clients[0].received().player().is_some()
+    });
+    let game = clients[0].received().opened().unwrap();
+    let host = clients[0].received().player().unwrap();
+
+    clients[1].send(StartMultiplayerEvent::new(NetGameConf::join_game(
+        game,
+        MapHashNet::from(MAP_HASH),
+    )));
+    update_until(&mut clients, |clients| {
+        clients[1].received().player().is_some()
+    });
+    let guest = clients[1].received().player().unwrap();
+    assert_ne!(host, guest);
+    update_until(&mut clients, |clients| {
+        clients[0].received().peers().contains(&guest)
+    });
+
+    progress(&mut clients, Readiness::Ready);
+    for client in clients.iter_mut() {
+        client.start_game();
+    }
+    progress(&mut clients, Readiness::Prepared);
+    progress(&mut clients, Readiness::Initialized);
+
+    let mut host_objects = vec![
+        spawn(
+            &mut clients[0],
+            host,
+            1,
+            ActiveObjectType::Building(BuildingType::Base),
+            Vec3::new(10., 0., -20.),
+        ),
+        spawn(
+            &mut clients[0],
+            host,
+            2,
+            ActiveObjectType::Unit(UnitType::Attacker),
+            Vec3::new(15., 0., -20.),
+        ),
+    ];
+    let guest_objects = vec![spawn(
+        &mut clients[1],
+        guest,
+        1,
+        ActiveObjectType::Unit(UnitType::Scout),
+        Vec3::new(-30., 0., 40.),
+    )];
+    converge(&mut clients, &host_objects, &guest_objects);
+
+    host_objects[1] = transform(
+        &mut clients[0],
+        2,
+        host_objects[1],
+        Vec3::new(18., 0., -25.),
+    );
+    converge(&mut clients, &host_objects, &guest_objects);
+
+    clients[0].send(ToPlayersEvent::new(ToPlayers::Despawn {
+        entity: EntityNet::new(host, 1.into()),
+    }));
+    host_objects.remove(0);
+    converge(&mut clients, &host_objects, &guest_objects);
+
+    for client in clients.iter_mut() {
+        client.send(ShutdownMultiplayerEvent);
+    }
+    update_until(&mut clients, |clients| {
+        clients
+            .iter()
+            .all(|client| client.net_state() == NetState::None)
+    });
+}
+
+/// Progresses readiness of all clients and waits until the game readiness
+/// follows.
+fn progress(clients: &mut [TestClient], readiness: Readiness) {
+    for client in clients.iter_mut() {
+        client.send(SetReadinessEvent::from(readiness));
+    }
+    update_until(clients, |clients| {
+        clients
+            .iter()
+            .all(|client| client.received().readiness() == readiness)
+    });
+}
+
+fn spawn(
+    client: &mut TestClient,
+    player: Player,
+    index: u32,
+    object_type: ActiveObjectType,
+    translation: Vec3,
+) -> RemoteObject {
+    client.send(ToPlayersEvent::new(ToPlayers::Spawn {
+        entity: EntityNet::new(player, index.into()),
+        player,
+        object_type,
+        transform: Transform::from_translation(translation).into(),
+    }));
+    RemoteObject::new(player, object_type, translation)
+}
+
+fn transform(
+    client: &mut TestClient,
+    index: u32,
+    object: RemoteObject,
+    translation: Vec3,
+) -> RemoteObject {
+    client.send(ToPlayersEvent::new(ToPlayers::Transform {
+        entity: EntityNet::new(object.player(), index.into()),
+        transform: Transform::from_translation(translation).into(),
+    }));
+    RemoteObject::new(object.player(), object.object_type(), translation)
+}
+
+/// Waits until each client observes exactly the objects simulated by the
+/// other client.
+fn converge(clients: &mut [TestClient], host: &[RemoteObject], guest: &[RemoteObject]) {
+    update_until(clients, |clients| {
+        same(&clients[0].received().objects(), guest)
+            && same(&clients[1].received().objects(), host)
+    });
+}
+
+fn same(received: &[RemoteObject], expected: &[RemoteObject]) -> bool {
+    received.len() == expected.len() && expected.iter().all(|object| received.contains(object))
+}
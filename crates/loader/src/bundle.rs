@@ -0,0 +1,659 @@
+//! Asset bundles are TAR archives with maps, object definitions, models and
+//! icons distributed together, for example as a mod.
+//!
+//! Each bundle contains a manifest listing all bundled files with their
+//! hashes. Files of mounted bundles take precedence over files from the game
+//! assets directory.
+
+use std::{
+    fmt::Write as _,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ahash::AHashMap;
+use async_std::{
+    fs::{self, File, OpenOptions},
+    io::{ReadExt, WriteExt},
+    path::{Path as AsyncPath, PathBuf as AsyncPathBuf},
+    stream::StreamExt,
+};
+use async_tar::{Archive, Builder, EntryType, Header};
+use bevy::{
+    asset::io::{
+        AssetReader, AssetReaderError, AssetSource, AssetSourceId, PathStream, Reader, VecReader,
+    },
+    prelude::*,
+    tasks::IoTaskPool,
+    utils::BoxedFuture,
+};
+use de_core::{
+    fs::{bundles_dir, maps_dir},
+    log_full_error,
+    state::AppState,
+};
+use de_map::io::MAP_FILE_SUFFIX;
+use futures::{
+    channel::oneshot::{self, Canceled, Receiver, Sender},
+    future::Shared,
+    FutureExt,
+};
+use iyes_progress::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use thiserror::Error;
+
+/// Bundles are normally named with this suffix.
+pub const BUNDLE_FILE_SUFFIX: &str = ".debundle.tar";
+const MANIFEST_JSON_ENTRY: &str = "manifest.json";
+/// Top-level bundle directories. Files outside of these are not allowed.
+pub const BUNDLE_DIRECTORIES: [&str; 4] = ["maps", "objects", "models", "icons"];
+
+/// Mounts all bundles from the user bundles directory into the default asset
+/// source.
+///
+/// The bundles are loaded on the IO task pool during
+/// [`AppState::AppLoading`]. Reads from the default asset source wait until
+/// the bundles are mounted.
+///
+/// The plugin must be added before [`bevy::asset::AssetPlugin`] (i.e. before
+/// `DefaultPlugins`), otherwise it has no effect.
+pub struct BundlePlugin;
+
+impl Plugin for BundlePlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = oneshot::channel();
+        let files = receiver.shared();
+
+        app.insert_resource(MountSender(Some(sender)))
+            .insert_resource(MountedFiles(files.clone()))
+            .add_systems(OnEnter(AppState::AppLoading), start_mounting)
+            .add_systems(
+                Update,
+                check_status
+                    .track_progress()
+                    .run_if(in_state(AppState::AppLoading)),
+            )
+            .register_asset_source(
+                AssetSourceId::Default,
+                AssetSource::build().with_reader(move || {
+                    Box::new(BundleReader::new(
+                        AssetSource::get_default_reader("assets".to_owned())(),
+                        files.clone(),
+                    ))
+                }),
+            );
+    }
+}
+
+type BundleFiles = Arc<AHashMap<PathBuf, Vec<u8>>>;
+type SharedFiles = Shared<Receiver<BundleFiles>>;
+
+#[derive(Resource)]
+struct MountSender(Option<Sender<BundleFiles>>);
+
+#[derive(Resource)]
+struct MountedFiles(SharedFiles);
+
+/// Summary of mounted asset bundles. This resource is inserted once all
+/// bundles are mounted.
+#[derive(Resource)]
+pub struct MountedBundles {
+    overrides_objects: bool,
+}
+
+impl MountedBundles {
+    /// Returns true if a mounted bundle overrides object definitions. Object
+    /// definitions are not verified among players, therefore such bundles
+    /// cannot be used in multiplayer.
+    pub fn overrides_objects(&self) -> bool {
+        self.overrides_objects
+    }
+}
+
+fn start_mounting(mut sender: ResMut<MountSender>) {
+    let Some(sender) = sender.0.take() else {
+        return;
+    };
+
+    IoTaskPool::get()
+        .spawn(async move {
+            let files = Arc::new(mount_bundles().await);
+            // The receiver is kept by the asset reader for the whole
+            // application lifetime.
+            let _ = sender.send(files);
+        })
+        .detach();
+}
+
+fn check_status(
+    mut commands: Commands,
+    files: Res<MountedFiles>,
+    mounted: Option<Res<MountedBundles>>,
+) -> Progress {
+    if mounted.is_some() {
+        return true.into();
+    }
+    let Some(result) = files.0.clone().now_or_never() else {
+        return false.into();
+    };
+
+    let overrides_objects = result.map_or(false, |files| {
+        files.keys().any(|path| path.starts_with("objects"))
+    });
+    commands.insert_resource(MountedBundles { overrides_objects });
+    true.into()
+}
+
+/// Loads all valid bundles from the user bundles directory and returns their
+/// merged content. Bundled maps are copied to the user maps directory so that
+/// they are listed among other maps.
+async fn mount_bundles() -> AHashMap<PathBuf, Vec<u8>> {
+    let mut files = AHashMap::new();
+
+    let dir = match bundles_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            warn!("Bundles directory cannot be established: {err}");
+            return files;
+        }
+    };
+    if !dir.is_dir().await {
+        return files;
+    }
+
+    let mut dir_entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            log_full_error!(err);
+            return files;
+        }
+    };
+
+    let mut paths = Vec::new();
+    while let Some(entry) = dir_entries.next().await {
+        match entry {
+            Ok(entry) => {
+                let path = entry.path();
+                if path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map_or(false, |n| n.ends_with(BUNDLE_FILE_SUFFIX))
+                {
+                    paths.push(path);
+                }
+            }
+            Err(err) => {
+                log_full_error!(err);
+            }
+        }
+    }
+    // Make the overriding order deterministic.
+    paths.sort();
+
+    for path in paths {
+        let bundle = match load_bundle(path.as_path()).await {
+            Ok(bundle) => bundle,
+            Err(err) => {
+                warn!("Skipping invalid bundle {}: {err}", path.display());
+                continue;
+            }
+        };
+
+        info!(
+            "Mounting bundle {} ({})",
+            bundle.manifest().name(),
+            bundle.manifest().hash()
+        );
+        if let Err(err) = install_maps(&bundle).await {
+            log_full_error!(err);
+        }
+        files.extend(bundle.files);
+    }
+
+    files
+}
+
+async fn install_maps(bundle: &Bundle) -> io::Result<()> {
+    let target_dir = match maps_dir() {
+        Ok(dir) => dir,
+        Err(err) => {
+            warn!("User maps directory cannot be established: {err}");
+            return Ok(());
+        }
+    };
+
+    for (path, data) in bundle.files.iter() {
+        if !path.starts_with("maps") {
+            continue;
+        }
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+
+        let target = target_dir.join(file_name);
+        if target.exists().await {
+            continue;
+        }
+        fs::create_dir_all(&target_dir).await?;
+        fs::write(target, data).await?;
+    }
+
+    Ok(())
+}
+
+/// Asset reader which serves files from mounted bundles and falls back to
+/// another reader for all other files.
+struct BundleReader {
+    fallback: Box<dyn AssetReader>,
+    files: SharedFiles,
+}
+
+impl BundleReader {
+    fn new(fallback: Box<dyn AssetReader>, files: SharedFiles) -> Self {
+        Self { fallback, files }
+    }
+
+    /// Waits until the bundles are mounted and returns their files.
+    async fn files(&self) -> Result<BundleFiles, AssetReaderError> {
+        self.files.clone().await.map_err(|Canceled| {
+            AssetReaderError::Io(Arc::new(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "mounting of bundles was canceled",
+            )))
+        })
+    }
+}
+
+impl AssetReader for BundleReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move {
+            match self.files().await?.get(path) {
+                Some(data) => {
+                    let reader: Box<Reader<'a>> = Box::new(VecReader::new(data.clone()));
+                    Ok(reader)
+                }
+                None => self.fallback.read(path).await,
+            }
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        self.fallback.read_meta(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        self.fallback.read_directory(path)
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move {
+            if self
+                .files()
+                .await?
+                .keys()
+                .any(|file| file != path && file.starts_with(path))
+            {
+                Ok(true)
+            } else {
+                self.fallback.is_directory(path).await
+            }
+        })
+    }
+}
+
+/// Bundle manifest lists all bundled files together with their hashes.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BundleManifest {
+    name: String,
+    files: Vec<BundleFile>,
+    /// Hash of the whole bundle computed from paths and hashes of all files.
+    hash: String,
+}
+
+impl BundleManifest {
+    /// Creates a new manifest of bundle files.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - human readable name of the bundle.
+    ///
+    /// * `files` - paths (relative to bundle root) and content of all bundled
+    ///   files.
+    pub fn new<'a, I>(name: String, files: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a Path, &'a [u8])>,
+    {
+        let mut files: Vec<BundleFile> = files
+            .into_iter()
+            .map(|(path, data)| BundleFile {
+                path: path_to_entry(path),
+                hash: hash_hex(data),
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let hash = Self::compute_hash(&files);
+        Self { name, files, hash }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Hexadecimal hash of the whole bundle.
+    pub fn hash(&self) -> &str {
+        self.hash.as_str()
+    }
+
+    fn compute_hash(files: &[BundleFile]) -> String {
+        let mut hasher = Sha3_256::new();
+        for file in files {
+            hasher.update(file.path.as_bytes());
+            hasher.update([0]);
+            hasher.update(file.hash.as_bytes());
+            hasher.update([0]);
+        }
+        to_hex(hasher.finalize().as_slice())
+    }
+
+    /// Checks that the manifest corresponds to the bundled files.
+    fn verify(&self, files: &AHashMap<PathBuf, Vec<u8>>) -> Result<(), BundleLoadingError> {
+        if files.len() != self.files.len() {
+            return Err(BundleLoadingError::Manifest(format!(
+                "the manifest lists {} files but the bundle contains {} files",
+                self.files.len(),
+                files.len()
+            )));
+        }
+
+        for file in self.files.iter() {
+            let Some(data) = files.get(Path::new(file.path.as_str())) else {
+                return Err(BundleLoadingError::Manifest(format!(
+                    "{} is not present in the bundle",
+                    file.path
+                )));
+            };
+            if hash_hex(data) != file.hash {
+                return Err(BundleLoadingError::Manifest(format!(
+                    "hash of {} does not match",
+                    file.path
+                )));
+            }
+        }
+
+        if Self::compute_hash(&self.files) != self.hash {
+            return Err(BundleLoadingError::Manifest(String::from(
+                "bundle hash does not match",
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct BundleFile {
+    path: String,
+    hash: String,
+}
+
+/// A loaded and verified bundle.
+pub struct Bundle {
+    manifest: BundleManifest,
+    files: AHashMap<PathBuf, Vec<u8>>,
+}
+
+impl Bundle {
+    pub fn manifest(&self) -> &BundleManifest {
+        &self.manifest
+    }
+
+    /// Returns content of a bundled file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path of the file relative to bundle root.
+    pub fn file(&self, path: &Path) -> Option<&[u8]> {
+        self.files.get(path).map(|data| data.as_slice())
+    }
+}
+
+/// Loads a bundle TAR file and verifies it against its manifest.
+pub async fn load_bundle<P: AsRef<AsyncPath>>(path: P) -> Result<Bundle, BundleLoadingError> {
+    let mut file = File::open(path.as_ref()).await?;
+    let archive = Archive::new(&mut file);
+    let mut entries = archive.entries()?;
+
+    let mut manifest = None;
+    let mut files = AHashMap::new();
+
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        let path = entry.path()?.to_path_buf();
+        let Some(path_str) = path.to_str() else {
+            return Err(BundleLoadingError::ArchiveContent(String::from(
+                "the bundle contains an entry with non-UTF-8 path",
+            )));
+        };
+
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).await?;
+
+        if path_str == MANIFEST_JSON_ENTRY {
+            manifest = Some(serde_json::from_slice::<BundleManifest>(&data)?);
+        } else {
+            validate_entry_path(&path)?;
+            files.insert(PathBuf::from(path_str), data);
+        }
+    }
+
+    let Some(manifest) = manifest else {
+        return Err(BundleLoadingError::ArchiveContent(format!(
+            "{MANIFEST_JSON_ENTRY} entry is not present"
+        )));
+    };
+    manifest.verify(&files)?;
+
+    Ok(Bundle { manifest, files })
+}
+
+fn validate_entry_path(path: &AsyncPath) -> Result<(), BundleLoadingError> {
+    let top = path
+        .components()
+        .next()
+        .and_then(|component| component.as_os_str().to_str());
+    if !top.map_or(false, |top| BUNDLE_DIRECTORIES.contains(&top)) {
+        return Err(BundleLoadingError::ArchiveContent(format!(
+            "{} does not lie in any of the allowed directories",
+            path.display()
+        )));
+    }
+
+    if path.starts_with("maps")
+        && !path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map_or(false, |n| n.ends_with(MAP_FILE_SUFFIX))
+    {
+        return Err(BundleLoadingError::ArchiveContent(format!(
+            "{} is not a map file",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum BundleLoadingError {
+    #[error(transparent)]
+    Io {
+        #[from]
+        source: io::Error,
+    },
+    #[error("{0}")]
+    ArchiveContent(String),
+    #[error("bundle manifest JSON parsing error")]
+    JsonParsing {
+        #[from]
+        source: serde_json::Error,
+    },
+    #[error("invalid bundle manifest: {0}")]
+    Manifest(String),
+}
+
+/// Writes a bundle TAR file. Overwrites the file if it already exists.
+///
+/// # Arguments
+///
+/// * `name` - human readable name of the bundle.
+///
+/// * `files` - paths (relative to bundle root) and content of all files to
+///   be bundled.
+///
+/// * `path` - path of the bundle file.
+///
+/// # Returns
+///
+/// Manifest of the stored bundle.
+pub async fn store_bundle<P: AsRef<AsyncPath>>(
+    name: String,
+    files: &[(AsyncPathBuf, Vec<u8>)],
+    path: P,
+) -> Result<BundleManifest, BundleStoringError> {
+    for (file_path, _) in files {
+        if let Err(error) = validate_entry_path(file_path) {
+            return Err(BundleStoringError::InvalidPath(error.to_string()));
+        }
+    }
+
+    let manifest = BundleManifest::new(
+        name,
+        files
+            .iter()
+            .map(|(file_path, data)| (Path::new(file_path.as_os_str()), data.as_slice())),
+    );
+
+    let file = OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(path.as_ref())
+        .await?;
+    let mut archive = Builder::new(file);
+
+    let manifest_data = serde_json::to_vec_pretty(&manifest)?;
+    append_entry(
+        &mut archive,
+        AsyncPath::new(MANIFEST_JSON_ENTRY),
+        &manifest_data,
+    )
+    .await?;
+    for (file_path, data) in files {
+        append_entry(&mut archive, file_path, data).await?;
+    }
+
+    let mut file = archive.into_inner().await?;
+    file.flush().await?;
+
+    Ok(manifest)
+}
+
+async fn append_entry(
+    archive: &mut Builder<File>,
+    path: &AsyncPath,
+    data: &[u8],
+) -> io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Regular);
+    header.set_mode(0x400);
+    header.set_size(data.len().try_into().unwrap());
+    archive.append_data(&mut header, path, data).await
+}
+
+#[derive(Error, Debug)]
+pub enum BundleStoringError {
+    #[error(transparent)]
+    Io {
+        #[from]
+        source: io::Error,
+    },
+    #[error("{0}")]
+    InvalidPath(String),
+    #[error("bundle manifest JSON serialization error")]
+    JsonSerialization {
+        #[from]
+        source: serde_json::Error,
+    },
+}
+
+/// Converts a relative path to a TAR entry path, i.e. with `/` separators.
+fn path_to_entry(path: &Path) -> String {
+    let components: Vec<&str> = path
+        .components()
+        .map(|component| component.as_os_str().to_str().unwrap())
+        .collect();
+    components.join("/")
+}
+
+fn hash_hex(data: &[u8]) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(data);
+    to_hex(hasher.finalize().as_slice())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 * bytes.len());
+    for &byte in bytes {
+        write!(&mut hex, "{byte:02x}").unwrap();
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest() {
+        let files = [
+            (Path::new("objects/base.obj.json"), b"{}".as_slice()),
+            (Path::new("icons/base.png"), b"png".as_slice()),
+        ];
+        let manifest = BundleManifest::new("Test".to_owned(), files);
+        assert_eq!(manifest.name(), "Test");
+        assert_eq!(manifest.files[0].path, "icons/base.png");
+        assert_eq!(manifest.files[1].path, "objects/base.obj.json");
+        assert_eq!(manifest.hash().len(), 64);
+
+        let mut contents = AHashMap::new();
+        for (path, data) in files {
+            contents.insert(path.to_path_buf(), data.to_vec());
+        }
+        manifest.verify(&contents).unwrap();
+
+        contents.insert(PathBuf::from("icons/base.png"), b"gif".to_vec());
+        assert!(manifest.verify(&contents).is_err());
+    }
+
+    #[test]
+    fn test_validate_entry_path() {
+        assert!(validate_entry_path(AsyncPath::new("objects/base.obj.json")).is_ok());
+        assert!(validate_entry_path(AsyncPath::new("maps/a.dem.tar")).is_ok());
+        assert!(validate_entry_path(AsyncPath::new("maps/a.json")).is_err());
+        assert!(validate_entry_path(AsyncPath::new("shaders/a.wgsl")).is_err());
+    }
+}
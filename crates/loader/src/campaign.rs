@@ -0,0 +1,372 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use ahash::AHashSet;
+use async_std::{
+    fs,
+    path::{Path as AsyncPath, PathBuf as AsyncPathBuf},
+};
+use de_core::fs::{campaigns_dir, DirError};
+use de_types::player::Player;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Campaign definition files are named with this suffix.
+pub const CAMPAIGN_FILE_SUFFIX: &str = ".campaign.json";
+const MAX_ID_LEN: usize = 64;
+
+/// A campaign is an ordered sequence of scenarios (maps) which are unlocked
+/// gradually as the player progresses.
+#[derive(Deserialize)]
+pub struct Campaign {
+    id: String,
+    name: String,
+    scenarios: Vec<Scenario>,
+}
+
+impl Campaign {
+    /// Unique identifier of the campaign. It is used to persist the campaign
+    /// progress.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn scenarios(&self) -> &[Scenario] {
+        self.scenarios.as_slice()
+    }
+
+    /// Returns true if a scenario may be played.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the scenario.
+    ///
+    /// * `progress` - progress of the campaign.
+    pub fn is_unlocked(&self, index: usize, progress: &CampaignProgress) -> bool {
+        match self.scenarios[index].unlock() {
+            Unlock::Previous => index == 0 || progress.is_completed(self.scenarios[index - 1].id()),
+            Unlock::Always => true,
+            Unlock::Completed { scenario } => progress.is_completed(scenario),
+            Unlock::Flag { name } => progress.has_flag(name),
+        }
+    }
+
+    fn validate(&self) -> Result<(), CampaignValidationError> {
+        validate_id(&self.id)?;
+        if self.scenarios.is_empty() {
+            return Err(CampaignValidationError::NoScenarios);
+        }
+
+        let mut ids = AHashSet::new();
+        for scenario in &self.scenarios {
+            validate_id(scenario.id())?;
+            if let Unlock::Completed { scenario: required } = scenario.unlock() {
+                if !ids.contains(required.as_str()) {
+                    return Err(CampaignValidationError::UnknownScenario(required.clone()));
+                }
+            }
+            if !ids.insert(scenario.id()) {
+                return Err(CampaignValidationError::DuplicateScenario(
+                    scenario.id().to_owned(),
+                ));
+            }
+            if scenario.map.is_absolute() {
+                return Err(CampaignValidationError::AbsoluteMapPath(
+                    scenario.id().to_owned(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_id(id: &str) -> Result<(), CampaignValidationError> {
+    if id.is_empty()
+        || id.len() > MAX_ID_LEN
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(CampaignValidationError::InvalidId(id.to_owned()));
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+pub struct Scenario {
+    id: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    /// Path of the map relative to the assets directory.
+    map: PathBuf,
+    /// All players up to this one take part in the game. The first one is
+    /// played by the user, objects of the others are simulated locally but
+    /// nobody controls them.
+    max_player: Player,
+    #[serde(default)]
+    unlock: Unlock,
+    /// Flags persisted in the campaign progress once the scenario is won.
+    #[serde(default)]
+    carry_over: Vec<String>,
+}
+
+impl Scenario {
+    /// Identifier of the scenario, unique within its campaign.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    pub fn title(&self) -> &str {
+        self.title.as_str()
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    pub fn map(&self) -> &Path {
+        self.map.as_path()
+    }
+
+    pub fn max_player(&self) -> Player {
+        self.max_player
+    }
+
+    pub fn unlock(&self) -> &Unlock {
+        &self.unlock
+    }
+
+    pub fn carry_over(&self) -> &[String] {
+        self.carry_over.as_slice()
+    }
+}
+
+/// Condition under which a scenario becomes playable.
+#[derive(Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Unlock {
+    /// Unlocked once the preceding scenario is completed. The first scenario
+    /// is always unlocked.
+    #[default]
+    Previous,
+    Always,
+    /// Unlocked once a scenario (of the same campaign) is completed.
+    Completed {
+        scenario: String,
+    },
+    /// Unlocked once a flag is carried over from a completed scenario.
+    Flag {
+        name: String,
+    },
+}
+
+/// Persistent progress of the user in a campaign.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    completed: Vec<String>,
+    flags: Vec<String>,
+}
+
+impl CampaignProgress {
+    pub fn is_completed(&self, scenario: &str) -> bool {
+        self.completed.iter().any(|id| id == scenario)
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    /// Marks a scenario as completed and stores all its carry-over flags.
+    pub fn complete(&mut self, scenario: &Scenario) {
+        if !self.is_completed(scenario.id()) {
+            self.completed.push(scenario.id().to_owned());
+        }
+        for flag in scenario.carry_over() {
+            if !self.has_flag(flag) {
+                self.flags.push(flag.clone());
+            }
+        }
+    }
+}
+
+/// Loads and validates a campaign definition file.
+pub async fn load_campaign<P: AsRef<AsyncPath>>(path: P) -> Result<Campaign, CampaignLoadingError> {
+    let bytes = fs::read(path)
+        .await
+        .map_err(|source| CampaignLoadingError::Io { source })?;
+    let campaign: Campaign = serde_json::from_slice(&bytes)
+        .map_err(|source| CampaignLoadingError::JsonParsing { source })?;
+    campaign
+        .validate()
+        .map_err(|source| CampaignLoadingError::Validation { source })?;
+    Ok(campaign)
+}
+
+/// Loads progress of a campaign from the user data directory. Default (empty)
+/// progress is returned if the campaign has not been played yet.
+pub async fn load_progress(campaign: &Campaign) -> Result<CampaignProgress, ProgressError> {
+    let path = progress_path(campaign)?;
+    if !path.is_file().await {
+        return Ok(CampaignProgress::default());
+    }
+
+    let bytes = fs::read(path)
+        .await
+        .map_err(|source| ProgressError::Io { source })?;
+    serde_json::from_slice(&bytes).map_err(|source| ProgressError::Json { source })
+}
+
+/// Stores progress of a campaign to the user data directory.
+pub async fn store_progress(
+    campaign: &Campaign,
+    progress: &CampaignProgress,
+) -> Result<(), ProgressError> {
+    let path = progress_path(campaign)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|source| ProgressError::Io { source })?;
+    }
+
+    let bytes = serde_json::to_vec(progress).map_err(|source| ProgressError::Json { source })?;
+    fs::write(path, bytes)
+        .await
+        .map_err(|source| ProgressError::Io { source })
+}
+
+fn progress_path(campaign: &Campaign) -> Result<AsyncPathBuf, ProgressError> {
+    campaigns_dir()
+        .map(|dir| dir.join(format!("{}.json", campaign.id())))
+        .map_err(|source| ProgressError::Dir { source })
+}
+
+#[derive(Error, Debug)]
+pub enum CampaignLoadingError {
+    #[error(transparent)]
+    Io { source: io::Error },
+    #[error("campaign JSON parsing error")]
+    JsonParsing { source: serde_json::Error },
+    #[error("invalid campaign")]
+    Validation { source: CampaignValidationError },
+}
+
+#[derive(Error, Debug)]
+pub enum CampaignValidationError {
+    #[error("campaign has no scenarios")]
+    NoScenarios,
+    #[error("invalid ID \"{0}\", only ASCII alphanumeric characters, - and _ are allowed")]
+    InvalidId(String),
+    #[error("scenario \"{0}\" is defined more than once")]
+    DuplicateScenario(String),
+    #[error("scenario \"{0}\" is not defined before it is referenced")]
+    UnknownScenario(String),
+    #[error("map path of scenario \"{0}\" is absolute")]
+    AbsoluteMapPath(String),
+}
+
+#[derive(Error, Debug)]
+pub enum ProgressError {
+    #[error("campaign progress directory error")]
+    Dir { source: DirError },
+    #[error(transparent)]
+    Io { source: io::Error },
+    #[error("campaign progress JSON error")]
+    Json { source: serde_json::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn campaign(scenarios: &str) -> Campaign {
+        serde_json::from_str(&format!(
+            r#"{{"id": "test", "name": "Test", "scenarios": {scenarios}}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = campaign(
+            r#"[
+                {"id": "a", "title": "A", "map": "maps/a.dem.tar", "max_player": "Player2"},
+                {"id": "b", "title": "B", "map": "maps/b.dem.tar", "max_player": "Player2",
+                 "unlock": {"type": "completed", "scenario": "a"}}
+            ]"#,
+        );
+        assert!(valid.validate().is_ok());
+
+        assert!(matches!(
+            campaign("[]").validate(),
+            Err(CampaignValidationError::NoScenarios)
+        ));
+        assert!(matches!(
+            campaign(
+                r#"[
+                    {"id": "a", "title": "A", "map": "maps/a.dem.tar", "max_player": "Player2",
+                     "unlock": {"type": "completed", "scenario": "b"}},
+                    {"id": "b", "title": "B", "map": "maps/b.dem.tar", "max_player": "Player2"}
+                ]"#
+            )
+            .validate(),
+            Err(CampaignValidationError::UnknownScenario(_))
+        ));
+        assert!(matches!(
+            campaign(
+                r#"[
+                    {"id": "a", "title": "A", "map": "maps/a.dem.tar", "max_player": "Player2"},
+                    {"id": "a", "title": "B", "map": "maps/b.dem.tar", "max_player": "Player2"}
+                ]"#
+            )
+            .validate(),
+            Err(CampaignValidationError::DuplicateScenario(_))
+        ));
+        assert!(matches!(
+            campaign(
+                r#"[{"id": "a/b", "title": "A", "map": "maps/a.dem.tar", "max_player": "Player2"}]"#
+            )
+            .validate(),
+            Err(CampaignValidationError::InvalidId(_))
+        ));
+    }
+
+    #[test]
+    fn test_unlock() {
+        let campaign = campaign(
+            r#"[
+                {"id": "a", "title": "A", "map": "maps/a.dem.tar", "max_player": "Player2",
+                 "carry_over": ["veteran"]},
+                {"id": "b", "title": "B", "map": "maps/b.dem.tar", "max_player": "Player2"},
+                {"id": "c", "title": "C", "map": "maps/c.dem.tar", "max_player": "Player3",
+                 "unlock": {"type": "flag", "name": "veteran"}},
+                {"id": "d", "title": "D", "map": "maps/d.dem.tar", "max_player": "Player2",
+                 "unlock": {"type": "always"}}
+            ]"#,
+        );
+
+        let mut progress = CampaignProgress::default();
+        let unlocked = |progress: &CampaignProgress| -> Vec<bool> {
+            (0..4)
+                .map(|index| campaign.is_unlocked(index, progress))
+                .collect()
+        };
+        assert_eq!(unlocked(&progress), vec![true, false, false, true]);
+
+        progress.complete(&campaign.scenarios()[0]);
+        assert!(progress.is_completed("a"));
+        assert!(progress.has_flag("veteran"));
+        assert_eq!(unlocked(&progress), vec![true, true, true, true]);
+
+        progress.complete(&campaign.scenarios()[0]);
+        assert_eq!(progress.completed.len(), 1);
+        assert_eq!(progress.flags.len(), 1);
+    }
+}
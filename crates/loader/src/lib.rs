@@ -1,9 +1,16 @@
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 use map::MapLoaderPlugin;
 use readiness::ReadinessPlugin;
+use script::ScriptPlugin;
+pub use script::{Objective, Objectives};
+pub use validation::diagnose_map;
 
+pub mod bundle;
+pub mod campaign;
 mod map;
 mod readiness;
+mod script;
+mod validation;
 
 pub struct LoaderPluginGroup;
 
@@ -12,5 +19,6 @@ impl PluginGroup for LoaderPluginGroup {
         PluginGroupBuilder::start::<Self>()
             .add(MapLoaderPlugin)
             .add(ReadinessPlugin)
+            .add(ScriptPlugin)
     }
 }
@@ -1,3 +1,6 @@
+use std::f32::consts::TAU;
+
+use ahash::AHashMap;
 use bevy::{
     prelude::*,
     tasks::{futures_lite::future, IoTaskPool, Task},
@@ -5,19 +8,34 @@ use bevy::{
 use de_camera::MoveFocusEvent;
 use de_core::{
     assets::asset_path, cleanup::DespawnOnGameExit, gamestate::GameState, gconfig::GameConfig,
-    log_full_error, state::AppState,
+    gresult::GameResult, log_full_error, state::AppState,
 };
+use de_gui::ToastEvent;
 use de_map::{
     content::InnerObject,
-    io::{load_map, MapLoadingError},
+    diagnostics::{MapReport, Severity},
+    io::{load_map_unvalidated, MapLoadingError},
     map::Map,
+    meta::{DayNightCycle, MapMetadata},
     size::MapBounds,
 };
+use de_objects::{Ichnography, SolidObjects};
 use de_spawner::{SpawnInactiveEvent, SpawnLocalActiveEvent, SpawnerSet};
 use de_terrain::TerrainBundle;
-use de_types::objects::{ActiveObjectType, BuildingType};
+use de_types::{
+    objects::{ActiveObjectType, BuildingType, ObjectType},
+    player::Player,
+    projection::{ToAltitude, ToFlat},
+};
 use iyes_progress::prelude::*;
 
+use crate::{script::Objectives, validation::diagnose_map};
+
+/// Gap in meters between a base and the closest ring of starting units.
+const STARTING_UNITS_GAP: f32 = 8.;
+/// Distance in meters between neighbouring starting units.
+const STARTING_UNITS_SPACING: f32 = 6.;
+
 pub(crate) struct MapLoaderPlugin;
 
 impl Plugin for MapLoaderPlugin {
@@ -34,29 +52,46 @@ impl Plugin for MapLoaderPlugin {
     }
 }
 
+/// The task loads and diagnoses the map.
 #[derive(Resource)]
-struct MapLoadingTask(Task<Result<Map, MapLoadingError>>);
+struct MapLoadingTask(Task<Result<(Map, MapReport), MapLoadingError>>);
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<MapLoadingTask>();
     commands.remove_resource::<MapBounds>();
+    commands.remove_resource::<MapMetadata>();
+    commands.remove_resource::<DayNightCycle>();
 }
 
-fn load_map_system(mut commands: Commands, game_config: Res<GameConfig>) {
+fn load_map_system(mut commands: Commands, game_config: Res<GameConfig>, solids: SolidObjects) {
     let map_path = if game_config.map_path().is_relative() {
         asset_path(game_config.map_path())
     } else {
         game_config.map_path().to_owned()
     };
 
+    // Ichnographies are copied so that the (potentially slow) map
+    // diagnostics run in the task rather than on the main thread.
+    let ichnographies: AHashMap<ObjectType, Ichnography> = enum_iterator::all::<ObjectType>()
+        .map(|object_type| (object_type, solids.get(object_type).ichnography().clone()))
+        .collect();
+
     info!("Loading map from {}", map_path.display());
-    let task = IoTaskPool::get().spawn(async { load_map(map_path).await });
+    let task = IoTaskPool::get().spawn(async move {
+        let map = load_map_unvalidated(map_path).await?;
+        let report = diagnose_map(&map, |object_type| &ichnographies[&object_type]);
+        Ok::<_, MapLoadingError>((map, report))
+    });
     commands.insert_resource(MapLoadingTask(task));
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_map(
     mut commands: Commands,
     task: Option<ResMut<MapLoadingTask>>,
+    solids: SolidObjects,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut toasts: EventWriter<ToastEvent>,
     mut move_focus_events: EventWriter<MoveFocusEvent>,
     mut spawn_active_events: EventWriter<SpawnLocalActiveEvent>,
     mut spawn_inactive_events: EventWriter<SpawnInactiveEvent>,
@@ -75,14 +110,32 @@ fn spawn_map(
     info!("Map loaded, spawning");
     commands.remove_resource::<MapLoadingTask>();
 
-    let map = match loading_result {
-        Ok(map) => map,
+    let (map, report) = match loading_result {
+        Ok(result) => result,
         Err(err) => {
             log_full_error!(err);
             panic!("{}", err);
         }
     };
 
+    for issue in report.issues() {
+        match issue.severity() {
+            Severity::Warning => warn!("Map {issue}"),
+            Severity::Error => error!("Map {issue}"),
+        }
+    }
+    if !report.is_valid() {
+        commands.insert_resource(GameResult::error(format!("Invalid map:\n{report}")));
+        next_state.set(AppState::InMenu);
+        return true.into();
+    }
+    if !report.is_empty() {
+        toasts.send(ToastEvent::new(format!(
+            "The map has {} problem(s), see the log for details.",
+            report.issues().len()
+        )));
+    }
+
     let initial_focus = map
         .content()
         .objects()
@@ -110,6 +163,16 @@ fn spawn_map(
         DespawnOnGameExit,
     ));
 
+    let bounds = map.metadata().bounds();
+    let base_radius = solids
+        .get(ObjectType::Active(ActiveObjectType::Building(
+            BuildingType::Base,
+        )))
+        .ichnography()
+        .radius();
+    // Starting units are spawned only around the first base of each player.
+    let mut equipped: Vec<Player> = Vec::new();
+
     let locals = game_config.locals();
     for object in map.content().objects() {
         let transform = object.placement().to_transform();
@@ -126,6 +189,24 @@ fn spawn_map(
                     transform,
                     player,
                 ));
+
+                if object.object_type() != ActiveObjectType::Building(BuildingType::Base)
+                    || equipped.contains(&player)
+                {
+                    continue;
+                }
+                equipped.push(player);
+
+                let units = game_config.starting(player).units();
+                let positions = starting_positions(transform.translation.to_flat(), base_radius)
+                    .filter(|&position| bounds.contains(position));
+                for (&unit_type, position) in units.iter().zip(positions) {
+                    spawn_active_events.send(SpawnLocalActiveEvent::stationary(
+                        ActiveObjectType::Unit(unit_type),
+                        Transform::from_translation(position.to_msl()),
+                        player,
+                    ));
+                }
             }
             InnerObject::Inactive(object) => {
                 spawn_inactive_events
@@ -135,9 +216,34 @@ fn spawn_map(
     }
 
     commands.insert_resource(map.metadata().bounds());
+    commands.insert_resource(map.metadata().clone());
+    commands.insert_resource(map.script().clone());
+    commands.init_resource::<Objectives>();
+    if let Some(day_night) = map.metadata().day_night() {
+        commands.insert_resource(day_night);
+    }
     true.into()
 }
 
+/// Returns an infinite sequence of positions of starting units. The units are
+/// placed on concentric rings around a base.
+///
+/// # Arguments
+///
+/// * `center` - flat position of the base.
+///
+/// * `radius` - radius of the base.
+fn starting_positions(center: Vec2, radius: f32) -> impl Iterator<Item = Vec2> {
+    (0..).flat_map(move |ring| {
+        let distance = radius + STARTING_UNITS_GAP + ring as f32 * STARTING_UNITS_SPACING;
+        let count = ((TAU * distance) / STARTING_UNITS_SPACING).floor().max(1.) as u32;
+        (0..count).map(move |i| {
+            let angle = TAU * i as f32 / count as f32;
+            center + distance * Vec2::from_angle(angle)
+        })
+    })
+}
+
 fn setup_light(commands: &mut Commands) {
     commands.insert_resource(AmbientLight {
         color: Color::WHITE,
@@ -161,3 +267,23 @@ fn setup_light(commands: &mut Commands) {
         DespawnOnGameExit,
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starting_positions() {
+        let center = Vec2::new(10., -20.);
+        let positions: Vec<Vec2> = starting_positions(center, 5.).take(100).collect();
+        assert_eq!(positions.len(), 100);
+        assert!((positions[0] - Vec2::new(23., -20.)).length() < 1e-4);
+
+        for (i, a) in positions.iter().enumerate() {
+            assert!(a.distance(center) >= 13. - 1e-4);
+            for b in &positions[i + 1..] {
+                assert!(a.distance(*b) >= STARTING_UNITS_SPACING - 1e-3);
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use de_camera::MoveFocusEvent;
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    gresult::GameResult,
+    objects::{Active, MovableSolid},
+    player::PlayerComponent,
+    state::AppState,
+};
+use de_map::{
+    script::{Action, Area, Condition, MapScript, ObjectiveStatus},
+    size::MapBounds,
+};
+use de_pathing::{PathQueryProps, PathTarget};
+use de_spawner::{
+    DespawnEventsPlugin, DespawnedComponentsEvent, DespawnerSet, SpawnLocalActiveEvent,
+};
+use de_types::{
+    objects::ActiveObjectType,
+    player::Player,
+    projection::{ToAltitude, ToFlat},
+};
+
+/// Distance in meters between neighbouring units of a spawned wave.
+const WAVE_SPACING: f32 = 4.;
+
+pub(crate) struct ScriptPlugin;
+
+impl Plugin for ScriptPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(DespawnEventsPlugin::<PlayerComponent, With<MovableSolid>>::default())
+            .add_event::<FireTriggerEvent>()
+            .add_systems(OnEnter(GameState::Playing), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    count_destroyed.after(DespawnerSet::Events),
+                    evaluate.after(count_destroyed),
+                    execute.after(evaluate),
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+/// Objectives of the current scenario set by the map script. The objectives
+/// are kept in the order of their creation.
+#[derive(Resource, Default)]
+pub struct Objectives(Vec<Objective>);
+
+impl Objectives {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Objective> {
+        self.0.iter()
+    }
+
+    /// Inserts a new objective or updates the objective with the same ID.
+    fn set(&mut self, id: u32, description: &str, status: ObjectiveStatus) {
+        let objective = Objective {
+            id,
+            description: description.to_owned(),
+            status,
+        };
+
+        match self.0.iter_mut().find(|objective| objective.id == id) {
+            Some(current) => *current = objective,
+            None => self.0.push(objective),
+        }
+    }
+}
+
+pub struct Objective {
+    id: u32,
+    description: String,
+    status: ObjectiveStatus,
+}
+
+impl Objective {
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    pub fn status(&self) -> ObjectiveStatus {
+        self.status
+    }
+}
+
+/// Progress of the map script execution.
+#[derive(Resource)]
+struct ScriptState {
+    /// Game time elapsed since the start of the game.
+    elapsed: f32,
+    /// Number of destroyed units of individual players.
+    destroyed: [u32; Player::MAX_PLAYERS],
+    /// Whether individual triggers of the script already fired.
+    fired: Vec<bool>,
+}
+
+impl ScriptState {
+    fn new(triggers: usize) -> Self {
+        Self {
+            elapsed: 0.,
+            destroyed: [0; Player::MAX_PLAYERS],
+            fired: vec![false; triggers],
+        }
+    }
+
+    fn destroyed_mut(&mut self, player: Player) -> &mut u32 {
+        &mut self.destroyed[(player.to_num() - 1) as usize]
+    }
+
+    /// Returns true if the condition is met.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - the condition to be evaluated.
+    ///
+    /// * `entered` - returns true if any object of a player is in an area.
+    fn is_met<F>(&self, condition: &Condition, entered: F) -> bool
+    where
+        F: Fn(Player, &Area) -> bool,
+    {
+        match condition {
+            Condition::Time { seconds } => self.elapsed >= *seconds,
+            Condition::AreaEntered { player, area } => entered(*player, area),
+            Condition::UnitDestroyed { player, count } => {
+                self.destroyed[(player.to_num() - 1) as usize] >= *count
+            }
+        }
+    }
+}
+
+/// Send this event to execute actions of a trigger with the given index.
+#[derive(Event)]
+struct FireTriggerEvent(usize);
+
+fn setup(mut commands: Commands, script: Res<MapScript>) {
+    commands.insert_resource(ScriptState::new(script.triggers().len()));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<MapScript>();
+    commands.remove_resource::<ScriptState>();
+    commands.remove_resource::<Objectives>();
+}
+
+fn count_destroyed(
+    mut state: ResMut<ScriptState>,
+    mut events: EventReader<DespawnedComponentsEvent<PlayerComponent>>,
+) {
+    for event in events.read() {
+        *state.destroyed_mut(*event.data) += 1;
+    }
+}
+
+fn evaluate(
+    time: Res<Time>,
+    script: Res<MapScript>,
+    mut state: ResMut<ScriptState>,
+    objects: Query<(&PlayerComponent, &Transform), With<Active>>,
+    mut events: EventWriter<FireTriggerEvent>,
+) {
+    state.elapsed += time.delta_seconds();
+
+    let entered = |player: Player, area: &Area| {
+        objects.iter().any(|(&object_player, transform)| {
+            *object_player == player && area.contains(transform.translation.to_flat())
+        })
+    };
+
+    for (index, trigger) in script.triggers().iter().enumerate() {
+        if state.fired[index] || !state.is_met(trigger.condition(), entered) {
+            continue;
+        }
+
+        state.fired[index] = true;
+        events.send(FireTriggerEvent(index));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    script: Res<MapScript>,
+    bounds: Res<MapBounds>,
+    mut objectives: ResMut<Objectives>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut events: EventReader<FireTriggerEvent>,
+    mut spawn_events: EventWriter<SpawnLocalActiveEvent>,
+    mut focus_events: EventWriter<MoveFocusEvent>,
+) {
+    for event in events.read() {
+        for action in script.triggers()[event.0].actions() {
+            match action {
+                Action::SpawnWave {
+                    player,
+                    units,
+                    position,
+                    target,
+                } => {
+                    // Each game instance simulates only its own players.
+                    if !config.locals().is_local(*player) {
+                        continue;
+                    }
+
+                    let path_target = target.map(|target| {
+                        PathTarget::new(target, PathQueryProps::new(0., f32::INFINITY), false)
+                    });
+                    for (&unit, point) in units.iter().zip(wave_positions(*position, units.len())) {
+                        let point = point.clamp(bounds.min(), bounds.max());
+                        spawn_events.send(SpawnLocalActiveEvent::new(
+                            ActiveObjectType::Unit(unit),
+                            Transform::from_translation(point.to_msl()),
+                            *player,
+                            path_target,
+                        ));
+                    }
+                }
+                Action::RevealArea { area } => {
+                    focus_events.send(MoveFocusEvent::new(area.center()));
+                }
+                Action::SetObjective {
+                    id,
+                    description,
+                    status,
+                } => {
+                    objectives.set(*id, description, *status);
+                }
+                Action::EndGame { won } => {
+                    info!("Game ended by the map script.");
+                    commands.insert_resource(GameResult::finished(*won));
+                    next_state.set(AppState::InMenu);
+                }
+            }
+        }
+    }
+}
+
+/// Returns positions of `count` units of a wave arranged in a square grid
+/// centered at `center`.
+fn wave_positions(center: Vec2, count: usize) -> impl Iterator<Item = Vec2> {
+    let columns = (count as f32).sqrt().ceil().max(1.) as usize;
+    let rows = count.div_ceil(columns);
+    let offset = 0.5 * WAVE_SPACING * Vec2::new((columns - 1) as f32, (rows - 1) as f32);
+
+    (0..count).map(move |index| {
+        let cell = Vec2::new((index % columns) as f32, (index / columns) as f32);
+        center - offset + WAVE_SPACING * cell
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_objectives() {
+        let mut objectives = Objectives::default();
+        assert!(objectives.is_empty());
+
+        objectives.set(2, "Build a base.", ObjectiveStatus::Active);
+        objectives.set(1, "Survive.", ObjectiveStatus::Active);
+        objectives.set(2, "Build a base.", ObjectiveStatus::Completed);
+
+        let statuses: Vec<(&str, ObjectiveStatus)> = objectives
+            .iter()
+            .map(|objective| (objective.description(), objective.status()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("Build a base.", ObjectiveStatus::Completed),
+                ("Survive.", ObjectiveStatus::Active),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_met() {
+        let mut state = ScriptState::new(3);
+        let area = Area::new(Vec2::ZERO, 10.);
+        let nobody = |_: Player, _: &Area| false;
+
+        let time = Condition::Time { seconds: 5. };
+        assert!(!state.is_met(&time, nobody));
+        state.elapsed = 5.;
+        assert!(state.is_met(&time, nobody));
+
+        let destroyed = Condition::UnitDestroyed {
+            player: Player::Player2,
+            count: 2,
+        };
+        *state.destroyed_mut(Player::Player2) += 1;
+        *state.destroyed_mut(Player::Player1) += 1;
+        assert!(!state.is_met(&destroyed, nobody));
+        *state.destroyed_mut(Player::Player2) += 1;
+        assert!(state.is_met(&destroyed, nobody));
+
+        let entered = Condition::AreaEntered {
+            player: Player::Player1,
+            area,
+        };
+        assert!(!state.is_met(&entered, nobody));
+        assert!(state.is_met(&entered, |player, _| player == Player::Player1));
+    }
+
+    #[test]
+    fn test_wave_positions() {
+        let positions: Vec<Vec2> = wave_positions(Vec2::new(10., 20.), 1).collect();
+        assert_eq!(positions, vec![Vec2::new(10., 20.)]);
+
+        let positions: Vec<Vec2> = wave_positions(Vec2::ZERO, 4).collect();
+        assert_eq!(
+            positions,
+            vec![
+                Vec2::new(-2., -2.),
+                Vec2::new(2., -2.),
+                Vec2::new(-2., 2.),
+                Vec2::new(2., 2.),
+            ]
+        );
+
+        let positions: Vec<Vec2> = wave_positions(Vec2::ZERO, 5).collect();
+        assert_eq!(positions.len(), 5);
+        let center = positions.iter().sum::<Vec2>() / 5.;
+        assert!(center.length() < WAVE_SPACING);
+    }
+}
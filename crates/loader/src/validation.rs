@@ -0,0 +1,340 @@
+use bevy::prelude::*;
+use de_map::{
+    content::InnerObject,
+    diagnostics::{Issue, IssueKind, MapReport, Severity},
+    map::Map,
+};
+use de_objects::{Ichnography, EXCLUSION_OFFSET};
+use de_pathing::{create_finder, ExclusionArea, PathQueryProps, PathTarget};
+use de_types::{
+    objects::{ActiveObjectType, BuildingType, ObjectType},
+    player::Player,
+    projection::ToFlat,
+};
+use glam::EulerRot;
+use parry2d::{
+    math::{Isometry, Point},
+    query,
+    shape::ConvexPolygon,
+};
+
+/// Thoroughly validates a map and returns a report with all found problems.
+///
+/// On top of [`Map::diagnose`], it checks that objects do not overlap, that
+/// they lie entirely inside the map and that starting positions of all
+/// players are mutually reachable. These checks are skipped if the map has
+/// any basic validation errors.
+///
+/// # Arguments
+///
+/// * `map` - map to be validated.
+///
+/// * `ichnography` - returns ichnography of an object type.
+pub fn diagnose_map<'a, F>(map: &Map, ichnography: F) -> MapReport
+where
+    F: Fn(ObjectType) -> &'a Ichnography,
+{
+    let mut report = map.diagnose();
+    if !report.is_valid() {
+        return report;
+    }
+
+    let footprints = Footprints::new(map, ichnography);
+    check_bounds(map, &footprints, &mut report);
+    check_overlaps(&footprints, &mut report);
+    check_reachability(map, &footprints, &mut report);
+    report
+}
+
+/// Ground footprints of all map objects in map coordinates.
+struct Footprints<'a> {
+    objects: Vec<Footprint<'a>>,
+}
+
+struct Footprint<'a> {
+    object_type: ObjectType,
+    transform: Transform,
+    ichnography: &'a Ichnography,
+    polygon: ConvexPolygon,
+}
+
+impl<'a> Footprints<'a> {
+    fn new<F>(map: &Map, ichnography: F) -> Self
+    where
+        F: Fn(ObjectType) -> &'a Ichnography,
+    {
+        let objects = map
+            .content()
+            .objects()
+            .iter()
+            .map(|object| {
+                let object_type = match object.inner() {
+                    InnerObject::Active(object) => ObjectType::Active(object.object_type()),
+                    InnerObject::Inactive(object) => ObjectType::Inactive(object.object_type()),
+                };
+                let transform = object.placement().to_transform();
+                let ichnography = ichnography(object_type);
+
+                let angle = transform.rotation.to_euler(EulerRot::YXZ).0;
+                let isometry = Isometry::new(transform.translation.to_flat().into(), angle);
+                let points: Vec<Point<f32>> = ichnography
+                    .convex_hull()
+                    .points()
+                    .iter()
+                    .map(|&point| isometry * point)
+                    .collect();
+
+                Footprint {
+                    object_type,
+                    transform,
+                    ichnography,
+                    polygon: ConvexPolygon::from_convex_polyline(points).unwrap(),
+                }
+            })
+            .collect();
+
+        Self { objects }
+    }
+}
+
+fn check_bounds(map: &Map, footprints: &Footprints, report: &mut MapReport) {
+    let bounds = map.metadata().bounds();
+
+    for (index, footprint) in footprints.objects.iter().enumerate() {
+        let outside = footprint
+            .polygon
+            .points()
+            .iter()
+            .any(|&point| !bounds.contains(Vec2::from(point)));
+
+        if outside {
+            report.push(Issue::new(
+                Severity::Warning,
+                IssueKind::OutOfBounds,
+                format!(
+                    "{} at objects[{index}] partially lies outside of the map",
+                    footprint.object_type
+                ),
+                vec![index],
+            ));
+        }
+    }
+}
+
+fn check_overlaps(footprints: &Footprints, report: &mut MapReport) {
+    let footprints = &footprints.objects;
+
+    for (a, footprint_a) in footprints.iter().enumerate() {
+        let aabb_a = footprint_a.polygon.local_aabb();
+
+        for (b, footprint_b) in footprints.iter().enumerate().skip(a + 1) {
+            let aabb_b = footprint_b.polygon.local_aabb();
+            if aabb_a.mins.x > aabb_b.maxs.x
+                || aabb_b.mins.x > aabb_a.maxs.x
+                || aabb_a.mins.y > aabb_b.maxs.y
+                || aabb_b.mins.y > aabb_a.maxs.y
+            {
+                continue;
+            }
+
+            let overlap = query::intersection_test(
+                &Isometry::identity(),
+                &footprint_a.polygon,
+                &Isometry::identity(),
+                &footprint_b.polygon,
+            )
+            .unwrap();
+
+            if overlap {
+                report.push(Issue::new(
+                    Severity::Warning,
+                    IssueKind::Overlap,
+                    format!(
+                        "{} at objects[{a}] overlaps with {} at objects[{b}]",
+                        footprint_a.object_type, footprint_b.object_type
+                    ),
+                    vec![a, b],
+                ));
+            }
+        }
+    }
+}
+
+/// Checks that base of each player can be reached from bases of all other
+/// players with the path finding triangulation.
+fn check_reachability(map: &Map, footprints: &Footprints, report: &mut MapReport) {
+    let mut starts: Vec<(Player, usize)> = Vec::new();
+    for (index, object) in map.content().objects().iter().enumerate() {
+        let InnerObject::Active(object) = object.inner() else {
+            continue;
+        };
+        if object.object_type() != ActiveObjectType::Building(BuildingType::Base) {
+            continue;
+        }
+        if starts.iter().all(|&(player, _)| player != object.player()) {
+            starts.push((object.player(), index));
+        }
+    }
+
+    if starts.len() < 2 {
+        return;
+    }
+
+    let exclusions = footprints
+        .objects
+        .iter()
+        .filter(|footprint| {
+            !matches!(
+                footprint.object_type,
+                ObjectType::Active(ActiveObjectType::Unit(_))
+            )
+        })
+        .map(|footprint| {
+            ExclusionArea::from_ichnography(&footprint.transform, footprint.ichnography)
+        })
+        .collect();
+    let finder = create_finder(map.metadata().bounds(), exclusions);
+
+    for &(source_player, source) in starts.iter() {
+        let from = footprints.objects[source].transform.translation.to_flat();
+
+        for &(target_player, target) in starts.iter() {
+            if target == source {
+                continue;
+            }
+
+            let footprint = &footprints.objects[target];
+            // The target base itself is not accessible, thus its exclusion
+            // area boundary is sufficient.
+            let max_distance = footprint.ichnography.radius() + 2. * EXCLUSION_OFFSET;
+            let path_target = PathTarget::new(
+                footprint.transform.translation.to_flat(),
+                PathQueryProps::new(0., max_distance),
+                false,
+            );
+
+            if finder.find_path(from, path_target).is_none() {
+                report.push(Issue::new(
+                    Severity::Warning,
+                    IssueKind::Unreachable,
+                    format!(
+                        "starting position of {target_player} cannot be reached \
+                        from starting position of {source_player}"
+                    ),
+                    vec![source, target],
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use de_map::{
+        content::{ActiveObject, InactiveObject, Object},
+        meta::MapMetadata,
+        size::MapBounds,
+    };
+    use de_types::objects::InactiveObjectType;
+
+    use super::*;
+
+    fn square(half_size: f32) -> Ichnography {
+        ConvexPolygon::from_convex_polyline(vec![
+            Point::new(-half_size, -half_size),
+            Point::new(half_size, -half_size),
+            Point::new(half_size, half_size),
+            Point::new(-half_size, half_size),
+        ])
+        .unwrap()
+        .into()
+    }
+
+    fn base(map: &Map, position: Vec2, player: Player) -> Object {
+        Object::new(
+            map.new_placement(position, 0.),
+            InnerObject::Active(ActiveObject::new(
+                ActiveObjectType::Building(BuildingType::Base),
+                player,
+            )),
+        )
+    }
+
+    fn tree(map: &Map, position: Vec2) -> Object {
+        Object::new(
+            map.new_placement(position, 0.),
+            InnerObject::Inactive(InactiveObject::new(InactiveObjectType::Tree)),
+        )
+    }
+
+    #[test]
+    fn test_diagnose_map() {
+        let base_ichnography = square(10.);
+        let tree_ichnography = square(2.);
+        let ichnography = |object_type: ObjectType| match object_type {
+            ObjectType::Inactive(_) => &tree_ichnography,
+            ObjectType::Active(_) => &base_ichnography,
+        };
+
+        let mut map = Map::empty(MapMetadata::new(
+            "Test Map".into(),
+            MapBounds::new(Vec2::new(200., 200.)),
+            Player::Player2,
+        ));
+        map.insert_object(base(&map, Vec2::new(-50., 0.), Player::Player1));
+        map.insert_object(base(&map, Vec2::new(50., 0.), Player::Player2));
+        assert!(diagnose_map(&map, ichnography).is_empty());
+
+        map.insert_object(tree(&map, Vec2::new(-50., 11.)));
+        map.insert_object(tree(&map, Vec2::new(0., 99.)));
+        let report = diagnose_map(&map, ichnography);
+        let kinds: Vec<(IssueKind, &[usize])> = report
+            .issues()
+            .iter()
+            .map(|issue| (issue.kind(), issue.objects()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (IssueKind::OutOfBounds, [3].as_slice()),
+                (IssueKind::Overlap, [0, 2].as_slice()),
+            ]
+        );
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let base_ichnography = square(10.);
+        let wall_ichnography: Ichnography = ConvexPolygon::from_convex_polyline(vec![
+            Point::new(-5., -100.),
+            Point::new(5., -100.),
+            Point::new(5., 100.),
+            Point::new(-5., 100.),
+        ])
+        .unwrap()
+        .into();
+        let ichnography = |object_type: ObjectType| match object_type {
+            ObjectType::Inactive(_) => &wall_ichnography,
+            ObjectType::Active(_) => &base_ichnography,
+        };
+
+        let mut map = Map::empty(MapMetadata::new(
+            "Test Map".into(),
+            MapBounds::new(Vec2::new(200., 200.)),
+            Player::Player2,
+        ));
+        map.insert_object(base(&map, Vec2::new(-50., 0.), Player::Player1));
+        map.insert_object(base(&map, Vec2::new(50., 0.), Player::Player2));
+        map.insert_object(tree(&map, Vec2::ZERO));
+
+        let report = diagnose_map(&map, ichnography);
+        let unreachable: Vec<&[usize]> = report
+            .issues()
+            .iter()
+            .filter(|issue| issue.kind() == IssueKind::Unreachable)
+            .map(|issue| issue.objects())
+            .collect();
+        assert_eq!(unreachable, vec![[0, 1].as_slice(), [1, 0].as_slice()]);
+    }
+}
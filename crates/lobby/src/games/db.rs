@@ -105,11 +105,12 @@ impl Games {
         let mut transaction = self.pool.begin().await.map_err(CreationError::Database)?;
 
         let result =
-            query("INSERT INTO games (name, max_players, map_hash, map_name, server) VALUES(?, ?, ?, ?, ?);")
+            query("INSERT INTO games (name, max_players, map_hash, map_name, starting_resources, server) VALUES(?, ?, ?, ?, ?, ?);")
                 .bind(game_config.name())
                 .bind(game_config.max_players())
                 .bind(game_config.map().hash())
                 .bind(game_config.map().name())
+                .bind(game_config.starting_resources())
                 .bind(game_setup.server().to_string())
                 .execute(&mut transaction)
                 .await;
@@ -334,8 +335,9 @@ impl FromRow for GameConfig {
     fn try_from_row(row: SqliteRow) -> Result<Self, Self::Error> {
         let name: String = row.try_get("name")?;
         let max_players: u8 = row.try_get("max_players")?;
+        let starting_resources: u32 = row.try_get("starting_resources")?;
         let map = GameMap::try_from_row(row)?;
-        Ok(Self::new(name, max_players, map))
+        Ok(Self::new(name, max_players, map).with_starting_resources(starting_resources))
     }
 }
 
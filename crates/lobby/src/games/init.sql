@@ -3,6 +3,7 @@ CREATE TABLE IF NOT EXISTS games (
     max_players TINYINT NOT NULL,
     map_hash CHARACTER({map_hash_len}) NOT NULL,
     map_name CHARACTER({map_name_len}) NOT NULL,
+    starting_resources INTEGER NOT NULL DEFAULT 0,
     server CHARACTER({server_len}) NOT NULL
 );
 
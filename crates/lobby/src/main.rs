@@ -3,12 +3,14 @@ use anyhow::{Context, Result};
 use auth::{Auth, AuthMiddlewareFactory};
 use games::GamesService;
 use log::info;
+use reports::ReportsService;
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
 
 mod auth;
 mod conf;
 mod db;
 mod games;
+mod reports;
 
 const JSON_PAYLOAD_LIMIT: usize = 10 * 1024;
 const DB_URL_VAR_NAME: &str = "DE_DB_URL";
@@ -42,12 +44,14 @@ async fn main() -> std::io::Result<()> {
     let db_pool = handle_error!(db_pool().await);
     let auth = handle_error!(Auth::setup(db_pool).await);
     let games = handle_error!(GamesService::setup(db_pool).await);
+    let reports = handle_error!(ReportsService::setup(db_pool).await);
 
     HttpServer::new(move || {
         let public_scope = web::scope("/p").configure(|c| auth.configure_public(c));
         let authenticated_scope = web::scope("/a")
             .wrap(AuthMiddlewareFactory)
-            .configure(|c| games.configure(c));
+            .configure(|c| games.configure(c))
+            .configure(|c| reports.configure(c));
 
         App::new()
             .wrap(Logger::default())
@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use de_lobby_model::{UserReport, MAX_REPORT_REASON_LEN, MAX_USERNAME_LEN};
+use log::info;
+use sqlx::{query, Pool, Sqlite};
+use thiserror::Error;
+
+use crate::{db::SQLITE_CONSTRAINT_FOREIGNKEY, db_error_code};
+
+#[derive(Clone)]
+pub(super) struct Reports {
+    pool: &'static Pool<Sqlite>,
+}
+
+impl Reports {
+    /// This method sets up the database by creating required tables if they do
+    /// not already exist.
+    ///
+    /// It is supposed users were already setup.
+    pub(super) async fn init(pool: &'static Pool<Sqlite>) -> Result<Self> {
+        let init_query = format!(
+            include_str!("init.sql"),
+            username_len = MAX_USERNAME_LEN,
+            reason_len = MAX_REPORT_REASON_LEN,
+        );
+
+        info!("Initializing reports...");
+        query(&init_query)
+            .execute(pool)
+            .await
+            .context("DB initialization failed")?;
+        Ok(Self { pool })
+    }
+
+    /// This method stores a new report of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `reporter` - username of the reporting user.
+    ///
+    /// * `report` - the report itself.
+    pub(super) async fn create(
+        &self,
+        reporter: &str,
+        report: &UserReport,
+    ) -> Result<(), ReportError> {
+        let result = query("INSERT INTO reports (reporter, reported, reason) VALUES (?, ?, ?);")
+            .bind(reporter)
+            .bind(report.user().username())
+            .bind(report.reason())
+            .execute(self.pool)
+            .await;
+
+        db_error_code!(
+            result,
+            ReportError::UserDoesNotExist,
+            SQLITE_CONSTRAINT_FOREIGNKEY
+        );
+        result.map_err(ReportError::Database)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub(super) enum ReportError {
+    #[error("The reported user does not exist")]
+    UserDoesNotExist,
+    #[error("A database error encountered")]
+    Database(#[source] sqlx::Error),
+}
@@ -0,0 +1,48 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use de_lobby_model::{UserReport, Validatable};
+use log::{error, info, warn};
+
+use super::db::{ReportError, Reports};
+use crate::auth::Claims;
+
+/// Registers all report endpoints.
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/reports").service(create));
+}
+
+#[post("")]
+async fn create(
+    claims: web::ReqData<Claims>,
+    reports: web::Data<Reports>,
+    report: web::Json<UserReport>,
+) -> impl Responder {
+    let report = report.into_inner();
+    if let Err(error) = report.validate() {
+        warn!("Invalid user report: {:?}", error);
+        return HttpResponse::BadRequest().json(format!("{error}"));
+    }
+
+    if report.user().username() == claims.username() {
+        warn!("User report error: a user reported themself.");
+        return HttpResponse::BadRequest().json("Users cannot report themselves.");
+    }
+
+    match reports.create(claims.username(), &report).await {
+        Ok(_) => {
+            info!(
+                "User {} reported user {}.",
+                claims.username(),
+                report.user().username()
+            );
+            HttpResponse::Ok().json(())
+        }
+        Err(ReportError::UserDoesNotExist) => {
+            warn!("User report error: the reported user does not exist.");
+            HttpResponse::NotFound().json("User not found.")
+        }
+        Err(error) => {
+            error!("User report error: {:?}", error);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
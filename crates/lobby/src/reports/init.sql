@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reporter CHARACTER({username_len}) NOT NULL,
+    reported CHARACTER({username_len}) NOT NULL,
+    reason CHARACTER({reason_len}) NOT NULL,
+    created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY(reporter) REFERENCES users(username)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY(reported) REFERENCES users(username)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
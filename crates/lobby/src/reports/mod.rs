@@ -0,0 +1,32 @@
+use actix_web::web;
+use anyhow::{Context, Result};
+use sqlx::{Pool, Sqlite};
+
+use self::db::Reports;
+
+mod db;
+mod endpoints;
+
+#[derive(Clone)]
+pub struct ReportsService {
+    reports: Reports,
+}
+
+impl ReportsService {
+    /// Setup reports DB and endpoints.
+    ///
+    /// This should be called after [`crate::auth::Auth`].
+    pub async fn setup(pool: &'static Pool<Sqlite>) -> Result<Self> {
+        Ok(Self {
+            reports: db::Reports::init(pool)
+                .await
+                .context("Failed to initialize reports")?,
+        })
+    }
+
+    /// Configure actix-web application.
+    pub fn configure(&self, cfg: &mut web::ServiceConfig) {
+        cfg.app_data(web::Data::new(self.reports.clone()));
+        endpoints::configure(cfg);
+    }
+}
@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 
 use de_lobby_model::{
-    Game, GameListing, GamePlayerInfo, GameSetup, Token, UserWithPassword, UsernameAndPassword,
+    Game, GameListing, GamePlayerInfo, GameSetup, Token, UserReport, UserWithPassword,
+    UsernameAndPassword,
 };
 use reqwest::{header::HeaderValue, Method, Request};
 use serde::Serialize;
@@ -168,6 +169,30 @@ impl LobbyRequestCreator for LeaveGameRequest {
     }
 }
 
+pub struct ReportUserRequest(UserReport);
+
+impl ReportUserRequest {
+    pub fn new(report: UserReport) -> Self {
+        Self(report)
+    }
+}
+
+impl LobbyRequest for ReportUserRequest {
+    type Response = ();
+}
+
+impl LobbyRequestCreator for ReportUserRequest {
+    fn path(&self) -> Cow<str> {
+        "/a/reports".into()
+    }
+
+    fn create(&self, url: Url) -> Request {
+        let mut request = Request::new(Method::POST, url);
+        json(&mut request, &self.0);
+        request
+    }
+}
+
 fn json<T: Serialize>(request: &mut Request, content: &T) {
     request.headers_mut().insert(
         "Content-Type",
@@ -254,12 +279,28 @@ mod tests {
             r#"{"server":"127.0.0.1:8082","config":{"name":"Druhá Hra","maxPlayers":2,"#,
             r#""map":{"hash":"#,
             r#""0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef","#,
-            r#""name":"custom"}}}"#
+            r#""name":"custom"},"startingResources":0}}"#
         );
 
         assert_eq!(body, expected_body);
     }
 
+    #[test]
+    fn test_report_user() {
+        let request = ReportUserRequest::new(UserReport::new(
+            User::new("Indy".to_owned()),
+            "Insults in chat.".to_owned(),
+        ));
+        assert_eq!(request.path().as_ref(), "/a/reports");
+
+        let request = request.create(Url::parse("http://example.com/a/reports").unwrap());
+        assert_eq!(request.method().as_str(), "POST");
+
+        let body = String::from_utf8(request.body().unwrap().as_bytes().unwrap().to_vec()).unwrap();
+        let expected_body = r#"{"user":{"username":"Indy"},"reason":"Insults in chat."}"#;
+        assert_eq!(body, expected_body);
+    }
+
     #[test]
     fn test_join() {
         let request = JoinGameRequest::new("Cool Game".to_owned(), GamePlayerInfo::new(2));
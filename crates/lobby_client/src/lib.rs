@@ -37,5 +37,6 @@ impl PluginGroup for LobbyClientPluginGroup {
             .add(EndpointPlugin::<GetGameRequest>::default())
             .add(EndpointPlugin::<JoinGameRequest>::default())
             .add(EndpointPlugin::<LeaveGameRequest>::default())
+            .add(EndpointPlugin::<ReportUserRequest>::default())
     }
 }
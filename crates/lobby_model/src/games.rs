@@ -8,6 +8,7 @@ pub const MAX_GAME_NAME_LEN: usize = 32;
 pub const MAX_MAP_NAME_LEN: usize = 32;
 pub const MAP_HASH_LEN: usize = 64;
 const MAX_PLAYERS: u8 = 4;
+const MAX_STARTING_RESOURCES: u32 = 1_000_000;
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -152,6 +153,8 @@ pub struct GameConfig {
     name: String,
     max_players: u8,
     map: GameMap,
+    #[serde(default)]
+    starting_resources: u32,
 }
 
 impl GameConfig {
@@ -160,9 +163,17 @@ impl GameConfig {
             name,
             max_players,
             map,
+            starting_resources: 0,
         }
     }
 
+    /// Sets amount of resources each player has in stock at the start of
+    /// the game. By default, players start with no resources.
+    pub fn with_starting_resources(mut self, resources: u32) -> Self {
+        self.starting_resources = resources;
+        self
+    }
+
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
@@ -174,6 +185,10 @@ impl GameConfig {
     pub fn map(&self) -> &GameMap {
         &self.map
     }
+
+    pub fn starting_resources(&self) -> u32 {
+        self.starting_resources
+    }
 }
 
 impl validation::Validatable for GameConfig {
@@ -199,6 +214,11 @@ impl validation::Validatable for GameConfig {
             "Maximum number of players must be at most {}.",
             MAX_PLAYERS
         );
+        ensure!(
+            self.starting_resources <= MAX_STARTING_RESOURCES,
+            "Starting resources must be at most {}.",
+            MAX_STARTING_RESOURCES
+        );
         self.map.validate()
     }
 }
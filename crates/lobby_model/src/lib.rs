@@ -6,8 +6,10 @@ pub use games::{
     Game, GameConfig, GameListing, GameMap, GamePartial, GamePlayer, GamePlayerInfo, GameSetup,
     MAP_HASH_LEN, MAX_GAME_NAME_LEN, MAX_MAP_NAME_LEN,
 };
+pub use reports::{UserReport, MAX_REPORT_REASON_LEN};
 pub use validation::Validatable;
 
 mod auth;
 mod games;
+mod reports;
 mod validation;
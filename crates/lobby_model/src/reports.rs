@@ -0,0 +1,83 @@
+//! User reports related API objects.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ensure,
+    validation::{self, Validatable},
+    User,
+};
+
+pub const MAX_REPORT_REASON_LEN: usize = 512;
+
+/// A report of a misbehaving user, for example of abusive chat messages.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserReport {
+    user: User,
+    reason: String,
+}
+
+impl UserReport {
+    /// # Arguments
+    ///
+    /// * `user` - the reported user.
+    ///
+    /// * `reason` - a short user provided description of the misbehavior.
+    pub fn new(user: User, reason: String) -> Self {
+        Self { user, reason }
+    }
+
+    /// The reported user.
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn reason(&self) -> &str {
+        self.reason.as_str()
+    }
+}
+
+impl Validatable for UserReport {
+    fn validate(&self) -> validation::Result {
+        self.user.validate()?;
+
+        ensure!(
+            !self.reason.trim().is_empty(),
+            "Report reason must not be empty."
+        );
+        ensure!(
+            self.reason.len() <= MAX_REPORT_REASON_LEN,
+            "Report reason has {} bytes, which is more than the limit of {} bytes.",
+            self.reason.len(),
+            MAX_REPORT_REASON_LEN
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_report() {
+        let report =
+            |reason: &str| UserReport::new(User::new("Indy".to_owned()), reason.to_owned());
+
+        assert!(report("Insults in chat.").validate().is_ok());
+        assert_eq!(
+            report("  ").validate().err().unwrap().to_string(),
+            "Report reason must not be empty."
+        );
+        assert_eq!(
+            report(&"x".repeat(513))
+                .validate()
+                .err()
+                .unwrap()
+                .to_string(),
+            "Report reason has 513 bytes, which is more than the limit of 512 bytes."
+        );
+    }
+}
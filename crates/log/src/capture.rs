@@ -0,0 +1,190 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::Resource;
+use chrono::{DateTime, Local};
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+/// Maximum number of retained log records. The oldest records are dropped
+/// once this is exceeded.
+const CAPACITY: usize = 512;
+
+/// Most recent log records captured from the tracing subscriber.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct CapturedLogs(Arc<Mutex<Captured>>);
+
+#[derive(Default)]
+struct Captured {
+    records: VecDeque<LogRecord>,
+    /// Total number of records captured so far.
+    revision: u64,
+}
+
+impl CapturedLogs {
+    /// Returns the number of records captured so far. It can be used to
+    /// detect newly captured records.
+    pub(crate) fn revision(&self) -> u64 {
+        self.0.lock().map_or(0, |captured| captured.revision)
+    }
+
+    /// Returns up to `max` most recent records accepted by a filter, the
+    /// oldest record first.
+    pub(crate) fn latest<F>(&self, max: usize, filter: F) -> Vec<LogRecord>
+    where
+        F: Fn(&LogRecord) -> bool,
+    {
+        let Ok(captured) = self.0.lock() else {
+            return Vec::new();
+        };
+
+        let mut records: Vec<LogRecord> = captured
+            .records
+            .iter()
+            .rev()
+            .filter(|record| filter(record))
+            .take(max)
+            .cloned()
+            .collect();
+        records.reverse();
+        records
+    }
+
+    fn push(&self, record: LogRecord) {
+        // Logging must not panic even if another thread panicked while
+        // holding the lock.
+        let Ok(mut captured) = self.0.lock() else {
+            return;
+        };
+        if captured.records.len() >= CAPACITY {
+            captured.records.pop_front();
+        }
+        captured.records.push_back(record);
+        captured.revision += 1;
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct LogRecord {
+    time: DateTime<Local>,
+    level: Level,
+    target: String,
+    message: String,
+}
+
+impl LogRecord {
+    pub(crate) fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Target of the event, usually the module path of its origin.
+    pub(crate) fn target(&self) -> &str {
+        self.target.as_str()
+    }
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:>5} {}: {}",
+            self.time.format("%H:%M:%S"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+/// Tracing layer storing all events into [`CapturedLogs`].
+pub(crate) struct CaptureLayer(CapturedLogs);
+
+impl CaptureLayer {
+    pub(crate) fn new(logs: CapturedLogs) -> Self {
+        Self(logs)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.0.push(LogRecord {
+            time: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_owned(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        self.message.push_str(self.fields.as_str());
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::subscriber::with_default;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry().with(CaptureLayer::new(logs.clone()));
+
+        with_default(subscriber, || {
+            tracing::warn!(target: "de_test::a", "first {}", 1);
+            tracing::error!(target: "de_test::b", code = 42, "second");
+            tracing::info!(target: "de_test::a", "third");
+        });
+
+        assert_eq!(logs.revision(), 3);
+
+        let records = logs.latest(10, |record| record.level() <= Level::WARN);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].message, "first 1");
+        assert_eq!(records[1].message, "second code=42");
+        assert_eq!(records[1].target(), "de_test::b");
+
+        let records = logs.latest(1, |record| record.target().starts_with("de_test::a"));
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].message, "third");
+    }
+}
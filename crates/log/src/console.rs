@@ -0,0 +1,288 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+};
+use de_core::{
+    cleanup::SurvivesGameExit,
+    command::{register_command, CommandEvent, CommandInfo, RunCommandEvent},
+};
+use de_gui::{
+    BodyTextCommands, BodyTextOps, ButtonCommands, ButtonOps, CapturesKeyboard, GuiCommands,
+    OuterStyle, SetFocusEvent, TextBoxCommands, TextBoxOps, TextBoxQuery, TextProps, ToastEvent,
+};
+use tracing::Level;
+
+use crate::capture::CapturedLogs;
+
+/// Key toggling the log console overlay.
+const TOGGLE_KEY: KeyCode = KeyCode::F12;
+/// Key submitting a command typed to the console.
+const SUBMIT_KEY: KeyCode = KeyCode::Enter;
+/// Maximum number of log records displayed at once.
+const MAX_LINES: usize = 30;
+const FPS_COMMAND: CommandInfo = CommandInfo::new("fps", &[], "Show frames per second.");
+
+pub(crate) struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        register_command(app, FPS_COMMAND);
+
+        app.init_resource::<ConsoleFilter>().add_systems(
+            Update,
+            (
+                toggle
+                    .run_if(input_just_pressed(TOGGLE_KEY))
+                    .run_if(resource_exists::<TextProps>)
+                    .run_if(resource_exists::<CapturedLogs>),
+                (level_button, update_text)
+                    .chain()
+                    .run_if(resource_exists::<Console>)
+                    .after(toggle),
+                submit
+                    .run_if(input_just_pressed(SUBMIT_KEY))
+                    .run_if(resource_exists::<Console>)
+                    .after(toggle),
+                fps_command.run_if(on_event::<CommandEvent>()),
+            ),
+        );
+    }
+}
+
+/// Log records displayed in the console.
+#[derive(Resource)]
+struct ConsoleFilter {
+    /// Only records of this or higher severity are displayed.
+    level: Level,
+    /// Revision of captured logs displayed the last time.
+    revision: Option<u64>,
+    /// Module filter displayed the last time.
+    module: String,
+}
+
+impl Default for ConsoleFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::WARN,
+            revision: None,
+            module: String::new(),
+        }
+    }
+}
+
+impl ConsoleFilter {
+    /// Sets the next (more verbose) level, wrapping around from the most
+    /// verbose level to the least verbose one.
+    fn cycle_level(&mut self) {
+        self.level = match self.level {
+            Level::ERROR => Level::WARN,
+            Level::WARN => Level::INFO,
+            Level::INFO => Level::DEBUG,
+            Level::DEBUG => Level::TRACE,
+            Level::TRACE => Level::ERROR,
+        };
+        self.revision = None;
+    }
+}
+
+#[derive(Resource)]
+struct Console {
+    root: Entity,
+    level_button: Entity,
+    module_box: Entity,
+    text: Entity,
+    command_box: Entity,
+}
+
+fn level_caption(level: Level) -> String {
+    format!("Level: {level}")
+}
+
+fn toggle(
+    mut commands: GuiCommands,
+    console: Option<Res<Console>>,
+    mut filter: ResMut<ConsoleFilter>,
+    mut focus_events: EventWriter<SetFocusEvent>,
+) {
+    if let Some(console) = console {
+        commands.entity(console.root).despawn_recursive();
+        commands.remove_resource::<Console>();
+        return;
+    }
+
+    // Make sure that the text is filled in the newly spawned console.
+    filter.revision = None;
+
+    let root = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    left: Val::Percent(1.),
+                    right: Val::Percent(30.),
+                    top: Val::Percent(1.),
+                    bottom: Val::Percent(40.),
+                    padding: UiRect::all(Val::Percent(0.5)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.8).into(),
+                z_index: ZIndex::Global(20000),
+                ..default()
+            },
+            SurvivesGameExit,
+            CapturesKeyboard,
+        ))
+        .id();
+
+    let header = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                width: Val::Percent(100.),
+                height: Val::Percent(10.),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(root).add_child(header);
+
+    let level_button = commands
+        .spawn_button(
+            OuterStyle {
+                width: Val::Percent(30.),
+                height: Val::Percent(100.),
+                margin: UiRect::right(Val::Percent(2.)),
+            },
+            level_caption(filter.level),
+        )
+        .id();
+    commands.entity(header).add_child(level_button);
+
+    let module_box = commands
+        .spawn_text_box(
+            OuterStyle {
+                width: Val::Percent(68.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            false,
+        )
+        .id();
+    commands.entity(header).add_child(module_box);
+
+    let text = commands
+        .spawn_body_text(
+            OuterStyle {
+                width: Val::Percent(100.),
+                height: Val::Percent(76.),
+                margin: UiRect::top(Val::Percent(2.)),
+            },
+            "",
+        )
+        .id();
+    commands.entity(root).add_child(text);
+
+    let command_box = commands
+        .spawn_text_box(
+            OuterStyle {
+                width: Val::Percent(100.),
+                height: Val::Percent(10.),
+                margin: UiRect::top(Val::Percent(2.)),
+            },
+            false,
+        )
+        .id();
+    commands.entity(root).add_child(command_box);
+    focus_events.send(SetFocusEvent::some(command_box));
+
+    commands.insert_resource(Console {
+        root,
+        level_button,
+        module_box,
+        text,
+        command_box,
+    });
+}
+
+fn level_button(
+    console: Res<Console>,
+    mut filter: ResMut<ConsoleFilter>,
+    mut buttons: ButtonOps,
+    interactions: Query<&Interaction, Changed<Interaction>>,
+) {
+    let Ok(&Interaction::Pressed) = interactions.get(console.level_button) else {
+        return;
+    };
+
+    filter.cycle_level();
+    buttons
+        .set_text(console.level_button, level_caption(filter.level))
+        .unwrap();
+}
+
+fn update_text(
+    console: Res<Console>,
+    logs: Res<CapturedLogs>,
+    mut filter: ResMut<ConsoleFilter>,
+    text_boxes: TextBoxQuery,
+    mut texts: BodyTextOps,
+) {
+    let module = text_boxes
+        .text(console.module_box)
+        .map(|module| module.trim().to_owned())
+        .unwrap_or_default();
+    let revision = logs.revision();
+    if filter.revision != Some(revision) || filter.module != module {
+        let level = filter.level;
+        let lines: Vec<String> = logs
+            .latest(MAX_LINES, |record| {
+                record.level() <= level && record.target().starts_with(module.as_str())
+            })
+            .iter()
+            .map(|record| record.to_string())
+            .collect();
+
+        texts.set_text(console.text, lines.join("\n")).unwrap();
+        filter.revision = Some(revision);
+        filter.module = module;
+    }
+}
+
+fn submit(
+    console: Res<Console>,
+    mut text_boxes: TextBoxOps,
+    mut events: EventWriter<RunCommandEvent>,
+) {
+    let Some(line) = text_boxes.take_text(console.command_box) else {
+        return;
+    };
+    if !line.trim().is_empty() {
+        events.send(RunCommandEvent::new(line));
+    }
+}
+
+fn fps_command(
+    diagnostics: Option<Res<DiagnosticsStore>>,
+    mut commands: EventReader<CommandEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    if !commands
+        .read()
+        .any(|command| command.name() == FPS_COMMAND.name())
+    {
+        return;
+    }
+
+    let fps = diagnostics
+        .as_ref()
+        .and_then(|diagnostics| diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS))
+        .and_then(|fps| fps.smoothed());
+    let text = match fps {
+        Some(fps) => format!("FPS: {fps:.0}"),
+        None => "FPS are not measured.".to_owned(),
+    };
+    toasts.send(ToastEvent::new(text));
+}
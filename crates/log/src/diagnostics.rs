@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::DiagnosticsStore, input::common_conditions::input_just_pressed, prelude::*,
+    time::common_conditions::on_timer,
+};
+use de_core::cleanup::SurvivesGameExit;
+use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle, TextProps};
+
+/// Key toggling the diagnostics overlay.
+const TOGGLE_KEY: KeyCode = KeyCode::F11;
+/// Interval between consecutive updates of the overlay.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// This plugin implements an overlay displaying latest values of all enabled
+/// diagnostics, e.g. FPS or path cache hits and misses.
+pub(crate) struct DiagnosticsPlugin;
+
+impl Plugin for DiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle
+                    .run_if(input_just_pressed(TOGGLE_KEY))
+                    .run_if(resource_exists::<TextProps>),
+                update
+                    .run_if(on_timer(UPDATE_INTERVAL))
+                    .run_if(resource_exists::<DiagnosticsOverlay>)
+                    .run_if(resource_exists::<DiagnosticsStore>)
+                    .after(toggle),
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+struct DiagnosticsOverlay {
+    root: Entity,
+    text: Entity,
+}
+
+fn toggle(mut commands: GuiCommands, overlay: Option<Res<DiagnosticsOverlay>>) {
+    if let Some(overlay) = overlay {
+        commands.entity(overlay.root).despawn_recursive();
+        commands.remove_resource::<DiagnosticsOverlay>();
+        return;
+    }
+
+    let root = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Percent(1.),
+                    bottom: Val::Percent(30.),
+                    width: Val::Percent(20.),
+                    padding: UiRect::all(Val::Percent(0.5)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.8).into(),
+                z_index: ZIndex::Global(20000),
+                ..default()
+            },
+            SurvivesGameExit,
+        ))
+        .id();
+
+    let text = commands
+        .spawn_body_text(
+            OuterStyle {
+                width: Val::Percent(100.),
+                ..default()
+            },
+            "",
+        )
+        .id();
+    commands.entity(root).add_child(text);
+
+    commands.insert_resource(DiagnosticsOverlay { root, text });
+}
+
+fn update(
+    overlay: Res<DiagnosticsOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut texts: BodyTextOps,
+) {
+    let mut lines: Vec<String> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_enabled)
+        .filter_map(|diagnostic| {
+            diagnostic
+                .smoothed()
+                .map(|value| format!("{}: {value:.1}{}", diagnostic.path(), diagnostic.suffix))
+        })
+        .collect();
+    lines.sort();
+
+    texts.set_text(overlay.text, lines.join("\n")).unwrap();
+}
@@ -1,12 +1,19 @@
+mod capture;
+mod console;
+mod diagnostics;
+mod rotation;
 mod setup;
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 
-use crate::setup::LogPlugin;
+use crate::{console::ConsolePlugin, diagnostics::DiagnosticsPlugin, setup::LogPlugin};
 
 pub struct LogPluginGroup;
 
 impl PluginGroup for LogPluginGroup {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>().add(LogPlugin)
+        PluginGroupBuilder::start::<Self>()
+            .add(LogPlugin)
+            .add(ConsolePlugin)
+            .add(DiagnosticsPlugin)
     }
 }
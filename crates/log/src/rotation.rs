@@ -0,0 +1,258 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    mem,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDateTime;
+use flate2::{write::GzEncoder, Compression};
+
+/// Format of log file stems, i.e. of the local time when the logger was
+/// started.
+pub(crate) const STEM_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+/// Suffix of log files being written to.
+const LOG_SUFFIX: &str = ".jsonl";
+/// Suffix of rotated (and compressed) log files.
+const COMPRESSED_SUFFIX: &str = ".jsonl.gz";
+
+/// Log file writer which starts a new file once the current one would exceed
+/// a size limit. Rotated files are compressed and only a limited number of
+/// the most recent log files is retained in the log directory.
+pub(crate) struct RotatingWriter {
+    dir: PathBuf,
+    stem: String,
+    max_size: u64,
+    max_files: usize,
+    /// Number of rotations done so far.
+    part: u32,
+    path: PathBuf,
+    file: File,
+    /// Number of bytes written to the current file.
+    written: u64,
+}
+
+impl RotatingWriter {
+    /// Creates a new log file in a directory and removes old log files above
+    /// the retention limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - log directory. It is created if it does not exist.
+    ///
+    /// * `stem` - name of the log file without extension, formatted with
+    ///   [`STEM_FORMAT`]. A rotation index is appended to it for all but the
+    ///   first log file.
+    ///
+    /// * `max_size` - maximum size of a single log file in bytes.
+    ///
+    /// * `max_files` - maximum number of log files retained in the directory.
+    pub(crate) fn new(
+        dir: PathBuf,
+        stem: String,
+        max_size: u64,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        assert!(max_size > 0);
+        assert!(max_files > 0);
+
+        fs::create_dir_all(dir.as_path())?;
+        let path = dir.join(format!("{stem}{LOG_SUFFIX}"));
+        let file = File::create(path.as_path())?;
+
+        let writer = Self {
+            dir,
+            stem,
+            max_size,
+            max_files,
+            part: 0,
+            path,
+            file,
+            written: 0,
+        };
+        writer.prune()?;
+        Ok(writer)
+    }
+
+    /// Path of the log file currently written to.
+    pub(crate) fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        self.part += 1;
+        let path = self
+            .dir
+            .join(format!("{}.{}{LOG_SUFFIX}", self.stem, self.part));
+        self.file = File::create(path.as_path())?;
+        self.written = 0;
+
+        let previous = mem::replace(&mut self.path, path);
+        compress(previous.as_path())?;
+        self.prune()
+    }
+
+    /// Removes the oldest log files so that at most `max_files` log files
+    /// (including the current one) remain in the log directory. Files not
+    /// named as log files of this writer are never removed.
+    fn prune(&self) -> io::Result<()> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(self.dir.as_path())? {
+            let entry = entry?;
+            let path = entry.path();
+            if path == self.path {
+                continue;
+            }
+
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !is_log_file(name) {
+                continue;
+            }
+
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                files.push((metadata.modified()?, path));
+            }
+        }
+
+        if files.len() < self.max_files {
+            return Ok(());
+        }
+
+        files.sort();
+        let excess = files.len() + 1 - self.max_files;
+        for (_, path) in files.into_iter().take(excess) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for RotatingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Returns true if a file name matches names of (possibly rotated and
+/// compressed) log files, i.e. `{stem}[.{part}].jsonl[.gz]`.
+fn is_log_file(name: &str) -> bool {
+    let Some(base) = name
+        .strip_suffix(COMPRESSED_SUFFIX)
+        .or_else(|| name.strip_suffix(LOG_SUFFIX))
+    else {
+        return false;
+    };
+
+    let stem = match base.split_once('.') {
+        Some((stem, part)) => {
+            if part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()) {
+                return false;
+            }
+            stem
+        }
+        None => base,
+    };
+    NaiveDateTime::parse_from_str(stem, STEM_FORMAT).is_ok()
+}
+
+/// Replaces a file with its gzip compressed version.
+fn compress(path: &Path) -> io::Result<()> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(".gz");
+
+    let mut encoder = GzEncoder::new(File::create(target)?, Compression::default());
+    io::copy(&mut File::open(path)?, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn list(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_rotation() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("unrelated.txt"), "keep").unwrap();
+        fs::write(dir.path().join("unrelated.jsonl"), "keep").unwrap();
+
+        let stem = "2023-05-31_17-44-25";
+        let mut writer =
+            RotatingWriter::new(dir.path().to_owned(), stem.to_owned(), 10, 3).unwrap();
+        assert_eq!(writer.path(), dir.path().join(format!("{stem}.jsonl")));
+
+        writer.write_all(b"0123456").unwrap();
+        writer.write_all(b"789").unwrap();
+        assert_eq!(
+            list(dir.path()),
+            vec![
+                format!("{stem}.jsonl"),
+                "unrelated.jsonl".to_owned(),
+                "unrelated.txt".to_owned()
+            ]
+        );
+
+        writer.write_all(b"abc").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.path(), dir.path().join(format!("{stem}.1.jsonl")));
+        assert_eq!(
+            list(dir.path()),
+            vec![
+                format!("{stem}.1.jsonl"),
+                format!("{stem}.jsonl.gz"),
+                "unrelated.jsonl".to_owned(),
+                "unrelated.txt".to_owned()
+            ]
+        );
+        assert_eq!(fs::read(writer.path()).unwrap(), b"abc");
+
+        for _ in 0..3 {
+            writer.write_all(b"0123456789").unwrap();
+        }
+        let names = list(dir.path());
+        assert_eq!(names.len(), 5);
+        assert!(names.contains(&format!("{stem}.4.jsonl")));
+        assert!(names.contains(&"unrelated.jsonl".to_owned()));
+        assert!(names.contains(&"unrelated.txt".to_owned()));
+    }
+
+    #[test]
+    fn test_is_log_file() {
+        assert!(is_log_file("2023-05-31_17-44-25.jsonl"));
+        assert!(is_log_file("2023-05-31_17-44-25.12.jsonl"));
+        assert!(is_log_file("2023-05-31_17-44-25.jsonl.gz"));
+        assert!(is_log_file("2023-05-31_17-44-25.3.jsonl.gz"));
+
+        assert!(!is_log_file("2023-05-31_17-44-25.txt"));
+        assert!(!is_log_file("2023-05-31_17-44-25..jsonl"));
+        assert!(!is_log_file("2023-05-31_17-44-25.a.jsonl"));
+        assert!(!is_log_file("data.jsonl"));
+        assert!(!is_log_file("data.jsonl.gz"));
+    }
+}
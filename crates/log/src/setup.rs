@@ -1,12 +1,21 @@
-use std::path::PathBuf;
+use std::{env, path::PathBuf};
 
+use async_std::task;
 use bevy::prelude::*;
-use de_core::fs::logs_dir;
-use tracing::Level;
+use de_conf::{Configuration, LoggingConf};
+use de_core::fs::{conf_dir, logs_dir};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::{fmt, EnvFilter};
 
+use crate::{
+    capture::{CaptureLayer, CapturedLogs},
+    rotation::{RotatingWriter, STEM_FORMAT},
+};
+
+/// Environment variable overriding the log directory.
+const LOG_DIR_VAR: &str = "DE_LOG_DIR";
+
 pub(crate) struct LogPlugin;
 
 /// the handle for the guard (dropping it will disable the log writer)
@@ -18,32 +27,60 @@ pub(crate) struct CurrentLogHandle {
 
 impl Plugin for LogPlugin {
     fn build(&self, app: &mut App) {
-        let dir: PathBuf = logs_dir()
-            .map(|d| d.into())
-            .unwrap_or(PathBuf::from("logs"));
+        // Logging is set up before the configuration is loaded by the
+        // configuration plugin, thus it has to be loaded here. Loading errors
+        // are reported later by the configuration plugin.
+        let conf = load_conf();
+
+        let dir: PathBuf = match env::var_os(LOG_DIR_VAR) {
+            Some(dir) => dir.into(),
+            None => match conf.directory() {
+                Some(dir) => dir.clone(),
+                None => logs_dir()
+                    .map(|d| d.into())
+                    .unwrap_or(PathBuf::from("logs")),
+            },
+        };
 
         // for file name
         let dt = chrono::Local::now();
-        let path: PathBuf = dt.format("%Y-%m-%d_%H-%M-%S.jsonl").to_string().into();
+        let stem = dt.format(STEM_FORMAT).to_string();
 
-        let file_appender = tracing_appender::rolling::never(dir.as_path(), path.as_path());
+        let file_appender =
+            RotatingWriter::new(dir.clone(), stem, conf.max_file_size(), conf.max_files())
+                .expect("Unable to create a log file");
+        let path = file_appender.path().to_owned();
 
         let (non_blocking_log_writer, _guard) = tracing_appender::non_blocking(file_appender);
 
+        let captured = CapturedLogs::default();
         let collector = tracing_subscriber::registry()
             .with(
                 EnvFilter::builder()
-                    // defaults to INFO if RUST_LOG not set
-                    .with_default_directive(Level::INFO.into())
+                    // defaults to configured level if RUST_LOG not set
+                    .with_default_directive(conf.level().into())
                     .from_env_lossy(),
             )
             .with(fmt::layer().with_writer(std::io::stdout))
-            .with(fmt::layer().json().with_writer(non_blocking_log_writer));
+            .with(fmt::layer().json().with_writer(non_blocking_log_writer))
+            .with(CaptureLayer::new(captured.clone()));
         tracing::subscriber::set_global_default(collector)
             .expect("Unable to set a global collector");
 
-        app.insert_resource(CurrentLogHandle { guard: _guard });
+        app.insert_resource(CurrentLogHandle { guard: _guard })
+            .insert_resource(captured);
 
         info!("Logging to file {path:?} in directory: {dir:?}");
     }
 }
+
+fn load_conf() -> LoggingConf {
+    let Ok(dir) = conf_dir() else {
+        return LoggingConf::default();
+    };
+
+    let path = dir.join("conf.yaml");
+    task::block_on(Configuration::load(path.as_path()))
+        .map(|conf| conf.logging().clone())
+        .unwrap_or_default()
+}
@@ -1,6 +1,9 @@
 use ahash::AHashMap;
 use de_types::{
-    objects::{ActiveObjectType, InactiveObjectType, PLAYER_MAX_BUILDINGS, PLAYER_MAX_UNITS},
+    objects::{
+        ActiveObjectType, BuildingType, InactiveObjectType, UnitType, PLAYER_MAX_BUILDINGS,
+        PLAYER_MAX_UNITS,
+    },
     player::Player,
 };
 use enum_map::Enum;
@@ -48,12 +51,22 @@ impl MapContent {
     }
 
     pub(crate) fn validate(&self, metadata: &MapMetadata) -> Result<(), MapContentValidationError> {
+        match self.validate_all(metadata).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns all validation errors of the content (as opposed to stopping
+    /// at the first one).
+    pub(crate) fn validate_all(&self, metadata: &MapMetadata) -> Vec<MapContentValidationError> {
         #[derive(Default)]
         struct Counter {
             buildings: u32,
             units: u32,
         }
 
+        let mut errors = Vec::new();
         let mut counts: AHashMap<Player, Counter> = AHashMap::new();
 
         for (i, object) in self.objects.iter().enumerate() {
@@ -67,23 +80,26 @@ impl MapContent {
             }
 
             if let Err(error) = object.validate(metadata.bounds(), metadata.max_player()) {
-                return Err(MapContentValidationError::Object {
+                errors.push(MapContentValidationError::Object {
                     index: i,
                     source: error,
                 });
             }
         }
 
-        for (&player, counter) in counts.iter() {
+        let mut players: Vec<Player> = counts.keys().copied().collect();
+        players.sort_by_key(|player| player.to_num());
+        for player in players {
+            let counter = &counts[&player];
             if counter.buildings > PLAYER_MAX_BUILDINGS {
-                return Err(MapContentValidationError::MaxBuildings {
+                errors.push(MapContentValidationError::MaxBuildings {
                     player,
                     max: PLAYER_MAX_BUILDINGS,
                     number: counter.buildings,
                 });
             }
             if counter.units > PLAYER_MAX_UNITS {
-                return Err(MapContentValidationError::MaxUnits {
+                errors.push(MapContentValidationError::MaxUnits {
                     player,
                     max: PLAYER_MAX_UNITS,
                     number: counter.units,
@@ -91,7 +107,7 @@ impl MapContent {
             }
         }
 
-        Ok(())
+        errors
     }
 }
 
@@ -201,10 +217,30 @@ impl ActiveObject {
     }
 
     fn update_hash(&self, hasher: &mut MapHasher) {
-        hasher.update_usize(self.object_type.into_usize());
+        hasher.update_usize(self.hash_id());
         hasher.update_u8(self.player.to_num());
     }
 
+    /// Returns a number identifying the object type in map hashes.
+    ///
+    /// Unlike [`Enum::into_usize`], the number does not change when a new
+    /// building type is added, therefore hashes (and file names) of existing
+    /// maps stay valid. New object types must get new numbers.
+    fn hash_id(&self) -> usize {
+        match self.object_type {
+            ActiveObjectType::Building(BuildingType::Base) => 0,
+            ActiveObjectType::Building(BuildingType::PowerHub) => 1,
+            ActiveObjectType::Unit(UnitType::Attacker) => 2,
+            ActiveObjectType::Unit(UnitType::Artillery) => 3,
+            ActiveObjectType::Unit(UnitType::Scout) => 4,
+            ActiveObjectType::Unit(UnitType::Harvester) => 5,
+            ActiveObjectType::Building(BuildingType::Bunker) => 6,
+            ActiveObjectType::Unit(UnitType::Transport) => 7,
+            ActiveObjectType::Building(BuildingType::ShieldDome) => 8,
+            ActiveObjectType::Building(BuildingType::Radar) => 9,
+        }
+    }
+
     pub fn object_type(&self) -> ActiveObjectType {
         self.object_type
     }
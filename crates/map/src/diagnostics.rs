@@ -0,0 +1,166 @@
+use std::{error::Error, fmt};
+
+use serde::Serialize;
+
+/// Result of a thorough map validation. Unlike simple validation, which stops
+/// at the first error, the report lists all found problems.
+#[derive(Default, Debug, Serialize)]
+pub struct MapReport {
+    issues: Vec<Issue>,
+}
+
+impl MapReport {
+    /// Returns true if there are no errors in the report. There might still
+    /// be warnings.
+    pub fn is_valid(&self) -> bool {
+        !self
+            .issues
+            .iter()
+            .any(|issue| issue.severity() == Severity::Error)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn issues(&self) -> &[Issue] {
+        self.issues.as_slice()
+    }
+
+    pub fn push(&mut self, issue: Issue) {
+        self.issues.push(issue);
+    }
+}
+
+impl fmt::Display for MapReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single problem found on a map.
+#[derive(Debug, Serialize)]
+pub struct Issue {
+    severity: Severity,
+    kind: IssueKind,
+    message: String,
+    /// Indices of map objects involved in the problem.
+    objects: Vec<usize>,
+}
+
+impl Issue {
+    /// Creates a new issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - severity of the issue.
+    ///
+    /// * `kind` - category of the issue.
+    ///
+    /// * `message` - human readable description of the issue.
+    ///
+    /// * `objects` - indices of involved map objects.
+    pub fn new(
+        severity: Severity,
+        kind: IssueKind,
+        message: impl ToString,
+        objects: Vec<usize>,
+    ) -> Self {
+        Self {
+            severity,
+            kind,
+            message: message.to_string(),
+            objects,
+        }
+    }
+
+    /// Creates a new error issue from an error and all its sources.
+    pub(crate) fn from_error(kind: IssueKind, error: &dyn Error, objects: Vec<usize>) -> Self {
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(inner) = source {
+            message.push_str(": ");
+            message.push_str(&inner.to_string());
+            source = inner.source();
+        }
+        Self::new(Severity::Error, kind, message, objects)
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn kind(&self) -> IssueKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    pub fn objects(&self) -> &[usize] {
+        self.objects.as_slice()
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.severity, self.kind, self.message)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The map may be played but it is likely not working as intended.
+    Warning,
+    /// The map cannot be played.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// Invalid map metadata.
+    Metadata,
+    /// Invalid map content, for example an object with an invalid player.
+    Content,
+    /// Invalid map script.
+    Script,
+    /// An object (partially) lies outside of the map.
+    OutOfBounds,
+    /// Two or more objects overlap.
+    Overlap,
+    /// Starting position of a player cannot be reached from starting position
+    /// of another player.
+    Unreachable,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Metadata => "metadata",
+            Self::Content => "content",
+            Self::Script => "script",
+            Self::OutOfBounds => "out of bounds",
+            Self::Overlap => "overlap",
+            Self::Unreachable => "unreachable",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -37,6 +37,8 @@ macro_rules! storing_io_error {
 pub const MAP_FILE_SUFFIX: &str = ".dem.tar";
 const METADATA_JSON_ENTRY: &str = "metadata.json";
 const CONTENT_JSON_ENTRY: &str = "content.json";
+/// Map script is optional, maps without it have an empty script.
+const SCRIPT_JSON_ENTRY: &str = "script.json";
 
 type LoadingResult<T> = Result<T, MapLoadingError>;
 type StoringResult = Result<(), MapStoringError>;
@@ -68,12 +70,23 @@ pub async fn load_metadata<P: AsRef<Path>>(path: P) -> LoadingResult<MapMetadata
 
 /// Load a map TAR file.
 pub async fn load_map<P: AsRef<Path>>(path: P) -> LoadingResult<Map> {
+    let map = load_map_unvalidated(path).await?;
+    if let Err(error) = map.validate() {
+        return Err(MapLoadingError::Validation { source: error });
+    }
+    Ok(map)
+}
+
+/// Loads a map without validating it. Use [`Map::diagnose`] to find all
+/// problems of the loaded map.
+pub async fn load_map_unvalidated<P: AsRef<Path>>(path: P) -> LoadingResult<Map> {
     let mut file = loading_io_error!(File::open(&path).await);
     let archive = Archive::new(&mut file);
     let mut entries = loading_io_error!(archive.entries());
 
     let mut map_meta = None;
     let mut map_content = None;
+    let mut map_script = None;
 
     while let Some(entry) = entries.next().await {
         let mut entry = loading_io_error!(entry);
@@ -88,18 +101,18 @@ pub async fn load_map<P: AsRef<Path>>(path: P) -> LoadingResult<Map> {
             map_meta = deserialize_entry(&mut entry).await?;
         } else if path == CONTENT_JSON_ENTRY {
             map_content = deserialize_entry(&mut entry).await?;
+        } else if path == SCRIPT_JSON_ENTRY {
+            map_script = deserialize_entry(&mut entry).await?;
         }
     }
 
     let map_meta = unwrap(METADATA_JSON_ENTRY, map_meta)?;
     let map_content = unwrap(CONTENT_JSON_ENTRY, map_content)?;
-    let map = Map::new(map_meta, map_content);
-
-    if let Err(error) = map.validate() {
-        return Err(MapLoadingError::Validation { source: error });
-    }
-
-    Ok(map)
+    Ok(Map::new(
+        map_meta,
+        map_content,
+        map_script.unwrap_or_default(),
+    ))
 }
 
 async fn deserialize_entry<T: DeserializeOwned>(
@@ -150,6 +163,9 @@ pub async fn store_map<P: AsRef<Path>>(map: &Map, path: P) -> StoringResult {
 
     serialize_entry(&mut archive, METADATA_JSON_ENTRY, map.metadata()).await?;
     serialize_entry(&mut archive, CONTENT_JSON_ENTRY, map.content()).await?;
+    if !map.script().is_empty() {
+        serialize_entry(&mut archive, SCRIPT_JSON_ENTRY, map.script()).await?;
+    }
 
     Ok(())
 }
@@ -207,6 +223,7 @@ mod test {
         content::{ActiveObject, InnerObject, Object},
         map::Map,
         meta::MapMetadata,
+        script::{Action, Condition, Trigger},
         size::MapBounds,
     };
 
@@ -232,6 +249,11 @@ mod test {
             ));
         }
 
+        map.insert_trigger(Trigger::new(
+            Condition::Time { seconds: 30. },
+            vec![Action::EndGame { won: true }],
+        ));
+
         let tmp_dir = Builder::new().prefix("de_map_").tempdir().unwrap();
         let mut tmp_dir_path = PathBuf::from(tmp_dir.path());
         tmp_dir_path.push("test-map.dem.tar");
@@ -243,6 +265,8 @@ mod test {
             loaded_map.metadata().bounds().aabb(),
             Aabb::new(Point::new(-500., -1000.), Point::new(500., 1000.))
         );
+        assert_eq!(loaded_map.script().triggers().len(), 1);
+        assert_eq!(loaded_map.compute_hash(), map.compute_hash());
     }
 
     #[test]
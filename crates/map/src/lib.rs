@@ -1,7 +1,9 @@
 pub mod content;
+pub mod diagnostics;
 pub mod hash;
 pub mod io;
 pub mod map;
 pub mod meta;
 pub mod placement;
+pub mod script;
 pub mod size;
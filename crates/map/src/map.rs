@@ -3,24 +3,32 @@ use thiserror::Error;
 
 use crate::{
     content::{MapContent, MapContentValidationError, Object},
+    diagnostics::{Issue, IssueKind, MapReport},
     hash::{MapHash, MapHasher},
     meta::{MapMetadata, MapMetadataValidationError},
     placement::Placement,
+    script::{MapScript, ScriptValidationError, Trigger},
 };
 
 pub struct Map {
     metadata: MapMetadata,
     content: MapContent,
+    script: MapScript,
 }
 
 impl Map {
-    /// Creates a new empty map (i.e. with no objects place on it).
+    /// Creates a new empty map (i.e. with no objects place on it and with an
+    /// empty script).
     pub fn empty(metadata: MapMetadata) -> Self {
-        Self::new(metadata, MapContent::empty())
+        Self::new(metadata, MapContent::empty(), MapScript::default())
     }
 
-    pub(crate) fn new(metadata: MapMetadata, content: MapContent) -> Self {
-        Self { metadata, content }
+    pub(crate) fn new(metadata: MapMetadata, content: MapContent, script: MapScript) -> Self {
+        Self {
+            metadata,
+            content,
+            script,
+        }
     }
 
     /// Compute deterministic hash of the map.
@@ -28,6 +36,7 @@ impl Map {
         let mut hasher = MapHasher::new();
         self.metadata.update_hash(&mut hasher);
         self.content.update_hash(&mut hasher);
+        self.script.update_hash(&mut hasher);
         hasher.finalize()
     }
 
@@ -39,6 +48,10 @@ impl Map {
         &self.content
     }
 
+    pub fn script(&self) -> &MapScript {
+        &self.script
+    }
+
     /// Insert an object to the map.
     ///
     /// # Panics
@@ -52,6 +65,16 @@ impl Map {
         self.content.insert_object(object);
     }
 
+    /// Appends a trigger to the map script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trigger is invalid, for example if it refers to a
+    /// position out of the map bounds or to an invalid player.
+    pub fn insert_trigger(&mut self, trigger: Trigger) {
+        self.script.insert_trigger(&self.metadata, trigger);
+    }
+
     /// Creates a new placement on the map.
     ///
     /// # Arguments
@@ -79,8 +102,39 @@ impl Map {
         if let Err(error) = self.content.validate(&self.metadata) {
             return Err(MapValidationError::Content { source: error });
         }
+        if let Err(error) = self.script.validate(&self.metadata) {
+            return Err(MapValidationError::Script { source: error });
+        }
         Ok(())
     }
+
+    /// Validates the map and returns a report with all found problems (as
+    /// opposed to stopping at the first one).
+    ///
+    /// Only problems detectable without knowledge of object shapes are
+    /// reported.
+    pub fn diagnose(&self) -> MapReport {
+        let mut report = MapReport::default();
+
+        if let Err(error) = self.metadata.validate() {
+            report.push(Issue::from_error(IssueKind::Metadata, &error, Vec::new()));
+            // Further validation depends on valid metadata.
+            return report;
+        }
+
+        for error in self.content.validate_all(&self.metadata) {
+            let objects = match error {
+                MapContentValidationError::Object { index, .. } => vec![index],
+                _ => Vec::new(),
+            };
+            report.push(Issue::from_error(IssueKind::Content, &error, objects));
+        }
+        for error in self.script.validate_all(&self.metadata) {
+            report.push(Issue::from_error(IssueKind::Script, &error, Vec::new()));
+        }
+
+        report
+    }
 }
 
 #[derive(Error, Debug)]
@@ -89,6 +143,8 @@ pub enum MapValidationError {
     Metadata { source: MapMetadataValidationError },
     #[error("invalid map content")]
     Content { source: MapContentValidationError },
+    #[error("invalid map script")]
+    Script { source: ScriptValidationError },
 }
 
 #[cfg(test)]
@@ -104,6 +160,7 @@ mod test {
     use super::*;
     use crate::{
         content::{ActiveObject, InactiveObject, InnerObject},
+        diagnostics::Severity,
         placement::Placement,
         size::MapBounds,
     };
@@ -157,6 +214,7 @@ mod test {
                 Player::Player4,
             ),
             content,
+            MapScript::default(),
         );
 
         let result = map.validate();
@@ -180,6 +238,52 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_map_diagnose() {
+        let mut content = MapContent::empty();
+        for position in [Vec2::new(100., 0.), Vec2::new(1., 1.), Vec2::new(0., 200.)] {
+            content.insert_object(Object::new(
+                Placement::new(position, 0.),
+                InnerObject::Active(ActiveObject::new(
+                    ActiveObjectType::Unit(UnitType::Attacker),
+                    Player::Player1,
+                )),
+            ));
+        }
+
+        let map = Map::new(
+            MapMetadata::new(
+                "Test Map".into(),
+                MapBounds::new(Vec2::new(5., 5.)),
+                Player::Player2,
+            ),
+            content,
+            MapScript::default(),
+        );
+
+        let report = map.diagnose();
+        assert!(!report.is_valid());
+        assert_eq!(report.issues().len(), 2);
+
+        let issue = &report.issues()[0];
+        assert_eq!(issue.severity(), Severity::Error);
+        assert_eq!(issue.kind(), IssueKind::Content);
+        assert_eq!(issue.objects(), &[0]);
+        assert_eq!(
+            issue.message(),
+            "invalid objects[0]: invalid object placement: \
+            position (100, 0) is out of map bounds"
+        );
+        assert_eq!(report.issues()[1].objects(), &[2]);
+
+        let valid = Map::empty(MapMetadata::new(
+            "Test Map".into(),
+            MapBounds::new(Vec2::new(5., 5.)),
+            Player::Player2,
+        ));
+        assert!(valid.diagnose().is_empty());
+    }
+
     #[test]
     fn test_map_hash() {
         let mut map = Map::empty(MapMetadata::new(
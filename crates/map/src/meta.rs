@@ -1,3 +1,4 @@
+use bevy::prelude::Resource;
 use de_types::player::Player;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -8,14 +9,21 @@ use crate::{
 };
 
 pub const MAX_MAP_NAME_LEN: usize = 16;
+/// Minimum length of a day/night cycle in seconds.
+pub const MIN_DAY_LENGTH: f32 = 60.;
+/// Maximum length of a day/night cycle in seconds.
+pub const MAX_DAY_LENGTH: f32 = 86_400.;
 
 /// General information about a map. It does not hold full content of the map
 /// (i.e. location of objects on the map).
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Resource)]
 pub struct MapMetadata {
     name: String,
     bounds: MapBounds,
     max_player: Player,
+    /// Lighting of the map is static if this is None.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    day_night: Option<DayNightCycle>,
 }
 
 impl MapMetadata {
@@ -39,6 +47,7 @@ impl MapMetadata {
             name,
             bounds,
             max_player,
+            day_night: None,
         };
         map.validate().unwrap();
         map
@@ -49,6 +58,10 @@ impl MapMetadata {
         hasher.update_vec2(self.bounds.min());
         hasher.update_vec2(self.bounds.max());
         hasher.update_u8(self.max_player.to_num());
+        // Keep hashes of maps without the cycle backward compatible.
+        if let Some(day_night) = self.day_night {
+            hasher.update_f32(day_night.length());
+        }
     }
 
     pub fn name(&self) -> &str {
@@ -63,6 +76,16 @@ impl MapMetadata {
         self.max_player
     }
 
+    /// Returns the day/night cycle configuration or None if the map has
+    /// static lighting.
+    pub fn day_night(&self) -> Option<DayNightCycle> {
+        self.day_night
+    }
+
+    pub fn set_day_night(&mut self, day_night: Option<DayNightCycle>) {
+        self.day_night = day_night;
+    }
+
     pub(crate) fn validate(&self) -> Result<(), MapMetadataValidationError> {
         if self.name.is_empty() {
             return Err(MapMetadataValidationError::MapName(
@@ -85,6 +108,47 @@ impl MapMetadata {
             return Err(MapMetadataValidationError::MaxPlayers(self.max_player));
         }
 
+        if let Some(day_night) = self.day_night {
+            day_night.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Configuration of day/night cycle of a map. The cycle is driven by game
+/// time.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Resource)]
+pub struct DayNightCycle {
+    /// Length of a full day in seconds.
+    length: f32,
+}
+
+impl DayNightCycle {
+    /// Creates a new day/night cycle configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - length of a full day (including night) in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is not finite or is outside of range
+    /// [`MIN_DAY_LENGTH`] to [`MAX_DAY_LENGTH`].
+    pub fn new(length: f32) -> Self {
+        let cycle = Self { length };
+        cycle.validate().unwrap();
+        cycle
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    fn validate(&self) -> Result<(), MapMetadataValidationError> {
+        if !self.length.is_finite() || !(MIN_DAY_LENGTH..=MAX_DAY_LENGTH).contains(&self.length) {
+            return Err(MapMetadataValidationError::DayLength(self.length));
+        }
         Ok(())
     }
 }
@@ -97,4 +161,45 @@ pub enum MapMetadataValidationError {
     MapBounds { source: MapBoundsValidationError },
     #[error("map has to have at least 2 players, got {0}")]
     MaxPlayers(Player),
+    #[error("day length has to be between {MIN_DAY_LENGTH} and {MAX_DAY_LENGTH} seconds, got {0}")]
+    DayLength(f32),
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn test_day_night_serde() {
+        let mut metadata = MapMetadata::new(
+            "Test".into(),
+            MapBounds::new(Vec2::splat(100.)),
+            Player::Player2,
+        );
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(!json.contains("day_night"));
+        let loaded: MapMetadata = serde_json::from_str(&json).unwrap();
+        assert!(loaded.day_night().is_none());
+
+        metadata.set_day_night(Some(DayNightCycle::new(600.)));
+        let json = serde_json::to_string(&metadata).unwrap();
+        let loaded: MapMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.day_night(), Some(DayNightCycle::new(600.)));
+        assert!(loaded.validate().is_ok());
+    }
+
+    #[test]
+    fn test_day_night_validate() {
+        assert!(DayNightCycle { length: 600. }.validate().is_ok());
+        assert!(DayNightCycle { length: 10. }.validate().is_err());
+        assert!(DayNightCycle { length: 1e6 }.validate().is_err());
+        assert!(DayNightCycle { length: f32::NAN }.validate().is_err());
+        assert!(DayNightCycle {
+            length: f32::INFINITY
+        }
+        .validate()
+        .is_err());
+    }
 }
@@ -0,0 +1,481 @@
+//! Declarative map scripting. A map script consists of triggers, each of
+//! which performs a list of actions once its condition is met. This makes it
+//! possible to build campaign-style scenarios purely from map data.
+
+use bevy::prelude::Resource;
+use de_types::{objects::UnitType, player::Player};
+use enum_map::Enum;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{hash::MapHasher, meta::MapMetadata, size::MapBounds};
+
+/// Maximum number of units spawned by a single wave.
+pub const MAX_WAVE_SIZE: usize = 64;
+/// Maximum length of an objective description in bytes.
+pub const MAX_OBJECTIVE_LEN: usize = 256;
+
+/// Scenario script of a map.
+#[derive(Clone, Default, Serialize, Deserialize, Resource)]
+pub struct MapScript {
+    triggers: Vec<Trigger>,
+}
+
+impl MapScript {
+    /// Returns true if the script contains no triggers.
+    pub fn is_empty(&self) -> bool {
+        self.triggers.is_empty()
+    }
+
+    pub fn triggers(&self) -> &[Trigger] {
+        self.triggers.as_slice()
+    }
+
+    /// Appends a trigger to the script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trigger is invalid with respect to the map metadata.
+    pub fn insert_trigger(&mut self, metadata: &MapMetadata, trigger: Trigger) {
+        trigger.validate(metadata).unwrap();
+        self.triggers.push(trigger);
+    }
+
+    pub(crate) fn update_hash(&self, hasher: &mut MapHasher) {
+        for trigger in &self.triggers {
+            trigger.update_hash(hasher);
+        }
+    }
+
+    pub(crate) fn validate(&self, metadata: &MapMetadata) -> Result<(), ScriptValidationError> {
+        match self.validate_all(metadata).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns validation errors of all invalid triggers.
+    pub(crate) fn validate_all(&self, metadata: &MapMetadata) -> Vec<ScriptValidationError> {
+        self.triggers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, trigger)| {
+                trigger
+                    .validate(metadata)
+                    .err()
+                    .map(|error| ScriptValidationError::Trigger {
+                        index,
+                        source: error,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ScriptValidationError {
+    #[error("invalid triggers[{index}]")]
+    Trigger {
+        index: usize,
+        source: TriggerValidationError,
+    },
+}
+
+/// Trigger performs its actions (in order) once its condition is met. Each
+/// trigger fires at most once during a game.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Trigger {
+    condition: Condition,
+    actions: Vec<Action>,
+}
+
+impl Trigger {
+    pub fn new(condition: Condition, actions: Vec<Action>) -> Self {
+        Self { condition, actions }
+    }
+
+    pub fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    pub fn actions(&self) -> &[Action] {
+        self.actions.as_slice()
+    }
+
+    fn update_hash(&self, hasher: &mut MapHasher) {
+        self.condition.update_hash(hasher);
+        for action in &self.actions {
+            action.update_hash(hasher);
+        }
+    }
+
+    fn validate(&self, metadata: &MapMetadata) -> Result<(), TriggerValidationError> {
+        if let Err(error) = self.condition.validate(metadata) {
+            return Err(TriggerValidationError::Condition { source: error });
+        }
+        for (index, action) in self.actions.iter().enumerate() {
+            if let Err(error) = action.validate(metadata) {
+                return Err(TriggerValidationError::Action {
+                    index,
+                    source: error,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TriggerValidationError {
+    #[error("invalid trigger condition")]
+    Condition { source: ElementValidationError },
+    #[error("invalid actions[{index}]")]
+    Action {
+        index: usize,
+        source: ElementValidationError,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    /// The condition is met once the game has been played for the given
+    /// number of seconds.
+    Time { seconds: f32 },
+    /// The condition is met once any active object of the player is inside
+    /// the area.
+    AreaEntered { player: Player, area: Area },
+    /// The condition is met once the given number of units of the player has
+    /// been destroyed.
+    UnitDestroyed { player: Player, count: u32 },
+}
+
+impl Condition {
+    fn update_hash(&self, hasher: &mut MapHasher) {
+        match self {
+            Self::Time { seconds } => {
+                hasher.update_u8(0);
+                hasher.update_f32(*seconds);
+            }
+            Self::AreaEntered { player, area } => {
+                hasher.update_u8(1);
+                hasher.update_u8(player.to_num());
+                area.update_hash(hasher);
+            }
+            Self::UnitDestroyed { player, count } => {
+                hasher.update_u8(2);
+                hasher.update_u8(player.to_num());
+                hasher.update_usize(*count as usize);
+            }
+        }
+    }
+
+    fn validate(&self, metadata: &MapMetadata) -> Result<(), ElementValidationError> {
+        match self {
+            Self::Time { seconds } => {
+                if !seconds.is_finite() || *seconds < 0. {
+                    return Err(ElementValidationError::Time(*seconds));
+                }
+            }
+            Self::AreaEntered { player, area } => {
+                validate_player(*player, metadata)?;
+                area.validate(metadata.bounds())?;
+            }
+            Self::UnitDestroyed { player, count } => {
+                validate_player(*player, metadata)?;
+                if *count == 0 {
+                    return Err(ElementValidationError::ZeroCount);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    /// Spawns units of a player around a position.
+    SpawnWave {
+        player: Player,
+        units: Vec<UnitType>,
+        position: Vec2,
+        /// The spawned units move to this position if it is not None.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target: Option<Vec2>,
+    },
+    /// Reveals an area to the local players by moving the camera to it.
+    RevealArea { area: Area },
+    /// Adds a new objective or updates an existing objective with the same
+    /// ID.
+    SetObjective {
+        id: u32,
+        description: String,
+        status: ObjectiveStatus,
+    },
+    /// Ends the game. Local players either win or lose.
+    EndGame { won: bool },
+}
+
+impl Action {
+    fn update_hash(&self, hasher: &mut MapHasher) {
+        match self {
+            Self::SpawnWave {
+                player,
+                units,
+                position,
+                target,
+            } => {
+                hasher.update_u8(0);
+                hasher.update_u8(player.to_num());
+                hasher.update_usize(units.len());
+                for unit in units {
+                    hasher.update_usize(unit.into_usize());
+                }
+                hasher.update_vec2(*position);
+                if let Some(target) = target {
+                    hasher.update_vec2(*target);
+                }
+            }
+            Self::RevealArea { area } => {
+                hasher.update_u8(1);
+                area.update_hash(hasher);
+            }
+            Self::SetObjective {
+                id,
+                description,
+                status,
+            } => {
+                hasher.update_u8(2);
+                hasher.update_usize(*id as usize);
+                hasher.update_str(description);
+                hasher.update_usize(status.into_usize());
+            }
+            Self::EndGame { won } => {
+                hasher.update_u8(3);
+                hasher.update_u8(*won as u8);
+            }
+        }
+    }
+
+    fn validate(&self, metadata: &MapMetadata) -> Result<(), ElementValidationError> {
+        match self {
+            Self::SpawnWave {
+                player,
+                units,
+                position,
+                target,
+            } => {
+                validate_player(*player, metadata)?;
+                if units.is_empty() || units.len() > MAX_WAVE_SIZE {
+                    return Err(ElementValidationError::WaveSize(units.len()));
+                }
+                for point in std::iter::once(position).chain(target.iter()) {
+                    if !metadata.bounds().contains(*point) {
+                        return Err(ElementValidationError::OutOfMapBounds(*point));
+                    }
+                }
+            }
+            Self::RevealArea { area } => area.validate(metadata.bounds())?,
+            Self::SetObjective { description, .. } => {
+                if description.is_empty() || description.len() > MAX_OBJECTIVE_LEN {
+                    return Err(ElementValidationError::ObjectiveLength(description.len()));
+                }
+            }
+            Self::EndGame { .. } => (),
+        }
+        Ok(())
+    }
+}
+
+/// Status of a scenario objective.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectiveStatus {
+    Active,
+    Completed,
+    Failed,
+}
+
+/// A circular area of the map.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Area {
+    center: Vec2,
+    radius: f32,
+}
+
+impl Area {
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns true if the point lies inside (or at the edge of) the area.
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.center.distance_squared(point) <= self.radius.powi(2)
+    }
+
+    fn update_hash(&self, hasher: &mut MapHasher) {
+        hasher.update_vec2(self.center);
+        hasher.update_f32(self.radius);
+    }
+
+    fn validate(&self, bounds: MapBounds) -> Result<(), ElementValidationError> {
+        if !bounds.contains(self.center) {
+            return Err(ElementValidationError::OutOfMapBounds(self.center));
+        }
+        if !self.radius.is_finite() || self.radius <= 0. {
+            return Err(ElementValidationError::Radius(self.radius));
+        }
+        Ok(())
+    }
+}
+
+fn validate_player(player: Player, metadata: &MapMetadata) -> Result<(), ElementValidationError> {
+    if player > metadata.max_player() {
+        return Err(ElementValidationError::MaxPlayer {
+            max_player: metadata.max_player(),
+            player,
+        });
+    }
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum ElementValidationError {
+    #[error("time must be a non-negative finite number, got: {0}")]
+    Time(f32),
+    #[error("count must be positive")]
+    ZeroCount,
+    #[error("maximum player is {max_player}, got player {player}")]
+    MaxPlayer { max_player: Player, player: Player },
+    #[error("position ({}, {}) is out of map bounds", .0.x, .0.y)]
+    OutOfMapBounds(Vec2),
+    #[error("area radius must be a positive finite number, got: {0}")]
+    Radius(f32),
+    #[error("wave must have between 1 and {MAX_WAVE_SIZE} units, got: {0}")]
+    WaveSize(usize),
+    #[error("objective description must have between 1 and {MAX_OBJECTIVE_LEN} bytes, got: {0}")]
+    ObjectiveLength(usize),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn metadata() -> MapMetadata {
+        MapMetadata::new(
+            "Test".into(),
+            MapBounds::new(Vec2::splat(100.)),
+            Player::Player2,
+        )
+    }
+
+    #[test]
+    fn test_serde() {
+        let script: MapScript = serde_json::from_str(
+            r#"{"triggers": [{
+                "condition": {"type": "time", "seconds": 60.0},
+                "actions": [
+                    {
+                        "type": "spawn_wave",
+                        "player": "Player2",
+                        "units": ["Attacker", "Attacker"],
+                        "position": [10.0, 10.0],
+                        "target": [-10.0, -10.0]
+                    },
+                    {
+                        "type": "set_objective",
+                        "id": 1,
+                        "description": "Survive the attack.",
+                        "status": "active"
+                    }
+                ]
+            }]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(script.triggers().len(), 1);
+        let trigger = &script.triggers()[0];
+        assert!(matches!(
+            trigger.condition(),
+            Condition::Time { seconds } if *seconds == 60.
+        ));
+        assert_eq!(trigger.actions().len(), 2);
+        assert!(script.validate(&metadata()).is_ok());
+    }
+
+    #[test]
+    fn test_validation() {
+        let metadata = metadata();
+
+        let invalid = [
+            Trigger::new(Condition::Time { seconds: -1. }, vec![]),
+            Trigger::new(
+                Condition::AreaEntered {
+                    player: Player::Player3,
+                    area: Area::new(Vec2::ZERO, 10.),
+                },
+                vec![],
+            ),
+            Trigger::new(
+                Condition::UnitDestroyed {
+                    player: Player::Player1,
+                    count: 0,
+                },
+                vec![],
+            ),
+            Trigger::new(
+                Condition::Time { seconds: 1. },
+                vec![Action::RevealArea {
+                    area: Area::new(Vec2::splat(60.), 5.),
+                }],
+            ),
+            Trigger::new(
+                Condition::Time { seconds: 1. },
+                vec![Action::SpawnWave {
+                    player: Player::Player1,
+                    units: vec![],
+                    position: Vec2::ZERO,
+                    target: None,
+                }],
+            ),
+            Trigger::new(
+                Condition::Time { seconds: 1. },
+                vec![Action::SetObjective {
+                    id: 1,
+                    description: String::new(),
+                    status: ObjectiveStatus::Active,
+                }],
+            ),
+        ];
+        for trigger in invalid {
+            assert!(trigger.validate(&metadata).is_err());
+        }
+
+        let valid = Trigger::new(
+            Condition::AreaEntered {
+                player: Player::Player2,
+                area: Area::new(Vec2::new(-20., 30.), 10.),
+            },
+            vec![Action::EndGame { won: false }],
+        );
+        assert!(valid.validate(&metadata).is_ok());
+    }
+
+    #[test]
+    fn test_area() {
+        let area = Area::new(Vec2::new(1., 2.), 2.);
+        assert!(area.contains(Vec2::new(1., 2.)));
+        assert!(area.contains(Vec2::new(3., 2.)));
+        assert!(!area.contains(Vec2::new(3., 3.)));
+    }
+}
@@ -0,0 +1,273 @@
+use std::sync::Arc;
+
+use async_std::{path::PathBuf, stream::StreamExt};
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
+use de_core::{
+    assets::asset_path,
+    gconfig::{GameConfig, LocalPlayers},
+    gresult::GameResult,
+    log_full_error,
+    state::AppState,
+};
+use de_gui::{ButtonCommands, GuiCommands, LabelCommands, OuterStyle};
+use de_loader::campaign::{
+    load_campaign, load_progress, store_progress, Campaign, CampaignProgress, CAMPAIGN_FILE_SUFFIX,
+};
+use de_types::player::Player;
+
+use crate::{menu::Menu, MenuState};
+
+pub(crate) struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Campaign), setup)
+            .add_systems(OnExit(MenuState::Campaign), cleanup)
+            .add_systems(OnEnter(AppState::InMenu), record_result)
+            .add_systems(
+                Update,
+                (init_buttons, button_system).run_if(in_state(MenuState::Campaign)),
+            );
+    }
+}
+
+/// A campaign together with the progress of the user.
+struct CampaignEntry {
+    campaign: Arc<Campaign>,
+    progress: CampaignProgress,
+}
+
+#[derive(Resource)]
+struct LoadingTask(Task<Vec<CampaignEntry>>);
+
+#[derive(Resource)]
+struct Campaigns(Vec<CampaignEntry>);
+
+#[derive(Resource)]
+struct ListNode(Entity);
+
+#[derive(Component, Clone, Copy)]
+struct ScenarioButton {
+    campaign: usize,
+    scenario: usize,
+}
+
+/// Scenario of a campaign being currently played.
+#[derive(Resource)]
+struct CampaignRun {
+    campaign: Arc<Campaign>,
+    progress: CampaignProgress,
+    scenario: usize,
+}
+
+fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
+    let task = IoTaskPool::get().spawn(load_campaigns());
+    commands.insert_resource(LoadingTask(task));
+
+    let column_node = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.),
+                height: Val::Percent(100.),
+                margin: UiRect::all(Val::Auto),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(menu.root_node()).add_child(column_node);
+    commands.insert_resource(ListNode(column_node));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<LoadingTask>();
+    commands.remove_resource::<Campaigns>();
+    commands.remove_resource::<ListNode>();
+}
+
+/// Loads all bundled campaigns and their progress. Campaigns which cannot be
+/// loaded are skipped.
+async fn load_campaigns() -> Vec<CampaignEntry> {
+    let mut entries = Vec::new();
+
+    let dir: PathBuf = asset_path("campaigns").into();
+    let mut dir_entries = match async_std::fs::read_dir(dir).await {
+        Ok(dir_entries) => dir_entries,
+        Err(err) => {
+            log_full_error!(err);
+            return entries;
+        }
+    };
+
+    while let Some(res) = dir_entries.next().await {
+        let path = match res {
+            Ok(entry) => entry.path(),
+            Err(err) => {
+                log_full_error!(err);
+                continue;
+            }
+        };
+        if !path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.ends_with(CAMPAIGN_FILE_SUFFIX))
+        {
+            continue;
+        }
+
+        let campaign = match load_campaign(&path).await {
+            Ok(campaign) => campaign,
+            Err(err) => {
+                warn!("Skipping campaign {}.", path.display());
+                log_full_error!(err);
+                continue;
+            }
+        };
+        let progress = match load_progress(&campaign).await {
+            Ok(progress) => progress,
+            Err(err) => {
+                warn!("Progress of campaign {} is reset.", campaign.id());
+                log_full_error!(err);
+                CampaignProgress::default()
+            }
+        };
+
+        entries.push(CampaignEntry {
+            campaign: Arc::new(campaign),
+            progress,
+        });
+    }
+
+    entries.sort_by(|a, b| a.campaign.name().cmp(b.campaign.name()));
+    entries
+}
+
+fn init_buttons(mut commands: GuiCommands, node: Res<ListNode>, task: Option<ResMut<LoadingTask>>) {
+    let Some(mut task) = task else { return };
+    let Some(entries) = future::block_on(future::poll_once(&mut task.0)) else {
+        return;
+    };
+    commands.remove_resource::<LoadingTask>();
+
+    if entries.is_empty() {
+        let label = commands
+            .spawn_label(item_style(), "No campaigns available.")
+            .id();
+        commands.entity(node.0).add_child(label);
+    }
+
+    for (campaign_index, entry) in entries.iter().enumerate() {
+        let label = commands
+            .spawn_label(item_style(), entry.campaign.name())
+            .id();
+        commands.entity(node.0).add_child(label);
+
+        for (index, scenario) in entry.campaign.scenarios().iter().enumerate() {
+            let item = if entry.campaign.is_unlocked(index, &entry.progress) {
+                let caption = if entry.progress.is_completed(scenario.id()) {
+                    format!("{} (completed)", scenario.title())
+                } else {
+                    scenario.title().to_owned()
+                };
+                commands
+                    .spawn_button(item_style(), caption)
+                    .insert(ScenarioButton {
+                        campaign: campaign_index,
+                        scenario: index,
+                    })
+                    .id()
+            } else {
+                commands
+                    .spawn_label(item_style(), format!("{} (locked)", scenario.title()))
+                    .id()
+            };
+            commands.entity(node.0).add_child(item);
+        }
+    }
+
+    commands.insert_resource(Campaigns(entries));
+}
+
+fn item_style() -> OuterStyle {
+    OuterStyle {
+        width: Val::Percent(100.),
+        height: Val::Percent(8.),
+        margin: UiRect::new(
+            Val::Percent(0.),
+            Val::Percent(0.),
+            Val::Percent(2.),
+            Val::Percent(2.),
+        ),
+    }
+}
+
+fn button_system(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    campaigns: Option<Res<Campaigns>>,
+    interactions: Query<(&Interaction, &ScenarioButton), Changed<Interaction>>,
+) {
+    let Some(campaigns) = campaigns else { return };
+
+    for (&interaction, &button) in interactions.iter() {
+        if interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let entry = &campaigns.0[button.campaign];
+        let scenario = &entry.campaign.scenarios()[button.scenario];
+        info!(
+            "Starting scenario {} of campaign {}.",
+            scenario.id(),
+            entry.campaign.id()
+        );
+
+        commands.insert_resource(GameConfig::new(
+            scenario.map(),
+            false,
+            LocalPlayers::from_max_player(Player::Player1, scenario.max_player()),
+        ));
+        commands.insert_resource(CampaignRun {
+            campaign: Arc::clone(&entry.campaign),
+            progress: entry.progress.clone(),
+            scenario: button.scenario,
+        });
+        next_state.set(AppState::InGame);
+    }
+}
+
+/// Persists campaign progress once a won campaign scenario finishes.
+fn record_result(
+    mut commands: Commands,
+    result: Option<Res<GameResult>>,
+    run: Option<ResMut<CampaignRun>>,
+) {
+    let Some(mut run) = run else { return };
+    commands.remove_resource::<CampaignRun>();
+
+    let Some(GameResult::Finished(result)) = result.as_deref() else {
+        return;
+    };
+    if !result.won() {
+        return;
+    }
+
+    let campaign = Arc::clone(&run.campaign);
+    let scenario = run.scenario;
+    run.progress.complete(&campaign.scenarios()[scenario]);
+    let progress = run.progress.clone();
+
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Err(err) = store_progress(&campaign, &progress).await {
+                log_full_error!(err);
+            }
+        })
+        .detach();
+}
@@ -1,17 +1,21 @@
 use aftergame::AfterGamePlugin;
 use bevy::{app::PluginGroupBuilder, prelude::*};
+use campaign::CampaignPlugin;
 use de_core::{gresult::GameResult, nested_state, state::AppState};
 use mainmenu::MainMenuPlugin;
 use mapselection::MapSelectionPlugin;
 use menu::{MenuPlugin, ScreenStatePlugin};
 use multiplayer::MultiplayerPlugin;
+use profile::ProfilePlugin;
 use singleplayer::SinglePlayerPlugin;
 
 mod aftergame;
+mod campaign;
 mod mainmenu;
 mod mapselection;
 mod menu;
 mod multiplayer;
+mod profile;
 mod singleplayer;
 
 pub struct MenuPluginGroup;
@@ -24,9 +28,11 @@ impl PluginGroup for MenuPluginGroup {
             .add(ScreenStatePlugin::<MenuState>::default())
             .add(MainMenuPlugin)
             .add(MapSelectionPlugin)
+            .add(CampaignPlugin)
             .add(SinglePlayerPlugin)
             .add(MultiplayerPlugin)
             .add(AfterGamePlugin)
+            .add(ProfilePlugin)
     }
 }
 
@@ -36,9 +42,11 @@ nested_state!(
     enter = menu_entered_system,
     variants = {
         MainMenu,
+        Campaign,
         SinglePlayerGame,
         Multiplayer,
         AfterGame,
+        Profile,
     }
 );
 
@@ -1,5 +1,6 @@
 use bevy::{app::AppExit, prelude::*};
-use de_gui::{ButtonCommands, GuiCommands, OuterStyle};
+use de_gui::{ButtonCommands, GuiCommands, OuterStyle, ToastEvent};
+use de_loader::bundle::MountedBundles;
 
 use crate::{menu::Menu, MenuState};
 
@@ -35,6 +36,12 @@ fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
         .id();
     commands.entity(menu.root_node()).add_child(column_node);
 
+    button(
+        &mut commands,
+        column_node,
+        ButtonAction::SwithState(MenuState::Campaign),
+        "Campaign",
+    );
     button(
         &mut commands,
         column_node,
@@ -47,6 +54,12 @@ fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
         ButtonAction::SwithState(MenuState::Multiplayer),
         "Multiplayer",
     );
+    button(
+        &mut commands,
+        column_node,
+        ButtonAction::SwithState(MenuState::Profile),
+        "Profile",
+    );
     button(&mut commands, column_node, ButtonAction::Quit, "Quit Game");
 }
 
@@ -72,12 +85,21 @@ fn button(commands: &mut GuiCommands, parent: Entity, action: ButtonAction, capt
 
 fn button_system(
     mut next_state: ResMut<NextState<MenuState>>,
+    bundles: Option<Res<MountedBundles>>,
     mut exit: EventWriter<AppExit>,
+    mut toasts: EventWriter<ToastEvent>,
     interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
 ) {
     for (&interaction, &action) in interactions.iter() {
         if let Interaction::Pressed = interaction {
             match action {
+                ButtonAction::SwithState(MenuState::Multiplayer)
+                    if bundles.as_ref().map_or(false, |b| b.overrides_objects()) =>
+                {
+                    toasts.send(ToastEvent::new(
+                        "Multiplayer is not available while a mounted bundle overrides objects.",
+                    ));
+                }
                 ButtonAction::SwithState(state) => next_state.set(state),
                 ButtonAction::Quit => {
                     exit.send(AppExit);
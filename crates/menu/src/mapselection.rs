@@ -1,14 +1,15 @@
 use std::path::{Path, PathBuf};
 
-use async_std::{fs, io, stream::StreamExt};
+use async_std::{fs, io, path::PathBuf as AsyncPathBuf, stream::StreamExt};
 use bevy::{
     prelude::*,
     tasks::{futures_lite::future, IoTaskPool, Task},
 };
-use de_core::{assets::asset_path, log_full_error, state::AppState};
+use de_core::{assets::asset_path, fs::maps_dir, log_full_error, state::AppState};
 use de_gui::{ButtonCommands, GuiCommands, OuterStyle};
 use de_map::{
-    io::{load_metadata, MapLoadingError, MAP_FILE_SUFFIX},
+    hash::MapHash,
+    io::{load_map, MapLoadingError, MAP_FILE_SUFFIX},
     meta::MapMetadata,
 };
 use thiserror::Error;
@@ -25,7 +26,7 @@ impl Plugin for MapSelectionPlugin {
             .add_systems(
                 Update,
                 (
-                    (init_buttons, button_system).run_if(in_state(MapState::On)),
+                    (init_buttons, button_system, refresh_system).run_if(in_state(MapState::On)),
                     select_map_system
                         .run_if(in_state(AppState::InMenu))
                         .run_if(on_event::<SelectMapEvent>()),
@@ -72,6 +73,13 @@ impl MapSelectedEvent {
 #[derive(Resource)]
 struct PopUpNode(Entity);
 
+/// Node containing buttons of all available maps.
+#[derive(Resource)]
+struct ListNode(Entity);
+
+#[derive(Component)]
+struct RefreshButton;
+
 #[derive(Resource)]
 struct LoadingTask(Task<Result<Vec<MapEntry>, LoadingError>>);
 
@@ -100,9 +108,16 @@ pub enum LoadingError {
     Map { source: MapLoadingError },
 }
 
-fn setup(mut commands: Commands) {
-    let task = IoTaskPool::get().spawn(load_available_maps());
-    commands.insert_resource(LoadingTask(task));
+#[derive(Error, Debug)]
+pub(crate) enum FindMapError {
+    #[error("Map {0} is not available locally")]
+    NotFound(String),
+    #[error(transparent)]
+    Map { source: MapLoadingError },
+}
+
+fn setup(mut commands: GuiCommands) {
+    start_loading(&mut commands);
 
     let node_id = commands
         .spawn(NodeBundle {
@@ -122,27 +137,6 @@ fn setup(mut commands: Commands) {
         })
         .id();
     commands.insert_resource(PopUpNode(node_id));
-}
-
-fn init_buttons(
-    mut commands: GuiCommands,
-    node: Res<PopUpNode>,
-    task: Option<ResMut<LoadingTask>>,
-) {
-    let Some(mut task) = task else { return };
-    let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
-        return;
-    };
-
-    let map_entries = match result {
-        Ok(entries) => entries,
-        Err(err) => {
-            log_full_error!(err);
-            panic!("{}", err);
-        }
-    };
-
-    commands.remove_resource::<LoadingTask>();
 
     let column_node = commands
         .spawn(NodeBundle {
@@ -158,20 +152,81 @@ fn init_buttons(
             ..default()
         })
         .id();
+    commands.entity(node_id).add_child(column_node);
 
-    commands.entity(node.0).add_child(column_node);
+    let refresh_button = commands
+        .spawn_button(button_style(), "Refresh")
+        .insert(RefreshButton)
+        .id();
+    commands.entity(column_node).add_child(refresh_button);
+
+    let list_node = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(column_node).add_child(list_node);
+    commands.insert_resource(ListNode(list_node));
+}
+
+fn start_loading(commands: &mut Commands) {
+    let task = IoTaskPool::get().spawn(load_available_maps());
+    commands.insert_resource(LoadingTask(task));
+}
+
+fn init_buttons(mut commands: GuiCommands, node: Res<ListNode>, task: Option<ResMut<LoadingTask>>) {
+    let Some(mut task) = task else { return };
+    let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+        return;
+    };
+
+    let map_entries = match result {
+        Ok(entries) => entries,
+        Err(err) => {
+            log_full_error!(err);
+            panic!("{}", err);
+        }
+    };
+
+    commands.remove_resource::<LoadingTask>();
 
     for map in map_entries {
         let button = map_button(&mut commands, map);
-        commands.entity(column_node).add_child(button);
+        commands.entity(node.0).add_child(button);
     }
 }
 
 fn cleanup(mut commands: Commands, node: Res<PopUpNode>) {
     commands.remove_resource::<LoadingTask>();
+    commands.remove_resource::<ListNode>();
     commands.entity(node.0).despawn_recursive();
 }
 
+fn refresh_system(
+    mut commands: Commands,
+    node: Res<ListNode>,
+    task: Option<Res<LoadingTask>>,
+    interactions: Query<&Interaction, (Changed<Interaction>, With<RefreshButton>)>,
+) {
+    if task.is_some() {
+        return;
+    }
+
+    if interactions
+        .iter()
+        .any(|&interaction| interaction == Interaction::Pressed)
+    {
+        commands.entity(node.0).despawn_descendants();
+        start_loading(&mut commands);
+    }
+}
+
 fn button_system(
     mut next_state: ResMut<NextState<MapState>>,
     interactions: Query<(&Interaction, &MapEntry), Changed<Interaction>>,
@@ -188,11 +243,75 @@ fn button_system(
     }
 }
 
+/// Loads maps bundled with the game and maps from the user maps directory.
+/// Maps with the same hash as an already listed map are skipped.
 async fn load_available_maps() -> Result<Vec<MapEntry>, LoadingError> {
-    let maps_dir = asset_path("maps");
+    let mut maps = load_maps_dir(asset_path("maps").into(), false).await?;
+
+    match maps_dir() {
+        Ok(dir) => {
+            if dir.is_dir().await {
+                match load_maps_dir(dir, true).await {
+                    Ok(user_maps) => maps.extend(user_maps),
+                    Err(err) => {
+                        log_full_error!(err);
+                    }
+                }
+            }
+        }
+        Err(err) => warn!("User maps directory cannot be established: {err}"),
+    }
+
+    let mut map_entries = remove_duplicates(maps);
+    map_entries.sort_by(|a, b| a.metadata().name().cmp(b.metadata().name()));
+    Ok(map_entries)
+}
+
+/// Searches for the file of a map with a given hash in the same directories
+/// the maps are selected from: among the maps bundled with the game first and
+/// in the user maps directory second.
+pub(crate) async fn find_map(hash: MapHash) -> Result<AsyncPathBuf, FindMapError> {
+    let mut dirs: Vec<AsyncPathBuf> = vec![asset_path("maps").into()];
+    match maps_dir() {
+        Ok(dir) => dirs.push(dir),
+        Err(err) => warn!("User maps directory cannot be established: {err}"),
+    }
 
+    for dir in dirs {
+        let path = hash.construct_path(dir);
+        if path.is_file().await {
+            return Ok(path);
+        }
+    }
+
+    Err(FindMapError::NotFound(hash.to_hex()))
+}
+
+/// Searches for the file of a map with a given hash (see [`find_map`]) and
+/// returns hash computed from content of the found file. The returned hash
+/// differs from the searched one if the local file does not hold the map.
+pub(crate) async fn hash_local_map(hash: MapHash) -> Result<MapHash, FindMapError> {
+    let path = find_map(hash).await?;
+    match load_map(path.as_path()).await {
+        Ok(map) => Ok(map.compute_hash()),
+        Err(err) => Err(FindMapError::Map { source: err }),
+    }
+}
+
+/// Loads all maps from a directory.
+///
+/// # Arguments
+///
+/// * `dir` - directory to be scanned (non-recursively).
+///
+/// * `skip_invalid` - if true, maps which cannot be loaded are skipped with
+///   a warning instead of failing the whole scan.
+async fn load_maps_dir(
+    dir: AsyncPathBuf,
+    skip_invalid: bool,
+) -> Result<Vec<(MapHash, MapEntry)>, LoadingError> {
     let mut map_entries = Vec::new();
-    let mut dir_entries = match fs::read_dir(maps_dir).await {
+    let mut dir_entries = match fs::read_dir(dir).await {
         Ok(entries) => entries,
         Err(err) => return Err(LoadingError::Io { source: err }),
     };
@@ -214,32 +333,60 @@ async fn load_available_maps() -> Result<Vec<MapEntry>, LoadingError> {
             continue;
         }
 
-        let metadata = match load_metadata(path.as_path()).await {
-            Ok(meta) => meta,
+        let map = match load_map(path.as_path()).await {
+            Ok(map) => map,
+            Err(err) if skip_invalid => {
+                warn!("Skipping invalid map {}: {err}", path.display());
+                continue;
+            }
             Err(err) => return Err(LoadingError::Map { source: err }),
         };
-        map_entries.push(MapEntry::new(path.into(), metadata));
+        map_entries.push((
+            map.compute_hash(),
+            MapEntry::new(path.into(), map.metadata().clone()),
+        ));
     }
 
-    map_entries.sort_by(|a, b| a.metadata().name().cmp(b.metadata().name()));
     Ok(map_entries)
 }
 
+/// Removes all but the first map entry with the same hash.
+fn remove_duplicates(maps: Vec<(MapHash, MapEntry)>) -> Vec<MapEntry> {
+    let mut hashes: Vec<MapHash> = Vec::with_capacity(maps.len());
+    let mut entries = Vec::with_capacity(maps.len());
+
+    for (hash, entry) in maps {
+        if hashes.contains(&hash) {
+            warn!(
+                "Skipping map {} as it is a duplicate of an already listed map.",
+                entry.path().display()
+            );
+            continue;
+        }
+
+        hashes.push(hash);
+        entries.push(entry);
+    }
+
+    entries
+}
+
+fn button_style() -> OuterStyle {
+    OuterStyle {
+        width: Val::Percent(100.),
+        height: Val::Percent(8.),
+        margin: UiRect::new(
+            Val::Percent(0.),
+            Val::Percent(0.),
+            Val::Percent(2.),
+            Val::Percent(2.),
+        ),
+    }
+}
+
 fn map_button(commands: &mut GuiCommands, map: MapEntry) -> Entity {
     commands
-        .spawn_button(
-            OuterStyle {
-                width: Val::Percent(100.),
-                height: Val::Percent(8.),
-                margin: UiRect::new(
-                    Val::Percent(0.),
-                    Val::Percent(0.),
-                    Val::Percent(2.),
-                    Val::Percent(2.),
-                ),
-            },
-            map.metadata().name(),
-        )
+        .spawn_button(button_style(), map.metadata().name())
         .insert(map)
         .id()
 }
@@ -50,6 +50,7 @@ enum ButtonAction {
 struct Inputs {
     name: Entity,
     max_players: Entity,
+    starting_resources: Entity,
     map: Entity,
 }
 
@@ -69,12 +70,16 @@ fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
     let max_players_row_id = row(&mut commands, column_id);
     let max_players_id = text_input(&mut commands, max_players_row_id, "Max Players");
 
+    let resources_row_id = row(&mut commands, column_id);
+    let resources_id = text_input(&mut commands, resources_row_id, "Starting Resources");
+
     let map_row_id = row(&mut commands, column_id);
     let map_id = map_button(&mut commands, map_row_id);
 
     commands.insert_resource(Inputs {
         name: name_id,
         max_players: max_players_id,
+        starting_resources: resources_id,
         map: map_id,
     });
 
@@ -257,7 +262,23 @@ fn create_game_system(
         }
     };
 
-    let game_config = GameConfig::new(name, max_players, selected_map.0.clone());
+    let resources = texts.text(inputs.starting_resources).unwrap();
+    let starting_resources: u32 = if resources.is_empty() {
+        0
+    } else {
+        match resources.parse() {
+            Ok(value) => value,
+            Err(error) => {
+                toasts.send(ToastEvent::new(format!(
+                    "Invalid starting resources: {error}"
+                )));
+                return;
+            }
+        }
+    };
+
+    let game_config = GameConfig::new(name, max_players, selected_map.0.clone())
+        .with_starting_resources(starting_resources);
     if let Err(error) = game_config.validate() {
         toasts.send(ToastEvent::new(format!("{error}")));
         return;
@@ -1,15 +1,27 @@
+use std::net::SocketAddr;
+
 use bevy::prelude::*;
+use de_multiplayer::DiscoveredGame;
 
+use super::MultiplayerState;
 use crate::MenuState;
 
 pub(super) struct CurrentGamePlugin;
 
 impl Plugin for CurrentGamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnExit(MenuState::Multiplayer), cleanup);
+        app.add_systems(OnExit(MenuState::Multiplayer), cleanup)
+            .add_systems(OnEnter(MultiplayerState::SignIn), cleanup_direct)
+            .add_systems(
+                OnEnter(MultiplayerState::GameListing),
+                (cleanup_lan, cleanup_direct),
+            )
+            .add_systems(OnEnter(MultiplayerState::LanGames), cleanup_lobby);
     }
 }
 
+/// Name of a game registered at DE Lobby which is being joined or was
+/// joined.
 #[derive(Resource)]
 pub(super) struct GameNameRes(String);
 
@@ -23,6 +35,50 @@ impl GameNameRes {
     }
 }
 
+/// A game discovered in the local network which is being joined or was
+/// joined. Such games are joined without DE Lobby.
+#[derive(Resource)]
+pub(super) struct LanGameRes(DiscoveredGame);
+
+impl LanGameRes {
+    pub(super) fn new(game: DiscoveredGame) -> Self {
+        Self(game)
+    }
+
+    pub(super) fn game(&self) -> &DiscoveredGame {
+        &self.0
+    }
+}
+
+/// Address of a DE Connector main server entered by the player. Games are
+/// searched for and created at this DE Connector without DE Lobby.
+#[derive(Resource)]
+pub(super) struct DirectConnectionRes(SocketAddr);
+
+impl DirectConnectionRes {
+    pub(super) fn new(addr: SocketAddr) -> Self {
+        Self(addr)
+    }
+
+    pub(super) fn addr(&self) -> SocketAddr {
+        self.0
+    }
+}
+
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<GameNameRes>();
+    commands.remove_resource::<LanGameRes>();
+    commands.remove_resource::<DirectConnectionRes>();
+}
+
+fn cleanup_direct(mut commands: Commands) {
+    commands.remove_resource::<DirectConnectionRes>();
+}
+
+fn cleanup_lan(mut commands: Commands) {
+    commands.remove_resource::<LanGameRes>();
+}
+
+fn cleanup_lobby(mut commands: Commands) {
+    commands.remove_resource::<GameNameRes>();
 }
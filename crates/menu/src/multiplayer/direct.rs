@@ -0,0 +1,187 @@
+use std::net::{AddrParseError, IpAddr, SocketAddr};
+
+use bevy::prelude::*;
+use de_conf::Configuration;
+use de_gui::{
+    ButtonCommands, GuiCommands, LabelCommands, OuterStyle, SetFocusEvent, TextBoxCommands,
+    TextBoxQuery, ToastEvent,
+};
+
+use super::{current::DirectConnectionRes, MultiplayerState};
+use crate::menu::Menu;
+
+pub(super) struct DirectConnectPlugin;
+
+impl Plugin for DirectConnectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MultiplayerState::DirectConnect), setup)
+            .add_systems(OnExit(MultiplayerState::DirectConnect), cleanup)
+            .add_systems(
+                Update,
+                button_system
+                    .run_if(resource_exists::<AddressInput>)
+                    .run_if(in_state(MultiplayerState::DirectConnect)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct AddressInput(Entity);
+
+#[derive(Component, Clone, Copy)]
+enum Action {
+    Host,
+    Find,
+    Back,
+}
+
+fn setup(mut commands: GuiCommands, menu: Res<Menu>, mut focus: EventWriter<SetFocusEvent>) {
+    let column = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(50.),
+                height: Val::Percent(100.),
+                margin: UiRect::all(Val::Auto),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(menu.root_node()).add_child(column);
+
+    let address_row = row(&mut commands, column);
+    let caption = commands
+        .spawn_label(
+            OuterStyle {
+                width: Val::Percent(35.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            "Server:",
+        )
+        .id();
+    commands.entity(address_row).add_child(caption);
+    let input = commands
+        .spawn_text_box(
+            OuterStyle {
+                width: Val::Percent(65.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            false,
+        )
+        .id();
+    commands.entity(address_row).add_child(input);
+    focus.send(SetFocusEvent::some(input));
+    commands.insert_resource(AddressInput(input));
+
+    let buttons_row = row(&mut commands, column);
+    button(&mut commands, buttons_row, Action::Host, Val::Percent(48.));
+    button(&mut commands, buttons_row, Action::Find, Val::Percent(48.));
+
+    let back_row = row(&mut commands, column);
+    button(&mut commands, back_row, Action::Back, Val::Percent(100.));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<AddressInput>();
+}
+
+fn row(commands: &mut GuiCommands, parent: Entity) -> Entity {
+    let id = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                width: Val::Percent(100.),
+                height: Val::Percent(8.),
+                margin: UiRect::vertical(Val::Percent(2.)),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(parent).add_child(id);
+    id
+}
+
+fn button(commands: &mut GuiCommands, parent: Entity, action: Action, width: Val) {
+    let caption = match action {
+        Action::Host => "Host Game",
+        Action::Find => "Find Games",
+        Action::Back => "Back",
+    };
+
+    let id = commands
+        .spawn_button(
+            OuterStyle {
+                width,
+                height: Val::Percent(100.),
+                ..default()
+            },
+            caption,
+        )
+        .insert(action)
+        .id();
+    commands.entity(parent).add_child(id);
+}
+
+fn button_system(
+    mut commands: Commands,
+    config: Res<Configuration>,
+    input: Res<AddressInput>,
+    texts: TextBoxQuery,
+    interactions: Query<(&Interaction, &Action), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    for (&interaction, &action) in interactions.iter() {
+        if let Interaction::Pressed = interaction {
+            let next = match action {
+                Action::Host => MultiplayerState::GameCreation,
+                Action::Find => MultiplayerState::LanGames,
+                Action::Back => {
+                    next_state.set(MultiplayerState::SignIn);
+                    break;
+                }
+            };
+
+            let text = texts.text(input.0).unwrap();
+            let addr = match parse_addr(&text, config.multiplayer().connector()) {
+                Ok(addr) => addr,
+                Err(error) => {
+                    toasts.send(ToastEvent::new(format!("Invalid server address: {error}")));
+                    break;
+                }
+            };
+
+            commands.insert_resource(DirectConnectionRes::new(addr));
+            next_state.set(next);
+            break;
+        }
+    }
+}
+
+/// Parses address of a DE Connector main server.
+///
+/// # Arguments
+///
+/// * `text` - an IP address with an optional port.
+///
+/// * `default` - address used if `text` is empty. Its port is used if `text`
+///   has no port.
+fn parse_addr(text: &str, default: SocketAddr) -> Result<SocketAddr, AddrParseError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(default);
+    }
+
+    text.parse::<SocketAddr>().or_else(|_| {
+        text.parse::<IpAddr>()
+            .map(|ip| SocketAddr::new(ip, default.port()))
+    })
+}
@@ -30,6 +30,8 @@ struct GamesTable(Entity);
 #[derive(Component)]
 enum ButtonAction {
     Create,
+    LanGames,
+    DirectConnect,
     Join(String),
 }
 
@@ -54,7 +56,24 @@ fn setup(
         .id();
     commands.entity(menu.root_node()).add_child(column_id);
 
-    create_game_button(&mut commands, column_id);
+    button(
+        &mut commands,
+        column_id,
+        ButtonAction::Create,
+        "Create Game",
+    );
+    button(
+        &mut commands,
+        column_id,
+        ButtonAction::LanGames,
+        "LAN Games",
+    );
+    button(
+        &mut commands,
+        column_id,
+        ButtonAction::DirectConnect,
+        "Direct Connect",
+    );
     let table_id = table(&mut commands, column_id);
     commands.insert_resource(GamesTable(table_id));
     requests.send(RequestEvent::new("list-games", ListGamesRequest));
@@ -64,7 +83,7 @@ fn cleanup(mut commands: Commands) {
     commands.remove_resource::<GamesTable>();
 }
 
-fn create_game_button(commands: &mut GuiCommands, parent_node: Entity) {
+fn button(commands: &mut GuiCommands, parent_node: Entity, action: ButtonAction, caption: &str) {
     let button_id = commands
         .spawn_button(
             OuterStyle {
@@ -72,9 +91,9 @@ fn create_game_button(commands: &mut GuiCommands, parent_node: Entity) {
                 height: Val::Percent(8.),
                 margin: UiRect::bottom(Val::Percent(1.)),
             },
-            "Create Game",
+            caption,
         )
-        .insert(ButtonAction::Create)
+        .insert(action)
         .id();
     commands.entity(parent_node).add_child(button_id);
 }
@@ -85,7 +104,7 @@ fn table(commands: &mut GuiCommands, parent_node: Entity) -> Entity {
             style: Style {
                 flex_direction: FlexDirection::Column,
                 width: Val::Percent(100.),
-                height: Val::Percent(91.),
+                height: Val::Percent(73.),
                 margin: UiRect::all(Val::Auto),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::FlexStart,
@@ -194,6 +213,8 @@ fn button_system(
         if let Interaction::Pressed = interaction {
             match action {
                 ButtonAction::Create => next_state.set(MultiplayerState::GameCreation),
+                ButtonAction::LanGames => next_state.set(MultiplayerState::LanGames),
+                ButtonAction::DirectConnect => next_state.set(MultiplayerState::DirectConnect),
                 ButtonAction::Join(name) => {
                     commands.insert_resource(GameNameRes::new(name));
                     next_state.set(MultiplayerState::GameJoining);
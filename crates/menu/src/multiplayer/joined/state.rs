@@ -1,24 +1,30 @@
-use bevy::prelude::*;
+use async_std::path::PathBuf;
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
 use de_core::{
-    assets::asset_path,
-    gconfig::{GameConfig, LocalPlayers},
+    gconfig::{GameConfig, Handicap, LocalPlayers, StartingConditions},
     state::AppState,
 };
 use de_gui::ToastEvent;
 use de_lobby_client::GetGameRequest;
-use de_lobby_model::GameMap;
+use de_lobby_model::{GameMap, GamePlayer};
 use de_map::hash::MapHash;
 use de_messages::Readiness;
 use de_multiplayer::{
-    GameReadinessEvent, PeerJoinedEvent, PeerLeftEvent, ShutdownMultiplayerEvent,
+    GameReadinessEvent, PeerJoinedEvent, PeerLeftEvent, PlayerNames, ShutdownMultiplayerEvent,
 };
-use de_types::player::Player;
+use de_types::player::{Player, PlayerRange};
 
 use super::ui::RefreshPlayersEvent;
-use crate::multiplayer::{
-    current::GameNameRes,
-    requests::{Receiver, Sender},
-    MultiplayerState,
+use crate::{
+    mapselection::{find_map, FindMapError},
+    multiplayer::{
+        current::{GameNameRes, LanGameRes},
+        requests::{Receiver, Sender},
+        MultiplayerState,
+    },
 };
 
 pub(super) struct JoinedGameStatePlugin;
@@ -26,18 +32,25 @@ pub(super) struct JoinedGameStatePlugin;
 impl Plugin for JoinedGameStatePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<StartGameEvent>()
-            .add_systems(OnEnter(MultiplayerState::GameJoined), (setup, refresh))
+            .add_systems(
+                OnEnter(MultiplayerState::GameJoined),
+                (setup, refresh.run_if(resource_exists::<GameNameRes>)),
+            )
             .add_systems(OnExit(MultiplayerState::GameJoined), cleanup)
             .add_systems(
                 Update,
                 (
                     refresh
-                        .run_if(on_event::<PeerJoinedEvent>().or_else(on_event::<PeerLeftEvent>())),
+                        .run_if(on_event::<PeerJoinedEvent>().or_else(on_event::<PeerLeftEvent>()))
+                        .run_if(resource_exists::<GameNameRes>),
                     handle_get_response,
                     start
                         .run_if(on_event::<StartGameEvent>())
-                        .after(handle_get_response),
-                    handle_readiness,
+                        .after(handle_get_response)
+                        .after(handle_lan_readiness),
+                    handle_readiness.run_if(resource_exists::<GameNameRes>),
+                    handle_lan_readiness.run_if(resource_exists::<LanGameRes>),
+                    enter_game.run_if(resource_exists::<MapSearchTask>),
                 )
                     .run_if(in_state(MultiplayerState::GameJoined)),
             );
@@ -45,7 +58,11 @@ impl Plugin for JoinedGameStatePlugin {
 }
 
 #[derive(Event)]
-struct StartGameEvent(GameMap);
+struct StartGameEvent {
+    map: GameMap,
+    players: Vec<GamePlayer>,
+    starting_resources: u32,
+}
 
 #[derive(Resource)]
 pub(crate) struct LocalPlayerRes(Player);
@@ -54,11 +71,22 @@ impl LocalPlayerRes {
     pub(crate) fn new(player: Player) -> Self {
         Self(player)
     }
+
+    pub(crate) fn player(&self) -> Player {
+        self.0
+    }
 }
 
 #[derive(Resource)]
 struct ReadyRes(bool);
 
+/// Search for the local file of the map of the game being started.
+#[derive(Resource)]
+struct MapSearchTask {
+    task: Task<Result<PathBuf, FindMapError>>,
+    starting_resources: u32,
+}
+
 fn setup(mut commands: Commands) {
     commands.insert_resource(ReadyRes(false));
 }
@@ -70,6 +98,7 @@ fn cleanup(
 ) {
     commands.remove_resource::<LocalPlayerRes>();
     commands.remove_resource::<ReadyRes>();
+    commands.remove_resource::<MapSearchTask>();
 
     if state.as_ref() != &AppState::InGame {
         shutdown.send(ShutdownMultiplayerEvent);
@@ -95,6 +124,29 @@ fn handle_readiness(
     ready.0 = true;
 }
 
+/// Starts a game joined via LAN discovery. The game is started directly with
+/// the announced map and default game options because no DE Lobby is
+/// involved.
+fn handle_lan_readiness(
+    mut events: EventReader<GameReadinessEvent>,
+    lan_game: Res<LanGameRes>,
+    mut start_events: EventWriter<StartGameEvent>,
+) {
+    if events.read().all(|e| **e != Readiness::Ready) {
+        return;
+    }
+
+    let description = lan_game.game().game().description();
+    start_events.send(StartGameEvent {
+        map: GameMap::new(
+            description.map_hash().to_owned(),
+            description.map_name().to_owned(),
+        ),
+        players: Vec::new(),
+        starting_resources: 0,
+    });
+}
+
 fn handle_get_response(
     mut multi_state: ResMut<NextState<MultiplayerState>>,
     mut receiver: Receiver<GetGameRequest>,
@@ -109,7 +161,11 @@ fn handle_get_response(
                 refresh.send(RefreshPlayersEvent::from_slice(game.players()));
 
                 if ready.0 {
-                    start_events.send(StartGameEvent(game.setup().config().map().clone()));
+                    start_events.send(StartGameEvent {
+                        map: game.setup().config().map().clone(),
+                        players: game.players().to_vec(),
+                        starting_resources: game.setup().config().starting_resources(),
+                    });
                 }
             }
             Err(error) => {
@@ -123,8 +179,6 @@ fn handle_get_response(
 fn start(
     mut commands: Commands,
     mut events: EventReader<StartGameEvent>,
-    player: Res<LocalPlayerRes>,
-    mut app_state: ResMut<NextState<AppState>>,
     mut multi_state: ResMut<NextState<MultiplayerState>>,
     mut toasts: EventWriter<ToastEvent>,
 ) {
@@ -132,8 +186,8 @@ fn start(
         return;
     };
 
-    let map_path = match MapHash::from_hex(event.0.hash()) {
-        Ok(hash) => hash.construct_path(asset_path("maps")),
+    let hash = match MapHash::from_hex(event.map.hash()) {
+        Ok(hash) => hash,
         Err(error) => {
             toasts.send(ToastEvent::new(error));
             multi_state.set(MultiplayerState::SignIn);
@@ -141,10 +195,55 @@ fn start(
         }
     };
 
-    commands.insert_resource(GameConfig::new(
-        map_path,
-        true,
-        LocalPlayers::from_single(player.0),
-    ));
-    app_state.set(AppState::InGame);
+    commands.insert_resource(
+        event
+            .players
+            .iter()
+            .filter_map(|player| {
+                Player::try_from(player.info().ordinal())
+                    .ok()
+                    .map(|ordinal| (ordinal, player.username().to_owned()))
+            })
+            .collect::<PlayerNames>(),
+    );
+    let task = IoTaskPool::get().spawn(find_map(hash));
+    commands.insert_resource(MapSearchTask {
+        task,
+        starting_resources: event.starting_resources,
+    });
+}
+
+/// Starts the game once the local file of the game map is found.
+fn enter_game(
+    mut commands: Commands,
+    mut task: ResMut<MapSearchTask>,
+    player: Res<LocalPlayerRes>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut multi_state: ResMut<NextState<MultiplayerState>>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let Some(result) = future::block_on(future::poll_once(&mut task.task)) else {
+        return;
+    };
+    commands.remove_resource::<MapSearchTask>();
+
+    match result {
+        Ok(map_path) => {
+            let mut config = GameConfig::new(map_path, true, LocalPlayers::from_single(player.0));
+            let starting = StartingConditions::new(
+                task.starting_resources as f32,
+                Vec::new(),
+                Handicap::default(),
+            );
+            for player in PlayerRange::up_to(Player::Player4) {
+                config = config.with_starting(player, starting.clone());
+            }
+            commands.insert_resource(config);
+            app_state.set(AppState::InGame);
+        }
+        Err(error) => {
+            toasts.send(ToastEvent::new(error.to_string()));
+            multi_state.set(MultiplayerState::SignIn);
+        }
+    }
 }
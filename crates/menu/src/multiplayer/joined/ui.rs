@@ -1,10 +1,22 @@
 use bevy::prelude::*;
-use de_gui::{ButtonCommands, GuiCommands, LabelCommands, OuterStyle};
-use de_lobby_model::GamePlayer;
+use de_gui::{ButtonCommands, ButtonOps, GuiCommands, LabelCommands, OuterStyle, ToastEvent};
+use de_lobby_client::ReportUserRequest;
+use de_lobby_model::{GamePlayer, User, UserReport};
 use de_messages::Readiness;
-use de_multiplayer::SetReadinessEvent;
+use de_multiplayer::{
+    CountdownEvent, MuteUserEvent, MutedUsers, PeerLeftEvent, PeerReadinessEvent, SetReadinessEvent,
+};
+use de_types::player::Player;
 
-use crate::{menu::Menu, multiplayer::MultiplayerState};
+use super::LocalPlayerRes;
+use crate::{
+    menu::Menu,
+    multiplayer::{
+        current::GameNameRes,
+        requests::{Receiver, Sender},
+        MultiplayerState,
+    },
+};
 
 pub(super) struct JoinedGameUiPlugin;
 
@@ -15,13 +27,27 @@ impl Plugin for JoinedGameUiPlugin {
             .add_systems(OnExit(MultiplayerState::GameJoined), cleanup)
             .add_systems(
                 Update,
-                button_system.run_if(in_state(MultiplayerState::GameJoined)),
+                (
+                    button_system,
+                    handle_report_response,
+                    handle_peer_readiness.run_if(
+                        on_event::<PeerReadinessEvent>().or_else(on_event::<PeerLeftEvent>()),
+                    ),
+                    handle_countdown.run_if(on_event::<CountdownEvent>()),
+                )
+                    .run_if(in_state(MultiplayerState::GameJoined)),
             )
             .add_systems(
                 PostUpdate,
-                refresh
-                    .run_if(in_state(MultiplayerState::GameJoined))
-                    .run_if(on_event::<RefreshPlayersEvent>()),
+                (
+                    refresh.run_if(
+                        on_event::<RefreshPlayersEvent>()
+                            .or_else(resource_changed::<MutedUsers>)
+                            .or_else(resource_exists_and_changed::<ReadinessRes>),
+                    ),
+                    update_ready_button.run_if(resource_exists_and_changed::<ReadinessRes>),
+                )
+                    .run_if(in_state(MultiplayerState::GameJoined)),
             );
     }
 }
@@ -38,20 +64,59 @@ impl RefreshPlayersEvent {
 #[derive(Resource)]
 struct PlayersBoxRes(Entity);
 
-#[derive(Clone, Copy, Component)]
+/// Players of the game as received in the latest refresh.
+#[derive(Resource, Default)]
+struct PlayersRes(Vec<GamePlayer>);
+
+#[derive(Resource)]
+struct ReadyButtonRes(Entity);
+
+/// Readiness of the players before the game starts.
+#[derive(Resource, Default)]
+struct ReadinessRes {
+    /// Players, including the local player, which are ready.
+    ready: Vec<Player>,
+    /// Number of seconds remaining until the game starts or None if no
+    /// countdown is running.
+    countdown: Option<u8>,
+}
+
+impl ReadinessRes {
+    fn is_ready(&self, player: Player) -> bool {
+        self.ready.contains(&player)
+    }
+
+    fn set(&mut self, player: Player, ready: bool) {
+        self.ready.retain(|&other| other != player);
+        if ready {
+            self.ready.push(player);
+        }
+    }
+}
+
+#[derive(Clone, Component)]
 enum ButtonAction {
     Ready,
+    Mute(String),
+    Unmute(String),
+    Report(String),
 }
 
 fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
     let mid_panel_id = mid_panel(&mut commands, menu.root_node());
     let players_box_id = players_box(&mut commands, mid_panel_id);
     commands.insert_resource(PlayersBoxRes(players_box_id));
-    ready_button(&mut commands, mid_panel_id);
+    commands.init_resource::<PlayersRes>();
+    commands.init_resource::<ReadinessRes>();
+    let ready_button_id = ready_button(&mut commands, mid_panel_id);
+    commands.insert_resource(ReadyButtonRes(ready_button_id));
 }
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<PlayersBoxRes>();
+    commands.remove_resource::<PlayersRes>();
+    commands.remove_resource::<ReadinessRes>();
+    commands.remove_resource::<ReadyButtonRes>();
 }
 
 fn mid_panel(commands: &mut GuiCommands, parent_id: Entity) -> Entity {
@@ -95,21 +160,34 @@ fn players_box(commands: &mut GuiCommands, parent_id: Entity) -> Entity {
 fn refresh(
     mut commands: GuiCommands,
     mut events: EventReader<RefreshPlayersEvent>,
+    mut players: ResMut<PlayersRes>,
+    local_player: Res<LocalPlayerRes>,
+    muted: Res<MutedUsers>,
+    readiness: Res<ReadinessRes>,
     box_id: Res<PlayersBoxRes>,
 ) {
-    let Some(event) = events.read().last() else {
-        return;
-    };
+    if let Some(event) = events.read().last() {
+        players.0 = event.0.clone();
+    }
 
     commands.entity(box_id.0).despawn_descendants();
 
-    for player in event.0.iter() {
-        let row_id = row(&mut commands, player);
+    for player in players.0.iter() {
+        let local = player.info().ordinal() == local_player.player().to_num();
+        let ready = Player::try_from(player.info().ordinal())
+            .map_or(false, |player| readiness.is_ready(player));
+        let row_id = row(&mut commands, player, local, ready, muted.as_ref());
         commands.entity(box_id.0).add_child(row_id);
     }
 }
 
-fn row(commands: &mut GuiCommands, player: &GamePlayer) -> Entity {
+fn row(
+    commands: &mut GuiCommands,
+    player: &GamePlayer,
+    local: bool,
+    ready: bool,
+    muted: &MutedUsers,
+) -> Entity {
     let row_id = commands
         .spawn(NodeBundle {
             style: Style {
@@ -128,7 +206,7 @@ fn row(commands: &mut GuiCommands, player: &GamePlayer) -> Entity {
     let ordinal_id = commands
         .spawn_label(
             OuterStyle {
-                width: Val::Percent(25.),
+                width: Val::Percent(15.),
                 height: Val::Percent(100.),
                 margin: UiRect::right(Val::Percent(5.)),
             },
@@ -140,7 +218,7 @@ fn row(commands: &mut GuiCommands, player: &GamePlayer) -> Entity {
     let username_id = commands
         .spawn_label(
             OuterStyle {
-                width: Val::Percent(70.),
+                width: Val::Percent(25.),
                 height: Val::Percent(100.),
                 ..default()
             },
@@ -149,10 +227,58 @@ fn row(commands: &mut GuiCommands, player: &GamePlayer) -> Entity {
         .id();
     commands.entity(row_id).add_child(username_id);
 
+    let readiness_id = commands
+        .spawn_label(
+            OuterStyle {
+                width: Val::Percent(15.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            if ready { "Ready" } else { "Not Ready" },
+        )
+        .id();
+    commands.entity(row_id).add_child(readiness_id);
+
+    if local {
+        return row_id;
+    }
+
+    let username = player.username().to_owned();
+    let (caption, action) = if muted.is_muted(username.as_str()) {
+        ("Unmute", ButtonAction::Unmute(username.clone()))
+    } else {
+        ("Mute", ButtonAction::Mute(username.clone()))
+    };
+    let mute_id = commands
+        .spawn_button(
+            OuterStyle {
+                width: Val::Percent(18.),
+                height: Val::Percent(100.),
+                margin: UiRect::right(Val::Percent(2.)),
+            },
+            caption,
+        )
+        .insert(action)
+        .id();
+    commands.entity(row_id).add_child(mute_id);
+
+    let report_id = commands
+        .spawn_button(
+            OuterStyle {
+                width: Val::Percent(18.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            "Report",
+        )
+        .insert(ButtonAction::Report(username))
+        .id();
+    commands.entity(row_id).add_child(report_id);
+
     row_id
 }
 
-fn ready_button(commands: &mut GuiCommands, parent: Entity) {
+fn ready_button(commands: &mut GuiCommands, parent: Entity) -> Entity {
     let button_id = commands
         .spawn_button(
             OuterStyle {
@@ -165,19 +291,99 @@ fn ready_button(commands: &mut GuiCommands, parent: Entity) {
         .insert(ButtonAction::Ready)
         .id();
     commands.entity(parent).add_child(button_id);
+    button_id
+}
+
+fn update_ready_button(
+    readiness: Res<ReadinessRes>,
+    local_player: Res<LocalPlayerRes>,
+    button: Res<ReadyButtonRes>,
+    mut buttons: ButtonOps,
+) {
+    let caption = match (
+        readiness.is_ready(local_player.player()),
+        readiness.countdown,
+    ) {
+        (false, _) => "Ready".to_owned(),
+        (true, None) => "Not Ready".to_owned(),
+        (true, Some(remaining)) => format!("Not Ready (game starts in {remaining} s)"),
+    };
+    buttons.set_text(button.0, caption).unwrap();
+}
+
+fn handle_peer_readiness(
+    mut readiness: ResMut<ReadinessRes>,
+    mut readiness_events: EventReader<PeerReadinessEvent>,
+    mut left_events: EventReader<PeerLeftEvent>,
+) {
+    for event in readiness_events.read() {
+        readiness.set(event.player(), event.readiness() == Readiness::Ready);
+    }
+    for event in left_events.read() {
+        readiness.set(event.id(), false);
+    }
+}
+
+fn handle_countdown(mut readiness: ResMut<ReadinessRes>, mut events: EventReader<CountdownEvent>) {
+    if let Some(event) = events.read().last() {
+        readiness.countdown = event.remaining();
+    }
 }
 
 fn button_system(
     interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
-    mut events: EventWriter<SetReadinessEvent>,
+    game_name: Option<Res<GameNameRes>>,
+    local_player: Res<LocalPlayerRes>,
+    mut readiness: ResMut<ReadinessRes>,
+    mut readiness_events: EventWriter<SetReadinessEvent>,
+    mut mute_events: EventWriter<MuteUserEvent>,
+    mut sender: Sender<ReportUserRequest>,
 ) {
-    for (&interaction, &action) in interactions.iter() {
+    for (&interaction, action) in interactions.iter() {
         if let Interaction::Pressed = interaction {
             match action {
                 ButtonAction::Ready => {
-                    events.send(SetReadinessEvent::from(Readiness::Ready));
+                    let ready = !readiness.is_ready(local_player.player());
+                    readiness.set(local_player.player(), ready);
+                    readiness_events.send(SetReadinessEvent::from(if ready {
+                        Readiness::Ready
+                    } else {
+                        Readiness::NotReady
+                    }));
+                }
+                ButtonAction::Mute(username) => {
+                    mute_events.send(MuteUserEvent::new(username.to_owned(), true));
+                }
+                ButtonAction::Unmute(username) => {
+                    mute_events.send(MuteUserEvent::new(username.to_owned(), false));
+                }
+                ButtonAction::Report(username) => {
+                    // Players are listed only in games registered at DE Lobby.
+                    let Some(game_name) = game_name.as_ref() else {
+                        continue;
+                    };
+                    sender.send(ReportUserRequest::new(UserReport::new(
+                        User::new(username.to_owned()),
+                        format!("Reported in game {}.", game_name.name_owned()),
+                    )));
                 }
             }
         }
     }
 }
+
+fn handle_report_response(
+    mut receiver: Receiver<ReportUserRequest>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    while let Some(result) = receiver.receive() {
+        match result {
+            Ok(_) => {
+                toasts.send(ToastEvent::new("The user has been reported."));
+            }
+            Err(error) => {
+                toasts.send(ToastEvent::new(error));
+            }
+        }
+    }
+}
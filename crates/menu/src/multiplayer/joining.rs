@@ -1,33 +1,76 @@
-use bevy::prelude::*;
+use std::net::SocketAddr;
+
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
 use de_gui::ToastEvent;
 use de_lobby_client::{GetGameRequest, JoinGameRequest};
 use de_lobby_model::GamePlayerInfo;
+use de_map::hash::MapHash;
+use de_messages::MapHashNet;
 use de_multiplayer::{
-    ConnectionType, GameJoinedEvent, NetGameConf, ShutdownMultiplayerEvent, StartMultiplayerEvent,
+    GameJoinedEvent, NetGameConf, ShutdownMultiplayerEvent, StartMultiplayerEvent,
 };
 
 use super::{
-    current::GameNameRes,
+    current::{GameNameRes, LanGameRes},
     joined::LocalPlayerRes,
     requests::{Receiver, Sender},
     MultiplayerState,
 };
+use crate::mapselection::{hash_local_map, FindMapError};
 
 pub(crate) struct JoiningGamePlugin;
 
 impl Plugin for JoiningGamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(MultiplayerState::GameJoining), get_game)
-            .add_systems(OnExit(MultiplayerState::GameJoining), cleanup)
-            .add_systems(
-                Update,
-                (
-                    handle_get_response,
-                    handle_joined_event.run_if(on_event::<GameJoinedEvent>()),
-                    handle_join_response,
-                )
-                    .run_if(in_state(MultiplayerState::GameJoining)),
-            );
+        app.add_systems(
+            OnEnter(MultiplayerState::GameJoining),
+            (
+                get_game.run_if(resource_exists::<GameNameRes>),
+                connect_lan.run_if(resource_exists::<LanGameRes>),
+            ),
+        )
+        .add_systems(OnExit(MultiplayerState::GameJoining), cleanup)
+        .add_systems(
+            Update,
+            (
+                handle_get_response,
+                join_game.run_if(resource_exists::<MapHashTask>),
+                handle_joined_event
+                    .run_if(on_event::<GameJoinedEvent>())
+                    .run_if(resource_exists::<GameNameRes>),
+                handle_lan_joined_event
+                    .run_if(on_event::<GameJoinedEvent>())
+                    .run_if(resource_exists::<LanGameRes>),
+                handle_join_response,
+            )
+                .run_if(in_state(MultiplayerState::GameJoining)),
+        );
+    }
+}
+
+/// Hashing of the local copy of the map of the game being joined. The game
+/// is joined at the DE Connector server once the hash is computed so that the
+/// server can verify that the local map matches the game map.
+#[derive(Resource)]
+struct MapHashTask {
+    server: SocketAddr,
+    task: Task<Result<MapHash, FindMapError>>,
+}
+
+impl MapHashTask {
+    /// # Arguments
+    ///
+    /// * `server` - address of the DE Connector server of the game.
+    ///
+    /// * `map_hash` - hex encoded hash of the game map as advertised by the
+    ///   game.
+    fn spawn(server: SocketAddr, map_hash: &str) -> Result<Self, String> {
+        let hash = MapHash::from_hex(map_hash).map_err(|error| error.to_string())?;
+        let task = IoTaskPool::get().spawn(hash_local_map(hash));
+        Ok(Self { server, task })
     }
 }
 
@@ -36,6 +79,8 @@ fn cleanup(
     state: Res<State<MultiplayerState>>,
     mut shutdown: EventWriter<ShutdownMultiplayerEvent>,
 ) {
+    commands.remove_resource::<MapHashTask>();
+
     if state.as_ref() != &MultiplayerState::GameJoined {
         commands.remove_resource::<LocalPlayerRes>();
         shutdown.send(ShutdownMultiplayerEvent);
@@ -46,20 +91,42 @@ fn get_game(game_name: Res<GameNameRes>, mut sender: Sender<GetGameRequest>) {
     sender.send(GetGameRequest::new(game_name.name_owned()));
 }
 
+fn connect_lan(
+    mut commands: Commands,
+    lan_game: Res<LanGameRes>,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let game = lan_game.game();
+    match MapHashTask::spawn(
+        SocketAddr::new(game.host(), game.game().port()),
+        game.game().description().map_hash(),
+    ) {
+        Ok(task) => commands.insert_resource(task),
+        Err(error) => {
+            toasts.send(ToastEvent::new(format!("Invalid game: {error}")));
+            next_state.set(MultiplayerState::SignIn);
+        }
+    }
+}
+
 fn handle_get_response(
+    mut commands: Commands,
     mut next_state: ResMut<NextState<MultiplayerState>>,
     mut receiver: Receiver<GetGameRequest>,
-    mut multiplayer: EventWriter<StartMultiplayerEvent>,
     mut toasts: EventWriter<ToastEvent>,
 ) {
     while let Some(result) = receiver.receive() {
         match result {
             Ok(game) => {
-                let server = game.setup().server();
-                multiplayer.send(StartMultiplayerEvent::new(NetGameConf::new(
-                    server.ip(),
-                    ConnectionType::JoinGame(server.port()),
-                )));
+                let setup = game.setup();
+                match MapHashTask::spawn(setup.server(), setup.config().map().hash()) {
+                    Ok(task) => commands.insert_resource(task),
+                    Err(error) => {
+                        toasts.send(ToastEvent::new(format!("Invalid game: {error}")));
+                        next_state.set(MultiplayerState::SignIn);
+                    }
+                }
             }
             Err(error) => {
                 toasts.send(ToastEvent::new(error));
@@ -69,6 +136,34 @@ fn handle_get_response(
     }
 }
 
+/// Joins the game with the hash of the local copy of the game map.
+fn join_game(
+    mut commands: Commands,
+    mut task: ResMut<MapHashTask>,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
+    mut multiplayer: EventWriter<StartMultiplayerEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let Some(result) = future::block_on(future::poll_once(&mut task.task)) else {
+        return;
+    };
+    commands.remove_resource::<MapHashTask>();
+
+    let map_hash = match result {
+        Ok(hash) => MapHashNet::from_hex(&hash.to_hex()).unwrap(),
+        Err(error) => {
+            toasts.send(ToastEvent::new(format!("Map error: {error}")));
+            next_state.set(MultiplayerState::SignIn);
+            return;
+        }
+    };
+
+    multiplayer.send(StartMultiplayerEvent::new(NetGameConf::join_game(
+        task.server,
+        map_hash,
+    )));
+}
+
 fn handle_joined_event(
     mut commands: Commands,
     game_name: Res<GameNameRes>,
@@ -86,6 +181,19 @@ fn handle_joined_event(
     ));
 }
 
+fn handle_lan_joined_event(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
+    mut events: EventReader<GameJoinedEvent>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    commands.insert_resource(LocalPlayerRes::new(event.player()));
+    next_state.set(MultiplayerState::GameJoined);
+}
+
 fn handle_join_response(
     mut next_state: ResMut<NextState<MultiplayerState>>,
     mut receiver: Receiver<JoinGameRequest>,
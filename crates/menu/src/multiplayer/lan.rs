@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::Stopwatch};
+use de_gui::{ButtonCommands, GuiCommands, LabelCommands, OuterStyle};
+use de_multiplayer::{DiscoverGamesEvent, DiscoveredGame, GamesDiscoveredEvent};
+
+use super::{
+    current::{DirectConnectionRes, LanGameRes},
+    MultiplayerState,
+};
+use crate::menu::Menu;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+pub(super) struct LanGamesPlugin;
+
+impl Plugin for LanGamesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MultiplayerState::LanGames), setup)
+            .add_systems(OnExit(MultiplayerState::LanGames), cleanup)
+            .add_systems(
+                Update,
+                (
+                    refresh_system,
+                    list_games_system.run_if(on_event::<GamesDiscoveredEvent>()),
+                    button_system,
+                )
+                    .run_if(in_state(MultiplayerState::LanGames)),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct GamesTable(Entity);
+
+#[derive(Component)]
+enum ButtonAction {
+    Back,
+    Join(DiscoveredGame),
+}
+
+fn setup(
+    mut commands: GuiCommands,
+    menu: Res<Menu>,
+    direct: Option<Res<DirectConnectionRes>>,
+    mut discovery: EventWriter<DiscoverGamesEvent>,
+) {
+    let column_id = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(80.),
+                height: Val::Percent(80.),
+                margin: UiRect::all(Val::Auto),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexStart,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(menu.root_node()).add_child(column_id);
+
+    back_button(&mut commands, column_id);
+    let table_id = table(&mut commands, column_id);
+    commands.insert_resource(GamesTable(table_id));
+    discovery.send(discover_event(direct.as_deref()));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<GamesTable>();
+}
+
+fn back_button(commands: &mut GuiCommands, parent_node: Entity) {
+    let button_id = commands
+        .spawn_button(
+            OuterStyle {
+                width: Val::Percent(100.),
+                height: Val::Percent(8.),
+                margin: UiRect::bottom(Val::Percent(1.)),
+            },
+            "Back",
+        )
+        .insert(ButtonAction::Back)
+        .id();
+    commands.entity(parent_node).add_child(button_id);
+}
+
+fn table(commands: &mut GuiCommands, parent_node: Entity) -> Entity {
+    let table_id = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.),
+                height: Val::Percent(91.),
+                margin: UiRect::all(Val::Auto),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexStart,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(parent_node).add_child(table_id);
+    table_id
+}
+
+fn row(commands: &mut GuiCommands, game: &DiscoveredGame) -> Entity {
+    let row_id = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                width: Val::Percent(100.),
+                height: Val::Percent(8.),
+                margin: UiRect::vertical(Val::Percent(0.5)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexStart,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    let lan_game = game.game();
+    let name_id = commands
+        .spawn_label(
+            OuterStyle {
+                width: Val::Percent(80.),
+                height: Val::Percent(100.),
+                margin: UiRect::right(Val::Percent(2.)),
+            },
+            format!(
+                "{} on {} ({}/{}) at {}",
+                lan_game.description().name(),
+                lan_game.description().map_name(),
+                lan_game.num_players(),
+                lan_game.max_players().to_num(),
+                game.host(),
+            ),
+        )
+        .id();
+    commands.entity(row_id).add_child(name_id);
+
+    let button_id = commands
+        .spawn_button(
+            OuterStyle {
+                width: Val::Percent(18.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            "Join",
+        )
+        .insert(ButtonAction::Join(game.clone()))
+        .id();
+    commands.entity(row_id).add_child(button_id);
+
+    row_id
+}
+
+/// Games are searched for at the DE Connector entered by the player or in the
+/// local network if no DE Connector was entered.
+fn discover_event(direct: Option<&DirectConnectionRes>) -> DiscoverGamesEvent {
+    match direct {
+        Some(direct) => DiscoverGamesEvent::host(direct.addr().ip()),
+        None => DiscoverGamesEvent::lan(),
+    }
+}
+
+fn refresh_system(
+    time: Res<Time>,
+    mut stopwatch: Local<Stopwatch>,
+    direct: Option<Res<DirectConnectionRes>>,
+    mut discovery: EventWriter<DiscoverGamesEvent>,
+) {
+    stopwatch.tick(time.delta());
+    if stopwatch.elapsed() >= REFRESH_INTERVAL {
+        stopwatch.reset();
+        discovery.send(discover_event(direct.as_deref()));
+    }
+}
+
+fn list_games_system(
+    mut commands: GuiCommands,
+    table: Res<GamesTable>,
+    mut events: EventReader<GamesDiscoveredEvent>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+    commands.entity(table.0).despawn_descendants();
+
+    for game in event.games() {
+        let row_id = row(&mut commands, game);
+        commands.entity(table.0).add_child(row_id);
+    }
+}
+
+fn button_system(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
+    direct: Option<Res<DirectConnectionRes>>,
+    interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
+) {
+    for (&interaction, action) in interactions.iter() {
+        if let Interaction::Pressed = interaction {
+            match action {
+                ButtonAction::Back => {
+                    if direct.is_some() {
+                        next_state.set(MultiplayerState::DirectConnect);
+                    } else {
+                        next_state.set(MultiplayerState::SignIn);
+                    }
+                }
+                ButtonAction::Join(game) => {
+                    commands.insert_resource(LanGameRes::new(game.clone()));
+                    next_state.set(MultiplayerState::GameJoining);
+                }
+            }
+        }
+    }
+}
@@ -1,22 +1,25 @@
 use bevy::prelude::*;
 use de_core::nested_state;
 use de_lobby_client::{
-    CreateGameRequest, GetGameRequest, JoinGameRequest, SignInRequest, SignUpRequest,
+    CreateGameRequest, GetGameRequest, JoinGameRequest, ReportUserRequest, SignInRequest,
+    SignUpRequest,
 };
 use de_multiplayer::MultiplayerShuttingDownEvent;
 
 use self::{
-    create::CreateGamePlugin, current::CurrentGamePlugin, gamelisting::GameListingPlugin,
-    joined::JoinedGamePlugin, joining::JoiningGamePlugin, requests::RequestsPlugin,
-    setup::SetupGamePlugin, signin::SignInPlugin,
+    create::CreateGamePlugin, current::CurrentGamePlugin, direct::DirectConnectPlugin,
+    gamelisting::GameListingPlugin, joined::JoinedGamePlugin, joining::JoiningGamePlugin,
+    lan::LanGamesPlugin, requests::RequestsPlugin, setup::SetupGamePlugin, signin::SignInPlugin,
 };
 use crate::{menu::ScreenStatePlugin, MenuState};
 
 mod create;
 mod current;
+mod direct;
 mod gamelisting;
 mod joined;
 mod joining;
+mod lan;
 mod requests;
 mod setup;
 mod signin;
@@ -31,11 +34,16 @@ impl Plugin for MultiplayerPlugin {
             RequestsPlugin::<GetGameRequest>::new(),
             RequestsPlugin::<CreateGameRequest>::new(),
             RequestsPlugin::<JoinGameRequest>::new(),
+            RequestsPlugin::<ReportUserRequest>::new(),
+        ))
+        .add_plugins((
             MultiplayerStatePlugin,
             ScreenStatePlugin::<MultiplayerState>::default(),
             CurrentGamePlugin,
             SignInPlugin,
             GameListingPlugin,
+            LanGamesPlugin,
+            DirectConnectPlugin,
             CreateGamePlugin,
             SetupGamePlugin,
             JoiningGamePlugin,
@@ -57,6 +65,8 @@ nested_state!(
     variants = {
         SignIn,
         GameListing,
+        LanGames,
+        DirectConnect,
         GameCreation,
         GameSetup,
         GameJoining,
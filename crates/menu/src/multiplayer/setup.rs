@@ -3,13 +3,14 @@ use de_conf::Configuration;
 use de_gui::ToastEvent;
 use de_lobby_client::CreateGameRequest;
 use de_lobby_model::{GameConfig, GameSetup};
+use de_messages::{GameDescription, GameDescriptionError, LanGame, MapHashNet};
 use de_multiplayer::{
-    ConnectionType, GameJoinedEvent, GameOpenedEvent, NetGameConf, ShutdownMultiplayerEvent,
+    DiscoveredGame, GameJoinedEvent, GameOpenedEvent, NetGameConf, ShutdownMultiplayerEvent,
     StartMultiplayerEvent,
 };
 
 use super::{
-    current::GameNameRes,
+    current::{DirectConnectionRes, GameNameRes, LanGameRes},
     joined::LocalPlayerRes,
     requests::{Receiver, Sender},
     MultiplayerState,
@@ -30,7 +31,8 @@ impl Plugin for SetupGamePlugin {
             .add_systems(
                 Update,
                 (
-                    create_game_in_lobby,
+                    create_game_in_lobby.run_if(not(resource_exists::<DirectConnectionRes>)),
+                    register_direct.run_if(resource_exists::<DirectConnectionRes>),
                     handle_lobby_response,
                     handle_joined_event,
                 )
@@ -46,8 +48,10 @@ impl Plugin for SetupGamePlugin {
 /// Send this event to initiate new multiplayer setup.
 ///
 /// The game will be opened at a DE Connector and registered at a DE Lobby.
-/// Once this is done, the menu transitions to
-/// [`MultiplayerState::GameJoined`].
+/// The DE Lobby is skipped if the game is opened at a directly connected DE
+/// Connector (see [`DirectConnectionRes`]). The game is also announced to
+/// players in the local network of the DE Connector. Once this is done, the
+/// menu transitions to [`MultiplayerState::GameJoined`].
 #[derive(Event)]
 pub(super) struct SetupGameEvent {
     config: GameConfig,
@@ -109,19 +113,47 @@ fn setup(mut commands: Commands) {
 fn setup_network(
     config: Res<Configuration>,
     game_config: Res<GameConfigRes>,
+    direct: Option<Res<DirectConnectionRes>>,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
     mut multiplayer: EventWriter<StartMultiplayerEvent>,
+    mut toasts: EventWriter<ToastEvent>,
 ) {
-    let connector_conf = config.multiplayer().connector();
-    let net_game_conf = NetGameConf::new(
-        connector_conf.ip(),
-        ConnectionType::CreateGame {
-            port: connector_conf.port(),
-            max_players: game_config.0.max_players().try_into().unwrap(),
-        },
-    );
+    let main_server = match direct {
+        Some(direct) => direct.addr(),
+        None => config.multiplayer().connector(),
+    };
+    let map_hash = match MapHashNet::from_hex(game_config.0.map().hash()) {
+        Ok(map_hash) => map_hash,
+        Err(error) => {
+            toasts.send(ToastEvent::new(format!("Invalid map: {error}")));
+            next_state.set(MultiplayerState::SignIn);
+            return;
+        }
+    };
+    let mut net_game_conf = NetGameConf::create_game(
+        main_server,
+        game_config.0.max_players().try_into().unwrap(),
+        map_hash,
+    )
+    .with_countdown(config.multiplayer().start_countdown());
+
+    match description(&game_config.0) {
+        Ok(description) => net_game_conf = net_game_conf.with_description(description),
+        Err(err) => warn!("The game is not announced in LAN: {err}"),
+    }
+
     multiplayer.send(StartMultiplayerEvent::new(net_game_conf));
 }
 
+fn description(config: &GameConfig) -> Result<GameDescription, GameDescriptionError> {
+    let map = config.map();
+    GameDescription::new(
+        config.name().to_owned(),
+        map.hash().to_owned(),
+        map.name().to_owned(),
+    )
+}
+
 fn create_game_in_lobby(
     mut commands: Commands,
     config: Res<GameConfigRes>,
@@ -138,6 +170,40 @@ fn create_game_in_lobby(
     sender.send(CreateGameRequest::new(game_setup));
 }
 
+/// Games opened at a directly connected DE Connector are not registered at
+/// DE Lobby, therefore they are handled the same way as discovered games.
+fn register_direct(
+    mut commands: Commands,
+    config: Res<GameConfigRes>,
+    mut joined: ResMut<JoinedRes>,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
+    mut opened_events: EventReader<GameOpenedEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let Some(opened_event) = opened_events.read().last() else {
+        return;
+    };
+
+    let description = match description(&config.0) {
+        Ok(description) => description,
+        Err(err) => {
+            toasts.send(ToastEvent::new(format!("Invalid game: {err}")));
+            next_state.set(MultiplayerState::SignIn);
+            return;
+        }
+    };
+
+    let addr = opened_event.0;
+    let game = LanGame::new(
+        addr.port(),
+        1,
+        config.0.max_players().try_into().unwrap(),
+        description,
+    );
+    commands.insert_resource(LanGameRes::new(DiscoveredGame::new(addr.ip(), game)));
+    joined.0 = true;
+}
+
 fn handle_joined_event(mut commands: Commands, mut events: EventReader<GameJoinedEvent>) {
     let Some(event) = events.read().last() else {
         return;
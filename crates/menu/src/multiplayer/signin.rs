@@ -41,6 +41,8 @@ struct Inputs {
 enum Action {
     SignIn,
     SignUp,
+    LanGames,
+    DirectConnect,
 }
 
 fn setup(mut commands: GuiCommands, menu: Res<Menu>, mut focus: EventWriter<SetFocusEvent>) {
@@ -57,6 +59,15 @@ fn setup(mut commands: GuiCommands, menu: Res<Menu>, mut focus: EventWriter<SetF
     let buttons_row = row(&mut commands, column);
     buttons(&mut commands, buttons_row);
 
+    let lan_row = row(&mut commands, column);
+    button(&mut commands, lan_row, Action::LanGames, Val::Percent(48.));
+    button(
+        &mut commands,
+        lan_row,
+        Action::DirectConnect,
+        Val::Percent(48.),
+    );
+
     commands.insert_resource(Inputs {
         username: input_text_box,
         password: password_text_box,
@@ -136,20 +147,22 @@ fn input(commands: &mut GuiCommands, parent: Entity, caption: &str, secret: bool
 }
 
 fn buttons(commands: &mut GuiCommands, parent: Entity) {
-    button(commands, parent, Action::SignIn);
-    button(commands, parent, Action::SignUp);
+    button(commands, parent, Action::SignIn, Val::Percent(48.));
+    button(commands, parent, Action::SignUp, Val::Percent(48.));
 }
 
-fn button(commands: &mut GuiCommands, parent: Entity, action: Action) {
+fn button(commands: &mut GuiCommands, parent: Entity, action: Action, width: Val) {
     let caption = match action {
         Action::SignIn => "Sign In",
         Action::SignUp => "Sign Up",
+        Action::LanGames => "LAN Games",
+        Action::DirectConnect => "Direct Connect",
     };
 
     let id = commands
         .spawn_button(
             OuterStyle {
-                width: Val::Percent(48.),
+                width,
                 height: Val::Percent(100.),
                 ..default()
             },
@@ -164,6 +177,7 @@ fn button_system(
     inputs: Res<Inputs>,
     texts: TextBoxQuery,
     interactions: Query<(&Interaction, &Action), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<MultiplayerState>>,
     mut sign_in_sender: Sender<SignInRequest>,
     mut sign_up_sender: Sender<SignUpRequest>,
 ) {
@@ -184,6 +198,8 @@ fn button_system(
                         User::new(username),
                     )));
                 }
+                Action::LanGames => next_state.set(MultiplayerState::LanGames),
+                Action::DirectConnect => next_state.set(MultiplayerState::DirectConnect),
             }
 
             break;
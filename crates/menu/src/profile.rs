@@ -0,0 +1,312 @@
+use std::{collections::HashMap, io};
+
+use async_std::{fs, path::PathBuf};
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
+use de_core::{
+    fs::{profile_dir, DirError},
+    gresult::GameResult,
+    gstats::GameStats,
+    log_full_error,
+    state::AppState,
+};
+use de_gui::{GuiCommands, LabelCommands, OuterStyle};
+use de_types::objects::UnitType;
+use enum_iterator::all;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{menu::Menu, MenuState};
+
+pub(crate) struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Profile), setup)
+            .add_systems(OnExit(MenuState::Profile), cleanup)
+            .add_systems(OnEnter(AppState::InMenu), record_result)
+            .add_systems(Update, init_labels.run_if(in_state(MenuState::Profile)));
+    }
+}
+
+#[derive(Resource)]
+struct LoadingTask(Task<Result<LifetimeStats, ProfileError>>);
+
+#[derive(Resource)]
+struct ListNode(Entity);
+
+/// Statistics of the local player accumulated over all finished games. These
+/// are stored locally and independently of lobby server accounts.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct LifetimeStats {
+    games: u32,
+    wins: u32,
+    actions: u64,
+    playing_time_sec: f64,
+    units: HashMap<UnitType, u32>,
+}
+
+impl LifetimeStats {
+    /// Accumulates statistics of a finished game.
+    fn record(&mut self, won: bool, game: &GameStats) {
+        self.games += 1;
+        if won {
+            self.wins += 1;
+        }
+        self.actions += u64::from(game.actions());
+        self.playing_time_sec += game.duration().as_secs_f64();
+        for unit in all::<UnitType>() {
+            let count = game.manufactured(unit);
+            if count > 0 {
+                *self.units.entry(unit).or_default() += count;
+            }
+        }
+    }
+
+    /// Returns the fraction of won games or None if no game was finished.
+    fn win_rate(&self) -> Option<f32> {
+        if self.games == 0 {
+            None
+        } else {
+            Some(self.wins as f32 / self.games as f32)
+        }
+    }
+
+    /// Returns the most manufactured unit type or None if no unit was
+    /// manufactured.
+    fn favorite_unit(&self) -> Option<UnitType> {
+        all::<UnitType>()
+            .filter_map(|unit| self.units.get(&unit).map(|&count| (unit, count)))
+            .filter(|&(_, count)| count > 0)
+            .max_by_key(|&(_, count)| count)
+            .map(|(unit, _)| unit)
+    }
+
+    /// Returns average number of actions per minute of playing time or None
+    /// if no game was played.
+    fn apm(&self) -> Option<f64> {
+        if self.playing_time_sec <= 0. {
+            None
+        } else {
+            Some(self.actions as f64 * 60. / self.playing_time_sec)
+        }
+    }
+}
+
+fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
+    let task = IoTaskPool::get().spawn(load_stats());
+    commands.insert_resource(LoadingTask(task));
+
+    let column_node = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.),
+                height: Val::Percent(100.),
+                margin: UiRect::all(Val::Auto),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(menu.root_node()).add_child(column_node);
+    commands.insert_resource(ListNode(column_node));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<LoadingTask>();
+    commands.remove_resource::<ListNode>();
+}
+
+fn init_labels(mut commands: GuiCommands, node: Res<ListNode>, task: Option<ResMut<LoadingTask>>) {
+    let Some(mut task) = task else { return };
+    let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+        return;
+    };
+    commands.remove_resource::<LoadingTask>();
+
+    let lines = match result {
+        Ok(stats) => vec![
+            format!("Games played: {}", stats.games),
+            match stats.win_rate() {
+                Some(rate) => format!("Win rate: {:.0} %", 100. * rate),
+                None => "Win rate: -".to_owned(),
+            },
+            match stats.favorite_unit() {
+                Some(unit) => format!("Favorite unit: {unit}"),
+                None => "Favorite unit: -".to_owned(),
+            },
+            match stats.apm() {
+                Some(apm) => format!("APM: {apm:.0}"),
+                None => "APM: -".to_owned(),
+            },
+        ],
+        Err(err) => {
+            log_full_error!(err);
+            vec!["Statistics are not available.".to_owned()]
+        }
+    };
+
+    for line in lines {
+        let label = commands.spawn_label(item_style(), line).id();
+        commands.entity(node.0).add_child(label);
+    }
+}
+
+fn item_style() -> OuterStyle {
+    OuterStyle {
+        width: Val::Percent(100.),
+        height: Val::Percent(8.),
+        margin: UiRect::new(
+            Val::Percent(0.),
+            Val::Percent(0.),
+            Val::Percent(2.),
+            Val::Percent(2.),
+        ),
+    }
+}
+
+/// Adds statistics of a just finished game to the lifetime statistics.
+fn record_result(
+    mut commands: Commands,
+    result: Option<Res<GameResult>>,
+    game: Option<Res<GameStats>>,
+) {
+    let Some(game) = game else { return };
+    commands.remove_resource::<GameStats>();
+
+    let Some(GameResult::Finished(result)) = result.as_deref() else {
+        return;
+    };
+    let won = result.won();
+    let game = game.clone();
+
+    IoTaskPool::get()
+        .spawn(async move {
+            let mut stats = match load_stats().await {
+                Ok(stats) => stats,
+                Err(err @ ProfileError::Json { .. }) => {
+                    // The error must not be held across the await below,
+                    // otherwise the future is not Send.
+                    {
+                        log_full_error!(err);
+                    }
+                    // Keep the unparsable statistics so that they can be
+                    // recovered manually.
+                    if let Err(err) = backup_stats().await {
+                        log_full_error!(err);
+                        return;
+                    }
+                    warn!("Lifetime statistics are reset, the original file is backed up.");
+                    LifetimeStats::default()
+                }
+                Err(err) => {
+                    // Do not overwrite statistics which could not be read.
+                    log_full_error!(err);
+                    return;
+                }
+            };
+            stats.record(won, &game);
+            if let Err(err) = store_stats(&stats).await {
+                log_full_error!(err);
+            }
+        })
+        .detach();
+}
+
+/// Loads lifetime statistics from the user data directory. Default (empty)
+/// statistics are returned if no game has been finished yet.
+async fn load_stats() -> Result<LifetimeStats, ProfileError> {
+    let path = stats_path()?;
+    if !path.is_file().await {
+        return Ok(LifetimeStats::default());
+    }
+
+    let bytes = fs::read(path)
+        .await
+        .map_err(|source| ProfileError::Io { source })?;
+    serde_json::from_slice(&bytes).map_err(|source| ProfileError::Json { source })
+}
+
+/// Stores lifetime statistics to the user data directory.
+async fn store_stats(stats: &LifetimeStats) -> Result<(), ProfileError> {
+    let path = stats_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|source| ProfileError::Io { source })?;
+    }
+
+    let bytes = serde_json::to_vec(stats).map_err(|source| ProfileError::Json { source })?;
+    // The statistics are written to a temporary file first so that an
+    // interrupted write does not corrupt previously stored statistics.
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, bytes)
+        .await
+        .map_err(|source| ProfileError::Io { source })?;
+    fs::rename(tmp_path, path)
+        .await
+        .map_err(|source| ProfileError::Io { source })
+}
+
+/// Renames the stored lifetime statistics file so that it is not overwritten.
+async fn backup_stats() -> Result<(), ProfileError> {
+    let path = stats_path()?;
+    fs::rename(&path, path.with_extension("json.bak"))
+        .await
+        .map_err(|source| ProfileError::Io { source })
+}
+
+fn stats_path() -> Result<PathBuf, ProfileError> {
+    profile_dir()
+        .map(|dir| dir.join("stats.json"))
+        .map_err(|source| ProfileError::Dir { source })
+}
+
+#[derive(Error, Debug)]
+enum ProfileError {
+    #[error("profile directory error")]
+    Dir { source: DirError },
+    #[error(transparent)]
+    Io { source: io::Error },
+    #[error("lifetime statistics JSON error")]
+    Json { source: serde_json::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut stats = LifetimeStats::default();
+        assert!(stats.win_rate().is_none());
+        assert!(stats.favorite_unit().is_none());
+        assert!(stats.apm().is_none());
+
+        let mut game = GameStats::default();
+        game.record_actions(120);
+        game.record_unit(UnitType::Scout);
+        game.record_unit(UnitType::Attacker);
+        game.record_unit(UnitType::Attacker);
+        game.tick(Duration::from_secs(60));
+        stats.record(true, &game);
+        stats.record(false, &GameStats::default());
+
+        assert_eq!(stats.games, 2);
+        assert_eq!(stats.win_rate(), Some(0.5));
+        assert_eq!(stats.favorite_unit(), Some(UnitType::Attacker));
+        assert_eq!(stats.apm(), Some(120.));
+
+        let json = serde_json::to_string(&stats).unwrap();
+        let loaded: LifetimeStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.units[&UnitType::Attacker], 2);
+    }
+}
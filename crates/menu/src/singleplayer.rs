@@ -1,10 +1,10 @@
 use async_std::path::PathBuf;
 use bevy::prelude::*;
 use de_core::{
-    gconfig::{GameConfig, LocalPlayers},
+    gconfig::{GameConfig, LocalPlayers, Teams},
     state::AppState,
 };
-use de_gui::{ButtonCommands, GuiCommands, OuterStyle, ToastEvent};
+use de_gui::{ButtonCommands, ButtonOps, GuiCommands, OuterStyle, ToastEvent};
 use de_types::player::Player;
 
 use crate::{
@@ -29,14 +29,73 @@ impl Plugin for SinglePlayerPlugin {
 #[derive(Resource)]
 struct SelectedMap(Option<PathBuf>);
 
+/// Alliances between players of the single player game.
+#[derive(Resource, Clone, Copy, Default)]
+enum TeamsSetup {
+    /// Each player plays on its own.
+    #[default]
+    FreeForAll,
+    /// Players 1 & 2 are allied against players 3 & 4. Allies share vision.
+    TwoVersusTwo,
+}
+
+impl TeamsSetup {
+    fn next(self) -> Self {
+        match self {
+            Self::FreeForAll => Self::TwoVersusTwo,
+            Self::TwoVersusTwo => Self::FreeForAll,
+        }
+    }
+
+    fn caption(self) -> String {
+        match self {
+            Self::FreeForAll => "Teams: Free for All".to_owned(),
+            Self::TwoVersusTwo => "Teams: 2 vs 2".to_owned(),
+        }
+    }
+
+    fn teams(self) -> Teams {
+        match self {
+            Self::FreeForAll => Teams::default(),
+            Self::TwoVersusTwo => Teams::new([0, 0, 1, 1], true, false),
+        }
+    }
+}
+
+/// Whether health of objects passively regenerates and degrades in the single
+/// player game.
+#[derive(Resource)]
+struct RegenerationSetup(bool);
+
+impl RegenerationSetup {
+    fn caption(&self) -> String {
+        if self.0 {
+            "Regeneration: On".to_owned()
+        } else {
+            "Regeneration: Off".to_owned()
+        }
+    }
+}
+
+impl Default for RegenerationSetup {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
 #[derive(Component, Clone, Copy)]
 enum ButtonAction {
     StartGame,
+    EditMap,
     SelectMap,
+    Teams,
+    Regeneration,
 }
 
 fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
     commands.insert_resource(SelectedMap(None));
+    commands.insert_resource(TeamsSetup::default());
+    commands.insert_resource(RegenerationSetup::default());
 
     let column_node = commands
         .spawn(NodeBundle {
@@ -60,12 +119,30 @@ fn setup(mut commands: GuiCommands, menu: Res<Menu>) {
         ButtonAction::StartGame,
         "Start Game",
     );
+    button(
+        &mut commands,
+        column_node,
+        ButtonAction::EditMap,
+        "Edit Map",
+    );
     button(
         &mut commands,
         column_node,
         ButtonAction::SelectMap,
         "Select Map",
     );
+    button(
+        &mut commands,
+        column_node,
+        ButtonAction::Teams,
+        &TeamsSetup::default().caption(),
+    );
+    button(
+        &mut commands,
+        column_node,
+        ButtonAction::Regeneration,
+        &RegenerationSetup::default().caption(),
+    );
 }
 
 fn button(commands: &mut GuiCommands, parent: Entity, action: ButtonAction, caption: &str) {
@@ -90,26 +167,45 @@ fn button(commands: &mut GuiCommands, parent: Entity, action: ButtonAction, capt
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<SelectedMap>();
+    commands.remove_resource::<TeamsSetup>();
+    commands.remove_resource::<RegenerationSetup>();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn button_system(
     mut commands: Commands,
-    interactions: Query<(&Interaction, &ButtonAction), Changed<Interaction>>,
+    interactions: Query<(Entity, &Interaction, &ButtonAction), Changed<Interaction>>,
     mut next_state: ResMut<NextState<AppState>>,
     map: Res<SelectedMap>,
+    mut teams: ResMut<TeamsSetup>,
+    mut regeneration: ResMut<RegenerationSetup>,
+    mut buttons: ButtonOps,
     mut map_events: EventWriter<SelectMapEvent>,
     mut toasts: EventWriter<ToastEvent>,
 ) {
-    for (&interaction, &action) in interactions.iter() {
+    for (entity, &interaction, &action) in interactions.iter() {
         if let Interaction::Pressed = interaction {
             match action {
                 ButtonAction::StartGame => match map.0.as_ref() {
                     Some(path) => {
-                        commands.insert_resource(GameConfig::new(
-                            path,
-                            false,
-                            LocalPlayers::from_max_player(Player::Player1, Player::Player4),
-                        ));
+                        commands.insert_resource(
+                            GameConfig::new(
+                                path,
+                                false,
+                                LocalPlayers::from_max_player(Player::Player1, Player::Player4),
+                            )
+                            .with_teams(teams.teams())
+                            .with_regeneration(regeneration.0),
+                        );
+                        next_state.set(AppState::InGame);
+                    }
+                    None => {
+                        toasts.send(ToastEvent::new("No map selected."));
+                    }
+                },
+                ButtonAction::EditMap => match map.0.as_ref() {
+                    Some(path) => {
+                        commands.insert_resource(GameConfig::new_editor(path));
                         next_state.set(AppState::InGame);
                     }
                     None => {
@@ -119,6 +215,14 @@ fn button_system(
                 ButtonAction::SelectMap => {
                     map_events.send(SelectMapEvent);
                 }
+                ButtonAction::Teams => {
+                    *teams = teams.next();
+                    buttons.set_text(entity, teams.caption()).unwrap();
+                }
+                ButtonAction::Regeneration => {
+                    regeneration.0 = !regeneration.0;
+                    buttons.set_text(entity, regeneration.caption()).unwrap();
+                }
             };
         }
     }
@@ -1,14 +1,18 @@
 use bincode::{Decode, Encode};
 use de_types::player::Player;
 
+use crate::MapHashNet;
+
 /// Message to be sent from a player/client to a game server (inside of a
 /// game).
 #[derive(Debug, Encode, Decode)]
 pub enum ToGame {
     /// Prompts the server to respond [`FromGame::Pong`] with the same ping ID.
     Ping(u32),
-    /// Connect the player to the game.
-    Join,
+    /// Connect the player to the game. The hash must be equal to the hash
+    /// of the map of the game otherwise the player is not connected and
+    /// [`JoinError::MapMismatch`] is sent back.
+    Join(MapHashNet),
     /// Disconnect the player from the game.
     ///
     /// The game is automatically closed once all players disconnect.
@@ -16,7 +20,9 @@ pub enum ToGame {
     /// Sets readiness of the client.
     ///
     /// New readiness must be greater by one or equal to the current readiness.
-    /// See [`Readiness::progress`].
+    /// See [`Readiness::progress`]. The only exception is that a client might
+    /// return from [`Readiness::Ready`] to [`Readiness::NotReady`] until the
+    /// game starts.
     Readiness(Readiness),
 }
 
@@ -50,6 +56,18 @@ pub enum FromGame {
     PeerLeft(Player),
     /// Game readiness has changed.
     GameReadiness(Readiness),
+    /// Another player changed their readiness before the game started.
+    ///
+    /// Readiness of already connected players which are not
+    /// [`Readiness::NotReady`] is sent to a player just after they join the
+    /// game.
+    PeerReadiness(Player, Readiness),
+    /// All players are ready and the game progresses to [`Readiness::Ready`]
+    /// in the given number of seconds. The message is repeated every second.
+    Countdown(u8),
+    /// A previously started countdown was cancelled because a player is no
+    /// longer ready or a new player joined the game.
+    CountdownCancelled,
 }
 
 #[derive(Debug, Encode, Decode)]
@@ -61,6 +79,8 @@ pub enum JoinError {
     AlreadyJoined,
     /// The player already participates on a different game.
     DifferentGame,
+    /// The player reported a different map than the map of the game.
+    MapMismatch,
 }
 
 /// Readiness of an individual client or the game as a whole. It consists of a
@@ -0,0 +1,175 @@
+use bincode::{Decode, Encode};
+use de_types::player::Player;
+use thiserror::Error;
+
+// These must be short enough so that a whole LAN game announcement fits into a
+// single datagram.
+/// Maximum length (in bytes) of a game name announced in the local network.
+pub const MAX_LAN_GAME_NAME_LEN: usize = 32;
+/// Maximum length (in bytes) of a map name announced in the local network.
+pub const MAX_LAN_MAP_NAME_LEN: usize = 32;
+/// Length of a hex encoded map hash.
+const MAP_HASH_LEN: usize = 64;
+
+/// Human readable description of a game which is announced to clients in the
+/// local network. Clients joining the game via LAN discovery rely on the
+/// description instead of DE Lobby.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct GameDescription {
+    name: String,
+    map_hash: String,
+    map_name: String,
+}
+
+impl GameDescription {
+    /// # Arguments
+    ///
+    /// * `name` - name of the game.
+    ///
+    /// * `map_hash` - hex encoded hash of the game map.
+    ///
+    /// * `map_name` - name of the game map.
+    pub fn new(
+        name: String,
+        map_hash: String,
+        map_name: String,
+    ) -> Result<Self, GameDescriptionError> {
+        let description = Self {
+            name,
+            map_hash,
+            map_name,
+        };
+        description.validate()?;
+        Ok(description)
+    }
+
+    /// Validates the description. This is useful for descriptions decoded
+    /// from untrusted sources.
+    pub fn validate(&self) -> Result<(), GameDescriptionError> {
+        if self.name.is_empty() || self.name.len() > MAX_LAN_GAME_NAME_LEN {
+            return Err(GameDescriptionError::GameName {
+                len: self.name.len(),
+                max_len: MAX_LAN_GAME_NAME_LEN,
+            });
+        }
+        if self.map_name.is_empty() || self.map_name.len() > MAX_LAN_MAP_NAME_LEN {
+            return Err(GameDescriptionError::MapName {
+                len: self.map_name.len(),
+                max_len: MAX_LAN_MAP_NAME_LEN,
+            });
+        }
+        if self.map_hash.len() != MAP_HASH_LEN
+            || !self
+                .map_hash
+                .bytes()
+                .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+        {
+            return Err(GameDescriptionError::MapHash);
+        }
+
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn map_hash(&self) -> &str {
+        self.map_hash.as_str()
+    }
+
+    pub fn map_name(&self) -> &str {
+        self.map_name.as_str()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GameDescriptionError {
+    #[error("Game name must have between 1 and {max_len} bytes, got {len}")]
+    GameName { len: usize, max_len: usize },
+    #[error("Map name must have between 1 and {max_len} bytes, got {len}")]
+    MapName { len: usize, max_len: usize },
+    #[error("Map hash must consist of {MAP_HASH_LEN} lowercase hexadecimal characters")]
+    MapHash,
+}
+
+/// A game announced by DE Connector in response to a LAN discovery query.
+/// Each game is announced in a separate datagram.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct LanGame {
+    port: u16,
+    num_players: u8,
+    max_players: Player,
+    description: GameDescription,
+}
+
+impl LanGame {
+    /// # Arguments
+    ///
+    /// * `port` - port of the game server.
+    ///
+    /// * `num_players` - number of players currently connected to the game.
+    ///
+    /// * `max_players` - maximum number of players of the game.
+    ///
+    /// * `description` - description of the game.
+    pub fn new(
+        port: u16,
+        num_players: u8,
+        max_players: Player,
+        description: GameDescription,
+    ) -> Self {
+        Self {
+            port,
+            num_players,
+            max_players,
+            description,
+        }
+    }
+
+    /// Port of the game server. Players join the game by connecting to this
+    /// port at the address of the announcing DE Connector.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn num_players(&self) -> u8 {
+        self.num_players
+    }
+
+    pub fn max_players(&self) -> Player {
+        self.max_players
+    }
+
+    pub fn description(&self) -> &GameDescription {
+        &self.description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_description() {
+        let hash = "0123456789abcdef".repeat(4);
+        let description =
+            GameDescription::new("Game".to_owned(), hash.clone(), "Map".to_owned()).unwrap();
+        assert_eq!(description.name(), "Game");
+        assert_eq!(description.map_hash(), hash);
+        assert_eq!(description.map_name(), "Map");
+
+        assert!(matches!(
+            GameDescription::new("".to_owned(), hash.clone(), "Map".to_owned()),
+            Err(GameDescriptionError::GameName { len: 0, .. })
+        ));
+        assert!(matches!(
+            GameDescription::new("Game".to_owned(), hash.clone(), "M".repeat(33)),
+            Err(GameDescriptionError::MapName { len: 33, .. })
+        ));
+        assert!(matches!(
+            GameDescription::new("Game".to_owned(), hash.to_uppercase(), "Map".to_owned()),
+            Err(GameDescriptionError::MapHash)
+        ));
+    }
+}
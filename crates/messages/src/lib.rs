@@ -2,13 +2,19 @@
 //! Connector during multiplayer game.
 
 pub use game::{FromGame, JoinError, Readiness, ToGame};
+pub use lan::{
+    GameDescription, GameDescriptionError, LanGame, MAX_LAN_GAME_NAME_LEN, MAX_LAN_MAP_NAME_LEN,
+};
+pub use map::{MapHashError, MapHashNet};
 pub use players::{
     BorrowedFromPlayers, ChatMessage, ChatMessageError, EntityNet, FromPlayers, HealthDelta,
-    NetEntityIndex, NetProjectile, PathError, PathNet, ToPlayers, TransformNet, Vec2Net, Vec3Net,
-    Vec4Net, MAX_CHAT_LEN,
+    InactiveNet, NetEntityIndex, NetProjectile, PathError, PathNet, ResourceAmount, ToPlayers,
+    TransformNet, Vec2Net, Vec3Net, Vec4Net, MAX_CHAT_LEN,
 };
 pub use server::{FromServer, GameOpenError, ToServer};
 
 mod game;
+mod lan;
+mod map;
 mod players;
 mod server;
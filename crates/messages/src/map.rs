@@ -0,0 +1,74 @@
+use bincode::{Decode, Encode};
+use thiserror::Error;
+
+/// Number of bytes of a map hash.
+const MAP_HASH_BYTES: usize = 32;
+
+/// Hash of the map of a game. All players of a single game must play on a map
+/// with the same hash.
+#[derive(Clone, Copy, Debug, Encode, Decode, PartialEq, Eq)]
+pub struct MapHashNet([u8; MAP_HASH_BYTES]);
+
+impl MapHashNet {
+    /// Constructs the hash from a hexadecimal string, for example as stored in
+    /// DE Lobby or in [`crate::GameDescription`].
+    pub fn from_hex(hex: &str) -> Result<Self, MapHashError> {
+        if hex.len() != 2 * MAP_HASH_BYTES {
+            return Err(MapHashError::Length(hex.len()));
+        }
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(MapHashError::Character);
+        }
+
+        let mut bytes = [0; MAP_HASH_BYTES];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[(2 * i)..(2 * i + 2)], 16).unwrap();
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl From<[u8; MAP_HASH_BYTES]> for MapHashNet {
+    fn from(bytes: [u8; MAP_HASH_BYTES]) -> Self {
+        Self(bytes)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MapHashError {
+    #[error("Map hash must consist of {} characters, got {0} bytes", 2 * MAP_HASH_BYTES)]
+    Length(usize),
+    #[error("Map hash contains a non-hexadecimal character")]
+    Character,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_hex() {
+        let mut expected = [0; MAP_HASH_BYTES];
+        expected[0] = 0x01;
+        expected[1] = 0xab;
+        expected[31] = 0xff;
+        let hex = format!("01ab{}ff", "0".repeat(58));
+        assert_eq!(
+            MapHashNet::from_hex(&hex).unwrap(),
+            MapHashNet::from(expected)
+        );
+        assert_eq!(
+            MapHashNet::from_hex(&hex.to_uppercase()).unwrap(),
+            MapHashNet::from(expected)
+        );
+
+        assert!(matches!(
+            MapHashNet::from_hex("01ab"),
+            Err(MapHashError::Length(4))
+        ));
+        assert!(matches!(
+            MapHashNet::from_hex(&"x".repeat(64)),
+            Err(MapHashError::Character)
+        ));
+    }
+}
@@ -9,6 +9,12 @@ pub const MAX_CHAT_LEN: usize = 140;
 #[derive(Debug, Encode, Decode)]
 pub struct ChatMessage(String);
 
+impl ChatMessage {
+    pub fn text(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
 impl TryFrom<String> for ChatMessage {
     type Error = ChatMessageError;
 
@@ -33,12 +33,37 @@ impl EntityNet {
 #[derive(Clone, Copy, Debug, Encode, Decode, Hash, PartialEq, Eq)]
 pub struct NetEntityIndex(u32);
 
+/// Identification of an inactive object (e.g. a tree).
+///
+/// Inactive objects are not owned by any player and are spawned in the same
+/// order by all game instances. The index is the order of spawning.
+#[derive(Clone, Copy, Debug, Encode, Decode, Hash, PartialEq, Eq)]
+pub struct InactiveNet(u32);
+
+impl InactiveNet {
+    pub fn new(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl From<InactiveNet> for u32 {
+    fn from(index: InactiveNet) -> u32 {
+        index.0
+    }
+}
+
 impl From<NetEntityIndex> for u32 {
     fn from(index: NetEntityIndex) -> u32 {
         index.0
     }
 }
 
+impl From<u32> for NetEntityIndex {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
 #[cfg(feature = "bevy")]
 impl From<Entity> for NetEntityIndex {
     fn from(entity: Entity) -> Self {
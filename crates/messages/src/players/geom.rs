@@ -14,6 +14,12 @@ pub struct TransformNet {
     rotation: Vec4Net,
 }
 
+impl TransformNet {
+    pub fn translation(&self) -> Vec3Net {
+        self.translation
+    }
+}
+
 #[cfg(feature = "bevy")]
 impl From<&Transform> for TransformNet {
     fn from(transform: &Transform) -> Self {
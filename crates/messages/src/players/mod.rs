@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use bincode::{Decode, Encode};
 pub use chat::{ChatMessage, ChatMessageError, MAX_CHAT_LEN};
 use de_types::{objects::ActiveObjectType, player::Player};
-pub use entity::{EntityNet, NetEntityIndex};
+pub use entity::{EntityNet, InactiveNet, NetEntityIndex};
 pub use geom::{TransformNet, Vec2Net, Vec3Net, Vec4Net};
 pub use path::{PathError, PathNet};
 pub use projectile::NetProjectile;
@@ -87,8 +89,52 @@ pub enum ToPlayers {
         entity: EntityNet,
         delta: HealthDelta,
     },
+    /// Temporarily disables an active object, for example after being hit by
+    /// an EMP. Disabled objects can neither fire, move nor use energy.
+    Disable {
+        entity: EntityNet,
+        duration: Duration,
+    },
+    /// Changes health of an inactive object by an amount.
+    ChangeInactiveHealth {
+        object: InactiveNet,
+        delta: HealthDelta,
+    },
     /// Some kind of projectile was spawned (e.g. rocket, laser trail).
     Projectile(NetProjectile),
+    /// The sending player surrendered. Despawning of all its active objects
+    /// is announced separately by the player.
+    Surrender,
+    /// Transfer ownership of an active object to another player. The object
+    /// remains simulated by the sending player.
+    TransferOwnership {
+        entity: EntityNet,
+        player: Player,
+    },
+    /// Cloaks or uncloaks an active object simulated by the sending player.
+    /// Cloaked objects are hidden from enemies unless detected.
+    Cloak {
+        entity: EntityNet,
+        cloaked: bool,
+    },
+    /// Garrisons an active object simulated by the sending player in a
+    /// carrier (e.g. a transport or a bunker), or ungarrisons it if the
+    /// carrier is None.
+    Garrison {
+        entity: EntityNet,
+        carrier: Option<EntityNet>,
+    },
+    /// Flat position of the point the camera of the sending player is
+    /// focused on. It is used to prioritize network updates of objects close
+    /// to the viewports of other players.
+    Viewport(Vec2Net),
+    /// Removes resources from a resource deposit (an inactive object). It is
+    /// sent by game instances simulating harvesters gathering from the
+    /// deposit.
+    ExtractDeposit {
+        object: InactiveNet,
+        amount: ResourceAmount,
+    },
 }
 
 #[derive(Debug, Encode, Decode)]
@@ -111,3 +157,24 @@ impl From<&HealthDelta> for f32 {
         delta.0
     }
 }
+
+#[derive(Debug, Encode, Decode)]
+pub struct ResourceAmount(f32);
+
+impl TryFrom<f32> for ResourceAmount {
+    type Error = &'static str;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        if value.is_finite() && value >= 0. {
+            Ok(Self(value))
+        } else {
+            Err("Got negative or non-finite resource amount.")
+        }
+    }
+}
+
+impl From<&ResourceAmount> for f32 {
+    fn from(amount: &ResourceAmount) -> f32 {
+        amount.0
+    }
+}
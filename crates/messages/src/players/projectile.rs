@@ -1,4 +1,5 @@
 use bincode::{Decode, Encode};
+use de_types::player::Player;
 
 use crate::players::Vec3Net;
 
@@ -9,4 +10,27 @@ pub enum NetProjectile {
         /// End of the trail lies at `origin + direction`.
         direction: Vec3Net,
     },
+    Shell {
+        /// Owner of the firing object.
+        player: Player,
+        origin: Vec3Net,
+        /// Initial velocity of the ballistic projectile.
+        velocity: Vec3Net,
+    },
+    /// A previously fired shell or rocket was shot down. The projectile is
+    /// identified by its launch parameters.
+    Intercepted { origin: Vec3Net, velocity: Vec3Net },
+    Rocket {
+        /// Owner of the firing object.
+        player: Player,
+        origin: Vec3Net,
+        /// Constant velocity of the projectile flying along a straight line.
+        velocity: Vec3Net,
+    },
+    /// Trail of a repair beam.
+    RepairBeam {
+        origin: Vec3Net,
+        /// End of the trail lies at `origin + direction`.
+        direction: Vec3Net,
+    },
 }
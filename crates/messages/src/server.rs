@@ -1,6 +1,8 @@
 use bincode::{Decode, Encode};
 use de_types::player::Player;
 
+use crate::{GameDescription, MapHashNet};
+
 /// Message to be sent from a player/client to a main server (outside of a
 /// game).
 #[derive(Debug, Encode, Decode)]
@@ -9,7 +11,17 @@ pub enum ToServer {
     Ping(u32),
     /// This message opens a new game on the server. The server responds with
     /// [`FromServer::GameOpened`].
-    OpenGame { max_players: Player },
+    OpenGame {
+        max_players: Player,
+        /// Hash of the map of the game. Only players with the same map hash
+        /// may join the game.
+        map_hash: MapHashNet,
+        /// Number of seconds counted down before the game starts once all
+        /// players are ready. See [`crate::FromGame::Countdown`].
+        countdown: u8,
+        /// The game is announced to clients in the local network if not None.
+        description: Option<GameDescription>,
+    },
 }
 
 /// Message to be sent from a main server to a player/client (outside of a
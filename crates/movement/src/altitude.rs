@@ -6,6 +6,7 @@ use de_core::{
     state::AppState,
 };
 use de_objects::SolidObjects;
+use de_uom::{Metre, MetrePerSecond};
 
 use crate::{
     movement::DesiredVelocity,
@@ -36,19 +37,25 @@ pub(crate) enum AltitudeSet {
     Update,
 }
 
-#[derive(Component, Default)]
-pub(crate) struct DesiredClimbing(f32);
+#[derive(Component)]
+pub(crate) struct DesiredClimbing(MetrePerSecond);
 
 impl DesiredClimbing {
-    pub(crate) fn speed(&self) -> f32 {
+    pub(crate) fn speed(&self) -> MetrePerSecond {
         self.0
     }
 
-    pub(crate) fn set_speed(&mut self, speed: f32) {
+    pub(crate) fn set_speed(&mut self, speed: MetrePerSecond) {
         self.0 = speed;
     }
 }
 
+impl Default for DesiredClimbing {
+    fn default() -> Self {
+        Self(MetrePerSecond::ZERO)
+    }
+}
+
 fn setup_entities(
     mut commands: Commands,
     objects: Query<Entity, (With<MovableSolid>, Without<DesiredClimbing>)>,
@@ -84,15 +91,17 @@ fn update(
                 flight.max_height()
             };
 
-            let remaining = desired_height - height;
-            let max_acceleration = if remaining > 0. {
+            let remaining = Metre::new(desired_height - height);
+            let max_acceleration = if remaining > Metre::ZERO {
                 G_ACCELERATION
             } else {
                 MAX_V_ACCELERATION
             };
             // Make sure that the object can slow down soon enough.
-            let desired = remaining.signum()
-                * MAX_V_SPEED.min((2. * remaining.abs() * max_acceleration).sqrt());
+            let stopping_speed = MetrePerSecond::new(
+                (2. * remaining.abs().inner() * max_acceleration.inner()).sqrt(),
+            );
+            let desired = remaining.inner().signum() * MAX_V_SPEED.min(stopping_speed);
 
             // Avoid change detection when possible.
             if climbing.speed() != desired {
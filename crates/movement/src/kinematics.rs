@@ -3,17 +3,20 @@ use std::f32::consts::{FRAC_PI_4, PI, TAU};
 use bevy::prelude::*;
 use de_core::{
     gamestate::GameState,
-    objects::MovableSolid,
+    objects::{MovableSolid, ObjectTypeComponent},
     schedule::{Movement, PreMovement},
     state::AppState,
 };
+use de_objects::SolidObjects;
 use de_types::projection::ToAltitude;
+use de_uom::{MetrePerSecond, Second};
 
 use crate::{
     altitude::{AltitudeSet, DesiredClimbing},
+    max_h_speed,
     movement::{DesiredVelocity, MovementSet, ObjectVelocity},
     repulsion::{RepulsionLables, RepulsionVelocity},
-    G_ACCELERATION, MAX_ANGULAR_SPEED, MAX_H_SPEED, MAX_V_ACCELERATION, MAX_V_SPEED,
+    G_ACCELERATION, MAX_ANGULAR_SPEED, MAX_H_ACCELERATION, MAX_V_ACCELERATION, MAX_V_SPEED,
 };
 
 pub(crate) struct KinematicsPlugin;
@@ -46,20 +49,20 @@ type Uninitialized<'w, 's> =
 
 #[derive(Component)]
 struct Kinematics {
-    /// Current horizontal speed in meters per second.
-    horizontal_speed: f32,
-    /// Current vertical speed in meters per second.
-    vertical_speed: f32,
+    /// Current horizontal speed.
+    horizontal_speed: MetrePerSecond,
+    /// Current vertical speed.
+    vertical_speed: MetrePerSecond,
     /// Current object heading in radians.
     heading: f32,
 }
 
 impl Kinematics {
-    fn horizontal_speed(&self) -> f32 {
+    fn horizontal_speed(&self) -> MetrePerSecond {
         self.horizontal_speed
     }
 
-    fn vertical_speed(&self) -> f32 {
+    fn vertical_speed(&self) -> MetrePerSecond {
         self.vertical_speed
     }
 
@@ -67,13 +70,14 @@ impl Kinematics {
         self.heading
     }
 
-    fn update_horizontal_speed(&mut self, delta: f32) {
-        debug_assert!(delta.is_finite());
-        self.horizontal_speed = (self.horizontal_speed + delta).clamp(0., MAX_H_SPEED);
+    fn update_horizontal_speed(&mut self, delta: MetrePerSecond, max_speed: MetrePerSecond) {
+        debug_assert!(delta.inner().is_finite());
+        self.horizontal_speed =
+            (self.horizontal_speed + delta).clamp(MetrePerSecond::ZERO, max_speed);
     }
 
-    fn update_vertical_speed(&mut self, delta: f32) {
-        debug_assert!(delta.is_finite());
+    fn update_vertical_speed(&mut self, delta: MetrePerSecond) {
+        debug_assert!(delta.inner().is_finite());
         self.vertical_speed = (self.vertical_speed + delta).clamp(-MAX_V_SPEED, MAX_V_SPEED);
     }
 
@@ -84,15 +88,16 @@ impl Kinematics {
 
     fn compute_velocity(&self) -> Vec3 {
         let (sin, cos) = self.heading.sin_cos();
-        (self.horizontal_speed * Vec2::new(cos, sin)).to_altitude(self.vertical_speed)
+        (self.horizontal_speed.inner() * Vec2::new(cos, sin))
+            .to_altitude(self.vertical_speed.inner())
     }
 }
 
 impl From<&Transform> for Kinematics {
     fn from(transform: &Transform) -> Self {
         Self {
-            horizontal_speed: 0.,
-            vertical_speed: 0.,
+            horizontal_speed: MetrePerSecond::ZERO,
+            vertical_speed: MetrePerSecond::ZERO,
             heading: normalize_angle(transform.rotation.to_euler(EulerRot::YXZ).0),
         }
     }
@@ -106,18 +111,19 @@ fn setup_entities(mut commands: Commands, objects: Uninitialized) {
 
 fn kinematics(
     time: Res<Time>,
+    solids: SolidObjects,
     mut objects: Query<(
+        &ObjectTypeComponent,
         &DesiredVelocity<RepulsionVelocity>,
         &DesiredClimbing,
         &mut Kinematics,
         &mut ObjectVelocity,
     )>,
 ) {
-    let time_delta = time.delta_seconds();
+    let time_delta = Second::try_from(time.delta_seconds()).unwrap();
 
-    objects
-        .par_iter_mut()
-        .for_each(|(movement, climbing, mut kinematics, mut velocity)| {
+    objects.par_iter_mut().for_each(
+        |(object_type, movement, climbing, mut kinematics, mut velocity)| {
             let desired_h_velocity = movement.velocity();
             let desired_heading = if desired_h_velocity == Vec2::ZERO {
                 kinematics.heading()
@@ -130,24 +136,26 @@ fn kinematics(
             let heading_delta = heading_diff.clamp(-max_heading_delta, max_heading_delta);
             kinematics.update_heading(heading_delta);
 
-            let max_h_speed_delta = MAX_H_SPEED * time_delta;
+            let max_h_speed_delta = MAX_H_ACCELERATION * time_delta;
             let h_speed_delta = if (heading_diff - heading_delta).abs() > FRAC_PI_4 {
                 // Slow down if not going in roughly good direction.
                 -kinematics.horizontal_speed()
             } else {
-                desired_h_velocity.length() - kinematics.horizontal_speed()
+                MetrePerSecond::new(desired_h_velocity.length()) - kinematics.horizontal_speed()
             }
             .clamp(-max_h_speed_delta, max_h_speed_delta);
-            kinematics.update_horizontal_speed(h_speed_delta);
+            kinematics
+                .update_horizontal_speed(h_speed_delta, max_h_speed(solids.get(**object_type)));
 
             let v_speed_delta = (climbing.speed() - kinematics.vertical_speed()).clamp(
-                -time_delta * G_ACCELERATION,
-                time_delta * MAX_V_ACCELERATION,
+                -(G_ACCELERATION * time_delta),
+                MAX_V_ACCELERATION * time_delta,
             );
             kinematics.update_vertical_speed(v_speed_delta);
 
             velocity.update(kinematics.compute_velocity(), kinematics.heading());
-        });
+        },
+    );
 }
 
 fn normalize_angle(mut angle: f32) -> f32 {
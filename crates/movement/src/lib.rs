@@ -12,6 +12,8 @@ use std::f32::consts::PI;
 
 use altitude::AltitudePlugin;
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
+use de_objects::SolidObject;
+use de_uom::{Metre, MetrePerSecond, MetrePerSecondSquared, Quantity, RadianPerSecond};
 use kinematics::KinematicsPlugin;
 use movement::MovementPlugin;
 use obstacles::ObstaclesPlugin;
@@ -19,20 +21,22 @@ use pathing::PathingPlugin;
 use repulsion::RepulsionPlugin;
 use syncing::SyncingPlugin;
 
-/// Maximum object horizontal speed in meters per second.
-const MAX_H_SPEED: f32 = 10.;
-/// Maximum object vertical ascending / descending rate in meters per second.
-const MAX_V_SPEED: f32 = 4.;
-/// Maximum object acceleration in meters per second squared.
-const MAX_H_ACCELERATION: f32 = 2. * MAX_H_SPEED;
-/// Gravitational acceleration in meters per second squared.
-const G_ACCELERATION: f32 = 9.8;
-/// Maximum upwards acceleration in meters per second squared.
-const MAX_V_ACCELERATION: f32 = 0.5 * G_ACCELERATION;
-/// Maximum object angular velocity in radians per second.
-const MAX_ANGULAR_SPEED: f32 = PI;
-/// Maximum altitude in meters (note that this is not height).
-const MAX_ALTITUDE: f32 = 100.;
+/// Maximum horizontal speed of objects without custom mobility
+/// configuration.
+const MAX_H_SPEED: MetrePerSecond = Quantity::new_unchecked(10.);
+/// Maximum object vertical ascending / descending rate.
+const MAX_V_SPEED: MetrePerSecond = Quantity::new_unchecked(4.);
+/// Maximum object horizontal acceleration.
+const MAX_H_ACCELERATION: MetrePerSecondSquared = Quantity::new_unchecked(20.);
+/// Gravitational acceleration.
+const G_ACCELERATION: MetrePerSecondSquared = Quantity::new_unchecked(9.8);
+/// Maximum upwards acceleration.
+const MAX_V_ACCELERATION: MetrePerSecondSquared =
+    Quantity::new_unchecked(0.5 * G_ACCELERATION.inner());
+/// Maximum object angular velocity.
+const MAX_ANGULAR_SPEED: RadianPerSecond = Quantity::new_unchecked(PI);
+/// Maximum altitude (note that this is not height).
+const MAX_ALTITUDE: Metre = Quantity::new_unchecked(100.);
 
 pub struct MovementPluginGroup;
 
@@ -48,3 +52,10 @@ impl PluginGroup for MovementPluginGroup {
             .add(SyncingPlugin)
     }
 }
+
+/// Returns maximum horizontal speed of an object.
+fn max_h_speed(solid: &SolidObject) -> MetrePerSecond {
+    solid
+        .mobility()
+        .map_or(MAX_H_SPEED, |mobility| mobility.max_speed())
+}
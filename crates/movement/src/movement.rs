@@ -150,6 +150,6 @@ fn update_transform(
 fn clamp(bounds: &MapBounds, translation: Vec3) -> Vec3 {
     let offset = Vec2::splat(EXCLUSION_OFFSET);
     let a = (bounds.min() + offset).to_msl();
-    let b = (bounds.max() - offset).to_altitude(MAX_ALTITUDE);
+    let b = (bounds.max() - offset).to_altitude(MAX_ALTITUDE.inner());
     translation.clamp(a.min(b), a.max(b))
 }
@@ -7,13 +7,14 @@ use de_core::{
 use de_index::SpatialQuery;
 use de_objects::SolidObjects;
 use de_types::projection::ToFlat;
+use de_uom::{Metre, Quantity};
 use parry3d::{bounding_volume::Aabb, math::Point};
 
 use crate::{cache::DecayingCache, disc::Disc};
 
 /// Obstacle avoidance algorithm takes into account only obstacles inside a
 /// rectangle of this half-size.
-const NEARBY_HALF_EXTENT: f32 = 10.;
+const NEARBY_HALF_EXTENT: Metre = Quantity::new_unchecked(10.);
 
 pub(crate) struct ObstaclesPlugin;
 
@@ -78,7 +79,7 @@ fn update_nearby<M: Send + Sync + 'static, T: Component>(
         .par_iter_mut()
         .for_each(|(entity, transform, mut cache)| {
             cache.clear();
-            let half_extent = Vec3::splat(NEARBY_HALF_EXTENT);
+            let half_extent = Vec3::splat(NEARBY_HALF_EXTENT.inner());
             let mins = transform.translation - half_extent;
             let maxs = transform.translation + half_extent;
             let region = Aabb::new(Point::from(mins), Point::from(maxs));
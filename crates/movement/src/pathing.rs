@@ -1,18 +1,25 @@
 use bevy::prelude::*;
 use de_core::{
     gamestate::GameState,
+    objects::{Anchored, Disabled, Garrisoned, ObjectTypeComponent},
     schedule::{Movement, PreMovement},
     state::AppState,
 };
+use de_objects::SolidObjects;
 use de_pathing::ScheduledPath;
 use de_types::projection::ToFlat;
+use de_uom::{Metre, MetrePerSecond, Quantity, Second};
 
 use crate::{
+    max_h_speed,
     movement::{add_desired_velocity, DesiredVelocity},
-    MAX_H_ACCELERATION, MAX_H_SPEED,
+    MAX_H_ACCELERATION,
 };
 
-const DESTINATION_ACCURACY: f32 = 0.1;
+const DESTINATION_ACCURACY: Metre = Quantity::new_unchecked(0.1);
+/// Objects head towards a point on their path which they would reach at full
+/// speed in this time.
+const LOOKAHEAD_TIME: Second = Quantity::new_unchecked(0.5);
 
 pub(crate) struct PathingPlugin;
 
@@ -41,6 +48,20 @@ pub(crate) enum PathingSet {
     FollowPath,
 }
 
+type FollowingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ObjectTypeComponent,
+        &'static Transform,
+        &'static mut ScheduledPath,
+        &'static mut DesiredVelocity<PathVelocity>,
+        Has<Anchored>,
+        Has<Disabled>,
+        Has<Garrisoned>,
+    ),
+>;
+
 fn finish_paths(
     mut commands: Commands,
     mut objects: Query<(
@@ -52,28 +73,32 @@ fn finish_paths(
 ) {
     for (entity, transform, path, mut movement) in objects.iter_mut() {
         let remaining = path.destination().distance(transform.translation.to_flat());
-        if remaining <= DESTINATION_ACCURACY {
+        if remaining <= DESTINATION_ACCURACY.inner() {
             movement.stop();
             commands.entity(entity).remove::<ScheduledPath>();
         }
     }
 }
 
-fn follow_path(
-    mut objects: Query<(
-        &Transform,
-        &mut ScheduledPath,
-        &mut DesiredVelocity<PathVelocity>,
-    )>,
-) {
-    objects
-        .par_iter_mut()
-        .for_each(|(transform, mut path, mut movement)| {
+fn follow_path(solids: SolidObjects, mut objects: FollowingQuery) {
+    objects.par_iter_mut().for_each(
+        |(object_type, transform, mut path, mut movement, anchored, disabled, garrisoned)| {
+            if anchored || disabled || garrisoned {
+                if !movement.stationary() {
+                    movement.stop();
+                }
+                return;
+            }
+
+            let max_speed = max_h_speed(solids.get(**object_type));
             let location = transform.translation.to_flat();
             let remaining = path.destination().distance(location);
-            let advancement = path.advance(location, MAX_H_SPEED * 0.5);
+            let advancement = path.advance(location, (max_speed * LOOKAHEAD_TIME).inner());
             let direction = (advancement - location).normalize();
-            let desired_speed = MAX_H_SPEED.min((2. * remaining * MAX_H_ACCELERATION).sqrt());
-            movement.update(desired_speed * direction);
-        });
+            let stopping_speed =
+                MetrePerSecond::new((2. * remaining * MAX_H_ACCELERATION.inner()).sqrt());
+            let desired_speed = max_speed.min(stopping_speed);
+            movement.update(desired_speed.inner() * direction);
+        },
+    );
 }
@@ -8,20 +8,22 @@ use de_core::{
 use de_map::size::MapBounds;
 use de_objects::{SolidObjects, EXCLUSION_OFFSET};
 use de_types::projection::ToFlat;
+use de_uom::{Metre, Quantity};
 use parry2d::{math::Isometry, query::PointQuery};
 
 use crate::{
     cache::DecayingCache,
     disc::Disc,
+    max_h_speed,
     movement::{add_desired_velocity, DesiredVelocity},
     obstacles::{MovableObstacles, ObstaclesLables, StaticObstacles},
     pathing::{PathVelocity, PathingSet},
-    MAX_H_ACCELERATION, MAX_H_SPEED,
+    MAX_H_ACCELERATION,
 };
 
-const MAX_REPULSION_DISTANCE: f32 = 4.0;
-const MIN_STATIC_OBJECT_DISTANCE: f32 = 1.;
-const MIN_MOVABLE_OBJECT_DISTANCE: f32 = 0.5;
+const MAX_REPULSION_DISTANCE: Metre = Quantity::new_unchecked(4.0);
+const MIN_STATIC_OBJECT_DISTANCE: Metre = Quantity::new_unchecked(1.);
+const MIN_MOVABLE_OBJECT_DISTANCE: Metre = Quantity::new_unchecked(0.5);
 const REPULSION_FACTOR: f32 = 0.6;
 
 pub(crate) struct RepulsionPlugin;
@@ -85,7 +87,7 @@ impl Repulsion {
     ///   until it reaches the point of minimum allowed distance. Note that
     ///   minimum allowed distance might be larger than zero.
     fn add(&mut self, direction: Vec2, room: f32) {
-        let mut max = REPULSION_FACTOR * (2. * MAX_H_ACCELERATION).sqrt();
+        let mut max = REPULSION_FACTOR * (2. * MAX_H_ACCELERATION.inner()).sqrt();
         if room > 0. {
             max *= room.sqrt();
         } else {
@@ -181,14 +183,14 @@ fn repel_static(
                 }
                 distance -= disc.radius();
 
-                if distance > MAX_REPULSION_DISTANCE {
+                if distance > MAX_REPULSION_DISTANCE.inner() {
                     continue;
                 }
 
                 let neg_local_normal = -footprint.feature_normal(feature_id).unwrap();
                 let direction = Vec2::from(isometry.transform_vector(&neg_local_normal));
 
-                repulsion.add(direction, distance - MIN_STATIC_OBJECT_DISTANCE);
+                repulsion.add(direction, distance - MIN_STATIC_OBJECT_DISTANCE.inner());
             }
         });
 }
@@ -219,8 +221,8 @@ fn repel_movable(
                     diff / distance
                 };
                 distance -= disc.radius() + other_disc.radius();
-                if distance < MAX_REPULSION_DISTANCE {
-                    repulsion.add(direction, distance - MIN_MOVABLE_OBJECT_DISTANCE);
+                if distance < MAX_REPULSION_DISTANCE.inner() {
+                    repulsion.add(direction, distance - MIN_MOVABLE_OBJECT_DISTANCE.inner());
                 }
             }
         });
@@ -246,24 +248,27 @@ fn repel_bounds(
             let diff_norm = diff.length();
             let distance = diff_norm - disc.radius();
 
-            if distance < MAX_REPULSION_DISTANCE {
+            if distance < MAX_REPULSION_DISTANCE.inner() {
                 repulsion.add(diff / diff_norm, distance - EXCLUSION_OFFSET);
             }
         });
 }
 
 fn apply(
+    solids: SolidObjects,
     mut objects: Query<(
+        &ObjectTypeComponent,
         &mut Repulsion,
         &DesiredVelocity<PathVelocity>,
         &mut DesiredVelocity<RepulsionVelocity>,
     )>,
 ) {
-    objects
-        .par_iter_mut()
-        .for_each(|(mut repulsion, path_velocity, mut repulsion_velocity)| {
+    objects.par_iter_mut().for_each(
+        |(object_type, mut repulsion, path_velocity, mut repulsion_velocity)| {
             let velocity = repulsion.apply(path_velocity.velocity());
-            repulsion_velocity.update(velocity.clamp_length_max(MAX_H_SPEED));
+            let max_speed = max_h_speed(solids.get(**object_type));
+            repulsion_velocity.update(velocity.clamp_length_max(max_speed.inner()));
             repulsion.clear();
-        });
+        },
+    );
 }
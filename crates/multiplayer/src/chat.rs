@@ -0,0 +1,366 @@
+//! Display of chat messages received from other players together with
+//! client-side moderation: muting of individual users and censoring of
+//! profane words.
+
+use ahash::{AHashMap, AHashSet};
+use async_std::{fs, io, path::PathBuf};
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
+use de_conf::Configuration;
+use de_core::{
+    command::{register_command, CommandEvent, CommandInfo},
+    fs::{conf_dir, DirError},
+    log_full_error,
+    state::AppState,
+};
+use de_gui::ToastEvent;
+use de_types::player::Player;
+use iyes_progress::prelude::*;
+use thiserror::Error;
+
+use crate::playermsg::NetRecvChatEvent;
+
+/// Lowercase beginnings of words censored by the profanity filter.
+const PROFANITIES: [&str; 8] = [
+    "asshole",
+    "bastard",
+    "bitch",
+    "cunt",
+    "fuck",
+    "motherfuck",
+    "shit",
+    "wanker",
+];
+
+const MUTE_COMMAND: CommandInfo =
+    CommandInfo::new("mute", &["player"], "Hide chat messages of a player.");
+const UNMUTE_COMMAND: CommandInfo = CommandInfo::new(
+    "unmute",
+    &["player"],
+    "Show chat messages of a previously muted player.",
+);
+
+pub(crate) struct ChatPlugin;
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        register_command(app, MUTE_COMMAND);
+        register_command(app, UNMUTE_COMMAND);
+
+        app.add_event::<MuteUserEvent>()
+            .add_systems(OnEnter(AppState::AppLoading), start_loading)
+            .add_systems(OnExit(AppState::AppLoading), cleanup_loading)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                poll_mutes
+                    .track_progress()
+                    .run_if(in_state(AppState::AppLoading)),
+            )
+            .add_systems(
+                Update,
+                (
+                    mute_command.run_if(on_event::<CommandEvent>()).before(mute),
+                    mute.run_if(on_event::<MuteUserEvent>()),
+                )
+                    .run_if(resource_exists::<MutedUsers>),
+            )
+            .add_systems(
+                Update,
+                display
+                    .run_if(on_event::<NetRecvChatEvent>())
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Usernames of players whose chat messages are not displayed. The set is
+/// persisted in the local configuration directory.
+#[derive(Resource, Default)]
+pub struct MutedUsers(AHashSet<String>);
+
+impl MutedUsers {
+    pub fn is_muted(&self, username: &str) -> bool {
+        self.0.contains(username)
+    }
+}
+
+/// Send this event to mute or unmute chat messages from a user.
+#[derive(Event)]
+pub struct MuteUserEvent {
+    username: String,
+    muted: bool,
+}
+
+impl MuteUserEvent {
+    /// # Arguments
+    ///
+    /// * `username` - username of the (un)muted user.
+    ///
+    /// * `muted` - true if the user is to be muted, false if unmuted.
+    pub fn new(username: String, muted: bool) -> Self {
+        Self { username, muted }
+    }
+}
+
+/// Usernames of players participating in the current multiplayer game. This
+/// resource is expected to be inserted before the game starts. It is removed
+/// once the game ends.
+#[derive(Resource)]
+pub struct PlayerNames(AHashMap<Player, String>);
+
+impl PlayerNames {
+    pub fn get(&self, player: Player) -> Option<&str> {
+        self.0.get(&player).map(|name| name.as_str())
+    }
+
+    /// Returns the username of a player identified either by the username
+    /// itself or by the player number.
+    fn resolve(&self, player: &str) -> Option<&str> {
+        match player
+            .parse::<u8>()
+            .ok()
+            .and_then(|num| Player::try_from(num).ok())
+        {
+            Some(player) => self.get(player),
+            None => self
+                .0
+                .values()
+                .find(|name| name.as_str() == player)
+                .map(|name| name.as_str()),
+        }
+    }
+}
+
+impl FromIterator<(Player, String)> for PlayerNames {
+    fn from_iter<T: IntoIterator<Item = (Player, String)>>(iter: T) -> Self {
+        Self(AHashMap::from_iter(iter))
+    }
+}
+
+#[derive(Resource)]
+struct LoadingTask(Task<Result<AHashSet<String>, MutesError>>);
+
+fn start_loading(mut commands: Commands) {
+    let task = IoTaskPool::get().spawn(load_mutes());
+    commands.insert_resource(LoadingTask(task));
+}
+
+fn cleanup_loading(mut commands: Commands) {
+    commands.remove_resource::<LoadingTask>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<PlayerNames>();
+}
+
+fn poll_mutes(
+    mut commands: Commands,
+    task: Option<ResMut<LoadingTask>>,
+    muted: Option<Res<MutedUsers>>,
+) -> Progress {
+    if muted.is_some() {
+        return true.into();
+    }
+
+    let Some(mut task) = task else {
+        return false.into();
+    };
+    match future::block_on(future::poll_once(&mut task.0)) {
+        Some(Ok(usernames)) => {
+            commands.insert_resource(MutedUsers(usernames));
+            true.into()
+        }
+        Some(Err(err)) => {
+            log_full_error!(err);
+            commands.init_resource::<MutedUsers>();
+            true.into()
+        }
+        None => false.into(),
+    }
+}
+
+fn mute_command(
+    names: Option<Res<PlayerNames>>,
+    mut commands: EventReader<CommandEvent>,
+    mut events: EventWriter<MuteUserEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    for command in commands.read() {
+        let muted = if command.name() == MUTE_COMMAND.name() {
+            true
+        } else if command.name() == UNMUTE_COMMAND.name() {
+            false
+        } else {
+            continue;
+        };
+
+        let player = command.args()[0].as_str();
+        let Some(username) = names.as_ref().and_then(|names| names.resolve(player)) else {
+            toasts.send(ToastEvent::new(format!("Unknown player: {player}")));
+            continue;
+        };
+
+        let action = if muted { "muted" } else { "unmuted" };
+        toasts.send(ToastEvent::new(format!("User {username} {action}.")));
+        events.send(MuteUserEvent::new(username.to_owned(), muted));
+    }
+}
+
+fn mute(mut events: EventReader<MuteUserEvent>, mut muted: ResMut<MutedUsers>) {
+    let mut changed = false;
+    for event in events.read() {
+        changed |= if event.muted {
+            muted.0.insert(event.username.clone())
+        } else {
+            muted.0.remove(event.username.as_str())
+        };
+    }
+    if !changed {
+        return;
+    }
+
+    let mut usernames: Vec<String> = muted.0.iter().cloned().collect();
+    usernames.sort();
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Err(err) = store_mutes(&usernames).await {
+                log_full_error!(err);
+            }
+        })
+        .detach();
+}
+
+fn display(
+    conf: Res<Configuration>,
+    names: Option<Res<PlayerNames>>,
+    muted: Res<MutedUsers>,
+    mut events: EventReader<NetRecvChatEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    for event in events.read() {
+        let name = names.as_ref().and_then(|names| names.get(event.player()));
+        if name.is_some_and(|name| muted.is_muted(name)) {
+            continue;
+        }
+
+        let text = if conf.chat().profanity_filter() {
+            censor(event.text())
+        } else {
+            event.text().to_owned()
+        };
+
+        let sender = match name {
+            Some(name) => name.to_owned(),
+            None => event.player().to_string(),
+        };
+        toasts.send(ToastEvent::new(format!("{sender}: {text}")));
+    }
+}
+
+/// Returns the text with all letters of profane words replaced with `*`.
+fn censor(text: &str) -> String {
+    let mut censored = String::with_capacity(text.len());
+    let mut word_start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), word_start) {
+            (true, None) => word_start = Some(i),
+            (true, Some(_)) => (),
+            (false, Some(start)) => {
+                push_word(&mut censored, &text[start..i]);
+                censored.push(c);
+                word_start = None;
+            }
+            (false, None) => censored.push(c),
+        }
+    }
+    if let Some(start) = word_start {
+        push_word(&mut censored, &text[start..]);
+    }
+
+    censored
+}
+
+fn push_word(target: &mut String, word: &str) {
+    let lowercase = word.to_lowercase();
+    if PROFANITIES.iter().any(|root| lowercase.starts_with(root)) {
+        target.extend(word.chars().map(|_| '*'));
+    } else {
+        target.push_str(word);
+    }
+}
+
+async fn load_mutes() -> Result<AHashSet<String>, MutesError> {
+    let path = mutes_path()?;
+    if !path.is_file().await {
+        return Ok(AHashSet::new());
+    }
+
+    let bytes = fs::read(path)
+        .await
+        .map_err(|source| MutesError::Io { source })?;
+    let usernames: Vec<String> =
+        serde_json::from_slice(&bytes).map_err(|source| MutesError::Json { source })?;
+    Ok(usernames.into_iter().collect())
+}
+
+async fn store_mutes(usernames: &[String]) -> Result<(), MutesError> {
+    let path = mutes_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|source| MutesError::Io { source })?;
+    }
+
+    let bytes = serde_json::to_vec(usernames).map_err(|source| MutesError::Json { source })?;
+    fs::write(path, bytes)
+        .await
+        .map_err(|source| MutesError::Io { source })
+}
+
+fn mutes_path() -> Result<PathBuf, MutesError> {
+    conf_dir()
+        .map(|dir| dir.join("muted.json"))
+        .map_err(|source| MutesError::Dir { source })
+}
+
+#[derive(Error, Debug)]
+enum MutesError {
+    #[error("configuration directory error")]
+    Dir { source: DirError },
+    #[error(transparent)]
+    Io { source: io::Error },
+    #[error("muted users JSON error")]
+    Json { source: serde_json::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_censor() {
+        assert_eq!(censor(""), "");
+        assert_eq!(censor("Good game!"), "Good game!");
+        assert_eq!(censor("Shit, my base."), "****, my base.");
+        assert_eq!(censor("you FUCKING noob"), "you ******* noob");
+        assert_eq!(censor("bastards"), "********");
+        assert_eq!(censor("ok...bitch!?"), "ok...*****!?");
+        assert_eq!(censor("Wir sind glücklich"), "Wir sind glücklich");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let names = PlayerNames::from_iter([
+            (Player::Player1, "Alice".to_owned()),
+            (Player::Player3, "Bob".to_owned()),
+        ]);
+        assert_eq!(names.resolve("Bob"), Some("Bob"));
+        assert_eq!(names.resolve("1"), Some("Alice"));
+        assert_eq!(names.resolve("2"), None);
+        assert_eq!(names.resolve("Carol"), None);
+    }
+}
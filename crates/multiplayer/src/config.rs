@@ -1,20 +1,87 @@
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 
+use de_messages::{GameDescription, MapHashNet};
 use de_types::player::Player;
 
 pub struct NetGameConf {
     server_host: IpAddr,
     connection_type: ConnectionType,
+    map_hash: MapHashNet,
+    countdown: u8,
+    description: Option<GameDescription>,
 }
 
 impl NetGameConf {
-    pub fn new(server_host: IpAddr, connection_type: ConnectionType) -> Self {
+    /// # Arguments
+    ///
+    /// * `server_host` - address of DE Connector.
+    ///
+    /// * `connection_type` - whether a game is created or joined.
+    ///
+    /// * `map_hash` - hash of the map of the game. DE Connector refuses to
+    ///   join the game if it is different from the hash of the game.
+    pub fn new(server_host: IpAddr, connection_type: ConnectionType, map_hash: MapHashNet) -> Self {
         Self {
             server_host,
             connection_type,
+            map_hash,
+            countdown: 0,
+            description: None,
         }
     }
 
+    /// Creates configuration for creation of a new game at a DE Connector.
+    ///
+    /// # Arguments
+    ///
+    /// * `main_server` - address of the main server of the DE Connector.
+    ///
+    /// * `max_players` - maximum number of players of the new game.
+    ///
+    /// * `map_hash` - hash of the map of the new game.
+    pub fn create_game(main_server: SocketAddr, max_players: Player, map_hash: MapHashNet) -> Self {
+        Self::new(
+            main_server.ip(),
+            ConnectionType::CreateGame {
+                port: main_server.port(),
+                max_players,
+            },
+            map_hash,
+        )
+    }
+
+    /// Creates configuration for joining of an already opened game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_server` - address of the game server of the DE Connector.
+    ///
+    /// * `map_hash` - hash of the map of the joined game.
+    pub fn join_game(game_server: SocketAddr, map_hash: MapHashNet) -> Self {
+        Self::new(
+            game_server.ip(),
+            ConnectionType::JoinGame(game_server.port()),
+            map_hash,
+        )
+    }
+
+    /// Sets description under which a newly created game is announced to
+    /// clients in the local network. It is ignored unless the connection type
+    /// is [`ConnectionType::CreateGame`].
+    pub fn with_description(mut self, description: GameDescription) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Sets number of seconds counted down before a newly created game
+    /// starts once all players are ready. There is no countdown by default.
+    /// It is ignored unless the connection type is
+    /// [`ConnectionType::CreateGame`].
+    pub fn with_countdown(mut self, countdown: u8) -> Self {
+        self.countdown = countdown;
+        self
+    }
+
     /// Address of DE Connector server.
     pub(crate) fn server_host(&self) -> IpAddr {
         self.server_host
@@ -23,6 +90,18 @@ impl NetGameConf {
     pub(crate) fn connection_type(&self) -> ConnectionType {
         self.connection_type
     }
+
+    pub(crate) fn map_hash(&self) -> MapHashNet {
+        self.map_hash
+    }
+
+    pub(crate) fn countdown(&self) -> u8 {
+        self.countdown
+    }
+
+    pub(crate) fn description(&self) -> Option<&GameDescription> {
+        self.description.as_ref()
+    }
 }
 
 /// Type of to be established connection to DE Connector.
@@ -25,6 +25,8 @@ impl Plugin for GamePlugin {
             .add_event::<PeerJoinedEvent>()
             .add_event::<PeerLeftEvent>()
             .add_event::<GameReadinessEvent>()
+            .add_event::<PeerReadinessEvent>()
+            .add_event::<CountdownEvent>()
             .add_event::<SetReadinessEvent>()
             .add_systems(OnEnter(NetState::Connected), open_or_join)
             .add_systems(
@@ -91,6 +93,37 @@ impl PeerLeftEvent {
 #[derive(Event, Deref)]
 pub struct GameReadinessEvent(Readiness);
 
+/// This event is sent when another player of the joined game changes their
+/// readiness before the game starts.
+#[derive(Event)]
+pub struct PeerReadinessEvent {
+    player: Player,
+    readiness: Readiness,
+}
+
+impl PeerReadinessEvent {
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    pub fn readiness(&self) -> Readiness {
+        self.readiness
+    }
+}
+
+/// This event is sent when the countdown to the start of the joined game
+/// progresses or is cancelled.
+#[derive(Event)]
+pub struct CountdownEvent(Option<u8>);
+
+impl CountdownEvent {
+    /// Number of seconds remaining until the game starts or None if the
+    /// countdown was cancelled.
+    pub fn remaining(&self) -> Option<u8> {
+        self.0
+    }
+}
+
 /// Send this event to change player readiness stage.
 #[derive(Event)]
 pub struct SetReadinessEvent(Readiness);
@@ -109,13 +142,21 @@ fn open_or_join(
     match conf.connection_type() {
         ConnectionType::CreateGame { max_players, .. } => {
             info!("Sending a open-game request.");
-            main_server.send(ToServer::OpenGame { max_players }.into());
+            main_server.send(
+                ToServer::OpenGame {
+                    max_players,
+                    map_hash: conf.map_hash(),
+                    countdown: conf.countdown(),
+                    description: conf.description().cloned(),
+                }
+                .into(),
+            );
         }
         ConnectionType::JoinGame(_) => {
             info!("Sending a join-game request.");
             game_server.send(ToGameServerEvent::new(
                 Reliability::SemiOrdered,
-                ToGame::Join,
+                ToGame::Join(conf.map_hash()),
             ));
         }
     }
@@ -168,6 +209,8 @@ fn process_from_game(
     mut peer_joined_events: EventWriter<PeerJoinedEvent>,
     mut peer_left_events: EventWriter<PeerLeftEvent>,
     mut readiness_events: EventWriter<GameReadinessEvent>,
+    mut peer_readiness_events: EventWriter<PeerReadinessEvent>,
+    mut countdown_events: EventWriter<CountdownEvent>,
     mut next_state: ResMut<NextState<NetState>>,
 ) {
     for event in inputs.read() {
@@ -204,6 +247,11 @@ fn process_from_game(
                         "Player already joined a different game.",
                     ));
                 }
+                JoinError::MapMismatch => {
+                    fatals.send(FatalErrorEvent::new(
+                        "The game is played on a different map, cannot join.",
+                    ));
+                }
             },
             FromGame::Left => {
                 if state.get() < &NetState::ShuttingDown {
@@ -222,6 +270,21 @@ fn process_from_game(
                 info!("Game readiness changed to: {readiness:?}");
                 readiness_events.send(GameReadinessEvent(*readiness));
             }
+            FromGame::PeerReadiness(player, readiness) => {
+                info!("Peer {player} readiness changed to: {readiness:?}");
+                peer_readiness_events.send(PeerReadinessEvent {
+                    player: *player,
+                    readiness: *readiness,
+                });
+            }
+            FromGame::Countdown(remaining) => {
+                info!("Game starts in {remaining} seconds.");
+                countdown_events.send(CountdownEvent(Some(*remaining)));
+            }
+            FromGame::CountdownCancelled => {
+                info!("Game start countdown cancelled.");
+                countdown_events.send(CountdownEvent(None));
+            }
         }
     }
 }
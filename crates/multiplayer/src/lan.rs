@@ -0,0 +1,217 @@
+//! Discovery of games opened at DE Connectors in the local network or at a
+//! DE Connector with a known address. Such games might be joined without DE
+//! Lobby.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use ahash::AHashSet;
+use async_std::{future::timeout, io};
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
+use de_core::log_full_error;
+use de_gui::ToastEvent;
+use de_messages::LanGame;
+use de_net::{DiscoveryClient, RecvError, SendError};
+use thiserror::Error;
+
+/// For how long are discovery responses collected after a query is sent.
+const DISCOVERY_DURATION: Duration = Duration::from_secs(1);
+
+pub(crate) struct LanPlugin;
+
+impl Plugin for LanPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DiscoverGamesEvent>()
+            .add_event::<GamesDiscoveredEvent>()
+            .add_systems(
+                Update,
+                (
+                    start_discovery
+                        .run_if(on_event::<DiscoverGamesEvent>())
+                        .run_if(not(resource_exists::<DiscoveryTask>)),
+                    poll_discovery.run_if(resource_exists::<DiscoveryTask>),
+                ),
+            );
+    }
+}
+
+/// Send this event to search for open games. [`GamesDiscoveredEvent`] is sent
+/// once the search finishes.
+#[derive(Event)]
+pub struct DiscoverGamesEvent {
+    host: Option<IpAddr>,
+}
+
+impl DiscoverGamesEvent {
+    /// Search for games in the local network.
+    pub fn lan() -> Self {
+        Self { host: None }
+    }
+
+    /// Search for games at a single DE Connector.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - address of the DE Connector.
+    pub fn host(host: IpAddr) -> Self {
+        Self { host: Some(host) }
+    }
+}
+
+/// This event is sent with all open games found during a search.
+#[derive(Event)]
+pub struct GamesDiscoveredEvent(Vec<DiscoveredGame>);
+
+impl GamesDiscoveredEvent {
+    pub fn games(&self) -> &[DiscoveredGame] {
+        self.0.as_slice()
+    }
+}
+
+/// A game announced by a DE Connector.
+#[derive(Clone)]
+pub struct DiscoveredGame {
+    host: IpAddr,
+    game: LanGame,
+}
+
+impl DiscoveredGame {
+    /// # Arguments
+    ///
+    /// * `host` - address of the DE Connector hosting the game.
+    ///
+    /// * `game` - the announced game.
+    pub fn new(host: IpAddr, game: LanGame) -> Self {
+        Self { host, game }
+    }
+
+    /// Address of the DE Connector hosting the game.
+    pub fn host(&self) -> IpAddr {
+        self.host
+    }
+
+    pub fn game(&self) -> &LanGame {
+        &self.game
+    }
+}
+
+#[derive(Resource)]
+struct DiscoveryTask(Task<Result<Vec<DiscoveredGame>, DiscoveryError>>);
+
+fn start_discovery(mut commands: Commands, mut events: EventReader<DiscoverGamesEvent>) {
+    let Some(host) = events.read().last().map(|event| event.host) else {
+        return;
+    };
+    let task = IoTaskPool::get().spawn(discover(host));
+    commands.insert_resource(DiscoveryTask(task));
+}
+
+fn poll_discovery(
+    mut commands: Commands,
+    mut task: ResMut<DiscoveryTask>,
+    mut events: EventWriter<GamesDiscoveredEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+        return;
+    };
+    commands.remove_resource::<DiscoveryTask>();
+
+    match result {
+        Ok(games) => {
+            events.send(GamesDiscoveredEvent(games));
+        }
+        Err(err) => {
+            log_full_error!(err);
+            toasts.send(ToastEvent::new("Search for games failed."));
+        }
+    }
+}
+
+/// Searches for open games.
+///
+/// # Arguments
+///
+/// * `host` - address of a DE Connector to query. The query is broadcasted to
+///   the local network if None.
+async fn discover(host: Option<IpAddr>) -> Result<Vec<DiscoveredGame>, DiscoveryError> {
+    let client = DiscoveryClient::bind()
+        .await
+        .map_err(|source| DiscoveryError::Bind { source })?;
+    match host {
+        Some(host) => client.query_host(host).await,
+        None => client.query().await,
+    }
+    .map_err(|source| DiscoveryError::Query { source })?;
+
+    let deadline = Instant::now() + DISCOVERY_DURATION;
+    let mut seen = AHashSet::new();
+    let mut games = Vec::new();
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let Ok(result) = timeout(remaining, client.recv()).await else {
+            break;
+        };
+        let (payload, source) = result.map_err(|source| DiscoveryError::Response { source })?;
+
+        let Some(game) = decode(payload.as_slice()) else {
+            warn!("Invalid game announcement received from {source:?}.");
+            continue;
+        };
+        if seen.insert(SocketAddr::new(source.ip(), game.port())) {
+            games.push(DiscoveredGame {
+                host: source.ip(),
+                game,
+            });
+        }
+    }
+
+    Ok(games)
+}
+
+fn decode(payload: &[u8]) -> Option<LanGame> {
+    let (game, _): (LanGame, usize) =
+        bincode::decode_from_slice(payload, bincode::config::standard()).ok()?;
+    game.description().validate().ok()?;
+    Some(game)
+}
+
+#[derive(Error, Debug)]
+enum DiscoveryError {
+    #[error("failed to open discovery socket")]
+    Bind { source: io::Error },
+    #[error("failed to send discovery query")]
+    Query { source: SendError },
+    #[error("failed to receive discovery response")]
+    Response { source: RecvError },
+}
+
+#[cfg(test)]
+mod tests {
+    use de_messages::GameDescription;
+    use de_types::player::Player;
+
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let description =
+            GameDescription::new("Game".to_owned(), "b".repeat(64), "Map".to_owned()).unwrap();
+        let game = LanGame::new(8090, 1, Player::Player3, description);
+        let payload = bincode::encode_to_vec(&game, bincode::config::standard()).unwrap();
+
+        let decoded = decode(payload.as_slice()).unwrap();
+        assert_eq!(decoded.port(), 8090);
+        assert_eq!(decoded.num_players(), 1);
+        assert_eq!(decoded.max_players(), Player::Player3);
+        assert_eq!(decoded.description().name(), "Game");
+
+        assert!(decode(&payload[..payload.len() - 1]).is_none());
+    }
+}
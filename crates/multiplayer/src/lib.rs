@@ -8,31 +8,38 @@
 //! down via [`ShutdownMultiplayerEvent`].
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
+use chat::ChatPlugin;
 use game::GamePlugin;
+use lan::LanPlugin;
 use lifecycle::LifecyclePlugin;
 use messages::MessagesPlugin;
 use playermsg::PlayerMsgPlugin;
 use stats::StatsPlugin;
 
 pub use crate::{
+    chat::{MuteUserEvent, MutedUsers, PlayerNames},
     config::{ConnectionType, NetGameConf},
     game::{
-        GameJoinedEvent, GameOpenedEvent, GameReadinessEvent, PeerJoinedEvent, PeerLeftEvent,
-        SetReadinessEvent,
+        CountdownEvent, GameJoinedEvent, GameOpenedEvent, GameReadinessEvent, PeerJoinedEvent,
+        PeerLeftEvent, PeerReadinessEvent, SetReadinessEvent,
     },
+    lan::{DiscoverGamesEvent, DiscoveredGame, GamesDiscoveredEvent},
     lifecycle::{MultiplayerShuttingDownEvent, ShutdownMultiplayerEvent, StartMultiplayerEvent},
     messages::{MessagesSet, ToPlayersEvent},
     netstate::NetState,
     playermsg::{
-        GameNetSet, NetEntities, NetEntityCommands, NetRecvDespawnActiveEvent, NetRecvHealthEvent,
-        NetRecvProjectileEvent, NetRecvSetPathEvent, NetRecvSpawnActiveEvent,
-        NetRecvTransformEvent,
+        GameNetSet, NetEntities, NetEntityCommands, NetRecvChatEvent, NetRecvCloakEvent,
+        NetRecvDespawnActiveEvent, NetRecvDisableEvent, NetRecvExtractEvent, NetRecvGarrisonEvent,
+        NetRecvHealthEvent, NetRecvProjectileEvent, NetRecvSetPathEvent, NetRecvSpawnActiveEvent,
+        NetRecvSurrenderEvent, NetRecvTransferOwnershipEvent, NetRecvTransformEvent,
     },
 };
 use crate::{netstate::NetStatePlugin, network::NetworkPlugin};
 
+mod chat;
 mod config;
 mod game;
+mod lan;
 mod lifecycle;
 mod messages;
 mod netstate;
@@ -52,5 +59,7 @@ impl PluginGroup for MultiplayerPluginGroup {
             .add(GamePlugin)
             .add(StatsPlugin)
             .add(PlayerMsgPlugin)
+            .add(ChatPlugin)
+            .add(LanPlugin)
     }
 }
@@ -1,17 +1,42 @@
-use std::{net::SocketAddr, time::Instant};
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
+use ahash::AHashMap;
 use bevy::prelude::*;
-use de_core::schedule::PreMovement;
-use de_messages::{FromGame, FromPlayers, FromServer, ToGame, ToPlayers, ToServer};
+use de_camera::CameraFocus;
+use de_core::{schedule::PreMovement, state::AppState};
+use de_messages::{
+    EntityNet, FromGame, FromPlayers, FromServer, NetProjectile, ToGame, ToPlayers, ToServer,
+};
 use de_net::{InPackage, PackageBuilder, Peers, Reliability};
+use de_types::{player::Player, projection::ToFlat};
 
 use crate::{
     config::ConnectionType,
+    game::PeerLeftEvent,
     lifecycle::{FatalErrorEvent, NetGameConfRes},
     netstate::NetState,
     network::{NetworkSet, PackageReceivedEvent, SendPackageEvent},
+    stats::ConnectionQuality,
 };
 
+/// Shortest period of transform updates of a single entity. It is slightly
+/// shorter than the transform sync period so that updates of entities near
+/// viewports of other players are never dropped on a good connection.
+const MIN_TRANSFORM_PERIOD: Duration = Duration::from_millis(750);
+/// Transform updates of entities up to this distance (in meters) from the
+/// closest viewport of another player are sent at the full rate.
+const NEAR_DISTANCE: f32 = 60.;
+/// Transform updates of entities at least this far (in meters) from all
+/// viewports of other players are sent at the lowest rate.
+const FAR_DISTANCE: f32 = 300.;
+/// Factor by which the rate of transform updates of far entities is reduced.
+const FAR_SLOWDOWN: f32 = 4.;
+/// Period of broadcasting of the local viewport to other players.
+const VIEWPORT_PERIOD: Duration = Duration::from_secs(1);
+
 pub(crate) struct MessagesPlugin;
 
 impl Plugin for MessagesPlugin {
@@ -24,22 +49,40 @@ impl Plugin for MessagesPlugin {
             .add_event::<FromPlayersEvent>()
             .add_systems(OnEnter(NetState::Connecting), setup)
             .add_systems(OnEnter(NetState::None), cleanup)
+            .add_systems(
+                OnExit(AppState::InGame),
+                clear_game.run_if(resource_exists::<Viewports>),
+            )
             .add_systems(
                 PostUpdate,
                 (
                     message_sender::<ToMainServerEvent>.run_if(on_event::<ToMainServerEvent>()),
                     message_sender::<ToGameServerEvent>.run_if(on_event::<ToGameServerEvent>()),
-                    message_sender::<ToPlayersEvent>.run_if(on_event::<ToPlayersEvent>()),
+                    players_message_sender.run_if(on_event::<ToPlayersEvent>()),
                 )
                     .in_set(MessagesSet::SendMessages)
                     .before(NetworkSet::SendPackages),
             )
+            .add_systems(
+                PostUpdate,
+                broadcast_viewport
+                    .run_if(in_state(NetState::Joined))
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_exists::<CameraFocus>)
+                    .before(MessagesSet::SendMessages),
+            )
             .add_systems(
                 PreMovement,
-                recv_messages
-                    .run_if(on_event::<PackageReceivedEvent>())
-                    .in_set(MessagesSet::RecvMessages)
-                    .after(NetworkSet::RecvPackages),
+                (
+                    recv_messages
+                        .run_if(on_event::<PackageReceivedEvent>())
+                        .in_set(MessagesSet::RecvMessages)
+                        .after(NetworkSet::RecvPackages),
+                    recv_viewports
+                        .run_if(on_event::<FromPlayersEvent>())
+                        .after(MessagesSet::RecvMessages),
+                    remove_viewports.run_if(on_event::<PeerLeftEvent>()),
+                ),
             );
     }
 }
@@ -144,7 +187,18 @@ impl ToMessage for ToPlayersEvent {
             ToPlayers::SetPath { .. } => Reliability::SemiOrdered,
             ToPlayers::Transform { .. } => Reliability::Unreliable,
             ToPlayers::ChangeHealth { .. } => Reliability::SemiOrdered,
+            ToPlayers::Disable { .. } => Reliability::SemiOrdered,
+            ToPlayers::ChangeInactiveHealth { .. } => Reliability::SemiOrdered,
+            // Shell interceptions decide whether a shell hits, thus they
+            // must not be lost.
+            ToPlayers::Projectile(NetProjectile::Intercepted { .. }) => Reliability::SemiOrdered,
             ToPlayers::Projectile(_) => Reliability::Unreliable,
+            ToPlayers::Surrender => Reliability::SemiOrdered,
+            ToPlayers::TransferOwnership { .. } => Reliability::SemiOrdered,
+            ToPlayers::Cloak { .. } => Reliability::SemiOrdered,
+            ToPlayers::Garrison { .. } => Reliability::SemiOrdered,
+            ToPlayers::Viewport(_) => Reliability::Unreliable,
+            ToPlayers::ExtractDeposit { .. } => Reliability::SemiOrdered,
         }
     }
 
@@ -292,13 +346,98 @@ enum PortType {
     Game,
 }
 
+/// Flat positions of the latest known viewports of other players.
+#[derive(Resource, Default)]
+struct Viewports(AHashMap<Player, Vec2>);
+
+impl Viewports {
+    fn update(&mut self, player: Player, point: Vec2) {
+        self.0.insert(player, point);
+    }
+
+    fn remove(&mut self, player: Player) {
+        self.0.remove(&player);
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns distance of a point to the closest known viewport or None if
+    /// no viewport is known.
+    fn distance(&self, point: Vec2) -> Option<f32> {
+        self.0
+            .values()
+            .map(|viewport| viewport.distance(point))
+            .reduce(f32::min)
+    }
+}
+
+/// Times (since app startup) of the last sent transform update of each local
+/// entity.
+#[derive(Resource, Default)]
+struct TransformRates(AHashMap<EntityNet, Duration>);
+
+impl TransformRates {
+    /// Returns true and restarts the period if a transform update of the
+    /// entity is due.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - the updated entity.
+    ///
+    /// * `time` - current time.
+    ///
+    /// * `period` - minimum time between two updates of the entity.
+    fn try_admit(&mut self, entity: EntityNet, time: Duration, period: Duration) -> bool {
+        if let Some(&last) = self.0.get(&entity) {
+            if time < last + period {
+                return false;
+            }
+        }
+
+        self.0.insert(entity, time);
+        true
+    }
+
+    fn forget(&mut self, entity: EntityNet) {
+        self.0.remove(&entity);
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Returns factor by which the rate of transform updates of an entity is
+/// reduced based on its distance to the closest viewport of another player.
+fn distance_slowdown(distance: Option<f32>) -> f32 {
+    // Nothing is known about other players yet.
+    let Some(distance) = distance else {
+        return 1.;
+    };
+
+    let progress = ((distance - NEAR_DISTANCE) / (FAR_DISTANCE - NEAR_DISTANCE)).clamp(0., 1.);
+    1. + progress * (FAR_SLOWDOWN - 1.)
+}
+
 fn setup(mut commands: Commands, conf: Res<NetGameConfRes>) {
     let ports: Ports = conf.connection_type().into();
     commands.insert_resource(ports);
+    commands.init_resource::<Viewports>();
+    commands.init_resource::<TransformRates>();
 }
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<Ports>();
+    commands.remove_resource::<Viewports>();
+    commands.remove_resource::<TransformRates>();
+}
+
+/// Forgets state bound to entities and players of the exited game.
+fn clear_game(mut viewports: ResMut<Viewports>, mut rates: ResMut<TransformRates>) {
+    viewports.clear();
+    rates.clear();
 }
 
 fn message_sender<E>(
@@ -308,6 +447,51 @@ fn message_sender<E>(
     mut outputs: EventWriter<SendPackageEvent>,
 ) where
     E: ToMessage,
+{
+    send_messages(&conf, &ports, inputs.read(), &mut outputs);
+}
+
+/// Sends messages to other players. Transform updates are throttled based on
+/// distance of the transformed entities to viewports of other players and on
+/// connection quality. This considerably reduces bandwidth in late games with
+/// many units.
+#[allow(clippy::too_many_arguments)]
+fn players_message_sender(
+    time: Res<Time>,
+    conf: Res<NetGameConfRes>,
+    ports: Res<Ports>,
+    viewports: Res<Viewports>,
+    quality: Option<Res<ConnectionQuality>>,
+    mut rates: ResMut<TransformRates>,
+    mut inputs: EventReader<ToPlayersEvent>,
+    mut outputs: EventWriter<SendPackageEvent>,
+) {
+    let time = time.elapsed();
+    let quality_slowdown = quality.map_or(1., |quality| quality.slowdown());
+
+    let events = inputs.read().filter(|event| match event.message() {
+        ToPlayers::Transform { entity, transform } => {
+            let position = Transform::from(transform).translation.to_flat();
+            let slowdown = distance_slowdown(viewports.distance(position)) * quality_slowdown;
+            rates.try_admit(*entity, time, MIN_TRANSFORM_PERIOD.mul_f32(slowdown))
+        }
+        ToPlayers::Despawn { entity } => {
+            rates.forget(*entity);
+            true
+        }
+        _ => true,
+    });
+    send_messages(&conf, &ports, events, &mut outputs);
+}
+
+fn send_messages<'a, E, I>(
+    conf: &NetGameConfRes,
+    ports: &Ports,
+    events: I,
+    outputs: &mut EventWriter<SendPackageEvent>,
+) where
+    E: ToMessage + 'a,
+    I: Iterator<Item = &'a E>,
 {
     let time = Instant::now();
 
@@ -321,7 +505,7 @@ fn message_sender<E>(
     let mut unordered = PackageBuilder::new(Reliability::Unordered, E::PEERS, addr);
     let mut semi_ordered = PackageBuilder::new(Reliability::SemiOrdered, E::PEERS, addr);
 
-    for event in inputs.read() {
+    for event in events {
         let builder = match event.reliability() {
             Reliability::Unreliable => &mut unreliable,
             Reliability::Unordered => &mut unordered,
@@ -339,6 +523,23 @@ fn message_sender<E>(
     }
 }
 
+fn broadcast_viewport(
+    time: Res<Time>,
+    mut next: Local<Duration>,
+    focus: Res<CameraFocus>,
+    mut events: EventWriter<ToPlayersEvent>,
+) {
+    let time = time.elapsed();
+    if time < *next {
+        return;
+    }
+
+    *next = time + VIEWPORT_PERIOD;
+    events.send(ToPlayersEvent::new(ToPlayers::Viewport(
+        focus.point().to_flat().into(),
+    )));
+}
+
 fn recv_messages(
     ports: Res<Ports>,
     mut packages: EventReader<PackageReceivedEvent>,
@@ -364,6 +565,24 @@ fn recv_messages(
     }
 }
 
+fn recv_viewports(mut viewports: ResMut<Viewports>, mut events: EventReader<FromPlayersEvent>) {
+    for event in events.read() {
+        if let ToPlayers::Viewport(point) = event.message() {
+            let point = Vec2::from(*point);
+            if point.is_finite() {
+                viewports.update(event.source(), point);
+            }
+        }
+    }
+}
+
+/// Forgets viewports of players who left the game.
+fn remove_viewports(mut viewports: ResMut<Viewports>, mut events: EventReader<PeerLeftEvent>) {
+    for event in events.read() {
+        viewports.remove(event.id());
+    }
+}
+
 fn decode_and_send<P, E>(
     package: &InPackage,
     events: &mut EventWriter<E>,
@@ -390,7 +609,7 @@ fn decode_and_send<P, E>(
 
 #[cfg(test)]
 mod tests {
-    use de_types::player::Player;
+    use de_messages::NetEntityIndex;
 
     use super::*;
 
@@ -412,4 +631,44 @@ mod tests {
         ports.init_game_port(4).unwrap();
         assert!(ports.init_game_port(5).is_err());
     }
+
+    #[test]
+    fn test_viewports() {
+        let mut viewports = Viewports::default();
+        assert_eq!(viewports.distance(Vec2::ZERO), None);
+
+        viewports.update(Player::Player2, Vec2::new(3., 4.));
+        viewports.update(Player::Player3, Vec2::new(100., 0.));
+        assert_eq!(viewports.distance(Vec2::ZERO), Some(5.));
+        viewports.update(Player::Player2, Vec2::new(300., 0.));
+        assert_eq!(viewports.distance(Vec2::ZERO), Some(100.));
+        viewports.remove(Player::Player3);
+        assert_eq!(viewports.distance(Vec2::ZERO), Some(300.));
+    }
+
+    #[test]
+    fn test_transform_rates() {
+        let mut rates = TransformRates::default();
+        let entity = EntityNet::new(Player::Player1, NetEntityIndex::from(Entity::from_raw(7)));
+        let period = Duration::from_secs(1);
+
+        assert!(rates.try_admit(entity, Duration::from_secs(10), period));
+        assert!(!rates.try_admit(entity, Duration::from_millis(10500), period));
+        assert!(rates.try_admit(entity, Duration::from_secs(11), period));
+
+        rates.forget(entity);
+        assert!(rates.try_admit(entity, Duration::from_millis(11100), period));
+    }
+
+    #[test]
+    fn test_distance_slowdown() {
+        assert_eq!(distance_slowdown(None), 1.);
+        assert_eq!(distance_slowdown(Some(10.)), 1.);
+        assert_eq!(distance_slowdown(Some(NEAR_DISTANCE)), 1.);
+        assert_eq!(distance_slowdown(Some(FAR_DISTANCE)), FAR_SLOWDOWN);
+        assert_eq!(distance_slowdown(Some(1000.)), FAR_SLOWDOWN);
+
+        let middle = distance_slowdown(Some(0.5 * (NEAR_DISTANCE + FAR_DISTANCE)));
+        assert_eq!(middle, 0.5 * (1. + FAR_SLOWDOWN));
+    }
 }
@@ -1,14 +1,33 @@
+use std::time::Duration;
+
 use ahash::AHashMap;
 use bevy::{
     ecs::{entity::Entities, system::SystemParam},
     prelude::*,
 };
-use de_core::{gconfig::GameConfig, schedule::PreMovement, state::AppState};
-use de_messages::{EntityNet, NetEntityIndex, NetProjectile, ToPlayers};
-use de_types::{objects::ActiveObjectType, path::Path, player::Player};
+use de_core::{
+    gconfig::GameConfig, objects::MovableSolid, player::PlayerComponent, schedule::PreMovement,
+    state::AppState,
+};
+use de_messages::{EntityNet, InactiveNet, NetEntityIndex, NetProjectile, ToPlayers};
+use de_types::{
+    objects::{ActiveObjectType, PLAYER_MAX_UNITS},
+    path::Path,
+    player::Player,
+};
+use thiserror::Error;
 
 use crate::messages::{FromPlayersEvent, MessagesSet};
 
+/// Sustained rate (per second) of object commands accepted from a single
+/// player. Commands over the limit are dropped.
+const COMMAND_RATE: f32 = 2. * PLAYER_MAX_UNITS as f32;
+/// Maximum number of object commands accepted from a single player in a
+/// burst.
+const COMMAND_BURST: f32 = 4. * PLAYER_MAX_UNITS as f32;
+/// Maximum duration of disabling of an object requested by a player.
+const MAX_DISABLE_DURATION: Duration = Duration::from_secs(60);
+
 /// This plugin handles incoming player messages during a multiplayer game.
 pub(crate) struct PlayerMsgPlugin;
 
@@ -17,9 +36,16 @@ impl Plugin for PlayerMsgPlugin {
         app.add_event::<NetRecvSpawnActiveEvent>()
             .add_event::<NetRecvDespawnActiveEvent>()
             .add_event::<NetRecvHealthEvent>()
+            .add_event::<NetRecvDisableEvent>()
             .add_event::<NetRecvTransformEvent>()
             .add_event::<NetRecvSetPathEvent>()
             .add_event::<NetRecvProjectileEvent>()
+            .add_event::<NetRecvSurrenderEvent>()
+            .add_event::<NetRecvTransferOwnershipEvent>()
+            .add_event::<NetRecvCloakEvent>()
+            .add_event::<NetRecvGarrisonEvent>()
+            .add_event::<NetRecvExtractEvent>()
+            .add_event::<NetRecvChatEvent>()
             .add_systems(OnEnter(AppState::InGame), setup)
             .add_systems(OnExit(AppState::InGame), cleanup)
             .add_systems(
@@ -127,6 +153,29 @@ impl NetRecvHealthEvent {
     }
 }
 
+/// This event is sent when an active entity is to be temporarily disabled.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvDisableEvent {
+    entity: Entity,
+    duration: Duration,
+}
+
+impl NetRecvDisableEvent {
+    fn new(entity: Entity, duration: Duration) -> Self {
+        Self { entity, duration }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
 #[derive(Event)]
 pub struct NetRecvTransformEvent {
     entity: Entity,
@@ -170,10 +219,145 @@ impl NetRecvSetPathEvent {
 #[derive(Event, Deref)]
 pub struct NetRecvProjectileEvent(NetProjectile);
 
+/// This event is sent when a non-local player surrenders the game.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvSurrenderEvent(Player);
+
+impl NetRecvSurrenderEvent {
+    /// The surrendering player.
+    pub fn player(&self) -> Player {
+        self.0
+    }
+}
+
+/// This event is sent when a chat message from a non-local player is
+/// received.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvChatEvent {
+    player: Player,
+    text: String,
+}
+
+impl NetRecvChatEvent {
+    /// The player who sent the message.
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+}
+
+/// This event is sent when ownership of a non-locally simulated active
+/// entity is transferred to another player.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvTransferOwnershipEvent {
+    entity: Entity,
+    player: Player,
+}
+
+impl NetRecvTransferOwnershipEvent {
+    fn new(entity: Entity, player: Player) -> Self {
+        Self { entity, player }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// The new owner of the entity.
+    pub fn player(&self) -> Player {
+        self.player
+    }
+}
+
+/// This event is sent when a non-locally simulated active entity is cloaked
+/// or uncloaked.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvCloakEvent {
+    entity: Entity,
+    cloaked: bool,
+}
+
+impl NetRecvCloakEvent {
+    fn new(entity: Entity, cloaked: bool) -> Self {
+        Self { entity, cloaked }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// True if the entity got cloaked, false if it got uncloaked.
+    pub fn cloaked(&self) -> bool {
+        self.cloaked
+    }
+}
+
+/// This event is sent when a non-locally simulated active entity is
+/// garrisoned in a carrier or ungarrisoned.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvGarrisonEvent {
+    entity: Entity,
+    carrier: Option<Entity>,
+}
+
+impl NetRecvGarrisonEvent {
+    fn new(entity: Entity, carrier: Option<Entity>) -> Self {
+        Self { entity, carrier }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// The carrier of the entity or None if the entity got ungarrisoned.
+    pub fn carrier(&self) -> Option<Entity> {
+        self.carrier
+    }
+}
+
+/// This event is sent when resources are extracted from a deposit by
+/// harvesters simulated by another game instance.
+///
+/// This event is send during [`GameNetSet::Messages`] set.
+#[derive(Event)]
+pub struct NetRecvExtractEvent {
+    deposit: Entity,
+    amount: f32,
+}
+
+impl NetRecvExtractEvent {
+    fn new(deposit: Entity, amount: f32) -> Self {
+        Self { deposit, amount }
+    }
+
+    pub fn deposit(&self) -> Entity {
+        self.deposit
+    }
+
+    /// Extracted amount of resources. It is a non-negative finite number.
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+}
+
 #[derive(SystemParam)]
 pub struct NetEntities<'w> {
     config: Res<'w, GameConfig>,
     map: Res<'w, EntityIdMapRes>,
+    inactive: Res<'w, InactiveIdMapRes>,
 }
 
 impl<'w> NetEntities<'w> {
@@ -196,15 +380,34 @@ impl<'w> NetEntities<'w> {
         let player = self.config.locals().playable();
         EntityNet::new(player, entity.into())
     }
+
+    /// Translates a local entity ID to an inactive object ID. None is
+    /// returned if the entity is not a registered inactive object.
+    pub fn inactive_net_id(&self, entity: Entity) -> Option<InactiveNet> {
+        self.inactive.local_to_net.get(&entity).copied()
+    }
 }
 
 #[derive(SystemParam)]
 pub struct NetEntityCommands<'w> {
     entities: &'w Entities,
     map: ResMut<'w, EntityIdMapRes>,
+    inactive: ResMut<'w, InactiveIdMapRes>,
 }
 
 impl<'w> NetEntityCommands<'w> {
+    /// Registers a newly spawned inactive object. All inactive objects must be
+    /// registered in the order of their spawning.
+    pub fn register_inactive(&mut self, entity: Entity) {
+        self.inactive.register(entity)
+    }
+
+    /// De-registers a despawned inactive object. It does nothing if the entity
+    /// is not registered.
+    pub fn deregister_inactive(&mut self, entity: Entity) {
+        self.inactive.deregister(entity)
+    }
+
     pub fn remove_player(&mut self, player: Player) -> Option<PlayerNetToLocal> {
         self.map.remove_player(player)
     }
@@ -227,6 +430,280 @@ impl<'w> NetEntityCommands<'w> {
     }
 }
 
+/// Remaining number of object commands each player is allowed to send (see
+/// [`COMMAND_RATE`] and [`COMMAND_BURST`]).
+#[derive(Resource, Default)]
+struct CommandBudgets(AHashMap<Player, CommandBudget>);
+
+impl CommandBudgets {
+    /// Returns true and consumes a single command from the budget of the
+    /// player if the budget is not exhausted.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the commanding player.
+    ///
+    /// * `time` - elapsed time since a fixed point in time in the past.
+    fn try_spend(&mut self, player: Player, time: Duration) -> bool {
+        let budget = self.0.entry(player).or_insert(CommandBudget {
+            commands: COMMAND_BURST,
+            time,
+        });
+
+        let elapsed = time.saturating_sub(budget.time).as_secs_f32();
+        budget.commands = (budget.commands + elapsed * COMMAND_RATE).min(COMMAND_BURST);
+        budget.time = time;
+
+        if budget.commands < 1. {
+            return false;
+        }
+        budget.commands -= 1.;
+        true
+    }
+}
+
+struct CommandBudget {
+    commands: f32,
+    /// Time of the last budget update.
+    time: Duration,
+}
+
+#[derive(Debug, Error)]
+enum InvalidMessage {
+    #[error("{player} does not simulate entity {entity:?}")]
+    NotSimulating { player: Player, entity: EntityNet },
+    #[error("{player} is not allowed to command entity {entity:?}")]
+    NotControlling { player: Player, entity: EntityNet },
+    #[error("entity {entity:?} cannot {action}")]
+    Incapable {
+        entity: EntityNet,
+        action: &'static str,
+    },
+    #[error("{player} is not allowed to use carrier {carrier:?}")]
+    ForeignCarrier { player: Player, carrier: EntityNet },
+    #[error("invalid transform")]
+    Transform,
+    #[error("invalid path")]
+    Path,
+    #[error("invalid health delta: {0}")]
+    HealthDelta(f32),
+    #[error("disable duration {0:?} is too long")]
+    DisableDuration(Duration),
+    #[error("invalid projectile")]
+    Projectile,
+    #[error("invalid extracted resource amount: {0}")]
+    ResourceAmount(f32),
+    #[error("too many commands")]
+    RateLimit,
+}
+
+/// Validates incoming player messages against ownership of the affected
+/// objects, their capabilities and command rate limits. This hardens the game
+/// against modified clients.
+#[derive(SystemParam)]
+struct Validator<'w, 's> {
+    time: Res<'w, Time>,
+    config: Res<'w, GameConfig>,
+    budgets: ResMut<'w, CommandBudgets>,
+    objects: Query<'w, 's, (&'static PlayerComponent, Has<MovableSolid>)>,
+}
+
+impl<'w, 's> Validator<'w, 's> {
+    /// Returns an error if the message is obviously invalid and must be
+    /// dropped.
+    ///
+    /// Messages referencing unknown entities are not rejected by the
+    /// validation. Such messages are often a result of message reordering or
+    /// of a recent despawn.
+    fn validate(
+        &mut self,
+        net_commands: &NetEntityCommands,
+        source: Player,
+        message: &ToPlayers,
+    ) -> Result<(), InvalidMessage> {
+        match message {
+            ToPlayers::Spawn {
+                entity, transform, ..
+            } => {
+                Self::simulating(source, *entity)?;
+                Self::transform(&transform.into())
+            }
+            ToPlayers::Despawn { entity } => Self::simulating(source, *entity),
+            ToPlayers::SetPath { entity, waypoints } => {
+                self.spend(source)?;
+
+                if let Some(path) = waypoints {
+                    let path = Path::from(path);
+                    if path.waypoints().is_empty()
+                        || !path.length().is_finite()
+                        || path.waypoints().iter().any(|point| !point.is_finite())
+                    {
+                        return Err(InvalidMessage::Path);
+                    }
+                }
+
+                let Some((owner, movable)) = self.object(net_commands, *entity) else {
+                    return Ok(());
+                };
+                // The simulating game instance commands all its objects
+                // (including AI players), others only based on alliances.
+                if source != entity.player() && !self.config.controls(source, owner) {
+                    return Err(InvalidMessage::NotControlling {
+                        player: source,
+                        entity: *entity,
+                    });
+                }
+                if !movable {
+                    return Err(InvalidMessage::Incapable {
+                        entity: *entity,
+                        action: "move",
+                    });
+                }
+                Ok(())
+            }
+            ToPlayers::Transform { entity, transform } => {
+                Self::simulating(source, *entity)?;
+                Self::transform(&transform.into())
+            }
+            ToPlayers::ChangeHealth { entity, delta } => {
+                let delta = f32::from(delta);
+                // Only the simulating game instance may increase health of its
+                // objects, others may only deal damage.
+                if !delta.is_finite() || (delta > 0. && source != entity.player()) {
+                    return Err(InvalidMessage::HealthDelta(delta));
+                }
+                Ok(())
+            }
+            ToPlayers::ChangeInactiveHealth { delta, .. } => {
+                let delta = f32::from(delta);
+                if !delta.is_finite() || delta > 0. {
+                    return Err(InvalidMessage::HealthDelta(delta));
+                }
+                Ok(())
+            }
+            ToPlayers::ExtractDeposit { amount, .. } => {
+                let amount = f32::from(amount);
+                if !amount.is_finite() || amount < 0. {
+                    return Err(InvalidMessage::ResourceAmount(amount));
+                }
+                Ok(())
+            }
+            ToPlayers::Disable { duration, .. } => {
+                if *duration > MAX_DISABLE_DURATION {
+                    return Err(InvalidMessage::DisableDuration(*duration));
+                }
+                Ok(())
+            }
+            ToPlayers::Projectile(projectile) => {
+                let (a, b) = match *projectile {
+                    NetProjectile::Laser { origin, direction }
+                    | NetProjectile::RepairBeam { origin, direction } => (origin, direction),
+                    NetProjectile::Shell {
+                        origin, velocity, ..
+                    }
+                    | NetProjectile::Rocket {
+                        origin, velocity, ..
+                    } => (origin, velocity),
+                    NetProjectile::Intercepted { origin, velocity } => (origin, velocity),
+                };
+                if !Vec3::from(a).is_finite() || !Vec3::from(b).is_finite() {
+                    return Err(InvalidMessage::Projectile);
+                }
+                Ok(())
+            }
+            ToPlayers::TransferOwnership { entity, .. } => {
+                // Only the simulating game instance may transfer its objects.
+                Self::simulating(source, *entity)?;
+                self.spend(source)
+            }
+            ToPlayers::Cloak { entity, .. } => {
+                // Only the simulating game instance may cloak its objects.
+                Self::simulating(source, *entity)?;
+                self.spend(source)?;
+
+                match self.object(net_commands, *entity) {
+                    // Only units are equipped with cloaking devices.
+                    Some((_, false)) => Err(InvalidMessage::Incapable {
+                        entity: *entity,
+                        action: "cloak",
+                    }),
+                    _ => Ok(()),
+                }
+            }
+            ToPlayers::Garrison { entity, carrier } => {
+                // Only the simulating game instance may garrison its objects.
+                Self::simulating(source, *entity)?;
+                self.spend(source)?;
+
+                let Some((owner, movable)) = self.object(net_commands, *entity) else {
+                    return Ok(());
+                };
+                if !movable {
+                    return Err(InvalidMessage::Incapable {
+                        entity: *entity,
+                        action: "garrison",
+                    });
+                }
+
+                let Some(carrier) = carrier else {
+                    return Ok(());
+                };
+                match self.object(net_commands, *carrier) {
+                    Some((carrier_owner, _)) if !self.config.controls(owner, carrier_owner) => {
+                        Err(InvalidMessage::ForeignCarrier {
+                            player: source,
+                            carrier: *carrier,
+                        })
+                    }
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn simulating(source: Player, entity: EntityNet) -> Result<(), InvalidMessage> {
+        if source == entity.player() {
+            Ok(())
+        } else {
+            Err(InvalidMessage::NotSimulating {
+                player: source,
+                entity,
+            })
+        }
+    }
+
+    fn transform(transform: &Transform) -> Result<(), InvalidMessage> {
+        if transform.translation.is_finite() && transform.rotation.is_normalized() {
+            Ok(())
+        } else {
+            Err(InvalidMessage::Transform)
+        }
+    }
+
+    fn spend(&mut self, source: Player) -> Result<(), InvalidMessage> {
+        if self.budgets.try_spend(source, self.time.elapsed()) {
+            Ok(())
+        } else {
+            Err(InvalidMessage::RateLimit)
+        }
+    }
+
+    /// Returns owner of an active object and whether it is movable. None is
+    /// returned if the entity is unknown or not (yet) fully spawned.
+    fn object(
+        &self,
+        net_commands: &NetEntityCommands,
+        entity: EntityNet,
+    ) -> Option<(Player, bool)> {
+        let local = net_commands.local_id(entity)?;
+        self.objects
+            .get(local)
+            .ok()
+            .map(|(&owner, movable)| (*owner, movable))
+    }
+}
+
 /// Mapping between remote and local entity IDs for non-locally simulated
 /// entities.
 #[derive(Resource)]
@@ -307,6 +784,35 @@ impl EntityIdMapRes {
     }
 }
 
+/// Mapping between inactive object IDs and local entities.
+#[derive(Resource, Default)]
+struct InactiveIdMapRes {
+    /// ID of the next registered inactive object.
+    next: u32,
+    net_to_local: AHashMap<InactiveNet, Entity>,
+    local_to_net: AHashMap<Entity, InactiveNet>,
+}
+
+impl InactiveIdMapRes {
+    fn register(&mut self, local: Entity) {
+        let net = InactiveNet::new(self.next);
+        self.next += 1;
+        self.net_to_local.insert(net, local);
+        let result = self.local_to_net.insert(local, net);
+        debug_assert!(result.is_none());
+    }
+
+    fn deregister(&mut self, local: Entity) {
+        if let Some(net) = self.local_to_net.remove(&local) {
+            self.net_to_local.remove(&net);
+        }
+    }
+
+    fn translate_net(&self, net: InactiveNet) -> Option<Entity> {
+        self.net_to_local.get(&net).copied()
+    }
+}
+
 /// Mapping from remote entity indices to local ECS entities.
 #[derive(Default)]
 pub struct PlayerNetToLocal(AHashMap<NetEntityIndex, Entity>);
@@ -341,25 +847,47 @@ impl PlayerNetToLocal {
 
 fn setup(mut commands: Commands) {
     commands.insert_resource(EntityIdMapRes::new());
+    commands.init_resource::<InactiveIdMapRes>();
+    commands.init_resource::<CommandBudgets>();
 }
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<EntityIdMapRes>();
+    commands.remove_resource::<InactiveIdMapRes>();
+    commands.remove_resource::<CommandBudgets>();
+}
+
+/// Writers of events generated from received player messages.
+#[derive(SystemParam)]
+struct RecvEvents<'w> {
+    spawn: EventWriter<'w, NetRecvSpawnActiveEvent>,
+    despawn: EventWriter<'w, NetRecvDespawnActiveEvent>,
+    path: EventWriter<'w, NetRecvSetPathEvent>,
+    transform: EventWriter<'w, NetRecvTransformEvent>,
+    health: EventWriter<'w, NetRecvHealthEvent>,
+    disable: EventWriter<'w, NetRecvDisableEvent>,
+    projectile: EventWriter<'w, NetRecvProjectileEvent>,
+    surrender: EventWriter<'w, NetRecvSurrenderEvent>,
+    transfer: EventWriter<'w, NetRecvTransferOwnershipEvent>,
+    cloak: EventWriter<'w, NetRecvCloakEvent>,
+    garrison: EventWriter<'w, NetRecvGarrisonEvent>,
+    extract: EventWriter<'w, NetRecvExtractEvent>,
+    chat: EventWriter<'w, NetRecvChatEvent>,
 }
 
-#[allow(clippy::too_many_arguments)]
 fn recv_messages(
     mut commands: Commands,
+    mut validator: Validator,
     mut net_commands: NetEntityCommands,
     mut inputs: EventReader<FromPlayersEvent>,
-    mut spawn_events: EventWriter<NetRecvSpawnActiveEvent>,
-    mut despawn_events: EventWriter<NetRecvDespawnActiveEvent>,
-    mut path_events: EventWriter<NetRecvSetPathEvent>,
-    mut transform_events: EventWriter<NetRecvTransformEvent>,
-    mut health_events: EventWriter<NetRecvHealthEvent>,
-    mut projectile_events: EventWriter<NetRecvProjectileEvent>,
+    mut events: RecvEvents,
 ) {
     for input in inputs.read() {
+        if let Err(error) = validator.validate(&net_commands, input.source(), input.message()) {
+            warn!("Dropping invalid message from {}: {error}", input.source());
+            continue;
+        }
+
         match input.message() {
             ToPlayers::Spawn {
                 entity,
@@ -370,7 +898,7 @@ fn recv_messages(
                 let local = commands.spawn_empty().id();
                 net_commands.register(*entity, local);
 
-                spawn_events.send(NetRecvSpawnActiveEvent::new(
+                events.spawn.send(NetRecvSpawnActiveEvent::new(
                     *player,
                     local,
                     *object_type,
@@ -379,23 +907,27 @@ fn recv_messages(
             }
             ToPlayers::Despawn { entity } => {
                 if let Some(local) = net_commands.deregister(*entity) {
-                    despawn_events.send(NetRecvDespawnActiveEvent::new(local));
+                    events.despawn.send(NetRecvDespawnActiveEvent::new(local));
                 }
             }
             ToPlayers::SetPath { entity, waypoints } => {
-                let Some(local) = net_commands.remote_local_id(*entity) else {
+                // Objects simulated by this game instance might be commanded
+                // by allies with shared control.
+                let Some(local) = net_commands.local_id(*entity) else {
                     warn!("Received net path update of unrecognized entity: {entity:?}");
                     continue;
                 };
 
-                path_events.send(NetRecvSetPathEvent::new(
+                events.path.send(NetRecvSetPathEvent::new(
                     local,
                     waypoints.as_ref().map(|p| p.into()),
                 ));
             }
             ToPlayers::Transform { entity, transform } => {
                 if let Some(local) = net_commands.remote_local_id(*entity) {
-                    transform_events.send(NetRecvTransformEvent::new(local, transform.into()));
+                    events
+                        .transform
+                        .send(NetRecvTransformEvent::new(local, transform.into()));
                 }
             }
             ToPlayers::ChangeHealth { entity, delta } => {
@@ -404,12 +936,129 @@ fn recv_messages(
                     continue;
                 };
 
-                health_events.send(NetRecvHealthEvent::new(local, delta.into()));
+                events
+                    .health
+                    .send(NetRecvHealthEvent::new(local, delta.into()));
+            }
+            ToPlayers::Disable { entity, duration } => {
+                let Some(local) = net_commands.local_id(*entity) else {
+                    warn!("Received net disable of unrecognized entity: {entity:?}");
+                    continue;
+                };
+
+                events
+                    .disable
+                    .send(NetRecvDisableEvent::new(local, *duration));
+            }
+            ToPlayers::ChangeInactiveHealth { object, delta } => {
+                // The object might have been already destroyed locally.
+                if let Some(local) = net_commands.inactive.translate_net(*object) {
+                    events
+                        .health
+                        .send(NetRecvHealthEvent::new(local, delta.into()));
+                }
             }
             ToPlayers::Projectile(projectile) => {
-                projectile_events.send(NetRecvProjectileEvent(*projectile));
+                events.projectile.send(NetRecvProjectileEvent(*projectile));
+            }
+            ToPlayers::Surrender => {
+                events.surrender.send(NetRecvSurrenderEvent(input.source()));
+            }
+            ToPlayers::TransferOwnership { entity, player } => {
+                let Some(local) = net_commands.remote_local_id(*entity) else {
+                    warn!("Received ownership transfer of unrecognized entity: {entity:?}");
+                    continue;
+                };
+
+                events
+                    .transfer
+                    .send(NetRecvTransferOwnershipEvent::new(local, *player));
+            }
+            ToPlayers::Cloak { entity, cloaked } => {
+                let Some(local) = net_commands.remote_local_id(*entity) else {
+                    warn!("Received cloak of unrecognized entity: {entity:?}");
+                    continue;
+                };
+
+                events.cloak.send(NetRecvCloakEvent::new(local, *cloaked));
+            }
+            ToPlayers::Garrison { entity, carrier } => {
+                let Some(local) = net_commands.remote_local_id(*entity) else {
+                    warn!("Received garrison of unrecognized entity: {entity:?}");
+                    continue;
+                };
+
+                let carrier = match carrier {
+                    Some(carrier) => match net_commands.local_id(*carrier) {
+                        Some(carrier) => Some(carrier),
+                        None => {
+                            warn!("Received garrison in unrecognized carrier: {carrier:?}");
+                            continue;
+                        }
+                    },
+                    None => None,
+                };
+
+                events
+                    .garrison
+                    .send(NetRecvGarrisonEvent::new(local, carrier));
+            }
+            ToPlayers::ExtractDeposit { object, amount } => {
+                // The deposit might have been already destroyed locally.
+                if let Some(local) = net_commands.inactive.translate_net(*object) {
+                    events
+                        .extract
+                        .send(NetRecvExtractEvent::new(local, amount.into()));
+                }
+            }
+            ToPlayers::Chat(message) => {
+                events.chat.send(NetRecvChatEvent {
+                    player: input.source(),
+                    text: message.text().to_owned(),
+                });
             }
             _ => (),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inactive_id_map() {
+        let mut map = InactiveIdMapRes::default();
+        let first = Entity::from_raw(7);
+        let second = Entity::from_raw(3);
+        map.register(first);
+        map.register(second);
+
+        assert_eq!(map.translate_net(InactiveNet::new(0)), Some(first));
+        assert_eq!(map.translate_net(InactiveNet::new(1)), Some(second));
+        assert_eq!(map.local_to_net.get(&second), Some(&InactiveNet::new(1)));
+
+        map.deregister(first);
+        map.deregister(first);
+        assert_eq!(map.translate_net(InactiveNet::new(0)), None);
+        assert_eq!(map.translate_net(InactiveNet::new(1)), Some(second));
+    }
+
+    #[test]
+    fn test_command_budgets() {
+        let mut budgets = CommandBudgets::default();
+        let start = Duration::from_secs(10);
+
+        for _ in 0..(COMMAND_BURST as usize) {
+            assert!(budgets.try_spend(Player::Player1, start));
+        }
+        assert!(!budgets.try_spend(Player::Player1, start));
+        assert!(budgets.try_spend(Player::Player2, start));
+
+        let time = start + Duration::from_secs(1);
+        for _ in 0..(COMMAND_RATE as usize) {
+            assert!(budgets.try_spend(Player::Player1, time));
+        }
+        assert!(!budgets.try_spend(Player::Player1, time));
+    }
+}
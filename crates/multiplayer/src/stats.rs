@@ -4,7 +4,11 @@ use std::{
 };
 
 use bevy::prelude::*;
-use de_core::schedule::PreMovement;
+use de_core::{
+    command::{register_command, CommandEvent, CommandInfo},
+    schedule::PreMovement,
+};
+use de_gui::ToastEvent;
 use de_messages::{FromGame, ToGame};
 use de_net::Reliability;
 use tracing::{debug, info, trace};
@@ -20,6 +24,15 @@ const UNRELIABLE_PING_INTERVAL: Duration = Duration::from_secs(1);
 const UNRELIABLE_HISTORY: usize = 100;
 const STATS_INTERVAL: Duration = Duration::from_secs(10);
 const STATS_OFFSET: Duration = Duration::from_secs(10);
+/// Weight of a new unreliable ping round trip time in the moving average.
+const ROUND_TRIP_WEIGHT: f32 = 0.1;
+/// Round trip time up to which the connection is considered good.
+const GOOD_ROUND_TRIP: Duration = Duration::from_millis(200);
+/// Maximum factor by which non-essential traffic is reduced on a bad
+/// connection.
+const MAX_SLOWDOWN: f32 = 2.;
+const PING_COMMAND: CommandInfo =
+    CommandInfo::new("ping", &[], "Show round trip time to the game server.");
 
 pub(crate) struct StatsPlugin;
 
@@ -54,6 +67,8 @@ impl Plugin for StatsPlugin {
         Self::build_spec::<false>(app);
         Self::build_spec::<true>(app);
 
+        register_command(app, PING_COMMAND);
+
         app.add_systems(OnEnter(NetState::Joined), setup)
             .add_systems(OnExit(NetState::Joined), cleanup)
             .add_systems(
@@ -65,7 +80,8 @@ impl Plugin for StatsPlugin {
                         .after(StatsSet::Unresolved),
                 )
                     .run_if(in_state(NetState::Joined)),
-            );
+            )
+            .add_systems(Update, ping_command.run_if(on_event::<CommandEvent>()));
     }
 }
 
@@ -76,6 +92,44 @@ enum StatsSet {
     StatsTick,
 }
 
+/// Measured quality of the connection to the game server.
+#[derive(Resource)]
+pub(crate) struct ConnectionQuality {
+    /// Moving average of unreliable ping round trip time.
+    round_trip: Option<Duration>,
+    /// End-to-end delivery rate of unreliable pings.
+    delivery_rate: f32,
+}
+
+impl ConnectionQuality {
+    fn new() -> Self {
+        Self {
+            round_trip: None,
+            delivery_rate: 1.,
+        }
+    }
+
+    fn update_round_trip(&mut self, round_trip: Duration) {
+        self.round_trip = Some(match self.round_trip {
+            Some(average) => {
+                average.mul_f32(1. - ROUND_TRIP_WEIGHT) + round_trip.mul_f32(ROUND_TRIP_WEIGHT)
+            }
+            None => round_trip,
+        });
+    }
+
+    /// Returns a factor (between 1 and [`MAX_SLOWDOWN`]) by which the rate of
+    /// non-essential network traffic should be reduced.
+    pub(crate) fn slowdown(&self) -> f32 {
+        // Lost packets indicate congestion: 5% loss doubles the factor.
+        let loss = 1. + 20. * (1. - self.delivery_rate).max(0.);
+        let latency = self.round_trip.map_or(1., |round_trip| {
+            (round_trip.as_secs_f32() / GOOD_ROUND_TRIP.as_secs_f32()).max(1.)
+        });
+        (loss * latency).min(MAX_SLOWDOWN)
+    }
+}
+
 #[derive(Resource)]
 struct PingTimer<const R: bool>(Timer);
 
@@ -191,11 +245,33 @@ impl<const R: bool> PingTracker<R> {
 fn setup(mut commands: Commands) {
     commands.insert_resource(Counter::new());
     commands.insert_resource(StatsTimer(Timer::new(STATS_INTERVAL, TimerMode::Repeating)));
+    commands.insert_resource(ConnectionQuality::new());
 }
 
 fn cleanup(mut commands: Commands) {
     commands.remove_resource::<Counter>();
     commands.remove_resource::<StatsTimer>();
+    commands.remove_resource::<ConnectionQuality>();
+}
+
+fn ping_command(
+    quality: Option<Res<ConnectionQuality>>,
+    mut commands: EventReader<CommandEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    if !commands
+        .read()
+        .any(|command| command.name() == PING_COMMAND.name())
+    {
+        return;
+    }
+
+    let text = match quality.as_ref().map(|quality| quality.round_trip) {
+        Some(Some(round_trip)) => format!("Round trip time: {} ms", round_trip.as_millis()),
+        Some(None) => "Round trip time not yet measured.".to_owned(),
+        None => "Not connected to a game server.".to_owned(),
+    };
+    toasts.send(ToastEvent::new(text));
 }
 
 fn setup_spec<const R: bool>(mut commands: Commands) {
@@ -241,6 +317,7 @@ fn ping<const R: bool>(
 
 fn pong<const R: bool>(
     mut tracker: ResMut<PingTracker<R>>,
+    mut quality: ResMut<ConnectionQuality>,
     mut messages: EventReader<FromGameServerEvent>,
 ) {
     for event in messages.read() {
@@ -264,6 +341,7 @@ fn pong<const R: bool>(
                         system_time.as_millis(),
                         network_time.as_millis(),
                     );
+                    quality.update_round_trip(network_time);
                 }
             }
         }
@@ -291,11 +369,16 @@ fn stats_tick(time: Res<Time>, mut timer: ResMut<StatsTimer>) {
     timer.0.tick(time.delta());
 }
 
-fn delivery_rate(timer: ResMut<StatsTimer>, tracker: Res<PingTracker<false>>) {
+fn delivery_rate(
+    timer: ResMut<StatsTimer>,
+    tracker: Res<PingTracker<false>>,
+    mut quality: ResMut<ConnectionQuality>,
+) {
     if timer.0.just_finished() {
         let Some(rate) = tracker.resolution_rate(Instant::now() - STATS_OFFSET) else {
             return;
         };
+        quality.delivery_rate = rate;
 
         let rate_percentage = rate * 100.;
         let rate_sqrt_percentage = rate.sqrt() * 100.;
@@ -335,4 +418,24 @@ mod tests {
         tracker.trim(2, &mut ids);
         assert_eq!(ids, vec![0, 3]);
     }
+
+    #[test]
+    fn test_connection_quality() {
+        let mut quality = ConnectionQuality::new();
+        assert_eq!(quality.slowdown(), 1.);
+
+        quality.update_round_trip(Duration::from_millis(100));
+        assert_eq!(quality.slowdown(), 1.);
+        quality.update_round_trip(Duration::from_millis(2100));
+        let round_trip = quality.round_trip.unwrap().as_secs_f32();
+        assert!((round_trip - 0.3).abs() < 1e-4);
+        assert!((quality.slowdown() - 1.5).abs() < 1e-4);
+
+        quality.round_trip = Some(Duration::from_millis(100));
+
+        quality.delivery_rate = 0.975;
+        assert!((quality.slowdown() - 1.5).abs() < 1e-4);
+        quality.delivery_rate = 0.5;
+        assert_eq!(quality.slowdown(), MAX_SLOWDOWN);
+    }
 }
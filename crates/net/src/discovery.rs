@@ -0,0 +1,115 @@
+//! Discovery of game servers in the local network or at a known host. Clients
+//! broadcast (or send directly) a query datagram to [`DISCOVERY_PORT`] and
+//! servers respond with zero or more datagrams with arbitrary payload (e.g.
+//! one datagram per open game).
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use async_std::net::{SocketAddr, UdpSocket};
+
+use crate::{RecvError, SendError, MAX_DATAGRAM_SIZE};
+
+/// UDP port at which servers listen to discovery queries.
+pub const DISCOVERY_PORT: u16 = 8083;
+/// Maximum size of payload of a single discovery response.
+pub const MAX_DISCOVERY_PAYLOAD: usize = MAX_DATAGRAM_SIZE - RESPONSE_MAGIC.len();
+
+/// Content of discovery query datagrams. The trailing byte is version of the
+/// discovery protocol.
+const QUERY_MAGIC: [u8; 4] = [b'D', b'E', b'Q', 1];
+/// Discovery responses start with these bytes.
+const RESPONSE_MAGIC: [u8; 4] = [b'D', b'E', b'R', 1];
+
+/// Server side of the discovery protocol.
+pub struct DiscoveryResponder {
+    socket: UdpSocket,
+}
+
+impl DiscoveryResponder {
+    /// Binds a new IPv4 socket to [`DISCOVERY_PORT`].
+    pub async fn bind() -> io::Result<Self> {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), DISCOVERY_PORT);
+        Ok(Self {
+            socket: UdpSocket::bind(addr).await?,
+        })
+    }
+
+    /// Waits for a discovery query and returns address of the querying
+    /// client. All other datagrams are silently ignored.
+    pub async fn recv_query(&self) -> Result<SocketAddr, RecvError> {
+        let mut buf = [0; MAX_DATAGRAM_SIZE];
+        loop {
+            let (len, source) = self.socket.recv_from(&mut buf).await?;
+            if buf[..len] == QUERY_MAGIC {
+                return Ok(source);
+            }
+        }
+    }
+
+    /// Sends a single discovery response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `payload` is longer than [`MAX_DISCOVERY_PAYLOAD`].
+    pub async fn respond(&self, target: SocketAddr, payload: &[u8]) -> Result<(), SendError> {
+        assert!(payload.len() <= MAX_DISCOVERY_PAYLOAD);
+
+        let mut data = Vec::with_capacity(RESPONSE_MAGIC.len() + payload.len());
+        data.extend_from_slice(&RESPONSE_MAGIC);
+        data.extend_from_slice(payload);
+
+        let n = self.socket.send_to(&data, target).await?;
+        if n < data.len() {
+            Err(SendError::PartialSend(n, data.len()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Client side of the discovery protocol.
+pub struct DiscoveryClient {
+    socket: UdpSocket,
+}
+
+impl DiscoveryClient {
+    /// Binds a new broadcast enabled IPv4 socket to a system assigned port.
+    pub async fn bind() -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0)).await?;
+        socket.set_broadcast(true)?;
+        Ok(Self { socket })
+    }
+
+    /// Broadcasts a discovery query to the local network.
+    pub async fn query(&self) -> Result<(), SendError> {
+        self.query_host(IpAddr::V4(Ipv4Addr::BROADCAST)).await
+    }
+
+    /// Sends a discovery query to a single host.
+    pub async fn query_host(&self, host: IpAddr) -> Result<(), SendError> {
+        let target = SocketAddr::new(host, DISCOVERY_PORT);
+        let n = self.socket.send_to(&QUERY_MAGIC, target).await?;
+        if n < QUERY_MAGIC.len() {
+            Err(SendError::PartialSend(n, QUERY_MAGIC.len()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Waits for a discovery response and returns its payload and the
+    /// address of the responding server. All other datagrams are silently
+    /// ignored.
+    pub async fn recv(&self) -> Result<(Vec<u8>, SocketAddr), RecvError> {
+        let mut buf = [0; MAX_DATAGRAM_SIZE];
+        loop {
+            let (len, source) = self.socket.recv_from(&mut buf).await?;
+            let len = len.min(MAX_DATAGRAM_SIZE);
+            if let Some(payload) = buf[..len].strip_prefix(&RESPONSE_MAGIC) {
+                return Ok((payload.to_vec(), source));
+            }
+        }
+    }
+}
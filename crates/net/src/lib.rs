@@ -1,3 +1,4 @@
+pub use discovery::{DiscoveryClient, DiscoveryResponder, DISCOVERY_PORT, MAX_DISCOVERY_PAYLOAD};
 pub use header::{Peers, Reliability};
 pub use protocol::MAX_PACKAGE_SIZE;
 pub use socket::{RecvError, SendError, Socket, MAX_DATAGRAM_SIZE};
@@ -7,6 +8,7 @@ pub use tasks::{
 };
 
 mod connection;
+mod discovery;
 mod header;
 mod protocol;
 mod record;
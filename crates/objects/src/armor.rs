@@ -0,0 +1,58 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Kind of damage dealt by a weapon. Actual damage dealt to an object
+/// depends on its [`ArmorClass`], see [`damage_multiplier`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageType {
+    Laser,
+    /// Damage dealt by a direct hit of a projectile, e.g. an artillery shell.
+    Shell,
+    /// Damage dealt by an explosion to nearby objects.
+    Blast,
+}
+
+/// Armor of an object.
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArmorClass {
+    #[default]
+    Light,
+    Heavy,
+    /// Armor of buildings.
+    Structure,
+}
+
+/// Returns the factor by which damage of a type is multiplied when dealt to
+/// an object with an armor class.
+pub fn damage_multiplier(damage: DamageType, armor: ArmorClass) -> f32 {
+    match (damage, armor) {
+        (DamageType::Laser, ArmorClass::Light) => 1.,
+        (DamageType::Laser, ArmorClass::Heavy) => 0.75,
+        (DamageType::Laser, ArmorClass::Structure) => 0.5,
+        (DamageType::Shell, ArmorClass::Light) => 0.75,
+        (DamageType::Shell, ArmorClass::Heavy) => 1.,
+        (DamageType::Shell, ArmorClass::Structure) => 1.5,
+        (DamageType::Blast, ArmorClass::Light) => 1.25,
+        (DamageType::Blast, ArmorClass::Heavy) => 0.75,
+        (DamageType::Blast, ArmorClass::Structure) => 1.,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_armor_class() {
+        assert_eq!(
+            serde_json::from_str::<ArmorClass>(r#""structure""#).unwrap(),
+            ArmorClass::Structure
+        );
+        assert_eq!(ArmorClass::default(), ArmorClass::Light);
+        assert_eq!(
+            damage_multiplier(DamageType::Laser, ArmorClass::Structure),
+            0.5
+        );
+    }
+}
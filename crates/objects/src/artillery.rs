@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use anyhow::ensure;
+use bevy::prelude::Component;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::{ChaseDistances, TargetDomain};
+
+/// Multiple of cannon range. Artillery chasing a bombarded target resumes the
+/// chase once the target gets further than this.
+const MAX_CHASE_DISTANCE: f32 = 0.9;
+/// Multiple of cannon range. Artillery chasing a bombarded target approaches
+/// it up to this distance unless it is too close to the minimum range.
+const MIN_CHASE_DISTANCE: f32 = 0.75;
+/// Multiple of cannon minimum range. Artillery approaches bombarded targets
+/// up to this distance at most.
+const MIN_RANGE_MARGIN: f32 = 1.2;
+
+/// Long range cannon firing projectiles with travel time (e.g. shells or
+/// rockets). The cannon can fire only while its carrier is deployed.
+#[derive(Component, Clone)]
+pub struct ArtilleryCannon {
+    muzzle: Vec3,
+    min_range: f32,
+    range: f32,
+    damage: f32,
+    muzzle_speed: f32,
+    trajectory: Trajectory,
+    targets: TargetDomain,
+    deploy_time: Duration,
+    reload: ArtilleryReload,
+}
+
+impl ArtilleryCannon {
+    /// Returns relative position of the cannon muzzle to the object.
+    pub fn muzzle(&self) -> Vec3 {
+        self.muzzle
+    }
+
+    /// Minimum range of the cannon in meters. Objects closer than this cannot
+    /// be fired at.
+    pub fn min_range(&self) -> f32 {
+        self.min_range
+    }
+
+    /// Maximum range of the cannon in meters. Objects further than this
+    /// cannot be fired at.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Distances kept by the carrier of the cannon from bombarded targets.
+    pub fn chase_distances(&self) -> ChaseDistances {
+        let max_distance = MAX_CHASE_DISTANCE * self.range;
+        let min_distance = (MIN_CHASE_DISTANCE * self.range)
+            .max(MIN_RANGE_MARGIN * self.min_range)
+            .min(max_distance);
+        ChaseDistances::new(min_distance, max_distance)
+    }
+
+    /// When an object is hit, its health is decreased by this amount.
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+
+    /// Initial speed of fired projectiles in meters per second.
+    pub fn muzzle_speed(&self) -> f32 {
+        self.muzzle_speed
+    }
+
+    /// Trajectory of fired projectiles.
+    pub fn trajectory(&self) -> Trajectory {
+        self.trajectory
+    }
+
+    /// Kinds of objects the cannon can fire at.
+    pub fn targets(&self) -> TargetDomain {
+        self.targets
+    }
+
+    /// Time it takes to deploy or undeploy the carrier.
+    pub fn deploy_time(&self) -> Duration {
+        self.deploy_time
+    }
+
+    pub fn reload(&self) -> &ArtilleryReload {
+        &self.reload
+    }
+
+    pub fn reload_mut(&mut self) -> &mut ArtilleryReload {
+        &mut self.reload
+    }
+}
+
+/// Flight path of projectiles fired by an artillery cannon.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Trajectory {
+    /// The projectile is affected by gravity, e.g. a shell.
+    #[default]
+    Ballistic,
+    /// The projectile flies in a straight line at a constant speed, e.g. a
+    /// rocket.
+    Linear,
+}
+
+/// Reload timer of an artillery cannon. The cannon is loaded at spawn and
+/// needs to reload after each fire.
+#[derive(Clone, PartialEq)]
+pub struct ArtilleryReload {
+    reload_time: Duration,
+    remaining: Duration,
+}
+
+impl ArtilleryReload {
+    fn new(reload_time: Duration) -> Self {
+        Self {
+            reload_time,
+            remaining: Duration::ZERO,
+        }
+    }
+
+    /// Updates the timer.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_delta` - time delta since last call to this method.
+    pub fn tick(&mut self, time_delta: Duration) {
+        self.remaining = self.remaining.saturating_sub(time_delta);
+    }
+
+    /// Returns true if the cannon is ready to fire.
+    pub fn loaded(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Starts reloading of the cannon.
+    ///
+    /// Must be called only when the cannon is loaded.
+    pub fn fire(&mut self) {
+        debug_assert!(self.loaded());
+        self.remaining = self.reload_time;
+    }
+}
+
+impl TryFrom<ArtilleryCannonSerde> for ArtilleryCannon {
+    type Error = anyhow::Error;
+
+    fn try_from(info: ArtilleryCannonSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.min_range >= 0. && info.min_range < info.range,
+            "Artillery minimum range must be non-negative and smaller than range."
+        );
+        ensure!(
+            info.muzzle_speed > 0.,
+            "Artillery muzzle speed must be positive."
+        );
+
+        Ok(Self {
+            muzzle: Vec3::from_slice(info.muzzle.as_slice()),
+            min_range: info.min_range,
+            range: info.range,
+            damage: info.damage,
+            muzzle_speed: info.muzzle_speed,
+            trajectory: info.trajectory,
+            targets: info.targets,
+            deploy_time: Duration::from_secs_f32(info.deploy_time_sec),
+            reload: ArtilleryReload::new(Duration::from_secs_f32(info.reload_time_sec)),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ArtilleryCannonSerde {
+    muzzle: [f32; 3],
+    min_range: f32,
+    range: f32,
+    damage: f32,
+    muzzle_speed: f32,
+    #[serde(default)]
+    trajectory: Trajectory,
+    #[serde(default)]
+    targets: TargetDomain,
+    reload_time_sec: f32,
+    deploy_time_sec: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload() {
+        let mut reload = ArtilleryReload::new(Duration::from_secs(8));
+        assert!(reload.loaded());
+
+        reload.fire();
+        assert!(!reload.loaded());
+        reload.tick(Duration::from_secs(5));
+        assert!(!reload.loaded());
+        reload.tick(Duration::from_secs(5));
+        assert!(reload.loaded());
+    }
+
+    #[test]
+    fn test_chase_distances() {
+        let parse = |min_range: f32| {
+            let json = format!(
+                r#"{{"muzzle": [0, 1, 0], "min_range": {min_range}, "range": 120,
+                "damage": 20, "muzzle_speed": 60, "reload_time_sec": 8,
+                "deploy_time_sec": 2}}"#
+            );
+            ArtilleryCannon::try_from(serde_json::from_str::<ArtilleryCannonSerde>(&json).unwrap())
+                .unwrap()
+        };
+
+        let distances = parse(30.).chase_distances();
+        assert_eq!(distances.min_distance(), 90.);
+        assert_eq!(distances.max_distance(), 108.);
+        assert_eq!(parse(80.).chase_distances().min_distance(), 96.);
+        assert_eq!(parse(100.).chase_distances().hysteresis(), 0.);
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let parse = |trajectory: &str| {
+            let json = format!(
+                r#"{{"muzzle": [0, 1, 0], "min_range": 10, "range": 120,
+                "damage": 20, "muzzle_speed": 60, {trajectory}
+                "reload_time_sec": 8, "deploy_time_sec": 2}}"#
+            );
+            ArtilleryCannon::try_from(serde_json::from_str::<ArtilleryCannonSerde>(&json).unwrap())
+                .unwrap()
+        };
+
+        assert_eq!(parse("").trajectory(), Trajectory::Ballistic);
+        assert_eq!(
+            parse(r#""trajectory": "linear","#).trajectory(),
+            Trajectory::Linear
+        );
+    }
+}
@@ -1,14 +1,27 @@
 use std::{cmp::Ordering, time::Duration};
 
+use anyhow::{bail, ensure};
 use bevy::prelude::Component;
 use glam::Vec3;
 use serde::{Deserialize, Serialize};
 
+use crate::{ChaseDistances, TargetDomain};
+
+/// Multiple of cannon range. Entities chasing an attacked target resume the
+/// chase once the target gets further than this.
+const MAX_CHASE_DISTANCE: f32 = 0.9;
+/// Multiple of cannon range. Entities chasing an attacked target approach it
+/// up to this distance.
+const MIN_CHASE_DISTANCE: f32 = 0.7;
+
 #[derive(Component, Clone)]
 pub struct LaserCannon {
     muzzle: Vec3,
     range: f32,
-    damage: f32,
+    aggro_range: f32,
+    keep_distance: Option<f32>,
+    targets: TargetDomain,
+    effect: WeaponEffect,
     charge: LaserCharge,
 }
 
@@ -24,9 +37,35 @@ impl LaserCannon {
         self.range
     }
 
-    /// When an object is hit, its health is decreased by this amount.
-    pub fn damage(&self) -> f32 {
-        self.damage
+    /// Distance in meters within which enemies are engaged without an
+    /// explicit command. Enemies further than the cannon range are chased.
+    pub fn aggro_range(&self) -> f32 {
+        self.aggro_range
+    }
+
+    /// Distance in meters which the carrier of the cannon tries to keep from
+    /// attacked targets by backing away from them. None if the carrier does
+    /// not back away.
+    pub fn keep_distance(&self) -> Option<f32> {
+        self.keep_distance
+    }
+
+    /// Distances kept by the carrier of the cannon from attacked targets.
+    pub fn chase_distances(&self) -> ChaseDistances {
+        ChaseDistances::new(
+            MIN_CHASE_DISTANCE * self.range,
+            MAX_CHASE_DISTANCE * self.range,
+        )
+    }
+
+    /// Kinds of objects the cannon can fire at.
+    pub fn targets(&self) -> TargetDomain {
+        self.targets
+    }
+
+    /// Effect of the laser on hit objects.
+    pub fn effect(&self) -> WeaponEffect {
+        self.effect
     }
 
     pub fn charge(&self) -> &LaserCharge {
@@ -38,6 +77,16 @@ impl LaserCannon {
     }
 }
 
+/// Effect of a weapon on hit objects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeaponEffect {
+    /// Health of the hit object is decreased by this amount.
+    Damage(f32),
+    /// The hit object is disabled for this long. Disabled objects can neither
+    /// fire, move nor use energy.
+    Disable(Duration),
+}
+
 /// Charge of a laser cannon. It is used to keep track of needed cannon
 /// charging time.
 ///
@@ -147,10 +196,39 @@ impl TryFrom<LaserCannonSerde> for LaserCannon {
     type Error = anyhow::Error;
 
     fn try_from(info: LaserCannonSerde) -> Result<Self, Self::Error> {
+        let effect = match (info.damage, info.disable_time_sec) {
+            (Some(damage), None) => WeaponEffect::Damage(damage),
+            (None, Some(disable_time)) => {
+                ensure!(
+                    disable_time.is_finite() && disable_time > 0.,
+                    "Laser cannon disable time must be a positive number."
+                );
+                WeaponEffect::Disable(Duration::from_secs_f32(disable_time))
+            }
+            _ => bail!("Exactly one of laser cannon damage and disable time must be set."),
+        };
+
+        let aggro_range = info.aggro_range.unwrap_or(info.range);
+        ensure!(
+            aggro_range.is_finite() && aggro_range > 0.,
+            "Laser cannon aggro range must be a positive number, got: {aggro_range}"
+        );
+        if let Some(keep_distance) = info.keep_distance {
+            let max = MIN_CHASE_DISTANCE * info.range;
+            ensure!(
+                keep_distance > 0. && keep_distance < max,
+                "Laser cannon keep distance must be positive and smaller than {max}, got: \
+                 {keep_distance}"
+            );
+        }
+
         Ok(Self {
             muzzle: Vec3::from_slice(info.muzzle.as_slice()),
             range: info.range,
-            damage: info.damage,
+            aggro_range,
+            keep_distance: info.keep_distance,
+            targets: info.targets,
+            effect,
             charge: LaserCharge::new(
                 Duration::from_secs_f32(info.charge_time_sec),
                 Duration::from_secs_f32(info.discharge_time_sec),
@@ -163,7 +241,17 @@ impl TryFrom<LaserCannonSerde> for LaserCannon {
 pub(crate) struct LaserCannonSerde {
     muzzle: [f32; 3],
     range: f32,
-    damage: f32,
+    /// Defaults to the cannon range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aggro_range: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    keep_distance: Option<f32>,
+    #[serde(default)]
+    targets: TargetDomain,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    damage: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disable_time_sec: Option<f32>,
     charge_time_sec: f32,
     discharge_time_sec: f32,
 }
@@ -217,4 +305,53 @@ mod tests {
         assert!(b.cmp(&c) == Ordering::Less);
         assert!(c.cmp(&b) == Ordering::Greater);
     }
+
+    #[test]
+    fn test_effect() {
+        let parse = |effect: &str| {
+            let json = format!(
+                r#"{{"muzzle": [0, 1, 0], "range": 50, {effect}
+                "charge_time_sec": 2, "discharge_time_sec": 10}}"#
+            );
+            LaserCannon::try_from(serde_json::from_str::<LaserCannonSerde>(&json).unwrap())
+        };
+
+        assert_eq!(
+            parse(r#""damage": 3,"#).unwrap().effect(),
+            WeaponEffect::Damage(3.)
+        );
+        assert_eq!(
+            parse(r#""disable_time_sec": 4,"#).unwrap().effect(),
+            WeaponEffect::Disable(Duration::from_secs(4))
+        );
+        assert!(parse("").is_err());
+        assert!(parse(r#""damage": 3, "disable_time_sec": 4,"#).is_err());
+        assert!(parse(r#""disable_time_sec": 0,"#).is_err());
+    }
+
+    #[test]
+    fn test_aggro_range() {
+        let parse = |aggro: &str| {
+            let json = format!(
+                r#"{{"muzzle": [0, 1, 0], "range": 50, {aggro} "damage": 3,
+                "charge_time_sec": 2, "discharge_time_sec": 10}}"#
+            );
+            LaserCannon::try_from(serde_json::from_str::<LaserCannonSerde>(&json).unwrap())
+        };
+
+        assert_eq!(parse("").unwrap().aggro_range(), 50.);
+        assert_eq!(parse(r#""aggro_range": 60,"#).unwrap().aggro_range(), 60.);
+        assert!(parse(r#""aggro_range": 0,"#).is_err());
+
+        assert!(parse("").unwrap().keep_distance().is_none());
+        let cannon = parse(r#""keep_distance": 20,"#).unwrap();
+        assert_eq!(cannon.keep_distance(), Some(20.));
+        assert!(parse(r#""keep_distance": 40,"#).is_err());
+
+        assert_eq!(parse("").unwrap().targets(), TargetDomain::All);
+        assert_eq!(
+            parse(r#""targets": "air","#).unwrap().targets(),
+            TargetDomain::Air
+        );
+    }
 }
@@ -0,0 +1,54 @@
+/// Distances kept by an entity chasing a target, for example an enemy
+/// within reach of its weapon.
+///
+/// The chase is (re)started only once the target gets further than
+/// [`Self::max_distance`]. The chasing entity then approaches the target up
+/// to [`Self::min_distance`]. The gap between the two distances is a
+/// hysteresis which prevents the entity from oscillating between chasing the
+/// target and standing still (e.g. firing at it).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChaseDistances {
+    min_distance: f32,
+    max_distance: f32,
+}
+
+impl ChaseDistances {
+    /// # Arguments
+    ///
+    /// * `min_distance` - distance up to which the chasing entity approaches
+    ///   the chased entity. Elevation is ignored during the distance
+    ///   calculation.
+    ///
+    /// * `max_distance` - distance beyond which the chase is resumed.
+    ///   Elevation is ignored during the distance calculation.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `min_distance` or `max_distance` is not a non-negative
+    /// finite number or when `min_distance` is greater than `max_distance`.
+    pub fn new(min_distance: f32, max_distance: f32) -> Self {
+        debug_assert!(min_distance.is_finite());
+        debug_assert!(max_distance.is_finite());
+        debug_assert!(min_distance >= 0.);
+        debug_assert!(min_distance <= max_distance);
+
+        Self {
+            min_distance,
+            max_distance,
+        }
+    }
+
+    pub fn min_distance(&self) -> f32 {
+        self.min_distance
+    }
+
+    pub fn max_distance(&self) -> f32 {
+        self.max_distance
+    }
+
+    /// Returns the width of the band between the minimum and maximum
+    /// distance.
+    pub fn hysteresis(&self) -> f32 {
+        self.max_distance - self.min_distance
+    }
+}
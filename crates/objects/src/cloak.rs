@@ -0,0 +1,82 @@
+use anyhow::ensure;
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Cloaking device of an object. A cloaked object is hidden from enemy
+/// players unless it is revealed by one of their detectors.
+#[derive(Component, Clone)]
+pub struct CloakingDevice {
+    drain: f64,
+}
+
+impl CloakingDevice {
+    /// Energy in joules per second consumed while the object is cloaked.
+    pub fn drain(&self) -> f64 {
+        self.drain
+    }
+}
+
+impl TryFrom<CloakingDeviceSerde> for CloakingDevice {
+    type Error = anyhow::Error;
+
+    fn try_from(info: CloakingDeviceSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.drain.is_finite() && info.drain >= 0.,
+            "Cloaking device drain must be a non-negative number, got: {}",
+            info.drain
+        );
+
+        Ok(Self { drain: info.drain })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CloakingDeviceSerde {
+    drain: f64,
+}
+
+/// Detector of an object. It reveals cloaked enemy objects nearby.
+#[derive(Component, Clone)]
+pub struct Detector {
+    range: f32,
+}
+
+impl Detector {
+    /// Maximum distance in meters of revealed objects.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+}
+
+impl TryFrom<DetectorSerde> for Detector {
+    type Error = anyhow::Error;
+
+    fn try_from(info: DetectorSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.range.is_finite() && info.range > 0.,
+            "Detector range must be a positive number, got: {}",
+            info.range
+        );
+
+        Ok(Self { range: info.range })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DetectorSerde {
+    range: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        assert!(CloakingDevice::try_from(CloakingDeviceSerde { drain: 100. }).is_ok());
+        assert!(CloakingDevice::try_from(CloakingDeviceSerde { drain: -1. }).is_err());
+        assert!(Detector::try_from(DetectorSerde { range: 40. }).is_ok());
+        assert!(Detector::try_from(DetectorSerde { range: 0. }).is_err());
+        assert!(Detector::try_from(DetectorSerde { range: f32::NAN }).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::{hash::Hash, path::PathBuf};
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use bevy::{
     asset::{AssetPath, RecursiveDependencyLoadState},
     prelude::*,
@@ -49,6 +49,31 @@ where
         )))
     }
 
+    /// Returns true if the asset of the key, including all its dependencies,
+    /// is loaded.
+    ///
+    /// It shall not be called before [`Self::init`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if loading of the asset is either failed or unknown.
+    fn is_ready(&self, server: &AssetServer, key: Self::Key) -> bool {
+        match server.get_recursive_dependency_load_state(self.get(key)) {
+            Some(load_state) => match load_state {
+                RecursiveDependencyLoadState::Failed => panic!("Model loading failed"),
+                RecursiveDependencyLoadState::NotLoaded => false,
+                RecursiveDependencyLoadState::Loading => false,
+                RecursiveDependencyLoadState::Loaded => true,
+            },
+            None => panic!(
+                "Unknown asset: {}/{}.{}",
+                Self::DIRECTORY,
+                key.stem(),
+                Self::SUFFIX
+            ),
+        }
+    }
+
     /// Returns progress of the loading.
     ///
     /// It shall not be called before [`Self::init`].
@@ -58,23 +83,70 @@ where
     /// Panics if loading any of the assets is either failed or unknown.
     fn progress(&self, server: &AssetServer) -> Progress {
         enum_iterator::all::<Self::Key>()
-            .map(
-                |key| match server.get_recursive_dependency_load_state(self.get(key)) {
-                    Some(load_state) => match load_state {
-                        RecursiveDependencyLoadState::Failed => panic!("Model loading failed"),
-                        RecursiveDependencyLoadState::NotLoaded => false.into(),
-                        RecursiveDependencyLoadState::Loading => false.into(),
-                        RecursiveDependencyLoadState::Loaded => true.into(),
-                    },
-                    None => panic!(
-                        "Unknown asset: {}/{}.{}",
-                        Self::DIRECTORY,
-                        key.stem(),
-                        Self::SUFFIX
-                    ),
-                },
-            )
+            .map(|key| self.is_ready(server, key).into())
             .reduce(|a, b| a + b)
             .unwrap_or(Progress { done: 0, total: 0 })
     }
 }
+
+/// Keys of an asset collection whose assets are already fully loaded. It is
+/// used to track assets loaded in the background after the application
+/// loading state.
+pub(crate) struct Readiness<K>(AHashSet<K>);
+
+impl<K> Readiness<K>
+where
+    K: Copy + Eq + Hash + Sequence,
+{
+    pub(crate) fn new() -> Self {
+        Self(AHashSet::new())
+    }
+
+    pub(crate) fn is_ready(&self, key: K) -> bool {
+        self.0.contains(&key)
+    }
+
+    /// Returns true if assets of all keys are ready.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.0.len() == enum_iterator::cardinality::<K>()
+    }
+
+    /// Marks assets of a key as ready.
+    pub(crate) fn mark(&mut self, key: K) {
+        self.0.insert(key);
+    }
+
+    /// Returns all keys which have not been marked as ready yet.
+    pub(crate) fn pending(&self) -> impl Iterator<Item = K> + '_ {
+        enum_iterator::all::<K>().filter(|key| !self.is_ready(*key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use de_types::objects::{ActiveObjectType, ObjectType, UnitType};
+
+    use super::*;
+
+    #[test]
+    fn test_readiness() {
+        let mut readiness = Readiness::<ObjectType>::new();
+        let total = enum_iterator::cardinality::<ObjectType>();
+        assert_eq!(readiness.pending().count(), total);
+
+        let attacker = ObjectType::Active(ActiveObjectType::Unit(UnitType::Attacker));
+        readiness.mark(attacker);
+        assert!(readiness.is_ready(attacker));
+        assert!(!readiness.is_complete());
+        assert_eq!(readiness.pending().count(), total - 1);
+        assert!(readiness
+            .pending()
+            .all(|object_type| object_type != attacker));
+
+        for object_type in enum_iterator::all::<ObjectType>() {
+            readiness.mark(object_type);
+        }
+        assert!(readiness.is_complete());
+        assert_eq!(readiness.pending().count(), 0);
+    }
+}
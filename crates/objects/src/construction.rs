@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+/// Requirements of placing a building by a player.
+#[derive(Clone)]
+pub struct Construction {
+    cost: f32,
+    time: Duration,
+}
+
+impl Construction {
+    /// Amount of resources paid when the building is placed.
+    pub fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    /// Time it takes to construct the building.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+impl TryFrom<ConstructionSerde> for Construction {
+    type Error = anyhow::Error;
+
+    fn try_from(info: ConstructionSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.cost.is_finite() && info.cost >= 0.,
+            "Construction cost must be a non-negative number, got: {}",
+            info.cost
+        );
+        ensure!(
+            info.time.is_finite() && info.time >= 0.,
+            "Construction time must be a non-negative number, got: {}",
+            info.time
+        );
+
+        Ok(Self {
+            cost: info.cost,
+            time: Duration::from_secs_f32(info.time),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ConstructionSerde {
+    cost: f32,
+    /// Construction time in seconds.
+    time: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction() {
+        let parse = |json: &str| {
+            Construction::try_from(serde_json::from_str::<ConstructionSerde>(json).unwrap())
+        };
+
+        let construction = parse(r#"{"cost": 150, "time": 12.5}"#).unwrap();
+        assert_eq!(construction.cost(), 150.);
+        assert_eq!(construction.time(), Duration::from_millis(12500));
+
+        assert!(parse(r#"{"cost": -1, "time": 10}"#).is_err());
+        assert!(parse(r#"{"cost": 10, "time": -1}"#).is_err());
+    }
+}
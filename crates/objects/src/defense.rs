@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use anyhow::ensure;
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Point defense module automatically shooting down incoming enemy
+/// projectiles (e.g. artillery shells).
+#[derive(Component, Clone)]
+pub struct PointDefense {
+    range: f32,
+    energy: f64,
+    cooldown: Duration,
+    remaining: Duration,
+}
+
+impl PointDefense {
+    /// Maximum distance in meters of intercepted projectiles.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Energy in joules consumed by a single interception.
+    pub fn energy(&self) -> f64 {
+        self.energy
+    }
+
+    /// Updates the cooldown timer.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_delta` - time delta since last call to this method.
+    pub fn tick(&mut self, time_delta: Duration) {
+        self.remaining = self.remaining.saturating_sub(time_delta);
+    }
+
+    /// Returns true if the module is ready to intercept a projectile.
+    pub fn ready(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Starts the cooldown after an interception.
+    ///
+    /// Must be called only when the module is ready.
+    pub fn intercept(&mut self) {
+        debug_assert!(self.ready());
+        self.remaining = self.cooldown;
+    }
+}
+
+impl TryFrom<PointDefenseSerde> for PointDefense {
+    type Error = anyhow::Error;
+
+    fn try_from(info: PointDefenseSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.range.is_finite() && info.range > 0.,
+            "Point defense range must be a positive number, got: {}",
+            info.range
+        );
+        ensure!(
+            info.energy.is_finite() && info.energy >= 0.,
+            "Point defense energy must be a non-negative number, got: {}",
+            info.energy
+        );
+        ensure!(
+            info.cooldown_sec.is_finite() && info.cooldown_sec >= 0.,
+            "Point defense cooldown must be a non-negative number, got: {}",
+            info.cooldown_sec
+        );
+
+        Ok(Self {
+            range: info.range,
+            energy: info.energy,
+            cooldown: Duration::from_secs_f32(info.cooldown_sec),
+            remaining: Duration::ZERO,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct PointDefenseSerde {
+    range: f32,
+    energy: f64,
+    cooldown_sec: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown() {
+        let mut defense = PointDefense::try_from(PointDefenseSerde {
+            range: 20.,
+            energy: 1000.,
+            cooldown_sec: 2.,
+        })
+        .unwrap();
+        assert!(defense.ready());
+
+        defense.intercept();
+        assert!(!defense.ready());
+        defense.tick(Duration::from_secs(1));
+        assert!(!defense.ready());
+        defense.tick(Duration::from_secs(1));
+        assert!(defense.ready());
+    }
+}
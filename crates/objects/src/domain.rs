@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// Kinds of objects a weapon is able to fire at.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetDomain {
+    /// Both flying and ground objects can be targeted.
+    #[default]
+    All,
+    /// Only objects without flight capability can be targeted.
+    Ground,
+    /// Only flying objects can be targeted.
+    Air,
+}
+
+impl TargetDomain {
+    /// Returns true if an object can be targeted.
+    ///
+    /// # Arguments
+    ///
+    /// * `flying` - whether the object is capable of flight, see
+    ///   [`crate::Flight`].
+    pub fn allows(self, flying: bool) -> bool {
+        match self {
+            Self::All => true,
+            Self::Ground => !flying,
+            Self::Air => flying,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        assert!(TargetDomain::All.allows(true));
+        assert!(TargetDomain::All.allows(false));
+        assert!(!TargetDomain::Ground.allows(true));
+        assert!(TargetDomain::Ground.allows(false));
+        assert!(TargetDomain::Air.allows(true));
+        assert!(!TargetDomain::Air.allows(false));
+
+        assert_eq!(
+            serde_json::from_str::<TargetDomain>(r#""ground""#).unwrap(),
+            TargetDomain::Ground
+        );
+    }
+}
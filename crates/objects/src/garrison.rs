@@ -0,0 +1,128 @@
+use anyhow::ensure;
+use bevy::prelude::{Component, Entity};
+use serde::{Deserialize, Serialize};
+
+/// Garrison of an object, for example a transport or a bunker. Small units
+/// can be loaded to (garrisoned in) the object.
+#[derive(Component, Clone)]
+pub struct Garrison {
+    capacity: u8,
+    max_radius: f32,
+    occupants: Vec<Entity>,
+}
+
+impl Garrison {
+    /// Maximum number of garrisoned units.
+    pub fn capacity(&self) -> u8 {
+        self.capacity
+    }
+
+    /// Maximum radius in meters of garrisoned units.
+    pub fn max_radius(&self) -> f32 {
+        self.max_radius
+    }
+
+    /// Currently garrisoned units.
+    pub fn occupants(&self) -> &[Entity] {
+        self.occupants.as_slice()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.occupants.len() >= usize::from(self.capacity)
+    }
+
+    /// Returns occupied fraction of the capacity.
+    pub fn occupancy(&self) -> f32 {
+        self.occupants.len() as f32 / f32::from(self.capacity)
+    }
+
+    /// Adds a unit to the garrison. Returns false if the garrison is full.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the unit is already garrisoned.
+    pub fn load(&mut self, unit: Entity) -> bool {
+        debug_assert!(!self.occupants.contains(&unit));
+        if self.is_full() {
+            return false;
+        }
+        self.occupants.push(unit);
+        true
+    }
+
+    /// Removes a unit from the garrison. Returns false if the unit was not
+    /// garrisoned.
+    pub fn unload(&mut self, unit: Entity) -> bool {
+        let Some(index) = self.occupants.iter().position(|&entity| entity == unit) else {
+            return false;
+        };
+        self.occupants.remove(index);
+        true
+    }
+
+    /// Retains only the garrisoned units for which `keep` returns true.
+    pub fn retain<F: FnMut(Entity) -> bool>(&mut self, mut keep: F) {
+        self.occupants.retain(|&unit| keep(unit));
+    }
+}
+
+impl TryFrom<GarrisonSerde> for Garrison {
+    type Error = anyhow::Error;
+
+    fn try_from(info: GarrisonSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.capacity > 0,
+            "Garrison capacity must be a positive number."
+        );
+        ensure!(
+            info.max_radius.is_finite() && info.max_radius > 0.,
+            "Garrison maximum unit radius must be a positive number, got: {}",
+            info.max_radius
+        );
+
+        Ok(Self {
+            capacity: info.capacity,
+            max_radius: info.max_radius,
+            occupants: Vec::new(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GarrisonSerde {
+    capacity: u8,
+    max_radius: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_unload() {
+        let mut garrison = Garrison::try_from(GarrisonSerde {
+            capacity: 2,
+            max_radius: 1.5,
+        })
+        .unwrap();
+        let a = Entity::from_raw(1);
+        let b = Entity::from_raw(2);
+        let c = Entity::from_raw(3);
+
+        assert_eq!(garrison.occupancy(), 0.);
+        assert!(garrison.load(a));
+        assert!(garrison.load(b));
+        assert!(garrison.is_full());
+        assert!(!garrison.load(c));
+        assert_eq!(garrison.occupants(), &[a, b]);
+        assert_eq!(garrison.occupancy(), 1.);
+
+        assert!(garrison.unload(a));
+        assert!(!garrison.unload(a));
+        assert!(garrison.load(c));
+        assert_eq!(garrison.occupants(), &[b, c]);
+
+        garrison.retain(|unit| unit != b);
+        assert_eq!(garrison.occupants(), &[c]);
+    }
+}
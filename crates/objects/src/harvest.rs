@@ -0,0 +1,172 @@
+use anyhow::ensure;
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Resource gathering capabilities of an object together with the currently
+/// carried load.
+#[derive(Component, Clone)]
+pub struct Harvester {
+    capacity: f32,
+    rate: f32,
+    load: f32,
+}
+
+impl Harvester {
+    /// Maximum amount of resources the harvester can carry.
+    pub fn capacity(&self) -> f32 {
+        self.capacity
+    }
+
+    /// Amount of resources gathered per second.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Amount of currently carried resources.
+    pub fn load(&self) -> f32 {
+        self.load
+    }
+
+    /// Returns true if the harvester cannot carry any more resources.
+    pub fn full(&self) -> bool {
+        self.load >= self.capacity
+    }
+
+    /// Returns amount of resources which can be loaded to the harvester.
+    pub fn free_capacity(&self) -> f32 {
+        (self.capacity - self.load).max(0.)
+    }
+
+    /// Adds resources to the carried load.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the amount is negative or larger than free capacity of
+    /// the harvester.
+    pub fn fill(&mut self, amount: f32) {
+        debug_assert!(amount >= 0.);
+        debug_assert!(amount <= self.free_capacity() + f32::EPSILON * self.capacity);
+        self.load = (self.load + amount).min(self.capacity);
+    }
+
+    /// Empties the harvester and returns amount of previously carried
+    /// resources.
+    pub fn unload(&mut self) -> f32 {
+        std::mem::take(&mut self.load)
+    }
+}
+
+impl TryFrom<HarvesterSerde> for Harvester {
+    type Error = anyhow::Error;
+
+    fn try_from(info: HarvesterSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.capacity.is_finite() && info.capacity > 0.,
+            "Harvester capacity must be a positive number, got: {}",
+            info.capacity
+        );
+        ensure!(
+            info.rate.is_finite() && info.rate > 0.,
+            "Harvester rate must be a positive number, got: {}",
+            info.rate
+        );
+
+        Ok(Self {
+            capacity: info.capacity,
+            rate: info.rate,
+            load: 0.,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct HarvesterSerde {
+    capacity: f32,
+    rate: f32,
+}
+
+/// Natural resource deposit which can be gathered by harvesters.
+#[derive(Component, Clone)]
+pub struct Deposit {
+    remaining: f32,
+}
+
+impl Deposit {
+    /// Amount of resources left in the deposit.
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Returns true if there is nothing left to be gathered.
+    pub fn exhausted(&self) -> bool {
+        self.remaining <= 0.
+    }
+
+    /// Removes up to `amount` of resources from the deposit and returns the
+    /// actually removed amount.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the amount is negative.
+    pub fn extract(&mut self, amount: f32) -> f32 {
+        debug_assert!(amount >= 0.);
+        let extracted = amount.min(self.remaining);
+        self.remaining -= extracted;
+        extracted
+    }
+}
+
+impl TryFrom<DepositSerde> for Deposit {
+    type Error = anyhow::Error;
+
+    fn try_from(info: DepositSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.amount.is_finite() && info.amount > 0.,
+            "Deposit amount must be a positive number, got: {}",
+            info.amount
+        );
+
+        Ok(Self {
+            remaining: info.amount,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DepositSerde {
+    amount: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_harvest() {
+        let mut deposit = Deposit { remaining: 25. };
+        let mut harvester = Harvester {
+            capacity: 20.,
+            rate: 2.,
+            load: 0.,
+        };
+
+        let extracted = deposit.extract(harvester.free_capacity().min(15.));
+        assert_eq!(extracted, 15.);
+        harvester.fill(extracted);
+        assert!(!harvester.full());
+        assert_eq!(harvester.free_capacity(), 5.);
+
+        let extracted = deposit.extract(harvester.free_capacity());
+        harvester.fill(extracted);
+        assert!(harvester.full());
+        assert_eq!(deposit.remaining(), 5.);
+        assert!(!deposit.exhausted());
+
+        assert_eq!(harvester.unload(), 20.);
+        assert_eq!(harvester.load(), 0.);
+
+        assert_eq!(deposit.extract(10.), 5.);
+        assert!(deposit.exhausted());
+        assert_eq!(deposit.extract(10.), 0.);
+    }
+}
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use de_types::objects::{ActiveObjectType, BuildingType, UnitType};
+use de_types::objects::{ActiveObjectType, BuildingType, InactiveObjectType, UnitType};
 use enum_map::{enum_map, EnumMap};
 
 pub(crate) struct HealthPlugin;
@@ -14,12 +14,19 @@ impl Plugin for HealthPlugin {
 #[derive(Resource)]
 pub struct InitialHealths {
     healths: EnumMap<ActiveObjectType, Health>,
+    inactive: EnumMap<InactiveObjectType, Option<Health>>,
 }
 
 impl InitialHealths {
     pub fn health(&self, object_type: ActiveObjectType) -> &Health {
         &self.healths[object_type]
     }
+
+    /// Returns initial health of an inactive object or None if objects of
+    /// the type are indestructible.
+    pub fn inactive_health(&self, object_type: InactiveObjectType) -> Option<&Health> {
+        self.inactive[object_type].as_ref()
+    }
 }
 
 impl Default for InitialHealths {
@@ -28,7 +35,18 @@ impl Default for InitialHealths {
             healths: enum_map! {
                 ActiveObjectType::Building(BuildingType::Base) => Health::full(100.),
                 ActiveObjectType::Building(BuildingType::PowerHub) => Health::full(40.),
+                ActiveObjectType::Building(BuildingType::Bunker) => Health::full(80.),
+                ActiveObjectType::Building(BuildingType::ShieldDome) => Health::full(50.),
+                ActiveObjectType::Building(BuildingType::Radar) => Health::full(30.),
                 ActiveObjectType::Unit(UnitType::Attacker) => Health::full(10.),
+                ActiveObjectType::Unit(UnitType::Artillery) => Health::full(14.),
+                ActiveObjectType::Unit(UnitType::Scout) => Health::full(4.),
+                ActiveObjectType::Unit(UnitType::Harvester) => Health::full(12.),
+                ActiveObjectType::Unit(UnitType::Transport) => Health::full(16.),
+            },
+            inactive: enum_map! {
+                InactiveObjectType::Tree => Some(Health::full(6.)),
+                InactiveObjectType::Ore => None,
             },
         }
     }
@@ -55,6 +73,17 @@ impl Health {
         }
     }
 
+    /// Returns current health. It might be negative or greater than maximum
+    /// health, see [`Self::update`].
+    pub fn health(&self) -> f32 {
+        self.health
+    }
+
+    /// Returns maximum health.
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
     /// Returns the fraction of remaining health, i.e. ratio between current
     /// health and maximum health.
     pub fn fraction(&self) -> f32 {
@@ -82,6 +111,17 @@ impl Health {
         self.health += delta;
     }
 
+    /// Changes maximum health. Current health is kept unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - new maximum health. Must be a positive finite number.
+    pub fn set_max(&mut self, max: f32) {
+        debug_assert!(max.is_finite());
+        debug_assert!(max > 0.);
+        self.max = max;
+    }
+
     pub fn destroyed(&self) -> bool {
         self.health <= 0.
     }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 /// object trajectory smoothing and non-zero moving object sizes.
 pub const EXCLUSION_OFFSET: f32 = 2.;
 
+#[derive(Clone)]
 pub struct Ichnography {
     radius: f32,
     local_aabb: Aabb,
@@ -0,0 +1,55 @@
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::state::AppState;
+use de_types::objects::ObjectType;
+use iyes_progress::prelude::*;
+
+use crate::collection::{AssetCollection, AssetCollectionLoader};
+
+pub(crate) struct IconsPlugin;
+
+impl Plugin for IconsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::AppLoading), setup)
+            .add_systems(
+                Update,
+                check_status
+                    .track_progress()
+                    .run_if(in_state(AppState::AppLoading)),
+            );
+    }
+}
+
+/// 2D icons of all object types, used to represent the objects in the UI.
+#[derive(Resource)]
+pub struct ObjectIcons(AHashMap<ObjectType, Handle<Image>>);
+
+impl AssetCollection for ObjectIcons {
+    type Key = ObjectType;
+    type Asset = Image;
+
+    fn get(&self, key: Self::Key) -> &Handle<Self::Asset> {
+        self.0.get(&key).unwrap()
+    }
+}
+
+impl AssetCollectionLoader for ObjectIcons {
+    const DIRECTORY: &'static str = "icons";
+    const SUFFIX: &'static str = "png";
+
+    fn new(map: AHashMap<Self::Key, Handle<Self::Asset>>) -> Self {
+        Self(map)
+    }
+
+    fn label() -> Option<String> {
+        None
+    }
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    commands.insert_resource(ObjectIcons::init(server.as_ref()));
+}
+
+fn check_status(server: Res<AssetServer>, icons: Res<ObjectIcons>) -> Progress {
+    icons.progress(server.as_ref())
+}
@@ -1,29 +1,63 @@
 //! This crate implements functionality around map object handling, mostly
 //! object asset caching and pre-loading.
 
+pub use armor::{damage_multiplier, ArmorClass, DamageType};
+pub use artillery::{ArtilleryCannon, ArtilleryReload, Trajectory};
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
-pub use cannon::LaserCannon;
+pub use cannon::{LaserCannon, WeaponEffect};
+pub use chase::ChaseDistances;
+pub use cloak::{CloakingDevice, Detector};
 pub use collection::AssetCollection;
 pub use collider::ObjectCollider;
+pub use construction::Construction;
+pub use defense::PointDefense;
+pub use domain::TargetDomain;
 pub use flight::Flight;
+pub use garrison::Garrison;
+pub use harvest::{Deposit, Harvester};
 use health::HealthPlugin;
 pub use health::{Health, InitialHealths};
 pub use ichnography::{Ichnography, EXCLUSION_OFFSET};
+use icons::IconsPlugin;
+pub use icons::ObjectIcons;
+pub use mobility::Mobility;
+pub use names::FileStem;
+pub use regeneration::Regeneration;
+pub use repair::RepairBeam;
 use scenes::ScenesPlugin;
-pub use scenes::{SceneType, Scenes};
+pub use scenes::{LodScenes, ModelLoadedEvent, SceneType, Scenes, SubNode, SubNodes};
+pub use shield::ShieldGenerator;
 use solids::SolidsPlugin;
-pub use solids::{SolidObject, SolidObjects};
+pub use solids::{SolidObject, SolidObjects, OBJECT_EXTENSION};
+pub use support::{Radar, ShieldDome};
+pub use vision::{SightRange, Vision};
 
+mod armor;
+mod artillery;
 mod cannon;
+mod chase;
+mod cloak;
 mod collection;
 mod collider;
+mod construction;
+mod defense;
+mod domain;
 mod factory;
 mod flight;
+mod garrison;
+mod harvest;
 mod health;
 mod ichnography;
+mod icons;
+mod mobility;
 mod names;
+mod regeneration;
+mod repair;
 mod scenes;
+mod shield;
 mod solids;
+mod support;
+mod vision;
 
 pub struct ObjectsPluginGroup;
 
@@ -33,5 +67,6 @@ impl PluginGroup for ObjectsPluginGroup {
             .add(ScenesPlugin)
             .add(SolidsPlugin)
             .add(HealthPlugin)
+            .add(IconsPlugin)
     }
 }
@@ -0,0 +1,37 @@
+use anyhow::ensure;
+use de_uom::MetrePerSecond;
+use serde::{Deserialize, Serialize};
+
+/// Movement capabilities of an object overriding the defaults shared by all
+/// movable objects.
+pub struct Mobility {
+    max_speed: MetrePerSecond,
+}
+
+impl Mobility {
+    /// Returns maximum horizontal speed of the object.
+    pub fn max_speed(&self) -> MetrePerSecond {
+        self.max_speed
+    }
+}
+
+impl TryFrom<MobilitySerde> for Mobility {
+    type Error = anyhow::Error;
+
+    fn try_from(mobility_serde: MobilitySerde) -> Result<Self, Self::Error> {
+        ensure!(
+            mobility_serde.max_speed.is_finite() && mobility_serde.max_speed > 0.,
+            "Maximum speed must be a positive number, got: {}",
+            mobility_serde.max_speed
+        );
+
+        Ok(Self {
+            max_speed: MetrePerSecond::new(mobility_serde.max_speed),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MobilitySerde {
+    max_speed: f32,
+}
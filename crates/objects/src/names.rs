@@ -1,6 +1,8 @@
 use de_types::objects::{ActiveObjectType, BuildingType, InactiveObjectType, ObjectType, UnitType};
 
-pub(crate) trait FileStem: Copy {
+/// Types whose assets are stored in files named after them.
+pub trait FileStem: Copy {
+    /// Returns asset file name without directory and extension.
     fn stem(self) -> &'static str;
 }
 
@@ -9,8 +11,16 @@ impl FileStem for ObjectType {
         match self {
             Self::Active(ActiveObjectType::Building(BuildingType::Base)) => "base",
             Self::Active(ActiveObjectType::Building(BuildingType::PowerHub)) => "powerhub",
+            Self::Active(ActiveObjectType::Building(BuildingType::Bunker)) => "bunker",
+            Self::Active(ActiveObjectType::Building(BuildingType::ShieldDome)) => "shield_dome",
+            Self::Active(ActiveObjectType::Building(BuildingType::Radar)) => "radar",
             Self::Active(ActiveObjectType::Unit(UnitType::Attacker)) => "attacker",
+            Self::Active(ActiveObjectType::Unit(UnitType::Artillery)) => "artillery",
+            Self::Active(ActiveObjectType::Unit(UnitType::Scout)) => "scout",
+            Self::Active(ActiveObjectType::Unit(UnitType::Harvester)) => "harvester",
+            Self::Active(ActiveObjectType::Unit(UnitType::Transport)) => "transport",
             Self::Inactive(InactiveObjectType::Tree) => "tree",
+            Self::Inactive(InactiveObjectType::Ore) => "ore",
         }
     }
 }
@@ -0,0 +1,83 @@
+use anyhow::ensure;
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Passive change of object health over time. Positive rates repair the
+/// object, negative rates degrade it.
+#[derive(Component, Clone)]
+pub struct Regeneration {
+    powered: f32,
+    unpowered: f32,
+}
+
+impl Regeneration {
+    /// Health change per second while the object is powered, i.e. while its
+    /// battery is not empty and the object is not disabled.
+    pub fn powered(&self) -> f32 {
+        self.powered
+    }
+
+    /// Health change per second while the object is not powered.
+    pub fn unpowered(&self) -> f32 {
+        self.unpowered
+    }
+
+    /// Returns health change per second.
+    pub fn rate(&self, powered: bool) -> f32 {
+        if powered {
+            self.powered
+        } else {
+            self.unpowered
+        }
+    }
+}
+
+impl TryFrom<RegenerationSerde> for Regeneration {
+    type Error = anyhow::Error;
+
+    fn try_from(info: RegenerationSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.powered.is_finite(),
+            "Powered regeneration rate must be a finite number, got: {}",
+            info.powered
+        );
+        ensure!(
+            info.unpowered.is_finite(),
+            "Unpowered regeneration rate must be a finite number, got: {}",
+            info.unpowered
+        );
+
+        Ok(Self {
+            powered: info.powered,
+            unpowered: info.unpowered,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegenerationSerde {
+    #[serde(default)]
+    powered: f32,
+    #[serde(default)]
+    unpowered: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regeneration() {
+        let parse = |json: &str| {
+            Regeneration::try_from(serde_json::from_str::<RegenerationSerde>(json).unwrap())
+        };
+
+        let regeneration = parse(r#"{"powered": 0.2}"#).unwrap();
+        assert_eq!(regeneration.rate(true), 0.2);
+        assert_eq!(regeneration.rate(false), 0.);
+
+        let regeneration = parse(r#"{"powered": 0.1, "unpowered": -0.05}"#).unwrap();
+        assert_eq!(regeneration.powered(), 0.1);
+        assert_eq!(regeneration.unpowered(), -0.05);
+    }
+}
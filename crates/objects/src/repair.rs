@@ -0,0 +1,118 @@
+use anyhow::ensure;
+use bevy::prelude::Component;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::ChaseDistances;
+
+/// Multiple of beam range. Entities following a repaired target resume the
+/// chase once the target gets further than this.
+const MAX_CHASE_DISTANCE: f32 = 0.9;
+/// Multiple of beam range. Entities following a repaired target approach it
+/// up to this distance.
+const MIN_CHASE_DISTANCE: f32 = 0.6;
+
+/// Repair beam of an object. The beam restores health of damaged friendly
+/// objects while draining the battery of the repairing object.
+#[derive(Component, Clone)]
+pub struct RepairBeam {
+    muzzle: Vec3,
+    range: f32,
+    rate: f32,
+    drain: f64,
+}
+
+impl RepairBeam {
+    /// Returns relative position of the beam emitter to the object.
+    pub fn muzzle(&self) -> Vec3 {
+        self.muzzle
+    }
+
+    /// Maximum distance in meters between the repairing object and the
+    /// footprint of the repaired object.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Health restored per second.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Energy in joules per second consumed while the beam is operating.
+    pub fn drain(&self) -> f64 {
+        self.drain
+    }
+
+    pub fn chase_distances(&self) -> ChaseDistances {
+        ChaseDistances::new(
+            MIN_CHASE_DISTANCE * self.range,
+            MAX_CHASE_DISTANCE * self.range,
+        )
+    }
+}
+
+impl TryFrom<RepairBeamSerde> for RepairBeam {
+    type Error = anyhow::Error;
+
+    fn try_from(info: RepairBeamSerde) -> Result<Self, Self::Error> {
+        let muzzle = Vec3::from_slice(info.muzzle.as_slice());
+        ensure!(
+            muzzle.is_finite(),
+            "Repair beam muzzle must be finite, got: {}",
+            muzzle
+        );
+        ensure!(
+            info.range.is_finite() && info.range > 0.,
+            "Repair beam range must be a positive number, got: {}",
+            info.range
+        );
+        ensure!(
+            info.rate.is_finite() && info.rate > 0.,
+            "Repair rate must be a positive number, got: {}",
+            info.rate
+        );
+        ensure!(
+            info.drain.is_finite() && info.drain >= 0.,
+            "Repair beam drain must be a non-negative number, got: {}",
+            info.drain
+        );
+
+        Ok(Self {
+            muzzle,
+            range: info.range,
+            rate: info.rate,
+            drain: info.drain,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RepairBeamSerde {
+    muzzle: [f32; 3],
+    range: f32,
+    rate: f32,
+    drain: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let parse = |json: &str| {
+            RepairBeam::try_from(serde_json::from_str::<RepairBeamSerde>(json).unwrap())
+        };
+
+        let beam = parse(r#"{"muzzle": [0, 1, 0], "range": 8, "rate": 5, "drain": 2000}"#).unwrap();
+        assert_eq!(beam.muzzle(), Vec3::Y);
+        assert_eq!(beam.range(), 8.);
+        assert_eq!(beam.rate(), 5.);
+        assert_eq!(beam.drain(), 2000.);
+
+        assert!(parse(r#"{"muzzle": [0, 1, 0], "range": 0, "rate": 5, "drain": 2000}"#).is_err());
+        assert!(parse(r#"{"muzzle": [0, 1, 0], "range": 8, "rate": 0, "drain": 2000}"#).is_err());
+        assert!(parse(r#"{"muzzle": [0, 1, 0], "range": 8, "rate": 5, "drain": -1}"#).is_err());
+    }
+}
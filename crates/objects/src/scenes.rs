@@ -1,25 +1,51 @@
 use ahash::AHashMap;
-use bevy::prelude::*;
+use bevy::{gltf::Gltf, prelude::*, scene::SceneInstanceReady};
 use de_core::state::AppState;
 use de_types::objects::ObjectType;
 use enum_iterator::Sequence;
 use iyes_progress::prelude::*;
 
 use crate::{
-    collection::{AssetCollection, AssetCollectionLoader},
+    collection::{AssetCollection, AssetCollectionLoader, Readiness},
     names::FileStem,
+    SolidObjects,
 };
 
+/// Detail level `n` (for `n >= 1`) of a model is used at camera distances
+/// larger or equal to `n` times this distance (in meters).
+const LOD_DISTANCE: f32 = 60.;
+/// Objects are displayed as imposters at camera distances larger or equal to
+/// this distance (in meters).
+const IMPOSTER_DISTANCE: f32 = 180.;
+/// Detail level is changed only once the camera distance crosses a threshold
+/// by this fraction of the threshold. This prevents frequent swapping of
+/// scenes of objects close to a threshold.
+const LOD_HYSTERESIS: f32 = 0.1;
+/// Name of imposter nodes. It makes the imposters tinted with the color of
+/// the owning player.
+const IMPOSTER_NAME: &str = "PlayerColorImposter";
+
 pub(crate) struct ScenesPlugin;
 
 impl Plugin for ScenesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::AppLoading), setup)
+        app.add_event::<ModelLoadedEvent>()
+            .add_systems(OnEnter(AppState::AppLoading), setup)
+            .add_systems(OnExit(AppState::AppLoading), setup_lods)
             .add_systems(
                 Update,
                 check_status
                     .track_progress()
                     .run_if(in_state(AppState::AppLoading)),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    stream_lods
+                        .run_if(resource_exists::<Models>)
+                        .run_if(resource_exists::<LodScenes>),
+                    find_sub_nodes.run_if(in_state(AppState::InGame)),
+                ),
             );
     }
 }
@@ -49,6 +75,123 @@ impl AssetCollectionLoader for Scenes {
     }
 }
 
+/// glTF files of all solid object models. Each file contains the default
+/// scene followed by optional scenes with gradually decreasing level of
+/// detail.
+#[derive(Resource)]
+struct Models(AHashMap<ObjectType, Handle<Gltf>>);
+
+impl AssetCollection for Models {
+    type Key = ObjectType;
+    type Asset = Gltf;
+
+    fn get(&self, key: Self::Key) -> &Handle<Self::Asset> {
+        self.0.get(&key).unwrap()
+    }
+}
+
+impl AssetCollectionLoader for Models {
+    const DIRECTORY: &'static str = "models";
+    const SUFFIX: &'static str = "glb";
+
+    fn new(map: AHashMap<Self::Key, Handle<Self::Asset>>) -> Self {
+        Self(map)
+    }
+
+    fn label() -> Option<String> {
+        None
+    }
+}
+
+/// Scenes of solid objects with decreasing level of detail (LOD).
+///
+/// Level 0 is the default scene of the object, i.e. the scene from
+/// [`Scenes`]. It is followed by the additional scenes of the object glTF
+/// file (if there are any). The last level is an imposter: a single box
+/// enclosing the object, which is very cheap to draw.
+///
+/// Object models are loaded in the background during the game. Until the
+/// model of an object is loaded, the imposter is its only level and serves as
+/// a placeholder. [`ModelLoadedEvent`] is sent once all levels are available.
+#[derive(Resource)]
+pub struct LodScenes {
+    levels: AHashMap<ObjectType, Vec<Handle<Scene>>>,
+    readiness: Readiness<ObjectType>,
+}
+
+impl LodScenes {
+    /// Returns the scene of a detail level of an object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object has no such level.
+    pub fn get(&self, object_type: ObjectType, level: usize) -> &Handle<Scene> {
+        &self.levels.get(&object_type).unwrap()[level]
+    }
+
+    /// Returns true if the model of the object is loaded, i.e. if not only
+    /// the placeholder is available.
+    pub fn is_ready(&self, object_type: ObjectType) -> bool {
+        self.readiness.is_ready(object_type)
+    }
+
+    /// Returns the detail level which should be used for an object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - type of the object.
+    ///
+    /// * `distance` - distance between the object and the camera.
+    ///
+    /// * `current` - currently used detail level of the object.
+    pub fn level(&self, object_type: ObjectType, distance: f32, current: usize) -> usize {
+        select_level(
+            self.levels.get(&object_type).unwrap().len(),
+            distance,
+            current,
+        )
+    }
+}
+
+/// This event is sent once the model of an object type is loaded and all its
+/// detail levels become available in [`LodScenes`].
+#[derive(Event)]
+pub struct ModelLoadedEvent(ObjectType);
+
+impl ModelLoadedEvent {
+    pub fn object_type(&self) -> ObjectType {
+        self.0
+    }
+}
+
+/// Returns the minimum camera distance of a detail level.
+///
+/// # Arguments
+///
+/// * `level` - the detail level. It must be greater than 0.
+///
+/// * `levels` - total number of detail levels, including the imposter.
+fn level_distance(level: usize, levels: usize) -> f32 {
+    if level + 1 == levels {
+        IMPOSTER_DISTANCE
+    } else {
+        (LOD_DISTANCE * level as f32).min(IMPOSTER_DISTANCE)
+    }
+}
+
+fn select_level(levels: usize, distance: f32, current: usize) -> usize {
+    let level_at = |distance: f32| {
+        (1..levels)
+            .take_while(|&level| level_distance(level, levels) <= distance)
+            .last()
+            .unwrap_or(0)
+    };
+
+    let coarsest = level_at(distance * (1. + LOD_HYSTERESIS));
+    let finest = level_at(distance * (1. - LOD_HYSTERESIS));
+    current.clamp(finest, coarsest)
+}
+
 #[derive(Sequence, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SceneType {
     Solid(ObjectType),
@@ -64,10 +207,184 @@ impl FileStem for SceneType {
     }
 }
 
+/// Named nodes of object models which are transformed independently of the
+/// rest of the model by the game logic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubNode {
+    /// A node rotated around its vertical axis to aim a cannon.
+    Turret,
+    /// A node rotated around its horizontal axis to elevate a cannon. It is
+    /// expected to be a descendant of [`Self::Turret`].
+    Barrel,
+}
+
+impl SubNode {
+    /// Name of the node in glTF models.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Turret => "Turret",
+            Self::Barrel => "Barrel",
+        }
+    }
+}
+
+/// Entities of named sub-nodes of a spawned model. The component is inserted
+/// to an entity once its scene is spawned and only if the model contains at
+/// least one [`SubNode`].
+#[derive(Component)]
+pub struct SubNodes {
+    turret: Option<Entity>,
+    barrel: Option<Entity>,
+}
+
+impl SubNodes {
+    pub fn get(&self, node: SubNode) -> Option<Entity> {
+        match node {
+            SubNode::Turret => self.turret,
+            SubNode::Barrel => self.barrel,
+        }
+    }
+}
+
 fn setup(mut commands: Commands, server: Res<AssetServer>) {
     commands.insert_resource(Scenes::init(server.as_ref()));
+    commands.insert_resource(Models::init(server.as_ref()));
 }
 
+/// Only scenes which are not object models are loaded during the application
+/// loading, object models are streamed (see [`LodScenes`]).
 fn check_status(server: Res<AssetServer>, scenes: Res<Scenes>) -> Progress {
-    scenes.progress(server.as_ref())
+    scenes.is_ready(server.as_ref(), SceneType::Pole).into()
+}
+
+fn setup_lods(
+    mut commands: Commands,
+    solids: SolidObjects,
+    mut scene_assets: ResMut<Assets<Scene>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = materials.add(StandardMaterial {
+        base_color: Color::rgb(0.8, 0.8, 0.8),
+        perceptual_roughness: 1.,
+        ..default()
+    });
+
+    let mut lods = AHashMap::new();
+    for object_type in enum_iterator::all::<ObjectType>() {
+        let aabb = solids.get(object_type).collider().aabb();
+        let mut world = World::new();
+        world.spawn((
+            PbrBundle {
+                mesh: meshes.add(Cuboid::from_size(Vec3::from(aabb.extents()))),
+                material: material.clone(),
+                transform: Transform::from_translation(Vec3::from(aabb.center())),
+                ..default()
+            },
+            Name::new(IMPOSTER_NAME),
+        ));
+        lods.insert(object_type, vec![scene_assets.add(Scene::new(world))]);
+    }
+
+    commands.insert_resource(LodScenes {
+        levels: lods,
+        readiness: Readiness::new(),
+    });
+}
+
+/// Completes detail levels of objects whose models finished loading.
+fn stream_lods(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    scenes: Res<Scenes>,
+    models: Res<Models>,
+    gltfs: Res<Assets<Gltf>>,
+    mut lods: ResMut<LodScenes>,
+    mut events: EventWriter<ModelLoadedEvent>,
+) {
+    let loaded: Vec<ObjectType> = lods
+        .readiness
+        .pending()
+        .filter(|&object_type| {
+            scenes.is_ready(server.as_ref(), SceneType::Solid(object_type))
+                && models.is_ready(server.as_ref(), object_type)
+        })
+        .collect();
+
+    for object_type in loaded {
+        let mut levels = vec![scenes.get(SceneType::Solid(object_type)).clone()];
+        let gltf = gltfs.get(models.get(object_type)).unwrap();
+        levels.extend(gltf.scenes.iter().skip(1).cloned());
+
+        let placeholder = lods.levels.get_mut(&object_type).unwrap();
+        levels.push(placeholder.pop().unwrap());
+        *placeholder = levels;
+
+        lods.readiness.mark(object_type);
+        info!("Model of {object_type} loaded.");
+        events.send(ModelLoadedEvent(object_type));
+    }
+
+    if lods.readiness.is_complete() {
+        commands.remove_resource::<Models>();
+    }
+}
+
+fn find_sub_nodes(
+    mut commands: Commands,
+    mut events: EventReader<SceneInstanceReady>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+) {
+    for event in events.read() {
+        let mut sub_nodes = SubNodes {
+            turret: None,
+            barrel: None,
+        };
+
+        for entity in children.iter_descendants(event.parent) {
+            let Ok(name) = names.get(entity) else {
+                continue;
+            };
+
+            if name.as_str() == SubNode::Turret.name() {
+                sub_nodes.turret = Some(entity);
+            } else if name.as_str() == SubNode::Barrel.name() {
+                sub_nodes.barrel = Some(entity);
+            }
+        }
+
+        // The scene might be re-spawned, e.g. due to a detail level change.
+        if sub_nodes.turret.is_some() || sub_nodes.barrel.is_some() {
+            commands.entity(event.parent).insert(sub_nodes);
+        } else {
+            commands.entity(event.parent).remove::<SubNodes>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_level() {
+        // Default scene and imposter only.
+        assert_eq!(select_level(2, 10., 0), 0);
+        assert_eq!(select_level(2, 190., 0), 0);
+        assert_eq!(select_level(2, 200., 0), 1);
+        assert_eq!(select_level(2, 170., 1), 1);
+        assert_eq!(select_level(2, 160., 1), 0);
+
+        // Two additional detail levels.
+        assert_eq!(select_level(4, 10., 0), 0);
+        assert_eq!(select_level(4, 70., 0), 1);
+        assert_eq!(select_level(4, 130., 1), 1);
+        assert_eq!(select_level(4, 140., 1), 2);
+        assert_eq!(select_level(4, 140., 0), 2);
+        assert_eq!(select_level(4, 300., 0), 3);
+        assert_eq!(select_level(4, 115., 2), 2);
+        assert_eq!(select_level(4, 100., 2), 1);
+        assert_eq!(select_level(4, 10., 3), 0);
+    }
 }
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+/// Personal energy shield of an object. The shield absorbs damage before
+/// object health and regenerates once the object is not damaged for a while.
+#[derive(Clone)]
+pub struct ShieldGenerator {
+    capacity: f32,
+    regeneration: f32,
+    delay: Duration,
+}
+
+impl ShieldGenerator {
+    /// # Arguments
+    ///
+    /// * `capacity` - maximum amount of damage absorbed by the shield.
+    ///
+    /// * `regeneration` - shield regeneration per second.
+    ///
+    /// * `delay` - time since the last damage after which the shield starts
+    ///   to regenerate.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `capacity` is not a positive finite number or if
+    /// `regeneration` is not a non-negative finite number.
+    pub fn new(capacity: f32, regeneration: f32, delay: Duration) -> Self {
+        debug_assert!(capacity.is_finite() && capacity > 0.);
+        debug_assert!(regeneration.is_finite() && regeneration >= 0.);
+        Self {
+            capacity,
+            regeneration,
+            delay,
+        }
+    }
+
+    /// Maximum amount of damage the fully charged shield absorbs.
+    pub fn capacity(&self) -> f32 {
+        self.capacity
+    }
+
+    /// Shield regeneration per second.
+    pub fn regeneration(&self) -> f32 {
+        self.regeneration
+    }
+
+    /// Time since the last damage after which the shield starts to
+    /// regenerate.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+}
+
+impl TryFrom<ShieldGeneratorSerde> for ShieldGenerator {
+    type Error = anyhow::Error;
+
+    fn try_from(info: ShieldGeneratorSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.capacity.is_finite() && info.capacity > 0.,
+            "Shield capacity must be a positive number, got: {}",
+            info.capacity
+        );
+        ensure!(
+            info.regeneration.is_finite() && info.regeneration >= 0.,
+            "Shield regeneration must be a non-negative number, got: {}",
+            info.regeneration
+        );
+        ensure!(
+            info.delay_sec.is_finite() && info.delay_sec >= 0.,
+            "Shield regeneration delay must be a non-negative number, got: {}",
+            info.delay_sec
+        );
+
+        Ok(Self {
+            capacity: info.capacity,
+            regeneration: info.regeneration,
+            delay: Duration::from_secs_f32(info.delay_sec),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ShieldGeneratorSerde {
+    capacity: f32,
+    regeneration: f32,
+    delay_sec: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let parse = |json: &str| {
+            ShieldGenerator::try_from(serde_json::from_str::<ShieldGeneratorSerde>(json).unwrap())
+        };
+
+        let shield = parse(r#"{"capacity": 20, "regeneration": 2, "delay_sec": 5}"#).unwrap();
+        assert_eq!(shield.capacity(), 20.);
+        assert_eq!(shield.regeneration(), 2.);
+        assert_eq!(shield.delay(), Duration::from_secs(5));
+
+        assert!(parse(r#"{"capacity": 0, "regeneration": 2, "delay_sec": 5}"#).is_err());
+        assert!(parse(r#"{"capacity": 20, "regeneration": -1, "delay_sec": 5}"#).is_err());
+        assert!(parse(r#"{"capacity": 20, "regeneration": 2, "delay_sec": -5}"#).is_err());
+    }
+}
@@ -13,16 +13,30 @@ use iyes_progress::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    armor::ArmorClass,
+    artillery::{ArtilleryCannon, ArtilleryCannonSerde},
     cannon::{LaserCannon, LaserCannonSerde},
+    cloak::{CloakingDevice, CloakingDeviceSerde, Detector, DetectorSerde},
     collection::AssetCollectionLoader,
     collider::{ColliderSerde, ObjectCollider},
+    construction::{Construction, ConstructionSerde},
+    defense::{PointDefense, PointDefenseSerde},
     factory::{Factory, FactorySerde},
     flight::{Flight, FlightSerde},
+    garrison::{Garrison, GarrisonSerde},
+    harvest::{Deposit, DepositSerde, Harvester, HarvesterSerde},
     ichnography::{FootprintSerde, Ichnography},
+    mobility::{Mobility, MobilitySerde},
+    regeneration::{Regeneration, RegenerationSerde},
+    repair::{RepairBeam, RepairBeamSerde},
+    shield::{ShieldGenerator, ShieldGeneratorSerde},
+    support::{Radar, RadarSerde, ShieldDome, ShieldDomeSerde},
+    vision::{Vision, VisionSerde},
     AssetCollection,
 };
 
-const OBJECT_EXTENSION: [&str; 1] = ["obj.json"];
+/// File name extension of solid object files.
+pub const OBJECT_EXTENSION: [&str; 1] = ["obj.json"];
 
 pub(crate) struct SolidsPlugin;
 
@@ -71,8 +85,25 @@ pub struct SolidObject {
     ichnography: Ichnography,
     collider: ObjectCollider,
     cannon: Option<LaserCannon>,
+    artillery: Option<ArtilleryCannon>,
+    point_defense: Option<PointDefense>,
+    cloak: Option<CloakingDevice>,
+    detector: Option<Detector>,
+    garrison: Option<Garrison>,
+    shield_dome: Option<ShieldDome>,
+    radar: Option<Radar>,
     flight: Option<Flight>,
+    mobility: Option<Mobility>,
+    vision: Option<Vision>,
+    regeneration: Option<Regeneration>,
+    shield: Option<ShieldGenerator>,
+    repair: Option<RepairBeam>,
     factory: Option<Factory>,
+    harvester: Option<Harvester>,
+    deposit: Option<Deposit>,
+    depot: bool,
+    armor: ArmorClass,
+    construction: Option<Construction>,
 }
 
 impl SolidObject {
@@ -80,18 +111,125 @@ impl SolidObject {
         self.cannon.as_ref()
     }
 
+    /// Artillery configuration. It is None for objects without an artillery
+    /// cannon.
+    pub fn artillery(&self) -> Option<&ArtilleryCannon> {
+        self.artillery.as_ref()
+    }
+
+    /// Point defense configuration. It is None for objects which cannot
+    /// intercept projectiles.
+    pub fn point_defense(&self) -> Option<&PointDefense> {
+        self.point_defense.as_ref()
+    }
+
+    /// Cloaking device configuration. It is None for objects which cannot
+    /// cloak.
+    pub fn cloak(&self) -> Option<&CloakingDevice> {
+        self.cloak.as_ref()
+    }
+
+    /// Detector configuration. It is None for objects which cannot reveal
+    /// cloaked objects.
+    pub fn detector(&self) -> Option<&Detector> {
+        self.detector.as_ref()
+    }
+
+    /// Garrison configuration. It is None for objects which cannot carry
+    /// other units.
+    pub fn garrison(&self) -> Option<&Garrison> {
+        self.garrison.as_ref()
+    }
+
+    /// Shield dome configuration. It is None for objects which do not protect
+    /// nearby units.
+    pub fn shield_dome(&self) -> Option<&ShieldDome> {
+        self.shield_dome.as_ref()
+    }
+
+    /// Radar configuration. It is None for objects without a radar.
+    pub fn radar(&self) -> Option<&Radar> {
+        self.radar.as_ref()
+    }
+
     /// Flight configuration configuration. It is None for objects which cannot
     /// fly.
     pub fn flight(&self) -> Option<&Flight> {
         self.flight.as_ref()
     }
 
+    /// Movement configuration. It is None for objects which move with the
+    /// default parameters or which cannot move at all.
+    pub fn mobility(&self) -> Option<&Mobility> {
+        self.mobility.as_ref()
+    }
+
+    /// Vision configuration. It is None for objects with the default vision.
+    pub fn vision(&self) -> Option<&Vision> {
+        self.vision.as_ref()
+    }
+
+    /// Passive health regeneration or degradation. It is None for objects
+    /// whose health changes only due to game events.
+    pub fn regeneration(&self) -> Option<&Regeneration> {
+        self.regeneration.as_ref()
+    }
+
+    /// Personal shield configuration. It is None for objects without a
+    /// shield.
+    pub fn shield(&self) -> Option<&ShieldGenerator> {
+        self.shield.as_ref()
+    }
+
+    /// Repair beam configuration. It is None for objects which cannot repair
+    /// other objects.
+    pub fn repair(&self) -> Option<&RepairBeam> {
+        self.repair.as_ref()
+    }
+
     /// Returns None if the object has no manufacturing capabilities, otherwise
     /// it returns info about object manufacturing capabilities.
     pub fn factory(&self) -> Option<&Factory> {
         self.factory.as_ref()
     }
 
+    /// Resource gathering configuration. It is None for objects which cannot
+    /// harvest.
+    pub fn harvester(&self) -> Option<&Harvester> {
+        self.harvester.as_ref()
+    }
+
+    /// Initial state of a resource deposit. It is None for objects which
+    /// cannot be harvested.
+    pub fn deposit(&self) -> Option<&Deposit> {
+        self.deposit.as_ref()
+    }
+
+    /// Returns true if harvesters can unload gathered resources at the
+    /// object.
+    pub fn depot(&self) -> bool {
+        self.depot
+    }
+
+    /// Armor class of the object. It determines the damage dealt to the
+    /// object by different weapons.
+    pub fn armor(&self) -> ArmorClass {
+        self.armor
+    }
+
+    /// Cost and time of placing the building by a player. It is None for
+    /// objects which cannot be built.
+    pub fn construction(&self) -> Option<&Construction> {
+        self.construction.as_ref()
+    }
+
+    /// Energy in joules per second consumed by the area effects (shield dome,
+    /// radar) of the object while they are active.
+    pub fn power_drain(&self) -> f64 {
+        self.shield_dome.as_ref().map_or(0., |dome| dome.drain())
+            + self.radar.as_ref().map_or(0., |radar| radar.drain())
+    }
+
     pub fn ichnography(&self) -> &Ichnography {
         &self.ichnography
     }
@@ -99,6 +237,14 @@ impl SolidObject {
     pub fn collider(&self) -> &ObjectCollider {
         &self.collider
     }
+
+    /// Parses a solid object from the content of an object file
+    /// (`*.obj.json`).
+    pub fn from_json(bytes: &[u8]) -> anyhow::Result<Self> {
+        let solid_serde: SolidObjectSerde =
+            serde_json::from_slice(bytes).context("Failed to parse object JSON")?;
+        Self::try_from(solid_serde)
+    }
 }
 
 impl TryFrom<SolidObjectSerde> for SolidObject {
@@ -109,8 +255,46 @@ impl TryFrom<SolidObjectSerde> for SolidObject {
             ichnography: Ichnography::try_from(solid_serde.footprint)?,
             collider: ObjectCollider::try_from(solid_serde.shape)?,
             cannon: solid_serde.cannon.map(LaserCannon::try_from).transpose()?,
+            artillery: solid_serde
+                .artillery
+                .map(ArtilleryCannon::try_from)
+                .transpose()?,
+            point_defense: solid_serde
+                .point_defense
+                .map(PointDefense::try_from)
+                .transpose()?,
+            cloak: solid_serde
+                .cloak
+                .map(CloakingDevice::try_from)
+                .transpose()?,
+            detector: solid_serde.detector.map(Detector::try_from).transpose()?,
+            garrison: solid_serde.garrison.map(Garrison::try_from).transpose()?,
+            shield_dome: solid_serde
+                .shield_dome
+                .map(ShieldDome::try_from)
+                .transpose()?,
+            radar: solid_serde.radar.map(Radar::try_from).transpose()?,
             flight: solid_serde.flight.map(Flight::try_from).transpose()?,
+            mobility: solid_serde.mobility.map(Mobility::try_from).transpose()?,
+            vision: solid_serde.vision.map(Vision::try_from).transpose()?,
+            regeneration: solid_serde
+                .regeneration
+                .map(Regeneration::try_from)
+                .transpose()?,
+            shield: solid_serde
+                .shield
+                .map(ShieldGenerator::try_from)
+                .transpose()?,
+            repair: solid_serde.repair.map(RepairBeam::try_from).transpose()?,
             factory: solid_serde.factory.map(Factory::try_from).transpose()?,
+            harvester: solid_serde.harvester.map(Harvester::try_from).transpose()?,
+            deposit: solid_serde.deposit.map(Deposit::try_from).transpose()?,
+            depot: solid_serde.depot,
+            armor: solid_serde.armor,
+            construction: solid_serde
+                .construction
+                .map(Construction::try_from)
+                .transpose()?,
         })
     }
 }
@@ -120,8 +304,27 @@ struct SolidObjectSerde {
     footprint: FootprintSerde,
     shape: ColliderSerde,
     cannon: Option<LaserCannonSerde>,
+    artillery: Option<ArtilleryCannonSerde>,
+    point_defense: Option<PointDefenseSerde>,
+    cloak: Option<CloakingDeviceSerde>,
+    detector: Option<DetectorSerde>,
+    garrison: Option<GarrisonSerde>,
+    shield_dome: Option<ShieldDomeSerde>,
+    radar: Option<RadarSerde>,
     flight: Option<FlightSerde>,
+    mobility: Option<MobilitySerde>,
+    vision: Option<VisionSerde>,
+    regeneration: Option<RegenerationSerde>,
+    shield: Option<ShieldGeneratorSerde>,
+    repair: Option<RepairBeamSerde>,
     factory: Option<FactorySerde>,
+    harvester: Option<HarvesterSerde>,
+    deposit: Option<DepositSerde>,
+    #[serde(default)]
+    depot: bool,
+    #[serde(default)]
+    armor: ArmorClass,
+    construction: Option<ConstructionSerde>,
 }
 
 struct SolidObjectLoader;
@@ -140,9 +343,7 @@ impl AssetLoader for SolidObjectLoader {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
-            let solid_serde: SolidObjectSerde =
-                serde_json::from_slice(&bytes).context("Failed to parse object JSON")?;
-            SolidObject::try_from(solid_serde)
+            SolidObject::from_json(&bytes)
         })
     }
 
@@ -0,0 +1,143 @@
+use anyhow::ensure;
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Shield dome projected by an object. It reduces damage dealt to allied
+/// units inside the dome.
+#[derive(Component, Clone)]
+pub struct ShieldDome {
+    radius: f32,
+    reduction: f32,
+    drain: f64,
+}
+
+impl ShieldDome {
+    /// Radius of the dome in meters.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Fraction of the damage absorbed by the dome.
+    pub fn reduction(&self) -> f32 {
+        self.reduction
+    }
+
+    /// Energy in joules per second consumed while the dome is projected.
+    pub fn drain(&self) -> f64 {
+        self.drain
+    }
+}
+
+impl TryFrom<ShieldDomeSerde> for ShieldDome {
+    type Error = anyhow::Error;
+
+    fn try_from(info: ShieldDomeSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.radius.is_finite() && info.radius > 0.,
+            "Shield dome radius must be a positive number, got: {}",
+            info.radius
+        );
+        ensure!(
+            info.reduction > 0. && info.reduction <= 1.,
+            "Shield dome damage reduction must be between 0 (exclusive) and 1 (inclusive), \
+             got: {}",
+            info.reduction
+        );
+        ensure!(
+            info.drain.is_finite() && info.drain >= 0.,
+            "Shield dome drain must be a non-negative number, got: {}",
+            info.drain
+        );
+
+        Ok(Self {
+            radius: info.radius,
+            reduction: info.reduction,
+            drain: info.drain,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ShieldDomeSerde {
+    radius: f32,
+    reduction: f32,
+    drain: f64,
+}
+
+/// Radar of an object. It reveals hidden (e.g. cloaked) enemy objects in a
+/// large region around the object.
+#[derive(Component, Clone)]
+pub struct Radar {
+    range: f32,
+    drain: f64,
+}
+
+impl Radar {
+    /// Maximum distance in meters of revealed objects.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Energy in joules per second consumed while the radar is operating.
+    pub fn drain(&self) -> f64 {
+        self.drain
+    }
+}
+
+impl TryFrom<RadarSerde> for Radar {
+    type Error = anyhow::Error;
+
+    fn try_from(info: RadarSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            info.range.is_finite() && info.range > 0.,
+            "Radar range must be a positive number, got: {}",
+            info.range
+        );
+        ensure!(
+            info.drain.is_finite() && info.drain >= 0.,
+            "Radar drain must be a non-negative number, got: {}",
+            info.drain
+        );
+
+        Ok(Self {
+            range: info.range,
+            drain: info.drain,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RadarSerde {
+    range: f32,
+    drain: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let shield = |radius, reduction| ShieldDomeSerde {
+            radius,
+            reduction,
+            drain: 1000.,
+        };
+        assert!(ShieldDome::try_from(shield(30., 0.5)).is_ok());
+        assert!(ShieldDome::try_from(shield(30., 1.)).is_ok());
+        assert!(ShieldDome::try_from(shield(30., 0.)).is_err());
+        assert!(ShieldDome::try_from(shield(30., 1.5)).is_err());
+        assert!(ShieldDome::try_from(shield(-1., 0.5)).is_err());
+
+        assert!(Radar::try_from(RadarSerde {
+            range: 150.,
+            drain: 1000.
+        })
+        .is_ok());
+        assert!(Radar::try_from(RadarSerde {
+            range: 150.,
+            drain: f64::NAN
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::ensure;
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Sight range (in meters) of active objects without a vision configuration.
+const DEFAULT_SIGHT_RANGE: f32 = 60.;
+
+/// Vision capabilities of an object. This is the input of the fog-of-war
+/// computation.
+pub struct Vision {
+    sight_range: f32,
+}
+
+impl Vision {
+    /// Returns distance in meters up to which the object reveals its
+    /// surroundings.
+    pub fn sight_range(&self) -> f32 {
+        self.sight_range
+    }
+}
+
+/// Distance in meters up to which an active object reveals its surroundings
+/// to its owner.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SightRange(f32);
+
+impl SightRange {
+    pub fn range(&self) -> f32 {
+        self.0
+    }
+}
+
+impl From<Option<&Vision>> for SightRange {
+    fn from(vision: Option<&Vision>) -> Self {
+        Self(vision.map_or(DEFAULT_SIGHT_RANGE, |vision| vision.sight_range()))
+    }
+}
+
+impl TryFrom<VisionSerde> for Vision {
+    type Error = anyhow::Error;
+
+    fn try_from(vision_serde: VisionSerde) -> Result<Self, Self::Error> {
+        ensure!(
+            vision_serde.sight_range.is_finite() && vision_serde.sight_range > 0.,
+            "Sight range must be a positive number, got: {}",
+            vision_serde.sight_range
+        );
+
+        Ok(Self {
+            sight_range: vision_serde.sight_range,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct VisionSerde {
+    sight_range: f32,
+}
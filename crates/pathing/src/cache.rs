@@ -0,0 +1,273 @@
+//! This module implements short-lived memoization of path searches.
+//!
+//! Bursts of nearly identical path queries are common, for example when a
+//! group of units is ordered to the same point. Such queries share a single
+//! search: the found path is reused for all queries starting in the same
+//! triangle near the original start point and only its first segment is
+//! adjusted, provided that the next waypoint is visible from the new start.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use ahash::AHashMap;
+use de_types::path::Path;
+use glam::Vec2;
+
+use crate::PathQueryProps;
+
+/// Cached paths are reused only within this time since the search started.
+const CACHE_TTL: Duration = Duration::from_secs(2);
+/// Cached paths are reused only for queries starting within this distance
+/// from the start of the cached path.
+const MAX_SOURCE_OFFSET: f32 = 10.;
+/// Once the cache grows beyond this number of entries, all entries are
+/// dropped.
+const MAX_ENTRIES: usize = 256;
+
+/// Identification of a group of path queries which share a single search.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    source_triangle: u32,
+    target_triangle: u32,
+    target: [u32; 2],
+    distance: u32,
+    max_distance: u32,
+}
+
+impl CacheKey {
+    /// # Arguments
+    ///
+    /// * `source_triangle` - ID of the triangle containing the path start.
+    ///
+    /// * `target_triangle` - ID of the triangle containing the path target.
+    ///
+    /// * `target` - exact path target. Paths to different points in the same
+    ///   triangle are not shared because the triangles might be very large.
+    ///
+    /// * `properties` - properties of the path query.
+    pub(crate) fn new(
+        source_triangle: u32,
+        target_triangle: u32,
+        target: Vec2,
+        properties: PathQueryProps,
+    ) -> Self {
+        Self {
+            source_triangle,
+            target_triangle,
+            target: [target.x.to_bits(), target.y.to_bits()],
+            distance: properties.distance().to_bits(),
+            max_distance: properties.max_distance().to_bits(),
+        }
+    }
+}
+
+/// Thread safe memoization cache of path searches.
+#[derive(Default)]
+pub(crate) struct PathCache {
+    entries: Mutex<AHashMap<CacheKey, Arc<CacheEntry>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl PathCache {
+    /// Returns a cached path adjusted to start at `source` or searches for a
+    /// new path with `search` and caches the result.
+    ///
+    /// A search already in progress for an identical query (e.g. in another
+    /// thread) is not waited for, the path is searched for independently
+    /// instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - identification of the query.
+    ///
+    /// * `source` - start of the path.
+    ///
+    /// * `search` - searches for the path.
+    ///
+    /// * `visible` - returns true if there is a straight line of sight
+    ///   between two points. It is used to verify that the first segment of
+    ///   an adjusted cached path does not cross any obstacle.
+    pub(crate) fn get_or_search<S, V>(
+        &self,
+        key: CacheKey,
+        source: Vec2,
+        search: S,
+        visible: V,
+    ) -> Option<Path>
+    where
+        S: FnOnce() -> Option<Path>,
+        V: FnOnce(Vec2, Vec2) -> bool,
+    {
+        let now = Instant::now();
+
+        let (entry, created) = {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.get(&key) {
+                Some(entry) if entry.reusable(source, now) => (Arc::clone(entry), false),
+                _ => {
+                    if entries.len() >= MAX_ENTRIES {
+                        entries.retain(|_, entry| now - entry.created < CACHE_TTL);
+                        if entries.len() >= MAX_ENTRIES {
+                            entries.clear();
+                        }
+                    }
+                    let entry = Arc::new(CacheEntry::new(source, now));
+                    entries.insert(key, Arc::clone(&entry));
+                    (entry, true)
+                }
+            }
+        };
+
+        if !created {
+            let reused = entry
+                .path
+                .get()
+                .and_then(|path| reuse(path.as_ref(), source, visible));
+            if let Some(path) = reused {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return path;
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let path = search();
+        if created {
+            // Only the creator of the entry stores the path, thus the cell
+            // is still empty.
+            let _ = entry.path.set(path.clone());
+        }
+        path
+    }
+
+    /// Returns the number of queries served from the cache.
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of queries which required a new search.
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+struct CacheEntry {
+    /// Start of the cached path.
+    source: Vec2,
+    created: Instant,
+    /// The found path. The cell is empty while the search is in progress.
+    /// Queries made in the meantime do not wait for the search.
+    path: OnceLock<Option<Path>>,
+}
+
+impl CacheEntry {
+    fn new(source: Vec2, created: Instant) -> Self {
+        Self {
+            source,
+            created,
+            path: OnceLock::new(),
+        }
+    }
+
+    fn reusable(&self, source: Vec2, now: Instant) -> bool {
+        now - self.created < CACHE_TTL && self.source.distance(source) <= MAX_SOURCE_OFFSET
+    }
+}
+
+/// Returns the result of a cached search adjusted to a path starting at
+/// `source` or None if the cached path cannot be reused because its next
+/// waypoint is not visible from `source`.
+fn reuse<V>(path: Option<&Path>, source: Vec2, visible: V) -> Option<Option<Path>>
+where
+    V: FnOnce(Vec2, Vec2) -> bool,
+{
+    let Some(path) = path else {
+        // No path exists from anywhere in the source triangle.
+        return Some(None);
+    };
+
+    let mut waypoints = path.waypoints().to_vec();
+    let last = waypoints.len() - 1;
+    let length = if last == 0 {
+        0.
+    } else {
+        let next = waypoints[last - 1];
+        if !visible(source, next) {
+            return None;
+        }
+        path.length() - waypoints[last].distance(next) + source.distance(next)
+    };
+    waypoints[last] = source;
+    Some(Some(Path::new(length, waypoints)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let cache = PathCache::default();
+        let props = PathQueryProps::exact();
+        let key = CacheKey::new(1, 2, Vec2::new(20., 0.), props);
+        let path = || {
+            Some(Path::new(
+                30.,
+                vec![Vec2::new(20., 0.), Vec2::new(10., 0.), Vec2::new(0., 0.)],
+            ))
+        };
+
+        let first = cache
+            .get_or_search(key, Vec2::ZERO, path, |_, _| unreachable!())
+            .unwrap();
+        assert_eq!(first.length(), 30.);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+        let second = cache
+            .get_or_search(key, Vec2::new(7., 4.), || unreachable!(), |_, _| true)
+            .unwrap();
+        assert_eq!(second.length(), 25.);
+        assert_eq!(
+            second.waypoints(),
+            &[Vec2::new(20., 0.), Vec2::new(10., 0.), Vec2::new(7., 4.)]
+        );
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // The next waypoint is not visible.
+        let third = cache.get_or_search(
+            key,
+            Vec2::new(5., 5.),
+            || None,
+            |_, next| {
+                assert_eq!(next, Vec2::new(10., 0.));
+                false
+            },
+        );
+        assert!(third.is_none());
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // The original path is still cached.
+        let fourth = cache
+            .get_or_search(key, Vec2::new(5., 0.), || unreachable!(), |_, _| true)
+            .unwrap();
+        assert_eq!(fourth.length(), 25.);
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+
+        // Too far from the start of the cached path.
+        assert!(cache
+            .get_or_search(key, Vec2::new(-20., 0.), || None, |_, _| true)
+            .is_none());
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+
+        let other = CacheKey::new(1, 2, Vec2::new(20., 1.), props);
+        assert!(cache
+            .get_or_search(other, Vec2::ZERO, || None, |_, _| true)
+            .is_none());
+        assert_eq!((cache.hits(), cache.misses()), (2, 4));
+    }
+}
@@ -3,13 +3,18 @@ use de_objects::Ichnography;
 use de_types::projection::ToFlat;
 use glam::EulerRot;
 use parry2d::{
+    bounding_volume::Aabb,
     math::{Isometry, Point},
     na, query,
     query::PointQuery,
-    shape::ConvexPolygon,
+    shape::{ConvexPolygon, Cuboid},
 };
 use rstar::{Envelope, PointDistance, RTree, RTreeObject, SelectionFunction, AABB as RstarAABB};
 
+/// Maximum distance from an exclusion area boundary within which points are
+/// not considered to be inside the area.
+const BOUNDARY_TOLERANCE: f32 = 0.01;
+
 /// Non accessible area on the map.
 ///
 /// An area is considered inaccessible if a centroid of a moving object cannot
@@ -78,8 +83,56 @@ impl ExclusionArea {
         Self::new(ConvexPolygon::from_convex_hull(&points).unwrap())
     }
 
+    /// Returns true if the area intersects a given AABB.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        query::intersection_test(
+            &Isometry::identity(),
+            &self.polygon,
+            &Isometry::translation(aabb.center().x, aabb.center().y),
+            &Cuboid::new(aabb.half_extents()),
+        )
+        .unwrap()
+    }
+
+    /// Returns true if the line segment between `a` and `b` passes through
+    /// the interior of the area. Segments only touching the area boundary,
+    /// for example path segments leading around a corner of the area, do not
+    /// intersect it.
+    pub(crate) fn intersects_segment(&self, a: Point<f32>, b: Point<f32>) -> bool {
+        let direction = b - a;
+        let mut entry = 0f32;
+        let mut exit = 1f32;
+
+        // Cyrus-Beck clipping with the polygon shrunk by `BOUNDARY_TOLERANCE`.
+        let points = self.points();
+        for (i, &start) in points.iter().enumerate() {
+            let edge = points[(i + 1) % points.len()] - start;
+            // Points are in counter-clockwise order, thus this is an outward
+            // normal.
+            let normal = na::Vector2::new(edge.y, -edge.x).normalize();
+            let distance = normal.dot(&(a - start)) + BOUNDARY_TOLERANCE;
+            let speed = normal.dot(&direction);
+
+            if speed == 0. {
+                if distance >= 0. {
+                    return false;
+                }
+            } else if speed < 0. {
+                entry = entry.max(-distance / speed);
+            } else {
+                exit = exit.min(-distance / speed);
+            }
+
+            if entry >= exit {
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// Returns counter-clockwise points of the area's convex polygon.
-    pub(crate) fn points(&self) -> &[Point<f32>] {
+    pub fn points(&self) -> &[Point<f32>] {
         self.polygon.points()
     }
 }
@@ -181,6 +234,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_intersects_segment() {
+        let area = ExclusionArea::new(
+            ConvexPolygon::from_convex_hull(&[
+                Point::new(-1., -1.),
+                Point::new(-1., 1.),
+                Point::new(1., 1.),
+                Point::new(1., -1.),
+            ])
+            .unwrap(),
+        );
+
+        assert!(area.intersects_segment(Point::new(-2., 0.), Point::new(2., 0.)));
+        assert!(area.intersects_segment(Point::new(0., 0.), Point::new(0., 5.)));
+        assert!(area.intersects_segment(Point::new(-2., -1.5), Point::new(0., 2.)));
+        // Around a corner.
+        assert!(!area.intersects_segment(Point::new(-3., 1.), Point::new(1., 1.)));
+        assert!(!area.intersects_segment(Point::new(-3., -1.), Point::new(-1., 1.)));
+        assert!(!area.intersects_segment(Point::new(-1., 2.), Point::new(1., 1.)));
+        assert!(!area.intersects_segment(Point::new(-5., 0.), Point::new(-2., 0.)));
+    }
+
     #[test]
     fn test_merged() {
         let a = ExclusionArea::new(
@@ -14,11 +14,12 @@ use tinyvec::{ArrayVec, TinyVec};
 use tracing::{debug, info};
 
 use crate::{
+    cache::{CacheKey, PathCache},
     exclusion::ExclusionArea,
     graph::{Step, VisibilityGraph},
     polyanya::{find_path, PointContext},
     utils::HashableSegment,
-    PathTarget,
+    PathQueryProps, PathTarget,
 };
 
 /// A struct used for path finding.
@@ -30,6 +31,9 @@ pub struct PathFinder {
     /// `triangles`. It is used to find way out of unreachable area.
     exclusions: RTree<GraphExclusion>,
     graph: VisibilityGraph,
+    /// Memoized recent path searches. The cache is implicitly invalidated
+    /// whenever the path finder is replaced after a map change.
+    cache: PathCache,
 }
 
 impl PathFinder {
@@ -130,6 +134,7 @@ impl PathFinder {
             triangles: RTree::bulk_load(indexed_triangles),
             exclusions: RTree::bulk_load(exclusions),
             graph,
+            cache: PathCache::default(),
         }
     }
 
@@ -173,9 +178,49 @@ impl PathFinder {
                 .truncated(target.properties().distance());
         }
 
+        let source_triangle = source_edges[0].triangle_id();
+        if source_triangle == u32::MAX {
+            // Paths leading out of unreachable area are not cached.
+            return self.search(from, source_edges, to, target_edges, target.properties());
+        }
+
+        let key = CacheKey::new(
+            source_triangle,
+            target_edges
+                .first()
+                .map_or(u32::MAX, |step| step.triangle_id()),
+            target.location(),
+            target.properties(),
+        );
+        self.cache.get_or_search(
+            key,
+            from.into(),
+            || self.search(from, source_edges, to, target_edges, target.properties()),
+            |a, b| self.visible(a.into(), b.into()),
+        )
+    }
+
+    /// Returns true if the straight line segment between two accessible
+    /// points does not cross any non-accessible area.
+    fn visible(&self, a: Point<f32>, b: Point<f32>) -> bool {
+        let envelope = AABB::from_corners([a.x, a.y], [b.x, b.y]);
+        !self
+            .exclusions
+            .locate_in_envelope_intersecting(&envelope)
+            .any(|exclusion| exclusion.area.intersects_segment(a, b))
+    }
+
+    fn search(
+        &self,
+        from: Point<f32>,
+        source_edges: Vec<Step>,
+        to: Point<f32>,
+        target_edges: Vec<Step>,
+        properties: PathQueryProps,
+    ) -> Option<Path> {
         let source = PointContext::new(from, source_edges);
         let target_context = PointContext::new(to, target_edges);
-        match find_path(&self.graph, source, target_context, target.properties()) {
+        match find_path(&self.graph, source, target_context, properties) {
             Some(path) => {
                 debug!(
                     "Path of length {} from {:?} to {:?} found",
@@ -192,6 +237,22 @@ impl PathFinder {
         }
     }
 
+    pub(crate) fn cache(&self) -> &PathCache {
+        &self.cache
+    }
+
+    /// Returns an iterator over all triangles of the accessible area
+    /// triangulation (navigation mesh) in no particular order.
+    pub fn triangles(&self) -> impl Iterator<Item = &Triangle> + '_ {
+        self.triangles.iter().map(|triangle| &triangle.triangle)
+    }
+
+    /// Returns an iterator over all mutually exclusive exclusion areas, i.e.
+    /// areas not covered by the triangulation, in no particular order.
+    pub fn exclusions(&self) -> impl Iterator<Item = &ExclusionArea> + '_ {
+        self.exclusions.iter().map(|exclusion| &exclusion.area)
+    }
+
     fn locate_triangle_edges(&self, point: Point<f32>) -> Vec<Step> {
         let mut result = Vec::new();
         for triangle in self.triangles.locate_all_at_point(&[point.x, point.y]) {
@@ -363,6 +424,8 @@ mod tests {
             ),
         ];
         let finder = PathFinder::from_triangles(triangles, vec![]);
+        assert_eq!(finder.triangles().count(), 8);
+        assert_eq!(finder.exclusions().count(), 0);
 
         let first_path = finder
             .find_path(
@@ -1,13 +1,18 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
     prelude::*,
     tasks::{futures_lite::future, AsyncComputeTaskPool, Task},
 };
 use de_core::{
     gamestate::GameState,
     objects::{ObjectTypeComponent, StaticSolid},
-    schedule::PreMovement,
+    schedule::{ComputeBudget, ComputeKind, ComputeTicket, PreMovement},
     state::AppState,
 };
 use de_map::size::MapBounds;
@@ -15,6 +20,11 @@ use de_objects::SolidObjects;
 
 use crate::{exclusion::ExclusionArea, finder::PathFinder, triangulation::triangulate};
 
+/// Total number of path queries served from the path cache.
+const CACHE_HITS: DiagnosticPath = DiagnosticPath::const_new("path_cache_hits");
+/// Total number of path queries which required a new path search.
+const CACHE_MISSES: DiagnosticPath = DiagnosticPath::const_new("path_cache_misses");
+
 /// This plugin registers systems which automatically update the path finder
 /// when static solid objects are added or removed from the world.
 ///
@@ -36,11 +46,17 @@ use crate::{exclusion::ExclusionArea, finder::PathFinder, triangulation::triangu
 /// * A visibility sub-graph is created. The each triangle edge is connected
 ///   with all neighboring triangle edges. See
 ///   [`crate::finder::PathFinder::from_triangles`].
+///
+/// Updates are time-sliced with [`ComputeKind::FinderUpdate`]: after an
+/// expensive update, further updates are postponed and all changes made in
+/// the meantime are included in a single update.
 pub struct FinderPlugin;
 
 impl Plugin for FinderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<PathFinderUpdatedEvent>()
+        app.register_diagnostic(Diagnostic::new(CACHE_HITS).with_max_history_length(1))
+            .register_diagnostic(Diagnostic::new(CACHE_MISSES).with_max_history_length(1))
+            .add_event::<PathFinderUpdatedEvent>()
             .add_systems(OnEnter(AppState::InGame), setup_loading)
             .add_systems(OnEnter(GameState::Playing), setup_playing)
             .add_systems(OnExit(AppState::InGame), cleanup)
@@ -50,6 +66,7 @@ impl Plugin for FinderPlugin {
                     check_removed
                         .run_if(in_state(AppState::InGame))
                         .in_set(FinderSet::CheckRemoved),
+                    measure_cache.run_if(resource_exists::<FinderRes>),
                     (
                         check_updated.in_set(FinderSet::CheckUpdated),
                         update
@@ -103,10 +120,17 @@ impl Deref for FinderRes {
     }
 }
 
+/// Path cache counters of all path finders replaced since the game started.
+#[derive(Resource, Default)]
+struct ReplacedCacheStats {
+    hits: u64,
+    misses: u64,
+}
+
 #[derive(Resource)]
 struct UpdateFinderState {
     invalid: bool,
-    task: Option<Task<PathFinder>>,
+    task: Option<(Task<(PathFinder, Duration)>, ComputeTicket)>,
 }
 
 impl UpdateFinderState {
@@ -118,8 +142,13 @@ impl UpdateFinderState {
         self.invalid && self.task.is_none()
     }
 
-    fn spawn_update<'a, T>(&mut self, solids: SolidObjects, bounds: MapBounds, entities: T)
-    where
+    fn spawn_update<'a, T>(
+        &mut self,
+        ticket: ComputeTicket,
+        solids: SolidObjects,
+        bounds: MapBounds,
+        entities: T,
+    ) where
         T: Iterator<Item = (&'a Transform, &'a ObjectTypeComponent)>,
     {
         debug_assert!(self.task.is_none());
@@ -131,19 +160,32 @@ impl UpdateFinderState {
             .collect();
 
         let pool = AsyncComputeTaskPool::get();
-        self.task = Some(pool.spawn(async move { create_finder(bounds, exclusions) }));
+        let task = pool.spawn(async move {
+            let start = Instant::now();
+            let finder = create_finder(bounds, exclusions);
+            (finder, start.elapsed())
+        });
+        self.task = Some((task, ticket));
         self.invalid = false;
     }
 
-    fn check_result(&mut self) -> Option<PathFinder> {
-        let finder = self
-            .task
-            .as_mut()
-            .and_then(|task| future::block_on(future::poll_once(task)));
-        if finder.is_some() {
-            self.task = None;
+    /// Returns the updated path finder if the update task has finished. The
+    /// duration of the update is charged to `budget`.
+    fn check_result(&mut self, budget: &mut ComputeBudget) -> Option<PathFinder> {
+        let (task, _) = self.task.as_mut()?;
+        let (finder, elapsed) = future::block_on(future::poll_once(task))?;
+        let (_, ticket) = self.task.take().unwrap();
+        budget.finish(ticket, elapsed);
+        Some(finder)
+    }
+
+    /// Cancels the unfinished update task, if any, and marks the path finder
+    /// for another update.
+    fn cancel(&mut self, budget: &mut ComputeBudget) {
+        if let Some((_, ticket)) = self.task.take() {
+            budget.cancel(ticket);
+            self.invalidate();
         }
-        finder
     }
 }
 
@@ -161,14 +203,21 @@ type ChangedQuery<'world, 'state> =
 
 fn setup_loading(mut commands: Commands) {
     commands.init_resource::<UpdateFinderState>();
+    commands.init_resource::<ReplacedCacheStats>();
 }
 
 fn setup_playing(mut commands: Commands, bounds: Res<MapBounds>) {
     commands.insert_resource(FinderRes::new(PathFinder::new(bounds.as_ref())));
 }
 
-fn cleanup(mut commands: Commands) {
+fn cleanup(
+    mut commands: Commands,
+    mut state: ResMut<UpdateFinderState>,
+    mut budget: ResMut<ComputeBudget>,
+) {
+    state.cancel(budget.as_mut());
     commands.remove_resource::<UpdateFinderState>();
+    commands.remove_resource::<ReplacedCacheStats>();
     commands.remove_resource::<FinderRes>();
 }
 
@@ -189,28 +238,49 @@ fn check_updated(mut state: ResMut<UpdateFinderState>, changed: ChangedQuery) {
 
 fn update(
     mut state: ResMut<UpdateFinderState>,
+    mut budget: ResMut<ComputeBudget>,
     bounds: Res<MapBounds>,
     solids: SolidObjects,
     entities: Query<(&Transform, &ObjectTypeComponent), With<StaticSolid>>,
 ) {
-    if state.should_update() {
-        info!("Spawning path finder update task");
-        state.spawn_update(solids, *bounds, entities.iter());
+    if !state.should_update() {
+        return;
     }
+    let Some(ticket) = budget.try_start(ComputeKind::FinderUpdate) else {
+        return;
+    };
+
+    info!("Spawning path finder update task");
+    state.spawn_update(ticket, solids, *bounds, entities.iter());
 }
 
 fn check_update_result(
     mut state: ResMut<UpdateFinderState>,
+    mut budget: ResMut<ComputeBudget>,
     mut finder_res: ResMut<FinderRes>,
+    mut replaced: ResMut<ReplacedCacheStats>,
     mut pf_updated: EventWriter<PathFinderUpdatedEvent>,
 ) {
-    if let Some(finder) = state.check_result() {
+    if let Some(finder) = state.check_result(budget.as_mut()) {
+        let cache = finder_res.cache();
+        replaced.hits += cache.hits();
+        replaced.misses += cache.misses();
         info!("Inserting updated path finder");
         finder_res.update(finder);
         pf_updated.send(PathFinderUpdatedEvent);
     }
 }
 
+fn measure_cache(
+    finder: Res<FinderRes>,
+    replaced: Res<ReplacedCacheStats>,
+    mut diagnostics: Diagnostics,
+) {
+    let cache = finder.cache();
+    diagnostics.add_measurement(&CACHE_HITS, || (replaced.hits + cache.hits()) as f64);
+    diagnostics.add_measurement(&CACHE_MISSES, || (replaced.misses + cache.misses()) as f64);
+}
+
 /// Creates a new path finder by triangulating accessible area on the map.
 // This function has to be public due to its benchmark.
 pub fn create_finder(bounds: MapBounds, exclusions: Vec<ExclusionArea>) -> PathFinder {
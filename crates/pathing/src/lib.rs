@@ -2,6 +2,7 @@
 //! This library implements a Bevy plugin for any angle path finding on the
 //! game map.
 
+mod cache;
 mod chain;
 mod exclusion;
 mod finder;
@@ -10,6 +11,7 @@ mod geometry;
 mod graph;
 mod interval;
 mod node;
+mod overview;
 mod path;
 mod polyanya;
 mod pplugin;
@@ -21,8 +23,11 @@ mod utils;
 
 use bevy::{app::PluginGroupBuilder, prelude::PluginGroup};
 pub use exclusion::ExclusionArea;
+pub use finder::PathFinder;
 pub use fplugin::create_finder;
 use fplugin::FinderPlugin;
+use overview::OverviewPlugin;
+pub use overview::TerrainOverview;
 pub use path::ScheduledPath;
 use pplugin::PathingPlugin;
 pub use pplugin::UpdateEntityPathEvent;
@@ -37,5 +42,6 @@ impl PluginGroup for PathingPluginGroup {
             .add(FinderPlugin)
             .add(PathingPlugin)
             .add(SyncingPlugin)
+            .add(OverviewPlugin)
     }
 }
@@ -0,0 +1,246 @@
+//! Terrain overview is a small top-down image of the map surface. It is
+//! computed once per map load and used by the minimap and by map previews.
+//!
+//! The map terrain is flat and without water bodies, thus the overview shows
+//! only accessible ground and impassable areas. Impassable areas are the
+//! exclusion areas (see [`crate::exclusion`]) of inactive static objects,
+//! e.g. trees. Buildings are not part of the overview since they come and go
+//! during the game.
+
+use std::time::{Duration, Instant};
+
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use de_core::{
+    gamestate::GameState,
+    objects::{ObjectTypeComponent, StaticSolid},
+    schedule::{ComputeBudget, ComputeKind, ComputeTicket},
+    state::AppState,
+};
+use de_map::size::MapBounds;
+use de_objects::SolidObjects;
+use de_types::objects::ObjectType;
+use rstar::{PointDistance, RTreeObject};
+
+use crate::exclusion::ExclusionArea;
+
+/// Resolution (in pixels) of the longer side of the overview.
+const RESOLUTION: u32 = 512;
+const GROUND_COLOR: [u8; 4] = [155, 117, 81, 255];
+const IMPASSABLE_COLOR: [u8; 4] = [84, 68, 52, 255];
+
+pub(crate) struct OverviewPlugin;
+
+impl Plugin for OverviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    spawn_task
+                        .run_if(not(resource_exists::<OverviewTask>))
+                        .run_if(not(resource_exists::<TerrainOverview>)),
+                    check_task.run_if(resource_exists::<OverviewTask>),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Rendering of the overview is time-sliced with
+/// [`ComputeKind::TerrainOverview`].
+#[derive(Resource)]
+struct OverviewTask {
+    task: Task<(TerrainOverview, Duration)>,
+    ticket: Option<ComputeTicket>,
+}
+
+/// RGBA image of the map terrain seen from the top. The resource is
+/// inserted shortly after the game starts.
+#[derive(Resource)]
+pub struct TerrainOverview {
+    size: UVec2,
+    data: Vec<u8>,
+}
+
+impl TerrainOverview {
+    /// Renders a new terrain overview.
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` - bounds of the map.
+    ///
+    /// * `exclusions` - impassable areas of the map. The areas do not need to
+    ///   be merged, see [`ExclusionArea::build`].
+    pub fn new(bounds: MapBounds, exclusions: &[ExclusionArea]) -> Self {
+        let map_size = bounds.size();
+        let size = (RESOLUTION as f32 * map_size / map_size.max_element())
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE);
+
+        let mut overview = Self {
+            size,
+            data: GROUND_COLOR.repeat((size.x * size.y) as usize),
+        };
+
+        let px_size = map_size / size.as_vec2();
+        for exclusion in exclusions {
+            let envelope = exclusion.envelope();
+            let min = overview.px(bounds, Vec2::new(envelope.lower()[0], envelope.upper()[1]));
+            let max = overview.px(bounds, Vec2::new(envelope.upper()[0], envelope.lower()[1]));
+
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let center = Vec2::new(
+                        bounds.min().x + (x as f32 + 0.5) * px_size.x,
+                        bounds.max().y - (y as f32 + 0.5) * px_size.y,
+                    );
+                    if exclusion.contains_point(&center.to_array()) {
+                        overview.set_pixel(x, y, IMPASSABLE_COLOR);
+                    }
+                }
+            }
+        }
+
+        overview
+    }
+
+    /// Size of the image in pixels.
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Returns RGBA (sRGB) data of the image. The rows go from north to
+    /// south and the first pixel corresponds to the north-west corner of the
+    /// map.
+    pub fn data(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// Returns RGBA (sRGB) color of the pixel closest to a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - relative position on the map between (0, 0) and (1, 1).
+    ///   Point (0, 0) corresponds to the north-west corner.
+    pub fn sample(&self, point: Vec2) -> [u8; 4] {
+        let px = (point * self.size.as_vec2())
+            .as_uvec2()
+            .min(self.size - UVec2::ONE);
+        let offset = self.offset(px.x, px.y);
+        self.data[offset..offset + 4].try_into().unwrap()
+    }
+
+    /// Returns pixel coordinates of the pixel closest to a map point.
+    fn px(&self, bounds: MapBounds, point: Vec2) -> UVec2 {
+        let rel = Vec2::new(point.x - bounds.min().x, bounds.max().y - point.y) / bounds.size();
+        (rel.clamp(Vec2::ZERO, Vec2::ONE) * self.size.as_vec2())
+            .as_uvec2()
+            .min(self.size - UVec2::ONE)
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let offset = self.offset(x, y);
+        self.data[offset..offset + 4].copy_from_slice(&color);
+    }
+
+    fn offset(&self, x: u32, y: u32) -> usize {
+        4 * (y * self.size.x + x) as usize
+    }
+}
+
+fn spawn_task(
+    mut commands: Commands,
+    mut budget: ResMut<ComputeBudget>,
+    bounds: Res<MapBounds>,
+    solids: SolidObjects,
+    entities: Query<(&Transform, &ObjectTypeComponent), With<StaticSolid>>,
+) {
+    let Some(ticket) = budget.try_start(ComputeKind::TerrainOverview) else {
+        return;
+    };
+
+    let exclusions: Vec<ExclusionArea> = entities
+        .iter()
+        .filter(|(_, object_type)| matches!(***object_type, ObjectType::Inactive(_)))
+        .map(|(transform, object_type)| {
+            ExclusionArea::from_ichnography(transform, solids.get(**object_type).ichnography())
+        })
+        .collect();
+
+    let bounds = *bounds;
+    let pool = AsyncComputeTaskPool::get();
+    let task = pool.spawn(async move {
+        let start = Instant::now();
+        let overview = TerrainOverview::new(bounds, exclusions.as_slice());
+        (overview, start.elapsed())
+    });
+    commands.insert_resource(OverviewTask {
+        task,
+        ticket: Some(ticket),
+    });
+}
+
+fn check_task(
+    mut commands: Commands,
+    mut budget: ResMut<ComputeBudget>,
+    mut task: ResMut<OverviewTask>,
+) {
+    if let Some((overview, elapsed)) = future::block_on(future::poll_once(&mut task.task)) {
+        info!("Terrain overview of size {:?} is ready", overview.size());
+        if let Some(ticket) = task.ticket.take() {
+            budget.finish(ticket, elapsed);
+        }
+        commands.remove_resource::<OverviewTask>();
+        commands.insert_resource(overview);
+    }
+}
+
+fn cleanup(
+    mut commands: Commands,
+    mut budget: ResMut<ComputeBudget>,
+    task: Option<ResMut<OverviewTask>>,
+) {
+    if let Some(ticket) = task.and_then(|mut task| task.ticket.take()) {
+        budget.cancel(ticket);
+    }
+    commands.remove_resource::<OverviewTask>();
+    commands.remove_resource::<TerrainOverview>();
+}
+
+#[cfg(test)]
+mod tests {
+    use de_objects::Ichnography;
+    use parry2d::{math::Point, shape::ConvexPolygon};
+
+    use super::*;
+
+    #[test]
+    fn test_overview() {
+        let ichnography = Ichnography::from(
+            ConvexPolygon::from_convex_hull(&[
+                Point::new(-1., 1.),
+                Point::new(-1., -1.),
+                Point::new(1., -1.),
+                Point::new(1., 1.),
+            ])
+            .unwrap(),
+        );
+        let exclusion =
+            ExclusionArea::from_ichnography(&Transform::from_xyz(250., 0., -50.), &ichnography);
+
+        let overview = TerrainOverview::new(MapBounds::new(Vec2::new(1000., 500.)), &[exclusion]);
+        assert_eq!(overview.size(), UVec2::new(512, 256));
+        assert_eq!(overview.data().len(), 4 * 512 * 256);
+
+        assert_eq!(overview.sample(Vec2::new(0.5, 0.5)), GROUND_COLOR);
+        assert_eq!(overview.sample(Vec2::ZERO), GROUND_COLOR);
+        assert_eq!(overview.sample(Vec2::ONE), GROUND_COLOR);
+        // The object is at (250, 50) in flat coordinates.
+        assert_eq!(overview.sample(Vec2::new(0.75, 0.4)), IMPASSABLE_COLOR);
+        assert_eq!(overview.sample(Vec2::new(0.76, 0.4)), GROUND_COLOR);
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use ahash::AHashMap;
 use bevy::{
     prelude::*,
@@ -6,7 +8,7 @@ use bevy::{
 use de_core::{
     gamestate::GameState,
     objects::MovableSolid,
-    schedule::{PostMovement, PreMovement},
+    schedule::{ComputeBudget, ComputeKind, ComputeTicket, PostMovement, PreMovement},
     state::AppState,
 };
 use de_types::{path::Path, projection::ToFlat};
@@ -21,6 +23,12 @@ const TARGET_TOLERANCE: f32 = 2.;
 
 /// This plugin handles path finding requests and keeps scheduled paths
 /// up-to-date.
+///
+/// Path searches are time-sliced with [`ComputeKind::PathSearch`]. Paths
+/// requested via [`UpdateEntityPathEvent`] are searched for immediately,
+/// re-planning of existing paths after a path finder update is spread over
+/// multiple frames. Searches superseded before they finish are cancelled and
+/// do not consume the budget.
 pub struct PathingPlugin;
 
 impl Plugin for PathingPlugin {
@@ -33,7 +41,6 @@ impl Plugin for PathingPlugin {
                 PreMovement,
                 (
                     update_existing_paths
-                        .run_if(on_event::<PathFinderUpdatedEvent>())
                         .in_set(PathingSet::UpdateExistingPaths)
                         .after(FinderSet::UpdateFinder),
                     update_requested_paths
@@ -125,6 +132,9 @@ impl PathFoundEvent {
 #[derive(Default, Resource)]
 struct UpdatePathsState {
     tasks: AHashMap<Entity, UpdatePathTask>,
+    /// Entities whose paths are to be re-planned due to a path finder
+    /// update.
+    outdated: Vec<Entity>,
 }
 
 impl UpdatePathsState {
@@ -132,43 +142,76 @@ impl UpdatePathsState {
         self.tasks.contains_key(&entity)
     }
 
-    fn spawn_new(&mut self, finder: FinderRes, entity: Entity, source: Vec2, target: PathTarget) {
+    /// Spawns a new path search task. A previously spawned and unfinished
+    /// task of the entity is cancelled.
+    fn spawn_new(
+        &mut self,
+        budget: &mut ComputeBudget,
+        ticket: ComputeTicket,
+        finder: FinderRes,
+        entity: Entity,
+        source: Vec2,
+        target: PathTarget,
+    ) {
         let pool = AsyncComputeTaskPool::get();
-        let task = pool.spawn(async move { finder.find_path(source, target) });
-        self.tasks.insert(entity, UpdatePathTask::new(task));
+        let task = pool.spawn(async move {
+            let start = Instant::now();
+            let path = finder.find_path(source, target);
+            (path, start.elapsed())
+        });
+        if let Some(superseded) = self.tasks.insert(entity, UpdatePathTask::new(task, ticket)) {
+            budget.cancel(superseded.ticket);
+        }
+    }
+
+    /// Cancels all unfinished path search tasks.
+    fn cancel_all(&mut self, budget: &mut ComputeBudget) {
+        for (_, task) in self.tasks.drain() {
+            budget.cancel(task.ticket);
+        }
     }
 
-    fn check_results(&mut self) -> Vec<(Entity, Option<Path>)> {
-        let mut results = Vec::new();
-        self.tasks.retain(|&entity, task| match task.check() {
-            UpdatePathState::Resolved(path) => {
-                results.push((entity, path));
-                false
+    /// Removes finished tasks and returns their results. Duration of the
+    /// finished path searches is charged to `budget`.
+    fn check_results(&mut self, budget: &mut ComputeBudget) -> Vec<(Entity, Option<Path>)> {
+        let mut finished = Vec::new();
+        for (&entity, task) in self.tasks.iter_mut() {
+            if let UpdatePathState::Resolved(path, elapsed) = task.check() {
+                finished.push((entity, path, elapsed));
             }
-            UpdatePathState::Processing => true,
-        });
+        }
+
+        let mut results = Vec::with_capacity(finished.len());
+        for (entity, path, elapsed) in finished {
+            let task = self.tasks.remove(&entity).unwrap();
+            budget.finish(task.ticket, elapsed);
+            results.push((entity, path));
+        }
 
         results
     }
 }
 
-struct UpdatePathTask(Task<Option<Path>>);
+struct UpdatePathTask {
+    task: Task<(Option<Path>, Duration)>,
+    ticket: ComputeTicket,
+}
 
 impl UpdatePathTask {
-    fn new(task: Task<Option<Path>>) -> Self {
-        Self(task)
+    fn new(task: Task<(Option<Path>, Duration)>, ticket: ComputeTicket) -> Self {
+        Self { task, ticket }
     }
 
     fn check(&mut self) -> UpdatePathState {
-        match future::block_on(future::poll_once(&mut self.0)) {
-            Some(path) => UpdatePathState::Resolved(path),
+        match future::block_on(future::poll_once(&mut self.task)) {
+            Some((path, elapsed)) => UpdatePathState::Resolved(path, elapsed),
             None => UpdatePathState::Processing,
         }
     }
 }
 
 enum UpdatePathState {
-    Resolved(Option<Path>),
+    Resolved(Option<Path>, Duration),
     Processing,
 }
 
@@ -176,25 +219,47 @@ fn setup(mut commands: Commands) {
     commands.init_resource::<UpdatePathsState>()
 }
 
-fn cleanup(mut commands: Commands) {
+fn cleanup(
+    mut commands: Commands,
+    mut state: ResMut<UpdatePathsState>,
+    mut budget: ResMut<ComputeBudget>,
+) {
+    state.cancel_all(budget.as_mut());
     commands.remove_resource::<UpdatePathsState>();
 }
 
 fn update_existing_paths(
     finder: Res<FinderRes>,
+    mut budget: ResMut<ComputeBudget>,
     mut state: ResMut<UpdatePathsState>,
+    mut events: EventReader<PathFinderUpdatedEvent>,
     entities: Query<(Entity, &Transform, &PathTarget, Has<ScheduledPath>)>,
 ) {
-    for (entity, transform, target, has_path) in entities.iter() {
+    if events.read().count() > 0 {
+        state.outdated = entities.iter().map(|(entity, ..)| entity).collect();
+    }
+
+    while let Some(&entity) = state.outdated.last() {
+        let Ok((_, transform, target, has_path)) = entities.get(entity) else {
+            state.outdated.pop();
+            continue;
+        };
+
         let position = transform.translation.to_flat();
         if !has_path && !state.contains(entity) {
             let current_distance = position.distance(target.location());
             let desired_distance = target.properties().distance();
             if (current_distance - desired_distance).abs() <= TARGET_TOLERANCE {
+                state.outdated.pop();
                 continue;
             }
         }
 
+        let Some(ticket) = budget.try_start(ComputeKind::PathSearch) else {
+            break;
+        };
+        state.outdated.pop();
+
         let new_target = PathTarget::new(
             target.location(),
             // Set max distance to infinity: the object has already departed
@@ -204,13 +269,21 @@ fn update_existing_paths(
             target.permanent(),
         );
 
-        state.spawn_new(finder.clone(), entity, position, new_target);
+        state.spawn_new(
+            budget.as_mut(),
+            ticket,
+            finder.clone(),
+            entity,
+            position,
+            new_target,
+        );
     }
 }
 
 fn update_requested_paths(
     mut commands: Commands,
     finder: Res<FinderRes>,
+    mut budget: ResMut<ComputeBudget>,
     mut state: ResMut<UpdatePathsState>,
     mut events: EventReader<UpdateEntityPathEvent>,
     entities: Query<&Transform, With<MovableSolid>>,
@@ -218,7 +291,10 @@ fn update_requested_paths(
     for event in events.read() {
         if let Ok(transform) = entities.get(event.entity()) {
             commands.entity(event.entity()).insert(event.target());
+            let ticket = budget.start(ComputeKind::PathSearch);
             state.spawn_new(
+                budget.as_mut(),
+                ticket,
                 finder.clone(),
                 event.entity(),
                 transform.translation.to_flat(),
@@ -230,9 +306,10 @@ fn update_requested_paths(
 
 fn check_path_results(
     mut state: ResMut<UpdatePathsState>,
+    mut budget: ResMut<ComputeBudget>,
     mut events: EventWriter<PathFoundEvent>,
 ) {
-    for (entity, path) in state.check_results() {
+    for (entity, path) in state.check_results(budget.as_mut()) {
         events.send(PathFoundEvent::new(entity, path));
     }
 }
@@ -55,7 +55,8 @@ fn send_new_paths(
 ) {
     for event in path_events.read() {
         net_events.send(ToPlayersEvent::new(ToPlayers::SetPath {
-            entity: net_entities.local_net_id(event.entity()),
+            // Objects of allies might be commanded with shared control.
+            entity: net_entities.net_id(event.entity()),
             waypoints: event.path().map(|p| p.try_into().unwrap()),
         }));
     }
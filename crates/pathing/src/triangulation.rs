@@ -1,8 +1,19 @@
 //! This module implements Constrained Delaunay triangulation (CDT) based
 //! triangulation of the accessible areas on the game map.
+//!
+//! Large maps are split into vertical strips (regions) along lines which do
+//! not intersect any exclusion area. The regions are triangulated in parallel
+//! and the resulting triangles are merged. Neighboring regions share their
+//! whole boundary as a single triangle edge, therefore the merged
+//! triangulation stays connected.
+
+use std::ops::Range;
 
 use ahash::AHashMap;
-use bevy::utils::FloatOrd;
+use bevy::{
+    tasks::{ComputeTaskPool, TaskPool},
+    utils::FloatOrd,
+};
 use de_map::size::MapBounds;
 use de_objects::EXCLUSION_OFFSET;
 use parry2d::{
@@ -14,6 +25,9 @@ use spade::{handles::FixedVertexHandle, ConstrainedDelaunayTriangulation, Point2
 use crate::exclusion::ExclusionArea;
 
 const MAP_OFFSET: Vector<f32> = Vector::new(EXCLUSION_OFFSET, EXCLUSION_OFFSET);
+/// Maximum number of exclusion areas in a region triangulated by a single
+/// task. The limit is exceeded when there is no way to split the region.
+const REGION_EXCLUSIONS: usize = 256;
 
 /// Returns a triangulation of rectangular area given by `bounds` shrinked by
 /// some distance with exclusion zones.
@@ -37,11 +51,114 @@ const MAP_OFFSET: Vector<f32> = Vector::new(EXCLUSION_OFFSET, EXCLUSION_OFFSET);
 ///
 /// May panic if any of the aforementioned assumptions does not hold.
 pub(crate) fn triangulate(bounds: &MapBounds, exclusions: &[ExclusionArea]) -> Vec<Triangle> {
-    let mut triangulation = MapTriangulation::new();
+    triangulate_split(bounds, exclusions, REGION_EXCLUSIONS)
+}
+
+fn triangulate_split(
+    bounds: &MapBounds,
+    exclusions: &[ExclusionArea],
+    max_exclusions: usize,
+) -> Vec<Triangle> {
     let (mins, maxs) = {
         let aabb = bounds.aabb();
         (aabb.mins + MAP_OFFSET, aabb.maxs - MAP_OFFSET)
     };
+
+    if exclusions.len() <= max_exclusions {
+        return triangulate_region(mins, maxs, exclusions);
+    }
+
+    let mut sorted = exclusions.to_vec();
+    sorted.sort_by(|a, b| x_range(a).0.total_cmp(&x_range(b).0));
+    let regions = split(mins.x, maxs.x, &sorted, max_exclusions);
+    if regions.len() == 1 {
+        return triangulate_region(mins, maxs, exclusions);
+    }
+
+    let sorted = sorted.as_slice();
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    pool.scope(|scope| {
+        for region in regions.iter() {
+            scope.spawn(async move {
+                triangulate_region(
+                    Point::new(region.min_x, mins.y),
+                    Point::new(region.max_x, maxs.y),
+                    &sorted[region.exclusions.clone()],
+                )
+            });
+        }
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// A vertical strip of the map.
+struct Region {
+    min_x: f32,
+    max_x: f32,
+    /// Range of exclusion areas (sorted by their minimum x coordinate) fully
+    /// contained in the region.
+    exclusions: Range<usize>,
+}
+
+/// Splits `min_x..max_x` into regions each containing at most
+/// `max_exclusions` exclusion areas where possible.
+///
+/// # Arguments
+///
+/// * `exclusions` - exclusion areas sorted by their minimum x coordinate.
+fn split(
+    min_x: f32,
+    max_x: f32,
+    exclusions: &[ExclusionArea],
+    max_exclusions: usize,
+) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut region_min_x = min_x;
+    let mut start = 0;
+    // Maximum x coordinate of all exclusion areas so far.
+    let mut reach = f32::NEG_INFINITY;
+
+    for (i, exclusion) in exclusions.iter().enumerate() {
+        let (exclusion_min_x, exclusion_max_x) = x_range(exclusion);
+        if i - start >= max_exclusions && reach < exclusion_min_x {
+            let split_x = 0.5 * (reach + exclusion_min_x);
+            regions.push(Region {
+                min_x: region_min_x,
+                max_x: split_x,
+                exclusions: start..i,
+            });
+            region_min_x = split_x;
+            start = i;
+        }
+        reach = reach.max(exclusion_max_x);
+    }
+
+    regions.push(Region {
+        min_x: region_min_x,
+        max_x,
+        exclusions: start..exclusions.len(),
+    });
+    regions
+}
+
+fn x_range(exclusion: &ExclusionArea) -> (f32, f32) {
+    exclusion
+        .points()
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+            (min.min(point.x), max.max(point.x))
+        })
+}
+
+/// Triangulates a rectangle given by `mins` and `maxs` with exclusion zones.
+fn triangulate_region(
+    mins: Point<f32>,
+    maxs: Point<f32>,
+    exclusions: &[ExclusionArea],
+) -> Vec<Triangle> {
+    let mut triangulation = MapTriangulation::new();
     triangulation.insert(Point::new(mins.x, mins.y), None);
     triangulation.insert(Point::new(mins.x, maxs.y), None);
     triangulation.insert(Point::new(maxs.x, maxs.y), None);
@@ -355,6 +472,67 @@ mod tests {
         assert_eq!(triangles, expected);
     }
 
+    #[test]
+    fn test_triangulation_split() {
+        let square = |x: f32, y: f32| {
+            ExclusionArea::new(
+                ConvexPolygon::from_convex_polyline(vec![
+                    Point::new(x, y),
+                    Point::new(x, y + 1.),
+                    Point::new(x + 1., y + 1.),
+                    Point::new(x + 1., y),
+                ])
+                .unwrap(),
+            )
+        };
+        let obstacles: Vec<ExclusionArea> = (0..10)
+            .flat_map(|i| {
+                let x = -9. + 2. * i as f32;
+                [square(x, -3.), square(x, 2.)]
+            })
+            .collect();
+        let bounds = MapBounds::new(Vec2::new(
+            22. + 2. * EXCLUSION_OFFSET,
+            10. + 2. * EXCLUSION_OFFSET,
+        ));
+
+        let mut sorted = obstacles.clone();
+        sorted.sort_by(|a, b| x_range(a).0.total_cmp(&x_range(b).0));
+        let regions = split(-11., 11., &sorted, 4);
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| (region.min_x, region.max_x, region.exclusions.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (-11., -5.5, 0..4),
+                (-5.5, -1.5, 4..8),
+                (-1.5, 2.5, 8..12),
+                (2.5, 6.5, 12..16),
+                (6.5, 11., 16..20),
+            ]
+        );
+
+        let triangles = triangulate_split(&bounds, &obstacles, 4);
+        let area: f32 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert!((area - (22. * 10. - 20.)).abs() < 0.01);
+
+        let mut segments: AHashMap<HashableSegment, usize> = AHashMap::new();
+        for triangle in triangles.iter() {
+            for edge in triangle.edges() {
+                *segments.entry(HashableSegment::new(edge)).or_default() += 1;
+            }
+        }
+        // Region boundaries are shared by exactly two triangles.
+        for x in [-5.5, -1.5, 2.5, 6.5] {
+            let boundary = HashableSegment::new(parry2d::shape::Segment::new(
+                Point::new(x, -5.),
+                Point::new(x, 5.),
+            ));
+            assert_eq!(segments.get(&boundary), Some(&2));
+        }
+    }
+
     #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
     struct HashableTriangle(HashableSegment, HashableSegment, HashableSegment);
 
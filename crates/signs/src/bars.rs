@@ -14,6 +14,7 @@ use bevy::{
     },
 };
 use de_camera::{CameraDistance, DistanceSet};
+use de_conf::Palette;
 use de_core::{
     objects::{Active, ObjectTypeComponent},
     state::AppState,
@@ -27,6 +28,14 @@ use crate::{DISTANCE_FLAG_BIT, MAX_VISIBILITY_DISTANCE, UPDATE_TIMER_FLAG_BIT};
 /// parent entity collider.
 const BAR_HEIGHT: f32 = 2.;
 
+/// Vertical offset of the progress bar center relative to the health bar
+/// center.
+const PROGRESS_BAR_OFFSET: f32 = -0.3;
+/// Vertical offset of the shield bar center relative to the health bar
+/// center.
+const SHIELD_BAR_OFFSET: f32 = 0.25;
+const PROGRESS_VISIBLE_FLAG_BIT: u32 = 0;
+
 /// Duration that a bar is visible when its value is updated.
 const UPDATE_VISIBILITY_DURATION: Duration = Duration::from_secs(3);
 
@@ -40,6 +49,7 @@ impl Plugin for BarsPlugin {
         app.add_plugins(MaterialPlugin::<BarMaterial>::default())
             .add_event::<UpdateBarValueEvent>()
             .add_event::<UpdateBarVisibilityEvent>()
+            .add_event::<UpdateProgressBarEvent>()
             .add_systems(OnEnter(AppState::InGame), setup)
             .add_systems(OnExit(AppState::InGame), cleanup)
             .add_systems(
@@ -49,6 +59,7 @@ impl Plugin for BarsPlugin {
                     update_value,
                     (
                         update_visibility_events,
+                        update_progress,
                         update_visibility_distance.after(DistanceSet::Update),
                         update_visibility_timer,
                     )
@@ -59,33 +70,55 @@ impl Plugin for BarsPlugin {
     }
 }
 
-/// An event which changes value displayed on the entity bar.
+/// An event which changes value displayed on an entity bar.
 #[derive(Event)]
 pub struct UpdateBarValueEvent {
     entity: Entity,
+    id: BarId,
     value: f32,
 }
 
 impl UpdateBarValueEvent {
     /// Crates new update event.
     ///
+    /// # Arguments
+    ///
+    /// * `entity` - entity whose bar is to be updated.
+    ///
+    /// * `id` - the updated bar.
+    ///
+    /// * `value` - the new value between 0. and 1. (inclusive).
+    ///
     /// # Panics
     ///
     /// May panic if the value is not between 0. and 1. (inclusive).
-    pub fn new(entity: Entity, value: f32) -> Self {
+    pub fn new(entity: Entity, id: BarId, value: f32) -> Self {
         debug_assert!((0. ..=1.).contains(&value));
-        Self { entity, value }
+        Self { entity, id, value }
     }
 
     fn entity(&self) -> Entity {
         self.entity
     }
 
+    fn id(&self) -> BarId {
+        self.id
+    }
+
     fn value(&self) -> f32 {
         self.value
     }
 }
 
+/// Bars whose value is set with [`UpdateBarValueEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarId {
+    Health,
+    /// Only objects with a personal shield have the shield bar. It is
+    /// displayed above the health bar.
+    Shield,
+}
+
 #[derive(Event)]
 pub struct UpdateBarVisibilityEvent {
     entity: Entity,
@@ -127,24 +160,54 @@ impl UpdateBarVisibilityEvent {
     }
 }
 
-#[derive(Resource)]
-struct BarMesh(Handle<Mesh>);
+/// An event which changes value displayed on the secondary (progress) bar of
+/// the entity, e.g. manufacturing progress of a factory or occupancy of a
+/// garrison.
+///
+/// Only objects capable of manufacturing or garrisoning other units have the
+/// progress bar.
+#[derive(Event)]
+pub struct UpdateProgressBarEvent {
+    entity: Entity,
+    value: Option<f32>,
+}
 
-impl BarMesh {
-    fn mesh(&self) -> Handle<Mesh> {
-        self.0.clone()
+impl UpdateProgressBarEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - entity whose progress bar is to be updated.
+    ///
+    /// * `value` - the progress between 0. and 1. (inclusive), or None if
+    ///   nothing is in progress. The bar is visible only while something is
+    ///   in progress.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the value is not between 0. and 1. (inclusive).
+    pub fn new(entity: Entity, value: Option<f32>) -> Self {
+        debug_assert!(value.is_none_or(|value| (0. ..=1.).contains(&value)));
+        Self { entity, value }
     }
 }
 
+#[derive(Resource)]
+struct BarMeshes {
+    health: Handle<Mesh>,
+    progress: Handle<Mesh>,
+    shield: Handle<Mesh>,
+}
+
 #[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
 struct BarMaterial {
     #[uniform(0)]
     value: f32,
+    #[uniform(1)]
+    color: Color,
 }
 
-impl Default for BarMaterial {
-    fn default() -> Self {
-        Self { value: 1. }
+impl BarMaterial {
+    fn new(value: f32, color: Color) -> Self {
+        Self { value, color }
     }
 }
 
@@ -179,6 +242,12 @@ impl Material for BarMaterial {
 #[derive(Component)]
 struct BarChild(Entity);
 
+#[derive(Component)]
+struct ProgressBarChild(Entity);
+
+#[derive(Component)]
+struct ShieldBarChild(Entity);
+
 #[derive(Component)]
 struct BarUpdateTimer(Timer);
 
@@ -192,31 +261,35 @@ impl Default for BarUpdateTimer {
 }
 
 fn setup(mut commans: Commands, mut meshes: ResMut<Assets<Mesh>>) {
-    commans.insert_resource(BarMesh(meshes.add(bar_mesh(1.5, 0.3))));
+    commans.insert_resource(BarMeshes {
+        health: meshes.add(bar_mesh(1.5, 0.3, 0.)),
+        progress: meshes.add(bar_mesh(1.5, 0.15, PROGRESS_BAR_OFFSET)),
+        shield: meshes.add(bar_mesh(1.5, 0.15, SHIELD_BAR_OFFSET)),
+    });
 }
 
 fn cleanup(mut commands: Commands) {
-    commands.remove_resource::<BarMesh>();
+    commands.remove_resource::<BarMeshes>();
 }
 
 fn spawn(
     mut commands: Commands,
     solids: SolidObjects,
-    mesh: Res<BarMesh>,
+    palette: Res<Palette>,
+    meshes: Res<BarMeshes>,
     mut materials: ResMut<Assets<BarMaterial>>,
     entities: Query<(Entity, &ObjectTypeComponent), Added<Active>>,
 ) {
     for (entity, &object_type) in entities.iter() {
-        let height = solids.get(*object_type).collider().aabb().maxs.y + BAR_HEIGHT;
+        let solid = solids.get(*object_type);
+        let height = solid.collider().aabb().maxs.y + BAR_HEIGHT;
         let transform = Transform::from_translation(height * Vec3::Y);
 
-        let material = materials.add(BarMaterial::default());
-
         let bar_entity = commands
             .spawn((
                 MaterialMeshBundle::<BarMaterial> {
-                    mesh: mesh.mesh(),
-                    material,
+                    mesh: meshes.health.clone(),
+                    material: materials.add(BarMaterial::new(1., palette.health())),
                     transform,
                     visibility: Visibility::Hidden,
                     ..Default::default()
@@ -232,51 +305,150 @@ fn spawn(
             .entity(entity)
             .add_child(bar_entity)
             .insert(BarChild(bar_entity));
+
+        if solid.factory().is_some() || solid.garrison().is_some() {
+            let progress_entity = commands
+                .spawn((
+                    MaterialMeshBundle::<BarMaterial> {
+                        mesh: meshes.progress.clone(),
+                        material: materials.add(BarMaterial::new(0., palette.progress())),
+                        transform,
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    },
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                    VisibilityFlags::default(),
+                ))
+                .id();
+
+            commands
+                .entity(entity)
+                .add_child(progress_entity)
+                .insert(ProgressBarChild(progress_entity));
+        }
+
+        if solid.shield().is_some() {
+            let shield_entity = commands
+                .spawn((
+                    MaterialMeshBundle::<BarMaterial> {
+                        mesh: meshes.shield.clone(),
+                        material: materials.add(BarMaterial::new(1., palette.shield())),
+                        transform,
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    },
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                    VisibilityFlags::default(),
+                    BarUpdateTimer::default(),
+                ))
+                .id();
+
+            commands
+                .entity(entity)
+                .add_child(shield_entity)
+                .insert(ShieldBarChild(shield_entity));
+        }
     }
 }
 
 fn update_value(
     mut materials: ResMut<Assets<BarMaterial>>,
-    parents: Query<&BarChild, With<Active>>,
+    parents: Query<(&BarChild, Option<&ShieldBarChild>), With<Active>>,
     mut bars: Query<(&Handle<BarMaterial>, &mut BarUpdateTimer)>,
     mut events: EventReader<UpdateBarValueEvent>,
 ) {
     for event in events.read() {
-        if let Ok(child) = parents.get(event.entity()) {
-            let (handle, mut timer) = bars.get_mut(child.0).unwrap();
-            let material = materials.get_mut(handle).unwrap();
-            material.value = event.value();
-
+        let Ok((health, shield)) = parents.get(event.entity()) else {
+            continue;
+        };
+        let child = match event.id() {
+            BarId::Health => health.0,
+            BarId::Shield => match shield {
+                Some(shield) => shield.0,
+                None => continue,
+            },
+        };
+
+        let (handle, _) = bars.get(child).unwrap();
+        materials.get_mut(handle).unwrap().value = event.value();
+
+        // Health and shield bars are always displayed together.
+        for child in std::iter::once(health.0).chain(shield.map(|child| child.0)) {
+            let (_, mut timer) = bars.get_mut(child).unwrap();
             timer.0.reset();
         }
     }
 }
 
 fn update_visibility_events(
-    parents: Query<&BarChild, With<Active>>,
+    parents: Query<(&BarChild, Option<&ShieldBarChild>), With<Active>>,
     mut bars: Query<&mut VisibilityFlags>,
     mut events: EventReader<UpdateBarVisibilityEvent>,
 ) {
     for event in events.read() {
-        if let Ok(child) = parents.get(event.entity()) {
-            bars.get_mut(child.0)
-                .unwrap()
-                .update_visible(event.id(), event.value());
+        if let Ok((health, shield)) = parents.get(event.entity()) {
+            for child in std::iter::once(health.0).chain(shield.map(|child| child.0)) {
+                bars.get_mut(child)
+                    .unwrap()
+                    .update_visible(event.id(), event.value());
+            }
         }
     }
 }
 
-fn update_visibility_distance(
-    parents: Query<(&BarChild, &CameraDistance), Changed<CameraDistance>>,
-    mut bars: Query<&mut VisibilityFlags>,
+fn update_progress(
+    mut materials: ResMut<Assets<BarMaterial>>,
+    parents: Query<&ProgressBarChild, With<Active>>,
+    mut bars: Query<(&Handle<BarMaterial>, &mut VisibilityFlags)>,
+    mut events: EventReader<UpdateProgressBarEvent>,
 ) {
-    for (child, distance) in parents.iter() {
-        let invisible = distance.distance() > MAX_VISIBILITY_DISTANCE;
-        let mut flags = bars.get_mut(child.0).unwrap();
+    for event in events.read() {
+        let Ok(child) = parents.get(event.entity) else {
+            continue;
+        };
 
+        let (handle, mut flags) = bars.get_mut(child.0).unwrap();
         // Do not trigger change detection unnecessarily.
-        if flags.invisible_value(DISTANCE_FLAG_BIT) != invisible {
-            flags.update_invisible(DISTANCE_FLAG_BIT, invisible);
+        if flags.visible_value(PROGRESS_VISIBLE_FLAG_BIT) != event.value.is_some() {
+            flags.update_visible(PROGRESS_VISIBLE_FLAG_BIT, event.value.is_some());
+        }
+        if let Some(value) = event.value {
+            // Do not trigger asset change detection unnecessarily.
+            if materials.get(handle).unwrap().value != value {
+                materials.get_mut(handle).unwrap().value = value;
+            }
+        }
+    }
+}
+
+type DistanceQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static BarChild,
+        Option<&'static ProgressBarChild>,
+        Option<&'static ShieldBarChild>,
+        &'static CameraDistance,
+    ),
+    Changed<CameraDistance>,
+>;
+
+fn update_visibility_distance(parents: DistanceQuery, mut bars: Query<&mut VisibilityFlags>) {
+    for (health, progress, shield, distance) in parents.iter() {
+        let invisible = distance.distance() > MAX_VISIBILITY_DISTANCE;
+
+        for child in std::iter::once(health.0)
+            .chain(progress.map(|child| child.0))
+            .chain(shield.map(|child| child.0))
+        {
+            let mut flags = bars.get_mut(child).unwrap();
+
+            // Do not trigger change detection unnecessarily.
+            if flags.invisible_value(DISTANCE_FLAG_BIT) != invisible {
+                flags.update_invisible(DISTANCE_FLAG_BIT, invisible);
+            }
         }
     }
 }
@@ -298,7 +470,16 @@ fn update_visibility_timer(
     }
 }
 
-fn bar_mesh(width: f32, height: f32) -> Mesh {
+/// Creates a bar mesh.
+///
+/// # Arguments
+///
+/// * `width` - width of the bar.
+///
+/// * `height` - height of the bar.
+///
+/// * `offset` - vertical offset of the bar center.
+fn bar_mesh(width: f32, height: f32, offset: f32) -> Mesh {
     let mut mesh = Mesh::new(
         PrimitiveTopology::TriangleList,
         RenderAssetUsages::RENDER_WORLD,
@@ -306,10 +487,10 @@ fn bar_mesh(width: f32, height: f32) -> Mesh {
     mesh.insert_attribute(
         ATTRIBUTE_POSITION,
         vec![
-            [-0.5 * width, 0.5 * height],
-            [-0.5 * width, -0.5 * height],
-            [0.5 * width, -0.5 * height],
-            [0.5 * width, 0.5 * height],
+            [-0.5 * width, offset + 0.5 * height],
+            [-0.5 * width, offset - 0.5 * height],
+            [0.5 * width, offset - 0.5 * height],
+            [0.5 * width, offset + 0.5 * height],
         ],
     );
     mesh.insert_attribute(
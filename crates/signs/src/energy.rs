@@ -0,0 +1,36 @@
+//! Status icons reflecting energy state of active objects.
+
+use bevy::prelude::*;
+use de_core::state::AppState;
+use de_energy::LowEnergyEvent;
+
+use crate::status::{StatusIcon, StatusIconsAppExt, StatusSet, UpdateStatusIconEvent};
+
+const LOW_ENERGY_ICON: StatusIcon = StatusIcon::new("low_energy");
+
+pub(crate) struct EnergyPlugin;
+
+impl Plugin for EnergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_status_icon(LOW_ENERGY_ICON).add_systems(
+            PostUpdate,
+            update_low_energy
+                .before(StatusSet::UpdateEvents)
+                .run_if(in_state(AppState::InGame))
+                .run_if(on_event::<LowEnergyEvent>()),
+        );
+    }
+}
+
+fn update_low_energy(
+    mut low_events: EventReader<LowEnergyEvent>,
+    mut icon_events: EventWriter<UpdateStatusIconEvent>,
+) {
+    for event in low_events.read() {
+        icon_events.send(UpdateStatusIconEvent::new(
+            event.entity(),
+            LOW_ENERGY_ICON,
+            event.low(),
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use bars::BarsPlugin;
-pub use bars::{UpdateBarValueEvent, UpdateBarVisibilityEvent};
+pub use bars::{BarId, UpdateBarValueEvent, UpdateBarVisibilityEvent, UpdateProgressBarEvent};
 use bevy::{app::PluginGroupBuilder, prelude::*};
+use energy::EnergyPlugin;
 use line::LinePlugin;
 pub use line::{
     LineLocation, UpdateLineEndEvent, UpdateLineLocationEvent, UpdateLineVisibilityEvent,
@@ -8,11 +9,18 @@ pub use line::{
 use markers::MarkersPlugin;
 use pole::PolePlugin;
 pub use pole::{UpdatePoleLocationEvent, UpdatePoleVisibilityEvent};
+use rings::RingsPlugin;
+pub use rings::UpdateRingVisibilityEvent;
+use status::StatusPlugin;
+pub use status::{StatusIcon, StatusIconsAppExt, UpdateStatusIconEvent};
 
 mod bars;
+mod energy;
 mod line;
 mod markers;
 mod pole;
+mod rings;
+mod status;
 
 /// The 3D signs are not displayed if further than this from the camera.
 const MAX_VISIBILITY_DISTANCE: f32 = 140.;
@@ -28,5 +36,8 @@ impl PluginGroup for SignsPluginGroup {
             .add(MarkersPlugin)
             .add(PolePlugin)
             .add(LinePlugin)
+            .add(RingsPlugin)
+            .add(StatusPlugin)
+            .add(EnergyPlugin)
     }
 }
@@ -0,0 +1,273 @@
+use ahash::AHashMap;
+use bevy::{
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    transform::TransformSystem,
+};
+use de_camera::{CameraDistance, DistanceSet};
+use de_conf::Palette;
+use de_core::{
+    objects::{Active, ObjectTypeComponent},
+    player::PlayerComponent,
+    state::AppState,
+    visibility::{VisibilityFlags, VisibilitySet},
+};
+use de_objects::SolidObjects;
+use de_terrain::{TerrainCollider, MAX_ELEVATION};
+use de_types::{
+    player::{Player, PlayerRange},
+    projection::{ToAltitude, ToFlat},
+};
+use parry3d::query::Ray;
+
+use crate::{DISTANCE_FLAG_BIT, MAX_VISIBILITY_DISTANCE};
+
+/// Gap in meters between the object footprint and the inner edge of the
+/// ring.
+const RING_MARGIN: f32 = 0.4;
+/// Offset above the terrain surface, stopping z-fighting with the terrain.
+const RING_OFFSET: f32 = 0.05;
+/// Inner radius of the ring relative to its outer radius. Keep this in sync
+/// with ring.wgsl.
+const RING_INNER_RADIUS: f32 = 0.85;
+const RING_VISIBLE_FLAG_BIT: u32 = 0;
+
+pub(crate) struct RingsPlugin;
+
+impl Plugin for RingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<RingMaterial>::default())
+            .add_event::<UpdateRingVisibilityEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostUpdate,
+                (
+                    spawn,
+                    recolor,
+                    (
+                        update_visibility_events,
+                        update_visibility_distance.after(DistanceSet::Update),
+                    )
+                        .before(VisibilitySet::Update),
+                    project.before(TransformSystem::TransformPropagate),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Send this event to show or hide the selection ring drawn on the terrain
+/// below an active object.
+#[derive(Event)]
+pub struct UpdateRingVisibilityEvent {
+    entity: Entity,
+    visible: bool,
+}
+
+impl UpdateRingVisibilityEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - an active object. The event is ignored for other
+    ///   entities.
+    ///
+    /// * `visible` - whether to display the ring.
+    pub fn new(entity: Entity, visible: bool) -> Self {
+        Self { entity, visible }
+    }
+}
+
+#[derive(Resource)]
+struct RingMesh(Handle<Mesh>);
+
+/// Ring materials of individual players.
+#[derive(Resource)]
+struct RingMaterials(AHashMap<Player, Handle<RingMaterial>>);
+
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+struct RingMaterial {
+    #[uniform(0)]
+    color: Color,
+}
+
+impl Material for RingMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/ring.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+}
+
+#[derive(Component)]
+struct RingChild(Entity);
+
+fn setup(
+    mut commands: Commands,
+    palette: Res<Palette>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<RingMaterial>>,
+) {
+    commands.insert_resource(RingMesh(meshes.add(Plane3d::default())));
+
+    let player_materials = PlayerRange::up_to(Player::Player4)
+        .map(|player| {
+            let material = RingMaterial {
+                color: palette.player(player),
+            };
+            (player, materials.add(material))
+        })
+        .collect();
+    commands.insert_resource(RingMaterials(player_materials));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<RingMesh>();
+    commands.remove_resource::<RingMaterials>();
+}
+
+fn spawn(
+    mut commands: Commands,
+    solids: SolidObjects,
+    mesh: Res<RingMesh>,
+    materials: Res<RingMaterials>,
+    entities: Query<(Entity, &ObjectTypeComponent, &PlayerComponent), Added<Active>>,
+) {
+    for (entity, &object_type, &player) in entities.iter() {
+        let radius = ring_radius(solids.get(*object_type).ichnography().radius());
+        let transform = Transform {
+            translation: RING_OFFSET * Vec3::Y,
+            scale: Vec3::new(radius, 1., radius),
+            ..Default::default()
+        };
+
+        let ring_entity = commands
+            .spawn((
+                MaterialMeshBundle::<RingMaterial> {
+                    mesh: mesh.0.clone(),
+                    material: materials.0.get(&*player).unwrap().clone(),
+                    transform,
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                NotShadowCaster,
+                NotShadowReceiver,
+                VisibilityFlags::default(),
+            ))
+            .id();
+
+        commands
+            .entity(entity)
+            .add_child(ring_entity)
+            .insert(RingChild(ring_entity));
+    }
+}
+
+/// Updates ring color of objects whose owner has changed.
+fn recolor(
+    materials: Res<RingMaterials>,
+    parents: Query<(&RingChild, &PlayerComponent), Changed<PlayerComponent>>,
+    mut rings: Query<&mut Handle<RingMaterial>>,
+) {
+    for (child, &player) in parents.iter() {
+        let Ok(mut material) = rings.get_mut(child.0) else {
+            continue;
+        };
+        *material = materials.0.get(&*player).unwrap().clone();
+    }
+}
+
+fn update_visibility_events(
+    parents: Query<&RingChild, With<Active>>,
+    mut rings: Query<&mut VisibilityFlags>,
+    mut events: EventReader<UpdateRingVisibilityEvent>,
+) {
+    for event in events.read() {
+        if let Ok(child) = parents.get(event.entity) {
+            rings
+                .get_mut(child.0)
+                .unwrap()
+                .update_visible(RING_VISIBLE_FLAG_BIT, event.visible);
+        }
+    }
+}
+
+fn update_visibility_distance(
+    parents: Query<(&RingChild, &CameraDistance), Changed<CameraDistance>>,
+    mut rings: Query<&mut VisibilityFlags>,
+) {
+    for (child, distance) in parents.iter() {
+        let invisible = distance.distance() > MAX_VISIBILITY_DISTANCE;
+        let mut flags = rings.get_mut(child.0).unwrap();
+
+        // Do not trigger change detection unnecessarily.
+        if flags.invisible_value(DISTANCE_FLAG_BIT) != invisible {
+            flags.update_invisible(DISTANCE_FLAG_BIT, invisible);
+        }
+    }
+}
+
+/// Places visible rings onto the terrain surface below their objects so that
+/// the rings follow terrain slope and do not float below airborne objects.
+fn project(
+    terrain: TerrainCollider,
+    parents: Query<(Ref<Transform>, &RingChild)>,
+    mut rings: Query<(&mut Transform, Ref<VisibilityFlags>), Without<RingChild>>,
+) {
+    for (parent_transform, child) in parents.iter() {
+        let Ok((mut transform, flags)) = rings.get_mut(child.0) else {
+            continue;
+        };
+        if !flags.visible() || !(parent_transform.is_changed() || flags.is_changed()) {
+            continue;
+        }
+
+        let ray = Ray::new(
+            parent_transform
+                .translation
+                .to_flat()
+                .to_altitude(MAX_ELEVATION)
+                .into(),
+            Vec3::NEG_Y.into(),
+        );
+        let Some(intersection) = terrain.cast_ray_msl(&ray, f32::INFINITY) else {
+            continue;
+        };
+
+        let normal = Vec3::from(intersection.normal);
+        let normal = if normal.y < 0. { -normal } else { normal };
+        let normal = normal.try_normalize().unwrap_or(Vec3::Y);
+        let world = Transform {
+            translation: Vec3::from(ray.point_at(intersection.toi)) + RING_OFFSET * normal,
+            rotation: Quat::from_rotation_arc(Vec3::Y, normal),
+            scale: transform.scale,
+        };
+
+        // The ring is a child of the object, thus the transform is relative
+        // to the object.
+        *transform = Transform::from_matrix(
+            parent_transform.compute_matrix().inverse() * world.compute_matrix(),
+        );
+    }
+}
+
+/// Returns outer radius of the ring drawn around an object whose footprint
+/// has radius `footprint_radius`.
+fn ring_radius(footprint_radius: f32) -> f32 {
+    (footprint_radius + RING_MARGIN) / RING_INNER_RADIUS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_radius() {
+        let radius = ring_radius(2.);
+        assert!(RING_INNER_RADIUS * radius > 2.);
+        assert!(ring_radius(4.) > radius);
+    }
+}
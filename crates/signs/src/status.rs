@@ -0,0 +1,412 @@
+//! Small icons displayed above active objects, each signaling a status of the
+//! object (e.g. low energy).
+//!
+//! Gameplay crates register their statuses with
+//! [`StatusIconsAppExt::register_status_icon`] and toggle them on individual
+//! objects with [`UpdateStatusIconEvent`].
+
+use ahash::AHashMap;
+use bevy::{
+    pbr::{MaterialPipeline, MaterialPipelineKey, NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    reflect::TypePath,
+    render::{
+        mesh::{Indices, MeshVertexAttribute, MeshVertexBufferLayout},
+        render_asset::RenderAssetUsages,
+        render_resource::{
+            AsBindGroup, PrimitiveTopology, RenderPipelineDescriptor, ShaderRef,
+            SpecializedMeshPipelineError, VertexFormat,
+        },
+    },
+};
+use de_camera::{CameraDistance, DistanceSet};
+use de_core::{
+    objects::{Active, ObjectTypeComponent},
+    state::AppState,
+    visibility::{VisibilityFlags, VisibilitySet},
+};
+use de_objects::SolidObjects;
+
+use crate::{DISTANCE_FLAG_BIT, MAX_VISIBILITY_DISTANCE};
+
+/// Vertical distance in meters between the icons and the top of the parent
+/// entity collider.
+const STATUS_HEIGHT: f32 = 2.;
+/// Size of a single icon, in the same units as entity bars.
+const ICON_SIZE: f32 = 0.4;
+/// Horizontal gap between neighboring icons.
+const ICON_GAP: f32 = 0.1;
+/// Vertical offset of the icons relative to the entity bar center.
+const ICON_OFFSET: f32 = 0.45;
+const ICONS_VISIBLE_FLAG_BIT: u32 = 0;
+
+const ATTRIBUTE_POSITION: MeshVertexAttribute =
+    MeshVertexAttribute::new("Position", 923108441, VertexFormat::Float32x2);
+
+pub(crate) struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<StatusMaterial>::default())
+            .init_resource::<StatusRegistry>()
+            .add_event::<UpdateStatusIconEvent>()
+            .add_systems(OnEnter(AppState::AppLoading), load)
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostUpdate,
+                (
+                    spawn,
+                    update_events.in_set(StatusSet::UpdateEvents).after(spawn),
+                    update_visibility_distance
+                        .after(DistanceSet::Update)
+                        .before(VisibilitySet::Update),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub(crate) enum StatusSet {
+    UpdateEvents,
+}
+
+/// A status displayed as an icon above active objects.
+///
+/// The icon image is loaded from `icons/status/{name}.png`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StatusIcon(&'static str);
+
+impl StatusIcon {
+    /// # Arguments
+    ///
+    /// * `name` - unique name of the status.
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    fn path(self) -> String {
+        format!("icons/status/{}.png", self.0)
+    }
+}
+
+pub trait StatusIconsAppExt {
+    /// Registers a status icon. Only registered icons may be displayed.
+    ///
+    /// Icons are displayed in a row, ordered by their registration.
+    fn register_status_icon(&mut self, icon: StatusIcon) -> &mut Self;
+}
+
+impl StatusIconsAppExt for App {
+    fn register_status_icon(&mut self, icon: StatusIcon) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(StatusRegistry::default)
+            .register(icon);
+        self
+    }
+}
+
+/// Send this event to display or hide a status icon above an active object.
+#[derive(Event)]
+pub struct UpdateStatusIconEvent {
+    entity: Entity,
+    icon: StatusIcon,
+    active: bool,
+}
+
+impl UpdateStatusIconEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - an active object. The event is ignored for other
+    ///   entities.
+    ///
+    /// * `icon` - a registered status icon.
+    ///
+    /// * `active` - whether to display the icon.
+    pub fn new(entity: Entity, icon: StatusIcon, active: bool) -> Self {
+        Self {
+            entity,
+            icon,
+            active,
+        }
+    }
+}
+
+/// All registered status icons in the order of their registration.
+#[derive(Resource, Default)]
+struct StatusRegistry(Vec<StatusIcon>);
+
+impl StatusRegistry {
+    fn register(&mut self, icon: StatusIcon) {
+        assert!(
+            !self.0.contains(&icon),
+            "Status icon {icon:?} already registered."
+        );
+        self.0.push(icon);
+    }
+
+    /// Returns the display order of an icon or None if it is not registered.
+    fn order(&self, icon: StatusIcon) -> Option<usize> {
+        self.0.iter().position(|&registered| registered == icon)
+    }
+}
+
+#[derive(Resource)]
+struct StatusImages(AHashMap<StatusIcon, Handle<Image>>);
+
+#[derive(Resource)]
+struct IconMesh(Handle<Mesh>);
+
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
+struct StatusMaterial {
+    #[uniform(0)]
+    offset: Vec2,
+    #[texture(1)]
+    #[sampler(2)]
+    image: Handle<Image>,
+}
+
+impl Material for StatusMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/status.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/status.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayout,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let vertex_layout = layout.get_layout(&[
+            ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_UV_0.at_shader_location(1),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        Ok(())
+    }
+}
+
+/// The parent of all status icons of an object.
+#[derive(Component)]
+struct StatusChild(Entity);
+
+/// Currently displayed statuses of an object, ordered by their registration.
+#[derive(Component, Default)]
+struct ActiveStatuses(Vec<StatusIcon>);
+
+impl ActiveStatuses {
+    /// Displays or hides an icon. Returns true if the statuses changed.
+    fn update(&mut self, registry: &StatusRegistry, icon: StatusIcon, active: bool) -> bool {
+        let position = self.0.iter().position(|&current| current == icon);
+        match (position, active) {
+            (None, true) => {
+                let order = registry.order(icon);
+                let index = self
+                    .0
+                    .iter()
+                    .position(|&current| registry.order(current) > order)
+                    .unwrap_or(self.0.len());
+                self.0.insert(index, icon);
+                true
+            }
+            (Some(index), false) => {
+                self.0.remove(index);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn load(mut commands: Commands, server: Res<AssetServer>, registry: Res<StatusRegistry>) {
+    let images = registry
+        .0
+        .iter()
+        .map(|&icon| (icon, server.load(icon.path())))
+        .collect();
+    commands.insert_resource(StatusImages(images));
+}
+
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.insert_resource(IconMesh(meshes.add(icon_mesh(ICON_SIZE))));
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<IconMesh>();
+}
+
+fn spawn(
+    mut commands: Commands,
+    solids: SolidObjects,
+    entities: Query<(Entity, &ObjectTypeComponent), Added<Active>>,
+) {
+    for (entity, &object_type) in entities.iter() {
+        let height = solids.get(*object_type).collider().aabb().maxs.y + STATUS_HEIGHT;
+        let transform = Transform::from_translation(height * Vec3::Y);
+
+        let mut flags = VisibilityFlags::default();
+        flags.update_visible(ICONS_VISIBLE_FLAG_BIT, true);
+
+        let status_entity = commands
+            .spawn((
+                SpatialBundle {
+                    transform,
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                flags,
+                ActiveStatuses::default(),
+            ))
+            .id();
+
+        commands
+            .entity(entity)
+            .add_child(status_entity)
+            .insert(StatusChild(status_entity));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_events(
+    mut commands: Commands,
+    registry: Res<StatusRegistry>,
+    images: Res<StatusImages>,
+    mesh: Res<IconMesh>,
+    mut materials: ResMut<Assets<StatusMaterial>>,
+    parents: Query<&StatusChild, With<Active>>,
+    mut statuses: Query<&mut ActiveStatuses>,
+    mut events: EventReader<UpdateStatusIconEvent>,
+) {
+    let mut changed = Vec::new();
+    for event in events.read() {
+        debug_assert!(
+            registry.order(event.icon).is_some(),
+            "Status icon {:?} is not registered.",
+            event.icon
+        );
+
+        let Ok(child) = parents.get(event.entity) else {
+            continue;
+        };
+
+        let mut active = statuses.get_mut(child.0).unwrap();
+        if active.update(&registry, event.icon, event.active) && !changed.contains(&child.0) {
+            changed.push(child.0);
+        }
+    }
+
+    for entity in changed {
+        let active = statuses.get(entity).unwrap();
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.despawn_descendants();
+        entity_commands.with_children(|parent| {
+            for (index, &icon) in active.0.iter().enumerate() {
+                let material = StatusMaterial {
+                    offset: icon_offset(index, active.0.len()),
+                    image: images.0.get(&icon).unwrap().clone(),
+                };
+
+                parent.spawn((
+                    MaterialMeshBundle::<StatusMaterial> {
+                        mesh: mesh.0.clone(),
+                        material: materials.add(material),
+                        ..Default::default()
+                    },
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                ));
+            }
+        });
+    }
+}
+
+fn update_visibility_distance(
+    parents: Query<(&StatusChild, &CameraDistance), Changed<CameraDistance>>,
+    mut icons: Query<&mut VisibilityFlags>,
+) {
+    for (child, distance) in parents.iter() {
+        let invisible = distance.distance() > MAX_VISIBILITY_DISTANCE;
+        let mut flags = icons.get_mut(child.0).unwrap();
+
+        // Do not trigger change detection unnecessarily.
+        if flags.invisible_value(DISTANCE_FLAG_BIT) != invisible {
+            flags.update_invisible(DISTANCE_FLAG_BIT, invisible);
+        }
+    }
+}
+
+/// Returns offset of the center of `index`-th icon from a row of `count`
+/// icons.
+fn icon_offset(index: usize, count: usize) -> Vec2 {
+    debug_assert!(index < count);
+    let x = (index as f32 - 0.5 * (count - 1) as f32) * (ICON_SIZE + ICON_GAP);
+    Vec2::new(x, ICON_OFFSET)
+}
+
+fn icon_mesh(size: f32) -> Mesh {
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    mesh.insert_attribute(
+        ATTRIBUTE_POSITION,
+        vec![
+            [-0.5 * size, 0.5 * size],
+            [-0.5 * size, -0.5 * size],
+            [0.5 * size, -0.5 * size],
+            [0.5 * size, 0.5 * size],
+        ],
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_UV_0,
+        vec![[0., 0.], [0., 1.], [1., 1.], [1., 0.]],
+    );
+
+    mesh.insert_indices(Indices::U16(vec![0, 1, 2, 0, 2, 3]));
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_statuses() {
+        let first = StatusIcon::new("first");
+        let second = StatusIcon::new("second");
+        let third = StatusIcon::new("third");
+
+        let mut registry = StatusRegistry::default();
+        registry.register(first);
+        registry.register(second);
+        registry.register(third);
+
+        let mut statuses = ActiveStatuses::default();
+        assert!(statuses.update(&registry, third, true));
+        assert!(statuses.update(&registry, first, true));
+        assert!(!statuses.update(&registry, first, true));
+        assert!(statuses.update(&registry, second, true));
+        assert_eq!(statuses.0, vec![first, second, third]);
+
+        assert!(statuses.update(&registry, second, false));
+        assert!(!statuses.update(&registry, second, false));
+        assert_eq!(statuses.0, vec![first, third]);
+    }
+
+    #[test]
+    fn test_icon_offset() {
+        assert_eq!(icon_offset(0, 1), Vec2::new(0., ICON_OFFSET));
+        assert_eq!(icon_offset(0, 2).x, -icon_offset(1, 2).x);
+        assert_eq!(icon_offset(1, 3).x, 0.);
+    }
+}
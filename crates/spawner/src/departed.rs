@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use ahash::AHashMap;
+use bevy::prelude::*;
+use de_core::{
+    cleanup::DespawnOnGameExit, gconfig::is_multiplayer, player::PlayerComponent, state::AppState,
+};
+use de_gui::{BodyTextCommands, BodyTextOps, GuiCommands, OuterStyle};
+use de_multiplayer::{NetEntityCommands, PeerLeftEvent};
+use de_pathing::ScheduledPath;
+use de_types::player::Player;
+
+use crate::{despawner::DespawnActiveEvent, DespawnerSet};
+
+/// Objects of a player who left a game are kept (frozen) for this long so
+/// that the remaining players are warned before the objects disappear.
+///
+/// Rejoining a game which has already started is not supported by the
+/// connector, therefore the objects are always removed once the period ends.
+const GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+pub(crate) struct DepartedPlugin;
+
+impl Plugin for DepartedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    player_left.run_if(on_event::<PeerLeftEvent>()),
+                    expire.after(player_left).before(DespawnerSet::Despawn),
+                    update_banner.after(expire),
+                )
+                    .run_if(is_multiplayer)
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Players who left the game and whose objects are still kept. The values
+/// are times (since app startup) when the grace period ends.
+#[derive(Resource, Default)]
+struct DepartedPlayers(AHashMap<Player, Duration>);
+
+/// Banner with countdowns of the grace periods.
+#[derive(Component)]
+struct DepartedBanner {
+    text: Entity,
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<DepartedPlayers>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<DepartedPlayers>();
+}
+
+fn player_left(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut departed: ResMut<DepartedPlayers>,
+    mut events: EventReader<PeerLeftEvent>,
+    objects: Query<(Entity, &PlayerComponent)>,
+) {
+    for event in events.read() {
+        let player = event.id();
+        info!(
+            "Keeping objects of departed {player} for {}s.",
+            GRACE_PERIOD.as_secs()
+        );
+        departed.0.insert(player, time.elapsed() + GRACE_PERIOD);
+
+        // Objects of the player are no longer driven by the player, thus
+        // they are stopped.
+        for (entity, &owner) in objects.iter() {
+            if *owner == player {
+                commands.entity(entity).remove::<ScheduledPath>();
+            }
+        }
+    }
+}
+
+fn expire(
+    time: Res<Time>,
+    mut departed: ResMut<DepartedPlayers>,
+    mut net_commands: NetEntityCommands,
+    mut despawn_events: EventWriter<DespawnActiveEvent>,
+) {
+    let now = time.elapsed();
+    departed.0.retain(|&player, &mut deadline| {
+        if now < deadline {
+            return true;
+        }
+
+        info!("Grace period of departed {player} expired.");
+        if let Some(entity_map) = net_commands.remove_player(player) {
+            for entity in entity_map.locals() {
+                despawn_events.send(DespawnActiveEvent::new(entity));
+            }
+        }
+        false
+    });
+}
+
+fn update_banner(
+    mut commands: GuiCommands,
+    time: Res<Time>,
+    departed: Res<DepartedPlayers>,
+    banners: Query<(Entity, &DepartedBanner)>,
+    mut texts: BodyTextOps,
+) {
+    let banner = banners.get_single().ok();
+
+    if departed.0.is_empty() {
+        if let Some((entity, _)) = banner {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let now = time.elapsed();
+    let mut players: Vec<(&Player, &Duration)> = departed.0.iter().collect();
+    players.sort();
+    let text = players
+        .iter()
+        .map(|&(player, deadline)| {
+            format!(
+                "Player {} disconnected, their objects will be removed in {}s.",
+                player.to_num(),
+                deadline.saturating_sub(now).as_secs()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    match banner {
+        Some((_, banner)) => texts.set_text(banner.text, text).unwrap(),
+        None => spawn_banner(&mut commands, text),
+    }
+}
+
+fn spawn_banner(commands: &mut GuiCommands, text: String) {
+    let text = commands
+        .spawn_body_text(
+            OuterStyle {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            text,
+        )
+        .id();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(30.),
+                    right: Val::Percent(30.),
+                    top: Val::Percent(12.),
+                    height: Val::Percent(6.),
+                    padding: UiRect::all(Val::Percent(0.5)),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.7).into(),
+                z_index: ZIndex::Local(10000),
+                ..default()
+            },
+            DepartedBanner { text },
+            DespawnOnGameExit,
+        ))
+        .add_child(text);
+}
@@ -4,15 +4,23 @@ use bevy::ecs::query::QueryFilter;
 use bevy::prelude::*;
 use de_audio::spatial::{PlaySpatialAudioEvent, Sound};
 use de_core::gconfig::GameConfig;
+use de_core::notification::{GameNotification, NotifyEvent};
 use de_core::{objects::ObjectTypeComponent, player::PlayerComponent, state::AppState};
 use de_messages::ToPlayers;
-use de_multiplayer::{
-    NetEntities, NetEntityCommands, NetRecvDespawnActiveEvent, PeerLeftEvent, ToPlayersEvent,
+use de_multiplayer::{NetEntities, NetEntityCommands, NetRecvDespawnActiveEvent, ToPlayersEvent};
+use de_objects::SolidObjects;
+use de_terrain::{DecalKind, SpawnDecalEvent};
+use de_types::{
+    objects::{ActiveObjectType, ObjectType},
+    projection::ToFlat,
 };
-use de_types::objects::{ActiveObjectType, ObjectType};
 
 use crate::{ObjectCounter, SpawnerSet};
 
+/// Size of the crater left after a destroyed object relative to the object
+/// footprint.
+const CRATER_SCALE: f32 = 1.3;
+
 pub(crate) struct DespawnerPlugin;
 
 impl Plugin for DespawnerPlugin {
@@ -24,11 +32,8 @@ impl Plugin for DespawnerPlugin {
                 despawn_active_remote
                     .run_if(on_event::<NetRecvDespawnActiveEvent>())
                     .before(despawn_active),
-                despawn_active_peer_left
-                    .run_if(on_event::<PeerLeftEvent>())
-                    .after(despawn_active_remote)
-                    .before(despawn_active),
                 despawn_active.before(despawn),
+                despawn_inactive.before(despawn),
                 despawn,
             )
                 .run_if(in_state(AppState::InGame))
@@ -37,6 +42,7 @@ impl Plugin for DespawnerPlugin {
         )
         .add_event::<DespawnActiveLocalEvent>()
         .add_event::<DespawnActiveEvent>()
+        .add_event::<DespawnInactiveEvent>()
         .add_event::<DespawnEvent>();
     }
 }
@@ -59,7 +65,27 @@ impl DespawnActiveLocalEvent {
 }
 
 #[derive(Event)]
-struct DespawnActiveEvent(Entity);
+pub(crate) struct DespawnActiveEvent(Entity);
+
+impl DespawnActiveEvent {
+    pub(crate) fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
+
+/// Send this event to despawn an inactive object.
+///
+/// Inactive objects are spawned by all game instances, therefore this event
+/// is not propagated to other players. It has to be sent by all game
+/// instances instead (e.g. based on synchronized health).
+#[derive(Event)]
+pub struct DespawnInactiveEvent(Entity);
+
+impl DespawnInactiveEvent {
+    pub fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
 
 #[derive(Event)]
 struct DespawnEvent(Entity);
@@ -91,26 +117,17 @@ fn despawn_active_remote(
     }
 }
 
-fn despawn_active_peer_left(
-    mut net_commands: NetEntityCommands,
-    mut peer_left_events: EventReader<PeerLeftEvent>,
-    mut event_writer: EventWriter<DespawnActiveEvent>,
-) {
-    for event in peer_left_events.read() {
-        if let Some(entity_map) = net_commands.remove_player(event.id()) {
-            for entity in entity_map.locals() {
-                event_writer.send(DespawnActiveEvent(entity));
-            }
-        }
-    }
-}
-
+#[allow(clippy::too_many_arguments)]
 fn despawn_active(
+    config: Res<GameConfig>,
     mut counter: ResMut<ObjectCounter>,
+    solids: SolidObjects,
     entities: Query<(&PlayerComponent, &ObjectTypeComponent, &Transform)>,
     mut event_reader: EventReader<DespawnActiveEvent>,
     mut event_writer: EventWriter<DespawnEvent>,
     mut play_audio: EventWriter<PlaySpatialAudioEvent>,
+    mut decal_events: EventWriter<SpawnDecalEvent>,
+    mut notify_events: EventWriter<NotifyEvent>,
 ) {
     for event in event_reader.read() {
         let Ok((&player, &object_type, transform)) = entities.get(event.0) else {
@@ -122,6 +139,12 @@ fn despawn_active(
         };
 
         counter.player_mut(*player).update(active_type, -1);
+        if matches!(active_type, ActiveObjectType::Unit(_)) && config.locals().is_playable(*player)
+        {
+            notify_events.send(NotifyEvent::new(GameNotification::unit_lost(
+                transform.translation.to_flat(),
+            )));
+        }
         play_audio.send(PlaySpatialAudioEvent::new(
             match active_type {
                 ActiveObjectType::Building(_) => Sound::DestroyBuilding,
@@ -130,6 +153,24 @@ fn despawn_active(
             transform.translation,
         ));
 
+        let radius = solids.get(*object_type).ichnography().radius();
+        decal_events.send(SpawnDecalEvent::circle(
+            DecalKind::Crater,
+            transform.translation.to_flat(),
+            CRATER_SCALE * radius,
+        ));
+
+        event_writer.send(DespawnEvent(event.0));
+    }
+}
+
+fn despawn_inactive(
+    mut net_commands: NetEntityCommands,
+    mut event_reader: EventReader<DespawnInactiveEvent>,
+    mut event_writer: EventWriter<DespawnEvent>,
+) {
+    for event in event_reader.read() {
+        net_commands.deregister_inactive(event.0);
         event_writer.send(DespawnEvent(event.0));
     }
 }
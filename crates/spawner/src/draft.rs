@@ -14,10 +14,12 @@ use de_core::{
 };
 use de_index::{ColliderWithCache, PreciseIndexSet, QueryCollider, SpatialQuery};
 use de_map::size::MapBounds;
-use de_objects::{AssetCollection, SceneType, Scenes, SolidObjects, EXCLUSION_OFFSET};
+use de_objects::{LodScenes, SolidObjects, EXCLUSION_OFFSET};
+use de_pathing::ExclusionArea;
+use de_terrain::{BuildGridMarker, MarkerSet, MAX_ELEVATION};
 use de_types::{
     objects::{ActiveObjectType, BuildingType, ObjectType},
-    projection::ToFlat,
+    projection::{ToAltitude, ToFlat},
 };
 use parry2d::{
     bounding_volume::{Aabb, BoundingVolume},
@@ -40,7 +42,12 @@ impl Plugin for DraftPlugin {
             .add_systems(Update, new_draft.run_if(in_state(GameState::Playing)))
             .add_systems(
                 PostUpdate,
-                (update_draft, check_draft_loaded, update_draft_colour)
+                (
+                    update_draft,
+                    update_draft_grid.before(MarkerSet::Update),
+                    check_draft_loaded,
+                    update_draft_colour,
+                )
                     .run_if(in_state(GameState::Playing))
                     .after(PreciseIndexSet::Index),
             );
@@ -56,6 +63,7 @@ pub struct DraftBundle {
     visibility: VisibilityBundle,
     draft: DraftAllowed,
     ready: DraftReady,
+    grid: BuildGridMarker,
 }
 
 impl DraftBundle {
@@ -67,6 +75,7 @@ impl DraftBundle {
             visibility: VisibilityBundle::default(),
             draft: DraftAllowed::default(),
             ready: DraftReady::default(),
+            grid: BuildGridMarker::default(),
         }
     }
 }
@@ -84,16 +93,18 @@ impl DraftAllowed {
 struct DraftReady(bool);
 
 type Solids<'w, 's> = SpatialQuery<'w, 's, Entity, Or<(With<StaticSolid>, With<MovableSolid>)>>;
+type StaticSolids<'w, 's> =
+    SpatialQuery<'w, 's, (&'static Transform, &'static ObjectTypeComponent), With<StaticSolid>>;
 
 fn new_draft(
     mut commands: Commands,
     drafts: Query<(Entity, &ObjectTypeComponent), Added<DraftAllowed>>,
-    scenes: Res<Scenes>,
+    lods: Res<LodScenes>,
 ) {
     for (entity, object_type) in drafts.iter() {
         commands.entity(entity).with_children(|parent| {
             parent.spawn(SceneBundle {
-                scene: scenes.get(SceneType::Solid(**object_type)).clone(),
+                scene: lods.get(**object_type, 0).clone(),
                 ..Default::default()
             });
         });
@@ -129,6 +140,64 @@ fn update_draft(
     }
 }
 
+type ChangedStaticSolids<'w, 's> = Query<
+    'w,
+    's,
+    (),
+    (
+        With<StaticSolid>,
+        Or<(Added<StaticSolid>, Changed<Transform>)>,
+    ),
+>;
+
+/// Blocks build grid cells around drafts which intersect exclusion areas of
+/// already placed static objects or which are not fully inside the map.
+///
+/// The grid of a draft is updated after the draft moves or after a static
+/// object is placed, moved or removed.
+fn update_draft_grid(
+    mut drafts: Query<(Ref<Transform>, &mut BuildGridMarker)>,
+    changed: ChangedStaticSolids,
+    mut removed: RemovedComponents<StaticSolid>,
+    solids: StaticSolids,
+    solid_objects: SolidObjects,
+    bounds: Res<MapBounds>,
+) {
+    let map_aabb = bounds.aabb();
+    let solids_changed = removed.read().count() > 0 || !changed.is_empty();
+
+    for (transform, mut grid) in drafts.iter_mut() {
+        if !solids_changed && !transform.is_changed() {
+            continue;
+        }
+
+        let center = transform.translation.to_flat();
+        // Re-center the grid first so that the query below covers its final
+        // position.
+        grid.update(center, |_| false);
+
+        let flat_aabb = grid.aabb().loosened(EXCLUSION_OFFSET);
+        let mut aabb = flat_aabb.to_msl();
+        aabb.maxs.y = MAX_ELEVATION;
+        let exclusions: Vec<ExclusionArea> = solids
+            .query_aabb(&aabb, None)
+            .map(|(transform, &object_type)| {
+                ExclusionArea::from_ichnography(
+                    transform,
+                    solid_objects.get(*object_type).ichnography(),
+                )
+            })
+            .collect();
+
+        grid.update(center, |cell| {
+            !map_aabb.contains(cell)
+                || exclusions
+                    .iter()
+                    .any(|exclusion| exclusion.intersects_aabb(cell))
+        });
+    }
+}
+
 /// Materials for the invalid and valid placing states
 #[derive(Clone, Resource)]
 struct DraftMaterials {
@@ -1,16 +1,59 @@
+use ahash::AHashSet;
 use bevy::prelude::*;
-use de_core::{gamestate::GameState, gconfig::GameConfig, gresult::GameResult, state::AppState};
+use de_core::{
+    gamestate::GameState,
+    gconfig::{is_editor, GameConfig},
+    gresult::GameResult,
+    notification::{GameNotification, NotifyEvent},
+    state::AppState,
+};
+use de_types::player::Player;
 
-use crate::ObjectCounter;
+use crate::{ObjectCounter, Surrendered};
 
 pub(crate) struct GameEndPlugin;
 
 impl Plugin for GameEndPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            PostUpdate,
-            game_end_detection_system.run_if(in_state(GameState::Playing)),
-        );
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostUpdate,
+                (
+                    elimination_detection_system.before(game_end_detection_system),
+                    game_end_detection_system,
+                )
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(is_editor)),
+            );
+    }
+}
+
+/// Players already known to be eliminated from the game.
+#[derive(Resource, Default)]
+struct Eliminated(AHashSet<Player>);
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<Eliminated>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Eliminated>();
+}
+
+fn elimination_detection_system(
+    mut eliminated: ResMut<Eliminated>,
+    counter: Res<ObjectCounter>,
+    surrendered: Res<Surrendered>,
+    mut events: EventWriter<NotifyEvent>,
+) {
+    for (&player, counter) in counter.counters() {
+        if (counter.total() == 0 || surrendered.contains(player)) && eliminated.0.insert(player) {
+            info!("Player {} has been eliminated.", player.to_num());
+            events.send(NotifyEvent::new(GameNotification::player_eliminated(
+                player,
+            )));
+        }
     }
 }
 
@@ -19,20 +62,23 @@ fn game_end_detection_system(
     mut next_state: ResMut<NextState<AppState>>,
     conf: Res<GameConfig>,
     counter: Res<ObjectCounter>,
+    surrendered: Res<Surrendered>,
 ) {
     let mut result = None;
 
-    let (playable, others) =
-        counter
-            .counters()
-            .fold((0, 0), |(playable, others), (&player, counter)| {
-                let total = counter.total();
-                if conf.locals().is_playable(player) {
-                    (playable + total, others)
-                } else {
-                    (playable, others + total)
-                }
-            });
+    // Players who surrendered are eliminated even if some of their objects
+    // were not yet despawned.
+    let (playable, others) = counter
+        .counters()
+        .filter(|(&player, _)| !surrendered.contains(player))
+        .fold((0, 0), |(playable, others), (&player, counter)| {
+            let total = counter.total();
+            if conf.locals().is_playable(player) {
+                (playable + total, others)
+            } else {
+                (playable, others + total)
+            }
+        });
 
     if playable == 0 {
         result = Some(GameResult::finished(false));
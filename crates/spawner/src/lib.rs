@@ -3,22 +3,35 @@
 use bevy::{app::PluginGroupBuilder, prelude::*};
 use counter::CounterPlugin;
 pub use counter::ObjectCounter;
+use departed::DepartedPlugin;
 pub use despawner::{
-    DespawnActiveLocalEvent, DespawnEventsPlugin, DespawnedComponentsEvent, DespawnerSet,
+    DespawnActiveLocalEvent, DespawnEventsPlugin, DespawnInactiveEvent, DespawnedComponentsEvent,
+    DespawnerSet,
 };
 use draft::DraftPlugin;
 pub use draft::{DraftAllowed, DraftBundle};
 use gameend::GameEndPlugin;
+use lod::LodPlugin;
 use spawner::SpawnerPlugin;
-pub use spawner::{SpawnInactiveEvent, SpawnLocalActiveEvent, SpawnerSet};
+pub use spawner::{LocalActiveSpawnedEvent, SpawnInactiveEvent, SpawnLocalActiveEvent, SpawnerSet};
+use surrender::SurrenderPlugin;
+pub use surrender::{SurrenderEvent, Surrendered};
+use tint::TintPlugin;
+pub use transfer::TransferOwnershipEvent;
+use transfer::TransferPlugin;
 
 use crate::despawner::DespawnerPlugin;
 
 mod counter;
+mod departed;
 mod despawner;
 mod draft;
 mod gameend;
+mod lod;
 mod spawner;
+mod surrender;
+mod tint;
+mod transfer;
 
 pub struct SpawnerPluginGroup;
 
@@ -29,6 +42,11 @@ impl PluginGroup for SpawnerPluginGroup {
             .add(SpawnerPlugin)
             .add(DraftPlugin)
             .add(GameEndPlugin)
+            .add(SurrenderPlugin)
+            .add(TransferPlugin)
             .add(DespawnerPlugin)
+            .add(DepartedPlugin)
+            .add(TintPlugin)
+            .add(LodPlugin)
     }
 }
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use de_camera::{CameraDistance, DistanceSet};
+use de_core::{objects::ObjectTypeComponent, state::AppState};
+use de_objects::{LodScenes, ModelLoadedEvent};
+
+pub(crate) struct LodPlugin;
+
+impl Plugin for LodPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                update.after(DistanceSet::Update),
+                refresh.after(update).run_if(on_event::<ModelLoadedEvent>()),
+            )
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+/// Currently displayed detail level of an object. See [`LodScenes`].
+#[derive(Component, Default)]
+pub(crate) struct LevelOfDetail(usize);
+
+type LodQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ObjectTypeComponent,
+        &'static CameraDistance,
+        &'static mut LevelOfDetail,
+        &'static mut Handle<Scene>,
+    ),
+    Changed<CameraDistance>,
+>;
+
+type AllLodQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ObjectTypeComponent,
+        Option<&'static CameraDistance>,
+        &'static mut LevelOfDetail,
+        &'static mut Handle<Scene>,
+    ),
+>;
+
+fn update(lods: Res<LodScenes>, mut objects: LodQuery) {
+    for (&object_type, distance, mut lod, mut scene) in objects.iter_mut() {
+        let level = lods.level(*object_type, distance.distance(), lod.0);
+        if level != lod.0 {
+            lod.0 = level;
+            *scene = lods.get(*object_type, level).clone();
+        }
+    }
+}
+
+/// Replaces placeholders of objects whose models have just been loaded.
+fn refresh(
+    lods: Res<LodScenes>,
+    mut events: EventReader<ModelLoadedEvent>,
+    mut objects: AllLodQuery,
+) {
+    for event in events.read() {
+        for (&object_type, distance, mut lod, mut scene) in objects.iter_mut() {
+            if *object_type != event.object_type() {
+                continue;
+            }
+
+            // Levels of the object changed, the current level is thus
+            // meaningless.
+            lod.0 = distance.map_or(0, |distance| {
+                lods.level(*object_type, distance.distance(), 0)
+            });
+            *scene = lods.get(*object_type, lod.0).clone();
+        }
+    }
+}
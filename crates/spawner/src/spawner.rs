@@ -11,22 +11,27 @@ use de_core::{
 };
 use de_energy::Battery;
 use de_messages::ToPlayers;
-use de_multiplayer::{NetEntities, NetRecvSpawnActiveEvent, ToPlayersEvent};
-use de_objects::{AssetCollection, InitialHealths, SceneType, Scenes, SolidObjects};
+use de_multiplayer::{NetEntities, NetEntityCommands, NetRecvSpawnActiveEvent, ToPlayersEvent};
+use de_objects::{InitialHealths, LodScenes, SightRange, SolidObjects};
 use de_pathing::{PathTarget, UpdateEntityPathEvent};
-use de_terrain::{CircleMarker, MarkerVisibility, RectangleMarker};
+use de_terrain::{CircleMarker, MarkerVisibility, RectangleMarker, SpawnDecalEvent};
 use de_types::{
     objects::{ActiveObjectType, InactiveObjectType, ObjectType},
     player::Player,
+    projection::ToFlat,
 };
 
-use crate::ObjectCounter;
+use crate::{lod::LevelOfDetail, ObjectCounter};
+
+/// Size of building foundations relative to the building footprint.
+const FOUNDATION_SCALE: f32 = 1.2;
 
 pub(crate) struct SpawnerPlugin;
 
 impl Plugin for SpawnerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<SpawnLocalActiveEvent>()
+            .add_event::<LocalActiveSpawnedEvent>()
             .add_event::<SpawnActiveEvent>()
             .add_event::<SpawnInactiveEvent>()
             .add_event::<SpawnEvent>()
@@ -59,6 +64,7 @@ pub struct SpawnLocalActiveEvent {
     transform: Transform,
     player: Player,
     path_target: Option<PathTarget>,
+    source: Option<Entity>,
 }
 
 impl SpawnLocalActiveEvent {
@@ -77,8 +83,49 @@ impl SpawnLocalActiveEvent {
             transform,
             player,
             path_target,
+            source: None,
+        }
+    }
+
+    /// Sets the entity (e.g. a factory) which produced the object. A
+    /// [`LocalActiveSpawnedEvent`] is sent once the object is spawned.
+    pub fn with_source(mut self, source: Entity) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+/// This event is sent when a locally simulated active object produced by
+/// another entity is spawned, see [`SpawnLocalActiveEvent::with_source`].
+#[derive(Event)]
+pub struct LocalActiveSpawnedEvent {
+    entity: Entity,
+    object_type: ActiveObjectType,
+    source: Entity,
+}
+
+impl LocalActiveSpawnedEvent {
+    fn new(entity: Entity, object_type: ActiveObjectType, source: Entity) -> Self {
+        Self {
+            entity,
+            object_type,
+            source,
         }
     }
+
+    /// The newly spawned object.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn object_type(&self) -> ActiveObjectType {
+        self.object_type
+    }
+
+    /// The entity which produced the object.
+    pub fn source(&self) -> Entity {
+        self.source
+    }
 }
 
 #[derive(Event)]
@@ -138,6 +185,7 @@ impl SpawnEvent {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_local_active(
     mut commands: Commands,
     config: Res<GameConfig>,
@@ -146,11 +194,12 @@ fn spawn_local_active(
     mut event_writer: EventWriter<SpawnActiveEvent>,
     mut path_events: EventWriter<UpdateEntityPathEvent>,
     mut net_events: EventWriter<ToPlayersEvent>,
+    mut spawned_events: EventWriter<LocalActiveSpawnedEvent>,
 ) {
     for event in event_reader.read() {
         let mut entity_commands = commands.spawn(Local);
 
-        if config.locals().is_playable(event.player) || cfg!(feature = "godmode") {
+        if config.controls(config.locals().playable(), event.player) || cfg!(feature = "godmode") {
             entity_commands.insert(Playable);
         }
 
@@ -166,6 +215,14 @@ fn spawn_local_active(
             path_events.send(UpdateEntityPathEvent::new(entity, path_target));
         }
 
+        if let Some(source) = event.source {
+            spawned_events.send(LocalActiveSpawnedEvent::new(
+                entity,
+                event.object_type,
+                source,
+            ));
+        }
+
         if config.multiplayer() {
             net_events.send(ToPlayersEvent::new(ToPlayers::Spawn {
                 entity: net_entities.local_net_id(entity),
@@ -178,10 +235,17 @@ fn spawn_local_active(
 }
 
 fn spawn_remote_active(
+    mut commands: Commands,
+    config: Res<GameConfig>,
     mut event_reader: EventReader<NetRecvSpawnActiveEvent>,
     mut event_writer: EventWriter<SpawnActiveEvent>,
 ) {
     for event in event_reader.read() {
+        // Objects of allies might be controlled with shared control.
+        if config.controls(config.locals().playable(), event.player()) {
+            commands.entity(event.entity()).insert(Playable);
+        }
+
         event_writer.send(SpawnActiveEvent::new(
             event.entity(),
             event.object_type(),
@@ -191,6 +255,7 @@ fn spawn_remote_active(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_active(
     mut commands: Commands,
     mut counter: ResMut<ObjectCounter>,
@@ -199,6 +264,7 @@ fn spawn_active(
     mut event_reader: EventReader<SpawnActiveEvent>,
     mut event_writer: EventWriter<SpawnEvent>,
     mut audio_events: EventWriter<PlaySpatialAudioEvent>,
+    mut decal_events: EventWriter<SpawnDecalEvent>,
 ) {
     for event in event_reader.read() {
         counter
@@ -223,6 +289,14 @@ fn spawn_active(
                     RectangleMarker::from_aabb_transform(local_aabb, &event.transform),
                 ));
 
+                let (heading, _, _) = event.transform.rotation.to_euler(EulerRot::YXZ);
+                decal_events.send(SpawnDecalEvent::foundation(
+                    event.entity,
+                    event.transform.translation.to_flat(),
+                    Vec2::from(local_aabb.half_extents()) * FOUNDATION_SCALE,
+                    heading,
+                ));
+
                 audio_events.send(PlaySpatialAudioEvent::new(
                     Sound::Construct,
                     event.transform.translation,
@@ -242,6 +316,38 @@ fn spawn_active(
         if let Some(cannon) = solid.cannon() {
             entity_commands.insert(cannon.clone());
         }
+        if let Some(artillery) = solid.artillery() {
+            entity_commands.insert(artillery.clone());
+        }
+        if let Some(harvester) = solid.harvester() {
+            entity_commands.insert(harvester.clone());
+        }
+        if let Some(point_defense) = solid.point_defense() {
+            entity_commands.insert(point_defense.clone());
+        }
+        if let Some(cloak) = solid.cloak() {
+            entity_commands.insert(cloak.clone());
+        }
+        if let Some(detector) = solid.detector() {
+            entity_commands.insert(detector.clone());
+        }
+        if let Some(garrison) = solid.garrison() {
+            entity_commands.insert(garrison.clone());
+        }
+        if let Some(shield_dome) = solid.shield_dome() {
+            entity_commands.insert(shield_dome.clone());
+        }
+        if let Some(radar) = solid.radar() {
+            entity_commands.insert(radar.clone());
+        }
+        if let Some(regeneration) = solid.regeneration() {
+            entity_commands.insert(regeneration.clone());
+        }
+        if let Some(repair) = solid.repair() {
+            entity_commands.insert(repair.clone());
+        }
+        entity_commands.insert(SightRange::from(solid.vision()));
+        entity_commands.insert(solid.armor());
 
         event_writer.send(SpawnEvent::new(
             entity_commands.id(),
@@ -253,20 +359,32 @@ fn spawn_active(
 
 fn spawn_inactive(
     mut commands: Commands,
+    solids: SolidObjects,
+    healths: Res<InitialHealths>,
+    mut net_commands: NetEntityCommands,
     mut event_reader: EventReader<SpawnInactiveEvent>,
     mut event_writer: EventWriter<SpawnEvent>,
 ) {
     for event in event_reader.read() {
-        let entity = commands.spawn(StaticSolid).id();
+        let object_type = ObjectType::Inactive(event.object_type);
+        let mut entity_commands = commands.spawn(StaticSolid);
+        net_commands.register_inactive(entity_commands.id());
+        if let Some(health) = healths.inactive_health(event.object_type) {
+            entity_commands.insert(health.clone());
+        }
+        if let Some(deposit) = solids.get(object_type).deposit() {
+            entity_commands.insert(deposit.clone());
+        }
+
         event_writer.send(SpawnEvent::new(
-            entity,
-            ObjectType::Inactive(event.object_type),
+            entity_commands.id(),
+            object_type,
             event.transform,
         ));
     }
 }
 
-fn spawn(mut commands: Commands, scenes: Res<Scenes>, mut events: EventReader<SpawnEvent>) {
+fn spawn(mut commands: Commands, lods: Res<LodScenes>, mut events: EventReader<SpawnEvent>) {
     for event in events.read() {
         info!("Spawning object {}", event.object_type);
         let mut entity_commands = commands.entity(event.entity);
@@ -276,7 +394,8 @@ fn spawn(mut commands: Commands, scenes: Res<Scenes>, mut events: EventReader<Sp
             GlobalTransform::from(event.transform),
             VisibilityBundle::default(),
             ObjectTypeComponent::from(event.object_type),
-            scenes.get(SceneType::Solid(event.object_type)).clone(),
+            lods.get(event.object_type, 0).clone(),
+            LevelOfDetail::default(),
             DespawnOnGameExit,
         ));
     }
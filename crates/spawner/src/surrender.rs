@@ -0,0 +1,129 @@
+use ahash::AHashSet;
+use bevy::prelude::*;
+use de_core::{
+    command::{register_command, CommandEvent, CommandInfo},
+    gconfig::GameConfig,
+    player::PlayerComponent,
+    state::AppState,
+};
+use de_gui::ToastEvent;
+use de_messages::ToPlayers;
+use de_multiplayer::{NetRecvSurrenderEvent, ToPlayersEvent};
+use de_types::player::Player;
+
+use crate::{DespawnActiveLocalEvent, DespawnerSet};
+
+const SURRENDER_COMMAND: CommandInfo =
+    CommandInfo::new("surrender", &[], "Surrender the current game.");
+
+pub(crate) struct SurrenderPlugin;
+
+impl Plugin for SurrenderPlugin {
+    fn build(&self, app: &mut App) {
+        register_command(app, SURRENDER_COMMAND);
+
+        app.add_event::<SurrenderEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                Update,
+                (
+                    surrender_command
+                        .run_if(on_event::<CommandEvent>())
+                        .before(surrender_local),
+                    surrender_local
+                        .run_if(on_event::<SurrenderEvent>())
+                        .before(DespawnerSet::Despawn),
+                    surrender_remote.run_if(on_event::<NetRecvSurrenderEvent>()),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Send this event to surrender the game on behalf of the local playable
+/// player. All active objects of the player are despawned and other players
+/// are notified.
+#[derive(Event)]
+pub struct SurrenderEvent;
+
+/// Players who surrendered the current game. These players are considered
+/// eliminated regardless of their remaining objects.
+#[derive(Resource, Default)]
+pub struct Surrendered(AHashSet<Player>);
+
+impl Surrendered {
+    pub fn contains(&self, player: Player) -> bool {
+        self.0.contains(&player)
+    }
+
+    /// Marks a player as surrendered. Returns false if the player has already
+    /// surrendered.
+    fn insert(&mut self, player: Player) -> bool {
+        self.0.insert(player)
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<Surrendered>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<Surrendered>();
+}
+
+fn surrender_command(
+    mut commands: EventReader<CommandEvent>,
+    mut events: EventWriter<SurrenderEvent>,
+) {
+    if commands
+        .read()
+        .any(|command| command.name() == SURRENDER_COMMAND.name())
+    {
+        events.send(SurrenderEvent);
+    }
+}
+
+fn surrender_local(
+    config: Res<GameConfig>,
+    mut surrendered: ResMut<Surrendered>,
+    objects: Query<(Entity, &PlayerComponent)>,
+    mut events: EventReader<SurrenderEvent>,
+    mut despawn_events: EventWriter<DespawnActiveLocalEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    events.clear();
+
+    let player = config.locals().playable();
+    if !surrendered.insert(player) {
+        return;
+    }
+
+    info!("Player {} surrendered.", player.to_num());
+    for (entity, &owner) in objects.iter() {
+        if *owner == player {
+            despawn_events.send(DespawnActiveLocalEvent::new(entity));
+        }
+    }
+
+    if config.multiplayer() {
+        net_events.send(ToPlayersEvent::new(ToPlayers::Surrender));
+    }
+}
+
+fn surrender_remote(
+    mut surrendered: ResMut<Surrendered>,
+    mut events: EventReader<NetRecvSurrenderEvent>,
+    mut toasts: EventWriter<ToastEvent>,
+) {
+    for event in events.read() {
+        let player = event.player();
+        if surrendered.insert(player) {
+            info!("Player {} surrendered.", player.to_num());
+            toasts.send(ToastEvent::new(format!(
+                "Player {} surrendered.",
+                player.to_num()
+            )));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use ahash::AHashMap;
+use bevy::{ecs::system::SystemParam, prelude::*, scene::SceneInstanceReady};
+use de_conf::Palette;
+use de_core::{player::PlayerComponent, state::AppState};
+use de_types::player::Player;
+
+/// Model nodes whose name starts with this prefix (together with all their
+/// descendants) are tinted with the color of the owning player.
+const TINTED_NODE_PREFIX: &str = "PlayerColor";
+
+pub(crate) struct TintPlugin;
+
+impl Plugin for TintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostUpdate,
+                (tint, retint).run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Tinted clones of model materials. Each material is cloned at most once per
+/// player so that objects of the same player share materials.
+#[derive(Resource, Default)]
+struct TintedMaterials {
+    tinted: AHashMap<(AssetId<StandardMaterial>, Player), Handle<StandardMaterial>>,
+    /// Original (untinted) materials of all tinted materials.
+    originals: AHashMap<AssetId<StandardMaterial>, Handle<StandardMaterial>>,
+}
+
+impl TintedMaterials {
+    /// Returns a material tinted for a player.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - either an original material or a material already tinted
+    ///   (for any player).
+    fn get_or_insert(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+        handle: &Handle<StandardMaterial>,
+        player: Player,
+        color: Color,
+    ) -> Option<Handle<StandardMaterial>> {
+        let original = self.originals.get(&handle.id()).unwrap_or(handle).clone();
+
+        if let Some(handle) = self.tinted.get(&(original.id(), player)) {
+            return Some(handle.clone());
+        }
+
+        let mut material = materials.get(&original)?.clone();
+        material.base_color = tinted(material.base_color, color);
+        let handle = materials.add(material);
+        self.tinted.insert((original.id(), player), handle.clone());
+        self.originals.insert(handle.id(), original);
+        Some(handle)
+    }
+}
+
+/// Multiplies a (linear) base color by a tint color. Alpha of the base color
+/// is preserved.
+fn tinted(base: Color, tint: Color) -> Color {
+    let base = base.as_linear_rgba_f32();
+    let tint = tint.as_linear_rgba_f32();
+    Color::rgba_linear(
+        base[0] * tint[0],
+        base[1] * tint[1],
+        base[2] * tint[2],
+        base[3],
+    )
+}
+
+fn setup(mut commands: Commands) {
+    commands.init_resource::<TintedMaterials>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<TintedMaterials>();
+}
+
+fn tint(
+    mut events: EventReader<SceneInstanceReady>,
+    mut tinter: Tinter,
+    players: Query<&PlayerComponent>,
+) {
+    for event in events.read() {
+        let Ok(&player) = players.get(event.parent) else {
+            continue;
+        };
+        tinter.tint(event.parent, *player);
+    }
+}
+
+/// Re-tints objects whose owner has changed since their spawning.
+fn retint(mut tinter: Tinter, players: Query<(Entity, Ref<PlayerComponent>)>) {
+    for (entity, player) in players.iter() {
+        if player.is_changed() && !player.is_added() {
+            tinter.tint(entity, **player);
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct Tinter<'w, 's> {
+    palette: Res<'w, Palette>,
+    tinted_materials: ResMut<'w, TintedMaterials>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    children: Query<'w, 's, &'static Children>,
+    names: Query<'w, 's, &'static Name>,
+    handles: Query<'w, 's, &'static mut Handle<StandardMaterial>>,
+}
+
+impl<'w, 's> Tinter<'w, 's> {
+    /// Tints all player color nodes among descendants of an entity with the
+    /// color of a player.
+    fn tint(&mut self, root: Entity, player: Player) {
+        let color = self.palette.player(player);
+
+        for node in self.children.iter_descendants(root) {
+            if !self
+                .names
+                .get(node)
+                .is_ok_and(|name| name.as_str().starts_with(TINTED_NODE_PREFIX))
+            {
+                continue;
+            }
+
+            for entity in std::iter::once(node).chain(self.children.iter_descendants(node)) {
+                let Ok(mut handle) = self.handles.get_mut(entity) else {
+                    continue;
+                };
+                if let Some(tinted) =
+                    self.tinted_materials
+                        .get_or_insert(&mut self.materials, &handle, player, color)
+                {
+                    *handle = tinted;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tinted() {
+        let color = tinted(
+            Color::rgba_linear(1., 0.5, 0., 0.8),
+            Color::rgba_linear(0.5, 0.5, 1., 0.1),
+        );
+        assert_eq!(color.as_linear_rgba_f32(), [0.5, 0.25, 0., 0.8]);
+    }
+}
@@ -0,0 +1,143 @@
+use ahash::AHashMap;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use de_core::{
+    gconfig::GameConfig,
+    objects::{self, Active, ObjectTypeComponent, Playable},
+    player::PlayerComponent,
+    state::AppState,
+};
+use de_messages::ToPlayers;
+use de_multiplayer::{NetEntities, NetRecvTransferOwnershipEvent, ToPlayersEvent};
+use de_types::{
+    objects::{ActiveObjectType, ObjectType},
+    player::Player,
+};
+
+use crate::{ObjectCounter, SpawnerSet};
+
+pub(crate) struct TransferPlugin;
+
+impl Plugin for TransferPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TransferOwnershipEvent>().add_systems(
+            Update,
+            (
+                transfer_local.run_if(on_event::<TransferOwnershipEvent>()),
+                transfer_remote.run_if(on_event::<NetRecvTransferOwnershipEvent>()),
+            )
+                .after(SpawnerSet::Spawner)
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+/// Send this event to transfer ownership of a locally simulated active object
+/// to another player. The object remains simulated by this game instance.
+#[derive(Event)]
+pub struct TransferOwnershipEvent {
+    entity: Entity,
+    player: Player,
+}
+
+impl TransferOwnershipEvent {
+    /// # Arguments
+    ///
+    /// * `entity` - the transferred active object.
+    ///
+    /// * `player` - the new owner of the object.
+    pub fn new(entity: Entity, player: Player) -> Self {
+        Self { entity, player }
+    }
+}
+
+#[derive(SystemParam)]
+struct OwnershipCommands<'w, 's> {
+    commands: Commands<'w, 's>,
+    config: Res<'w, GameConfig>,
+    counter: ResMut<'w, ObjectCounter>,
+    objects: Query<'w, 's, (&'static PlayerComponent, &'static ObjectTypeComponent), With<Active>>,
+    /// Owners assigned during the current system run. These are not yet
+    /// visible in the ECS.
+    pending: Local<'s, AHashMap<Entity, Player>>,
+}
+
+impl<'w, 's> OwnershipCommands<'w, 's> {
+    /// Changes owner of an active object. Returns false if the entity is not
+    /// an active object or if the object already belongs to the player.
+    fn transfer(&mut self, entity: Entity, player: Player) -> bool {
+        let Ok((&owner, &object_type)) = self.objects.get(entity) else {
+            warn!("Cannot transfer ownership of non-active entity {entity:?}.");
+            return false;
+        };
+        let ObjectType::Active(object_type) = *object_type else {
+            unreachable!("Active entity {entity:?} has inactive object type.");
+        };
+
+        let owner = self.pending.get(&entity).copied().unwrap_or(*owner);
+        if owner == player {
+            return false;
+        }
+
+        self.pending.insert(entity, player);
+        info!("Transferring {object_type} from {owner} to {player}.");
+        self.update_counter(object_type, owner, player);
+
+        let playable = self.config.locals().playable();
+        let mut entity_commands = self.commands.entity(entity);
+        entity_commands.insert(PlayerComponent::from(player));
+        if self.config.controls(playable, player) || cfg!(feature = "godmode") {
+            entity_commands.insert(Playable);
+        } else {
+            entity_commands.remove::<Playable>();
+        }
+
+        true
+    }
+
+    fn update_counter(&mut self, object_type: ActiveObjectType, from: Player, to: Player) {
+        self.counter.player_mut(from).update(object_type, -1);
+        self.counter.player_mut(to).update(object_type, 1);
+    }
+
+    /// Must be called at the end of each system run.
+    fn finish(&mut self) {
+        self.pending.clear();
+    }
+}
+
+fn transfer_local(
+    config: Res<GameConfig>,
+    net_entities: NetEntities,
+    locals: Query<(), With<objects::Local>>,
+    mut ownership: OwnershipCommands,
+    mut events: EventReader<TransferOwnershipEvent>,
+    mut net_events: EventWriter<ToPlayersEvent>,
+) {
+    for event in events.read() {
+        if !locals.contains(event.entity) {
+            warn!(
+                "Cannot transfer ownership of non-local entity {:?}.",
+                event.entity
+            );
+            continue;
+        }
+
+        if ownership.transfer(event.entity, event.player) && config.multiplayer() {
+            net_events.send(ToPlayersEvent::new(ToPlayers::TransferOwnership {
+                entity: net_entities.local_net_id(event.entity),
+                player: event.player,
+            }));
+        }
+    }
+    ownership.finish();
+}
+
+fn transfer_remote(
+    mut ownership: OwnershipCommands,
+    mut events: EventReader<NetRecvTransferOwnershipEvent>,
+) {
+    for event in events.read() {
+        ownership.transfer(event.entity(), event.player());
+    }
+    ownership.finish();
+}
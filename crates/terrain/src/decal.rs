@@ -0,0 +1,612 @@
+//! This module implements pooled decals, i.e. flat textures projected onto
+//! the terrain surface. Decals are used for example for laser scorch marks,
+//! explosion craters or building foundations.
+//!
+//! A fixed number of decal entities is spawned when a game is started. Each
+//! new decal reuses the oldest entity from the pool, thus the number of live
+//! decals never exceeds [`MAX_DECALS`].
+//!
+//! Each decal has its own mesh, vertices of which are placed onto the terrain
+//! surface so that the decal follows slopes of the terrain.
+//!
+//! Building foundations do not fade out and are not recycled. They are kept
+//! in a separate pool and displayed until their building is despawned.
+
+use std::time::Duration;
+
+use bevy::{
+    ecs::entity::Entities,
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    reflect::TypePath,
+    render::{
+        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+        primitives::Aabb,
+        render_asset::RenderAssetUsages,
+        render_resource::{AsBindGroup, ShaderRef},
+    },
+};
+use de_core::{cleanup::DespawnOnGameExit, gamestate::GameState, state::AppState};
+use de_types::projection::{ToAltitude, ToFlat};
+use parry3d::query::Ray;
+
+use crate::{collider::TerrainCollider, MAX_ELEVATION};
+
+/// Maximum number of simultaneously displayed decals.
+const MAX_DECALS: usize = 256;
+/// Offset above terrain surface of the decals, stopping z-fighting with the
+/// terrain.
+const DECAL_OFFSET: f32 = 0.02;
+/// Number of quads along each side of a decal mesh.
+const DECAL_SUBDIVISIONS: u16 = 4;
+
+pub(crate) struct DecalPlugin;
+
+impl Plugin for DecalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<DecalMaterial>::default())
+            .add_event::<SpawnDecalEvent>()
+            .add_systems(OnEnter(AppState::InGame), setup)
+            .add_systems(OnExit(AppState::InGame), cleanup)
+            .add_systems(
+                PostUpdate,
+                (
+                    (spawn, spawn_foundations)
+                        .run_if(on_event::<SpawnDecalEvent>())
+                        .in_set(DecalSet::Spawn),
+                    update.after(DecalSet::Spawn),
+                    release_foundations.before(DecalSet::Spawn),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum DecalSet {
+    Spawn,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecalKind {
+    /// Burnt terrain where a laser hit the ground.
+    Scorch,
+    /// Remains of a destroyed object.
+    Crater,
+    /// Ground prepared for a building.
+    Foundation,
+}
+
+impl DecalKind {
+    /// Time after which the decal completely fades out or None if the decal
+    /// does not fade out.
+    fn lifespan(self) -> Option<Duration> {
+        match self {
+            Self::Scorch => Some(Duration::from_secs(20)),
+            Self::Crater => Some(Duration::from_secs(90)),
+            Self::Foundation => None,
+        }
+    }
+
+    /// Keep this in sync with decal.wgsl.
+    fn shader_id(self) -> u32 {
+        match self {
+            Self::Scorch => 0,
+            Self::Crater => 1,
+            Self::Foundation => 2,
+        }
+    }
+}
+
+/// Send this event to display a new decal on the terrain. The decal
+/// automatically fades out and disappears, except for foundations which are
+/// displayed until their owner is despawned.
+#[derive(Event)]
+pub struct SpawnDecalEvent {
+    kind: DecalKind,
+    position: Vec2,
+    half_size: Vec2,
+    heading: f32,
+    owner: Option<Entity>,
+}
+
+impl SpawnDecalEvent {
+    /// Creates a new event spawning a circular decal.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - type of the decal. Use [`Self::foundation`] for
+    ///   foundations.
+    ///
+    /// * `position` - position of the decal center on the map.
+    ///
+    /// * `radius` - radius of the decal. Must be a positive finite number.
+    pub fn circle(kind: DecalKind, position: Vec2, radius: f32) -> Self {
+        Self::rectangle(kind, position, Vec2::splat(radius), 0.)
+    }
+
+    /// Creates a new event spawning a rectangular decal.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - type of the decal. Use [`Self::foundation`] for
+    ///   foundations.
+    ///
+    /// * `position` - position of the decal center on the map.
+    ///
+    /// * `half_size` - half of the rectangle size. Both coordinates must be
+    ///   positive finite numbers.
+    ///
+    /// * `heading` - rotation (in radians) of the decal around the world-space
+    ///   Y axis (pointing upwards).
+    pub fn rectangle(kind: DecalKind, position: Vec2, half_size: Vec2, heading: f32) -> Self {
+        debug_assert!(kind != DecalKind::Foundation);
+        Self::new(kind, position, half_size, heading, None)
+    }
+
+    /// Creates a new event spawning a rectangular foundation decal. The
+    /// foundation does not fade out, it is displayed until its owner is
+    /// despawned.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - entity of the building standing on the foundation.
+    ///
+    /// * `position` - position of the decal center on the map.
+    ///
+    /// * `half_size` - half of the rectangle size. Both coordinates must be
+    ///   positive finite numbers.
+    ///
+    /// * `heading` - rotation (in radians) of the decal around the world-space
+    ///   Y axis (pointing upwards).
+    pub fn foundation(owner: Entity, position: Vec2, half_size: Vec2, heading: f32) -> Self {
+        Self::new(
+            DecalKind::Foundation,
+            position,
+            half_size,
+            heading,
+            Some(owner),
+        )
+    }
+
+    fn new(
+        kind: DecalKind,
+        position: Vec2,
+        half_size: Vec2,
+        heading: f32,
+        owner: Option<Entity>,
+    ) -> Self {
+        debug_assert!(position.is_finite());
+        debug_assert!(half_size.is_finite());
+        debug_assert!(half_size.cmpgt(Vec2::ZERO).all());
+        debug_assert!(heading.is_finite());
+
+        Self {
+            kind,
+            position,
+            half_size,
+            heading,
+            owner,
+        }
+    }
+
+    /// Places the decal onto the terrain. Vertices of the decal mesh are
+    /// moved to the terrain surface and transform of the decal is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `terrain` - collider of the terrain.
+    ///
+    /// * `mesh` - decal mesh generated with [`generate_decal_mesh`].
+    fn place(&self, terrain: &TerrainCollider, mesh: &mut Mesh) -> Transform {
+        let center_altitude = altitude(terrain, self.position);
+        let transform = Transform {
+            translation: self.position.to_altitude(center_altitude + DECAL_OFFSET),
+            rotation: Quat::from_rotation_y(self.heading),
+            scale: Vec3::new(self.half_size.x, 1., self.half_size.y),
+        };
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("Decal mesh has no 3D vertex positions.");
+        };
+        for position in positions.iter_mut() {
+            let local = Vec3::new(position[0], 0., position[2]);
+            let point = transform.transform_point(local).to_flat();
+            // The decal is rotated around Y axis only and its Y scale is 1,
+            // thus local and world-space height differences are equal.
+            position[1] = altitude(terrain, point) - center_altitude;
+        }
+
+        transform
+    }
+}
+
+/// Returns altitude of the terrain surface at a point on the map.
+fn altitude(terrain: &TerrainCollider, point: Vec2) -> f32 {
+    let ray = Ray::new(point.to_altitude(MAX_ELEVATION).into(), Vec3::NEG_Y.into());
+    terrain
+        .cast_ray_msl(&ray, f32::INFINITY)
+        .map_or(0., |intersection| ray.point_at(intersection.toi).y)
+}
+
+#[derive(Resource)]
+struct DecalPool {
+    entities: Vec<Entity>,
+    next: usize,
+}
+
+impl DecalPool {
+    fn new(entities: Vec<Entity>) -> Self {
+        debug_assert!(!entities.is_empty());
+        Self { entities, next: 0 }
+    }
+
+    /// Returns the least recently used decal entity.
+    fn next(&mut self) -> Entity {
+        let entity = self.entities[self.next];
+        self.next = (self.next + 1) % self.entities.len();
+        entity
+    }
+}
+
+/// Pool of foundation decals. Unlike [`DecalPool`], the pool grows as needed
+/// and a decal is returned to the pool only after its owner is despawned.
+#[derive(Resource, Default)]
+struct FoundationPool {
+    free: Vec<Entity>,
+    /// Owners and their foundation decals.
+    used: Vec<(Entity, Entity)>,
+}
+
+impl FoundationPool {
+    /// Returns an unused decal entity or None if all decals are used.
+    fn take_free(&mut self) -> Option<Entity> {
+        self.free.pop()
+    }
+
+    /// Marks a decal entity as used until its owner is despawned.
+    fn insert(&mut self, owner: Entity, decal: Entity) {
+        self.used.push((owner, decal));
+    }
+
+    /// Returns decals of despawned owners to the pool and returns these
+    /// decals.
+    ///
+    /// # Arguments
+    ///
+    /// * `exists` - returns true if an owner entity still exists.
+    fn release<F>(&mut self, exists: F) -> Vec<Entity>
+    where
+        F: Fn(Entity) -> bool,
+    {
+        let mut released = Vec::new();
+        self.used.retain(|&(owner, decal)| {
+            let keep = exists(owner);
+            if !keep {
+                released.push(decal);
+            }
+            keep
+        });
+        self.free.extend_from_slice(released.as_slice());
+        released
+    }
+}
+
+/// Marker component of foundation decals, see [`FoundationPool`].
+#[derive(Component)]
+struct Foundation;
+
+#[derive(Component, Default)]
+struct Decal {
+    age: Duration,
+    lifespan: Duration,
+}
+
+impl Decal {
+    fn reset(&mut self, lifespan: Duration) {
+        self.age = Duration::ZERO;
+        self.lifespan = lifespan;
+    }
+
+    /// Returns true if the decal has just faded out.
+    fn tick(&mut self, duration: Duration) -> bool {
+        let finished = self.finished();
+        self.age += duration;
+        !finished && self.finished()
+    }
+
+    fn finished(&self) -> bool {
+        self.age >= self.lifespan
+    }
+}
+
+#[derive(Asset, AsBindGroup, TypePath, Debug, Clone, Default)]
+struct DecalMaterial {
+    #[uniform(0)]
+    kind: u32,
+    #[uniform(1)]
+    start_time: f32,
+    #[uniform(2)]
+    lifespan: f32,
+}
+
+impl Material for DecalMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/decal.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<DecalMaterial>>,
+) {
+    let entities = (0..MAX_DECALS)
+        .map(|_| {
+            commands
+                .spawn((
+                    MaterialMeshBundle::<DecalMaterial> {
+                        mesh: meshes.add(generate_decal_mesh()),
+                        material: materials.add(DecalMaterial::default()),
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    },
+                    Decal::default(),
+                    DespawnOnGameExit,
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                ))
+                .id()
+        })
+        .collect();
+
+    commands.insert_resource(DecalPool::new(entities));
+    commands.init_resource::<FoundationPool>();
+}
+
+fn cleanup(mut commands: Commands) {
+    commands.remove_resource::<DecalPool>();
+    commands.remove_resource::<FoundationPool>();
+}
+
+type DecalQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Decal,
+        &'static mut Transform,
+        &'static mut Visibility,
+        &'static Handle<Mesh>,
+        Option<&'static mut Aabb>,
+        &'static Handle<DecalMaterial>,
+    ),
+>;
+
+fn spawn(
+    mut pool: ResMut<DecalPool>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<DecalMaterial>>,
+    time: Res<Time>,
+    terrain: TerrainCollider,
+    mut decals: DecalQuery,
+    mut events: EventReader<SpawnDecalEvent>,
+) {
+    for event in events.read() {
+        // Non-fading decals are handled by spawn_foundations().
+        let Some(lifespan) = event.kind.lifespan() else {
+            continue;
+        };
+
+        let (mut decal, mut transform, mut visibility, mesh, aabb, material) =
+            decals.get_mut(pool.next()).unwrap();
+        let mesh = meshes.get_mut(mesh).unwrap();
+        *transform = event.place(&terrain, mesh);
+        update_aabb(aabb, mesh);
+        decal.reset(lifespan);
+
+        let material = materials.get_mut(material).unwrap();
+        material.kind = event.kind.shader_id();
+        material.start_time = time.elapsed_seconds_wrapped();
+        material.lifespan = lifespan.as_secs_f32();
+
+        *visibility = Visibility::Inherited;
+    }
+}
+
+type FoundationQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut Visibility,
+        &'static Handle<Mesh>,
+        Option<&'static mut Aabb>,
+    ),
+    With<Foundation>,
+>;
+
+fn spawn_foundations(
+    mut commands: Commands,
+    mut pool: ResMut<FoundationPool>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<DecalMaterial>>,
+    terrain: TerrainCollider,
+    mut decals: FoundationQuery,
+    mut events: EventReader<SpawnDecalEvent>,
+) {
+    for event in events.read() {
+        let Some(owner) = event.owner else {
+            continue;
+        };
+
+        let entity = match pool.take_free() {
+            Some(entity) => {
+                let (mut transform, mut visibility, mesh, aabb) = decals.get_mut(entity).unwrap();
+                let mesh = meshes.get_mut(mesh).unwrap();
+                *transform = event.place(&terrain, mesh);
+                update_aabb(aabb, mesh);
+                *visibility = Visibility::Inherited;
+                entity
+            }
+            None => {
+                let mut mesh = generate_decal_mesh();
+                let transform = event.place(&terrain, &mut mesh);
+                commands
+                    .spawn((
+                        MaterialMeshBundle::<DecalMaterial> {
+                            mesh: meshes.add(mesh),
+                            material: materials.add(DecalMaterial {
+                                kind: DecalKind::Foundation.shader_id(),
+                                // Zero lifespan disables fading in the shader.
+                                ..Default::default()
+                            }),
+                            transform,
+                            ..Default::default()
+                        },
+                        Foundation,
+                        DespawnOnGameExit,
+                        NotShadowCaster,
+                        NotShadowReceiver,
+                    ))
+                    .id()
+            }
+        };
+        pool.insert(owner, entity);
+    }
+}
+
+fn release_foundations(
+    entities: &Entities,
+    mut pool: ResMut<FoundationPool>,
+    mut decals: Query<&mut Visibility, With<Foundation>>,
+) {
+    for entity in pool.release(|owner| entities.contains(owner)) {
+        *decals.get_mut(entity).unwrap() = Visibility::Hidden;
+    }
+}
+
+fn update(time: Res<Time>, mut decals: Query<(&mut Decal, &mut Visibility)>) {
+    for (mut decal, mut visibility) in decals.iter_mut() {
+        if decal.tick(time.delta()) {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+/// Updates AABB of a decal whose mesh vertices have moved. Bevy computes the
+/// AABB only once, thus it would not cover the bent mesh otherwise. Decals
+/// without AABB get it computed by Bevy from the already updated mesh.
+fn update_aabb(aabb: Option<Mut<Aabb>>, mesh: &Mesh) {
+    if let (Some(mut aabb), Some(new)) = (aabb, mesh.compute_aabb()) {
+        *aabb = new;
+    }
+}
+
+/// This generates a flat square decal mesh spanning from -1 to 1 along X and
+/// Z axes. The mesh is subdivided so that it can be bent along the terrain
+/// surface.
+fn generate_decal_mesh() -> Mesh {
+    let vertices_per_side = DECAL_SUBDIVISIONS + 1;
+
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    for row in 0..vertices_per_side {
+        for column in 0..vertices_per_side {
+            let uv = Vec2::new(column as f32, row as f32) / DECAL_SUBDIVISIONS as f32;
+            let point = 2. * uv - Vec2::ONE;
+            positions.push([point.x, 0., point.y]);
+            uvs.push(uv.to_array());
+        }
+    }
+    let normals = vec![[0., 1., 0.]; positions.len()];
+
+    let mut indices = Vec::new();
+    for row in 0..DECAL_SUBDIVISIONS {
+        for column in 0..DECAL_SUBDIVISIONS {
+            let index = row * vertices_per_side + column;
+            indices.extend([
+                index,
+                index + vertices_per_side,
+                index + vertices_per_side + 1,
+                index,
+                index + vertices_per_side + 1,
+                index + 1,
+            ]);
+        }
+    }
+
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U16(indices));
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool() {
+        let mut pool = DecalPool::new(vec![
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        ]);
+        assert_eq!(pool.next(), Entity::from_raw(1));
+        assert_eq!(pool.next(), Entity::from_raw(2));
+        assert_eq!(pool.next(), Entity::from_raw(3));
+        assert_eq!(pool.next(), Entity::from_raw(1));
+    }
+
+    #[test]
+    fn test_foundation_pool() {
+        let mut pool = FoundationPool::default();
+        assert!(pool.take_free().is_none());
+
+        pool.insert(Entity::from_raw(1), Entity::from_raw(11));
+        pool.insert(Entity::from_raw(2), Entity::from_raw(12));
+        assert!(pool.release(|_| true).is_empty());
+        assert!(pool.take_free().is_none());
+
+        let released = pool.release(|owner| owner != Entity::from_raw(1));
+        assert_eq!(released, vec![Entity::from_raw(11)]);
+        assert_eq!(pool.take_free(), Some(Entity::from_raw(11)));
+        assert!(pool.take_free().is_none());
+    }
+
+    #[test]
+    fn test_decal_mesh() {
+        let mesh = generate_decal_mesh();
+        assert_eq!(mesh.count_vertices(), 25);
+        assert_eq!(mesh.indices().unwrap().len(), 96);
+
+        let aabb = mesh.compute_aabb().unwrap();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(-1., 0., -1.));
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(1., 0., 1.));
+    }
+
+    #[test]
+    fn test_decal() {
+        let mut decal = Decal::default();
+        assert!(decal.finished());
+        assert!(!decal.tick(Duration::from_secs(1)));
+
+        decal.reset(Duration::from_secs(2));
+        assert!(!decal.finished());
+        assert!(!decal.tick(Duration::from_millis(1500)));
+        assert!(decal.tick(Duration::from_millis(600)));
+        assert!(decal.finished());
+        assert!(!decal.tick(Duration::from_secs(1)));
+    }
+}
@@ -1,4 +1,5 @@
 mod collider;
+mod decal;
 mod marker;
 mod plugin;
 mod shader;
@@ -6,8 +7,10 @@ mod terrain;
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
 pub use collider::TerrainCollider;
+use decal::DecalPlugin;
+pub use decal::{DecalKind, DecalSet, SpawnDecalEvent};
 use marker::MarkerPlugin;
-pub use marker::{CircleMarker, MarkerVisibility, RectangleMarker};
+pub use marker::{BuildGridMarker, CircleMarker, MarkerSet, MarkerVisibility, RectangleMarker};
 use plugin::TerrainPlugin;
 pub use terrain::TerrainBundle;
 
@@ -20,5 +23,6 @@ impl PluginGroup for TerrainPluginGroup {
         PluginGroupBuilder::start::<Self>()
             .add(TerrainPlugin)
             .add(MarkerPlugin)
+            .add(DecalPlugin)
     }
 }
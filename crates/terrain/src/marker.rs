@@ -11,13 +11,26 @@ use de_core::{
     frustum, gamestate::GameState, objects::ObjectTypeComponent, visibility::VisibilityFlags,
 };
 use de_objects::SolidObjects;
-use de_types::projection::ToFlat;
+use de_types::projection::{ToAltitude, ToFlat};
 use glam::Vec3A;
-use parry2d::bounding_volume::Aabb;
+use parry2d::{bounding_volume::Aabb, math::Point};
+use parry3d::query::Ray;
 
-use crate::shader::{Circle, Rectangle, TerrainMaterial, CIRCLE_CAPACITY, RECTANGLE_CAPACITY};
+use crate::{
+    collider::TerrainCollider,
+    shader::{
+        BuildGrid, Circle, Rectangle, TerrainMaterial, BUILD_GRID_SIZE, CIRCLE_CAPACITY,
+        RECTANGLE_CAPACITY,
+    },
+    MAX_ELEVATION,
+};
 
 const RECTANGLE_MARKER_MARGIN: f32 = 1.;
+/// Size (in meters) of a single build grid cell.
+const BUILD_GRID_CELL_SIZE: f32 = 2.;
+/// Build grid cells whose terrain surface is inclined by more than this angle
+/// (in radians) are blocked.
+const MAX_BUILD_SLOPE: f32 = 0.3;
 
 pub(crate) struct MarkerPlugin;
 
@@ -28,13 +41,21 @@ impl Plugin for MarkerPlugin {
             (
                 update_markers::<CircleMarker>,
                 update_markers::<RectangleMarker>,
+                update_build_grid,
             )
                 .run_if(in_state(GameState::Playing))
+                .in_set(MarkerSet::Update)
                 .after(VisibilitySystems::CheckVisibility),
         );
     }
 }
 
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, SystemSet)]
+pub enum MarkerSet {
+    /// Terrain markers are propagated to the terrain material in this set.
+    Update,
+}
+
 /// A component representing the visibility of a terrain marker.
 #[derive(Component, Default)]
 pub struct MarkerVisibility(pub VisibilityFlags);
@@ -114,6 +135,77 @@ impl Marker for RectangleMarker {
     }
 }
 
+/// This component configures a grid of square cells drawn on the terrain
+/// surface around the entity. Each cell is drawn either as buildable or as
+/// blocked.
+///
+/// The grid is aligned with the map axes and its cells are snapped to
+/// multiples of the cell size, thus the grid does not "swim" when the entity
+/// moves. Cells on too steep terrain are blocked automatically, other cells
+/// can be blocked with [`Self::update`].
+///
+/// Only a single grid is drawn at a time.
+#[derive(Component)]
+pub struct BuildGridMarker {
+    origin: Vec2,
+    blocked: Vec<bool>,
+}
+
+impl BuildGridMarker {
+    /// Re-centers the grid and recomputes its blocked cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - position of the entity on the map. The grid is centered
+    ///   on the closest cell corner.
+    ///
+    /// * `blocked` - returns true if a cell with given map AABB is blocked.
+    pub fn update<F>(&mut self, center: Vec2, mut blocked: F)
+    where
+        F: FnMut(&Aabb) -> bool,
+    {
+        let half_size = 0.5 * BUILD_GRID_SIZE as f32 * BUILD_GRID_CELL_SIZE;
+        self.origin =
+            (center / BUILD_GRID_CELL_SIZE).round() * BUILD_GRID_CELL_SIZE - Vec2::splat(half_size);
+
+        for (index, aabb) in self.cells() {
+            self.blocked[index] = blocked(&aabb);
+        }
+    }
+
+    /// Returns map AABB of the whole grid.
+    pub fn aabb(&self) -> Aabb {
+        let size = BUILD_GRID_SIZE as f32 * BUILD_GRID_CELL_SIZE;
+        Aabb::new(
+            Point::from(self.origin),
+            Point::from(self.origin + Vec2::splat(size)),
+        )
+    }
+
+    /// Returns an iterator over indices and map AABBs of all grid cells.
+    fn cells(&self) -> impl Iterator<Item = (usize, Aabb)> {
+        let origin = self.origin;
+        (0..BUILD_GRID_SIZE * BUILD_GRID_SIZE).map(move |index| {
+            let cell = Vec2::new(
+                (index % BUILD_GRID_SIZE) as f32,
+                (index / BUILD_GRID_SIZE) as f32,
+            );
+            let mins = origin + cell * BUILD_GRID_CELL_SIZE;
+            let maxs = mins + Vec2::splat(BUILD_GRID_CELL_SIZE);
+            (index, Aabb::new(Point::from(mins), Point::from(maxs)))
+        })
+    }
+}
+
+impl Default for BuildGridMarker {
+    fn default() -> Self {
+        Self {
+            origin: Vec2::ZERO,
+            blocked: vec![false; BUILD_GRID_SIZE * BUILD_GRID_SIZE],
+        }
+    }
+}
+
 fn update_markers<M>(
     mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, TerrainMaterial>>>,
     solids: SolidObjects,
@@ -183,3 +275,80 @@ fn update_markers<M>(
         M::apply_to_material(&mut material.extension, shapes.clone());
     }
 }
+
+fn update_build_grid(
+    mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, TerrainMaterial>>>,
+    terrain: TerrainCollider,
+    terrains: Query<&Handle<ExtendedMaterial<StandardMaterial, TerrainMaterial>>>,
+    changed: Query<(), Changed<BuildGridMarker>>,
+    mut removed: RemovedComponents<BuildGridMarker>,
+    markers: Query<&BuildGridMarker>,
+) {
+    let removed = removed.read().count() > 0;
+    if changed.is_empty() && !removed {
+        return;
+    }
+
+    let grid = match markers.iter().next() {
+        Some(marker) => {
+            let min_normal_y = MAX_BUILD_SLOPE.cos();
+            let mut grid = BuildGrid::new(marker.origin, BUILD_GRID_CELL_SIZE);
+
+            for (index, aabb) in marker.cells() {
+                let blocked = marker.blocked[index] || {
+                    let center = Vec2::from(aabb.center());
+                    let ray =
+                        Ray::new(center.to_altitude(MAX_ELEVATION).into(), Vec3::NEG_Y.into());
+                    terrain
+                        .cast_ray_msl(&ray, f32::INFINITY)
+                        .is_some_and(|intersection| intersection.normal.y < min_normal_y)
+                };
+
+                if blocked {
+                    grid.block(index);
+                }
+            }
+
+            grid
+        }
+        None => BuildGrid::disabled(),
+    };
+
+    for material in terrains.iter() {
+        let material = materials.get_mut(material).unwrap();
+        material.extension.set_build_grid(grid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_grid_marker() {
+        let mut marker = BuildGridMarker::default();
+        marker.update(Vec2::new(10.9, -3.2), |aabb| aabb.mins.x < 0.);
+
+        let half_size = 0.5 * BUILD_GRID_SIZE as f32 * BUILD_GRID_CELL_SIZE;
+        assert_eq!(marker.origin, Vec2::new(10. - half_size, -4. - half_size));
+
+        let aabb = marker.aabb();
+        assert_eq!(Vec2::from(aabb.center()), Vec2::new(10., -4.));
+        assert_eq!(Vec2::from(aabb.extents()), Vec2::splat(2. * half_size));
+
+        let cells: Vec<(usize, Aabb)> = marker.cells().collect();
+        assert_eq!(cells.len(), BUILD_GRID_SIZE * BUILD_GRID_SIZE);
+        assert_eq!(
+            cells[1].1.mins,
+            Point::new(12. - half_size, -4. - half_size)
+        );
+        assert_eq!(
+            cells[BUILD_GRID_SIZE].1.mins,
+            Point::new(10. - half_size, -2. - half_size)
+        );
+
+        for (index, aabb) in cells {
+            assert_eq!(marker.blocked[index], aabb.mins.x < 0.);
+        }
+    }
+}
@@ -6,7 +6,7 @@ use bevy::{
     reflect::TypePath,
     render::render_resource::{AsBindGroup, ShaderRef, ShaderType},
 };
-use glam::{Mat3, Vec2};
+use glam::{Mat3, UVec4, Vec2};
 
 pub(crate) const UV_SCALE: f32 = 16.;
 // * Keep this in sync with terrain.wgsl.
@@ -15,6 +15,11 @@ pub(crate) const CIRCLE_CAPACITY: usize = 127;
 // * Keep this in sync with terrain.wgsl.
 // * Keep this smaller or equal to de_types::objects::PLAYER_MAX_BUILDINGS.
 pub(crate) const RECTANGLE_CAPACITY: usize = 31;
+// * Keep this in sync with terrain.wgsl.
+// * Square of this must be a multiple of 128 so that the grid is exactly
+//   representable as an array of vec4<u32> bit masks.
+pub(crate) const BUILD_GRID_SIZE: usize = 32;
+const BUILD_GRID_MASKS: usize = BUILD_GRID_SIZE * BUILD_GRID_SIZE / 128;
 
 #[derive(Asset, AsBindGroup, TypePath, Debug, Clone)]
 pub struct TerrainMaterial {
@@ -24,6 +29,8 @@ pub struct TerrainMaterial {
     circles: KdTree,
     #[uniform(102)]
     rectangles: Rectangles,
+    #[uniform(103)]
+    build_grid: BuildGrid,
 }
 
 impl TerrainMaterial {
@@ -32,6 +39,7 @@ impl TerrainMaterial {
             uv_scale,
             circles: KdTree::empty(),
             rectangles: Rectangles::default(),
+            build_grid: BuildGrid::default(),
         }
     }
 
@@ -42,6 +50,10 @@ impl TerrainMaterial {
     pub(crate) fn set_rectangle_markers(&mut self, rectangles: Vec<Rectangle>) {
         self.rectangles.set_rectangles(rectangles);
     }
+
+    pub(crate) fn set_build_grid(&mut self, build_grid: BuildGrid) {
+        self.build_grid = build_grid;
+    }
 }
 
 impl MaterialExtension for TerrainMaterial {
@@ -248,6 +260,62 @@ impl Default for Rectangles {
     }
 }
 
+/// Grid of square cells, aligned with the map axes, each of them displayed
+/// either as buildable or as blocked.
+#[derive(ShaderType, Debug, Clone, Copy, Default)]
+pub(crate) struct BuildGrid {
+    /// Map coordinates of the grid corner with minimum coordinates.
+    origin: Vec2,
+    cell_size: f32,
+    /// Set to 0 if the grid is not displayed.
+    enabled: u32,
+    /// Bit mask of blocked cells. Cell at column `c` and row `r` is stored at
+    /// bit `r * BUILD_GRID_SIZE + c`.
+    blocked: [UVec4; BUILD_GRID_MASKS],
+}
+
+impl BuildGrid {
+    /// Creates a new enabled grid with all cells buildable.
+    ///
+    /// # Panics
+    ///
+    /// * If `origin` is not finite.
+    /// * If `cell_size` is non finite or is smaller or equal to zero.
+    pub(crate) fn new(origin: Vec2, cell_size: f32) -> Self {
+        if !origin.is_finite() {
+            panic!("Build grid origin is not finite: {origin:?}");
+        }
+        if !cell_size.is_finite() {
+            panic!("Build grid cell size is not finite: {cell_size:?}");
+        }
+        if cell_size <= 0. {
+            panic!("Build grid cell size is smaller or equal to 0: {cell_size:?}");
+        }
+
+        Self {
+            origin,
+            cell_size,
+            enabled: 1,
+            blocked: [UVec4::ZERO; BUILD_GRID_MASKS],
+        }
+    }
+
+    /// Creates a new disabled, i.e. not displayed, grid.
+    pub(crate) fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Marks a cell as blocked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than the number of grid cells.
+    pub(crate) fn block(&mut self, index: usize) {
+        assert!(index < BUILD_GRID_SIZE * BUILD_GRID_SIZE);
+        self.blocked[index / 128][(index / 32) % 4] |= 1 << (index % 32);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -303,4 +371,22 @@ mod tests {
             assert_eq!(tree.nodes[5].center, Vec2::new(1.5, 0.));
         }
     }
+
+    #[test]
+    fn test_build_grid_block() {
+        let mut grid = BuildGrid::new(Vec2::new(1., 2.), 2.);
+        assert_eq!(grid.enabled, 1);
+        assert!(grid.blocked.iter().all(|mask| *mask == UVec4::ZERO));
+
+        grid.block(0);
+        grid.block(33);
+        grid.block(130);
+        grid.block(BUILD_GRID_SIZE * BUILD_GRID_SIZE - 1);
+        assert_eq!(grid.blocked[0], UVec4::new(1, 2, 0, 0));
+        assert_eq!(grid.blocked[1], UVec4::new(4, 0, 0, 0));
+        assert_eq!(
+            grid.blocked[BUILD_GRID_MASKS - 1],
+            UVec4::new(0, 0, 0, 1 << 31)
+        );
+    }
 }
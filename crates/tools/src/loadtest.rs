@@ -0,0 +1,393 @@
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use async_std::{future::timeout, task};
+use clap::ValueEnum;
+use de_messages::{
+    ChatMessage, FromGame, FromPlayers, FromServer, MapHashNet, ToGame, ToPlayers, ToServer,
+};
+use de_net::{
+    startup, ConnErrorReceiver, OutPackage, PackageReceiver, PackageSender, Peers, Reliability,
+    Socket,
+};
+use de_types::player::Player;
+
+/// Maximum time to wait for a server response during game setup.
+const SETUP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Time to wait for in-flight messages after all clients stop sending.
+const DRAIN_PERIOD: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum LoadReliability {
+    Unreliable,
+    Unordered,
+    SemiOrdered,
+}
+
+impl From<LoadReliability> for Reliability {
+    fn from(reliability: LoadReliability) -> Self {
+        match reliability {
+            LoadReliability::Unreliable => Self::Unreliable,
+            LoadReliability::Unordered => Self::Unordered,
+            LoadReliability::SemiOrdered => Self::SemiOrdered,
+        }
+    }
+}
+
+pub struct LoadTestConfig {
+    /// Address of the main server of the connector.
+    pub server: SocketAddr,
+    /// Total number of simulated clients.
+    pub clients: usize,
+    /// Maximum number of clients joined to a single game.
+    pub game_size: u8,
+    /// Number of messages sent by each client per second.
+    pub rate: f64,
+    pub duration: Duration,
+    pub reliability: Reliability,
+}
+
+pub fn execute(config: LoadTestConfig) {
+    assert!(
+        (2..=Player::MAX_PLAYERS).contains(&(config.game_size as usize)),
+        "Game size must be between 2 and {}.",
+        Player::MAX_PLAYERS
+    );
+    assert!(config.rate > 0., "Message rate must be positive.");
+    let sizes = game_sizes(config.clients, config.game_size as usize).unwrap_or_else(|| {
+        panic!(
+            "{} clients cannot be split into games of 2 to {} clients.",
+            config.clients, config.game_size
+        )
+    });
+
+    task::block_on(run(config, sizes));
+}
+
+/// Splits clients into as few games as possible with (almost) equal number of
+/// clients. None is returned if there would be a game with less than two
+/// clients.
+fn game_sizes(clients: usize, max_size: usize) -> Option<Vec<usize>> {
+    let games = clients.div_ceil(max_size);
+    if games == 0 || clients / games < 2 {
+        return None;
+    }
+
+    // The first `clients % games` games get one extra client.
+    Some(
+        (0..games)
+            .map(|index| clients / games + usize::from(index < clients % games))
+            .collect(),
+    )
+}
+
+async fn run(config: LoadTestConfig, sizes: Vec<usize>) {
+    let mut games = Vec::with_capacity(sizes.len());
+    for size in sizes {
+        games.push(open_game(config.server, size).await);
+    }
+    println!(
+        "Connected {} clients to {} games.",
+        config.clients,
+        games.len()
+    );
+
+    let start = Instant::now();
+    let interval = Duration::from_secs_f64(1. / config.rate);
+    let duration = config.duration;
+    let reliability = config.reliability;
+
+    let mut handles = Vec::with_capacity(config.clients);
+    for game in games {
+        let peers = game.len() - 1;
+        for client in game {
+            handles.push(task::spawn(async move {
+                let stats = client.load(start, interval, duration, reliability).await;
+                (peers, stats)
+            }));
+        }
+    }
+
+    let mut report = Report::default();
+    for handle in handles {
+        let (peers, stats) = handle.await;
+        report.add(peers, stats);
+    }
+    report.print();
+}
+
+/// Opens a new game on the server and joins `size` clients to it.
+async fn open_game(server: SocketAddr, size: usize) -> Vec<Client> {
+    let map_hash = MapHashNet::from([0; 32]);
+
+    let mut host = Client::connect(server).await;
+    host.send(
+        server,
+        &ToServer::OpenGame {
+            max_players: u8::try_from(size).unwrap().try_into().unwrap(),
+            map_hash,
+            countdown: 0,
+            description: None,
+        },
+        Peers::Server,
+        Reliability::SemiOrdered,
+    )
+    .await;
+    let port = host
+        .expect(server, |message: FromServer| match message {
+            FromServer::GameOpened { port } => Some(port),
+            _ => None,
+        })
+        .await;
+
+    let game = SocketAddr::new(server.ip(), port);
+    host.game = Some(game);
+    host.expect(game, |message: FromGame| match message {
+        FromGame::Joined(_) => Some(()),
+        _ => None,
+    })
+    .await;
+
+    let mut clients = vec![host];
+    for _ in 1..size {
+        let mut client = Client::connect(server).await;
+        client.game = Some(game);
+        client
+            .send(
+                game,
+                &ToGame::Join(map_hash),
+                Peers::Server,
+                Reliability::SemiOrdered,
+            )
+            .await;
+        client
+            .expect(game, |message: FromGame| match message {
+                FromGame::Joined(_) => Some(()),
+                FromGame::JoinError(error) => panic!("Joining game failed: {error:?}"),
+                _ => None,
+            })
+            .await;
+        clients.push(client);
+    }
+
+    clients
+}
+
+struct Client {
+    game: Option<SocketAddr>,
+    sender: PackageSender,
+    receiver: PackageReceiver,
+    errors: ConnErrorReceiver,
+}
+
+impl Client {
+    async fn connect(server: SocketAddr) -> Self {
+        let socket = match Socket::bind(None).await {
+            Ok(socket) => socket,
+            Err(error) => panic!("Failed to bind a socket: {error:?}"),
+        };
+        let (sender, receiver, errors) = startup(
+            |t| {
+                task::spawn(t);
+            },
+            socket,
+        );
+        // Make sure that the server is reachable before continuing.
+        let client = Self {
+            game: None,
+            sender,
+            receiver,
+            errors,
+        };
+        client
+            .send(
+                server,
+                &ToServer::Ping(0),
+                Peers::Server,
+                Reliability::SemiOrdered,
+            )
+            .await;
+        client
+            .expect(server, |message: FromServer| match message {
+                FromServer::Pong(0) => Some(()),
+                _ => None,
+            })
+            .await;
+        client
+    }
+
+    async fn send<E: bincode::Encode>(
+        &self,
+        target: SocketAddr,
+        message: &E,
+        peers: Peers,
+        reliability: Reliability,
+    ) {
+        let package = OutPackage::encode_single(message, reliability, peers, target).unwrap();
+        self.sender.send(package).await.unwrap();
+    }
+
+    /// Waits for a message from `source` server accepted by `filter` and
+    /// returns the filter result. Other messages are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no such message is received within [`SETUP_TIMEOUT`].
+    async fn expect<M, T, F>(&self, source: SocketAddr, filter: F) -> T
+    where
+        M: bincode::Decode,
+        F: Fn(M) -> Option<T>,
+    {
+        let deadline = Instant::now() + SETUP_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Ok(package) = timeout(remaining, self.receiver.recv()).await else {
+                panic!("Server did not respond in time.");
+            };
+            let package = package.unwrap();
+            if package.peers() != Peers::Server || package.source() != source {
+                continue;
+            }
+
+            for message in package.decode::<M>() {
+                let Ok(message) = message else {
+                    // Message of a different type.
+                    break;
+                };
+                if let Some(result) = filter(message) {
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Sends timestamped chat messages to other players of the game while
+    /// receiving their messages.
+    async fn load(
+        self,
+        start: Instant,
+        interval: Duration,
+        duration: Duration,
+        reliability: Reliability,
+    ) -> ClientStats {
+        let game = self.game.unwrap();
+        let sending_end = Instant::now() + duration;
+        let receiving_end = sending_end + DRAIN_PERIOD;
+
+        let mut stats = ClientStats::default();
+        let mut next_send = Instant::now();
+
+        loop {
+            let now = Instant::now();
+            if now >= receiving_end {
+                break;
+            }
+
+            if now >= next_send && now < sending_end {
+                let text = format!("{}", start.elapsed().as_micros());
+                let message = ToPlayers::Chat(ChatMessage::try_from(text).unwrap());
+                self.send(game, &message, Peers::Players, reliability).await;
+                stats.sent += 1;
+                next_send += interval;
+                continue;
+            }
+
+            while self.errors.try_recv().is_ok() {
+                stats.failures += 1;
+            }
+
+            let wait = if now < sending_end {
+                next_send.saturating_duration_since(now)
+            } else {
+                receiving_end - now
+            };
+            let Ok(package) = timeout(wait, self.receiver.recv()).await else {
+                continue;
+            };
+            let package = package.unwrap();
+            if package.peers() != Peers::Players {
+                continue;
+            }
+
+            let received = start.elapsed();
+            for message in package.decode::<FromPlayers>() {
+                let Ok(message) = message else {
+                    stats.invalid += 1;
+                    continue;
+                };
+                let ToPlayers::Chat(chat) = message.message() else {
+                    continue;
+                };
+                match chat.text().parse::<u64>() {
+                    Ok(sent) => {
+                        stats.received += 1;
+                        stats
+                            .latencies
+                            .push(received.saturating_sub(Duration::from_micros(sent)));
+                    }
+                    Err(_) => stats.invalid += 1,
+                }
+            }
+        }
+
+        stats
+    }
+}
+
+#[derive(Default)]
+struct ClientStats {
+    sent: u64,
+    received: u64,
+    invalid: u64,
+    /// Number of reliably sent packages which could not be delivered despite
+    /// all resends.
+    failures: u64,
+    latencies: Vec<Duration>,
+}
+
+#[derive(Default)]
+struct Report {
+    sent: u64,
+    expected: u64,
+    received: u64,
+    invalid: u64,
+    failures: u64,
+    latencies: Vec<Duration>,
+}
+
+impl Report {
+    fn add(&mut self, peers: usize, stats: ClientStats) {
+        self.sent += stats.sent;
+        self.expected += stats.sent * peers as u64;
+        self.received += stats.received;
+        self.invalid += stats.invalid;
+        self.failures += stats.failures;
+        self.latencies.extend(stats.latencies);
+    }
+
+    fn print(mut self) {
+        println!("Sent messages: {}", self.sent);
+        println!("Expected deliveries: {}", self.expected);
+        println!("Received deliveries: {}", self.received);
+        if self.expected > 0 {
+            let loss = 1. - (self.received as f64 / self.expected as f64);
+            println!("Loss: {:.2} %", 100. * loss.max(0.));
+        }
+        println!("Invalid messages: {}", self.invalid);
+        println!("Failed reliable deliveries: {}", self.failures);
+
+        if self.latencies.is_empty() {
+            return;
+        }
+        self.latencies.sort();
+        println!("Relay latency:");
+        println!("  min: {:?}", self.latencies[0]);
+        for percentile in [50, 90, 99] {
+            let index = (self.latencies.len() - 1) * percentile / 100;
+            println!("  p{percentile}: {:?}", self.latencies[index]);
+        }
+        println!("  max: {:?}", self.latencies[self.latencies.len() - 1]);
+    }
+}
@@ -1,9 +1,15 @@
-use std::path::PathBuf;
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
 use clap::{Args, Parser, Subcommand};
 
 mod bounds;
+mod loadtest;
 mod map;
+mod navmesh;
+mod pack;
+mod preview;
+mod solids;
+mod validate;
 
 #[derive(Parser)]
 #[clap(author, version, about)]
@@ -18,6 +24,22 @@ enum Command {
     Bounds(Bounds),
     /// Computes and outputs hash of a Digital Extinction map.
     MapHash(MapHash),
+    /// Thoroughly validates a Digital Extinction map, prints a JSON report of
+    /// all found problems and exits with a non-zero code if the map is
+    /// invalid (or has any problem in strict mode).
+    MapValidate(MapValidate),
+    /// Triangulates accessible area of a Digital Extinction map and outputs
+    /// the resulting navigation mesh.
+    Navmesh(Navmesh),
+    /// Renders a top-down overview of the terrain of a Digital Extinction
+    /// map, i.e. the minimap background, and stores it as a PPM image.
+    MapPreview(MapPreview),
+    /// Validates and packs a directory with maps, object definitions, models
+    /// and icons into a single distributable bundle.
+    Pack(Pack),
+    /// Simulates many game clients exchanging messages via a DE Connector
+    /// and reports message loss and relay latency.
+    NetLoadtest(NetLoadtest),
 }
 
 #[derive(Args)]
@@ -39,11 +61,160 @@ struct MapHash {
     check: bool,
 }
 
+#[derive(Args)]
+struct MapValidate {
+    #[clap(
+        short,
+        long,
+        value_parser,
+        help = "Path of a Digital Extinction map file."
+    )]
+    path: PathBuf,
+    #[clap(
+        short,
+        long,
+        value_parser,
+        default_value = "assets",
+        help = "Path of the game assets directory."
+    )]
+    assets: PathBuf,
+    #[clap(
+        short,
+        long,
+        help = "Treat warnings, e.g. unreachable starting positions, as errors."
+    )]
+    strict: bool,
+}
+
+#[derive(Args)]
+struct Navmesh {
+    #[clap(
+        short,
+        long,
+        value_parser,
+        help = "Path of a Digital Extinction map file."
+    )]
+    path: PathBuf,
+    #[clap(
+        short,
+        long,
+        value_parser,
+        default_value = "assets",
+        help = "Path of the game assets directory."
+    )]
+    assets: PathBuf,
+    #[clap(
+        short,
+        long,
+        value_enum,
+        default_value_t = navmesh::Format::Svg,
+        help = "Output format."
+    )]
+    format: navmesh::Format,
+}
+
+#[derive(Args)]
+struct MapPreview {
+    #[clap(
+        short,
+        long,
+        value_parser,
+        help = "Path of a Digital Extinction map file."
+    )]
+    path: PathBuf,
+    #[clap(
+        short,
+        long,
+        value_parser,
+        default_value = "assets",
+        help = "Path of the game assets directory."
+    )]
+    assets: PathBuf,
+    #[clap(short, long, value_parser, help = "Path of the output PPM file.")]
+    output: PathBuf,
+}
+
+#[derive(Args)]
+struct Pack {
+    #[clap(
+        short,
+        long,
+        value_parser,
+        help = "Path of the directory to be packed."
+    )]
+    dir: PathBuf,
+    #[clap(short, long, help = "Human readable name of the bundle.")]
+    name: String,
+    #[clap(short, long, value_parser, help = "Path of the output bundle file.")]
+    output: PathBuf,
+}
+
+#[derive(Args)]
+struct NetLoadtest {
+    #[clap(
+        short,
+        long,
+        default_value = "127.0.0.1:8082",
+        help = "Address of the connector main server."
+    )]
+    server: SocketAddr,
+    #[clap(
+        short,
+        long,
+        default_value_t = 8,
+        help = "Number of simulated clients."
+    )]
+    clients: usize,
+    #[clap(
+        short,
+        long,
+        default_value_t = 4,
+        help = "Maximum number of clients in a single game."
+    )]
+    game_size: u8,
+    #[clap(
+        short,
+        long,
+        default_value_t = 10.,
+        help = "Number of messages sent by each client per second."
+    )]
+    rate: f64,
+    #[clap(short, long, default_value_t = 30, help = "Test duration in seconds.")]
+    duration: u64,
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = loadtest::LoadReliability::Unreliable,
+        help = "Reliability of sent messages."
+    )]
+    reliability: loadtest::LoadReliability,
+}
+
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
         Command::Bounds(args) => bounds::execute(args.path.as_path()),
         Command::MapHash(args) => map::execute(args.path.as_path(), args.check),
+        Command::MapValidate(args) => {
+            validate::execute(args.path.as_path(), args.assets.as_path(), args.strict)
+        }
+        Command::Navmesh(args) => {
+            navmesh::execute(args.path.as_path(), args.assets.as_path(), args.format)
+        }
+        Command::MapPreview(args) => preview::execute(
+            args.path.as_path(),
+            args.assets.as_path(),
+            args.output.as_path(),
+        ),
+        Command::NetLoadtest(args) => loadtest::execute(loadtest::LoadTestConfig {
+            server: args.server,
+            clients: args.clients,
+            game_size: args.game_size,
+            rate: args.rate,
+            duration: Duration::from_secs(args.duration),
+            reliability: args.reliability.into(),
+        }),
+        Command::Pack(args) => pack::execute(args.dir.as_path(), args.name, args.output.as_path()),
     }
 }
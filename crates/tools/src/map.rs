@@ -1,14 +1,20 @@
-use std::path::Path;
+use std::{path::Path, process};
 
 use async_std::task;
-use de_map::{hash::MapHash, io::load_map};
+use de_map::{hash::MapHash, io::load_map_unvalidated};
 
 pub fn execute(path: &Path, check: bool) {
-    let map = match task::block_on(load_map(path)) {
+    let map = match task::block_on(load_map_unvalidated(path)) {
         Ok(map) => map,
         Err(error) => panic!("Map loading failed: {error:?}"),
     };
 
+    let report = map.diagnose();
+    if !report.is_valid() {
+        eprintln!("Invalid map:\n{report}");
+        process::exit(1);
+    }
+
     let hash = map.compute_hash();
 
     if check {
@@ -0,0 +1,146 @@
+use std::{fmt::Write, path::Path};
+
+use async_std::task;
+use clap::ValueEnum;
+use de_map::{content::InnerObject, io::load_map, map::Map};
+use de_objects::SolidObject;
+use de_pathing::{create_finder, ExclusionArea, PathFinder};
+use de_types::objects::{ActiveObjectType, ObjectType};
+use serde::Serialize;
+
+use crate::solids::load_solids;
+
+/// Stroke width of navigation mesh triangles in map units.
+const STROKE_WIDTH: f32 = 0.2;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// An SVG image with accessible area triangles and inaccessible areas.
+    Svg,
+    /// A JSON document with triangles and exclusion area polygons.
+    Json,
+}
+
+#[derive(Serialize)]
+struct NavMesh {
+    min: [f32; 2],
+    max: [f32; 2],
+    triangles: Vec<[[f32; 2]; 3]>,
+    exclusions: Vec<Vec<[f32; 2]>>,
+}
+
+impl NavMesh {
+    fn new(map: &Map, finder: &PathFinder) -> Self {
+        let bounds = map.metadata().bounds();
+        Self {
+            min: bounds.min().to_array(),
+            max: bounds.max().to_array(),
+            triangles: finder
+                .triangles()
+                .map(|triangle| {
+                    [
+                        [triangle.a.x, triangle.a.y],
+                        [triangle.b.x, triangle.b.y],
+                        [triangle.c.x, triangle.c.y],
+                    ]
+                })
+                .collect(),
+            exclusions: finder
+                .exclusions()
+                .map(|exclusion| {
+                    exclusion
+                        .points()
+                        .iter()
+                        .map(|point| [point.x, point.y])
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    fn to_svg(&self) -> String {
+        let width = self.max[0] - self.min[0];
+        let height = self.max[1] - self.min[1];
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {width} {height}">"#,
+            self.min[0], -self.max[1]
+        )
+        .unwrap();
+        // Map Y axis points up while SVG Y axis points down.
+        writeln!(svg, r#"<g transform="scale(1, -1)">"#).unwrap();
+        writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{width}" height="{height}" fill="lightgray"/>"#,
+            self.min[0], self.min[1]
+        )
+        .unwrap();
+
+        for triangle in self.triangles.iter() {
+            write_polygon(&mut svg, triangle, "palegreen", "darkgreen");
+        }
+        for exclusion in self.exclusions.iter() {
+            write_polygon(&mut svg, exclusion, "salmon", "darkred");
+        }
+
+        writeln!(svg, "</g>").unwrap();
+        writeln!(svg, "</svg>").unwrap();
+        svg
+    }
+}
+
+fn write_polygon(svg: &mut String, points: &[[f32; 2]], fill: &str, stroke: &str) {
+    let points: Vec<String> = points.iter().map(|[x, y]| format!("{x},{y}")).collect();
+    writeln!(
+        svg,
+        r#"<polygon points="{}" fill="{fill}" stroke="{stroke}" stroke-width="{STROKE_WIDTH}"/>"#,
+        points.join(" ")
+    )
+    .unwrap();
+}
+
+pub fn execute(path: &Path, assets: &Path, format: Format) {
+    let map = match task::block_on(load_map(path)) {
+        Ok(map) => map,
+        Err(error) => panic!("Map loading failed: {error:?}"),
+    };
+
+    let solids = load_solids(assets);
+    let finder = create_finder(
+        map.metadata().bounds(),
+        exclusions(&map, |object_type| &solids[&object_type]),
+    );
+    let navmesh = NavMesh::new(&map, &finder);
+
+    match format {
+        Format::Svg => print!("{}", navmesh.to_svg()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&navmesh).unwrap()),
+    }
+}
+
+/// Returns exclusion areas of all static objects on the map.
+fn exclusions<'a, F>(map: &Map, solid: F) -> Vec<ExclusionArea>
+where
+    F: Fn(ObjectType) -> &'a SolidObject,
+{
+    map.content()
+        .objects()
+        .iter()
+        .filter_map(|object| {
+            let object_type = match object.inner() {
+                InnerObject::Active(object) => match object.object_type() {
+                    ActiveObjectType::Unit(_) => return None,
+                    object_type => ObjectType::Active(object_type),
+                },
+                InnerObject::Inactive(object) => ObjectType::Inactive(object.object_type()),
+            };
+
+            Some(ExclusionArea::from_ichnography(
+                &object.placement().to_transform(),
+                solid(object_type).ichnography(),
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use async_std::{path::PathBuf as AsyncPathBuf, task};
+use de_loader::bundle::{store_bundle, BUNDLE_DIRECTORIES};
+use de_map::io::{load_map, MAP_FILE_SUFFIX};
+use de_objects::{SolidObject, OBJECT_EXTENSION};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+pub fn execute(dir: &Path, name: String, output: &Path) {
+    let mut paths = Vec::new();
+    if let Err(error) = collect_files(dir, &mut paths) {
+        panic!("Failed to list {}: {error:?}", dir.display());
+    }
+    paths.sort();
+
+    let mut files = Vec::with_capacity(paths.len());
+    let mut problems = Vec::new();
+
+    for path in paths {
+        let relative = path.strip_prefix(dir).unwrap().to_path_buf();
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(error) => panic!("Failed to read {}: {error:?}", path.display()),
+        };
+
+        if let Err(problem) = validate_file(&path, &relative, &data) {
+            problems.push(format!("{}: {problem}", relative.display()));
+        }
+        files.push((AsyncPathBuf::from(relative), data));
+    }
+
+    if !problems.is_empty() {
+        eprintln!("Invalid bundle:\n{}", problems.join("\n"));
+        process::exit(1);
+    }
+
+    match task::block_on(store_bundle(name, &files, output)) {
+        Ok(manifest) => println!("{}", manifest.hash()),
+        Err(error) => panic!("Bundle storing failed: {error:?}"),
+    }
+}
+
+/// Recursively collects paths of all files in a directory.
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a file to be bundled is located in a proper directory and
+/// that it can be loaded by the game.
+///
+/// # Arguments
+///
+/// * `path` - full path of the file.
+///
+/// * `relative` - path of the file relative to the bundle root.
+///
+/// * `data` - content of the file.
+fn validate_file(path: &Path, relative: &Path, data: &[u8]) -> Result<(), String> {
+    let file_name = relative
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| String::from("non-UTF-8 file name"))?;
+
+    match relative
+        .components()
+        .next()
+        .and_then(|c| c.as_os_str().to_str())
+    {
+        Some("maps") => {
+            if !file_name.ends_with(MAP_FILE_SUFFIX) {
+                return Err(format!("map files must end with {MAP_FILE_SUFFIX}"));
+            }
+            task::block_on(load_map(path))
+                .map(|_| ())
+                .map_err(|error| format!("invalid map: {error}"))
+        }
+        Some("objects") => {
+            if !file_name.ends_with(&format!(".{}", OBJECT_EXTENSION[0])) {
+                return Err(format!(
+                    "object files must end with .{}",
+                    OBJECT_EXTENSION[0]
+                ));
+            }
+            SolidObject::from_json(data)
+                .map(|_| ())
+                .map_err(|error| format!("invalid object: {error:#}"))
+        }
+        Some("models") => {
+            if !file_name.ends_with(".glb") {
+                return Err(String::from("model files must end with .glb"));
+            }
+            gltf::Gltf::from_slice(data)
+                .map(|_| ())
+                .map_err(|error| format!("invalid model: {error}"))
+        }
+        Some("icons") => {
+            if !file_name.ends_with(".png") || !data.starts_with(&PNG_SIGNATURE) {
+                return Err(String::from("icons must be PNG images"));
+            }
+            Ok(())
+        }
+        _ => Err(format!(
+            "files must be placed in one of these directories: {}",
+            BUNDLE_DIRECTORIES.join(", ")
+        )),
+    }
+}
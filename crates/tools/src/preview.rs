@@ -0,0 +1,54 @@
+use std::{fs, path::Path};
+
+use async_std::task;
+use de_map::{content::InnerObject, io::load_map, map::Map};
+use de_objects::SolidObject;
+use de_pathing::{ExclusionArea, TerrainOverview};
+use de_types::objects::ObjectType;
+
+use crate::solids::load_solids;
+
+pub fn execute(path: &Path, assets: &Path, output: &Path) {
+    let map = match task::block_on(load_map(path)) {
+        Ok(map) => map,
+        Err(error) => panic!("Map loading failed: {error:?}"),
+    };
+
+    let solids = load_solids(assets);
+    let overview = TerrainOverview::new(
+        map.metadata().bounds(),
+        exclusions(&map, |object_type| &solids[&object_type]).as_slice(),
+    );
+
+    if let Err(error) = fs::write(output, to_ppm(&overview)) {
+        panic!("Failed to write {}: {error:?}", output.display());
+    }
+}
+
+/// Returns exclusion areas of all inactive objects on the map.
+fn exclusions<'a, F>(map: &Map, solid: F) -> Vec<ExclusionArea>
+where
+    F: Fn(ObjectType) -> &'a SolidObject,
+{
+    map.content()
+        .objects()
+        .iter()
+        .filter_map(|object| match object.inner() {
+            InnerObject::Active(_) => None,
+            InnerObject::Inactive(inner) => Some(ExclusionArea::from_ichnography(
+                &object.placement().to_transform(),
+                solid(ObjectType::Inactive(inner.object_type())).ichnography(),
+            )),
+        })
+        .collect()
+}
+
+/// Encodes the overview as a binary PPM image.
+fn to_ppm(overview: &TerrainOverview) -> Vec<u8> {
+    let size = overview.size();
+    let mut ppm = format!("P6\n{} {}\n255\n", size.x, size.y).into_bytes();
+    for pixel in overview.data().chunks_exact(4) {
+        ppm.extend_from_slice(&pixel[..3]);
+    }
+    ppm
+}
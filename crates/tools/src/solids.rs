@@ -0,0 +1,28 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use de_objects::{FileStem, SolidObject, OBJECT_EXTENSION};
+use de_types::objects::ObjectType;
+
+/// Loads definitions of all objects from the assets directory.
+pub fn load_solids(assets: &Path) -> HashMap<ObjectType, SolidObject> {
+    enum_iterator::all::<ObjectType>()
+        .map(|object_type| {
+            let path = assets.join("objects").join(format!(
+                "{}.{}",
+                object_type.stem(),
+                OBJECT_EXTENSION[0]
+            ));
+
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(error) => panic!("Failed to read {}: {error:?}", path.display()),
+            };
+            let solid = match SolidObject::from_json(&bytes) {
+                Ok(solid) => solid,
+                Err(error) => panic!("Invalid object {}: {error:?}", path.display()),
+            };
+
+            (object_type, solid)
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use std::{path::Path, process};
+
+use async_std::task;
+use de_loader::diagnose_map;
+use de_map::{diagnostics::MapReport, io::load_map_unvalidated};
+use serde::Serialize;
+
+use crate::solids::load_solids;
+
+#[derive(Serialize)]
+struct Output {
+    map: String,
+    valid: bool,
+    #[serde(flatten)]
+    report: MapReport,
+}
+
+/// # Arguments
+///
+/// * `strict` - if true, the map is considered invalid if it has any problem,
+///   including warnings.
+pub fn execute(path: &Path, assets: &Path, strict: bool) {
+    let map = match task::block_on(load_map_unvalidated(path)) {
+        Ok(map) => map,
+        Err(error) => panic!("Map loading failed: {error:?}"),
+    };
+
+    let solids = load_solids(assets);
+    let report = diagnose_map(&map, |object_type| solids[&object_type].ichnography());
+
+    let output = Output {
+        map: path.display().to_string(),
+        valid: report.is_valid() && (!strict || report.is_empty()),
+        report,
+    };
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+
+    if !output.valid {
+        process::exit(1);
+    }
+}
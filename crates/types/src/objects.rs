@@ -1,6 +1,3 @@
-#![allow(clippy::modulo_one)] // Caused by derive(Enum) on an enum with only a
-                              // single variant.
-
 use std::fmt;
 
 use bincode::{Decode, Encode};
@@ -33,12 +30,14 @@ impl fmt::Display for ObjectType {
 )]
 pub enum InactiveObjectType {
     Tree,
+    Ore,
 }
 
 impl fmt::Display for InactiveObjectType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Tree => write!(f, "Tree"),
+            Self::Ore => write!(f, "Ore"),
         }
     }
 }
@@ -66,6 +65,9 @@ impl fmt::Display for ActiveObjectType {
 pub enum BuildingType {
     Base,
     PowerHub,
+    Bunker,
+    ShieldDome,
+    Radar,
 }
 
 impl fmt::Display for BuildingType {
@@ -73,6 +75,9 @@ impl fmt::Display for BuildingType {
         match self {
             Self::Base => write!(f, "Base"),
             Self::PowerHub => write!(f, "Power Hub"),
+            Self::Bunker => write!(f, "Bunker"),
+            Self::ShieldDome => write!(f, "Shield Dome"),
+            Self::Radar => write!(f, "Radar"),
         }
     }
 }
@@ -82,12 +87,20 @@ impl fmt::Display for BuildingType {
 )]
 pub enum UnitType {
     Attacker,
+    Artillery,
+    Scout,
+    Harvester,
+    Transport,
 }
 
 impl fmt::Display for UnitType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Attacker => write!(f, "Attacker"),
+            Self::Artillery => write!(f, "Artillery"),
+            Self::Scout => write!(f, "Scout"),
+            Self::Harvester => write!(f, "Harvester"),
+            Self::Transport => write!(f, "Transport"),
         }
     }
 }
@@ -3,6 +3,7 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 pub type Unit = i32;
@@ -16,7 +17,7 @@ pub enum QuantityValueError {
 /// A quantity with associated units.
 ///
 /// The units are either base SI units and several extensions (for example m,
-/// s, px) or derived units (for example rad, m/s⁻²). Only unit powers up to
+/// s, px, hp) or derived units (for example rad, m/s⁻²). Only unit powers up to
 /// +/-7 are supported: id est m² or m⁻² are supported but m⁸ is not.
 #[derive(Debug, Clone, Copy)]
 pub struct Quantity<const U: Unit>(pub(crate) f32);
@@ -85,6 +86,20 @@ impl<const U: Unit> TryFrom<f32> for Quantity<U> {
     }
 }
 
+/// Quantities are (de)serialized as plain numbers in their units.
+impl<const U: Unit> Serialize for Quantity<U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(self.0)
+    }
+}
+
+impl<'de, const U: Unit> Deserialize<'de> for Quantity<U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = f32::deserialize(deserializer)?;
+        Self::try_from(value).map_err(D::Error::custom)
+    }
+}
+
 impl<const U: Unit> Eq for Quantity<U> {}
 
 impl<const U: Unit> PartialOrd for Quantity<U> {
@@ -243,6 +258,15 @@ mod tests {
         assert_eq!(b.err().unwrap(), QuantityValueError::NaN);
     }
 
+    #[test]
+    fn test_serde() {
+        let a: Quantity<42> = Quantity::new(2.5);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, "2.5");
+        let b: Quantity<42> = serde_json::from_str(&json).unwrap();
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn test_ord() {
         let a: Quantity<42> = Quantity::new(5.5);
@@ -1,4 +1,4 @@
-use std::ops::Mul;
+use std::ops::{Div, Mul};
 
 use crate::quantity::{Quantity, Unit};
 
@@ -11,6 +11,12 @@ const KELVIN: Unit = 1 << 12;
 const MOLE: Unit = 1 << 15;
 const CANDELA: Unit = 1 << 18;
 const PIXEL: Unit = 1 << 21;
+const HIT_POINT: Unit = 1 << 24;
+const METRE_PER_SECOND: Unit = METRE - SECOND;
+const METRE_PER_SECOND_SQUARED: Unit = METRE - 2 * SECOND;
+const NEWTON: Unit = KILOGRAM + METRE_PER_SECOND_SQUARED;
+const JOULE: Unit = NEWTON + METRE;
+const WATT: Unit = JOULE - SECOND;
 
 // Base Units
 pub type Second = Quantity<SECOND>;
@@ -21,12 +27,20 @@ pub type Kelvin = Quantity<KELVIN>;
 pub type Mole = Quantity<MOLE>;
 pub type Candela = Quantity<CANDELA>;
 pub type Pixel = Quantity<PIXEL>;
+/// Unit of object health and of damage dealt to objects.
+pub type HitPoint = Quantity<HIT_POINT>;
 
 // Derived units
 pub type InverseSecond = Quantity<{ -SECOND }>;
 pub type LogicalPixel = Quantity<PIXEL>;
 pub type InverseLogicalPixel = Quantity<{ -PIXEL }>;
 pub type Radian = Quantity<DIMENSIONLESS>;
+pub type RadianPerSecond = InverseSecond;
+pub type MetrePerSecond = Quantity<METRE_PER_SECOND>;
+pub type MetrePerSecondSquared = Quantity<METRE_PER_SECOND_SQUARED>;
+pub type Newton = Quantity<NEWTON>;
+pub type Joule = Quantity<JOULE>;
+pub type Watt = Quantity<WATT>;
 
 macro_rules! impl_mul_inverse {
     ($units:expr) => {
@@ -48,10 +62,63 @@ macro_rules! impl_mul_inverse {
     };
 }
 
+/// Implements `a * b = c`, `b * a = c`, `c / a = b` and `c / b = a` where
+/// `c` has units of `a` and `b` combined.
+macro_rules! impl_mul_div {
+    ($a:expr, $b:expr) => {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl Mul<Quantity<$b>> for Quantity<$a> {
+            type Output = Quantity<{ $a + $b }>;
+
+            fn mul(self, rhs: Quantity<$b>) -> Self::Output {
+                Quantity::new(self.0 * rhs.0)
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl Mul<Quantity<$a>> for Quantity<$b> {
+            type Output = Quantity<{ $a + $b }>;
+
+            fn mul(self, rhs: Quantity<$a>) -> Self::Output {
+                Quantity::new(self.0 * rhs.0)
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl Div<Quantity<$a>> for Quantity<{ $a + $b }> {
+            type Output = Quantity<$b>;
+
+            fn div(self, rhs: Quantity<$a>) -> Self::Output {
+                Quantity::new(self.0 / rhs.0)
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl Div<Quantity<$b>> for Quantity<{ $a + $b }> {
+            type Output = Quantity<$a>;
+
+            fn div(self, rhs: Quantity<$b>) -> Self::Output {
+                Quantity::new(self.0 / rhs.0)
+            }
+        }
+    };
+}
+
 // Due to combinatorial explosion, only needed multiplications are implemented.
 impl_mul_inverse!(SECOND);
 impl_mul_inverse!(PIXEL);
 
+// distance = speed * time
+impl_mul_div!(METRE_PER_SECOND, SECOND);
+// speed = acceleration * time
+impl_mul_div!(METRE_PER_SECOND_SQUARED, SECOND);
+// force = mass * acceleration
+impl_mul_div!(KILOGRAM, METRE_PER_SECOND_SQUARED);
+// energy = force * distance
+impl_mul_div!(NEWTON, METRE);
+// energy = power * time
+impl_mul_div!(WATT, SECOND);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +129,31 @@ mod tests {
         let b = InverseSecond::try_from(20.).unwrap();
         assert_eq!(a * b, 400.);
     }
+
+    #[test]
+    fn test_kinematics() {
+        let speed = MetrePerSecond::new(4.);
+        let time = Second::new(2.5);
+        let distance: Metre = speed * time;
+        assert_eq!(distance, Metre::new(10.));
+        assert_eq!(time * speed, distance);
+        assert_eq!(distance / time, speed);
+        assert_eq!(distance / speed, time);
+
+        let acceleration = MetrePerSecondSquared::new(2.);
+        assert_eq!(acceleration * time, MetrePerSecond::new(5.));
+        assert_eq!(speed / acceleration, Second::new(2.));
+    }
+
+    #[test]
+    fn test_energy() {
+        let force: Newton = Kilogram::new(2.) * MetrePerSecondSquared::new(3.);
+        assert_eq!(force, Newton::new(6.));
+        let work: Joule = force * Metre::new(5.);
+        assert_eq!(work, Joule::new(30.));
+
+        let power: Watt = work / Second::new(10.);
+        assert_eq!(power, Watt::new(3.));
+        assert_eq!(work / power, Second::new(10.));
+    }
 }
@@ -15,12 +15,13 @@ use de_camera::CameraPluginGroup;
 use de_combat::CombatPluginGroup;
 use de_conf::ConfigPluginGroup;
 use de_construction::ConstructionPluginGroup;
-use de_controller::ControllerPluginGroup;
+use de_controller::{ControllerPluginGroup, EditorPluginGroup};
 use de_core::{state::AppState, transition::DeStateTransition, CorePluginGroup};
+use de_economy::EconomyPluginGroup;
 use de_energy::EnergyPluginGroup;
 use de_gui::GuiPluginGroup;
 use de_index::IndexPluginGroup;
-use de_loader::LoaderPluginGroup;
+use de_loader::{bundle::BundlePlugin, LoaderPluginGroup};
 use de_lobby_client::LobbyClientPluginGroup;
 use de_log::LogPluginGroup;
 use de_menu::MenuPluginGroup;
@@ -51,6 +52,8 @@ fn main() {
         let _enter = span.enter();
 
         app.insert_resource(Msaa::Sample4)
+            // Asset sources must be registered before the asset plugin.
+            .add_plugins(BundlePlugin)
             .add_plugins(
                 DefaultPlugins
                     .set(WindowPlugin {
@@ -88,10 +91,12 @@ fn main() {
             .add_plugins(SpawnerPluginGroup)
             .add_plugins(MovementPluginGroup)
             .add_plugins(ControllerPluginGroup)
+            .add_plugins(EditorPluginGroup)
             .add_plugins(CameraPluginGroup)
             .add_plugins(BehaviourPluginGroup)
             .add_plugins(CombatPluginGroup)
             .add_plugins(ConstructionPluginGroup)
+            .add_plugins(EconomyPluginGroup)
             .add_plugins(AudioPluginGroup)
             .add_plugins(MultiplayerPluginGroup);
     }